@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+use std::path::Path;
 use std::thread;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
@@ -6,47 +8,482 @@ use qr_explore::meta_properties as mp;
 use qr_explore::{
     amos::{self, InvokeResult, OperationMetaData, ResultMetaData},
     amos_generation::GeneratedOperation,
+    amos_relations::RelationHint,
     behaviours,
-    exploration_settings::StateMutationSettings,
+    cache::ClientCache,
+    chaos,
+    config::{self, Config, SpecificationConfig},
+    contract::Contract,
+    exploration_settings::{
+        AuthRole, AuthSettings, BehaviourSettings, BoundarySettings, ChaosSettings,
+        CorrelationSettings, CredentialSet, DeprecatedOperations, HealthSettings,
+        InjectionSettings, ModelSettings, OperationWeights, ParameterCombinations, PayloadLimits,
+        PayloadStress, RepeatedSequence, ResponseTimeSla, RunTagSettings, ShrinkSettings,
+        ShrinkStrategy, StateMutationSettings, StringGeneration, SutSettings,
+    },
     explore,
+    health::HealthCheck,
+    invocation_log::InvocationSummary,
+    manifest::RunManifest,
+    model::Model,
+    queue::{QueueConfiguration, QueueProtocol},
+    role_matrix::RoleMatrix,
+    secrets::{self, SecretStore},
+    state_observer::{ObservationMode, StateObservation, StateObserver},
+    sut_manager::SutManager,
+    value_provider::ValueProvider,
 };
 use qr_http_resource::http;
 use qr_http_resource::http::HTTPMethod;
 use qr_objective_manager as obj_mgr;
 use qr_open_api::open_api;
-use qr_report::report;
+use qr_report::{
+    baseline::{read_baseline, write_baseline, Baseline},
+    comparison::{self, Finding},
+    export, report,
+    summary::{write_summary, RunSummary},
+};
 use qr_specification_manager as spec_mgr;
 use reqwest::Url;
 use tracing::info;
 
 #[derive(Debug, Args)]
 struct SUTArgs {
-    /// Port number of the SUT
-    #[arg(short, long, default_value_t = 80, value_parser = clap::value_parser!(u16).range(1..))]
-    port: u16,
+    /// Port number of the SUT [default: 80]
+    #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..))]
+    port: Option<u16>,
+
+    /// Hostname of the SUT [default: http://localhost], 'coap://' for a CoAP
+    ///  server, 'amqp://' for a queue, the path of which prefixes the topics
+    #[arg(short('H'), long, value_parser = valid_hostname)]
+    hostname: Option<Url>,
+
+    /// Path the operations are relative to, e.g. '/api/v2', instead of the
+    ///  base path of the specification. '/' sends them to the root
+    #[arg(long)]
+    base_path: Option<String>,
+
+    /// Send '//' in the paths of the requests as '/'
+    #[arg(long)]
+    collapse_slashes: bool,
+
+    /// If the paths of the requests end with a slash [default: keep]
+    #[arg(long)]
+    trailing_slash: Option<TrailingSlashArg>,
+
+    /// Lowercase the paths of the specification, the values keep their case
+    #[arg(long)]
+    lowercase_paths: bool,
+
+    /// A parameter sent with a constant value, e.g. 'tenantId=42', repeat for
+    ///  several parameters
+    #[arg(long("pin"), value_parser = pinned_parameter)]
+    pinned: Vec<(String, String)>,
+
+    /// A header sent with every request, e.g. 'X-Feature: beta', repeat for
+    ///  several headers
+    #[arg(long("header"), value_parser = static_header)]
+    headers: Vec<(String, String)>,
+
+    /// A parameter computed when a request is sent, e.g. 'timestamp=now()'.
+    ///  The providers are now(), uuid(), counter() and hmac(<secret>, body)
+    #[arg(long("provide"), value_parser = provided_parameter)]
+    provided: Vec<(String, String)>,
+
+    /// Header the correlation id of each sequence is sent in, e.g. 'X-Request-Id'
+    #[arg(long)]
+    correlation_header: Option<String>,
+
+    /// Header a key of each POST is sent in, e.g. 'Idempotency-Key'. The
+    ///  idempotent retry behaviour sends its keys in it
+    #[arg(long)]
+    idempotency_header: Option<String>,
+
+    /// Path of the health endpoint of the SUT, e.g. '/health'. The invocations
+    ///  are paused while it responds with a server error
+    #[arg(long)]
+    health_path: Option<String>,
+
+    /// Path the SUT responds its version at, e.g. '/version'. The version is
+    ///  recorded in the run manifest
+    #[arg(long)]
+    version_path: Option<String>,
+
+    /// Git commit of the SUT build, recorded in the run manifest
+    #[arg(long)]
+    git_commit: Option<String>,
+
+    /// Name of the environment of the SUT, e.g. 'staging', recorded in the run
+    ///  manifest
+    #[arg(long)]
+    environment: Option<String>,
+}
+
+impl SUTArgs {
+    /// The hostname and port of the SUT, from the flags or else the target of
+    ///  the config
+    fn resolve(&self, config: &Config) -> (Url, u16) {
+        let target = config.target().expect("Validated when the config was read");
+
+        let hostname = match (&self.hostname, &target) {
+            (Some(hostname), _) => hostname.clone(),
+            (None, Some((protocol, host, _))) => {
+                Url::parse(&format!("{}{}", protocol, host)).expect("Validated target URL")
+            }
+            (None, None) => Url::parse("http://localhost").unwrap(),
+        };
+        let default_port = match hostname.scheme() {
+            "coap" => qr_explore::coap::DEFAULT_PORT,
+            "amqp" => QueueProtocol::AMQP.default_port(),
+            "kafka" => QueueProtocol::Kafka.default_port(),
+            _ => 80,
+        };
+        let port = self
+            .port
+            .or(target.map(|(_, _, port)| port))
+            .unwrap_or(default_port);
+
+        (hostname, port)
+    }
+
+    /// The base path of the flag, or else that of the config
+    fn base_path(&self, config: &Config) -> Option<String> {
+        self.base_path.clone().or(config.target.base_path.clone())
+    }
+
+    /// The normalization of the flags, or else that of the config
+    fn paths(&self, config: &Config) -> http::PathNormalization {
+        let paths = &config.target.paths;
+        http::PathNormalization {
+            collapse_slashes: self.collapse_slashes || paths.collapse_slashes,
+            trailing_slash: match self.trailing_slash {
+                Some(TrailingSlashArg::Keep) => http::TrailingSlash::Keep,
+                Some(TrailingSlashArg::Add) => http::TrailingSlash::Add,
+                Some(TrailingSlashArg::Remove) => http::TrailingSlash::Remove,
+                None => paths.trailing_slash,
+            },
+            lowercase: self.lowercase_paths || paths.lowercase,
+        }
+    }
 
-    /// Hostname of the SUT
-    #[arg(short('H'), long, default_value_t= Url::parse("http://localhost").unwrap(), value_parser = valid_hostname)]
-    hostname: Url,
+    /// The pinning of the config, with the values of the flags for all
+    ///  operations
+    fn pinning(&self, config: &Config) -> http::RequestPinning {
+        let mut pinning = config.target.pinning.clone();
+        pinning.parameters.extend(self.pinned.iter().cloned());
+        pinning.headers.extend(self.headers.iter().cloned());
+        pinning
+    }
+
+    /// The providers of the config, with the parameters of the flags for all
+    ///  operations
+    fn providers(&self, config: &Config) -> http::ValueProviders {
+        let mut providers = config.target.providers.clone();
+        providers.parameters.extend(self.provided.iter().cloned());
+        providers
+    }
+
+    /// The correlation and idempotency headers of the flags, or else those of
+    ///  the config
+    fn correlation(&self, config: &Config) -> CorrelationSettings {
+        CorrelationSettings {
+            header: self
+                .correlation_header
+                .clone()
+                .or(config.target.correlation_header.clone()),
+            idempotency_header: self
+                .idempotency_header
+                .clone()
+                .or(config.target.idempotency_header.clone()),
+        }
+    }
+
+    /// The health endpoint of the flag, or else that of the config
+    fn health(&self, config: &Config) -> HealthSettings {
+        HealthSettings {
+            path: self
+                .health_path
+                .clone()
+                .or(config.target.health.path.clone()),
+            ..config.target.health.clone()
+        }
+    }
+
+    /// The tags of the flags, or else those of the config
+    fn tags(&self, config: &Config) -> RunTagSettings {
+        RunTagSettings {
+            version_path: self
+                .version_path
+                .clone()
+                .or(config.tags.version_path.clone()),
+            git_commit: self.git_commit.clone().or(config.tags.git_commit.clone()),
+            environment: self.environment.clone().or(config.tags.environment.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum TrailingSlashArg {
+    /// As the specification and the values make the path
+    Keep,
+    Add,
+    Remove,
 }
 
 #[derive(Debug, Args)]
-#[group(required = true, multiple = false)]
+#[group(multiple = false)]
 struct OASArgs {
     /// URL of the OpenAPI specification
     #[arg(short, long, value_parser = valid_hostname)]
     url: Option<Url>,
 
-    /// File path to OpenAPI specification
+    /// File path to OpenAPI specification, repeat to merge the specifications
+    ///  of several services
     #[arg(short, long)]
-    file: Option<String>,
+    file: Vec<String>,
+
+    /// Discover the API by crawling the SUT, when there is no OpenAPI specification
+    #[arg(long)]
+    discover: bool,
+
+    /// File path to a Postman collection, imported when there is no OpenAPI specification
+    #[arg(long)]
+    postman: Option<String>,
+
+    /// File path to the CoRE resources of a CoAP server, as served at
+    ///  '/.well-known/core'
+    #[arg(long)]
+    link_format: Option<String>,
+}
+
+impl OASArgs {
+    /// The specification of the config, if none is given by the flags
+    fn or_config(self, config: &SpecificationConfig) -> OASArgs {
+        if !self.file.is_empty()
+            || self.url.is_some()
+            || self.discover
+            || self.postman.is_some()
+            || self.link_format.is_some()
+        {
+            return self;
+        }
+
+        let url = config.url.as_deref().map(|url| match valid_hostname(url) {
+            Ok(url) => url,
+            Err(e) => {
+                println!("{}: {}", "Invalid specification URL in config".red(), e);
+                std::process::exit(EXIT_ERROR)
+            }
+        });
+
+        OASArgs {
+            url,
+            file: config.files.clone(),
+            discover: config.discover,
+            postman: config.postman.clone(),
+            link_format: config.link_format.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct SLAArgs {
+    /// Max response time in milliseconds of the operations, checked by the response time behaviour
+    #[arg(long, default_value_t = ResponseTimeSla::DEFAULT_THRESHOLD_MS)]
+    max_response_time: u64,
+
+    /// Max response time of an operation, e.g. 'getPersons=500', repeat for several operations
+    #[arg(long("response-time"), value_parser = operation_threshold)]
+    response_times: Vec<(String, u64)>,
+}
+
+impl SLAArgs {
+    fn sla(&self) -> ResponseTimeSla {
+        ResponseTimeSla {
+            global_ms: self.max_response_time,
+            operations_ms: self.response_times.iter().cloned().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct PayloadArgs {
+    /// Max size in bytes of the response payloads, checked by the payload behaviour
+    #[arg(long, default_value_t = PayloadLimits::DEFAULT_MAX_SIZE)]
+    max_payload_size: usize,
+
+    /// Payloads of at least this size in bytes must be compressed
+    #[arg(long, default_value_t = PayloadLimits::DEFAULT_COMPRESS_FROM)]
+    compress_from: usize,
+
+    /// Allow payloads of any size to be uncompressed
+    #[arg(long)]
+    allow_uncompressed: bool,
+
+    /// Generate very large strings and arrays for the body parameters, a 413
+    ///  is then accepted but a 500 or a timeout fails
+    #[arg(long)]
+    stress_payloads: bool,
+
+    /// Max length of the large strings of the bodies
+    #[arg(long, default_value_t = PayloadStress::DEFAULT_MAX_STRING_LENGTH)]
+    max_string_length: usize,
+
+    /// Max length of the large arrays of the bodies
+    #[arg(long, default_value_t = PayloadStress::DEFAULT_MAX_ARRAY_LENGTH)]
+    max_array_length: usize,
+}
+
+impl PayloadArgs {
+    fn limits(&self) -> PayloadLimits {
+        PayloadLimits {
+            max_size: self.max_payload_size,
+            compress_from: (!self.allow_uncompressed).then_some(self.compress_from),
+            stress: self.stress_payloads.then_some(PayloadStress {
+                max_string_length: self.max_string_length,
+                max_array_length: self.max_array_length,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum ShrinkOrder {
+    /// Remove operations first, then simplify the parameter values
+    LengthFirst,
+    /// Simplify the parameter values first, then remove operations
+    ValueFirst,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Deprecated {
+    /// Generate them as any other operation
+    Include,
+    /// Leave them out of the sequences
+    Exclude,
+    /// Only target them
+    Only,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Strings {
+    /// Lowercase ASCII letters
+    Ascii,
+    /// Emoji, CJK, right-to-left and combining characters and null bytes
+    Unicode,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Combinations {
+    /// Every combination of the values
+    All,
+    /// Every pair of values of two parameters in at least one combination
+    Pairwise,
 }
 
+#[derive(Debug, Args)]
+struct ShrinkArgs {
+    /// Max iterations shrinking a failing sequence, the simplest sequence so
+    ///  far is reported when reached [default: unbounded]
+    #[arg(long)]
+    max_shrink_iterations: Option<u32>,
+
+    /// Max seconds shrinking a failing sequence [default: unbounded]
+    #[arg(long)]
+    max_shrink_time: Option<u64>,
+
+    /// The order a failing sequence is shrunk in [default: length-first]
+    #[arg(long)]
+    shrink_strategy: Option<ShrinkOrder>,
+
+    /// Invoke the sequences shrinking already invoked again, instead of
+    ///  reusing their checks, e.g. for a SUT whose state changes its responses
+    #[arg(long, conflicts_with = "shrink_reset")]
+    reinvoke_repeated: bool,
+
+    /// Shell command resetting the SUT before shrinking invokes a sequence
+    ///  again, e.g. './reset-db.sh'
+    #[arg(long)]
+    shrink_reset: Option<String>,
+}
+
+impl ShrinkArgs {
+    /// The budget and order of the flags, or else those of the config
+    fn settings(&self, generation: &config::GenerationConfig) -> ShrinkSettings {
+        let strategy = match self.shrink_strategy {
+            Some(ShrinkOrder::LengthFirst) => ShrinkStrategy::LengthFirst,
+            Some(ShrinkOrder::ValueFirst) => ShrinkStrategy::ValueFirst,
+            None => generation.shrink_strategy.unwrap_or_default(),
+        };
+
+        let repeated = match &self.shrink_reset {
+            Some(command) => RepeatedSequence::ResetFirst {
+                command: command.clone(),
+            },
+            None if self.reinvoke_repeated => RepeatedSequence::Reinvoke,
+            None => generation.repeated_sequences.clone().unwrap_or_default(),
+        };
+
+        ShrinkSettings {
+            max_iterations: self
+                .max_shrink_iterations
+                .or(generation.max_shrink_iterations),
+            max_time_secs: self.max_shrink_time.or(generation.max_shrink_time_secs),
+            strategy,
+            repeated,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct AuthArgs {
+    /// Credential header sent with the requests, e.g. 'Authorization: Bearer <token>'
+    #[arg(long, value_parser = auth_header)]
+    auth: Option<http::HTTPAuth>,
+
+    /// Credential header of a role with lesser privileges, replayed by the auth bypass behaviour
+    #[arg(long, value_parser = auth_header, requires = "auth")]
+    downgraded_auth: Option<http::HTTPAuth>,
+
+    /// A role of the role matrix, e.g. 'admin=Authorization: Bearer <token>', or
+    ///  only the name for a role without credentials, repeat for several roles
+    #[arg(long("role"), value_parser = credential_set)]
+    roles: Vec<CredentialSet>,
+}
+
+impl AuthArgs {
+    /// The credentials of the flags, or else those of the config
+    fn settings(&self, config: &Config) -> AuthSettings {
+        let config = config.auth().expect("Validated when the config was read");
+
+        AuthSettings {
+            credentials: self.auth.clone().or(config.credentials),
+            downgraded: self.downgraded_auth.clone().or(config.downgraded),
+            roles: if self.roles.is_empty() {
+                config.roles
+            } else {
+                self.roles.clone()
+            },
+        }
+    }
+}
+
+/// Findings were found, or with a baseline, findings not in it
+const EXIT_FINDINGS: i32 = 1;
+/// The run could not be made, e.g. an unreadable specification or an
+///  unreachable target
+const EXIT_ERROR: i32 = 2;
+
 #[derive(Parser)]
+#[command(after_help = "Exit codes: 0 = no findings, 1 = findings, 2 = execution error")]
 struct Cli {
     /// Use QuickREST to explore or to execute test cases
     #[command(subcommand)]
     command: Commands,
+
+    /// Config file of the run, the flags override its values [default: quickrest.toml, if it exists]
+    #[arg(long, global = true)]
+    config: Option<String>,
 }
 
 #[derive(Debug, Copy, Clone, ValueEnum)]
@@ -61,8 +498,42 @@ enum Behaviour {
     StateMutation,
     /// Find sequences of operations where the state of a GET operation has changed, but is then undone, bringing the state back to the initial state
     StateIdentity,
+    /// Find sequences where an operation responds slower than its max response time
+    ResponseTime,
+    /// Find sequences where a response payload is too large or lacks compression
+    Payload,
+    /// Find sequences where an injected attack payload crashes the SUT or is reflected, in the operations given by '--inject'
+    Injection,
+    /// Find operations that still succeed when their authorized sequences are replayed without credentials, or with the '--downgraded-auth' ones
+    AuthBypass,
+    /// Invoke the same sequences as each '--role', and print which roles each operation succeeds for
+    RoleMatrix,
+    /// Audit the response headers and error payloads of the operations for security issues
+    SecurityAudit,
+    /// Repeat a sequence many times to find operations whose response time degrades, e.g. from a leak
+    Soak,
+    /// Find sequences where the responses diverge from the model given by '--model'
+    Model,
+    /// Invoke sequences on a collection at the same time, and find histories that are not linearizable
+    Linearizability,
+    /// Invoke the same sequences on the target and on the '--reference', and find the responses that differ
+    Differential,
+    /// Find responses that do not conform to the '--contract', or capture it with '--capture-contract'
+    Contract,
+    /// Inject delays, dropped and repeated requests between the operations, and find sequences the query operation observes an inconsistent state after
+    Chaos,
+    /// Invoke each operation with the combinations of the boundary values of its parameters, a deterministic pass before the fuzzing
+    Boundary,
+    /// Cache the responses as a client honoring the cache headers, and find sequences where a GET operation serves the state from before a mutation
+    StaleCache,
+    /// Update a resource after a conflicting update with the ETag read before it, and find PUT operations that do not reject it with 412
+    ConditionalRequest,
+    /// Retry the last POST of the sequences with the same '--idempotency-header' key, and find POST operations that create a duplicate
+    IdempotentRetry,
 }
 
+// The arguments are parsed once, boxing the larger variant gains nothing
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 enum Commands {
     Explore {
@@ -72,21 +543,202 @@ enum Commands {
         #[command(flatten)]
         oas: OASArgs,
 
+        #[command(flatten)]
+        sla: SLAArgs,
+
+        #[command(flatten)]
+        payload: PayloadArgs,
+
+        #[command(flatten)]
+        auth: AuthArgs,
+
+        #[command(flatten)]
+        shrink: ShrinkArgs,
+
         /// The name of the behaviour to explore
         #[arg(short, long)]
         behaviour: Vec<Behaviour>,
 
-        /// Min number of operations per behaviour seq
-        #[arg(long("min"), default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=5))]
-        min_seq_size: u8,
+        /// Min number of operations per behaviour seq [default: 1]
+        #[arg(long("min"), value_parser = clap::value_parser!(u8).range(1..=5))]
+        min_seq_size: Option<u8>,
+
+        /// Max number of operations per behaviour seq [default: 1]
+        #[arg(long("max"), value_parser = clap::value_parser!(u8).range(1..=5))]
+        max_seq_size: Option<u8>,
+
+        /// The max number of tests per behaviour [default: 100]
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..=1000))]
+        tests: Option<u16>,
+
+        /// Bias the generation towards operations not yet covered in the run
+        #[arg(long)]
+        coverage_guided: bool,
+
+        /// Also fuzz GETs of the hypermedia links found in the responses
+        #[arg(long)]
+        follow_links: bool,
+
+        /// Sample the parameter values from the values seen in the responses
+        #[arg(long)]
+        harvest_responses: bool,
+
+        /// Order the operations after the orders that succeeded earlier in the run
+        #[arg(long)]
+        learn_precedence: bool,
+
+        /// Also mutate the sequences generated earlier in the run, e.g. swap
+        ///  adjacent operations or drop the setup before them
+        #[arg(long)]
+        mutate_sequences: bool,
+
+        /// Honor the cache headers of the responses within each sequence, e.g.
+        ///  revalidate the cached responses with their ETag
+        #[arg(long)]
+        honor_cache_headers: bool,
+
+        /// Take a 409 Conflict to the update with a stale ETag as a rejection,
+        ///  as the 412 Precondition Failed
+        #[arg(long)]
+        accept_conflict: bool,
+
+        /// Directory of the corpus of the campaign. The sequences that produce
+        ///  new statuses are saved to it, and replayed first in later runs
+        #[arg(long)]
+        corpus: Option<String>,
+
+        /// File the values of every invoked sequence are recorded to, one
+        ///  sequence per line, to replay them as they were sent
+        #[arg(long)]
+        script: Option<String>,
+
+        /// What is generated of the operations the specification marks as
+        ///  deprecated [default: include]
+        #[arg(long)]
+        deprecated: Option<Deprecated>,
+
+        /// The strings generated for the string parameters without a
+        ///  generator of their own [default: ascii]
+        #[arg(long)]
+        strings: Option<Strings>,
+
+        /// Observe all query operations before and after the same mutations,
+        ///  instead of one at a time, in state mutation
+        #[arg(long)]
+        observe_all_queries: bool,
+
+        /// File path to a JSON object of operation names and their generation weight
+        #[arg(short, long)]
+        weights: Option<String>,
+
+        /// File path to the model the model behaviour checks the SUT against
+        #[arg(long)]
+        model: Option<String>,
+
+        /// URL of the target the differential behaviour compares the responses
+        ///  with, e.g. the version being replaced
+        #[arg(long, value_parser = reference_url)]
+        reference: Option<String>,
+
+        /// A field the differential behaviour ignores in the responses, e.g. a
+        ///  timestamp, repeat for several fields
+        #[arg(long("ignore-field"))]
+        ignore_fields: Vec<String>,
+
+        /// File path to the contract the contract behaviour checks the
+        ///  responses against
+        #[arg(long)]
+        contract: Option<String>,
+
+        /// Capture the responses of the target to the '--contract' instead of
+        ///  checking them, run against a version known to be good
+        #[arg(long, requires = "contract")]
+        capture_contract: bool,
+
+        /// The chance the chaos behaviour delays a request, by up to
+        ///  '--chaos-max-delay-ms'
+        #[arg(long, default_value_t = 0.0)]
+        chaos_delay: f64,
 
-        /// Max number of operations per behaviour seq
-        #[arg(long("max"), default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=5))]
-        max_seq_size: u8,
+        #[arg(long, default_value_t = 500)]
+        chaos_max_delay_ms: u64,
 
-        /// The max number of tests per behaviour
-        #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u16).range(1..=1000))]
-        tests: u16,
+        /// The chance the chaos behaviour drops the rest of the requests of a
+        ///  sequence, the query operation is still invoked
+        #[arg(long, default_value_t = 0.0)]
+        chaos_drop: f64,
+
+        /// The chance the chaos behaviour sends a request twice
+        #[arg(long, default_value_t = 0.0)]
+        chaos_repeat: f64,
+
+        /// The length of the longest string of the boundary behaviour
+        #[arg(long, default_value_t = BoundarySettings::DEFAULT_MAX_STRING_LENGTH)]
+        boundary_max_length: usize,
+
+        /// The most combinations of boundary values invoked of an operation
+        #[arg(long, default_value_t = BoundarySettings::DEFAULT_MAX_COMBINATIONS)]
+        boundary_max_combinations: usize,
+
+        /// Which combinations of the boundary values are invoked
+        #[arg(long, value_enum, default_value_t = Combinations::All)]
+        boundary_combinations: Combinations,
+
+        /// Answer the requests with the mock of the config instead of the SUT
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Shell command printing the SUT state as JSON, checked by the state
+        ///  behaviours instead of the query operation results
+        #[arg(long)]
+        observe: Option<String>,
+
+        /// Check the observed state alongside the query operation results
+        #[arg(long, requires = "observe")]
+        observe_alongside: bool,
+
+        /// Record client traffic through a proxy at the address, e.g.
+        ///  127.0.0.1:8888, and learn from it before exploring
+        #[arg(long)]
+        record: Option<String>,
+
+        /// Declare where a parameter value comes from, e.g.
+        ///  'orders.postOrder:customerId <- customers.postCustomer:$.id'
+        #[arg(long("relation"))]
+        relations: Vec<RelationHint>,
+
+        /// Inject attack payloads into the string parameters of the operation,
+        ///  explored by the injection behaviour, repeat for several operations
+        #[arg(long("inject"))]
+        inject: Vec<String>,
+
+        /// Also export the invocations, their statistics and the results as
+        ///  CSV files to the directory
+        #[arg(long)]
+        export_csv: Option<String>,
+
+        /// Write the AMOS, with what was recorded and learned, as an OpenAPI
+        ///  document to the file
+        #[arg(long)]
+        export_open_api: Option<String>,
+
+        /// Seed of the generation, recorded in the run manifest to repeat a
+        ///  run. A random seed is drawn if not given
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// File of known findings, the run only fails on findings not in it
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Accept the findings of the run, writing them to the '--baseline'
+        #[arg(long, requires = "baseline")]
+        update_baseline: bool,
+
+        /// Write the counts of findings per behaviour and operation, as JSON,
+        ///  to the file
+        #[arg(long)]
+        summary_json: Option<String>,
     },
     Test {
         #[command(flatten)]
@@ -95,7 +747,70 @@ enum Commands {
         /// File path to examples to test
         #[arg(short, long)]
         file: Vec<String>,
+
+        /// File path to the model the model examples are checked against
+        #[arg(long)]
+        model: Option<String>,
+
+        /// File path to the contract the contract examples are checked against
+        #[arg(long)]
+        contract: Option<String>,
+
+        #[command(flatten)]
+        sla: SLAArgs,
+
+        #[command(flatten)]
+        payload: PayloadArgs,
+
+        #[command(flatten)]
+        auth: AuthArgs,
+
+        /// Write the counts of failing examples per behaviour and operation,
+        ///  as JSON, to the file
+        #[arg(long)]
+        summary_json: Option<String>,
     },
+    Compare {
+        /// Report file, or directory of report files, of the baseline run
+        #[arg(long)]
+        baseline: String,
+
+        /// Report file, or directory of report files, of the run to compare
+        #[arg(long)]
+        current: String,
+    },
+    /// Lint the OpenAPI specification, e.g. the operations without an
+    ///  operationId or the definitions no operation takes or responds
+    Lint {
+        /// File path to the OpenAPI specification
+        #[arg(short, long)]
+        file: String,
+
+        /// Write the lint report, as JSON, to the file
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Manage the secrets the credentials of the config reference as
+    ///  '${secret:<name>}', encrypted with the passphrase in
+    ///  QUICKREST_SECRETS_PASSPHRASE
+    Secrets {
+        /// File path to the secrets store
+        #[arg(long, default_value = secrets::DEFAULT_SECRETS_FILE)]
+        file: String,
+
+        #[command(subcommand)]
+        action: SecretsAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SecretsAction {
+    /// Store the secret of the name, its value is read from stdin
+    Set { name: String },
+    /// Remove the secret of the name
+    Remove { name: String },
+    /// List the names of the secrets
+    List,
 }
 
 struct AppState {
@@ -106,6 +821,8 @@ struct AppState {
     current_root_operation: String,
     start_time: Option<std::time::Instant>,
     end_time: Option<std::time::Instant>,
+    manifest: Option<RunManifest>,
+    aborted: Option<String>,
 }
 
 impl AppState {
@@ -118,6 +835,8 @@ impl AppState {
             current_root_operation: "".to_string(),
             start_time: None,
             end_time: None,
+            manifest: None,
+            aborted: None,
         }
     }
 }
@@ -138,6 +857,107 @@ fn valid_hostname(s: &str) -> Result<Url, String> {
     }
 }
 
+fn reference_url(s: &str) -> Result<String, String> {
+    qr_explore::config::parse_target_url(s)?;
+
+    Ok(s.to_string())
+}
+
+fn operation_threshold(s: &str) -> Result<(String, u64), String> {
+    let (operation, ms) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("Expected <OPERATION>=<MS>, got '{s}'"))?;
+    let ms = ms.trim().parse::<u64>().map_err(|e| e.to_string())?;
+
+    Ok((operation.trim().to_string(), ms))
+}
+
+fn auth_header(s: &str) -> Result<http::HTTPAuth, String> {
+    config::parse_auth_header(s)
+}
+
+fn pinned_parameter(s: &str) -> Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Expected <NAME>=<VALUE>, got '{s}'"))?;
+
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn provided_parameter(s: &str) -> Result<(String, String), String> {
+    let (name, expression) = pinned_parameter(s)?;
+    ValueProvider::parse(&expression)?;
+
+    Ok((name, expression))
+}
+
+fn static_header(s: &str) -> Result<(String, String), String> {
+    config::parse_auth_header(s).map(|header| (header.header, header.value))
+}
+
+/// The config of the path, or of the default file if there is one
+fn read_config(path: Option<&str>) -> Config {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(config::DEFAULT_CONFIG_FILE).exists() => config::DEFAULT_CONFIG_FILE,
+        None => return Config::default(),
+    };
+
+    info!("Config file : {}", path);
+    match Config::read(Path::new(path)) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}: {}", path.red(), e);
+            std::process::exit(EXIT_ERROR)
+        }
+    }
+}
+
+fn credential_set(s: &str) -> Result<CredentialSet, String> {
+    match s.split_once('=') {
+        Some((name, header)) => Ok(CredentialSet::new(name.trim(), Some(auth_header(header)?))),
+        None => Ok(CredentialSet::new(s.trim(), None)),
+    }
+}
+
+/// Open the store with the passphrase of the environment, change it and
+///  save it
+fn manage_secrets(file: &str, action: &SecretsAction) {
+    let fail = |e: secrets::SecretError| -> ! {
+        println!("{}: {}", file.red(), e);
+        std::process::exit(EXIT_ERROR)
+    };
+
+    let mut store = secrets::passphrase()
+        .and_then(|passphrase| SecretStore::open(Path::new(file), &passphrase))
+        .unwrap_or_else(|e| fail(e));
+
+    match action {
+        SecretsAction::Set { name } => {
+            // Not given as an argument, to keep it out of the shell history
+            let mut value = String::new();
+            if let Err(e) = std::io::stdin().read_line(&mut value) {
+                fail(e.into())
+            }
+            store.set(name, value.trim_end_matches(['\r', '\n']));
+            store.save().unwrap_or_else(|e| fail(e));
+            println!("Stored secret '{}'", name);
+        }
+        SecretsAction::Remove { name } => {
+            if !store.remove(name) {
+                fail(secrets::SecretError::UnknownSecret(name.clone()))
+            }
+            store.save().unwrap_or_else(|e| fail(e));
+            println!("Removed secret '{}'", name);
+        }
+        SecretsAction::List => {
+            for name in store.names() {
+                println!("{}", name);
+            }
+        }
+    }
+}
+
 fn main() {
     // install global collector configured based on RUST_LOG env var.
     tracing_subscriber::fmt()
@@ -147,40 +967,75 @@ fn main() {
     //tracing_subscriber::fmt::init();
 
     let cli = Cli::parse();
+    // Managed before the config is read, its credentials may reference
+    //  secrets not yet stored
+    if let Commands::Secrets { file, action } = &cli.command {
+        manage_secrets(file, action);
+        return;
+    }
+    let config = read_config(cli.config.as_deref());
 
     match cli.command {
-        Commands::Test { sut, file } => {
+        Commands::Test {
+            sut,
+            file,
+            model,
+            contract,
+            sla,
+            payload,
+            auth,
+            summary_json,
+        } => {
+            reset_run_summary(summary_json.as_deref());
+            let (hostname, port) = sut.resolve(&config);
+            let auth = auth.settings(&config);
+            let base_path = sut.base_path(&config);
+            let paths = sut.paths(&config);
+            let pinning = sut.pinning(&config);
+            let providers = sut.providers(&config);
+            let correlation = sut.correlation(&config);
+            let health = sut.health(&config);
+            let model =
+                model.map(|path| Model::parse(&read_model(&path).source).expect("Validated model"));
+            let contract = contract.map(|path| read_contract(&path));
+
             println!("Running QuickREST in Test-mode!");
-            println!("SUT Port: {}", sut.port);
-            println!("SUT Hostname: {}", sut.hostname);
+            println!("SUT Port: {}", port);
+            println!("SUT Hostname: {}", hostname);
             println!("Test files: {:?}", file);
 
             println!("Starting invocations..");
             let mut app_state = AppState::new();
+            let mut failing = BTreeSet::new();
+            let mut replayed = vec![];
 
             for test_file in file {
                 app_state.start_time = Some(std::time::Instant::now());
                 match report::read_results_for_test(&test_file) {
                     Ok(report) => {
-                        let scheme = match sut.hostname.scheme() {
+                        let scheme = match hostname.scheme() {
                             "http" => http::Protocol::HTTP,
                             "https" => http::Protocol::HTTPS,
                             _ => {
                                 println!(
                                     "{}: {}",
                                     "Unsupported SUT scheme: ".red(),
-                                    sut.hostname.scheme()
+                                    hostname.scheme()
                                 );
-                                std::process::exit(1)
+                                std::process::exit(EXIT_ERROR)
                             }
                         };
 
                         let target = explore::Target::HTTP {
                             config: http::HTTPConfiguration::new(
-                                sut.hostname.host().unwrap().to_string(),
-                                sut.port,
+                                hostname.host().unwrap().to_string(),
+                                port,
                                 scheme,
-                            ),
+                            )
+                            .with_base_path(base_path.clone())
+                            .with_paths(paths.clone())
+                            .with_pinning(pinning.clone())
+                            .with_providers(providers.clone()),
                         };
 
                         // TODO: pull from options
@@ -190,20 +1045,46 @@ fn main() {
 
                         let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
+                        // An auth bypass example is replayed with the credentials it bypassed with
+                        let replay_role = match report.behaviour {
+                            behaviours::Behaviour::AuthBypass if auth.downgraded.is_some() => {
+                                AuthRole::Downgraded
+                            }
+                            behaviours::Behaviour::AuthBypass => AuthRole::Stripped,
+                            _ => AuthRole::Configured,
+                        };
+
                         let ctx = explore::ExplorationContext {
-                            http_client: reqwest::blocking::Client::new(),
-                            http_send_fn: explore::invoke_with_reqwest,
-                            target,
-                            query_operation: None,
-                            tx: Some(exploration_log_tx),
+                            observer: Some(Box::new(exploration_log_tx)),
                             number_of_tests,
                             // TODO: Adapt to the different properties
                             min_length: min_seq_length,
                             max_length: max_seq_length,
+                            auth: auth.clone(),
+                            auth_role: std::cell::Cell::new(replay_role),
+                            health: HealthCheck::new(&health),
+                            correlation: explore::CorrelationIds::new(
+                                correlation.header.clone(),
+                                explore::random_seed() as u32,
+                            )
+                            .with_idempotency_header(correlation.idempotency_header.clone()),
+                            behaviour_settings: match report.behaviour {
+                                behaviours::Behaviour::IdempotentRetry => {
+                                    BehaviourSettings::IdempotentRetry {
+                                        header: correlation.idempotency_key_header().to_string(),
+                                    }
+                                }
+                                _ => BehaviourSettings::new(&report.behaviour),
+                            },
+                            // A stale cache example is replayed through the cache
+                            cache: (report.behaviour == behaviours::Behaviour::StaleCache)
+                                .then(|| std::cell::RefCell::new(ClientCache::new())),
+                            ..explore::ExplorationContext::new(target)
                         };
 
                         let amos = report.amos.clone();
                         let sequences = report.sequences.clone();
+                        let first_span = app_state.invocation_spans.len();
                         let thread_handle = thread::spawn(move || {
                             for seq in sequences {
                                 // TODO: this do not feel like the best way of keeping track
@@ -226,13 +1107,16 @@ fn main() {
 
                         process_exploration_events(&mut app_state, exploration_log_rx);
                         thread_handle.join().expect("Invocation thread panicked");
+                        exit_if_aborted(&app_state, None);
 
                         info!(
                             "Test finished with {} invocations",
                             app_state.invocation_results.len()
                         );
                         info!("Checking results:");
-                        for idx in 0..app_state.invocation_spans.len() {
+                        replayed.push(report.behaviour.name());
+                        // The spans of earlier files were checked with their own behaviour
+                        for idx in first_span..app_state.invocation_spans.len() {
                             println!();
                             info!("Sequence: {}", idx + 1);
                             let current_span = &app_state.invocation_spans[idx];
@@ -246,10 +1130,12 @@ fn main() {
                                 .map(|result| {
                                     let meta = if let Some(meta) = &result.meta_data {
                                         match meta {
-                                            ResultMetaData::HTTP { url, status } => {
+                                            ResultMetaData::HTTP { url, status, .. } => {
                                                 format!(" ({url}) - {status}")
                                             }
                                         }
+                                    } else if let Some(error) = result.transport_error {
+                                        format!(" - no response, {error}")
                                     } else {
                                         "".to_string()
                                     };
@@ -260,9 +1146,75 @@ fn main() {
 
                             info!("{}", span_text);
 
+                            // Without every response the check can neither pass nor fail
+                            if span_results.iter().any(InvokeResult::is_transport_error) {
+                                info!("Inconclusive, a request got no response");
+                                continue;
+                            }
+
                             // Check the result based on which behaviour it was reported for
                             let check_result = match report.behaviour {
-                                behaviours::Behaviour::Property => mp::check_response(span_results),
+                                // A boundary example is a single operation,
+                                //  checked as a fuzz example
+                                behaviours::Behaviour::Property
+                                | behaviours::Behaviour::Boundary => {
+                                    mp::check_response(span_results)
+                                }
+                                // A single replay has no trend, a soak example
+                                //  is checked against the max response times
+                                behaviours::Behaviour::ResponseTime
+                                | behaviours::Behaviour::Soak => {
+                                    mp::check_response_time(&sla.sla(), span_results)
+                                }
+                                behaviours::Behaviour::Payload => {
+                                    mp::check_payload(&payload.limits(), span_results)
+                                }
+                                behaviours::Behaviour::Injection => {
+                                    mp::check_injection(span_results)
+                                }
+                                behaviours::Behaviour::StaleCache => {
+                                    mp::check_stale_cache(span_results)
+                                }
+                                // The replay does not know if a 409 was accepted
+                                behaviours::Behaviour::ConditionalRequest => {
+                                    mp::check_conditional_request(false, span_results)
+                                }
+                                behaviours::Behaviour::IdempotentRetry => {
+                                    mp::check_idempotent_retry(span_results)
+                                }
+                                // A single replay has no roles to compare, no
+                                //  concurrency and no reference, these examples
+                                //  are checked as fuzz examples
+                                behaviours::Behaviour::RoleMatrix
+                                | behaviours::Behaviour::Linearizability
+                                | behaviours::Behaviour::Differential => {
+                                    mp::check_response(span_results)
+                                }
+                                behaviours::Behaviour::AuthBypass => mp::check_auth_bypass(
+                                    &current_span.query_operation,
+                                    span_results,
+                                ),
+                                behaviours::Behaviour::SecurityAudit => {
+                                    mp::check_security_headers(span_results)
+                                }
+                                // Without a model a model example is checked
+                                //  as a fuzz example
+                                behaviours::Behaviour::Model => match &model {
+                                    Some(model) => model.check(span_results).is_none(),
+                                    None => mp::check_response(span_results),
+                                },
+                                // And without a contract, so is a contract example
+                                behaviours::Behaviour::Contract => match &contract {
+                                    Some(contract) => contract.check(span_results).is_empty(),
+                                    None => mp::check_response(span_results),
+                                },
+                                // A replay injects no faults, the sequence is
+                                //  checked to be consistent without them
+                                behaviours::Behaviour::Chaos => chaos::inconsistency(
+                                    &current_span.query_operation,
+                                    span_results,
+                                )
+                                .is_none(),
                                 behaviours::Behaviour::ResponseEquality => {
                                     mp::check_response_equality(span_results)
                                 }
@@ -292,6 +1244,16 @@ fn main() {
                             };
 
                             info!("Failing check: {}", !check_result);
+                            if !check_result {
+                                failing.insert(Finding {
+                                    behaviour: report.behaviour.name().to_string(),
+                                    operation: current_span.query_operation.clone(),
+                                    sequence: span_results
+                                        .iter()
+                                        .map(|result| result.operation.name.clone())
+                                        .collect(),
+                                });
+                            }
                         }
                         app_state.end_time = Some(std::time::Instant::now());
 
@@ -302,35 +1264,264 @@ fn main() {
                     }
                     Err(e) => {
                         println!("Could not read file: {}", e);
-                        std::process::exit(1);
+                        std::process::exit(EXIT_ERROR);
+                    }
+                }
+            }
+
+            let exit_code = if failing.is_empty() { 0 } else { EXIT_FINDINGS };
+            if let Some(path) = summary_json {
+                write_run_summary(
+                    &path,
+                    &RunSummary::new(exit_code, &replayed, &failing, None),
+                );
+            }
+
+            std::process::exit(exit_code)
+        }
+        Commands::Secrets { .. } => unreachable!("Managed before the config is read"),
+        Commands::Compare { baseline, current } => {
+            let read = |path: &str| match comparison::read_reports(path) {
+                Ok(reports) => reports,
+                Err(e) => {
+                    println!(
+                        "{}: {}",
+                        format!("Failed to read reports {}", path).red(),
+                        e
+                    );
+                    std::process::exit(EXIT_ERROR)
+                }
+            };
+
+            let comparison = comparison::Comparison::new(&read(&baseline), &read(&current));
+
+            println!("Fixed findings: {}", comparison.fixed.len());
+            for finding in &comparison.fixed {
+                println!("  {}", finding.to_string().green());
+            }
+            println!("New findings: {}", comparison.new.len());
+            for finding in &comparison.new {
+                println!("  {}", finding.to_string().red());
+            }
+            println!("Persisting findings: {}", comparison.persisting.len());
+            for finding in &comparison.persisting {
+                println!("  {}", finding.to_string().yellow());
+            }
+
+            // Fail the build of a SUT that regressed
+            if comparison.has_regressions() {
+                std::process::exit(EXIT_FINDINGS)
+            }
+        }
+        Commands::Lint { file, output } => {
+            let (parse_result, translation_result) =
+                match spec_mgr::manager::load_open_api_file_path(&file) {
+                    Ok(loaded) => loaded,
+                    Err(e) => {
+                        println!("{}: {}", format!("Failed to read {}", file).red(), e);
+                        std::process::exit(EXIT_ERROR)
                     }
+                };
+
+            let report = spec_mgr::lint::lint(&parse_result, &translation_result.amos);
+            for finding in &report.findings {
+                match &finding.operation {
+                    Some(operation) => println!(
+                        "  {} {}: {}",
+                        finding.kind.presentation().yellow(),
+                        operation,
+                        finding.message
+                    ),
+                    None => println!(
+                        "  {}: {}",
+                        finding.kind.presentation().yellow(),
+                        finding.message
+                    ),
+                }
+            }
+            for kind in spec_mgr::lint::LintKind::ALL {
+                println!("{}: {}", kind.presentation(), report.count(kind));
+            }
+
+            if let Some(path) = output {
+                if let Err(e) = report.save(std::path::Path::new(&path)) {
+                    println!("Failed to write the lint report: {}", e);
+                    std::process::exit(EXIT_ERROR)
                 }
             }
+
+            if !report.is_clean() {
+                std::process::exit(EXIT_FINDINGS)
+            }
         }
         Commands::Explore {
             sut,
             oas,
+            sla,
+            payload,
+            auth,
+            shrink,
             behaviour,
             min_seq_size,
             max_seq_size,
             tests,
+            coverage_guided,
+            follow_links,
+            harvest_responses,
+            learn_precedence,
+            mutate_sequences,
+            honor_cache_headers,
+            accept_conflict,
+            corpus,
+            script,
+            deprecated,
+            strings,
+            observe_all_queries,
+            weights,
+            model,
+            reference,
+            ignore_fields,
+            contract,
+            capture_contract,
+            chaos_delay,
+            chaos_max_delay_ms,
+            chaos_drop,
+            chaos_repeat,
+            boundary_max_length,
+            boundary_max_combinations,
+            boundary_combinations,
+            dry_run,
+            observe,
+            observe_alongside,
+            record,
+            relations,
+            inject,
+            export_csv,
+            export_open_api,
+            seed,
+            baseline,
+            update_baseline,
+            summary_json,
         } => {
+            reset_run_summary(summary_json.as_deref());
             let mut app_state = AppState::new();
 
+            // The flags override the values of the config
+            let (hostname, port) = sut.resolve(&config);
+            let oas = oas.or_config(&config.specification);
+            let auth = auth.settings(&config);
+            let base_path = sut.base_path(&config);
+            let paths = sut.paths(&config);
+            let pinning = sut.pinning(&config);
+            let providers = sut.providers(&config);
+            let correlation = sut.correlation(&config);
+            let health = sut.health(&config);
+            let tags = sut.tags(&config);
+            let behaviour = if behaviour.is_empty() {
+                config
+                    .behaviours
+                    .iter()
+                    .map(|name| Behaviour::from_str(name, false).expect("Validated behaviour"))
+                    .collect()
+            } else {
+                behaviour
+            };
+            let generation = &config.generation;
+            let tests = tests.or(generation.tests).unwrap_or(100);
+            let min_seq_size = min_seq_size.or(generation.min_length).unwrap_or(1);
+            let max_seq_size = max_seq_size.or(generation.max_length).unwrap_or(1);
+            let coverage_guided = coverage_guided || generation.coverage_guided.unwrap_or(false);
+            let follow_links = follow_links || generation.follow_links.unwrap_or(false);
+            let harvest_responses =
+                harvest_responses || generation.harvest_responses.unwrap_or(false);
+            let learn_precedence = learn_precedence || generation.learn_precedence.unwrap_or(false);
+            let mutate_sequences = mutate_sequences || generation.mutate_sequences.unwrap_or(false);
+            let honor_cache_headers =
+                honor_cache_headers || generation.honor_cache_headers.unwrap_or(false);
+            let corpus_dir = corpus.or(generation.corpus_dir.clone());
+            let script = script.or(generation.script_file.clone());
+            let deprecated = match deprecated {
+                Some(Deprecated::Include) => DeprecatedOperations::Include,
+                Some(Deprecated::Exclude) => DeprecatedOperations::Exclude,
+                Some(Deprecated::Only) => DeprecatedOperations::Only,
+                None => generation.deprecated.unwrap_or_default(),
+            };
+            let strings = match strings {
+                Some(Strings::Ascii) => StringGeneration::Ascii,
+                Some(Strings::Unicode) => StringGeneration::Unicode,
+                None => generation.strings.unwrap_or_default(),
+            };
+            let seed = seed.or(generation.seed);
+            let shrinking = shrink.settings(generation);
+
             println!("Running QuickREST in Explore-mode!");
-            println!("SUT Port: {}", sut.port);
-            println!("SUT Hostname: {}", sut.hostname);
+            println!("SUT Port: {}", port);
+            println!("SUT Hostname: {}", hostname);
 
-            let amos_translation = if let Some(path) = oas.file {
+            // The behaviours append to the script, it only holds this run
+            if let Some(path) = &script {
+                if let Err(e) = std::fs::File::create(path) {
+                    println!("{} {}: {}", "Could not create the script".red(), path, e);
+                    std::process::exit(EXIT_ERROR);
+                }
+            }
+
+            let amos_translation = if let [path] = oas.file.as_slice() {
                 info!("Parsing OpenAPI file : {}", path);
-                handle_parse_result(spec_mgr::manager::load_open_api_file_path(&path))
+                handle_parse_result(spec_mgr::manager::load_open_api_file_path(path))
+            } else if oas.file.len() > 1 {
+                info!("Merging OpenAPI files : {:?}", oas.file);
+                handle_merge_result(
+                    spec_mgr::manager::load_open_api_file_paths(&oas.file),
+                    oas.file.len(),
+                )
             } else if let Some(url) = oas.url {
                 info!("URL of OpenAPI-specification: {}", url);
                 handle_parse_result(spec_mgr::manager::fetch_open_api_from_url(&url))
+            } else if let Some(path) = oas.postman {
+                info!("Importing Postman collection : {}", path);
+                handle_import_result(spec_mgr::postman::load_postman_file_path(&path))
+            } else if let Some(path) = oas.link_format {
+                info!("Importing CoRE resources : {}", path);
+                handle_import_result(spec_mgr::link_format::load_link_format_file_path(&path))
+            } else if oas.discover {
+                let mut base = hostname.clone();
+                if base.set_port(Some(port)).is_err() {
+                    println!("{}: {}", "Invalid SUT URL".red(), base);
+                    std::process::exit(EXIT_ERROR);
+                }
+                handle_discovery_result(spec_mgr::discovery::discover_from_url(
+                    &base,
+                    &spec_mgr::discovery::DiscoveryOptions::new(),
+                ))
             } else {
                 println!("{}", "No source of OpenAPI-specification provided".red());
-                std::process::exit(1);
+                std::process::exit(EXIT_ERROR);
+            };
+
+            let mut amos_translation = match record {
+                Some(listen) => {
+                    let mut base = hostname.clone();
+                    if base.set_port(Some(port)).is_err() {
+                        println!("{}: {}", "Invalid SUT URL".red(), base);
+                        std::process::exit(EXIT_ERROR);
+                    }
+                    record_traffic(&listen, &base, amos_translation)
+                }
+                None => amos_translation,
             };
+            amos_translation.amos.relation_hints.extend(relations);
+
+            if let Some(path) = export_open_api {
+                info!("Export OpenAPI to {}..", path);
+
+                let document = amos::to_open_api(&amos_translation.amos);
+                let contents = serde_json::to_string_pretty(&document).expect("Valid OpenAPI JSON");
+                if let Err(e) = std::fs::write(&path, contents) {
+                    println!("Failed to export OpenAPI: {}", e);
+                    std::process::exit(EXIT_ERROR)
+                }
+            }
 
             println!("Exploration settings:");
             println!("Behaviour: {:?}", behaviour);
@@ -341,42 +1532,119 @@ fn main() {
 
             // Steps
             // 1. setup context to call explore
-            let scheme = match sut.hostname.scheme() {
-                "http" => http::Protocol::HTTP,
-                "https" => http::Protocol::HTTPS,
+            let scheme = match hostname.scheme() {
+                "http" => Some(http::Protocol::HTTP),
+                "https" => Some(http::Protocol::HTTPS),
+                "coap" | "amqp" | "kafka" => None,
                 _ => {
                     println!(
                         "{}: {}",
                         "Unsupported SUT scheme: ".red(),
-                        sut.hostname.scheme()
+                        hostname.scheme()
                     );
-                    std::process::exit(1)
+                    std::process::exit(EXIT_ERROR)
                 }
             };
 
-            let target = explore::Target::HTTP {
-                config: http::HTTPConfiguration::new(
-                    sut.hostname.host().unwrap().to_string(),
-                    sut.port,
-                    scheme,
-                ),
+            let host = hostname.host().unwrap().to_string();
+            let target = match scheme {
+                Some(scheme) => explore::Target::HTTP {
+                    config: http::HTTPConfiguration::new(host, port, scheme)
+                        .with_base_path(base_path)
+                        .with_paths(paths)
+                        .with_pinning(pinning)
+                        .with_providers(providers),
+                },
+                None if hostname.scheme() == "coap" => explore::Target::CoAP {
+                    config: qr_explore::coap::CoapConfiguration::new(host, port)
+                        .with_base_path(base_path),
+                },
+                None => match QueueConfiguration::parse(hostname.as_str()) {
+                    Ok(config) => explore::Target::Queue {
+                        config: QueueConfiguration { port, ..config },
+                    },
+                    Err(e) => {
+                        println!("{}: {}", "Invalid queue: ".red(), e);
+                        std::process::exit(EXIT_ERROR)
+                    }
+                },
+            };
+
+            let is_dry_run = dry_run;
+
+            let operation_weights = if let Some(path) = weights {
+                info!("Operation weights file : {}", path);
+                read_operation_weights(&path)
+            } else {
+                generation.weights.clone()
             };
 
-            // TODO: get from args
-            let is_dry_run = false;
+            let model_settings = model
+                .map(|path| {
+                    info!("Model file : {}", path);
+                    read_model(&path)
+                })
+                .unwrap_or_default();
+
+            let state_observation = observe.map(|command| StateObservation {
+                observer: StateObserver::Command { command },
+                mode: if observe_alongside {
+                    ObservationMode::AlongsideQuery
+                } else {
+                    ObservationMode::InsteadOfQuery
+                },
+            });
 
             println!("Target: {:#?}", target);
 
             //let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
-            for b in behaviour {
-                let query_ops = match b {
+            let mut run_findings = BTreeSet::new();
+            let mut explored = vec![];
+
+            // A managed SUT is started before the first behaviour and torn
+            //  down when the run ends, a dry run has no SUT to start
+            let mut sut_manager = SutManager::new(if is_dry_run {
+                SutSettings::new()
+            } else {
+                config.sut.clone()
+            });
+            if let Err(e) = sut_manager.start(&target) {
+                println!("{}: {}", "Could not start the SUT".red(), e);
+                std::process::exit(EXIT_ERROR)
+            }
+
+            for (idx, b) in behaviour.into_iter().enumerate() {
+                if idx > 0 && sut_manager.resets_between_behaviours() {
+                    info!("Reset the SUT");
+                    if let Err(e) = sut_manager.reset(&target) {
+                        println!("{}: {}", "Could not reset the SUT".red(), e);
+                        std::process::exit(EXIT_ERROR)
+                    }
+                }
+
+                let mut query_ops = match b {
                     Behaviour::Fuzz
                     | Behaviour::ResponseEquality
-                    | Behaviour::ResponseInequality => {
+                    | Behaviour::ResponseInequality
+                    | Behaviour::ResponseTime
+                    | Behaviour::Payload
+                    | Behaviour::Injection
+                    | Behaviour::AuthBypass
+                    | Behaviour::RoleMatrix
+                    | Behaviour::SecurityAudit
+                    | Behaviour::Soak
+                    | Behaviour::Model
+                    | Behaviour::Differential
+                    | Behaviour::Contract
+                    | Behaviour::Boundary => {
                         (0..amos_translation.amos.operations.len()).collect::<Vec<usize>>()
                     }
-                    Behaviour::StateMutation | Behaviour::StateIdentity => {
+                    Behaviour::StateMutation
+                    | Behaviour::StateIdentity
+                    | Behaviour::Linearizability
+                    | Behaviour::Chaos
+                    | Behaviour::StaleCache => {
                         let mut get_ops = vec![];
 
                         for idx in 0..amos_translation.amos.operations.len() {
@@ -393,7 +1661,14 @@ fn main() {
 
                         get_ops
                     }
+                    Behaviour::ConditionalRequest => amos_translation.amos.resource_updates(),
+                    Behaviour::IdempotentRetry => amos_translation.amos.creations(),
                 };
+                query_ops.retain(|idx| {
+                    config
+                        .filters
+                        .matches(&amos_translation.amos.operations[*idx].info.name)
+                });
 
                 let explore_behaviour = match b {
                     Behaviour::Fuzz => qr_explore::behaviours::Behaviour::Property,
@@ -405,11 +1680,51 @@ fn main() {
                     }
                     Behaviour::StateMutation => qr_explore::behaviours::Behaviour::StateMutation,
                     Behaviour::StateIdentity => qr_explore::behaviours::Behaviour::StateIdentity,
+                    Behaviour::ResponseTime => qr_explore::behaviours::Behaviour::ResponseTime,
+                    Behaviour::Payload => qr_explore::behaviours::Behaviour::Payload,
+                    Behaviour::Injection => qr_explore::behaviours::Behaviour::Injection,
+                    Behaviour::AuthBypass => qr_explore::behaviours::Behaviour::AuthBypass,
+                    Behaviour::RoleMatrix => qr_explore::behaviours::Behaviour::RoleMatrix,
+                    Behaviour::SecurityAudit => qr_explore::behaviours::Behaviour::SecurityAudit,
+                    Behaviour::Soak => qr_explore::behaviours::Behaviour::Soak,
+                    Behaviour::Model => qr_explore::behaviours::Behaviour::Model,
+                    Behaviour::Linearizability => {
+                        qr_explore::behaviours::Behaviour::Linearizability
+                    }
+                    Behaviour::Differential => qr_explore::behaviours::Behaviour::Differential,
+                    Behaviour::Contract => qr_explore::behaviours::Behaviour::Contract,
+                    Behaviour::Chaos => qr_explore::behaviours::Behaviour::Chaos,
+                    Behaviour::Boundary => qr_explore::behaviours::Behaviour::Boundary,
+                    Behaviour::StaleCache => qr_explore::behaviours::Behaviour::StaleCache,
+                    Behaviour::ConditionalRequest => {
+                        qr_explore::behaviours::Behaviour::ConditionalRequest
+                    }
+                    Behaviour::IdempotentRetry => {
+                        qr_explore::behaviours::Behaviour::IdempotentRetry
+                    }
                 };
 
+                let first_result = app_state.results.len();
                 let (handle, rx) = obj_mgr::manager::explore(
                     &target,
-                    &obj_mgr::manager::Options { is_dry_run },
+                    &obj_mgr::manager::Options {
+                        is_dry_run,
+                        operation_weights: operation_weights.clone(),
+                        state_observation: state_observation.clone(),
+                        response_time: sla.sla(),
+                        payload: payload.limits(),
+                        injection: InjectionSettings {
+                            operations: inject.clone(),
+                        },
+                        auth: auth.clone(),
+                        correlation: correlation.clone(),
+                        generators: generation.overrides.clone(),
+                        model: model_settings.clone(),
+                        mock: config.mock.clone(),
+                        health: health.clone(),
+                        tags: tags.clone(),
+                        invoker: None,
+                    },
                     &amos_translation.amos,
                     &explore_behaviour,
                     &StateMutationSettings {
@@ -418,11 +1733,72 @@ fn main() {
                         max_length: max_seq_size,
                         query_operation_ids: query_ops,
                         selected_query_operation: None,
+                        coverage_guided,
+                        follow_links,
+                        harvest_responses,
+                        learn_precedence,
+                        mutate_sequences,
+                        honor_cache_headers,
+                        corpus_dir: corpus_dir.clone(),
+                        script_file: script.clone(),
+                        deprecated,
+                        strings,
+                        seed,
+                        shrinking: shrinking.clone(),
+                    },
+                    &match explore_behaviour {
+                        qr_explore::behaviours::Behaviour::StateMutation => {
+                            BehaviourSettings::StateMutation {
+                                observe_all_queries,
+                            }
+                        }
+                        qr_explore::behaviours::Behaviour::Differential => {
+                            BehaviourSettings::Differential {
+                                reference_url: reference.clone().unwrap_or_default(),
+                                ignore_fields: ignore_fields.clone(),
+                            }
+                        }
+                        qr_explore::behaviours::Behaviour::Contract => {
+                            BehaviourSettings::Contract {
+                                contract_file: contract.clone().unwrap_or_default(),
+                                capture: capture_contract,
+                            }
+                        }
+                        qr_explore::behaviours::Behaviour::Chaos => BehaviourSettings::Chaos {
+                            faults: ChaosSettings {
+                                delay_probability: chaos_delay,
+                                max_delay_ms: chaos_max_delay_ms,
+                                drop_probability: chaos_drop,
+                                repeat_probability: chaos_repeat,
+                            },
+                        },
+                        qr_explore::behaviours::Behaviour::Boundary => {
+                            BehaviourSettings::Boundary {
+                                limits: BoundarySettings {
+                                    max_string_length: boundary_max_length,
+                                    max_combinations: boundary_max_combinations,
+                                    combinations: match boundary_combinations {
+                                        Combinations::All => ParameterCombinations::All,
+                                        Combinations::Pairwise => ParameterCombinations::Pairwise,
+                                    },
+                                },
+                            }
+                        }
+                        qr_explore::behaviours::Behaviour::ConditionalRequest => {
+                            BehaviourSettings::ConditionalRequest { accept_conflict }
+                        }
+                        qr_explore::behaviours::Behaviour::IdempotentRetry => {
+                            BehaviourSettings::IdempotentRetry {
+                                header: correlation.idempotency_key_header().to_string(),
+                            }
+                        }
+                        _ => BehaviourSettings::new(&explore_behaviour),
                     },
                 );
 
                 process_exploration_events(&mut app_state, rx);
                 handle.join().expect("Exploration thread panicked");
+                exit_if_aborted(&app_state, Some(&mut sut_manager));
                 //println!("Got {} results", app_state.results.len());
 
                 info!(
@@ -440,6 +1816,12 @@ fn main() {
                 );
                 info!("{:#?}", coverage);
 
+                let matrix = RoleMatrix::from_results(&app_state.results);
+                if !matrix.is_empty() {
+                    info!("Role matrix, * marks operations the roles differ for:");
+                    println!("{}", matrix);
+                }
+
                 info!("Write results..");
 
                 match report::write_results_for_test(
@@ -447,18 +1829,151 @@ fn main() {
                     &explore_behaviour,
                     &amos_translation.amos,
                     &app_state.results,
+                    app_state.manifest.as_ref(),
                 ) {
                     Ok(_) => (),
                     Err(e) => {
                         println!("Failed to write result: {}", e);
-                        std::process::exit(1)
+                        std::process::exit(EXIT_ERROR)
                     }
                 }
+
+                explored.push(explore_behaviour.name());
+                // The results of earlier behaviours are not findings of this one
+                run_findings.extend(comparison::findings(&[report::report_for_test(
+                    &explore_behaviour,
+                    &amos_translation.amos,
+                    &app_state.results[first_result..],
+                    None,
+                )]));
             }
+
+            if let Err(e) = sut_manager.stop() {
+                println!("Failed to stop the SUT: {}", e);
+            }
+
+            if let Some(dir_path) = export_csv {
+                info!("Export CSV to {}..", dir_path);
+
+                let summaries: Vec<InvocationSummary> = app_state
+                    .invocation_results
+                    .iter()
+                    .map(|r| {
+                        // The invocations are listed by the aliases of the operations
+                        let mut summary = InvocationSummary::new(r, r.duration.unwrap_or_default());
+                        summary.operation = amos_translation
+                            .amos
+                            .display_name(&summary.operation)
+                            .to_string();
+                        summary
+                    })
+                    .collect();
+
+                if let Err(e) = export::write_csv_export(&dir_path, &summaries, &app_state.results)
+                {
+                    println!("Failed to export CSV: {}", e);
+                    std::process::exit(EXIT_ERROR)
+                }
+            }
+
+            // Checked against a baseline, only the findings not in it fail the run
+            let new_findings = match baseline {
+                Some(path) if update_baseline => {
+                    info!("Accept {} findings into {}", run_findings.len(), path);
+                    if let Err(e) = write_baseline(&path, &Baseline::new(&run_findings)) {
+                        println!("Failed to write baseline: {}", e);
+                        std::process::exit(EXIT_ERROR)
+                    }
+                    Some(vec![])
+                }
+                Some(path) => match read_baseline(&path) {
+                    Ok(known) => Some(known.new_findings(&run_findings)),
+                    Err(e) => {
+                        println!("Failed to read baseline {}: {}", path, e);
+                        std::process::exit(EXIT_ERROR)
+                    }
+                },
+                None => None,
+            };
+
+            if let Some(new_findings) = &new_findings {
+                println!("New findings: {}", new_findings.len());
+                for finding in new_findings {
+                    println!("  {}", finding.to_string().red());
+                }
+            }
+
+            let failing = new_findings
+                .as_ref()
+                .map_or(run_findings.len(), |new| new.len());
+            let exit_code = if failing > 0 { EXIT_FINDINGS } else { 0 };
+
+            if let Some(path) = summary_json {
+                write_run_summary(
+                    &path,
+                    &RunSummary::new(
+                        exit_code,
+                        &explored,
+                        &run_findings,
+                        new_findings.map(|new| new.len()),
+                    ),
+                );
+            }
+
+            std::process::exit(exit_code)
         }
     }
 }
 
+fn write_run_summary(path: &str, summary: &RunSummary) {
+    if let Err(e) = write_summary(path, summary) {
+        println!("Failed to write summary: {}", e);
+        std::process::exit(EXIT_ERROR)
+    }
+}
+
+/// The summary of a run that exits with an error, until the run completes.
+///  A script does not read the summary of an earlier run after an error exit.
+fn reset_run_summary(path: Option<&str>) {
+    if let Some(path) = path {
+        write_run_summary(
+            path,
+            &RunSummary::new(EXIT_ERROR, &[], &BTreeSet::new(), None),
+        );
+    }
+}
+
+/// The results of an aborted run are not the results of the target, neither
+///  are the results of a run that failed to explore an operation. The exit
+///  skips the teardown of the SUT at the end of the run, it is stopped first.
+fn exit_if_aborted(app_state: &AppState, sut_manager: Option<&mut SutManager>) {
+    let failed = app_state.results.iter().find_map(|result| match result {
+        explore::ExplorationResult::Failed { operation, reason } => Some((operation, reason)),
+        _ => None,
+    });
+    if app_state.aborted.is_none() && failed.is_none() {
+        return;
+    }
+
+    if let Some(sut_manager) = sut_manager {
+        if let Err(e) = sut_manager.stop() {
+            println!("Failed to stop the SUT: {}", e);
+        }
+    }
+
+    if let Some(reason) = &app_state.aborted {
+        println!("{}: {}", "Exploration aborted".red(), reason);
+    } else if let Some((operation, reason)) = failed {
+        println!(
+            "{} {}: {}",
+            "Exploration failed for".red(),
+            operation,
+            reason
+        );
+    }
+    std::process::exit(EXIT_ERROR);
+}
+
 fn process_exploration_events(
     app_state: &mut AppState,
     rx: std::sync::mpsc::Receiver<explore::Event>,
@@ -470,11 +1985,31 @@ fn process_exploration_events(
                 //println!("{:?}", message)
             }
 
-            explore::Event::Result { result } => app_state.results.push(result),
+            explore::Event::Result { result } => {
+                if let Some(truncation) = result.shrink_truncation() {
+                    println!(
+                        "{}: stopped after {} iterations at {}, the example may not be minimal",
+                        "Shrinking truncated".yellow(),
+                        truncation.iterations,
+                        truncation.limit
+                    );
+                }
+                app_state.results.push(result)
+            }
+            explore::Event::Manifest { manifest } => {
+                info!("Seed: {}", manifest.seed);
+                if !manifest.tags.is_empty() {
+                    info!("Tags: {}", manifest.tags);
+                }
+                app_state.manifest = Some(*manifest)
+            }
             explore::Event::Control { event } => {
                 match event {
                     explore::ControlEvent::Finished => {
                         info!("Finished");
+                        if let Some(manifest) = &mut app_state.manifest {
+                            manifest.finish();
+                        }
                         break;
                     }
                     // We do not want to react to started, since the UI already
@@ -482,6 +2017,7 @@ fn process_exploration_events(
                     explore::ControlEvent::Started => {
                         info!("Started")
                     }
+                    explore::ControlEvent::Aborted { reason } => app_state.aborted = Some(reason),
                 }
             }
             explore::Event::TimeLineStart { enter, .. } => {
@@ -549,6 +2085,18 @@ fn handle_parse_result(
                 spec_mgr::Error::OpenAPIFetchFailed(e) => {
                     format!("Could not fetch Open API specification: {}", e)
                 }
+                spec_mgr::Error::DiscoveryFailed(msg) => {
+                    format!("Could not discover the API: {}", msg)
+                }
+                spec_mgr::Error::CaptureFailed(msg) => {
+                    format!("Could not record traffic: {}", msg)
+                }
+                spec_mgr::Error::PostmanImportFailed(msg) => {
+                    format!("Could not import the Postman collection: {}", msg)
+                }
+                spec_mgr::Error::LinkFormatImportFailed(msg) => {
+                    format!("Could not import the CoRE resources: {}", msg)
+                }
             };
             println!(
                 "{} - {}",
@@ -556,7 +2104,188 @@ fn handle_parse_result(
                 err_str.red()
             );
 
-            std::process::exit(1);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn handle_merge_result(
+    result: spec_mgr::Result<amos::TranslationResult>,
+    specifications: usize,
+) -> amos::TranslationResult {
+    match result {
+        Ok(translation_result) => {
+            println!(
+                "{}",
+                format!(
+                    "Merged {} specifications into {} operations",
+                    specifications,
+                    translation_result.amos.operations.len()
+                )
+                .green()
+            );
+            if !translation_result.warnings.is_empty() {
+                println!("{}", "Warnings:".yellow());
+                for (n, warning) in translation_result.warnings.iter().enumerate() {
+                    println!("{} - {}", n + 1, warning.message.yellow());
+                }
+            }
+            translation_result
+        }
+        Err(e) => {
+            println!(
+                "{} - {}",
+                "Parsing Failed Fatally!".red().bold(),
+                e.to_string().red()
+            );
+
+            std::process::exit(EXIT_ERROR);
         }
     }
 }
+
+fn handle_import_result(
+    result: spec_mgr::Result<amos::TranslationResult>,
+) -> amos::TranslationResult {
+    match result {
+        Ok(translation_result) => {
+            println!(
+                "{}",
+                format!(
+                    "Imported {} operations",
+                    translation_result.amos.operations.len()
+                )
+                .green()
+            );
+            if !translation_result.warnings.is_empty() {
+                println!("{}", "Warnings:".yellow());
+                for (n, warning) in translation_result.warnings.iter().enumerate() {
+                    println!("{} - {}", n + 1, warning.message.yellow());
+                }
+            }
+            translation_result
+        }
+        Err(e) => {
+            println!(
+                "{} - {}",
+                "Import Failed Fatally!".red().bold(),
+                e.to_string().red()
+            );
+
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn handle_discovery_result(
+    result: spec_mgr::Result<amos::TranslationResult>,
+) -> amos::TranslationResult {
+    match result {
+        Ok(translation_result) => {
+            println!(
+                "{}",
+                format!(
+                    "Discovered {} operations",
+                    translation_result.amos.operations.len()
+                )
+                .green()
+            );
+            translation_result
+        }
+        Err(e) => {
+            println!(
+                "{} - {}",
+                "Discovery Failed Fatally!".red().bold(),
+                e.to_string().red()
+            );
+
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Record the traffic through the capture proxy until enter is pressed, and
+///  learn operations and parameter examples from it
+fn record_traffic(
+    listen: &str,
+    target: &Url,
+    translation: amos::TranslationResult,
+) -> amos::TranslationResult {
+    let proxy = match spec_mgr::capture::CaptureProxy::start(listen, target) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            println!(
+                "{} - {}",
+                "Recording Failed Fatally!".red().bold(),
+                e.to_string().red()
+            );
+
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+
+    println!(
+        "Recording traffic to {} through {}, press enter to stop",
+        target,
+        proxy.address()
+    );
+    let mut line = String::new();
+    let _ = std::io::stdin().read_line(&mut line);
+
+    let requests = proxy.stop();
+    let learned = spec_mgr::capture::learn_from_requests(translation.amos, &requests);
+    for warning in &learned.warnings {
+        println!("{}", warning.message.green());
+    }
+
+    amos::TranslationResult {
+        amos: learned.amos,
+        warnings: translation.warnings,
+        errors: translation.errors,
+    }
+}
+
+fn read_operation_weights(path: &str) -> OperationWeights {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            println!("{}: {}", "Could not read weights file".red(), e);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+
+    match serde_json::from_str(&content) {
+        Ok(weights) => weights,
+        Err(e) => {
+            println!("{}: {}", "Invalid weights file".red(), e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn read_contract(path: &str) -> Contract {
+    match Contract::load(std::path::Path::new(path)) {
+        Ok(contract) => contract,
+        Err(e) => {
+            println!("{}: {}", "Could not read contract file".red(), e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+fn read_model(path: &str) -> ModelSettings {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            println!("{}: {}", "Could not read model file".red(), e);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+
+    if let Err(e) = Model::parse(&source) {
+        println!("{}: {}", "Invalid model file".red(), e);
+        std::process::exit(EXIT_ERROR);
+    }
+
+    ModelSettings { source }
+}
@@ -0,0 +1,404 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::json;
+use tokio::sync::oneshot;
+
+/// The path of the specification of the demo SUT
+pub const SPECIFICATION: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/demo-sut.json");
+
+/// The oldest age a person is created with, older persons crash the SUT
+pub const MAX_AGE: i64 = 120;
+
+#[derive(Debug, Default)]
+struct State {
+    last_id: u64,
+    persons: BTreeMap<u64, serde_json::Value>,
+    /// The ids of the list of persons, deleted persons are never removed
+    listed: Vec<u64>,
+    products: BTreeSet<String>,
+}
+
+/// A small REST service of persons and products, with seeded bugs for the
+///  explorations to find:
+///  - creating a person older than `MAX_AGE` responds with a 500
+///  - a deleted person is still in the list of persons
+///  - deleting a product that does not exist responds with a 500
+///
+/// The service listens on a free local port until it is dropped. Each
+///  instance has its own state, and the ids start at 1.
+pub struct DemoSut {
+    address: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DemoSut {
+    pub fn start() -> std::io::Result<DemoSut> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let handle = std::thread::spawn(move || {
+            runtime.block_on(async move {
+                let service = make_service_fn(move |_| {
+                    let state = state.clone();
+                    async move {
+                        Ok::<_, Infallible>(service_fn(move |request| {
+                            handle(state.clone(), request)
+                        }))
+                    }
+                });
+
+                let server = match Server::from_tcp(listener) {
+                    Ok(server) => server,
+                    Err(e) => panic!("The demo SUT failed to listen: {}", e),
+                };
+                let _ = server
+                    .serve(service)
+                    .with_graceful_shutdown(async {
+                        let _ = stopped.await;
+                    })
+                    .await;
+            })
+        });
+
+        Ok(DemoSut {
+            address,
+            shutdown: Some(shutdown),
+            handle: Some(handle),
+        })
+    }
+
+    /// The URL to target, e.g. 'http://127.0.0.1:41234'
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn port(&self) -> u16 {
+        self.address.port()
+    }
+}
+
+impl Drop for DemoSut {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let body = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => form(&body),
+        Err(_) => return Ok(respond(StatusCode::BAD_REQUEST, None)),
+    };
+
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+
+    let mut state = state.lock().unwrap();
+    let response = match (&method, segments.as_slice()) {
+        (&Method::GET, ["persons"]) => {
+            let persons: Vec<serde_json::Value> = state
+                .listed
+                .iter()
+                .map(|id| {
+                    state
+                        .persons
+                        .get(id)
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "id": id }))
+                })
+                .collect();
+            respond(StatusCode::OK, Some(json!(persons)))
+        }
+        (&Method::POST, ["persons"]) => match person(&body) {
+            Some((_, age)) if age > MAX_AGE => respond(StatusCode::INTERNAL_SERVER_ERROR, None),
+            Some((name, age)) => {
+                state.last_id += 1;
+                let id = state.last_id;
+                let created = json!({ "id": id, "name": name, "age": age });
+                state.persons.insert(id, created.clone());
+                state.listed.push(id);
+                respond(StatusCode::CREATED, Some(created))
+            }
+            None => respond(StatusCode::BAD_REQUEST, None),
+        },
+        (_, ["persons", id]) => {
+            let Some(id) = id
+                .parse::<u64>()
+                .ok()
+                .filter(|id| state.persons.contains_key(id))
+            else {
+                return Ok(respond(StatusCode::NOT_FOUND, None));
+            };
+
+            match method {
+                Method::GET => respond(StatusCode::OK, state.persons.get(&id).cloned()),
+                Method::PUT => match person(&body) {
+                    Some((name, age)) => {
+                        let updated = json!({ "id": id, "name": name, "age": age });
+                        state.persons.insert(id, updated.clone());
+                        respond(StatusCode::OK, Some(updated))
+                    }
+                    None => respond(StatusCode::BAD_REQUEST, None),
+                },
+                Method::DELETE => {
+                    state.persons.remove(&id);
+                    respond(StatusCode::NO_CONTENT, None)
+                }
+                _ => respond(StatusCode::METHOD_NOT_ALLOWED, None),
+            }
+        }
+        (&Method::GET, ["products"]) => respond(StatusCode::OK, Some(json!(state.products))),
+        (_, ["products", name]) => {
+            let exists = state.products.contains(*name);
+            match (method, exists) {
+                (Method::GET, true) => respond(StatusCode::OK, Some(product(&state, name))),
+                (Method::POST, false) => {
+                    state.products.insert(name.to_string());
+                    respond(StatusCode::CREATED, Some(product(&state, name)))
+                }
+                (Method::POST, true) => respond(StatusCode::CONFLICT, None),
+                (Method::DELETE, true) => {
+                    state.products.remove(*name);
+                    respond(StatusCode::NO_CONTENT, None)
+                }
+                (Method::DELETE, false) => respond(StatusCode::INTERNAL_SERVER_ERROR, None),
+                (Method::GET, false) => respond(StatusCode::NOT_FOUND, None),
+                _ => respond(StatusCode::METHOD_NOT_ALLOWED, None),
+            }
+        }
+        _ => respond(StatusCode::NOT_FOUND, None),
+    };
+
+    Ok(response)
+}
+
+/// The id of a product is its position among the products
+fn product(state: &State, name: &str) -> serde_json::Value {
+    let id = state.products.iter().position(|p| p == name).unwrap_or(0) + 1;
+    json!({ "id": id, "name": name })
+}
+
+/// The name and age of the form data of a person
+fn person(form: &BTreeMap<String, String>) -> Option<(String, i64)> {
+    let name = form.get("name")?.clone();
+    let age = form.get("age")?.parse().ok()?;
+    Some((name, age))
+}
+
+fn respond(status: StatusCode, payload: Option<serde_json::Value>) -> Response<Body> {
+    let mut response = match payload {
+        Some(payload) => {
+            let mut response = Response::new(Body::from(payload.to_string()));
+            response.headers_mut().insert(
+                hyper::header::CONTENT_TYPE,
+                hyper::header::HeaderValue::from_static("application/json"),
+            );
+            response
+        }
+        None => Response::new(Body::empty()),
+    };
+    *response.status_mut() = status;
+    response
+}
+
+/// The pairs of an 'application/x-www-form-urlencoded' body
+fn form(body: &[u8]) -> BTreeMap<String, String> {
+    String::from_utf8_lossy(body)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                percent_decode(&name.replace('+', " ")),
+                percent_decode(&value.replace('+', " ")),
+            )
+        })
+        .collect()
+}
+
+fn percent_decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = (bytes[idx] == b'%')
+            .then(|| encoded.get(idx + 1..idx + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            None => {
+                decoded.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use quickrest::{Behaviour, ExplorationResult, Explorer, OperationWeights};
+
+    use crate as sut;
+
+    #[test]
+    fn serves_persons_and_products() {
+        let demo = sut::DemoSut::start().unwrap();
+        let client = reqwest::blocking::Client::new();
+
+        let created = client
+            .post(format!("{}/persons", demo.url()))
+            .form(&[("name", "Ada Lovelace"), ("age", "36")])
+            .send()
+            .unwrap();
+        assert_eq!(created.status(), 201);
+        assert_eq!(
+            created.text().unwrap(),
+            r#"{"age":36,"id":1,"name":"Ada Lovelace"}"#
+        );
+
+        let crashed = client
+            .post(format!("{}/persons", demo.url()))
+            .form(&[("name", "Methuselah"), ("age", "969")])
+            .send()
+            .unwrap();
+        assert_eq!(crashed.status(), 500);
+
+        let deleted = client
+            .delete(format!("{}/persons/1", demo.url()))
+            .send()
+            .unwrap();
+        assert_eq!(deleted.status(), 204);
+        let listed = client
+            .get(format!("{}/persons", demo.url()))
+            .send()
+            .unwrap();
+        assert_eq!(listed.text().unwrap(), r#"[{"id":1}]"#);
+
+        let missing = client
+            .delete(format!("{}/products/foo", demo.url()))
+            .send()
+            .unwrap();
+        assert_eq!(missing.status(), 500);
+    }
+
+    #[test]
+    fn response_check_finds_and_shrinks_the_crashes() {
+        let demo = sut::DemoSut::start().unwrap();
+
+        let outcome = Explorer::new()
+            .with_spec(sut::SPECIFICATION)
+            .with_target(&demo.url())
+            .with_behaviour(Behaviour::Property)
+            .with_tests(50)
+            .with_seed(42)
+            .run()
+            .unwrap();
+
+        let run = &outcome.runs[0];
+        assert!(run.aborted.is_none());
+        let found = |name: &str| {
+            run.results.iter().find_map(|result| match result {
+                ExplorationResult::ResponseCheck {
+                    operation,
+                    minimal_sequence,
+                    ..
+                } if operation == name => minimal_sequence.clone(),
+                _ => None,
+            })
+        };
+
+        let too_old = found("addPerson").unwrap();
+        let age = too_old[0]
+            .parameters
+            .iter()
+            .find(|p| p.name == "age")
+            .unwrap()
+            .value
+            .as_int_value();
+        assert!(age > sut::MAX_AGE);
+
+        assert!(found("deleteProductByName").is_some());
+        assert!(found("getPersons").is_none());
+        assert!(found("addProduct").is_none());
+    }
+
+    #[test]
+    fn model_finds_the_deleted_persons_in_the_list() {
+        let demo = sut::DemoSut::start().unwrap();
+        // Only the operations of the model are in the sequences
+        let mut weights = OperationWeights::new();
+        for operation in [
+            "getPerson",
+            "updatePerson",
+            "getAllProducts",
+            "getProductByName",
+            "addProduct",
+            "deleteProductByName",
+        ] {
+            weights.set(operation, 0);
+        }
+
+        let outcome = Explorer::new()
+            .with_spec(sut::SPECIFICATION)
+            .with_target(&demo.url())
+            .with_behaviour(Behaviour::Model)
+            .with_weights(weights)
+            .with_model(
+                "state persons\n\
+                 on addPerson when 201: persons += 1\n\
+                 on deletePerson when 204: persons -= 1\n\
+                 on getPersons: length == persons",
+            )
+            .with_tests(100)
+            .with_sequence_length(1, 4)
+            .with_seed(7)
+            .run()
+            .unwrap();
+
+        // The list is read after a person was deleted
+        let divergent = outcome.runs[0].results.iter().any(|result| match result {
+            ExplorationResult::ModelDivergence {
+                divergence: Some(divergence),
+                minimal_sequence: Some(sequence),
+                ..
+            } => {
+                divergence.operation == "getPersons"
+                    && sequence.iter().any(|op| op.name == "deletePerson")
+            }
+            _ => false,
+        });
+        assert!(divergent);
+    }
+}
@@ -0,0 +1,104 @@
+//! Per-exploration setup cost on large AMOSes. Compares creating a context,
+//!  its runner and buckets, for every exploration with reusing one context.
+//!
+//! Run with: `cargo bench -p qr_explore --bench exploration_setup`
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use qr_explore::amos::{
+    Operation, OperationInfo, OperationMetaData, Parameter, ParameterOwnership, Schema,
+};
+use qr_explore::explore::{self, ExplorationContext, Target};
+use qr_http_resource::http::{HTTPConfiguration, HTTPMethod, Protocol};
+
+const EXPLORATIONS: u32 = 100;
+
+/// Operations for nested resources, up to five levels deep
+fn large_amos_operations(size: usize) -> Vec<Operation> {
+    (0..size)
+        .map(|idx| {
+            let depth = idx % 5 + 1;
+            let url = (0..depth)
+                .map(|level| format!("/r{idx}_{level}/{{id{level}}}"))
+                .collect::<String>();
+            let method = match idx % 3 {
+                0 => HTTPMethod::POST,
+                1 => HTTPMethod::GET,
+                _ => HTTPMethod::DELETE,
+            };
+
+            Operation {
+                info: OperationInfo {
+                    name: format!("op_{idx}"),
+                    key: format!("operation/op_{idx}"),
+                    alias: None,
+                    deprecated: false,
+                },
+                parameters: (0..depth)
+                    .map(|level| Parameter {
+                        name: format!("id{level}"),
+                        schema: Schema::StringNonEmpty,
+                        required: true,
+                        ownership: ParameterOwnership::Dependency,
+                        meta_data: None,
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None,
+                    })
+                    .collect(),
+                responses: vec![],
+                meta_data: Some(OperationMetaData::HTTP { url, method }),
+            }
+        })
+        .collect()
+}
+
+fn context() -> ExplorationContext {
+    ExplorationContext {
+        http_send_fn: explore::invoke_dry,
+        number_of_tests: 100,
+        ..ExplorationContext::new(Target::HTTP {
+            config: HTTPConfiguration::new("localhost".to_string(), 80, Protocol::HTTP),
+        })
+    }
+}
+
+/// A context of its own for every exploration, the buckets are created for
+///  each of them
+fn fresh_setup(operations: &[Operation]) -> Duration {
+    let start = Instant::now();
+    for _ in 0..EXPLORATIONS {
+        let mut context = context();
+        black_box(context.buckets(operations));
+        black_box(context.runner.borrow_mut().new_rng());
+    }
+
+    start.elapsed()
+}
+
+/// One context for all explorations, the buckets are created for the first
+fn shared_setup(operations: &[Operation]) -> Duration {
+    let start = Instant::now();
+    let mut context = context();
+    for _ in 0..EXPLORATIONS {
+        black_box(context.buckets(operations));
+        black_box(context.runner.borrow_mut().new_rng());
+    }
+
+    start.elapsed()
+}
+
+fn main() {
+    for size in [100, 1_000, 10_000] {
+        let operations = large_amos_operations(size);
+
+        let fresh = fresh_setup(&operations);
+        let shared = shared_setup(&operations);
+
+        println!(
+            "{size:>6} operations, {EXPLORATIONS} explorations: fresh {:>10.3?}, shared {:>10.3?}",
+            fresh, shared
+        );
+    }
+}
@@ -1,6 +1,9 @@
-use crate::amos_generation;
+use crate::amos_generation::{self, RegexPattern};
+use crate::amos_relations::{RelationHint, RelationSource};
+use crate::cache::CacheOutcome;
+use crate::conditional;
 use qr_http_resource::http::HTTPParameterTarget;
-use qr_http_resource::http::{HTTPMethod, HTTPStatus};
+use qr_http_resource::http::{HTTPMethod, HTTPStatus, TransportError};
 use qr_open_api::open_api::DataType;
 use qr_open_api::open_api::{
     DataType as OpenAPIDataType, Definition as OpenAPIDefinition, DefinitionKind,
@@ -9,6 +12,7 @@ use qr_open_api::open_api::{
 };
 use serde;
 use serde_json;
+use std::collections::{HashMap, HashSet};
 use tracing::error;
 
 pub type Result<T> = core::result::Result<T, Error>;
@@ -18,20 +22,97 @@ pub enum Error {
     LoadFileFailure,
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub enum ResultMetaData {
-    HTTP { url: String, status: HTTPStatus },
+    HTTP {
+        url: String,
+        status: HTTPStatus,
+        /// Size in bytes of the payload as received
+        #[serde(default)]
+        size: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_encoding: Option<String>,
+        /// The response headers, with lower case names
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        headers: Vec<(String, String)>,
+    },
+}
+
+impl ResultMetaData {
+    /// The value of the header with the name, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        match self {
+            ResultMetaData::HTTP { headers, .. } => headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str()),
+        }
+    }
+}
+
+// Headers such as Date differ between any two responses, the meta data is
+//  compared without them
+impl PartialEq for ResultMetaData {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ResultMetaData::HTTP {
+                    url,
+                    status,
+                    size,
+                    content_encoding,
+                    ..
+                },
+                ResultMetaData::HTTP {
+                    url: other_url,
+                    status: other_status,
+                    size: other_size,
+                    content_encoding: other_content_encoding,
+                    ..
+                },
+            ) => {
+                url == other_url
+                    && status == other_status
+                    && size == other_size
+                    && content_encoding == other_content_encoding
+            }
+        }
+    }
 }
 
 /// Results of invoking AMOS operations
 // TODO: should probably have a way of expressing a failed result
 // TODO: Fix the cycle between amos/amos_generation
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct InvokeResult {
     pub operation: amos_generation::GeneratedOperation,
     pub result: String,
     pub success: bool,
     pub meta_data: Option<ResultMetaData>,
+    /// How long the SUT took to respond, if the result is from an invocation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<std::time::Duration>,
+    /// Set if the request got no response, the result then says nothing of
+    ///  the SUT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport_error: Option<TransportError>,
+    /// The id of the sequence the result is from, as sent to the SUT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<SequencePosition>,
+    /// Set if the client cache took part in the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheOutcome>,
+}
+
+/// Where in an exploration an invocation was made
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SequencePosition {
+    /// The number of the sequence in the run, from 1
+    pub test_case: u64,
+    /// The index of the operation in the sequence
+    pub index: usize,
 }
 
 impl InvokeResult {
@@ -46,14 +127,74 @@ impl InvokeResult {
             result,
             success,
             meta_data,
+            duration: None,
+            transport_error: None,
+            correlation_id: None,
+            position: None,
+            cache: None,
+        }
+    }
+
+    /// The result of a request that got no response
+    pub fn transport_failure(
+        operation: amos_generation::GeneratedOperation,
+        error: TransportError,
+    ) -> Self {
+        InvokeResult {
+            transport_error: Some(error),
+            ..InvokeResult::new(operation, error.to_string(), false, None)
+        }
+    }
+
+    pub fn is_transport_error(&self) -> bool {
+        self.transport_error.is_some()
+    }
+
+    pub fn with_duration(self, duration: std::time::Duration) -> Self {
+        InvokeResult {
+            duration: Some(duration),
+            ..self
+        }
+    }
+
+    pub fn with_correlation_id(self, correlation_id: &str) -> Self {
+        InvokeResult {
+            correlation_id: Some(correlation_id.to_string()),
+            ..self
         }
     }
+
+    pub fn with_position(self, test_case: u64, index: usize) -> Self {
+        InvokeResult {
+            position: Some(SequencePosition { test_case, index }),
+            ..self
+        }
+    }
+
+    pub fn with_cache(self, cache: Option<CacheOutcome>) -> Self {
+        InvokeResult { cache, ..self }
+    }
+}
+
+// The duration, correlation id and position differ between any two
+//  invocations, results are compared by what the SUT responded
+impl PartialEq for InvokeResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.operation == other.operation
+            && self.result == other.result
+            && self.success == other.success
+            && self.meta_data == other.meta_data
+            && self.transport_error == other.transport_error
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Property {
     pub name: String,
     pub schema: Schema,
+    /// The example values of the specification, see `Parameter::examples`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
@@ -69,7 +210,7 @@ pub enum Schema {
     String,
     StringNonEmpty,
     StringDateTime,
-    StringRegex { regex: String },
+    StringRegex { regex: RegexPattern },
     Number,
     Double,
     Float,
@@ -99,6 +240,35 @@ pub enum ParameterOwnership {
     Unknown,
 }
 
+impl std::fmt::Display for ParameterOwnership {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterOwnership::Owned => write!(f, "Owned"),
+            ParameterOwnership::Dependency => write!(f, "Dependency"),
+            ParameterOwnership::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// The ownership the user gave a parameter, in place of the inferred one
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct OwnershipOverride {
+    pub operation: String,
+    pub parameter: String,
+    pub ownership: ParameterOwnership,
+}
+
+/// An operation the SUT sends after the operation declaring it as a callback,
+///  to the URL of the runtime expression of its meta data. It is observed, it
+///  is not invoked.
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Callback {
+    /// The operation declaring the callback
+    pub operation: String,
+    pub name: String,
+    pub callback: Operation,
+}
+
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Parameter {
     pub name: String,
@@ -106,12 +276,57 @@ pub struct Parameter {
     pub required: bool,
     pub ownership: ParameterOwnership,
     pub meta_data: Option<ParameterMetaData>,
+    /// Values seen for the parameter, e.g. in recorded traffic, generated
+    ///  alongside the values of the schema
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    /// The declared sources of the value, see `AMOS::relation_hints`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relation_hints: Vec<RelationSource>,
+    /// Generates the values in place of the schema, see
+    ///  `GeneratorOverrides`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<GeneratorSpec>,
+}
+
+/// A user defined generator of parameter values
+#[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GeneratorSpec {
+    Regex {
+        regex: RegexPattern,
+    },
+    /// One of the values, read as values of the schema of the parameter
+    Enum {
+        values: Vec<String>,
+    },
+    /// Integers, or doubles for a number schema, within the bounds
+    Range {
+        min: i64,
+        max: i64,
+    },
+    /// The values of a file, one per line, read when the exploration starts
+    Dictionary {
+        path: String,
+    },
+    /// Strings of emoji, CJK, right-to-left and combining characters and null
+    ///  bytes, to find where the SUT gets the encoding wrong
+    Unicode,
+    /// Strings of up to the length, or arrays of up to as many strings, to
+    ///  probe the payload limits of the SUT
+    Large {
+        max_length: usize,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Response {
     pub name: String,
     pub schema: Schema,
+    /// The status the response is declared for, none if the specification
+    ///  has no status for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<HTTPStatus>,
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
@@ -119,6 +334,20 @@ pub struct OperationInfo {
     pub name: String,
     // TODO: needed?
     pub key: String,
+    /// A name given by the user, shown instead of the name. The name stays
+    ///  the key of the operation in settings, results and reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Marked as deprecated in the specification, to be removed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+}
+
+impl OperationInfo {
+    /// The alias of the operation, or its name without one
+    pub fn display_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, serde::Deserialize, serde::Serialize)]
@@ -153,6 +382,21 @@ pub struct AMOS {
     pub domain: Domain,
     pub definitions: Vec<Definition>,
     pub operations: Vec<Operation>,
+    /// Relations declared by the user, given to the parameters of the resolved
+    ///  operations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relation_hints: Vec<RelationHint>,
+    /// Ownerships given by the user, given to the parameters of the resolved
+    ///  operations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ownership_overrides: Vec<OwnershipOverride>,
+    /// The operations the SUT sends, of the operations declaring them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub callbacks: Vec<Callback>,
+    /// The path the URLs of the operations are relative to, of the
+    ///  specification. A target may have a base path of its own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
 }
 
 impl AMOS {
@@ -164,6 +408,10 @@ impl AMOS {
             },
             definitions: vec![],
             operations: vec![],
+            relation_hints: vec![],
+            ownership_overrides: vec![],
+            callbacks: vec![],
+            base_path: None,
         }
     }
 
@@ -175,20 +423,188 @@ impl AMOS {
         self.operations.iter().find(|o| o.info.name == name)
     }
 
+    /// The callbacks the SUT sends after the operation of the name
+    pub fn callbacks_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Callback> {
+        self.callbacks.iter().filter(move |c| c.operation == name)
+    }
+
+    /// The name to show for the operation of the name, as it is referred to
+    ///  in results and invocations
+    pub fn display_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.find_operation(name)
+            .map_or(name, |operation| operation.info.display_name())
+    }
+
+    /// Give the operation an alias, an empty alias removes it
+    pub fn set_alias(&mut self, name: &str, alias: &str) {
+        if let Some(operation) = self.operations.iter_mut().find(|o| o.info.name == name) {
+            operation.info.alias = (!alias.is_empty()).then(|| alias.to_string());
+        }
+    }
+
+    /// The ownership the user gave the parameter of the operation, if any
+    pub fn ownership_override(
+        &self,
+        operation: &str,
+        parameter: &str,
+    ) -> Option<&ParameterOwnership> {
+        self.ownership_overrides
+            .iter()
+            .find(|o| o.operation == operation && o.parameter == parameter)
+            .map(|o| &o.ownership)
+    }
+
+    /// Override the inferred ownership of the parameter, `None` removes the
+    ///  override
+    pub fn set_ownership_override(
+        &mut self,
+        operation: &str,
+        parameter: &str,
+        ownership: Option<ParameterOwnership>,
+    ) {
+        self.ownership_overrides
+            .retain(|o| o.operation != operation || o.parameter != parameter);
+        if let Some(ownership) = ownership {
+            self.ownership_overrides.push(OwnershipOverride {
+                operation: operation.to_string(),
+                parameter: parameter.to_string(),
+                ownership,
+            });
+        }
+    }
+
     pub fn find_definition(&self, name: &str) -> Option<&Definition> {
         self.definitions.iter().find(|d| d.name == name)
     }
 
+    /// The names of the definitions the operations take or respond with, also
+    ///  through the definitions these reference
+    pub fn reachable_definitions(&self) -> HashSet<String> {
+        let mut reachable = HashSet::new();
+        for operation in &self.operations {
+            for param in &operation.parameters {
+                self.referenced_definitions(&param.schema, &mut reachable);
+            }
+            for response in &operation.responses {
+                self.referenced_definitions(&response.schema, &mut reachable);
+            }
+        }
+        reachable
+    }
+
+    /// The names of the definitions the schema references, also through the
+    ///  definitions it references
+    fn referenced_definitions(&self, schema: &Schema, names: &mut HashSet<String>) {
+        match schema {
+            Schema::Reference(reference)
+            | Schema::ArrayOfRefItems(reference)
+            | Schema::ArrayOfUniqueRefItems(reference) => {
+                let name = reference.rsplit('/').next().unwrap_or(reference);
+                if names.insert(name.to_string()) {
+                    if let Some(definition) = self.find_definition(name) {
+                        self.referenced_definitions(&definition.schema, names);
+                    }
+                }
+            }
+            Schema::Object { properties } => {
+                for property in properties {
+                    self.referenced_definitions(&property.schema, names);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// The operations that can change the state, by their index
+    pub fn mutating_operations(&self) -> Vec<usize> {
+        self.operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| !is_get(op))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The PUT operations of the resources that are also read with a GET, by
+    ///  their index
+    pub fn resource_updates(&self) -> Vec<usize> {
+        self.operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| conditional::read_of(op, &self.operations).is_some())
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The POST operations, by their index
+    pub fn creations(&self) -> Vec<usize> {
+        self.operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| {
+                matches!(
+                    op.meta_data,
+                    Some(OperationMetaData::HTTP {
+                        method: HTTPMethod::POST,
+                        ..
+                    })
+                )
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// The query operations suggested to observe the state changed by the
+    ///  mutating operations, by their index. These are the GET operations
+    ///  that respond with a definition the mutating operations take or
+    ///  respond with, all of them when the mutating operations touch none.
+    pub fn suggested_query_operations(&self, mutating: &[usize]) -> Vec<usize> {
+        let mut touched = HashSet::new();
+        for operation in mutating.iter().filter_map(|&idx| self.operations.get(idx)) {
+            for param in &operation.parameters {
+                self.referenced_definitions(&param.schema, &mut touched);
+            }
+            for response in &operation.responses {
+                self.referenced_definitions(&response.schema, &mut touched);
+            }
+        }
+
+        let gets = self
+            .operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| is_get(op))
+            .map(|(idx, _)| idx);
+        if touched.is_empty() {
+            return gets.collect();
+        }
+
+        gets.filter(|&idx| {
+            let mut responded = HashSet::new();
+            for response in &self.operations[idx].responses {
+                self.referenced_definitions(&response.schema, &mut responded);
+            }
+            !responded.is_disjoint(&touched)
+        })
+        .collect()
+    }
+
     pub fn resolve_operation(&self, name: &str) -> Option<Operation> {
         if let Some(op) = self.find_operation(name) {
             let mut resolved = op.clone();
 
             let mut new_params = vec![];
 
-            for param in resolved.parameters {
+            for mut param in resolved.parameters {
+                // The properties of a definition take the ownership of the
+                //  parameter
+                if let Some(ownership) = self.ownership_override(&resolved.info.name, &param.name) {
+                    param.ownership = ownership.clone();
+                }
+
                 match param.schema {
                     Schema::Reference(ref r) => {
-                        if let Some(name) = r.split('/').last() {
+                        if let Some(name) = r.split('/').next_back() {
                             if let Some(definition) = self.find_definition(name) {
                                 // the definition contains an object/properties schema
                                 //  which need to be resolved into a [properties] format
@@ -201,6 +617,9 @@ impl AMOS {
                                                 required: param.required,
                                                 ownership: param.ownership.clone(),
                                                 meta_data: param.meta_data.clone(),
+                                                examples: def_prop.examples.clone(),
+                                                relation_hints: vec![],
+                                                generator: None,
                                             })
                                         }
                                     }
@@ -214,6 +633,15 @@ impl AMOS {
                 }
             }
 
+            for param in new_params.iter_mut() {
+                param.relation_hints = self
+                    .relation_hints
+                    .iter()
+                    .filter(|h| h.operation == resolved.info.name && h.parameter == param.name)
+                    .map(|h| h.source.clone())
+                    .collect();
+            }
+
             resolved.parameters = new_params;
             Some(resolved)
         } else {
@@ -275,6 +703,200 @@ impl AMOS {
             }
         }
     }
+
+    /// Combine the translations of the specifications of several services, by
+    ///  service name, into one AMOS. Operations and definitions with a name used
+    ///  by more than one service are prefixed by the service name, e.g.
+    ///  `orders.getItem`, unless the definitions are the same. The services are
+    ///  expected to be reached through one target, e.g. an API gateway.
+    pub fn merge(services: Vec<(String, TranslationResult)>) -> TranslationResult {
+        let mut operation_services: HashMap<String, usize> = HashMap::new();
+        for (_, result) in &services {
+            for operation in &result.amos.operations {
+                *operation_services
+                    .entry(operation.info.name.clone())
+                    .or_default() += 1;
+            }
+        }
+
+        // Definitions are only renamed when the services define them
+        //  differently. Renaming a definition changes the definitions referring
+        //  to it, they are compared again until no more collide.
+        let mut colliding_definitions: HashSet<String> = HashSet::new();
+        loop {
+            let mut definition_schemas: HashMap<&str, Vec<Schema>> = HashMap::new();
+            for (service, result) in &services {
+                let renames =
+                    definition_renames(service, &result.amos.definitions, &colliding_definitions);
+                for definition in &result.amos.definitions {
+                    let mut schema = definition.schema.clone();
+                    rename_references(&mut schema, &renames);
+
+                    let schemas = definition_schemas.entry(&definition.name).or_default();
+                    if !schemas.contains(&schema) {
+                        schemas.push(schema);
+                    }
+                }
+            }
+
+            let colliding: Vec<String> = definition_schemas
+                .into_iter()
+                .filter(|(name, schemas)| {
+                    schemas.len() > 1 && !colliding_definitions.contains(*name)
+                })
+                .map(|(name, _)| name.to_string())
+                .collect();
+            if colliding.is_empty() {
+                break;
+            }
+            colliding_definitions.extend(colliding);
+        }
+
+        let mut merged = TranslationResult {
+            amos: AMOS::new(),
+            warnings: vec![],
+            errors: vec![],
+        };
+        merged.amos.name = services
+            .iter()
+            .map(|(service, _)| service.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // The services share a base path, or each operation is given the base
+        //  path of its service
+        let shared_base_path = services
+            .windows(2)
+            .all(|pair| pair[0].1.amos.base_path == pair[1].1.amos.base_path);
+        if shared_base_path {
+            merged.amos.base_path = services
+                .first()
+                .and_then(|(_, result)| result.amos.base_path.clone());
+        }
+
+        for (service, result) in services {
+            let prefixed = |name: &str| format!("{}.{}", service, name);
+            let renames =
+                definition_renames(&service, &result.amos.definitions, &colliding_definitions);
+
+            for mut definition in result.amos.definitions {
+                if let Some(name) = renames.get(&definition.name) {
+                    definition.name = name.clone();
+                    definition.key = format!("definition/{}", name);
+                }
+                rename_references(&mut definition.schema, &renames);
+
+                if !merged.amos.definitions.contains(&definition) {
+                    merged.amos.definitions.push(definition);
+                }
+            }
+
+            let service_base_path = result.amos.base_path.clone().filter(|_| !shared_base_path);
+            for mut operation in result.amos.operations {
+                if let (Some(base_path), Some(OperationMetaData::HTTP { url, .. })) =
+                    (&service_base_path, &mut operation.meta_data)
+                {
+                    *url = format!("{}{}", base_path, url);
+                }
+                if operation_services[&operation.info.name] > 1 {
+                    let name = prefixed(&operation.info.name);
+                    merged.warnings.push(TranslationMessage::new(format!(
+                        "Renamed operation {} of {} to {}",
+                        operation.info.name, service, name
+                    )));
+                    operation.info.key = format!("operation/{}", name);
+                    operation.info.name = name;
+                }
+
+                for parameter in operation.parameters.iter_mut() {
+                    rename_references(&mut parameter.schema, &renames);
+                }
+                for response in operation.responses.iter_mut() {
+                    rename_references(&mut response.schema, &renames);
+                }
+
+                merged.amos.push_operation(operation);
+            }
+
+            // Hints within a service follow the renamed operations
+            let renamed = |name: &mut String| {
+                if operation_services
+                    .get(name.as_str())
+                    .is_some_and(|n| *n > 1)
+                {
+                    *name = prefixed(name);
+                }
+            };
+            for mut hint in result.amos.relation_hints {
+                renamed(&mut hint.operation);
+                match &mut hint.source {
+                    RelationSource::Parameter { operation, .. }
+                    | RelationSource::Response { operation, .. } => renamed(operation),
+                }
+                merged.amos.relation_hints.push(hint);
+            }
+            for mut callback in result.amos.callbacks {
+                renamed(&mut callback.operation);
+                merged.amos.callbacks.push(callback);
+            }
+
+            let messages = |messages: Vec<TranslationMessage>| {
+                messages
+                    .into_iter()
+                    .map(|m| TranslationMessage::new(format!("{}: {}", service, m.message)))
+                    .collect::<Vec<_>>()
+            };
+            merged.warnings.extend(messages(result.warnings));
+            merged.errors.extend(messages(result.errors));
+        }
+
+        merged
+    }
+}
+
+/// Point the references of the schema to the renamed definitions
+fn is_get(operation: &Operation) -> bool {
+    matches!(
+        &operation.meta_data,
+        Some(OperationMetaData::HTTP { method, .. }) if *method == HTTPMethod::GET
+    )
+}
+
+/// The new names of the colliding definitions of the service, prefixed with it
+fn definition_renames(
+    service: &str,
+    definitions: &[Definition],
+    colliding: &HashSet<String>,
+) -> HashMap<String, String> {
+    definitions
+        .iter()
+        .filter(|d| colliding.contains(&d.name))
+        .map(|d| (d.name.clone(), format!("{}.{}", service, d.name)))
+        .collect()
+}
+
+fn rename_references(schema: &mut Schema, renames: &HashMap<String, String>) {
+    let rename = |reference: &mut String| {
+        if let Some((path, name)) = reference.rsplit_once('/') {
+            if let Some(new_name) = renames.get(name) {
+                *reference = format!("{}/{}", path, new_name);
+            }
+        } else if let Some(new_name) = renames.get(reference.as_str()) {
+            *reference = new_name.clone();
+        }
+    };
+
+    match schema {
+        Schema::Reference(reference)
+        | Schema::ArrayOfRefItems(reference)
+        | Schema::ArrayOfUniqueRefItems(reference) => rename(reference),
+        Schema::Object { properties } => {
+            for property in properties.iter_mut() {
+                rename_references(&mut property.schema, renames);
+            }
+        }
+        _ => (),
+    }
 }
 
 impl Default for AMOS {
@@ -330,76 +952,41 @@ fn definition_object_to_schema(
 ) -> Schema {
     let props = properties
         .iter()
-        .map(|p| match &p.kind {
-            OpenAPIDataType::ArrayOfUniqueRefItems(refstr) => Property {
-                name: p.name.clone(),
-                schema: Schema::ArrayOfUniqueRefItems(refstr.clone()),
-            },
-            OpenAPIDataType::ArrayOfRefItems(refstr) => Property {
-                name: p.name.clone(),
-                schema: Schema::ArrayOfRefItems(refstr.clone()),
-            },
-            OpenAPIDataType::ArrayOfStrings => Property {
-                name: p.name.clone(),
-                schema: Schema::ArrayOfString,
-            },
-            OpenAPIDataType::File => Property {
-                name: p.name.clone(),
-                schema: Schema::File,
-            },
-            OpenAPIDataType::Integer64 => Property {
-                name: p.name.clone(),
-                schema: Schema::Int,
-            },
-            OpenAPIDataType::Integer32 => Property {
-                name: p.name.clone(),
-                schema: Schema::Int32,
-            },
-            OpenAPIDataType::String => Property {
-                name: p.name.clone(),
-                schema: Schema::String,
-            },
-            OpenAPIDataType::Boolean => Property {
-                name: p.name.clone(),
-                schema: Schema::Bool,
-            },
-            OpenAPIDataType::StringDateTime => Property {
-                name: p.name.clone(),
-                schema: Schema::StringDateTime,
-            },
-            OpenAPIDataType::Double => Property {
-                name: p.name.clone(),
-                schema: Schema::Double,
-            },
-            OpenAPIDataType::Float => Property {
-                name: p.name.clone(),
-                schema: Schema::Float,
-            },
-            OpenAPIDataType::Number => Property {
-                name: p.name.clone(),
-                schema: Schema::Number,
-            },
-            OpenAPIDataType::IPV4 => Property {
-                name: p.name.clone(),
-                schema: Schema::IPV4,
-            },
-            OpenAPIDataType::Schema(schema) => Property {
-                name: p.name.clone(),
-                schema: match_schema(ctx, schema),
-            },
-            OpenAPIDataType::Unsupported => {
-                ctx.push_warning(TranslationMessage::new(format!(
-                    "Unsupported Property kind: {:?} at {}:{}",
-                    p.kind,
-                    file!(),
-                    line!(),
-                )));
-
-                Property {
-                    name: p.name.clone(),
-                    schema: Schema::Unsupported,
+        .map(|p| {
+            let schema = match &p.kind {
+                OpenAPIDataType::ArrayOfUniqueRefItems(refstr) => {
+                    Schema::ArrayOfUniqueRefItems(refstr.clone())
                 }
-            } //_ => todo!(),
+                OpenAPIDataType::ArrayOfRefItems(refstr) => Schema::ArrayOfRefItems(refstr.clone()),
+                OpenAPIDataType::ArrayOfStrings => Schema::ArrayOfString,
+                OpenAPIDataType::File => Schema::File,
+                OpenAPIDataType::Integer64 => Schema::Int,
+                OpenAPIDataType::Integer32 => Schema::Int32,
+                OpenAPIDataType::String => Schema::String,
+                OpenAPIDataType::Boolean => Schema::Bool,
+                OpenAPIDataType::StringDateTime => Schema::StringDateTime,
+                OpenAPIDataType::Double => Schema::Double,
+                OpenAPIDataType::Float => Schema::Float,
+                OpenAPIDataType::Number => Schema::Number,
+                OpenAPIDataType::IPV4 => Schema::IPV4,
+                OpenAPIDataType::Schema(schema) => match_schema(ctx, schema),
+                OpenAPIDataType::Unsupported => {
+                    ctx.push_warning(TranslationMessage::new(format!(
+                        "Unsupported Property kind: {:?} at {}:{}",
+                        p.kind,
+                        file!(),
+                        line!(),
+                    )));
+
+                    Schema::Unsupported
+                } //_ => todo!(),
+            };
+
+            Property {
+                name: p.name.clone(),
+                schema,
+                examples: p.examples.clone(),
+            }
         })
         .collect();
 
@@ -451,14 +1038,19 @@ fn open_api_response_to_amos(
         (HTTPStatus::OK, Some(s)) => match_data_type_to_schema(ctx, s, None),
         (HTTPStatus::Created, _) => Schema::Int,
         (HTTPStatus::NoContent, _) => Schema::Int,
+        (HTTPStatus::NotModified, _) => Schema::Int,
         (HTTPStatus::BadRequest, _) => Schema::Int,
         (HTTPStatus::Unauthorized, _) => Schema::Int,
         (HTTPStatus::Forbidden, _) => Schema::Int,
         (HTTPStatus::MethodNotAllowed, _) => Schema::Int,
+        (HTTPStatus::Conflict, _) => Schema::Int,
+        (HTTPStatus::PreconditionFailed, _) => Schema::Int,
+        (HTTPStatus::PayloadTooLarge, _) => Schema::Int,
         (HTTPStatus::UnsupportedMediaType, _) => Schema::Int,
         (HTTPStatus::Default, _) => Schema::Int,
         (HTTPStatus::NotFound, _) => Schema::Int,
         (HTTPStatus::InternalServerError, _) => Schema::Int,
+        (HTTPStatus::Other(_), _) => Schema::Int,
         (HTTPStatus::Unsupported, _) => {
             ctx.push_warning(TranslationMessage::new(format!(
                 "Unsupported HTTP Status: {:?}",
@@ -471,6 +1063,7 @@ fn open_api_response_to_amos(
     Response {
         name: response.description.clone(),
         schema,
+        status: Some(response.status.clone()),
     }
 }
 
@@ -530,6 +1123,16 @@ fn match_data_type_to_schema(
     }
 }
 
+/// If the name is that of an id, e.g. `id`, `personId` or `person_id`
+fn is_id_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == "id"
+        || lower.ends_with("_id")
+        || lower.ends_with("-id")
+        || name.ends_with("Id")
+        || name.ends_with("ID")
+}
+
 fn open_api_parameter_to_amos(
     ctx: &mut TranslationContext,
     parameter: &OperationParameter,
@@ -597,6 +1200,11 @@ fn open_api_parameter_to_amos(
     let ownership = match method {
         HTTPMethod::POST | HTTPMethod::PUT => {
             match &parameter.target {
+                // The resource of an id in the path of a PUT exists, it is
+                //  updated rather than created
+                ParameterIn::Path if *method == HTTPMethod::PUT && is_id_name(&parameter.name) => {
+                    ParameterOwnership::Dependency
+                }
                 ParameterIn::Path => {
                     // URL positions except the last is a dependecy, otherwise owned
                     let pos = url_parts
@@ -615,6 +1223,10 @@ fn open_api_parameter_to_amos(
                     }
                 }
                 ParameterIn::FormData => ParameterOwnership::Owned, // TODO: revisit, this depends
+                // An id outside of the path refers to another resource
+                ParameterIn::Query | ParameterIn::Header if is_id_name(&parameter.name) => {
+                    ParameterOwnership::Dependency
+                }
                 ParameterIn::Body | ParameterIn::Query | ParameterIn::Header => {
                     ParameterOwnership::Unknown
                 }
@@ -639,6 +1251,9 @@ fn open_api_parameter_to_amos(
         required: parameter.required,
         ownership,
         meta_data,
+        examples: parameter.examples.clone(),
+        relation_hints: vec![],
+        generator: None,
     }
 }
 
@@ -660,6 +1275,8 @@ fn open_api_operation_to_amos(
         info: OperationInfo {
             name: operation.id.clone(),
             key,
+            alias: None,
+            deprecated: operation.deprecated,
         },
         parameters: operation
             .parameters
@@ -680,6 +1297,103 @@ fn open_api_operation_to_amos(
     }
 }
 
+/// Compile the regex schemas of the operations ahead of generation. Each pattern
+///  that fails to compile gives a message, and generates plain strings instead.
+pub fn precompile_regex_schemas(operations: &[Operation]) -> Vec<TranslationMessage> {
+    operations
+        .iter()
+        .flat_map(|op| {
+            op.parameters
+                .iter()
+                .filter_map(move |param| match &param.schema {
+                    Schema::StringRegex { regex } => regex.compile().err().map(|e| {
+                        TranslationMessage::new(format!(
+                            "Invalid regex '{}' of parameter {} in {}: {}",
+                            regex, param.name, op.info.name, e
+                        ))
+                    }),
+                    _ => None,
+                })
+        })
+        .collect()
+}
+
+/// The relations declared by the links of the responses, from the linking
+///  operation to the parameters of the linked one
+fn open_api_links_to_hints(
+    ctx: &mut TranslationContext,
+    operations: &[OpenAPIOperation],
+) -> Vec<RelationHint> {
+    let mut hints: Vec<RelationHint> = vec![];
+
+    for op in operations {
+        for link in op.responses.iter().flat_map(|r| &r.links) {
+            if !operations.iter().any(|o| o.id == link.operation_id) {
+                ctx.push_warning(TranslationMessage::new(format!(
+                    "The link {} of {} is to an unknown operation: {}",
+                    link.name, op.id, link.operation_id
+                )));
+                continue;
+            }
+
+            for (parameter, expression) in &link.parameters {
+                let Some(source) = link_source(&op.id, expression) else {
+                    ctx.push_warning(TranslationMessage::new(format!(
+                        "Unsupported expression of the link {} of {}: {}",
+                        link.name, op.id, expression
+                    )));
+                    continue;
+                };
+
+                // The parameter may be qualified by its location, e.g. 'path.id'
+                let parameter = match parameter.split_once('.') {
+                    Some(("path" | "query" | "header" | "cookie", name)) => name,
+                    _ => parameter,
+                };
+                let hint = RelationHint {
+                    operation: link.operation_id.clone(),
+                    parameter: parameter.to_string(),
+                    source,
+                };
+                // The responses of the statuses may declare the same links
+                if !hints.contains(&hint) {
+                    hints.push(hint);
+                }
+            }
+        }
+    }
+
+    hints
+}
+
+/// The source of the runtime expression of a link of the operation, a field
+///  of its response, e.g. `$response.body#/data/id`, or one of its parameters,
+///  e.g. `$request.path.id`
+fn link_source(operation: &str, expression: &str) -> Option<RelationSource> {
+    if let Some(pointer) = expression.strip_prefix("$response.body#/") {
+        return Some(RelationSource::Response {
+            operation: operation.to_string(),
+            field: pointer.replace('/', "."),
+        });
+    }
+
+    let request = expression.strip_prefix("$request.")?;
+    let parameter = match request.strip_prefix("body#/") {
+        // The fields of the body are parameters, not those nested in them
+        Some(field) if !field.contains('/') => field,
+        Some(_) => return None,
+        None => match request.split_once('.')? {
+            ("path" | "query" | "header", name) => name,
+            _ => return None,
+        },
+    };
+
+    Some(RelationSource::Parameter {
+        operation: operation.to_string(),
+        parameter: parameter.to_string(),
+    })
+}
+
 pub fn open_api_v2_to_amos(
     operations: &[OpenAPIOperation],
     definitions: &[OpenAPIDefinition],
@@ -694,6 +1408,19 @@ pub fn open_api_v2_to_amos(
     let ops = operations
         .iter()
         .map(|op| open_api_operation_to_amos(&mut ctx, op))
+        .collect::<Vec<Operation>>();
+
+    ctx.errors.extend(precompile_regex_schemas(&ops));
+
+    let relation_hints = open_api_links_to_hints(&mut ctx, operations);
+    let callbacks = operations
+        .iter()
+        .flat_map(|op| op.callbacks.iter().map(move |c| (op, c)))
+        .map(|(op, c)| Callback {
+            operation: op.id.clone(),
+            name: c.name.clone(),
+            callback: open_api_operation_to_amos(&mut ctx, &c.operation),
+        })
         .collect();
 
     let amos = AMOS {
@@ -703,6 +1430,10 @@ pub fn open_api_v2_to_amos(
         },
         definitions: defs,
         operations: ops,
+        relation_hints,
+        ownership_overrides: vec![],
+        callbacks,
+        base_path: None,
     };
 
     TranslationResult {
@@ -712,6 +1443,239 @@ pub fn open_api_v2_to_amos(
     }
 }
 
+/// The JSON schema of the schema, as in a Swagger 2.0 definition
+fn schema_to_open_api(schema: &Schema) -> serde_json::Value {
+    use serde_json::json;
+
+    match schema {
+        Schema::Reference(reference) => json!({ "$ref": reference }),
+        Schema::Object { properties } => {
+            let properties: serde_json::Map<String, serde_json::Value> = properties
+                .iter()
+                .map(|property| {
+                    let mut schema = schema_to_open_api(&property.schema);
+                    if let (Some(example), Some(fields)) =
+                        (property.examples.first(), schema.as_object_mut())
+                    {
+                        fields.insert("example".to_string(), json!(example));
+                    }
+                    (property.name.clone(), schema)
+                })
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+        Schema::ArrayOfUniqueRefItems(reference) => json!({
+            "type": "array",
+            "uniqueItems": true,
+            "items": { "$ref": reference },
+        }),
+        Schema::ArrayOfString => json!({ "type": "array", "items": { "type": "string" } }),
+        Schema::ArrayOfRefItems(reference) => {
+            json!({ "type": "array", "items": { "$ref": reference } })
+        }
+        Schema::DateTime | Schema::StringDateTime => {
+            json!({ "type": "string", "format": "date-time" })
+        }
+        Schema::IPV4 => json!({ "type": "string", "format": "ipv4" }),
+        Schema::String => json!({ "type": "string" }),
+        Schema::StringNonEmpty => json!({ "type": "string", "minLength": 1 }),
+        Schema::StringRegex { regex } => json!({ "type": "string", "pattern": regex }),
+        Schema::Number => json!({ "type": "number" }),
+        Schema::Double => json!({ "type": "number", "format": "double" }),
+        Schema::Float => json!({ "type": "number", "format": "float" }),
+        Schema::Int => json!({ "type": "integer", "format": "int64" }),
+        Schema::Int8 => json!({ "type": "integer", "minimum": -128, "maximum": 127 }),
+        Schema::Int32 => json!({ "type": "integer", "format": "int32" }),
+        Schema::Bool => json!({ "type": "boolean" }),
+        Schema::File => json!({ "type": "file" }),
+        Schema::Unsupported => json!({}),
+    }
+}
+
+fn parameter_target(parameter: &Parameter) -> &'static str {
+    match &parameter.meta_data {
+        Some(ParameterMetaData::HTTP { target }) => match target {
+            HTTPParameterTarget::Path => "path",
+            HTTPParameterTarget::FormData => "formData",
+            HTTPParameterTarget::Query => "query",
+            HTTPParameterTarget::Body | HTTPParameterTarget::Unsupported => "body",
+        },
+        None => "body",
+    }
+}
+
+/// The Swagger 2.0 parameters of the operation. Several body parameters of
+///  the AMOS are the fields of one body object.
+fn parameters_to_open_api(parameters: &[Parameter]) -> Vec<serde_json::Value> {
+    use serde_json::json;
+
+    let (body, others): (Vec<&Parameter>, Vec<&Parameter>) = parameters
+        .iter()
+        .partition(|parameter| parameter_target(parameter) == "body");
+
+    let mut open_api: Vec<serde_json::Value> = others
+        .iter()
+        .map(|parameter| {
+            let mut object = json!({
+                "name": parameter.name,
+                "in": parameter_target(parameter),
+                "required": parameter.required,
+            });
+            if let (Some(fields), serde_json::Value::Object(schema)) = (
+                object.as_object_mut(),
+                schema_to_open_api(&parameter.schema),
+            ) {
+                fields.extend(schema);
+                if let Some(example) = parameter.examples.first() {
+                    fields.insert("x-example".to_string(), json!(example));
+                }
+            }
+            object
+        })
+        .collect();
+
+    // The body is where its first field was among the parameters
+    let body_position = parameters
+        .iter()
+        .take_while(|parameter| parameter_target(parameter) != "body")
+        .count();
+
+    match body.as_slice() {
+        [] => (),
+        [parameter] => open_api.insert(
+            body_position,
+            json!({
+                "name": parameter.name,
+                "in": "body",
+                "required": parameter.required,
+                "schema": schema_to_open_api(&parameter.schema),
+            }),
+        ),
+        fields => {
+            let schema = Schema::Object {
+                properties: fields
+                    .iter()
+                    .map(|field| Property {
+                        name: field.name.clone(),
+                        schema: field.schema.clone(),
+                        examples: field.examples.clone(),
+                    })
+                    .collect(),
+            };
+            let mut schema = schema_to_open_api(&schema);
+            let required: Vec<&str> = fields
+                .iter()
+                .filter(|field| field.required)
+                .map(|field| field.name.as_str())
+                .collect();
+            if let (false, Some(object)) = (required.is_empty(), schema.as_object_mut()) {
+                object.insert("required".to_string(), json!(required));
+            }
+            open_api.insert(
+                body_position,
+                json!({
+                    "name": "body",
+                    "in": "body",
+                    "required": !required.is_empty(),
+                    "schema": schema,
+                }),
+            )
+        }
+    }
+
+    open_api
+}
+
+/// The AMOS as a Swagger 2.0 document, with the schemas and examples it has
+///  learned. The status of the responses is not kept in the AMOS, a response
+///  with a payload is the 200 response of its operation.
+pub fn to_open_api(amos: &AMOS) -> serde_json::Value {
+    use serde_json::json;
+
+    let mut paths = serde_json::Map::new();
+    for operation in &amos.operations {
+        let Some(OperationMetaData::HTTP { url, method }) = &operation.meta_data else {
+            continue;
+        };
+        let method = match method {
+            HTTPMethod::GET => "get",
+            HTTPMethod::DELETE => "delete",
+            HTTPMethod::POST => "post",
+            HTTPMethod::PUT => "put",
+            HTTPMethod::Unsupported => continue,
+        };
+
+        let payload = operation
+            .responses
+            .iter()
+            .find(|response| !matches!(response.schema, Schema::Int | Schema::Unsupported));
+        let responses = match (payload, operation.responses.first()) {
+            (Some(response), _) => json!({
+                "200": {
+                    "description": response.name,
+                    "schema": schema_to_open_api(&response.schema),
+                }
+            }),
+            (None, Some(response)) => json!({ "default": { "description": response.name } }),
+            (None, None) => json!({ "default": { "description": "Response" } }),
+        };
+
+        // The AMOS does not keep the media types, form data is sent as a form
+        let form = operation.parameters.iter().any(|param| {
+            param.meta_data
+                == Some(ParameterMetaData::HTTP {
+                    target: HTTPParameterTarget::FormData,
+                })
+        });
+        let consumes = if form {
+            "application/x-www-form-urlencoded"
+        } else {
+            "application/json"
+        };
+
+        let path = paths
+            .entry(url.clone())
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("A path item is an object");
+        path.insert(
+            method.to_string(),
+            json!({
+                "operationId": operation.info.name,
+                "consumes": [consumes],
+                "produces": ["application/json"],
+                "parameters": parameters_to_open_api(&operation.parameters),
+                "responses": responses,
+                "deprecated": operation.info.deprecated,
+            }),
+        );
+    }
+
+    // The references of the AMOS are to '#/definitions/<name>'
+    let definitions: serde_json::Map<String, serde_json::Value> = amos
+        .definitions
+        .iter()
+        .map(|definition| {
+            (
+                definition.name.clone(),
+                schema_to_open_api(&definition.schema),
+            )
+        })
+        .collect();
+
+    let mut open_api = json!({
+        "swagger": "2.0",
+        "info": { "title": amos.name, "version": "1.0.0" },
+        "paths": paths,
+        "definitions": definitions,
+    });
+    if let Some(base_path) = &amos.base_path {
+        open_api["basePath"] = json!(base_path);
+    }
+
+    open_api
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::OnceLock;
@@ -719,7 +1683,7 @@ mod tests {
     use crate::amos::OperationMetaData::HTTP;
     use crate::amos::Schema::*;
     use crate::amos::*;
-    use qr_http_resource::http::HTTPMethod::{GET, POST};
+    use qr_http_resource::http::HTTPMethod::{GET, POST, PUT};
     use qr_http_resource::http::HTTPParameterTarget;
     use qr_open_api::open_api;
     use qr_open_api::open_api::{
@@ -753,6 +1717,8 @@ mod tests {
             info: OperationInfo {
                 name: "getAllProducts".to_string(),
                 key: "operation/getAllProducts".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "productName".to_string(),
@@ -762,10 +1728,14 @@ mod tests {
                 meta_data: Some(ParameterMetaData::HTTP {
                     target: HTTPParameterTarget::Path,
                 }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: ArrayOfString,
+                status: None,
             }],
             meta_data: Some(HTTP {
                 url: "/products".to_string(),
@@ -783,16 +1753,19 @@ mod tests {
                     Property {
                         name: "id".to_string(),
                         schema: Int,
+                        examples: vec![],
                     },
                     Property {
                         name: "name".to_string(),
                         schema: String,
+                        examples: vec![],
                     },
                 ],
             },
         }];
 
-        let path = std::path::Path::new("./test_amos.amos");
+        let dir = tempfile::tempdir().unwrap();
+        let path = &dir.path().join("test_amos.amos");
         amos.save(path);
         let loaded_or_default_amos = AMOS::load_or_default(path);
         let loaded_amos = AMOS::load(path).unwrap();
@@ -824,6 +1797,383 @@ mod tests {
         assert_eq!(Error::LoadFileFailure, amos.unwrap_err())
     }
 
+    fn service(definition_schema: Schema, operations: &[&str]) -> TranslationResult {
+        let mut amos = AMOS::new();
+        amos.definitions = vec![Definition {
+            name: "Item".to_string(),
+            key: "definition/Item".to_string(),
+            schema: definition_schema,
+        }];
+        for name in operations {
+            amos.push_operation(Operation {
+                info: OperationInfo {
+                    name: name.to_string(),
+                    key: format!("operation/{}", name),
+                    alias: None,
+                    deprecated: false,
+                },
+                parameters: vec![],
+                responses: vec![Response {
+                    name: "OK".to_string(),
+                    schema: ArrayOfRefItems("#/definitions/Item".to_string()),
+                    status: None,
+                }],
+                meta_data: None,
+            });
+        }
+
+        TranslationResult {
+            amos,
+            warnings: vec![TranslationMessage::new("a warning".to_string())],
+            errors: vec![],
+        }
+    }
+
+    #[test]
+    fn merge_prefixes_colliding_names() {
+        let orders = service(Int, &["getItems", "postOrder"]);
+        let stock = service(String, &["getItems"]);
+
+        let merged = AMOS::merge(vec![
+            ("orders".to_string(), orders),
+            ("stock".to_string(), stock),
+        ]);
+
+        let names: Vec<&str> = merged
+            .amos
+            .operations
+            .iter()
+            .map(|o| o.info.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["orders.getItems", "postOrder", "stock.getItems"]
+        );
+
+        // The definitions differ between the services
+        let definitions: Vec<&str> = merged
+            .amos
+            .definitions
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(definitions, vec!["orders.Item", "stock.Item"]);
+        assert_eq!(
+            merged.amos.operations[2].responses[0].schema,
+            ArrayOfRefItems("#/definitions/stock.Item".to_string())
+        );
+
+        assert!(merged
+            .warnings
+            .iter()
+            .any(|w| w.message == "stock: a warning"));
+    }
+
+    #[test]
+    fn merge_renames_the_definitions_referring_to_colliding_ones() {
+        // Both services define an Order of an Item, but not the same Item
+        let with_order = |item_schema: Schema| {
+            let mut result = service(item_schema, &[]);
+            result.amos.definitions.push(Definition {
+                name: "Order".to_string(),
+                key: "definition/Order".to_string(),
+                schema: Schema::Object {
+                    properties: vec![Property {
+                        name: "item".to_string(),
+                        schema: Reference("#/definitions/Item".to_string()),
+                        examples: vec![],
+                    }],
+                },
+            });
+            result
+        };
+
+        let merged = AMOS::merge(vec![
+            ("orders".to_string(), with_order(Int)),
+            ("stock".to_string(), with_order(String)),
+        ]);
+
+        let definitions: Vec<&str> = merged
+            .amos
+            .definitions
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(
+            definitions,
+            vec!["orders.Item", "orders.Order", "stock.Item", "stock.Order"]
+        );
+        let Schema::Object { properties } = &merged.amos.definitions[3].schema else {
+            panic!("The order is an object")
+        };
+        assert_eq!(
+            properties[0].schema,
+            Reference("#/definitions/stock.Item".to_string())
+        );
+    }
+
+    #[test]
+    fn merge_keeps_one_of_equal_definitions() {
+        let merged = AMOS::merge(vec![
+            ("a".to_string(), service(Int, &["getA"])),
+            ("b".to_string(), service(Int, &["getB"])),
+        ]);
+
+        assert_eq!(merged.amos.definitions.len(), 1);
+        assert_eq!(merged.amos.definitions[0].name, "Item");
+        assert_eq!(merged.amos.operations.len(), 2);
+        assert_eq!(merged.amos.name, "a, b");
+    }
+
+    #[test]
+    fn merge_keeps_the_base_paths_of_the_services() {
+        let with_base_path = |name: &str, base_path: &str| {
+            let mut result = service(Int, &[name]);
+            result.amos.base_path = Some(base_path.to_string());
+            result.amos.operations[0].meta_data = Some(HTTP {
+                url: "/items".to_string(),
+                method: GET,
+            });
+            result
+        };
+        let url = |operation: &Operation| match &operation.meta_data {
+            Some(HTTP { url, .. }) => url.clone(),
+            None => panic!("An HTTP operation"),
+        };
+
+        let shared = AMOS::merge(vec![
+            ("a".to_string(), with_base_path("getA", "/api")),
+            ("b".to_string(), with_base_path("getB", "/api")),
+        ]);
+        assert_eq!(shared.amos.base_path.as_deref(), Some("/api"));
+        assert_eq!(url(&shared.amos.operations[0]), "/items");
+
+        // Each operation is relative to the base path of its service
+        let differing = AMOS::merge(vec![
+            ("orders".to_string(), with_base_path("getOrders", "/orders")),
+            ("stock".to_string(), with_base_path("getStock", "/stock")),
+        ]);
+        assert_eq!(differing.amos.base_path, None);
+        assert_eq!(url(&differing.amos.operations[0]), "/orders/items");
+        assert_eq!(url(&differing.amos.operations[1]), "/stock/items");
+    }
+
+    #[test]
+    fn links_are_relations_and_callbacks_are_observed() {
+        let oas_json = serde_json::json!({
+            "paths": {
+                "/users": { "post": {
+                    "operationId": "createUser",
+                    "parameters": [
+                        { "name": "callbackUrl", "in": "query", "required": true, "type": "string" }
+                    ],
+                    "responses": { "201": {
+                        "description": "Created",
+                        "links": {
+                            "GetUser": {
+                                "operationId": "getUser",
+                                "parameters": { "path.userId": "$response.body#/id" }
+                            },
+                            "GetGroup": { "operationId": "getGroup" }
+                        }
+                    }},
+                    "callbacks": { "onCreated": { "{$request.query.callbackUrl}": { "post": {
+                        "operationId": "userCreated",
+                        "responses": { "200": { "description": "OK" } }
+                    }}}}
+                }},
+                "/users/{userId}": { "get": {
+                    "operationId": "getUser",
+                    "parameters": [
+                        { "name": "userId", "in": "path", "required": true, "type": "string" }
+                    ],
+                    "responses": { "200": { "description": "OK" } }
+                }}
+            }
+        });
+        let parse_result = open_api::parse_json_object(oas_json.as_object().unwrap()).unwrap();
+        let result = open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions);
+        let amos = result.amos;
+
+        assert_eq!(
+            amos.relation_hints,
+            vec![RelationHint {
+                operation: "getUser".to_string(),
+                parameter: "userId".to_string(),
+                source: RelationSource::Response {
+                    operation: "createUser".to_string(),
+                    field: "id".to_string(),
+                },
+            }]
+        );
+        assert_eq!(
+            amos.resolve_operation("getUser").unwrap().parameters[0].relation_hints,
+            vec![amos.relation_hints[0].source.clone()]
+        );
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.message
+                == "The link GetGroup of createUser is to an unknown operation: getGroup"));
+
+        // The callback is not an operation to invoke
+        assert_eq!(amos.operations.len(), 2);
+        let callbacks = amos.callbacks_of("createUser").collect::<Vec<_>>();
+        assert_eq!(callbacks.len(), 1);
+        assert_eq!(callbacks[0].name, "onCreated");
+        assert_eq!(callbacks[0].callback.info.name, "userCreated");
+        assert_eq!(
+            callbacks[0].callback.meta_data,
+            Some(HTTP {
+                url: "{$request.query.callbackUrl}".to_string(),
+                method: POST,
+            })
+        );
+    }
+
+    #[test]
+    fn an_alias_is_shown_instead_of_the_name() {
+        let parse_result = oas_file_1();
+        let mut amos =
+            open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+        let name = amos.operations[0].info.name.clone();
+        assert_eq!(amos.display_name(&name), name);
+
+        amos.set_alias(&name, "List products");
+        assert_eq!(amos.display_name(&name), "List products");
+        assert_eq!(
+            amos.find_operation(&name).unwrap().info.display_name(),
+            "List products"
+        );
+        assert_eq!(amos.display_name("unknown"), "unknown");
+
+        // The alias is kept with the AMOS, an AMOS without aliases still loads
+        let json = serde_json::to_string(&amos).unwrap();
+        assert_eq!(serde_json::from_str::<AMOS>(&json).unwrap(), amos);
+
+        amos.set_alias(&name, "");
+        assert_eq!(amos.operations[0].info.alias, None);
+        let json = serde_json::to_string(&amos).unwrap();
+        assert!(!json.contains("alias"));
+    }
+
+    #[test]
+    fn ids_outside_of_a_post_are_dependencies() {
+        let ownership = |name: &str, target: ParameterIn, url: &str, method: HTTPMethod| {
+            let parameter = OperationParameter {
+                name: name.to_string(),
+                kind: DataType::String,
+                target,
+                required: true,
+                examples: vec![],
+            };
+            let url_parts: Vec<&str> = url.split('/').collect();
+            open_api_parameter_to_amos(
+                &mut TranslationContext::new(),
+                &parameter,
+                url,
+                &url_parts,
+                &method,
+            )
+            .ownership
+        };
+
+        assert_eq!(
+            ownership("id", ParameterIn::Path, "/persons/{id}", POST),
+            ParameterOwnership::Owned
+        );
+        assert_eq!(
+            ownership("id", ParameterIn::Path, "/persons/{id}", PUT),
+            ParameterOwnership::Dependency
+        );
+        assert_eq!(
+            ownership("name", ParameterIn::Path, "/persons/{name}", PUT),
+            ParameterOwnership::Owned
+        );
+        assert_eq!(
+            ownership("person_id", ParameterIn::Query, "/orders", POST),
+            ParameterOwnership::Dependency
+        );
+        assert_eq!(
+            ownership("idea", ParameterIn::Query, "/orders", POST),
+            ParameterOwnership::Unknown
+        );
+    }
+
+    #[test]
+    fn overridden_ownership_is_given_to_the_resolved_parameters() {
+        let parse_result = oas_file_1();
+        let mut amos =
+            open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+        let operation = amos
+            .operations
+            .iter()
+            .find(|op| !op.parameters.is_empty())
+            .unwrap();
+        let (name, parameter) = (
+            operation.info.name.clone(),
+            operation.parameters[0].name.clone(),
+        );
+
+        amos.set_ownership_override(&name, &parameter, Some(ParameterOwnership::Unknown));
+        assert_eq!(
+            amos.ownership_override(&name, &parameter),
+            Some(&ParameterOwnership::Unknown)
+        );
+        let resolved = amos.resolve_operation(&name).unwrap();
+        assert_eq!(
+            resolved.parameters[0].ownership,
+            ParameterOwnership::Unknown
+        );
+
+        // The override is kept with the AMOS, and is replaced by the next
+        let json = serde_json::to_string(&amos).unwrap();
+        assert_eq!(serde_json::from_str::<AMOS>(&json).unwrap(), amos);
+        amos.set_ownership_override(&name, &parameter, Some(ParameterOwnership::Owned));
+        assert_eq!(amos.ownership_overrides.len(), 1);
+
+        amos.set_ownership_override(&name, &parameter, None);
+        assert_eq!(amos.ownership_override(&name, &parameter), None);
+    }
+
+    #[test]
+    fn suggested_query_operations_respond_with_the_touched_definitions() {
+        let parse_result = parse_open_api("./test/resources/rest-news.json");
+        let amos = open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+        let idx = |name: &str| {
+            amos.operations
+                .iter()
+                .position(|o| o.info.name == name)
+                .unwrap()
+        };
+        let names = |indices: Vec<usize>| -> Vec<std::string::String> {
+            indices
+                .into_iter()
+                .map(|idx| amos.operations[idx].info.name.clone())
+                .collect()
+        };
+
+        // Posting news touches the news, not the countries
+        let mut suggested = names(amos.suggested_query_operations(&[idx("createNewsUsingPOST")]));
+        suggested.sort();
+        assert_eq!(suggested, vec!["getNewsUsingGET", "getUsingGET_1"]);
+
+        // All operations that are not GET operations can mutate the news
+        assert_eq!(amos.mutating_operations().len(), 4);
+        assert_eq!(
+            amos.suggested_query_operations(&amos.mutating_operations())
+                .len(),
+            2
+        );
+
+        // Deleting news touches no definitions, any GET operation can observe it
+        assert_eq!(
+            amos.suggested_query_operations(&[idx("deleteUsingDELETE")])
+                .len(),
+            3
+        );
+    }
+
     #[test]
     fn load_errors_if_unable_to_deserialize_file() {
         let path = std::path::Path::new("./test/resources/not_json.txt");
@@ -832,6 +2182,39 @@ mod tests {
         assert_eq!(Error::LoadFileFailure, amos.unwrap_err())
     }
 
+    #[test]
+    fn precompile_regex_schemas_reports_invalid_patterns() {
+        let param = |name: &str, regex: &str| Parameter {
+            name: name.to_string(),
+            schema: StringRegex {
+                regex: RegexPattern::new(regex),
+            },
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "op".to_string(),
+                key: "operation/op".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![param("valid", "[a-z]+"), param("invalid", "(a")],
+            responses: vec![],
+            meta_data: None,
+        };
+
+        let messages = precompile_regex_schemas(&[op]);
+
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].message.contains("invalid"));
+    }
+
     #[test]
     fn schemas_can_display() {
         let schema = Schema::String;
@@ -845,19 +2228,22 @@ mod tests {
         let parse_result = oas_file_1();
         let op = &parse_result.operations[0];
 
-        let amos = open_api_v2_to_amos(&vec![op.clone()], &vec![]).amos;
+        let amos = open_api_v2_to_amos(std::slice::from_ref(op), &[]).amos;
 
         assert_eq!(
             amos.operations,
             vec![Operation {
                 info: OperationInfo {
                     name: "getAllProducts".to_string(),
-                    key: "operation/getAllProducts".to_string()
+                    key: "operation/getAllProducts".to_string(),
+                    alias: None,
+                    deprecated: false,
                 },
                 parameters: vec![],
                 responses: vec![Response {
                     name: "successful operation".to_string(),
-                    schema: ArrayOfString
+                    schema: ArrayOfString,
+                    status: Some(HTTPStatus::OK),
                 }],
                 meta_data: Some(HTTP {
                     url: "/products".to_string(),
@@ -872,14 +2258,16 @@ mod tests {
         let parse_result = oas_file_1();
         let op = &parse_result.operations[3];
 
-        let amos = open_api_v2_to_amos(&vec![op.clone()], &vec![]).amos;
+        let amos = open_api_v2_to_amos(std::slice::from_ref(op), &[]).amos;
 
         assert_eq!(
             amos.operations,
             vec![Operation {
                 info: OperationInfo {
                     name: "addProduct".to_string(),
-                    key: "operation/addProduct".to_string()
+                    key: "operation/addProduct".to_string(),
+                    alias: None,
+                    deprecated: false,
                 },
                 parameters: vec![Parameter {
                     name: "productName".to_string(),
@@ -888,11 +2276,15 @@ mod tests {
                     required: true,
                     meta_data: Some(ParameterMetaData::HTTP {
                         target: HTTPParameterTarget::Path
-                    })
+                    }),
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None
                 }],
                 responses: vec![Response {
                     name: "successful operation".to_string(),
-                    schema: Int
+                    schema: Int,
+                    status: Some(HTTPStatus::Default),
                 }],
                 meta_data: Some(HTTP {
                     url: "/products/{productName}".to_string(),
@@ -911,14 +2303,16 @@ mod tests {
             .find(|op| op.id == "addRequiresConstraintToProduct")
             .expect("Open API operation missing");
 
-        let amos = open_api_v2_to_amos(&vec![op.clone()], &[]).amos;
+        let amos = open_api_v2_to_amos(std::slice::from_ref(op), &[]).amos;
 
         assert_eq!(
             amos.operations,
             vec![Operation {
                 info: OperationInfo {
                     name: "addRequiresConstraintToProduct".to_string(),
-                    key: "operation/addRequiresConstraintToProduct".to_string()
+                    key: "operation/addRequiresConstraintToProduct".to_string(),
+                    alias: None,
+                    deprecated: false,
                 },
                 parameters: vec![
                     Parameter {
@@ -928,7 +2322,10 @@ mod tests {
                         ownership: ParameterOwnership::Dependency,
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Path
-                        })
+                        }),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "sourceFeature".to_string(),
@@ -937,7 +2334,10 @@ mod tests {
                         ownership: ParameterOwnership::Owned,
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::FormData
-                        })
+                        }),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "requiredFeature".to_string(),
@@ -946,12 +2346,16 @@ mod tests {
                         ownership: ParameterOwnership::Owned,
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::FormData
-                        })
+                        }),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     }
                 ],
                 responses: vec![Response {
                     name: "successful operation".to_string(),
-                    schema: Int
+                    schema: Int,
+                    status: Some(HTTPStatus::Default),
                 }],
                 meta_data: Some(HTTP {
                     url: "/products/{productName}/constraints/requires".to_string(),
@@ -966,14 +2370,16 @@ mod tests {
         let parse_result = oas_file_1();
         let op = &parse_result.operations[2];
 
-        let amos = open_api_v2_to_amos(&vec![op.clone()], &vec![]).amos;
+        let amos = open_api_v2_to_amos(std::slice::from_ref(op), &[]).amos;
 
         assert_eq!(
             amos.operations,
             vec![Operation {
                 info: OperationInfo {
                     name: "getProductByName".to_string(),
-                    key: "operation/getProductByName".to_string()
+                    key: "operation/getProductByName".to_string(),
+                    alias: None,
+                    deprecated: false,
                 },
                 parameters: vec![Parameter {
                     name: "productName".to_string(),
@@ -982,11 +2388,15 @@ mod tests {
                     ownership: ParameterOwnership::Dependency,
                     meta_data: Some(ParameterMetaData::HTTP {
                         target: HTTPParameterTarget::Path
-                    })
+                    }),
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None
                 }],
                 responses: vec![Response {
                     name: "successful operation".to_string(),
-                    schema: Reference("#/definitions/Product".to_string())
+                    schema: Reference("#/definitions/Product".to_string()),
+                    status: Some(HTTPStatus::OK),
                 }],
                 meta_data: Some(HTTP {
                     url: "/products/{productName}".to_string(),
@@ -1010,61 +2420,74 @@ mod tests {
                     kind: OpenAPIDataType::ArrayOfUniqueRefItems(
                         "#/definitions/FeatureConstraint".to_string(),
                     ),
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "features".to_string(),
                     kind: OpenAPIDataType::ArrayOfUniqueRefItems(
                         "#/definitions/Feature".to_string(),
                     ),
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "array_with_ref".to_string(),
                     kind: OpenAPIDataType::ArrayOfRefItems("foo".to_string()),
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "array_of_strings".to_string(),
                     kind: OpenAPIDataType::ArrayOfStrings,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "file".to_string(),
                     kind: OpenAPIDataType::File,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "id".to_string(),
                     kind: OpenAPIDataType::Integer64,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "name".to_string(),
                     kind: OpenAPIDataType::String,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "bool".to_string(),
                     kind: OpenAPIDataType::Boolean,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "float".to_string(),
                     kind: OpenAPIDataType::Float,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "number".to_string(),
                     kind: OpenAPIDataType::Number,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "IPV4".to_string(),
                     kind: OpenAPIDataType::IPV4,
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "schema".to_string(),
                     kind: OpenAPIDataType::Schema(open_api::Schema::ArrayOfString),
+                    examples: vec![],
                 },
                 OpenAPIProperty {
                     name: "unsupported".to_string(),
                     kind: OpenAPIDataType::Unsupported,
+                    examples: vec![],
                 },
             ]),
         };
 
-        let amos = open_api_v2_to_amos(&vec![], &vec![definition]).amos;
+        let amos = open_api_v2_to_amos(&[], &[definition]).amos;
 
         assert_eq!(
             amos.definitions,
@@ -1077,55 +2500,68 @@ mod tests {
                             name: "constraints".to_string(),
                             schema: ArrayOfUniqueRefItems(
                                 "#/definitions/FeatureConstraint".to_string()
-                            )
+                            ),
+                            examples: vec![],
                         },
                         Property {
                             name: "features".to_string(),
-                            schema: ArrayOfUniqueRefItems("#/definitions/Feature".to_string())
+                            schema: ArrayOfUniqueRefItems("#/definitions/Feature".to_string()),
+                            examples: vec![],
                         },
                         Property {
                             name: "array_with_ref".to_string(),
-                            schema: ArrayOfRefItems("foo".to_string())
+                            schema: ArrayOfRefItems("foo".to_string()),
+                            examples: vec![],
                         },
                         Property {
                             name: "array_of_strings".to_string(),
-                            schema: ArrayOfString
+                            schema: ArrayOfString,
+                            examples: vec![],
                         },
                         Property {
                             name: "file".to_string(),
-                            schema: File
+                            schema: File,
+                            examples: vec![],
                         },
                         Property {
                             name: "id".to_string(),
-                            schema: Int
+                            schema: Int,
+                            examples: vec![],
                         },
                         Property {
                             name: "name".to_string(),
-                            schema: String
+                            schema: String,
+                            examples: vec![],
                         },
                         Property {
                             name: "bool".to_string(),
-                            schema: Bool
+                            schema: Bool,
+                            examples: vec![],
                         },
                         Property {
                             name: "float".to_string(),
-                            schema: Float
+                            schema: Float,
+                            examples: vec![],
                         },
                         Property {
                             name: "number".to_string(),
-                            schema: Number
+                            schema: Number,
+                            examples: vec![],
                         },
                         Property {
                             name: "IPV4".to_string(),
-                            schema: IPV4
+                            schema: IPV4,
+                            examples: vec![],
                         },
                         Property {
                             name: "schema".to_string(),
-                            schema: ArrayOfString
+                            schema: ArrayOfString,
+                            examples: vec![],
                         },
                         Property {
                             name: "unsupported".to_string(),
-                            schema: Unsupported
+                            schema: Unsupported,
+                            examples: vec![],
                         }
                     ]
                 }
@@ -1166,6 +2602,8 @@ mod tests {
                 info: OperationInfo {
                     name: "createNewsUsingPOST".to_string(),
                     key: "operation/createNewsUsingPOST".to_string(),
+                    alias: None,
+                    deprecated: false,
                 },
                 parameters: vec![
                     Parameter {
@@ -1176,6 +2614,9 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "country".to_string(),
@@ -1185,6 +2626,9 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "creationTime".to_string(),
@@ -1194,6 +2638,9 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "id".to_string(),
@@ -1203,6 +2650,9 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "newsId".to_string(),
@@ -1212,6 +2662,9 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                     Parameter {
                         name: "text".to_string(),
@@ -1221,28 +2674,36 @@ mod tests {
                         meta_data: Some(ParameterMetaData::HTTP {
                             target: HTTPParameterTarget::Body,
                         },),
+                        examples: vec![],
+                        relation_hints: vec![],
+                        generator: None
                     },
                 ],
                 responses: vec![
                     Response {
                         name: "OK".to_string(),
                         schema: Int,
+                        status: Some(HTTPStatus::OK),
                     },
                     Response {
                         name: "Created".to_string(),
                         schema: Int,
+                        status: Some(HTTPStatus::Created),
                     },
                     Response {
                         name: "Unauthorized".to_string(),
                         schema: Int,
+                        status: Some(HTTPStatus::Unauthorized),
                     },
                     Response {
                         name: "Forbidden".to_string(),
                         schema: Int,
+                        status: Some(HTTPStatus::Forbidden),
                     },
                     Response {
                         name: "Not Found".to_string(),
                         schema: Int,
+                        status: Some(HTTPStatus::NotFound),
                     },
                 ],
                 meta_data: Some(HTTP {
@@ -1252,4 +2713,84 @@ mod tests {
             },)
         )
     }
+
+    #[test]
+    fn exported_open_api_translates_to_the_same_amos() {
+        for path in [
+            "./test/resources/feature-service.json",
+            "./test/resources/rest-ncs.json",
+            "./test/resources/rest-news.json",
+        ] {
+            let parse_result = parse_open_api(path);
+            let amos =
+                open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+
+            let exported = to_open_api(&amos);
+            let reparsed = open_api::parse_json_object(exported.as_object().unwrap()).unwrap();
+            let reimported = open_api_v2_to_amos(&reparsed.operations, &reparsed.definitions).amos;
+
+            assert_eq!(reimported.definitions, amos.definitions, "{}", path);
+            assert_eq!(
+                reimported.operations.len(),
+                amos.operations.len(),
+                "{}",
+                path
+            );
+            for operation in &amos.operations {
+                let other = reimported.find_operation(&operation.info.name).unwrap();
+                assert_eq!(other.parameters, operation.parameters, "{}", path);
+                assert_eq!(other.meta_data, operation.meta_data, "{}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn export_the_fields_of_a_body_as_one_object() {
+        let body_field = |name: &str, schema: Schema| Parameter {
+            name: name.to_string(),
+            schema,
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::Body,
+            }),
+            examples: vec!["7".to_string()],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let mut amos = AMOS::new();
+        amos.push_operation(Operation {
+            info: OperationInfo {
+                name: "post_persons".to_string(),
+                key: "operation/post_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![body_field("name", String), body_field("age", Int)],
+            responses: vec![],
+            meta_data: Some(HTTP {
+                url: "/persons".to_string(),
+                method: POST,
+            }),
+        });
+
+        let exported = to_open_api(&amos);
+        let parameters = &exported["paths"]["/persons"]["post"]["parameters"];
+        assert_eq!(
+            parameters,
+            &serde_json::json!([{
+                "name": "body",
+                "in": "body",
+                "required": true,
+                "schema": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "example": "7" },
+                        "age": { "type": "integer", "format": "int64", "example": "7" },
+                    },
+                    "required": ["name", "age"],
+                },
+            }])
+        );
+    }
 }
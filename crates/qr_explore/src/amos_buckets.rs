@@ -2,6 +2,7 @@ use crate::amos::Operation;
 use crate::amos::OperationMetaData::HTTP;
 use qr_http_resource::http::HTTPMethod;
 use std::collections::HashMap;
+use tracing::warn;
 
 #[derive(Debug, Clone)]
 pub enum BucketKind {
@@ -80,7 +81,13 @@ impl Buckets {
                                     (init_bucket, BucketKind::Update)
                                 }
                             }
-                            _ => panic!(),
+                            HTTPMethod::Unsupported => {
+                                warn!(
+                                    op.info.name,
+                                    "Operation of an unsupported method, not bucketed"
+                                );
+                                continue;
+                            }
                         };
 
                         items.push(BucketItem {
@@ -145,8 +152,20 @@ impl Buckets {
             .collect()
     }
 
+    /// A set-up chain for lengths beyond the hand tuned ones. Every position can
+    ///  create one nesting level deeper than the previous, starting at the root,
+    ///  the chain ends with a delete.
+    fn setup_chain(&self, bucket_len: u8) -> Vec<Vec<&BucketItem>> {
+        let mut chain = (1..bucket_len)
+            .map(|pos| self.find_create_operations_with_precedence(pos - 1, pos))
+            .collect::<Vec<Vec<&BucketItem>>>();
+        chain.push(self.find_delete_operations_with_precedence(2, bucket_len));
+
+        chain
+    }
+
     pub fn bucketize_for_state_identity(&self, bucket_len: u8) -> Vec<Vec<&BucketItem>> {
-        // state identity means at least 2, at most 5
+        // state identity means at least 2
         match bucket_len {
             2 => {
                 // Q(X) -> C(X) -> Q(X) -> D(X) -> Q(X)
@@ -188,7 +207,7 @@ impl Buckets {
                     self.find_delete_operations_with_precedence(4, 5),
                 ]
             }
-            _ => panic!("Unsupported bucket length"),
+            _ => self.setup_chain(bucket_len),
         }
     }
 }
@@ -198,12 +217,13 @@ pub fn bucketize_for_state_identity_strategy(
     bucket: &Buckets,
     bucket_len: u8,
 ) -> Vec<Vec<&BucketItem>> {
-    // state identity means at least 2, at most 5
+    // state identity means at least 2. The first create may create a
+    //  resource at the root, e.g. `POST /persons`.
     match bucket_len {
         2 => {
             // Q(X) -> C(X) -> Q(X) -> D(X) -> Q(X)
             vec![
-                bucket.find_create_operations_with_precedence(1, 1),
+                bucket.find_create_operations_with_precedence(0, 1),
                 bucket.find_delete_operations_with_precedence(2, 3),
                 vec![],
                 vec![],
@@ -213,7 +233,7 @@ pub fn bucketize_for_state_identity_strategy(
         3 => {
             // C(X) -> Q(Y) -> C(Y/X) -> Q(Y) -> D(Y/X) -> Q(X)
             vec![
-                bucket.find_create_operations_with_precedence(1, 1),
+                bucket.find_create_operations_with_precedence(0, 1),
                 bucket.find_create_operations_with_precedence(2, 2),
                 bucket.find_delete_operations_with_precedence(2, 4),
                 vec![],
@@ -223,7 +243,7 @@ pub fn bucketize_for_state_identity_strategy(
         4 => {
             // C(X) -> C(Y) -> Q(Y) -> U(Z/Y) -> Q(Y) -> D(Z/Y) -> Q(Y)
             vec![
-                bucket.find_create_operations_with_precedence(1, 1),
+                bucket.find_create_operations_with_precedence(0, 1),
                 bucket.find_create_operations_with_precedence(2, 2),
                 bucket.find_update_operations_with_precedence(2, 3),
                 bucket.find_delete_operations_with_precedence(4, 4),
@@ -233,14 +253,14 @@ pub fn bucketize_for_state_identity_strategy(
         5 => {
             // C(X) -> C(Y) -> Q(Y) -> U(Z/Y) -> Q(Y) -> D(Z/Y) -> Q(Y)
             vec![
-                bucket.find_create_operations_with_precedence(1, 1),
+                bucket.find_create_operations_with_precedence(0, 1),
                 bucket.find_create_operations_with_precedence(2, 2),
                 bucket.find_create_operations_with_precedence(2, 3),
                 bucket.find_create_operations_with_precedence(3, 3),
                 bucket.find_delete_operations_with_precedence(4, 5),
             ]
         }
-        _ => panic!("Unsupported bucket length"),
+        _ => bucket.setup_chain(bucket_len),
     }
 }
 
@@ -248,7 +268,7 @@ pub fn bucketize_for_state_identity_update_strategy(
     bucket: &Buckets,
     bucket_len: u8,
 ) -> Vec<Vec<&BucketItem>> {
-    // state identity means at least 2, at most 5
+    // state identity means at least 2
     match bucket_len {
         2 => {
             //panic!("To short sequence for update strategy");
@@ -290,6 +310,81 @@ pub fn bucketize_for_state_identity_update_strategy(
                 bucket.find_delete_operations_with_precedence(2, 5),
             ]
         }
-        _ => panic!("Unsupported bucket length"),
+        _ => {
+            // C(X) -> .. -> C(Y) -> U(Z/Y) -> D(Z/Y)
+            let mut chain = bucket.setup_chain(bucket_len);
+            let update_pos = chain.len() - 2;
+            chain[update_pos] = bucket.find_update_operations_with_precedence(2, bucket_len - 1);
+
+            chain
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::{Operation, OperationInfo, OperationMetaData};
+    use crate::amos_buckets as sut;
+    use qr_http_resource::http::HTTPMethod;
+
+    fn op(name: &str, method: HTTPMethod, url: &str) -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        }
+    }
+
+    fn names(bucket: &[&sut::BucketItem]) -> Vec<String> {
+        bucket.iter().map(|item| item.name.clone()).collect()
+    }
+
+    #[test]
+    fn state_identity_first_create_at_the_root() {
+        let buckets = sut::Buckets::new(&[
+            op("create_person", HTTPMethod::POST, "/persons"),
+            op("add_person", HTTPMethod::POST, "/persons/{id}"),
+            op("get_person", HTTPMethod::GET, "/persons/{id}"),
+            op("delete_person", HTTPMethod::DELETE, "/persons/{id}"),
+        ]);
+
+        for bucket_len in 2..=5 {
+            let chain = sut::bucketize_for_state_identity_strategy(&buckets, bucket_len);
+
+            assert_eq!(
+                names(&chain[0]),
+                vec!["create_person", "add_person"],
+                "length {bucket_len}"
+            );
+        }
+    }
+
+    #[test]
+    fn setup_chain_starts_at_the_root() {
+        let mut url = String::new();
+        let mut operations = vec![op("create_0", HTTPMethod::POST, "/r0")];
+        for level in 1..=5 {
+            url = format!("{url}/r{level}/{{id{level}}}");
+            operations.push(op(&format!("create_{level}"), HTTPMethod::POST, &url));
+        }
+        operations.push(op("delete", HTTPMethod::DELETE, "/r1/{id1}"));
+        let buckets = sut::Buckets::new(&operations);
+
+        let chain = sut::bucketize_for_state_identity_strategy(&buckets, 6);
+
+        assert_eq!(chain.len(), 6);
+        assert_eq!(names(&chain[0]), vec!["create_0", "create_1"]);
+        assert_eq!(names(&chain[1]), vec!["create_1", "create_2"]);
+        assert_eq!(names(&chain[4]), vec!["create_4", "create_5"]);
+        assert_eq!(names(&chain[5]), vec!["delete"]);
     }
 }
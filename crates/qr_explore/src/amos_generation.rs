@@ -1,8 +1,19 @@
-use crate::amos::{self, Operation, Parameter, Schema};
+use crate::amos::{self, GeneratorSpec, Operation, Parameter, Schema};
 use crate::amos_buckets::{BucketItem, Buckets};
 use crate::amos_relations::{self, Relation};
+use crate::amos_result::SharedCoverage;
+use crate::campaign::SharedCampaign;
+use crate::corpus::SharedCorpus;
+use crate::exploration_settings::{OperationWeights, ShrinkStrategy};
+use crate::injection;
+use crate::mutation::SharedMutation;
+use crate::precedence::{PrecedenceLearning, SharedPrecedence};
 use proptest::prelude::*;
+use proptest::strategy::NewTree;
+use proptest::test_runner::TestRunner;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 use tracing::warn;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -76,6 +87,48 @@ impl ParameterValue {
         }
     }
 
+    /// The value with the seed and activation, for the variants that have them
+    pub fn with_seed(self, seed: i32, active: bool) -> Self {
+        match self {
+            ParameterValue::StringValue { value, .. } => ParameterValue::StringValue {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::IntValue { value, .. } => ParameterValue::IntValue {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::BoolValue { value, .. } => ParameterValue::BoolValue {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::DoubleValue { value, .. } => ParameterValue::DoubleValue {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::IPV4Value { value, .. } => ParameterValue::IPV4Value {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::ArrayOfString { value, .. } => ParameterValue::ArrayOfString {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::File { value, .. } => ParameterValue::File {
+                value,
+                seed,
+                active,
+            },
+            ParameterValue::Reference { .. } | ParameterValue::Empty => self,
+        }
+    }
+
     pub fn active(&self) -> bool {
         match self {
             ParameterValue::StringValue { active, .. } => *active,
@@ -105,315 +158,1322 @@ pub struct GeneratedOperation {
     pub parameters: Vec<GeneratedParameter>,
 }
 
-pub type GenerationOperationWithParameters = (Operation, [ParameterValue; 10]);
+pub type GenerationOperationWithParameters = (Operation, Vec<ParameterValue>);
 
-// EXPERIMENT: make a path UI -> gen
-pub fn gen_banana_cake_value(expr: String) -> BoxedStrategy<ParameterValue> {
-    let regex = proptest::string::string_regex(&expr).unwrap();
+type RegexStrategy = SBoxedStrategy<String>;
 
-    (regex, (1..10i32), proptest::bool::weighted(0.5))
-        .prop_map(|(s, seed, active)| ParameterValue::StringValue {
-            value: s,
-            seed,
-            active,
+/// A regex pattern of the AMOS and the strategy of its strings. The strategy
+///  is compiled on first use and kept with the pattern, shared by its clones,
+///  a pattern that fails to compile keeps its error.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct RegexPattern {
+    pattern: String,
+    strategy: Arc<OnceLock<Result<RegexStrategy, String>>>,
+}
+
+impl RegexPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        RegexPattern {
+            pattern: pattern.into(),
+            strategy: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Compile the strategy of the pattern, if not already compiled
+    pub fn compile(&self) -> Result<(), String> {
+        self.compiled().as_ref().map(|_| ()).map_err(String::clone)
+    }
+
+    fn compiled(&self) -> &Result<RegexStrategy, String> {
+        self.strategy.get_or_init(|| {
+            proptest::string::string_regex(&self.pattern)
+                .map(Strategy::sboxed)
+                .map_err(|e| {
+                    warn!(
+                        "Invalid regex '{}', generating plain strings: {e}",
+                        self.pattern
+                    );
+                    e.to_string()
+                })
         })
-        .boxed()
+    }
+
+    fn strategy(&self) -> Option<RegexStrategy> {
+        self.compiled().as_ref().ok().cloned()
+    }
+}
+
+impl From<String> for RegexPattern {
+    fn from(pattern: String) -> Self {
+        RegexPattern::new(pattern)
+    }
+}
+
+impl From<RegexPattern> for String {
+    fn from(regex: RegexPattern) -> Self {
+        regex.pattern
+    }
+}
+
+impl PartialEq for RegexPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl std::fmt::Debug for RegexPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.pattern)
+    }
+}
+
+impl std::fmt::Display for RegexPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
 }
 
 fn gen_parameter_value(t: Option<&Parameter>) -> BoxedStrategy<ParameterValue> {
     match t {
-        Some(tt) => {
-            let ref_weight = match tt.ownership {
-                amos::ParameterOwnership::Owned => 0.95,
-                amos::ParameterOwnership::Dependency => 0.95,
-                amos::ParameterOwnership::Unknown => 0.5,
-            };
+        Some(
+            tt @ Parameter {
+                generator: Some(generator),
+                ..
+            },
+        ) => gen_override_value(tt, generator),
+        Some(tt) => match gen_example_value(tt) {
+            // Half of the values are picked from the examples, the schema still
+            //  generates values that were never seen. The examples come first,
+            //  values of the schema shrink to the first example.
+            Some(examples) => prop_oneof![examples, gen_schema_value(tt)].boxed(),
+            None => gen_schema_value(tt),
+        },
+        None => Just(ParameterValue::Empty).boxed(),
+    }
+}
 
-            match &tt.schema {
-                Schema::StringRegex { regex } => {
-                    // TODO: would be strongly prefered if we check and store the regex
-                    //  at entry/parse time, not at generation time
-                    let regex = proptest::string::string_regex(regex).unwrap();
-
-                    (regex, (1..10i32), proptest::bool::weighted(ref_weight))
-                        .prop_map(|(s, seed, active)| ParameterValue::StringValue {
-                            value: s,
-                            seed,
-                            active,
-                        })
-                        .boxed()
-                }
-                Schema::String => ("[a-z]*", (1..10i32), proptest::bool::weighted(ref_weight))
-                    .prop_map(|(s, seed, active)| ParameterValue::StringValue {
-                        value: s,
-                        seed,
-                        active,
+/// The values of the user defined generator, or of the schema when the
+///  generator has none
+fn gen_override_value(tt: &Parameter, generator: &GeneratorSpec) -> BoxedStrategy<ParameterValue> {
+    let string_value = |value| ParameterValue::StringValue {
+        value,
+        seed: 0,
+        active: false,
+    };
+
+    let values = match generator {
+        GeneratorSpec::Regex { regex } => match regex.strategy() {
+            Some(strategy) => strategy.prop_map(string_value).boxed(),
+            None => return gen_schema_value(tt),
+        },
+        GeneratorSpec::Enum { values } => {
+            let values = values
+                .iter()
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .filter_map(|value| example_value(&tt.schema, value))
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                return gen_schema_value(tt);
+            }
+            proptest::sample::select(values).boxed()
+        }
+        GeneratorSpec::Range { min, max } => {
+            let (min, max) = (*min.min(max), *min.max(max));
+            match tt.schema {
+                Schema::Double | Schema::Float | Schema::Number => (min as f64..=max as f64)
+                    .prop_map(|value| ParameterValue::DoubleValue {
+                        value,
+                        seed: 0,
+                        active: false,
                     })
                     .boxed(),
-                Schema::StringNonEmpty => {
-                    ("[a-z]+", (1..10i32), proptest::bool::weighted(ref_weight))
-                        .prop_map(|(s, seed, active)| ParameterValue::StringValue {
-                            value: s,
-                            seed,
-                            active,
-                        })
-                        .boxed()
-                }
-                Schema::Int8 => (0..256i64, (1..10i32), proptest::bool::weighted(ref_weight))
-                    .prop_map(|(i, seed, active)| ParameterValue::IntValue {
-                        value: i,
-                        seed,
-                        active,
+                _ => (min..=max)
+                    .prop_map(|value| ParameterValue::IntValue {
+                        value,
+                        seed: 0,
+                        active: false,
                     })
                     .boxed(),
-                Schema::Int => (
-                    //0..u64::MAX,
-                    //-1000..1000,
-                    prop_oneof![
-                        8 => -1000i64..=1000i64,
-                        1 => i64::MIN..-1000,
-                        1 => 1000..i64::MAX,
-                    ],
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::IntValue {
-                        value: i,
-                        seed,
-                        active,
-                    })
+            }
+        }
+        GeneratorSpec::Large { max_length } => {
+            // Mostly close to the max, shrinking towards the shortest that fails
+            let max_length = *max_length;
+            let lengths = prop_oneof![
+                3 => (max_length / 2)..=max_length,
+                1 => 0..=max_length,
+            ];
+            match tt.schema {
+                Schema::String | Schema::StringNonEmpty | Schema::StringRegex { .. } => lengths
+                    .prop_map(move |length| string_value("x".repeat(length.max(1))))
                     .boxed(),
-
-                Schema::Int32 => (
-                    //0..u32::MAX as u64,
-                    //-1000..1000,
-                    prop_oneof![
-                        8 => -1000..=1000,
-                        1 => i32::MIN..-1000,
-                        1 => 1000..i32::MAX,
-                    ],
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::IntValue {
-                        value: i as i64,
-                        seed,
-                        active,
+                Schema::ArrayOfString => lengths
+                    .prop_map(|length| ParameterValue::ArrayOfString {
+                        value: vec!["x".to_string(); length],
+                        seed: 0,
+                        active: false,
                     })
                     .boxed(),
+                _ => return gen_schema_value(tt),
+            }
+        }
+        GeneratorSpec::Unicode => match tt.schema {
+            Schema::String | Schema::StringNonEmpty | Schema::StringRegex { .. } => {
+                unicode_strings().prop_map(string_value).boxed()
+            }
+            _ => return gen_schema_value(tt),
+        },
+        // Dictionaries are read into values by `GeneratorOverrides::apply`
+        GeneratorSpec::Dictionary { path } => {
+            warn!("Dictionary '{}' was not read, generating the schema", path);
+            return gen_schema_value(tt);
+        }
+    };
 
-                Schema::Double => (
-                    // TODO: What's a 'good' default range?
-                    //   also, enable this to be configured
-                    //f64::MIN..f64::MAX,
-                    //-100.0..100.0,
-                    prop_oneof![
-                        8 => -100.0..=100.0,
-                        1 => f64::MIN..-100.0,
-                        1 => 100.0..f64::MAX,
-                    ],
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::DoubleValue {
-                        value: i,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
+    (
+        values,
+        (1..10i32),
+        proptest::bool::weighted(reference_weight(&tt.ownership)),
+    )
+        .prop_map(|(value, seed, active)| value.with_seed(seed, active))
+        .boxed()
+}
 
-                Schema::Float => (
-                    (f32::MIN..f32::MAX),
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::DoubleValue {
-                        value: i as f64,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
+/// Text the encodings of a SUT tend to get wrong: emoji with modifiers and
+///  joiners, CJK, right-to-left scripts and overrides, combining characters,
+///  byte order marks, null bytes and the delimiters of URLs
+const UNICODE_FRAGMENTS: &[&str] = &[
+    "😀",
+    "👍🏽",
+    "👩‍👩‍👧",
+    "🇸🇪",
+    "日本語",
+    "中文",
+    "한국어",
+    "עברית",
+    "العربية",
+    "\u{202E}",
+    "e\u{0301}",
+    "Å",
+    "ß",
+    "İ",
+    "\u{FEFF}",
+    "\u{0000}",
+    "/",
+    "?",
+    "#",
+    "%",
+    "&",
+    " ",
+];
+
+/// Non-empty strings of the fragments, and of any other characters
+fn unicode_strings() -> BoxedStrategy<String> {
+    prop::collection::vec(
+        prop_oneof![
+            3 => proptest::sample::select(UNICODE_FRAGMENTS).prop_map(str::to_string),
+            1 => proptest::char::any().prop_map(String::from),
+        ],
+        1..8,
+    )
+    .prop_map(|fragments| fragments.concat())
+    .boxed()
+}
 
-                Schema::Bool => (
-                    proptest::bool::ANY,
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::BoolValue {
-                        value: i,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
+fn reference_weight(ownership: &amos::ParameterOwnership) -> f64 {
+    match ownership {
+        amos::ParameterOwnership::Owned => 0.95,
+        amos::ParameterOwnership::Dependency => 0.95,
+        amos::ParameterOwnership::Unknown => 0.5,
+    }
+}
 
-                Schema::IPV4 => (
-                    (
-                        proptest::num::u8::ANY,
-                        proptest::num::u8::ANY,
-                        proptest::num::u8::ANY,
-                        proptest::num::u8::ANY,
-                    ),
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::IPV4Value {
-                        value: i,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
+/// Pick one of the examples of the parameter, the examples that cannot be
+///  read as a value of its schema are skipped
+fn gen_example_value(tt: &Parameter) -> Option<BoxedStrategy<ParameterValue>> {
+    let values = tt
+        .examples
+        .iter()
+        .filter_map(|example| example_value(&tt.schema, example))
+        .collect::<Vec<_>>();
+
+    if values.is_empty() {
+        return None;
+    }
 
-                Schema::File => (
-                    proptest::num::u8::ANY,
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::File {
-                        value: i,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
+    Some(
+        (
+            proptest::sample::select(values),
+            (1..10i32),
+            proptest::bool::weighted(reference_weight(&tt.ownership)),
+        )
+            .prop_map(|(value, seed, active)| value.with_seed(seed, active))
+            .boxed(),
+    )
+}
 
-                Schema::ArrayOfString => (
-                    prop::collection::vec("[a-z]*", 0..10),
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(i, seed, active)| ParameterValue::ArrayOfString {
-                        value: i,
-                        seed,
-                        active,
-                    })
-                    .boxed(),
-                Schema::StringDateTime => (
-                    0..10000,
-                    1..13,
-                    1..32,
-                    0..24,
-                    0..60,
-                    0..60,
-                    (1..10i32),
-                    proptest::bool::weighted(ref_weight),
-                )
-                    .prop_map(|(y, m, d, h, min, sec, seed, active)| {
-                        // https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
-                        // date-time       = full-date "T" full-time
-                        // for example, 2017-07-21T17:32:28Z
-                        let date_str =
-                            format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", y, m, d, h, min, sec);
-                        ParameterValue::StringValue {
-                            value: date_str,
-                            seed,
-                            active,
-                        }
-                    })
-                    .boxed(),
-                Schema::Reference(_)
-                | Schema::Object { .. }
-                | Schema::DateTime
-                | Schema::ArrayOfUniqueRefItems(_)
-                | Schema::ArrayOfRefItems(_)
-                | Schema::Number => {
-                    warn!(
-                        "Generating with undecided schema, parameter: {}:{}",
-                        tt.name, tt.schema
-                    );
-                    Just(ParameterValue::Empty).boxed()
+/// The value of an example of the schema, with its seed and activation unset
+pub(crate) fn example_value(schema: &Schema, example: &str) -> Option<ParameterValue> {
+    let value = match schema {
+        Schema::String | Schema::StringDateTime | Schema::StringRegex { .. } => {
+            ParameterValue::StringValue {
+                value: example.to_string(),
+                seed: 0,
+                active: false,
+            }
+        }
+        Schema::StringNonEmpty if !example.is_empty() => ParameterValue::StringValue {
+            value: example.to_string(),
+            seed: 0,
+            active: false,
+        },
+        Schema::Int | Schema::Int8 | Schema::Int32 => ParameterValue::IntValue {
+            value: example.parse().ok()?,
+            seed: 0,
+            active: false,
+        },
+        Schema::Double | Schema::Float | Schema::Number => ParameterValue::DoubleValue {
+            value: example.parse().ok()?,
+            seed: 0,
+            active: false,
+        },
+        Schema::Bool => ParameterValue::BoolValue {
+            value: example.parse().ok()?,
+            seed: 0,
+            active: false,
+        },
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn gen_schema_value(tt: &Parameter) -> BoxedStrategy<ParameterValue> {
+    let ref_weight = reference_weight(&tt.ownership);
+
+    match &tt.schema {
+        Schema::StringRegex { regex } => {
+            // Invalid patterns are reported when the AMOS is created, here
+            //  they fall back to plain strings
+            let strings = match regex.strategy() {
+                Some(strategy) => strategy.boxed(),
+                None => "[a-z]*".boxed(),
+            };
+
+            (strings, (1..10i32), proptest::bool::weighted(ref_weight))
+                .prop_map(|(s, seed, active)| ParameterValue::StringValue {
+                    value: s,
+                    seed,
+                    active,
+                })
+                .boxed()
+        }
+        Schema::String => ("[a-z]*", (1..10i32), proptest::bool::weighted(ref_weight))
+            .prop_map(|(s, seed, active)| ParameterValue::StringValue {
+                value: s,
+                seed,
+                active,
+            })
+            .boxed(),
+        Schema::StringNonEmpty => ("[a-z]+", (1..10i32), proptest::bool::weighted(ref_weight))
+            .prop_map(|(s, seed, active)| ParameterValue::StringValue {
+                value: s,
+                seed,
+                active,
+            })
+            .boxed(),
+        Schema::Int8 => (0..256i64, (1..10i32), proptest::bool::weighted(ref_weight))
+            .prop_map(|(i, seed, active)| ParameterValue::IntValue {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+        Schema::Int => (
+            //0..u64::MAX,
+            //-1000..1000,
+            prop_oneof![
+                8 => -1000i64..=1000i64,
+                1 => i64::MIN..-1000,
+                1 => 1000..i64::MAX,
+            ],
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::IntValue {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::Int32 => (
+            //0..u32::MAX as u64,
+            //-1000..1000,
+            prop_oneof![
+                8 => -1000..=1000,
+                1 => i32::MIN..-1000,
+                1 => 1000..i32::MAX,
+            ],
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::IntValue {
+                value: i as i64,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::Double => (
+            // TODO: What's a 'good' default range?
+            //   also, enable this to be configured
+            //f64::MIN..f64::MAX,
+            //-100.0..100.0,
+            prop_oneof![
+                8 => -100.0..=100.0,
+                1 => f64::MIN..-100.0,
+                1 => 100.0..f64::MAX,
+            ],
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::DoubleValue {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::Float => (
+            (f32::MIN..f32::MAX),
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::DoubleValue {
+                value: i as f64,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::Bool => (
+            proptest::bool::ANY,
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::BoolValue {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::IPV4 => (
+            (
+                proptest::num::u8::ANY,
+                proptest::num::u8::ANY,
+                proptest::num::u8::ANY,
+                proptest::num::u8::ANY,
+            ),
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::IPV4Value {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::File => (
+            proptest::num::u8::ANY,
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::File {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+
+        Schema::ArrayOfString => (
+            prop::collection::vec("[a-z]*", 0..10),
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(i, seed, active)| ParameterValue::ArrayOfString {
+                value: i,
+                seed,
+                active,
+            })
+            .boxed(),
+        Schema::StringDateTime => (
+            0..10000,
+            1..13,
+            1..32,
+            0..24,
+            0..60,
+            0..60,
+            (1..10i32),
+            proptest::bool::weighted(ref_weight),
+        )
+            .prop_map(|(y, m, d, h, min, sec, seed, active)| {
+                // https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
+                // date-time       = full-date "T" full-time
+                // for example, 2017-07-21T17:32:28Z
+                let date_str =
+                    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", y, m, d, h, min, sec);
+                ParameterValue::StringValue {
+                    value: date_str,
+                    seed,
+                    active,
+                }
+            })
+            .boxed(),
+        Schema::Reference(_)
+        | Schema::Object { .. }
+        | Schema::DateTime
+        | Schema::ArrayOfUniqueRefItems(_)
+        | Schema::ArrayOfRefItems(_)
+        | Schema::Number => {
+            warn!(
+                "Generating with undecided schema, parameter: {}:{}",
+                tt.name, tt.schema
+            );
+            Just(ParameterValue::Empty).boxed()
+        }
+        Schema::Unsupported => {
+            warn!("Generating with unsupported schema, parameter: {}", tt.name);
+            Just(ParameterValue::Empty).boxed()
+        }
+    }
+}
+
+/// How many simplifications of a previewed value are kept
+const PREVIEW_SHRINK_STEPS: usize = 32;
+
+/// A generated value and the values it shrinks to, from the first
+///  simplification to the simplest
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValuePreview {
+    pub value: ParameterValue,
+    pub shrunk: Vec<ParameterValue>,
+}
+
+/// Generate `count` values of the parameter the way an exploration does. The
+///  value is shrunk as if every simplification still failed, the same seed
+///  previews the same values.
+pub fn preview_parameter_values(param: &Parameter, count: usize, seed: u64) -> Vec<ValuePreview> {
+    let mut runner = crate::explore::seeded_runner(seed).into_inner();
+    let strategy = gen_parameter_value(Some(param));
+
+    (0..count)
+        .filter_map(|_| {
+            let mut tree = strategy.new_tree(&mut runner).ok()?;
+            let value = tree.current();
+
+            let mut shrunk = vec![];
+            while shrunk.len() < PREVIEW_SHRINK_STEPS && tree.simplify() {
+                shrunk.push(tree.current());
+            }
+
+            Some(ValuePreview { value, shrunk })
+        })
+        .collect()
+}
+
+/// One strategy per parameter, shrinking each parameter value in place
+pub fn gen_param_array(params: &[Parameter]) -> BoxedStrategy<Vec<ParameterValue>> {
+    params
+        .iter()
+        .map(|param| gen_parameter_value(Some(param)))
+        .collect::<Vec<_>>()
+        .boxed()
+}
+
+/// Same as `gen_param_array`, but half of the string values are attack
+///  payloads of the injection corpus
+pub fn gen_injection_param_array(params: &[Parameter]) -> BoxedStrategy<Vec<ParameterValue>> {
+    params
+        .iter()
+        .map(|param| match param.schema {
+            Schema::String
+            | Schema::StringNonEmpty
+            | Schema::StringDateTime
+            | Schema::StringRegex { .. } => {
+                prop_oneof![gen_injection_value(param), gen_parameter_value(Some(param))].boxed()
+            }
+            _ => gen_parameter_value(Some(param)),
+        })
+        .collect::<Vec<_>>()
+        .boxed()
+}
+
+/// Sample the values harvested from the responses of the run. The corpus is
+///  read when a new value is generated, hence the samples grow as the run
+///  progresses. Half of the values are still generated for the parameter.
+#[derive(Debug)]
+struct HarvestedValue {
+    param: Parameter,
+    corpus: SharedCorpus,
+}
+
+impl Strategy for HarvestedValue {
+    type Tree = Box<dyn proptest::strategy::ValueTree<Value = ParameterValue>>;
+    type Value = ParameterValue;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let values = self.corpus.lock().unwrap().values(&self.param);
+        if values.is_empty() {
+            return gen_parameter_value(Some(&self.param)).new_tree(runner);
+        }
+
+        let harvested = (
+            proptest::sample::select(values),
+            (1..10i32),
+            proptest::bool::weighted(reference_weight(&self.param.ownership)),
+        )
+            .prop_map(|(value, seed, active)| value.with_seed(seed, active));
+
+        prop_oneof![harvested, gen_parameter_value(Some(&self.param))]
+            .boxed()
+            .new_tree(runner)
+    }
+}
+
+/// Same as `gen_param_array`, but the values are also sampled from the
+///  corpus. Parameters with a user defined generator keep to it.
+pub fn gen_harvested_param_array(
+    params: &[Parameter],
+    corpus: &SharedCorpus,
+) -> BoxedStrategy<Vec<ParameterValue>> {
+    params
+        .iter()
+        .map(|param| match param.generator {
+            Some(_) => gen_parameter_value(Some(param)),
+            None => HarvestedValue {
+                param: param.clone(),
+                corpus: corpus.clone(),
+            }
+            .boxed(),
+        })
+        .collect::<Vec<_>>()
+        .boxed()
+}
+
+fn gen_injection_value(param: &Parameter) -> BoxedStrategy<ParameterValue> {
+    (
+        proptest::sample::select(injection::payloads()),
+        (1..10i32),
+        proptest::bool::weighted(reference_weight(&param.ownership)),
+    )
+        .prop_map(|(value, seed, active)| ParameterValue::StringValue {
+            value,
+            seed,
+            active,
+        })
+        .boxed()
+}
+
+fn gen_operation(ops: Vec<Operation>, weights: &OperationWeights) -> BoxedStrategy<Operation> {
+    debug_assert!(!ops.is_empty(), "Operations to select from cannot be empty");
+
+    let op_weights = ops
+        .iter()
+        .map(|op| u64::from(weights.weight(&op.info.name)))
+        .collect::<Vec<u64>>();
+    let total = total_weight(&op_weights);
+
+    // Keep the uniform selection when the weights make no difference
+    if total == 0 || op_weights.iter().all(|w| *w == op_weights[0]) {
+        (0..ops.len()).prop_map(move |idx| ops[idx].clone()).boxed()
+    } else {
+        (0..total)
+            .prop_map(move |pick| ops[weighted_index(&op_weights, pick)].clone())
+            .boxed()
+    }
+}
+
+/// The sum of the weights, wide enough that no configured weights overflow
+fn total_weight(weights: &[u64]) -> u128 {
+    weights.iter().map(|w| u128::from(*w)).sum()
+}
+
+/// The index of the weight that `pick` falls into, where `pick` is in the
+///  range of the sum of all weights
+fn weighted_index(weights: &[u64], pick: u128) -> usize {
+    let mut remaining = pick;
+    for (idx, weight) in weights.iter().enumerate() {
+        let weight = u128::from(*weight);
+        if remaining < weight {
+            return idx;
+        }
+        remaining -= weight;
+    }
+
+    weights.len() - 1
+}
+
+/// Select an operation weighted by the current coverage of the run. The
+///  weights are read when a new value is generated, hence the selection
+///  follows the coverage as the run progresses.
+#[derive(Debug)]
+struct CoverageGuidedOperation {
+    ops: Vec<Operation>,
+    weights: OperationWeights,
+    coverage: SharedCoverage,
+}
+
+impl Strategy for CoverageGuidedOperation {
+    type Tree = Just<Operation>;
+    type Value = Operation;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        debug_assert!(
+            !self.ops.is_empty(),
+            "Operations to select from cannot be empty"
+        );
+
+        let mut op_weights = {
+            let coverage = self.coverage.lock().unwrap();
+            self.ops
+                .iter()
+                .map(|op| {
+                    u64::from(coverage.weight(op)) * u64::from(self.weights.weight(&op.info.name))
+                })
+                .collect::<Vec<u64>>()
+        };
+
+        // All operations configured to never be selected, select uniformly
+        if op_weights.iter().all(|w| *w == 0) {
+            op_weights.fill(1);
+        }
+
+        let pick = runner.rng().gen_range(0..total_weight(&op_weights));
+
+        // Shrinking the choice of operation has no meaning as the weights
+        //  change over the run, so do not try
+        Ok(Just(self.ops[weighted_index(&op_weights, pick)].clone()))
+    }
+}
+
+pub fn gen_operation_with_params(
+    ops: Vec<Operation>,
+) -> impl Strategy<Value = (Operation, Vec<ParameterValue>)> {
+    gen_weighted_operation_with_params(ops, &OperationWeights::new())
+}
+
+pub fn gen_weighted_operation_with_params(
+    ops: Vec<Operation>,
+    weights: &OperationWeights,
+) -> impl Strategy<Value = (Operation, Vec<ParameterValue>)> {
+    gen_operation(ops, weights)
+        .prop_flat_map(|op| (Just(op.clone()), gen_param_array(&op.parameters)))
+}
+
+pub fn gen_coverage_guided_operation_with_params(
+    ops: Vec<Operation>,
+    weights: &OperationWeights,
+    coverage: SharedCoverage,
+) -> impl Strategy<Value = (Operation, Vec<ParameterValue>)> {
+    CoverageGuidedOperation {
+        ops,
+        weights: weights.clone(),
+        coverage,
+    }
+    .prop_flat_map(|op| (Just(op.clone()), gen_param_array(&op.parameters)))
+}
+
+pub fn gen_operation_sequence(
+    ops: Vec<Operation>,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
+    // TODO: set the 0..5 via config
+    prop::collection::vec(gen_operation_with_params(ops), 0..5)
+}
+
+// NOTE: We don't need a Vec here, but that is the current upstream interface
+pub fn gen_static_operation_with_params(
+    op: Operation,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    (Just(op.clone()), gen_param_array(&op.parameters)).prop_map(|op| (0, vec![op]))
+}
+
+/// Same as `gen_static_operation_with_params`, with attack payloads mixed into
+///  the string parameters
+pub fn gen_static_injection_operation_with_params(
+    op: Operation,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    (Just(op.clone()), gen_injection_param_array(&op.parameters)).prop_map(|op| (0, vec![op]))
+}
+
+pub fn gen_operation_sequence_with_pinned(
+    pinned_op: Operation,
+    ops: Vec<Operation>,
+    min: u8,
+    max: u8,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
+    gen_sequence_with_pinned(
+        pinned_op,
+        gen_operation_with_params(ops),
+        min,
+        max,
+        ShrinkStrategy::LengthFirst,
+    )
+}
+
+fn gen_sequence_with_pinned(
+    pinned_op: Operation,
+    op_gen: impl Strategy<Value = (Operation, Vec<ParameterValue>)> + 'static,
+    min: u8,
+    max: u8,
+    shrink: ShrinkStrategy,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
+    gen_sequence_with_pinned_ops(vec![pinned_op], op_gen, min, max, shrink)
+}
+
+fn gen_sequence_with_pinned_ops(
+    pinned_ops: Vec<Operation>,
+    op_gen: impl Strategy<Value = (Operation, Vec<ParameterValue>)> + 'static,
+    min: u8,
+    max: u8,
+    shrink: ShrinkStrategy,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
+    let pinned = pinned_ops
+        .into_iter()
+        .map(|op| {
+            let params = gen_param_array(&op.parameters);
+            (op, params)
+        })
+        .collect();
+    let seq_gen = gen_sequence(op_gen, min, max, shrink);
+    gen_sequence_with_pinned_params(pinned, seq_gen)
+}
+
+/// Sequences of the generated operations, shrunk in the order of the strategy
+fn gen_sequence<S>(
+    op_gen: S,
+    min: u8,
+    max: u8,
+    shrink: ShrinkStrategy,
+) -> BoxedStrategy<Vec<S::Value>>
+where
+    S: Strategy + 'static,
+{
+    let (min, max) = (min as usize, max as usize);
+    match shrink {
+        ShrinkStrategy::LengthFirst => prop::collection::vec(op_gen, min..=max).boxed(),
+        ShrinkStrategy::ValueFirst => ValueFirstVec {
+            element: op_gen,
+            min,
+            max,
+        }
+        .boxed(),
+    }
+}
+
+/// Same as `prop::collection::vec`, but every element is simplified before
+///  any element is removed
+#[derive(Debug)]
+struct ValueFirstVec<S> {
+    element: S,
+    min: usize,
+    max: usize,
+}
+
+impl<S: Strategy> Strategy for ValueFirstVec<S> {
+    type Tree = ValueFirstVecTree<S::Tree>;
+    type Value = Vec<S::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let len = runner.rng().gen_range(self.min..=self.max.max(self.min));
+        let elements = (0..len)
+            .map(|_| self.element.new_tree(runner))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ValueFirstVecTree {
+            included: vec![true; elements.len()],
+            elements,
+            min: self.min,
+            next: ValueFirstStep::Simplify(0),
+            prev: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ValueFirstStep {
+    Simplify(usize),
+    Remove(usize),
+}
+
+struct ValueFirstVecTree<T> {
+    elements: Vec<T>,
+    included: Vec<bool>,
+    min: usize,
+    next: ValueFirstStep,
+    prev: Option<ValueFirstStep>,
+}
+
+impl<T: proptest::strategy::ValueTree> proptest::strategy::ValueTree for ValueFirstVecTree<T> {
+    type Value = Vec<T::Value>;
+
+    fn current(&self) -> Self::Value {
+        self.elements
+            .iter()
+            .zip(&self.included)
+            .filter(|(_, included)| **included)
+            .map(|(element, _)| element.current())
+            .collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        loop {
+            match self.next {
+                ValueFirstStep::Simplify(idx) if idx < self.elements.len() => {
+                    if self.included[idx] && self.elements[idx].simplify() {
+                        self.prev = Some(self.next);
+                        return true;
+                    }
+                    self.next = ValueFirstStep::Simplify(idx + 1);
                 }
-                Schema::Unsupported => {
-                    warn!("Generating with unsupported schema, parameter: {}", tt.name);
-                    Just(ParameterValue::Empty).boxed()
+                ValueFirstStep::Simplify(_) => self.next = ValueFirstStep::Remove(0),
+                ValueFirstStep::Remove(idx) if idx < self.elements.len() => {
+                    self.next = ValueFirstStep::Remove(idx + 1);
+                    let len = self.included.iter().filter(|included| **included).count();
+                    if self.included[idx] && len > self.min {
+                        self.included[idx] = false;
+                        self.prev = Some(ValueFirstStep::Remove(idx));
+                        return true;
+                    }
                 }
+                ValueFirstStep::Remove(_) => return false,
             }
         }
-        None => Just(ParameterValue::Empty).boxed(),
     }
-}
 
-#[allow(clippy::get_first)]
-pub fn gen_param_array(params: &[Parameter]) -> BoxedStrategy<[ParameterValue; 10]> {
-    [
-        gen_parameter_value(params.get(0)),
-        gen_parameter_value(params.get(1)),
-        gen_parameter_value(params.get(2)),
-        gen_parameter_value(params.get(3)),
-        gen_parameter_value(params.get(4)),
-        gen_parameter_value(params.get(5)),
-        gen_parameter_value(params.get(6)),
-        gen_parameter_value(params.get(7)),
-        gen_parameter_value(params.get(8)),
-        gen_parameter_value(params.get(9)),
-    ]
-    .boxed()
+    fn complicate(&mut self) -> bool {
+        match self.prev {
+            Some(ValueFirstStep::Simplify(idx)) => {
+                if self.elements[idx].complicate() {
+                    // The element may complicate further
+                    true
+                } else {
+                    self.prev = None;
+                    false
+                }
+            }
+            Some(ValueFirstStep::Remove(idx)) => {
+                self.included[idx] = true;
+                self.prev = None;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
-fn gen_operation(ops: Vec<Operation>) -> impl Strategy<Value = Operation> {
-    debug_assert!(!ops.is_empty(), "Operations to select from cannot be empty");
-    (0..ops.len()).prop_map(move |idx| ops[idx].clone())
+/// The pinned operations, in order, followed by the generated sequence
+fn gen_sequence_with_pinned_params(
+    pinned: Vec<(Operation, BoxedStrategy<Vec<ParameterValue>>)>,
+    seq_gen: impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
+    let pinned = pinned
+        .into_iter()
+        .map(|(op, params)| (Just(op), params))
+        .collect::<Vec<_>>();
+    (pinned, seq_gen).prop_map(|(mut pinned_seq, mut gen_ops)| {
+        pinned_seq.append(&mut gen_ops);
+        pinned_seq
+    })
 }
 
-pub fn gen_operation_with_params(
+pub fn gen_pinned_operation_sequence_with_params(
+    pinned_op: Operation,
     ops: Vec<Operation>,
-) -> impl Strategy<Value = (Operation, [ParameterValue; 10])> {
-    gen_operation(ops).prop_flat_map(|op| (Just(op.clone()), gen_param_array(&op.parameters)))
+    min: u8,
+    max: u8,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    gen_pinned_weighted_operation_sequence_with_params(
+        vec![pinned_op],
+        ops,
+        &OperationWeights::new(),
+        min,
+        max,
+        ShrinkStrategy::LengthFirst,
+    )
 }
 
-pub fn gen_operation_sequence(
+/// Same as `gen_pinned_operation_sequence_with_params`, but the operations
+///  following the pinned ones are selected according to `weights`, and
+///  shrunk in the order of `shrink`. The position of the last pinned
+///  operation is generated alongside the sequence.
+pub fn gen_pinned_weighted_operation_sequence_with_params(
+    pinned_ops: Vec<Operation>,
     ops: Vec<Operation>,
-) -> impl Strategy<Value = Vec<(Operation, [ParameterValue; 10])>> {
-    // TODO: set the 0..5 via config
-    prop::collection::vec(gen_operation_with_params(ops), 0..5)
-}
-
-// NOTE: We don't need a Vec here, but that is the current upstream interface
-pub fn gen_static_operation_with_params(
-    op: Operation,
-) -> impl Strategy<Value = (u8, Vec<(Operation, [ParameterValue; 10])>)> {
-    (Just(op.clone()), gen_param_array(&op.parameters)).prop_map(|op| (0, vec![op]))
+    weights: &OperationWeights,
+    min: u8,
+    max: u8,
+    shrink: ShrinkStrategy,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    (
+        Just(pinned_ops.len().saturating_sub(1) as u8),
+        gen_operation_sequence_added_params(gen_sequence_with_pinned_ops(
+            pinned_ops,
+            gen_weighted_operation_with_params(ops, weights),
+            min,
+            max,
+            shrink,
+        )),
+    )
 }
 
-pub fn gen_operation_sequence_with_pinned(
+/// Same as `gen_pinned_weighted_operation_sequence_with_params`, but the
+///  operations following the pinned one are selected with a bias towards what
+///  is not yet covered in the run
+pub fn gen_pinned_coverage_guided_operation_sequence_with_params(
     pinned_op: Operation,
     ops: Vec<Operation>,
+    weights: &OperationWeights,
     min: u8,
     max: u8,
-) -> impl Strategy<Value = Vec<(Operation, [ParameterValue; 10])>> {
+    coverage: SharedCoverage,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
     (
-        (
-            Just(pinned_op.clone()),
-            gen_param_array(&pinned_op.parameters),
-        ),
-        prop::collection::vec(
-            gen_operation_with_params(ops),
-            (min as usize)..=(max as usize),
-        ),
+        Just(0),
+        gen_operation_sequence_added_params(gen_sequence_with_pinned(
+            pinned_op,
+            gen_coverage_guided_operation_with_params(ops, weights, coverage),
+            min,
+            max,
+            ShrinkStrategy::LengthFirst,
+        )),
     )
-        .prop_map(|(pin, mut gen_ops)| {
-            let mut pinned_seq = vec![pin];
-            pinned_seq.append(&mut gen_ops);
-            pinned_seq
-        })
 }
 
-pub fn gen_pinned_operation_sequence_with_params(
-    pinned_op: Operation,
+/// The state of a run guiding the generation, each part is optional
+#[derive(Debug, Clone, Default)]
+pub struct GenerationGuidance {
+    /// Bias the operations towards what is not yet covered
+    pub coverage: Option<SharedCoverage>,
+    /// Sample the parameter values from the values harvested from responses
+    pub corpus: Option<SharedCorpus>,
+    /// Order the operations as learned from the outcomes of earlier orders
+    pub precedence: Option<SharedPrecedence>,
+    /// Mutate the sequences generated before, besides generating new ones
+    pub mutation: Option<SharedMutation>,
+    /// Replay the sequences of the earlier campaigns, before generating any
+    pub campaign: Option<SharedCampaign>,
+}
+
+/// Same as `gen_pinned_weighted_operation_sequence_with_params`, but guided
+///  by what the run has seen so far
+pub fn gen_pinned_guided_operation_sequence_with_params(
+    pinned_ops: Vec<Operation>,
     ops: Vec<Operation>,
+    weights: &OperationWeights,
     min: u8,
     max: u8,
-) -> impl Strategy<Value = (u8, Vec<(Operation, [ParameterValue; 10])>)> {
+    guidance: &GenerationGuidance,
+    shrink: ShrinkStrategy,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    let names = ops.iter().map(|op| op.info.name.clone()).collect();
+    let seeded_ops = ops.clone();
+    let op_gen = match &guidance.coverage {
+        Some(coverage) => CoverageGuidedOperation {
+            ops,
+            weights: weights.clone(),
+            coverage: coverage.clone(),
+        }
+        .boxed(),
+        None => gen_operation(ops, weights),
+    };
+
+    let params_gen = {
+        let corpus = guidance.corpus.clone();
+        move |params: &[Parameter]| match &corpus {
+            Some(corpus) => gen_harvested_param_array(params, corpus),
+            None => gen_param_array(params),
+        }
+    };
+    let pinned = pinned_ops
+        .iter()
+        .map(|op| (op.clone(), params_gen(&op.parameters)))
+        .collect();
+    let op_gen = op_gen.prop_flat_map(move |op| (Just(op.clone()), params_gen(&op.parameters)));
+
+    let seq_gen = gen_sequence(op_gen, min, max, shrink);
+    let seq_gen = match &guidance.precedence {
+        Some(precedence) => LearnedOrder {
+            seq_gen,
+            names,
+            precedence: precedence.clone(),
+        }
+        .boxed(),
+        None => seq_gen.boxed(),
+    };
+    let seq_gen = match &guidance.mutation {
+        Some(mutation) => MutatedSequence {
+            seq_gen,
+            mutation: mutation.clone(),
+            min: min as usize,
+            max: max as usize,
+        }
+        .boxed(),
+        None => seq_gen,
+    };
+    let seq_gen = match &guidance.campaign {
+        Some(campaign) => SeededSequence {
+            seq_gen,
+            campaign: campaign.clone(),
+            ops: seeded_ops,
+            min: min as usize,
+        }
+        .boxed(),
+        None => seq_gen,
+    };
+
     (
-        Just(0),
-        gen_operation_sequence_added_params(gen_operation_sequence_with_pinned(
-            pinned_op, ops, min, max,
-        )),
+        Just(pinned_ops.len().saturating_sub(1) as u8),
+        gen_operation_sequence_added_params(gen_sequence_with_pinned_params(pinned, seq_gen)),
     )
 }
 
+/// The spread of the random tie-break between the operations of a learned
+///  order. Operations without observations are ordered at random, a learned
+///  precedence outweighs it as it grows past the spread.
+const LEARNED_ORDER_SPREAD: f64 = 0.5;
+
+/// Order the generated operations after the precedence learned in the run.
+///  The learning is read when a new value is generated, and kept while the
+///  value is shrunk, so that shrinking sees the same order.
+#[derive(Debug)]
+struct LearnedOrder<S> {
+    seq_gen: S,
+    names: Vec<String>,
+    precedence: SharedPrecedence,
+}
+
+impl<S> Strategy for LearnedOrder<S>
+where
+    S: Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+{
+    type Tree = LearnedOrderTree<S::Tree>;
+    type Value = Vec<(Operation, Vec<ParameterValue>)>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let learning = self.precedence.lock().unwrap().clone();
+        let tie_break = self
+            .names
+            .iter()
+            .map(|name| {
+                (
+                    name.clone(),
+                    runner.rng().gen::<f64>() * LEARNED_ORDER_SPREAD,
+                )
+            })
+            .collect();
+
+        Ok(LearnedOrderTree {
+            seq: self.seq_gen.new_tree(runner)?,
+            learning,
+            tie_break,
+        })
+    }
+}
+
+struct LearnedOrderTree<T> {
+    seq: T,
+    learning: PrecedenceLearning,
+    tie_break: HashMap<String, f64>,
+}
+
+impl<T> proptest::strategy::ValueTree for LearnedOrderTree<T>
+where
+    T: proptest::strategy::ValueTree<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+{
+    type Value = Vec<(Operation, Vec<ParameterValue>)>;
+
+    fn current(&self) -> Self::Value {
+        let seq = self.seq.current();
+        let names: Vec<&str> = seq.iter().map(|(op, _)| op.info.name.as_str()).collect();
+        let order = |name: &str| {
+            self.learning.score(name, &names) + self.tie_break.get(name).copied().unwrap_or(0.0)
+        };
+
+        let mut ordered: Vec<(f64, (Operation, Vec<ParameterValue>))> = seq
+            .iter()
+            .map(|op| (order(&op.0.info.name), op.clone()))
+            .collect();
+        ordered.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ordered.into_iter().map(|(_, op)| op).collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.seq.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.seq.complicate()
+    }
+}
+
+/// How often a sequence generated before is mutated, instead of a new
+///  sequence generated
+const MUTATION_PROBABILITY: f64 = 0.5;
+
+/// Mutate the sequences generated before in the run, e.g. swap adjacent
+///  operations, besides generating new ones. A mutated sequence outside
+///  the length of the generated ones is generated anew. The mutated
+///  sequences shrink by removing their operations.
+#[derive(Debug)]
+struct MutatedSequence<S> {
+    seq_gen: S,
+    mutation: SharedMutation,
+    min: usize,
+    max: usize,
+}
+
+impl<S> Strategy for MutatedSequence<S>
+where
+    S: Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+{
+    type Tree = ReplacedSequenceTree<S::Tree>;
+    type Value = Vec<(Operation, Vec<ParameterValue>)>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let seq = self.seq_gen.new_tree(runner)?;
+
+        let mut mutation = self.mutation.lock().unwrap();
+        let mutated = if runner.rng().gen_bool(MUTATION_PROBABILITY) {
+            mutation
+                .mutate(runner.rng())
+                .filter(|mutated| (self.min..=self.max).contains(&mutated.len()))
+        } else {
+            None
+        };
+        mutation.record(
+            mutated
+                .clone()
+                .unwrap_or_else(|| proptest::strategy::ValueTree::current(&seq)),
+        );
+
+        Ok(ReplacedSequenceTree {
+            seq,
+            included: vec![true; mutated.as_ref().map_or(0, Vec::len)],
+            replaced: mutated,
+            min: self.min,
+            next: 0,
+            prev: None,
+        })
+    }
+}
+
+/// Replay the sequences of the corpus of the campaign, before generating any
+///  new ones. A replayed sequence shrinks by removing its operations.
+#[derive(Debug)]
+struct SeededSequence<S> {
+    seq_gen: S,
+    campaign: SharedCampaign,
+    ops: Vec<Operation>,
+    min: usize,
+}
+
+impl<S> Strategy for SeededSequence<S>
+where
+    S: Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+{
+    type Tree = ReplacedSequenceTree<S::Tree>;
+    type Value = Vec<(Operation, Vec<ParameterValue>)>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let seq = self.seq_gen.new_tree(runner)?;
+        let seed = self.campaign.lock().unwrap().next_seed(&self.ops);
+
+        Ok(ReplacedSequenceTree {
+            seq,
+            included: vec![true; seed.as_ref().map_or(0, Vec::len)],
+            replaced: seed,
+            min: self.min,
+            next: 0,
+            prev: None,
+        })
+    }
+}
+
+/// The generated sequence, or the sequence replacing it, e.g. a mutation of
+///  an earlier one
+struct ReplacedSequenceTree<T> {
+    seq: T,
+    replaced: Option<Vec<(Operation, Vec<ParameterValue>)>>,
+    included: Vec<bool>,
+    min: usize,
+    next: usize,
+    prev: Option<usize>,
+}
+
+impl<T> proptest::strategy::ValueTree for ReplacedSequenceTree<T>
+where
+    T: proptest::strategy::ValueTree<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+{
+    type Value = Vec<(Operation, Vec<ParameterValue>)>;
+
+    fn current(&self) -> Self::Value {
+        match &self.replaced {
+            Some(replaced) => replaced
+                .iter()
+                .zip(&self.included)
+                .filter(|(_, included)| **included)
+                .map(|(op, _)| op.clone())
+                .collect(),
+            None => self.seq.current(),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.replaced.is_none() {
+            return self.seq.simplify();
+        }
+
+        while self.next < self.included.len() {
+            let idx = self.next;
+            self.next += 1;
+            let len = self.included.iter().filter(|included| **included).count();
+            if self.included[idx] && len > self.min {
+                self.included[idx] = false;
+                self.prev = Some(idx);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn complicate(&mut self) -> bool {
+        if self.replaced.is_none() {
+            return self.seq.complicate();
+        }
+
+        match self.prev.take() {
+            Some(idx) => {
+                self.included[idx] = true;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 ////////////////////////////////////////
 // Experiment
 fn resolve_parameters(
-    op: &mut (Operation, [ParameterValue; 10]),
+    op: &mut (Operation, Vec<ParameterValue>),
     related_candidates: &[GenerationOperationWithParameters],
 ) {
     // bucket_2[i] (op)
@@ -426,10 +1486,9 @@ fn resolve_parameters(
             let possible_params_relations =
                 amos_relations::related_parameters(related_candidates, param);
 
-            if !possible_params_relations.is_empty() {
-                let choose = param_value.seed() % (possible_params_relations.len()) as i32;
-                let selected_rel = &possible_params_relations[choose as usize];
-
+            if let Some(selected_rel) =
+                amos_relations::choose_relation(&possible_params_relations, param_value.seed())
+            {
                 let idx = match selected_rel {
                     Relation::Parameter(r) | Relation::Response(r) => [r.op_idx, r.idx],
                 };
@@ -447,7 +1506,7 @@ fn resolve_parameters(
 
 #[derive(Debug, Clone)]
 pub enum GenOperation {
-    Generated((Operation, [ParameterValue; 10])),
+    Generated((Operation, Vec<ParameterValue>)),
     Empty,
 }
 
@@ -455,168 +1514,183 @@ fn gen_bucket_operation(
     bucket: u8,
     bucket_len: u8,
     ops: &[Operation],
+    weights: &OperationWeights,
+    guidance: &GenerationGuidance,
 ) -> BoxedStrategy<GenOperation> {
     if bucket > bucket_len || ops.is_empty() {
         Just(GenOperation::Empty).boxed()
     } else {
-        gen_operation_with_params(ops.to_vec())
+        gen_guided_operation_with_params(ops.to_vec(), weights, guidance)
             .prop_map(GenOperation::Generated)
             .boxed()
     }
 }
 
+/// An operation selected by the weights, and by the coverage when guided by
+///  it, with the values of the corpus when guided by one
+fn gen_guided_operation_with_params(
+    ops: Vec<Operation>,
+    weights: &OperationWeights,
+    guidance: &GenerationGuidance,
+) -> BoxedStrategy<(Operation, Vec<ParameterValue>)> {
+    let op_gen = match &guidance.coverage {
+        Some(coverage) => CoverageGuidedOperation {
+            ops,
+            weights: weights.clone(),
+            coverage: coverage.clone(),
+        }
+        .boxed(),
+        None => gen_operation(ops, weights),
+    };
+
+    let corpus = guidance.corpus.clone();
+    op_gen
+        .prop_flat_map(move |op| {
+            let params = match &corpus {
+                Some(corpus) => gen_harvested_param_array(&op.parameters, corpus),
+                None => gen_param_array(&op.parameters),
+            };
+            (Just(op), params)
+        })
+        .boxed()
+}
+
 pub struct QueryOptions {
     pub precedence: u8,
     pub slack_min: u8,
     pub slack_max: u8,
+    pub min_length: u8,
+    pub max_length: u8,
 }
 
-// TODO: If the GET op is in first position and have a param, it's ok for it
-// to generate, so It can be changed to 'Owned' (However, as there is nothing to dep
-//  on it will revert to 'gen'. BUUT a CREATE following such a GET should be fine
-//  with refering to its param)
-pub fn gen_buckets_5(
+impl QueryOptions {
+    /// The 1-indexed positions of the query operation, bounded by the slack around
+    ///  its precedence and by the sequence lengths
+    fn query_positions(&self) -> std::ops::RangeInclusive<u8> {
+        let lower = self
+            .precedence
+            .saturating_sub(self.slack_min)
+            .max(self.min_length.saturating_sub(1));
+        let upper = self
+            .precedence
+            .saturating_add(self.slack_max)
+            .min(self.max_length.saturating_sub(1))
+            .max(lower);
+
+        lower..=upper
+    }
+}
+
+/// Sequences of the bucketized operations around the query operation. The
+///  operations of a bucket are selected by the weights, the coverage and the
+///  corpus of the guidance, the order of the buckets is not learned, mutated or
+///  seeded from a campaign. TODO: a GET in first position may own its parameter,
+///  a CREATE following such a GET could then refer to it.
+pub fn gen_buckets(
     query_op: Operation,
     query_options: QueryOptions,
     buckets: Buckets,
     //TODO: should be ids
     bucketize: fn(&Buckets, u8) -> Vec<Vec<&BucketItem>>,
     operations: Vec<Operation>,
-) -> impl Strategy<Value = (u8, Vec<(Operation, [ParameterValue; 10])>)> {
-    ({
-        let lower = if query_options.slack_min > query_options.precedence {
-            0
-        } else {
-            query_options.precedence - query_options.slack_min
-        };
-        lower..=(query_options.precedence + query_options.slack_max)
-    })
-    .prop_flat_map(move |q_pos| {
-        // We need to bucketize based on seq length
-        let seq_length = q_pos + 1;
-        let bucketized = bucketize(&buckets, seq_length.clamp(2, 5));
-
-        let ops_bucket_1 = bucketized[0]
-            .iter()
-            .map(|bucket_item| {
-                let op = operations
-                    .iter()
-                    .find(|op| op.info.name == bucket_item.name);
-                op.unwrap().clone()
-            })
-            .collect::<Vec<Operation>>();
-
-        let ops_bucket_2 = bucketized[1]
-            .iter()
-            .map(|bucket_item| {
-                let op = operations
-                    .iter()
-                    .find(|op| op.info.name == bucket_item.name);
-                op.unwrap().clone()
-            })
-            .collect::<Vec<Operation>>();
-
-        let ops_bucket_3 = bucketized[2]
-            .iter()
-            .map(|bucket_item| {
-                let op = operations
-                    .iter()
-                    .find(|op| op.info.name == bucket_item.name);
-                op.unwrap().clone()
-            })
-            .collect::<Vec<Operation>>();
+    weights: &OperationWeights,
+    guidance: &GenerationGuidance,
+) -> impl Strategy<Value = (u8, Vec<(Operation, Vec<ParameterValue>)>)> {
+    let weights = weights.clone();
+    let guidance = guidance.clone();
+    let query_params = match &guidance.corpus {
+        Some(corpus) => gen_harvested_param_array(&query_op.parameters, corpus),
+        None => gen_param_array(&query_op.parameters),
+    };
 
-        let ops_bucket_4 = bucketized[3]
-            .iter()
-            .map(|bucket_item| {
-                let op = operations
-                    .iter()
-                    .find(|op| op.info.name == bucket_item.name);
-                op.unwrap().clone()
-            })
-            .collect::<Vec<Operation>>();
+    query_options
+        .query_positions()
+        .prop_flat_map(move |q_pos| {
+            // We need to bucketize based on seq length
+            let seq_length = q_pos + 1;
+            let bucketized = bucketize(&buckets, seq_length.max(2));
+
+            let bucket_ops = bucketized
+                .iter()
+                .enumerate()
+                .map(|(idx, bucket)| {
+                    let ops = bucket
+                        .iter()
+                        .map(|bucket_item| {
+                            let op = operations
+                                .iter()
+                                .find(|op| op.info.name == bucket_item.name);
+                            op.unwrap().clone()
+                        })
+                        .collect::<Vec<Operation>>();
 
-        let ops_bucket_5 = bucketized[4]
-            .iter()
-            .map(|bucket_item| {
-                let op = operations
-                    .iter()
-                    .find(|op| op.info.name == bucket_item.name);
-                op.unwrap().clone()
-            })
-            .collect::<Vec<Operation>>();
+                    gen_bucket_operation(idx as u8 + 1, seq_length, &ops, &weights, &guidance)
+                })
+                .collect::<Vec<BoxedStrategy<GenOperation>>>();
 
-        (
-            (Just(q_pos)),
             (
-                Just(query_op.clone()),
-                gen_param_array(&query_op.parameters),
-            ),
-            gen_bucket_operation(1, seq_length, &ops_bucket_1),
-            gen_bucket_operation(2, seq_length, &ops_bucket_2),
-            gen_bucket_operation(3, seq_length, &ops_bucket_3),
-            gen_bucket_operation(4, seq_length, &ops_bucket_4),
-            gen_bucket_operation(5, seq_length, &ops_bucket_5),
-        )
-    })
-    .prop_map(move |(q_pos, mut q_op, op_1, op_2, op_3, op_4, op_5)| {
-        // First OP can ref the query OP. Even though the query OP might
-        //  be put in a later position at synth., it is conceptually the
-        //  'root' of the sequence
-        let gen_ops = vec![op_1, op_2, op_3, op_4, op_5];
-
-        let mut gen_op_seq = vec![];
-
-        // pull out the generated operations
-        for gen_op in gen_ops {
-            match gen_op {
-                GenOperation::Generated(op) => gen_op_seq.push(op),
-                GenOperation::Empty => (),
+                (Just(q_pos)),
+                (Just(query_op.clone()), query_params.clone()),
+                bucket_ops,
+            )
+        })
+        .prop_map(move |(q_pos, mut q_op, gen_ops)| {
+            // First OP can ref the query OP. Even though the query OP might
+            //  be put in a later position at synth., it is conceptually the
+            //  'root' of the sequence
+            let mut gen_op_seq = vec![];
+
+            // pull out the generated operations
+            for gen_op in gen_ops {
+                match gen_op {
+                    GenOperation::Generated(op) => gen_op_seq.push(op),
+                    GenOperation::Empty => (),
+                }
             }
-        }
 
-        // NOTE: This is 0 indexed while the q_pos is 1 indexed.
-        //  For now, adjust q_pos, but this should be normalized throughout
-        let zero_based_q_pos = if q_pos == 0 { 0 } else { q_pos - 1 };
-        // The query position can not be outside the length of the sequence
-        let q_pos = if zero_based_q_pos > 0 && zero_based_q_pos > ((gen_op_seq.len() - 1) as u8) {
-            (gen_op_seq.len() - 1) as u8
-        } else {
-            zero_based_q_pos
-        };
+            // NOTE: This is 0 indexed while the q_pos is 1 indexed.
+            //  For now, adjust q_pos, but this should be normalized throughout
+            let zero_based_q_pos = q_pos.saturating_sub(1);
+            // The query position can not be outside the length of the sequence
+            let last_pos = gen_op_seq.len().saturating_sub(1) as u8;
+            let q_pos = zero_based_q_pos.min(last_pos);
+
+            let mut final_seq = vec![];
+            for (idx, mut op) in gen_op_seq.clone().into_iter().enumerate() {
+                #[allow(clippy::comparison_chain)] // Hot path, match might not inline
+                if idx == q_pos as usize {
+                    // Time the inject the query-op, resolve/add it and then
+                    //  resolve/add the current generated op
+                    resolve_parameters(&mut q_op, &final_seq[0..idx]);
+                    final_seq.push(q_op.clone());
+                    resolve_parameters(&mut op, &final_seq[0..idx + 1]);
+                    final_seq.push(op.clone());
+                } else if idx > q_pos as usize {
+                    // Resolve/add the current generated operation.
+                    // We have passed the point of where the query-op is injected,
+                    // so we can now reference idx + 1
+                    resolve_parameters(&mut op, &final_seq[0..idx + 1]);
+                    final_seq.push(op.clone());
+                } else {
+                    // Resolve/add the current generated operation.
+                    // Query op has not yet been added.
+                    resolve_parameters(&mut op, &final_seq[0..idx]);
+                    final_seq.push(op.clone());
+                }
+            }
 
-        let mut final_seq = vec![];
-        for (idx, mut op) in gen_op_seq.clone().into_iter().enumerate() {
-            //if idx == query_options.precedence as usize {
-            #[allow(clippy::comparison_chain)] // Hot path, match might not inline
-            if idx == q_pos as usize {
-                // Time the inject the query-op, resolve/add it and then
-                //  resolve/add the current generated op
-                resolve_parameters(&mut q_op, &final_seq[0..idx]);
-                final_seq.push(q_op.clone());
-                resolve_parameters(&mut op, &final_seq[0..idx + 1]);
-                final_seq.push(op.clone());
-            } else if idx > q_pos as usize {
-                // Resolve/add the current generated operation.
-                // We have passed the point of where the query-op is injected,
-                // so we can now reference idx + 1
-                resolve_parameters(&mut op, &final_seq[0..idx + 1]);
-                final_seq.push(op.clone());
-            } else {
-                // Resolve/add the current generated operation.
-                // Query op has not yet been added.
-                resolve_parameters(&mut op, &final_seq[0..idx]);
-                final_seq.push(op.clone());
+            // Nothing could be generated around the query op, it is the sequence
+            if final_seq.is_empty() {
+                final_seq.push(q_op);
             }
-        }
 
-        (q_pos, final_seq)
-    })
+            (q_pos, final_seq)
+        })
 }
 
 pub fn gen_operation_sequence_added_params(
-    seq_gen: impl Strategy<Value = Vec<(Operation, [ParameterValue; 10])>>,
-) -> impl Strategy<Value = Vec<(Operation, [ParameterValue; 10])>> {
+    seq_gen: impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>>,
+) -> impl Strategy<Value = Vec<(Operation, Vec<ParameterValue>)>> {
     seq_gen.prop_map(|mut gen_ops| {
         if gen_ops.len() > 1 {
             for i in 0..gen_ops.len() {
@@ -628,11 +1702,10 @@ pub fn gen_operation_sequence_added_params(
                         let possible_params_relations =
                             amos_relations::related_parameters(&gen_ops[0..i], param);
 
-                        if !possible_params_relations.is_empty() {
-                            let choose =
-                                param_value.seed() % (possible_params_relations.len()) as i32;
-                            let selected_rel = &possible_params_relations[choose as usize];
-
+                        if let Some(selected_rel) = amos_relations::choose_relation(
+                            &possible_params_relations,
+                            param_value.seed(),
+                        ) {
                             let idx = match selected_rel {
                                 Relation::Parameter(r) | Relation::Response(r) => [r.op_idx, r.idx],
                             };
@@ -664,8 +1737,9 @@ mod tests {
         strategy::{Strategy, ValueTree},
         test_runner::{Config, FileFailurePersistence, RngAlgorithm, TestRng, TestRunner},
     };
+    use qr_http_resource::http::HTTPMethod;
 
-    use crate::amos::ParameterOwnership;
+    use crate::amos::{OperationMetaData, ParameterOwnership};
     use crate::amos_generation::ParameterValue::*;
     use crate::amos_generation::Schema::*;
     use crate::amos_generation::*;
@@ -679,15 +1753,13 @@ mod tests {
             ],
         );
 
-        let runner = TestRunner::new_with_rng(
+        TestRunner::new_with_rng(
             Config {
                 failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
                 ..Config::default()
             },
             rng,
-        );
-
-        runner
+        )
     }
 
     #[test]
@@ -705,7 +1777,8 @@ mod tests {
             seed: 123,
             active: true,
         };
-        let failed_str = std::panic::catch_unwind(|| pi.as_string_value());
+        let failed_str =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| pi.as_string_value()));
 
         assert!(failed_str.is_err());
         assert_eq!(p_str, "abc".to_string());
@@ -726,7 +1799,8 @@ mod tests {
             seed: 123,
             active: true,
         };
-        let failed_int = std::panic::catch_unwind(|| pi.as_int_value());
+        let failed_int =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| pi.as_int_value()));
 
         assert!(failed_int.is_err());
         assert_eq!(p_int, 123);
@@ -750,7 +1824,7 @@ mod tests {
             seed: 123,
             active: true,
         };
-        let failed_seed = std::panic::catch_unwind(|| pb.seed());
+        let failed_seed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| pb.seed()));
 
         assert!(failed_seed.is_err());
         assert_eq!(123, ps.seed());
@@ -790,6 +1864,8 @@ mod tests {
                 strength: 1,
                 op_idx: 0,
                 idx: 0,
+                field: None,
+                reasons: vec![],
             }),
         };
         let pa = ParameterValue::ArrayOfString {
@@ -804,14 +1880,14 @@ mod tests {
         };
         let pe = ParameterValue::Empty;
 
-        assert_eq!(true, ps.active());
-        assert_eq!(true, pi.active());
-        assert_eq!(true, pb.active());
-        assert_eq!(false, pd.active());
-        assert_eq!(false, pr.active());
-        assert_eq!(true, pa.active());
-        assert_eq!(false, pip.active());
-        assert_eq!(false, pe.active());
+        assert!(ps.active());
+        assert!(pi.active());
+        assert!(pb.active());
+        assert!(!pd.active());
+        assert!(!pr.active());
+        assert!(pa.active());
+        assert!(!pip.active());
+        assert!(!pe.active());
     }
 
     #[test]
@@ -821,11 +1897,14 @@ mod tests {
         let param = Parameter {
             name: "name".to_string(),
             schema: Schema::StringRegex {
-                regex: "a*".to_string(),
+                regex: RegexPattern::new("a*"),
             },
             required: true,
             ownership: ParameterOwnership::Owned,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -841,6 +1920,114 @@ mod tests {
         )
     }
 
+    #[test]
+    fn gen_parameter_value_unicode_strings() {
+        let mut runner = create_runner();
+
+        let param = Parameter {
+            name: "name".to_string(),
+            schema: Schema::StringNonEmpty,
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: Some(GeneratorSpec::Unicode),
+        };
+
+        let gen = gen_parameter_value(Some(&param));
+        let values: Vec<std::string::String> = (0..50)
+            .map(|_| {
+                gen.new_tree(&mut runner)
+                    .unwrap()
+                    .current()
+                    .as_string_value()
+            })
+            .collect();
+
+        assert!(values.iter().all(|value| !value.is_empty()));
+        assert!(values.iter().any(|value| !value.is_ascii()));
+    }
+
+    #[test]
+    fn gen_parameter_value_large_payloads() {
+        let mut runner = create_runner();
+
+        let param = |schema| Parameter {
+            name: "name".to_string(),
+            schema,
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: Some(GeneratorSpec::Large { max_length: 64 }),
+        };
+
+        let gen = gen_parameter_value(Some(&param(Schema::String)));
+        for _ in 0..20 {
+            let value = gen
+                .new_tree(&mut runner)
+                .unwrap()
+                .current()
+                .as_string_value();
+            assert!(!value.is_empty() && value.len() <= 64);
+        }
+
+        let gen = gen_parameter_value(Some(&param(Schema::ArrayOfString)));
+        for _ in 0..20 {
+            match gen.new_tree(&mut runner).unwrap().current() {
+                ParameterValue::ArrayOfString { value, .. } => assert!(value.len() <= 64),
+                value => panic!("Expected an array, got {:?}", value),
+            }
+        }
+    }
+
+    #[test]
+    fn gen_parameter_value_invalid_regex_falls_back_to_string() {
+        let mut runner = create_runner();
+
+        let param = Parameter {
+            name: "name".to_string(),
+            schema: Schema::StringRegex {
+                regex: RegexPattern::new("[a-"),
+            },
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        assert!(RegexPattern::new("[a-").compile().is_err());
+
+        let gen = gen_parameter_value(Some(&param));
+        let value = gen.new_tree(&mut runner).unwrap();
+
+        assert!(matches!(
+            value.current(),
+            ParameterValue::StringValue { .. }
+        ))
+    }
+
+    #[test]
+    fn regex_pattern_is_compiled_once_for_its_clones() {
+        let regex = RegexPattern::new("[a-z]{3}");
+        let clone = regex.clone();
+        assert!(clone.compile().is_ok());
+
+        // Compiled by the clone, kept with the pattern
+        assert!(regex.strategy.get().is_some());
+        // Another AMOS with the same pattern compiles its own
+        assert!(RegexPattern::new("[a-z]{3}").strategy.get().is_none());
+
+        // Saved as the pattern only
+        let saved = serde_json::to_string(&regex).unwrap();
+        assert_eq!(saved, "\"[a-z]{3}\"");
+        assert_eq!(serde_json::from_str::<RegexPattern>(&saved).unwrap(), regex);
+    }
+
     #[test]
     fn gen_parameter_value_string_non_empty() {
         let mut runner = create_runner();
@@ -851,6 +2038,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Owned,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -876,6 +2066,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Dependency,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -901,6 +2094,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Dependency,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -926,6 +2122,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -951,6 +2150,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -976,6 +2178,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -1001,6 +2206,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -1026,6 +2234,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -1055,6 +2266,9 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
@@ -1080,30 +2294,142 @@ mod tests {
             required: true,
             ownership: ParameterOwnership::Unknown,
             meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        let gen = gen_parameter_value(Some(&param));
+        let value = gen.new_tree(&mut runner).unwrap();
+
+        assert_eq!(value.current(), ParameterValue::Empty,)
+    }
+
+    #[test]
+    fn gen_parameter_value_unsupported() {
+        let mut runner = create_runner();
+
+        let param = Parameter {
+            name: "name".to_string(),
+            schema: Schema::Unsupported,
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
         let value = gen.new_tree(&mut runner).unwrap();
 
-        assert_eq!(value.current(), ParameterValue::Empty,)
+        assert_eq!(value.current(), ParameterValue::Empty,)
+    }
+
+    #[test]
+    fn gen_parameter_value_examples() {
+        let mut runner = create_runner();
+
+        let param = Parameter {
+            name: "age".to_string(),
+            schema: Schema::Int,
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: None,
+            examples: vec!["42".to_string(), "not a number".to_string()],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        let gen = gen_parameter_value(Some(&param));
+        let values = (0..50)
+            .map(|_| gen.new_tree(&mut runner).unwrap().current())
+            .collect::<Vec<_>>();
+
+        assert!(values
+            .iter()
+            .any(|v| matches!(v, ParameterValue::IntValue { value: 42, .. })));
+        assert!(values
+            .iter()
+            .all(|v| matches!(v, ParameterValue::IntValue { .. })));
+
+        // Without a valid example, only the schema generates values
+        let invalid = Parameter {
+            examples: vec!["not a number".to_string()],
+            ..param
+        };
+        assert!(gen_example_value(&invalid).is_none());
     }
 
     #[test]
-    fn gen_parameter_value_unsupported() {
+    fn gen_parameter_value_shrinks_to_the_example() {
         let mut runner = create_runner();
 
         let param = Parameter {
-            name: "name".to_string(),
-            schema: Schema::Unsupported,
+            name: "age".to_string(),
+            schema: Schema::Int,
             required: true,
-            ownership: ParameterOwnership::Unknown,
+            ownership: ParameterOwnership::Owned,
             meta_data: None,
+            examples: vec!["42".to_string(), "7".to_string()],
+            relation_hints: vec![],
+            generator: None,
         };
 
         let gen = gen_parameter_value(Some(&param));
-        let value = gen.new_tree(&mut runner).unwrap();
+        for _ in 0..20 {
+            // Every value fails, shrinking as far as possible
+            let mut tree = gen.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+
+            assert!(matches!(
+                tree.current(),
+                ParameterValue::IntValue { value: 42, .. }
+            ));
+        }
+    }
 
-        assert_eq!(value.current(), ParameterValue::Empty,)
+    #[test]
+    fn gen_injection_param_array_mixes_payloads() {
+        let mut runner = create_runner();
+
+        let params = vec![
+            Parameter {
+                name: "name".to_string(),
+                schema: Schema::String,
+                required: true,
+                ownership: ParameterOwnership::Unknown,
+                meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            },
+            Parameter {
+                name: "age".to_string(),
+                schema: Schema::Int,
+                required: true,
+                ownership: ParameterOwnership::Unknown,
+                meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            },
+        ];
+
+        let gen = gen_injection_param_array(&params);
+        let values = (0..50)
+            .map(|_| gen.new_tree(&mut runner).unwrap().current())
+            .collect::<Vec<_>>();
+
+        assert!(values
+            .iter()
+            .any(|v| injection::is_payload(&v[0].as_string_value())));
+        assert!(values
+            .iter()
+            .any(|v| !injection::is_payload(&v[0].as_string_value())));
+        assert!(values
+            .iter()
+            .all(|v| matches!(v[1], ParameterValue::IntValue { .. })));
     }
 
     #[test]
@@ -1114,6 +2440,8 @@ mod tests {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1121,10 +2449,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1139,37 +2471,32 @@ mod tests {
                     Operation {
                         info: OperationInfo {
                             name: "get_persons".to_string(),
-                            key: "operation/get_persons".to_string()
+                            key: "operation/get_persons".to_string(),
+                            alias: None,
+                            deprecated: false,
                         },
                         parameters: vec![Parameter {
                             name: "name".to_string(),
                             schema: String,
                             required: true,
                             ownership: ParameterOwnership::Owned,
-                            meta_data: None
+                            meta_data: None,
+                            examples: vec![],
+                            relation_hints: vec![],
+                            generator: None
                         }],
                         responses: vec![Response {
                             name: "successful operation".to_string(),
-                            schema: Schema::ArrayOfRefItems("person".to_string())
+                            schema: Schema::ArrayOfRefItems("person".to_string()),
+                            status: None,
                         }],
                         meta_data: None
                     },
-                    [
-                        StringValue {
-                            value: "jieewpsmyqg".to_string(),
-                            seed: 5,
-                            active: true
-                        },
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty
-                    ]
+                    vec![StringValue {
+                        value: "jieewpsmyqg".to_string(),
+                        seed: 5,
+                        active: true
+                    }]
                 )]
             ),
             value.current(),
@@ -1184,11 +2511,14 @@ mod tests {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1197,6 +2527,8 @@ mod tests {
             info: OperationInfo {
                 name: "delete_person".to_string(),
                 key: "operation/delete_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1204,10 +2536,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1216,6 +2552,8 @@ mod tests {
             info: OperationInfo {
                 name: "post_person".to_string(),
                 key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![
                 Parameter {
@@ -1224,6 +2562,9 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
                 Parameter {
                     name: "age".to_string(),
@@ -1231,11 +2572,15 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
             ],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1252,22 +2597,27 @@ mod tests {
                         Operation {
                             info: OperationInfo {
                                 name: "get_persons".to_string(),
-                                key: "operation/get_persons".to_string()
+                                key: "operation/get_persons".to_string(),
+                                alias: None,
+                                deprecated: false,
                             },
                             parameters: vec![],
                             responses: vec![Response {
                                 name: "successful operation".to_string(),
-                                schema: Schema::ArrayOfRefItems("person".to_string())
+                                schema: Schema::ArrayOfRefItems("person".to_string()),
+                                status: None,
                             }],
                             meta_data: None
                         },
-                        [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty]
+                        vec![]
                     ),
                     (
                         Operation {
                             info: OperationInfo {
                                 name: "post_person".to_string(),
-                                key: "operation/post_person".to_string()
+                                key: "operation/post_person".to_string(),
+                                alias: None,
+                                deprecated: false,
                             },
                             parameters: vec![
                                 Parameter {
@@ -1275,23 +2625,30 @@ mod tests {
                                     schema: String,
                                     required: true,
                                     ownership: ParameterOwnership::Owned,
-                                    meta_data: None
+                                    meta_data: None,
+                                    examples: vec![],
+                                    relation_hints: vec![],
+                                    generator: None
                                 },
                                 Parameter {
                                     name: "age".to_string(),
                                     schema: Int8,
                                     required: true,
                                     ownership: ParameterOwnership::Owned,
-                                    meta_data: None
+                                    meta_data: None,
+                                    examples: vec![],
+                                    relation_hints: vec![],
+                                    generator: None
                                 }
                             ],
                             responses: vec![Response {
                                 name: "successful operation".to_string(),
-                                schema: Schema::Reference("person".to_string())
+                                schema: Schema::Reference("person".to_string()),
+                                status: None,
                             }],
                             meta_data: None
                         },
-                        [
+                        vec![
                             StringValue {
                                 value: "tieewpsmyqgdnlmtcpc".to_string(),
                                 seed: 1,
@@ -1301,31 +2658,26 @@ mod tests {
                                 value: 188,
                                 seed: 7,
                                 active: true
-                            },
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty
+                            }
                         ]
                     ),
                     (
                         Operation {
                             info: OperationInfo {
                                 name: "get_persons".to_string(),
-                                key: "operation/get_persons".to_string()
+                                key: "operation/get_persons".to_string(),
+                                alias: None,
+                                deprecated: false,
                             },
                             parameters: vec![],
                             responses: vec![Response {
                                 name: "successful operation".to_string(),
-                                schema: Schema::ArrayOfRefItems("person".to_string())
+                                schema: Schema::ArrayOfRefItems("person".to_string()),
+                                status: None,
                             }],
                             meta_data: None
                         },
-                        [Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty, Empty]
+                        vec![]
                     )
                 ]
             ),
@@ -1341,6 +2693,8 @@ mod tests {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1348,10 +2702,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1360,6 +2718,8 @@ mod tests {
             info: OperationInfo {
                 name: "delete_person".to_string(),
                 key: "operation/delete_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1367,10 +2727,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1387,87 +2751,82 @@ mod tests {
                         Operation {
                             info: OperationInfo {
                                 name: "get_persons".to_string(),
-                                key: "operation/get_persons".to_string()
+                                key: "operation/get_persons".to_string(),
+                                alias: None,
+                                deprecated: false,
                             },
                             parameters: vec![Parameter {
                                 name: "name".to_string(),
                                 schema: String,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             }],
                             responses: vec![Response {
                                 name: "successful operation".to_string(),
-                                schema: Schema::ArrayOfRefItems("person".to_string())
+                                schema: Schema::ArrayOfRefItems("person".to_string()),
+                                status: None,
                             }],
                             meta_data: None
                         },
-                        [
-                            StringValue {
-                                value: "jieewpsmyqg".to_string(),
-                                seed: 5,
-                                active: true
-                            },
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty
-                        ]
+                        vec![StringValue {
+                            value: "jieewpsmyqg".to_string(),
+                            seed: 5,
+                            active: true
+                        }]
                     ),
                     (
                         Operation {
                             info: OperationInfo {
                                 name: "get_persons".to_string(),
-                                key: "operation/get_persons".to_string()
+                                key: "operation/get_persons".to_string(),
+                                alias: None,
+                                deprecated: false,
                             },
                             parameters: vec![Parameter {
                                 name: "name".to_string(),
                                 schema: String,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             }],
                             responses: vec![Response {
                                 name: "successful operation".to_string(),
-                                schema: Schema::ArrayOfRefItems("person".to_string())
+                                schema: Schema::ArrayOfRefItems("person".to_string()),
+                                status: None,
                             }],
                             meta_data: None
                         },
-                        [
-                            ParameterValue::Reference {
-                                active: true,
-                                idx: [0, 0],
-                                fallback: Box::new(StringValue {
-                                    value: "nlmtcpckwygdgbgr".to_string(),
-                                    seed: 8,
-                                    active: true
-                                }),
-                                relation: amos_relations::Relation::Parameter(
-                                    amos_relations::RelationInfo {
-                                        operation: "get_persons".to_string(),
-                                        name: "name".to_string(),
-                                        schema: Schema::String,
-                                        strength: 1,
-                                        op_idx: 0,
-                                        idx: 0
-                                    }
-                                )
-                            },
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty,
-                            Empty
-                        ]
+                        vec![ParameterValue::Reference {
+                            active: true,
+                            idx: [0, 0],
+                            fallback: Box::new(StringValue {
+                                value: "nlmtcpckwygdgbgr".to_string(),
+                                seed: 8,
+                                active: true
+                            }),
+                            relation: amos_relations::Relation::Parameter(
+                                amos_relations::RelationInfo {
+                                    operation: "get_persons".to_string(),
+                                    name: "name".to_string(),
+                                    schema: Schema::String,
+                                    strength: 18,
+                                    op_idx: 0,
+                                    idx: 0,
+                                    field: None,
+                                    reasons: vec![
+                                        "same name 'name'".to_string(),
+                                        "same schema String".to_string()
+                                    ]
+                                }
+                            )
+                        }]
                     ),
                 ]
             ),
@@ -1483,11 +2842,14 @@ mod tests {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1496,6 +2858,8 @@ mod tests {
             info: OperationInfo {
                 name: "delete_person".to_string(),
                 key: "operation/delete_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1503,10 +2867,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1515,6 +2883,8 @@ mod tests {
             info: OperationInfo {
                 name: "post_person".to_string(),
                 key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![
                 Parameter {
@@ -1523,6 +2893,9 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
                 Parameter {
                     name: "age".to_string(),
@@ -1530,11 +2903,15 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
             ],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1551,6 +2928,8 @@ mod tests {
                     info: OperationInfo {
                         name: "post_person".to_string(),
                         key: "operation/post_person".to_string(),
+                        alias: None,
+                        deprecated: false,
                     },
                     parameters: vec![
                         Parameter {
@@ -1558,23 +2937,30 @@ mod tests {
                             schema: String,
                             required: true,
                             ownership: ParameterOwnership::Owned,
-                            meta_data: None
+                            meta_data: None,
+                            examples: vec![],
+                            relation_hints: vec![],
+                            generator: None
                         },
                         Parameter {
                             name: "age".to_string(),
                             schema: Int8,
                             required: true,
                             ownership: ParameterOwnership::Owned,
-                            meta_data: None
+                            meta_data: None,
+                            examples: vec![],
+                            relation_hints: vec![],
+                            generator: None
                         }
                     ],
                     responses: vec![Response {
                         name: "successful operation".to_string(),
                         schema: Schema::Reference("person".to_string()),
+                        status: None,
                     }],
                     meta_data: None,
                 },
-                [
+                vec![
                     StringValue {
                         value: "tieewpsmyqgdnlmtcpc".to_string(),
                         seed: 1,
@@ -1585,14 +2971,6 @@ mod tests {
                         seed: 7,
                         active: true
                     },
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
-                    ParameterValue::Empty,
                 ],
             )],
             value.current()
@@ -1607,11 +2985,14 @@ mod tests {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1620,6 +3001,8 @@ mod tests {
             info: OperationInfo {
                 name: "delete_person".to_string(),
                 key: "operation/delete_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1627,10 +3010,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1639,6 +3026,8 @@ mod tests {
             info: OperationInfo {
                 name: "post_person".to_string(),
                 key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![
                 Parameter {
@@ -1647,6 +3036,9 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
                 Parameter {
                     name: "age".to_string(),
@@ -1654,11 +3046,15 @@ mod tests {
                     required: true,
                     ownership: ParameterOwnership::Owned,
                     meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
                 },
             ],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1667,6 +3063,8 @@ mod tests {
             info: OperationInfo {
                 name: "PINNED".to_string(),
                 key: "operation/PINNED".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![Parameter {
                 name: "name".to_string(),
@@ -1674,10 +3072,14 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             }],
             responses: vec![Response {
                 name: "successful operation".to_string(),
                 schema: Schema::Reference("person".to_string()),
+                status: None,
             }],
             meta_data: None,
         };
@@ -1694,43 +3096,40 @@ mod tests {
                     Operation {
                         info: OperationInfo {
                             name: "PINNED".to_string(),
-                            key: "operation/PINNED".to_string()
+                            key: "operation/PINNED".to_string(),
+                            alias: None,
+                            deprecated: false,
                         },
                         parameters: vec![Parameter {
                             name: "name".to_string(),
                             schema: String,
                             required: true,
                             ownership: ParameterOwnership::Owned,
-                            meta_data: None
+                            meta_data: None,
+                            examples: vec![],
+                            relation_hints: vec![],
+                            generator: None
                         }],
                         responses: vec![Response {
                             name: "successful operation".to_string(),
-                            schema: Reference("person".to_string())
+                            schema: Reference("person".to_string()),
+                            status: None,
                         }],
                         meta_data: None
                     },
-                    [
-                        StringValue {
-                            value: "jieewpsmyqg".to_string(),
-                            seed: 5,
-                            active: true,
-                        },
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty
-                    ]
+                    vec![StringValue {
+                        value: "jieewpsmyqg".to_string(),
+                        seed: 5,
+                        active: true,
+                    }]
                 ),
                 (
                     Operation {
                         info: OperationInfo {
                             name: "post_person".to_string(),
-                            key: "operation/post_person".to_string()
+                            key: "operation/post_person".to_string(),
+                            alias: None,
+                            deprecated: false,
                         },
                         parameters: vec![
                             Parameter {
@@ -1738,23 +3137,30 @@ mod tests {
                                 schema: String,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             },
                             Parameter {
                                 name: "age".to_string(),
                                 schema: Int8,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             }
                         ],
                         responses: vec![Response {
                             name: "successful operation".to_string(),
-                            schema: Reference("person".to_string())
+                            schema: Reference("person".to_string()),
+                            status: None,
                         }],
                         meta_data: None
                     },
-                    [
+                    vec![
                         StringValue {
                             value: "nlmtcpckwygdgbgr".to_string(),
                             seed: 8,
@@ -1764,58 +3170,47 @@ mod tests {
                             value: 219,
                             seed: 9,
                             active: true
-                        },
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty
+                        }
                     ]
                 ),
                 (
                     Operation {
                         info: OperationInfo {
                             name: "delete_person".to_string(),
-                            key: "operation/delete_person".to_string()
+                            key: "operation/delete_person".to_string(),
+                            alias: None,
+                            deprecated: false,
                         },
                         parameters: vec![Parameter {
                             name: "name".to_string(),
                             schema: String,
                             required: true,
                             ownership: ParameterOwnership::Owned,
-                            meta_data: None
+                            meta_data: None,
+                            examples: vec![],
+                            relation_hints: vec![],
+                            generator: None
                         }],
                         responses: vec![Response {
                             name: "successful operation".to_string(),
-                            schema: Reference("person".to_string())
+                            schema: Reference("person".to_string()),
+                            status: None,
                         }],
                         meta_data: None
                     },
-                    [
-                        StringValue {
-                            value: "yjikjbcdoodwzochuzvbaqyrearvwqp".to_string(),
-                            seed: 6,
-                            active: true
-                        },
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty
-                    ]
+                    vec![StringValue {
+                        value: "yjikjbcdoodwzochuzvbaqyrearvwqp".to_string(),
+                        seed: 6,
+                        active: true
+                    }]
                 ),
                 (
                     Operation {
                         info: OperationInfo {
                             name: "post_person".to_string(),
-                            key: "operation/post_person".to_string()
+                            key: "operation/post_person".to_string(),
+                            alias: None,
+                            deprecated: false,
                         },
                         parameters: vec![
                             Parameter {
@@ -1823,23 +3218,30 @@ mod tests {
                                 schema: String,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             },
                             Parameter {
                                 name: "age".to_string(),
                                 schema: Int8,
                                 required: true,
                                 ownership: ParameterOwnership::Owned,
-                                meta_data: None
+                                meta_data: None,
+                                examples: vec![],
+                                relation_hints: vec![],
+                                generator: None
                             }
                         ],
                         responses: vec![Response {
                             name: "successful operation".to_string(),
-                            schema: Reference("person".to_string())
+                            schema: Reference("person".to_string()),
+                            status: None,
                         }],
                         meta_data: None
                     },
-                    [
+                    vec![
                         StringValue {
                             value: "".to_string(),
                             seed: 1,
@@ -1849,19 +3251,547 @@ mod tests {
                             value: 0,
                             seed: 3,
                             active: true
-                        },
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty,
-                        Empty
+                        }
                     ]
                 )
             ],
             value.current()
         );
     }
+
+    #[test]
+    fn gen_coverage_guided_operation_favours_uncovered() {
+        let mut runner = create_runner();
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+
+        let coverage =
+            std::sync::Arc::new(std::sync::Mutex::new(crate::amos_result::CoverageMap::new()));
+
+        // Cover the first operation in the run
+        coverage
+            .lock()
+            .unwrap()
+            .record(&crate::amos::InvokeResult::new(
+                GeneratedOperation {
+                    name: "covered".to_string(),
+                    parameters: vec![],
+                },
+                "".to_string(),
+                true,
+                Some(crate::amos::ResultMetaData::HTTP {
+                    url: "/covered".to_string(),
+                    status: qr_http_resource::http::HTTPStatus::OK,
+                    size: 0,
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            ));
+
+        let strategy = gen_coverage_guided_operation_with_params(
+            vec![op("covered"), op("uncovered")],
+            &OperationWeights::new(),
+            coverage,
+        );
+
+        let mut uncovered_count = 0;
+        for _ in 0..100 {
+            let value = strategy.new_tree(&mut runner).unwrap();
+            if value.current().0.info.name == "uncovered" {
+                uncovered_count += 1;
+            }
+        }
+
+        assert!(uncovered_count > 50);
+    }
+
+    #[test]
+    fn gen_harvested_param_array_samples_the_corpus() {
+        let mut runner = create_runner();
+
+        let param = Parameter {
+            name: "personId".to_string(),
+            schema: Int,
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let corpus =
+            std::sync::Arc::new(std::sync::Mutex::new(crate::corpus::ResponseCorpus::new()));
+        let strategy = gen_harvested_param_array(std::slice::from_ref(&param), &corpus);
+        let harvested_count = |runner: &mut TestRunner| {
+            (0..100)
+                .filter(|_| {
+                    let value = strategy.new_tree(runner).unwrap().current();
+                    matches!(value[0], IntValue { value: 987654, .. })
+                })
+                .count()
+        };
+
+        // The corpus grows during the run, the same strategy samples it
+        assert_eq!(harvested_count(&mut runner), 0);
+        corpus
+            .lock()
+            .unwrap()
+            .harvest(&crate::amos::InvokeResult::new(
+                GeneratedOperation {
+                    name: "post_person".to_string(),
+                    parameters: vec![],
+                },
+                r#"{"id": 987654, "age": 36}"#.to_string(),
+                true,
+                None,
+            ));
+        assert!(harvested_count(&mut runner) > 25);
+    }
+
+    #[test]
+    fn gen_pinned_guided_sequence_orders_after_the_learned_precedence() {
+        let mut runner = create_runner();
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+        let result = |name: &str, success| {
+            crate::amos::InvokeResult::new(
+                GeneratedOperation {
+                    name: name.to_string(),
+                    parameters: vec![],
+                },
+                "".to_string(),
+                success,
+                None,
+            )
+        };
+
+        // The GET only succeeds after the POST
+        let mut learning = crate::precedence::PrecedenceLearning::new();
+        for _ in 0..10 {
+            learning.record(&[result("post_person", true), result("get_person", true)]);
+            learning.record(&[result("get_person", false)]);
+        }
+        let guidance = GenerationGuidance {
+            precedence: Some(std::sync::Arc::new(std::sync::Mutex::new(learning))),
+            ..Default::default()
+        };
+
+        let strategy = gen_pinned_guided_operation_sequence_with_params(
+            vec![op("get_persons")],
+            vec![op("post_person"), op("get_person")],
+            &OperationWeights::new(),
+            2,
+            2,
+            &guidance,
+            ShrinkStrategy::LengthFirst,
+        );
+
+        let mut both = 0;
+        for _ in 0..100 {
+            let (_, seq) = strategy.new_tree(&mut runner).unwrap().current();
+            let position = |name: &str| seq.iter().position(|(op, _)| op.info.name == name);
+            if let (Some(post), Some(get)) = (position("post_person"), position("get_person")) {
+                assert!(post < get);
+                both += 1;
+            }
+        }
+        assert!(both > 0);
+    }
+
+    #[test]
+    fn gen_pinned_guided_sequence_mutates_the_earlier_sequences() {
+        let mut runner = create_runner();
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+        let mutation = std::sync::Arc::new(std::sync::Mutex::new(
+            crate::mutation::SequenceMutation::new(),
+        ));
+        let guidance = GenerationGuidance {
+            mutation: Some(mutation.clone()),
+            ..Default::default()
+        };
+
+        let strategy = gen_pinned_guided_operation_sequence_with_params(
+            vec![op("get_persons")],
+            vec![op("post_person"), op("put_person"), op("delete_person")],
+            &OperationWeights::new(),
+            1,
+            3,
+            &guidance,
+            ShrinkStrategy::LengthFirst,
+        );
+
+        for _ in 0..100 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            let (_, seq) = tree.current();
+            // The query operation stays pinned, the mutations keep the length
+            assert_eq!(seq[0].0.info.name, "get_persons");
+            assert!((2..=4).contains(&seq.len()));
+
+            while tree.simplify() {}
+            assert!(tree.current().1.len() >= 2);
+        }
+        assert_eq!(
+            mutation.lock().unwrap().len(),
+            crate::mutation::SequenceMutation::POOL_SIZE
+        );
+    }
+
+    #[test]
+    fn gen_sequence_value_first_removes_after_the_values_are_minimal() {
+        let mut runner = create_runner();
+
+        let strategy = gen_sequence(10..100i32, 1, 4, ShrinkStrategy::ValueFirst);
+        for _ in 0..20 {
+            // Every value fails, shrinking as far as possible
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            loop {
+                let before = tree.current();
+                if !tree.simplify() {
+                    break;
+                }
+                if tree.current().len() < before.len() {
+                    assert!(before.iter().all(|value| *value == 10));
+                }
+            }
+
+            assert_eq!(tree.current(), vec![10]);
+        }
+    }
+
+    #[test]
+    fn gen_sequence_value_first_complicates_back_to_the_failing_value() {
+        let mut runner = create_runner();
+
+        let strategy = gen_sequence(0..1000i32, 2, 2, ShrinkStrategy::ValueFirst);
+        for _ in 0..20 {
+            // Fails while the first value is at least 100
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            if tree.current()[0] < 100 {
+                continue;
+            }
+            let mut minimal = tree.current();
+            loop {
+                let shrunk = if tree.current()[0] >= 100 {
+                    minimal = tree.current();
+                    tree.simplify()
+                } else {
+                    tree.complicate()
+                };
+                if !shrunk {
+                    break;
+                }
+            }
+
+            assert_eq!(minimal, vec![100, 0]);
+        }
+    }
+
+    #[test]
+    fn gen_weighted_operation_favours_weight() {
+        let mut runner = create_runner();
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+
+        let mut weights = OperationWeights::new();
+        weights.set("favoured", 20);
+        weights.set("excluded", 0);
+
+        let strategy = gen_weighted_operation_with_params(
+            vec![op("excluded"), op("normal"), op("favoured")],
+            &weights,
+        );
+
+        let mut favoured_count = 0;
+        for _ in 0..100 {
+            let value = strategy.new_tree(&mut runner).unwrap();
+            let name = value.current().0.info.name;
+
+            assert_ne!(name, "excluded");
+            if name == "favoured" {
+                favoured_count += 1;
+            }
+        }
+
+        assert!(favoured_count > 80);
+    }
+
+    #[test]
+    fn gen_weighted_operation_with_extreme_weights() {
+        let mut runner = create_runner();
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+        let ops = vec![op("first"), op("second"), op("third")];
+
+        let mut weights = OperationWeights::new();
+        weights.set("first", u32::MAX);
+        weights.set("second", u32::MAX - 1);
+        weights.set("third", u32::MAX);
+
+        let strategy = gen_weighted_operation_with_params(ops.clone(), &weights);
+        let coverage =
+            std::sync::Arc::new(std::sync::Mutex::new(crate::amos_result::CoverageMap::new()));
+        let guided = gen_coverage_guided_operation_with_params(ops, &weights, coverage);
+        for _ in 0..100 {
+            assert!(strategy.new_tree(&mut runner).is_ok());
+            assert!(guided.new_tree(&mut runner).is_ok());
+        }
+
+        let extreme = [u64::MAX, u64::MAX];
+        assert_eq!(total_weight(&extreme), 2 * u128::from(u64::MAX));
+        assert_eq!(weighted_index(&extreme, u128::from(u64::MAX)), 1);
+    }
+
+    #[test]
+    fn weighted_index_test() {
+        let weights = [0, 2, 1];
+
+        assert_eq!(weighted_index(&weights, 0), 1);
+        assert_eq!(weighted_index(&weights, 1), 1);
+        assert_eq!(weighted_index(&weights, 2), 2);
+    }
+
+    #[test]
+    fn gen_buckets_beyond_five_positions() {
+        let mut runner = create_runner();
+
+        let op = |name: &str, method: HTTPMethod, url: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        };
+
+        // Every create is nested one level deeper than the previous
+        let mut url = std::string::String::new();
+        let mut operations = vec![];
+        for level in 1..=6 {
+            url = format!("{url}/r{level}/{{id{level}}}");
+            operations.push(op(&format!("create_{level}"), HTTPMethod::POST, &url));
+        }
+        operations.push(op("delete", HTTPMethod::DELETE, "/r1/{id1}"));
+        let query_op = op("query", HTTPMethod::GET, &url);
+        operations.push(query_op.clone());
+
+        let buckets = Buckets::new(&operations);
+        let strategy = gen_buckets(
+            query_op,
+            QueryOptions {
+                precedence: 6,
+                slack_min: 0,
+                slack_max: 0,
+                min_length: 7,
+                max_length: 7,
+            },
+            buckets,
+            crate::amos_buckets::bucketize_for_state_identity_strategy,
+            operations,
+            &OperationWeights::new(),
+            &GenerationGuidance::default(),
+        );
+
+        let (q_pos, sequence) = strategy.new_tree(&mut runner).unwrap().current();
+
+        assert_eq!(sequence.len(), 8);
+        assert_eq!(q_pos, 5);
+        assert_eq!(sequence[q_pos as usize].0.info.name, "query");
+        assert_eq!(sequence.last().unwrap().0.info.name, "delete");
+    }
+
+    #[test]
+    fn gen_buckets_follow_the_weights() {
+        let mut runner = create_runner();
+
+        let op = |name: &str, method: HTTPMethod, url: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        };
+
+        let query_op = op("query", HTTPMethod::GET, "/persons/{id}");
+        let operations = vec![
+            op("create", HTTPMethod::POST, "/persons"),
+            op("excluded", HTTPMethod::POST, "/persons"),
+            query_op.clone(),
+        ];
+
+        let mut weights = OperationWeights::new();
+        weights.set("excluded", 0);
+
+        let strategy = gen_buckets(
+            query_op,
+            QueryOptions {
+                precedence: 1,
+                slack_min: 0,
+                slack_max: 0,
+                min_length: 2,
+                max_length: 2,
+            },
+            Buckets::new(&operations),
+            crate::amos_buckets::bucketize_for_state_identity_strategy,
+            operations,
+            &weights,
+            &GenerationGuidance::default(),
+        );
+
+        for _ in 0..50 {
+            let (_, sequence) = strategy.new_tree(&mut runner).unwrap().current();
+
+            assert!(sequence.iter().all(|(op, _)| op.info.name != "excluded"));
+            assert!(sequence.iter().any(|(op, _)| op.info.name == "create"));
+        }
+    }
+
+    #[test]
+    fn preview_parameter_values_shrinks_the_values() {
+        let param = Parameter {
+            name: "id".to_string(),
+            schema: Schema::StringRegex {
+                regex: RegexPattern::new("[a-c]{1,8}"),
+            },
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        let previews = preview_parameter_values(&param, 5, 7);
+
+        assert_eq!(previews.len(), 5);
+        assert_eq!(previews, preview_parameter_values(&param, 5, 7));
+        let matches = |value: &ParameterValue| {
+            let value = value.as_string_value();
+            (1..=8).contains(&value.len()) && value.chars().all(|c| "abc".contains(c))
+        };
+        for preview in &previews {
+            assert!(matches(&preview.value));
+            assert!(preview.shrunk.iter().all(matches));
+        }
+        // Shrinking ends at the shortest string of the regex
+        let longest = previews
+            .iter()
+            .max_by_key(|preview| preview.value.as_string_value().len())
+            .unwrap();
+        assert_eq!(
+            longest
+                .shrunk
+                .last()
+                .map(|value| value.as_string_value().len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn gen_parameter_value_with_generator_override() {
+        let mut runner = create_runner();
+
+        let param = |schema, generator| Parameter {
+            name: "age".to_string(),
+            schema,
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: Some(generator),
+        };
+
+        let range = param(Schema::Int, GeneratorSpec::Range { min: 18, max: 20 });
+        let enumerated = param(
+            Schema::Int,
+            GeneratorSpec::Enum {
+                values: vec!["7".to_string(), "seven".to_string()],
+            },
+        );
+        let doubles = param(Schema::Double, GeneratorSpec::Range { min: 0, max: 1 });
+        for _ in 0..20 {
+            let value = gen_parameter_value(Some(&range))
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!((18..=20).contains(&value.as_int_value()));
+
+            // Values that are not of the schema are never generated
+            let value = gen_parameter_value(Some(&enumerated))
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(value.as_int_value(), 7);
+
+            let value = gen_parameter_value(Some(&doubles))
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert!(matches!(value, DoubleValue { value, .. } if (0.0..=1.0).contains(&value)));
+        }
+    }
 }
@@ -1,10 +1,11 @@
+use petgraph::graph::DiGraph;
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::amos::{Operation, Parameter, Schema};
 use crate::amos_generation::ParameterValue;
 
-pub type GenerationOperationWithParameters = (Operation, [ParameterValue; 10]);
+pub type GenerationOperationWithParameters = (Operation, Vec<ParameterValue>);
 
 fn word_contains(a: &[String], b: &[String]) -> Vec<String> {
     let mut matches = vec![];
@@ -39,6 +40,16 @@ fn camel_split(s: &str) -> Vec<String> {
     words
 }
 
+/// The points of each kind of evidence of a relation, its strength is their sum
+const SAME_NAME: u8 = 12;
+const NAME_WORD: u8 = 3;
+const SAME_SCHEMA: u8 = 6;
+const COMPATIBLE_SCHEMA: u8 = 3;
+const DEFINITION_LINK: u8 = 6;
+
+/// The strength of a declared relation, no inferred relation is as strong
+pub const HINTED_STRENGTH: u8 = u8::MAX;
+
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub struct RelationInfo {
     pub operation: String,
@@ -47,6 +58,24 @@ pub struct RelationInfo {
     pub strength: u8,
     pub op_idx: usize,
     pub idx: usize,
+    /// The path of the field in a JSON response, e.g. `data.id`, holding the value
+    #[serde(default)]
+    pub field: Option<String>,
+    /// The evidence the strength is the sum of
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<String>,
+}
+
+impl RelationInfo {
+    /// Why the relation was inferred, e.g. `strength 18: same name 'id',
+    ///  same schema String`
+    pub fn explanation(&self) -> String {
+        if self.reasons.is_empty() {
+            format!("strength {}", self.strength)
+        } else {
+            format!("strength {}: {}", self.strength, self.reasons.join(", "))
+        }
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
@@ -55,48 +84,266 @@ pub enum Relation {
     Response(RelationInfo),
 }
 
+impl Relation {
+    pub fn info(&self) -> &RelationInfo {
+        match self {
+            Relation::Parameter(info) | Relation::Response(info) => info,
+        }
+    }
+}
+
+/// The evidence of a relation, see the points above
+#[derive(Default)]
+struct Score {
+    strength: u8,
+    reasons: Vec<String>,
+}
+
+impl Score {
+    fn add(&mut self, points: u8, reason: String) {
+        self.strength = self
+            .strength
+            .saturating_add(points)
+            .min(HINTED_STRENGTH - 1);
+        self.reasons.push(reason);
+    }
+}
+
+fn score_name(score: &mut Score, param: &Parameter, name: &str, matches: &[String]) {
+    if name == param.name {
+        score.add(SAME_NAME, format!("same name '{}'", name));
+    } else if !matches.is_empty() {
+        score.add(
+            NAME_WORD.saturating_mul(matches.len() as u8),
+            format!("name shares '{}'", matches.join("', '")),
+        );
+    }
+}
+
+/// The definition the schema holds values of
+fn referenced_definition(schema: &Schema) -> Option<&str> {
+    match schema {
+        Schema::Reference(name)
+        | Schema::ArrayOfRefItems(name)
+        | Schema::ArrayOfUniqueRefItems(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// A definition the operation responds that the name of the parameter refers
+///  to, e.g. `petId` to a `Pet`
+fn score_definition_link(score: &mut Score, operation: &Operation, param: &Parameter) {
+    let param_name = param.name.to_lowercase();
+    let linked = operation
+        .responses
+        .iter()
+        .filter_map(|r| referenced_definition(&r.schema))
+        .find(|definition| {
+            let definition = definition.to_lowercase();
+            !definition.is_empty() && param_name.starts_with(&definition)
+        });
+
+    if let Some(definition) = linked {
+        score.add(
+            DEFINITION_LINK,
+            format!(
+                "{} responds the '{}' it names",
+                operation.info.name, definition
+            ),
+        );
+    }
+}
+
+/// The relation to pick of the relations of a parameter. The relations at
+///  least half as strong as the strongest are picked from by the seed, so a
+///  weak relation is not picked over a strong one.
+pub fn choose_relation(relations: &[Relation], seed: i32) -> Option<&Relation> {
+    let strongest = relations.iter().map(|r| r.info().strength).max()?;
+    let strong = relations
+        .iter()
+        .filter(|r| r.info().strength >= strongest.div_ceil(2))
+        .collect::<Vec<_>>();
+
+    Some(strong[seed.unsigned_abs() as usize % strong.len()])
+}
+
+/// Where the value of a parameter comes from, as declared by the user
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum RelationSource {
+    Parameter {
+        operation: String,
+        parameter: String,
+    },
+    /// A field of the JSON response of the operation, e.g. `id` or `data.id`
+    Response { operation: String, field: String },
+}
+
+/// A relation the names and schemas do not reveal, e.g. between the operations
+///  of merged services. Written as `<operation>:<parameter> <- <source>`, where
+///  the source is `<operation>:<parameter>` or `<operation>:$.<field>`, e.g.
+///  `orders.postOrder:customerId <- customers.postCustomer:$.id`.
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct RelationHint {
+    pub operation: String,
+    pub parameter: String,
+    pub source: RelationSource,
+}
+
+impl std::str::FromStr for RelationHint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, source) = s
+            .split_once("<-")
+            .ok_or_else(|| format!("Expected '<target> <- <source>' in '{}'", s))?;
+
+        // Operation names may hold ':', parameter names do not
+        let split = |part: &str| match part.trim().rsplit_once(':') {
+            Some((operation, name)) if !operation.is_empty() && !name.is_empty() => {
+                Ok((operation.to_string(), name.to_string()))
+            }
+            _ => Err(format!(
+                "Expected '<operation>:<name>' in '{}'",
+                part.trim()
+            )),
+        };
+
+        let (operation, parameter) = split(target)?;
+        let (source_operation, source_name) = split(source)?;
+
+        let source = match source_name.strip_prefix("$.") {
+            Some(field) if !field.is_empty() => RelationSource::Response {
+                operation: source_operation,
+                field: field.to_string(),
+            },
+            _ => RelationSource::Parameter {
+                operation: source_operation,
+                parameter: source_name,
+            },
+        };
+
+        Ok(RelationHint {
+            operation,
+            parameter,
+            source,
+        })
+    }
+}
+
+impl std::fmt::Display for RelationHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{} <- ", self.operation, self.parameter)?;
+        match &self.source {
+            RelationSource::Parameter {
+                operation,
+                parameter,
+            } => write!(f, "{}:{}", operation, parameter),
+            RelationSource::Response { operation, field } => {
+                write!(f, "{}:$.{}", operation, field)
+            }
+        }
+    }
+}
+
+/// The relations of the parameter to the operations. The declared relations of
+///  the parameter take precedence, when their sources are among the operations.
 pub fn related_parameters(
     operations: &[GenerationOperationWithParameters],
     param: &Parameter,
+) -> Vec<Relation> {
+    let hinted = hinted_relations(operations.iter().map(|(o, _)| o), param);
+    if !hinted.is_empty() {
+        return hinted;
+    }
+
+    operation_relations(operations.iter().map(|(o, _)| o), param)
+}
+
+fn hinted_relations<'a>(
+    operations: impl Iterator<Item = &'a Operation>,
+    param: &Parameter,
+) -> Vec<Relation> {
+    let mut relations = vec![];
+
+    for (op_idx, o) in operations.enumerate() {
+        for source in &param.relation_hints {
+            match source {
+                RelationSource::Parameter {
+                    operation,
+                    parameter,
+                } if *operation == o.info.name => {
+                    if let Some(idx) = o.parameters.iter().position(|p| p.name == *parameter) {
+                        relations.push(Relation::Parameter(RelationInfo {
+                            operation: operation.clone(),
+                            name: parameter.clone(),
+                            schema: o.parameters[idx].schema.clone(),
+                            strength: HINTED_STRENGTH,
+                            op_idx,
+                            idx,
+                            field: None,
+                            reasons: vec!["declared by a relation hint".to_string()],
+                        }));
+                    }
+                }
+                RelationSource::Response { operation, field } if *operation == o.info.name => {
+                    relations.push(Relation::Response(RelationInfo {
+                        operation: operation.clone(),
+                        name: field.clone(),
+                        schema: param.schema.clone(),
+                        strength: HINTED_STRENGTH,
+                        op_idx,
+                        idx: 0,
+                        field: Some(field.clone()),
+                        reasons: vec!["declared by a relation hint".to_string()],
+                    }))
+                }
+                _ => (),
+            }
+        }
+    }
+
+    relations
+}
+
+fn operation_relations<'a>(
+    operations: impl Iterator<Item = &'a Operation>,
+    param: &Parameter,
 ) -> Vec<Relation> {
     let mut candidate_relations = vec![];
 
     let camel_param = camel_split(&param.name);
 
-    for (op_idx, (o, _op_params)) in operations.iter().enumerate() {
+    for (op_idx, o) in operations.enumerate() {
         let camel_operation = camel_split(&o.info.name);
 
         for (param_idx, p) in o.parameters.iter().enumerate() {
             // 1. Schema match
             //  - find the parameters and responses that match the schema or sub-schema
-            if p.schema == param.schema {
-                // 2. name match
-                if p.name == param.name {
-                    candidate_relations.push(Relation::Parameter(RelationInfo {
-                        operation: o.info.name.clone(),
-                        name: p.name.clone(),
-                        schema: p.schema.clone(),
-                        strength: camel_param.len() as u8,
-                        op_idx,
-                        idx: param_idx,
-                    }));
-                } else {
-                    // partial match on param?
-                    let camel_op_param = camel_split(&p.name);
-                    let matches = word_contains(&camel_param, &camel_op_param);
-
-                    if !matches.is_empty() {
-                        candidate_relations.push(Relation::Parameter(RelationInfo {
-                            operation: o.info.name.clone(),
-                            name: p.name.clone(),
-                            schema: p.schema.clone(),
-                            strength: matches.len() as u8,
-                            op_idx,
-                            idx: param_idx,
-                        }))
-                    }
-                }
+            if p.schema != param.schema {
+                continue;
             }
+
+            // 2. name match, in full or in part
+            let matches = word_contains(&camel_param, &camel_split(&p.name));
+            if p.name != param.name && matches.is_empty() {
+                continue;
+            }
+
+            let mut score = Score::default();
+            score_name(&mut score, param, &p.name, &matches);
+            score.add(SAME_SCHEMA, format!("same schema {}", p.schema));
+            score_definition_link(&mut score, o, param);
+
+            candidate_relations.push(Relation::Parameter(RelationInfo {
+                operation: o.info.name.clone(),
+                name: p.name.clone(),
+                schema: p.schema.clone(),
+                strength: score.strength,
+                op_idx,
+                idx: param_idx,
+                field: None,
+                reasons: score.reasons,
+            }));
         }
 
         for (r_idx, r) in o.responses.iter().enumerate() {
@@ -115,13 +362,26 @@ pub fn related_parameters(
 
                 debug!("Schema match: {:?}", schema_match);
                 if schema_match {
+                    let mut score = Score::default();
+                    score.add(
+                        NAME_WORD.saturating_mul(matches.len() as u8),
+                        format!("operation name shares '{}'", matches.join("', '")),
+                    );
+                    score.add(
+                        COMPATIBLE_SCHEMA,
+                        format!("{} responds items of {}", r.schema, param.schema),
+                    );
+                    score_definition_link(&mut score, o, param);
+
                     candidate_relations.push(Relation::Response(RelationInfo {
                         operation: o.info.name.clone(),
                         name: r.name.clone(),
                         schema: r.schema.clone(),
-                        strength: matches.len() as u8,
+                        strength: score.strength,
                         op_idx,
                         idx: r_idx,
+                        field: None,
+                        reasons: score.reasons,
                     }))
                 }
             }
@@ -130,3 +390,229 @@ pub fn related_parameters(
 
     candidate_relations
 }
+
+/// An edge in the operation dependency graph. The edge goes from the operation
+/// that can supply a value to the operation owning `parameter`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Dependency {
+    pub parameter: String,
+    pub relation: Relation,
+}
+
+/// Nodes are indices into the operations the graph was built from
+pub type DependencyGraph = DiGraph<usize, Dependency>;
+
+/// Build a graph of which operations can supply parameters to which, using the
+/// same relations as the generator. An operation is not related to itself.
+pub fn operation_dependency_graph(operations: &[Operation]) -> DependencyGraph {
+    let mut graph = DependencyGraph::new();
+
+    let nodes = (0..operations.len())
+        .map(|idx| graph.add_node(idx))
+        .collect::<Vec<_>>();
+
+    for (target_idx, target) in operations.iter().enumerate() {
+        for param in &target.parameters {
+            for relation in operation_relations(operations.iter(), param) {
+                let source_idx = match &relation {
+                    Relation::Parameter(info) => info.op_idx,
+                    Relation::Response(info) => info.op_idx,
+                };
+
+                if source_idx != target_idx {
+                    graph.add_edge(
+                        nodes[source_idx],
+                        nodes[target_idx],
+                        Dependency {
+                            parameter: param.name.clone(),
+                            relation,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::visit::EdgeRef;
+
+    use crate::amos;
+    use crate::amos_relations::*;
+    use qr_open_api::open_api;
+    use qr_specification_resource_access::specification as spec_ra;
+
+    fn feature_service_operations() -> Vec<Operation> {
+        let oas_json =
+            spec_ra::open_api_from_file("./test/resources/feature-service.json").unwrap();
+        let parse_result = open_api::parse_json_object(&oas_json).unwrap();
+
+        amos::open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions)
+            .amos
+            .operations
+    }
+
+    fn find_operation(operations: &[Operation], name: &str) -> usize {
+        operations
+            .iter()
+            .position(|op| op.info.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn dependency_graph_has_node_per_operation() {
+        let operations = feature_service_operations();
+        let graph = operation_dependency_graph(&operations);
+
+        assert_eq!(graph.node_count(), operations.len());
+    }
+
+    #[test]
+    fn dependency_graph_parameter_edge() {
+        let operations = feature_service_operations();
+        let graph = operation_dependency_graph(&operations);
+
+        let source = find_operation(&operations, "addProduct");
+        let target = find_operation(&operations, "getProductByName");
+
+        let edge = graph
+            .edge_references()
+            .find(|e| graph[e.source()] == source && graph[e.target()] == target)
+            .unwrap();
+
+        assert_eq!(edge.weight().parameter, "productName");
+        assert!(matches!(edge.weight().relation, Relation::Parameter(_)));
+    }
+
+    #[test]
+    fn dependency_graph_has_no_self_edges() {
+        let operations = feature_service_operations();
+        let graph = operation_dependency_graph(&operations);
+
+        assert!(graph.edge_references().all(|e| e.source() != e.target()));
+    }
+
+    #[test]
+    fn relation_hint_from_str() {
+        let hint: RelationHint = "orders.postOrder:customerId <- customers.postCustomer:$.id"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            hint,
+            RelationHint {
+                operation: "orders.postOrder".to_string(),
+                parameter: "customerId".to_string(),
+                source: RelationSource::Response {
+                    operation: "customers.postCustomer".to_string(),
+                    field: "id".to_string(),
+                },
+            }
+        );
+        assert_eq!(
+            hint.to_string(),
+            "orders.postOrder:customerId <- customers.postCustomer:$.id"
+        );
+
+        let parameter_hint: RelationHint = "a:x <- b:y".parse().unwrap();
+        assert!(matches!(
+            parameter_hint.source,
+            RelationSource::Parameter { .. }
+        ));
+
+        assert!("a:x".parse::<RelationHint>().is_err());
+        assert!("a <- b:y".parse::<RelationHint>().is_err());
+    }
+
+    #[test]
+    fn hinted_relations_take_precedence() {
+        let mut amos = amos::AMOS::new();
+        for operation in feature_service_operations() {
+            amos.push_operation(operation);
+        }
+        amos.relation_hints.push(
+            "getProductByName:productName <- getAllProducts:$.0"
+                .parse()
+                .unwrap(),
+        );
+
+        let target = amos.resolve_operation("getProductByName").unwrap();
+        let param = &target.parameters[0];
+        let candidates = vec![
+            (amos.resolve_operation("addProduct").unwrap(), vec![]),
+            (amos.resolve_operation("getAllProducts").unwrap(), vec![]),
+        ];
+
+        let relations = related_parameters(&candidates, param);
+        assert_eq!(relations.len(), 1);
+        let Relation::Response(info) = &relations[0] else {
+            panic!("Expected a response relation: {:?}", relations[0]);
+        };
+        assert_eq!(info.op_idx, 1);
+        assert_eq!(info.field, Some("0".to_string()));
+
+        // Without the hinted operation, the inferred relations are used
+        let relations = related_parameters(&candidates[0..1], param);
+        assert!(relations
+            .iter()
+            .all(|r| matches!(r, Relation::Parameter(info) if info.field.is_none())));
+        assert!(!relations.is_empty());
+    }
+
+    #[test]
+    fn relations_are_scored_by_their_evidence() {
+        let operations = feature_service_operations();
+        let target = &operations[find_operation(&operations, "getProductByName")];
+        let param = &target.parameters[0];
+        let candidates = vec![(
+            operations[find_operation(&operations, "addProduct")].clone(),
+            vec![],
+        )];
+
+        let relations = related_parameters(&candidates, param);
+        let info = relations
+            .iter()
+            .map(Relation::info)
+            .find(|info| info.name == param.name)
+            .unwrap();
+        assert!(info.strength >= SAME_NAME + SAME_SCHEMA);
+        assert!(info
+            .explanation()
+            .contains(&format!("same name '{}'", param.name)));
+    }
+
+    #[test]
+    fn weak_relations_are_not_chosen_over_strong() {
+        let relation = |name: &str, strength: u8| {
+            Relation::Parameter(RelationInfo {
+                operation: "op".to_string(),
+                name: name.to_string(),
+                schema: Schema::String,
+                strength,
+                op_idx: 0,
+                idx: 0,
+                field: None,
+                reasons: vec![],
+            })
+        };
+        let relations = vec![relation("weak", 3), relation("a", 18), relation("b", 12)];
+
+        let chosen = (0..10)
+            .map(|seed| {
+                choose_relation(&relations, seed)
+                    .unwrap()
+                    .info()
+                    .name
+                    .clone()
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(
+            chosen,
+            ["a".to_string(), "b".to_string()].into_iter().collect()
+        );
+        assert_eq!(choose_relation(&[], 1), None);
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
-use crate::amos::InvokeResult;
+use crate::amos::{InvokeResult, Operation, ResultMetaData};
 
 #[allow(dead_code)] // WIP for now
 #[derive(Debug)]
@@ -22,4 +23,172 @@ pub fn invocation_coverage(results: &[InvokeResult]) -> InvocationCoverage {
     InvocationCoverage { covered }
 }
 
+/// Weight of an operation that has not been invoked in the current run
+const UNCOVERED_OPERATION_WEIGHT: u32 = 8;
+/// Weight of an operation that has been invoked, but never succeeded
+const UNCOVERED_SUCCESS_WEIGHT: u32 = 4;
+/// Weight of an operation that has been invoked with success
+const COVERED_WEIGHT: u32 = 1;
+/// Weight added for each declared status of an operation invoked with
+///  success that has not been responded with
+const UNCOVERED_STATUS_WEIGHT: u32 = 1;
+
+/// The operations and statuses observed during a run. Used as feedback to
+///  bias the generation towards what has not yet been covered.
+#[derive(Debug, Default)]
+pub struct CoverageMap {
+    statuses: HashMap<String, HashSet<String>>,
+}
+
+impl CoverageMap {
+    pub fn new() -> Self {
+        Self {
+            statuses: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, result: &InvokeResult) {
+        let statuses = self
+            .statuses
+            .entry(result.operation.name.clone())
+            .or_default();
+
+        if let Some(ResultMetaData::HTTP { status, .. }) = &result.meta_data {
+            statuses.insert(status.to_string());
+        }
+    }
+
+    pub fn is_covered(&self, operation: &str) -> bool {
+        self.statuses.contains_key(operation)
+    }
+
+    pub fn statuses(&self, operation: &str) -> Option<&HashSet<String>> {
+        self.statuses.get(operation)
+    }
+
+    /// Selection weight of the operation, always at least 1 so that any
+    ///  operation can still be selected. An operation invoked with success
+    ///  is favoured by the declared statuses it has not been responded with.
+    pub fn weight(&self, operation: &Operation) -> u32 {
+        match self.statuses.get(&operation.info.name) {
+            None => UNCOVERED_OPERATION_WEIGHT,
+            Some(statuses) => {
+                if statuses.iter().any(|status| status.starts_with('2')) {
+                    let uncovered = operation
+                        .responses
+                        .iter()
+                        .filter_map(|response| response.status.as_ref()?.code())
+                        .filter(|code| !statuses.contains(&code.to_string()))
+                        .count() as u32;
+                    (COVERED_WEIGHT + uncovered * UNCOVERED_STATUS_WEIGHT)
+                        .min(UNCOVERED_SUCCESS_WEIGHT)
+                } else {
+                    UNCOVERED_SUCCESS_WEIGHT
+                }
+            }
+        }
+    }
+}
+
+/// Coverage shared between the invocation and the generation of a run
+pub type SharedCoverage = Arc<Mutex<CoverageMap>>;
+
 //pub fn example_coverage()
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::{HTTPMethod, HTTPStatus};
+
+    use crate::amos::{
+        InvokeResult, Operation, OperationInfo, OperationMetaData, Response, ResultMetaData, Schema,
+    };
+    use crate::amos_generation::GeneratedOperation;
+    use crate::amos_result::*;
+
+    fn operation(name: &str) -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/foo".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        }
+    }
+
+    fn result(name: &str, status: HTTPStatus) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            "".to_string(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/foo".to_string(),
+                status,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn coverage_weight_favours_uncovered() {
+        let mut coverage = CoverageMap::new();
+
+        coverage.record(&result("ok", HTTPStatus::OK));
+        coverage.record(&result("failing", HTTPStatus::BadRequest));
+
+        let uncovered = coverage.weight(&operation("uncovered"));
+        let failing = coverage.weight(&operation("failing"));
+        let ok = coverage.weight(&operation("ok"));
+
+        assert!(uncovered > failing);
+        assert!(failing > ok);
+        assert!(ok > 0);
+    }
+
+    #[test]
+    fn coverage_weight_favours_uncovered_declared_statuses() {
+        let mut coverage = CoverageMap::new();
+        let mut op = operation("op");
+        op.responses = [HTTPStatus::OK, HTTPStatus::NotFound, HTTPStatus::Default]
+            .into_iter()
+            .map(|status| Response {
+                name: status.to_string(),
+                schema: Schema::Int,
+                status: Some(status),
+            })
+            .collect();
+
+        coverage.record(&result("op", HTTPStatus::OK));
+        let uncovered_not_found = coverage.weight(&op);
+
+        coverage.record(&result("op", HTTPStatus::NotFound));
+        let covered = coverage.weight(&op);
+
+        assert!(uncovered_not_found > covered);
+        assert_eq!(covered, coverage.weight(&operation("op")));
+    }
+
+    #[test]
+    fn coverage_records_statuses() {
+        let mut coverage = CoverageMap::new();
+
+        coverage.record(&result("op", HTTPStatus::OK));
+        coverage.record(&result("op", HTTPStatus::NotFound));
+        coverage.record(&result("op", HTTPStatus::OK));
+
+        assert!(coverage.is_covered("op"));
+        assert!(!coverage.is_covered("other"));
+        assert_eq!(coverage.statuses("op").unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures_lite::{future, StreamExt};
+use lapin::options::{BasicConsumeOptions, BasicPublishOptions, QueueDeclareOptions};
+use lapin::types::{AMQPValue, FieldTable, ShortString};
+use lapin::{BasicProperties, Channel, Connection, ConnectionProperties};
+
+use crate::queue::{Broker, Message, QueueConfiguration, Reply};
+
+/// The header of a reply with its outcome as the status of an HTTP response,
+///  a reply without it is successful
+pub const STATUS_HEADER: &str = "status";
+
+/// A client of an AMQP broker, e.g. RabbitMQ. A message is published to the
+///  default exchange with its topic as the routing key, the service replies to
+///  the queue of its `reply_to` with its `correlation_id`.
+pub struct AmqpBroker {
+    // The channel is closed with the connection
+    _connection: Connection,
+    channel: Channel,
+    reply_to: ShortString,
+    replies: Mutex<Replies>,
+}
+
+struct Replies {
+    received: Receiver<(String, Reply)>,
+    /// The replies received while the one to another message was waited for
+    pending: HashMap<String, Reply>,
+}
+
+impl AmqpBroker {
+    pub fn connect(config: &QueueConfiguration) -> Result<Self, String> {
+        future::block_on(Self::open(config)).map_err(|e| {
+            format!(
+                "could not connect to {}:{}: {}",
+                config.host, config.port, e
+            )
+        })
+    }
+
+    async fn open(config: &QueueConfiguration) -> lapin::Result<Self> {
+        let uri = format!("amqp://{}:{}/%2f", config.host, config.port);
+        let connection = Connection::connect(&uri, ConnectionProperties::default()).await?;
+        let channel = connection.create_channel().await?;
+
+        // A queue of its own for the replies, deleted when it is closed
+        let queue = channel
+            .queue_declare(
+                "",
+                QueueDeclareOptions {
+                    exclusive: true,
+                    auto_delete: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+        let mut consumer = channel
+            .basic_consume(
+                queue.name().as_str(),
+                "quickrest",
+                BasicConsumeOptions {
+                    no_ack: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+
+        let (sender, received) = mpsc::channel();
+        std::thread::spawn(move || {
+            future::block_on(async {
+                while let Some(Ok(delivery)) = consumer.next().await {
+                    let Some(id) = delivery.properties.correlation_id() else {
+                        continue;
+                    };
+                    let reply = Reply::new(
+                        status(&delivery.properties),
+                        String::from_utf8_lossy(&delivery.data).to_string(),
+                    );
+                    if sender.send((id.to_string(), reply)).is_err() {
+                        break;
+                    }
+                }
+            })
+        });
+
+        Ok(AmqpBroker {
+            _connection: connection,
+            channel,
+            reply_to: queue.name().clone(),
+            replies: Mutex::new(Replies {
+                received,
+                pending: HashMap::new(),
+            }),
+        })
+    }
+}
+
+impl Broker for AmqpBroker {
+    fn publish(&self, message: &Message) -> Result<(), String> {
+        let properties = BasicProperties::default()
+            .with_correlation_id(message.correlation_id.as_str().into())
+            .with_reply_to(self.reply_to.clone())
+            .with_content_type("application/json".into());
+
+        future::block_on(async {
+            self.channel
+                .basic_publish(
+                    "",
+                    &message.topic,
+                    BasicPublishOptions::default(),
+                    message.payload.as_bytes(),
+                    properties,
+                )
+                .await?
+                .await
+        })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    fn reply(&self, correlation_id: &str, timeout: Duration) -> Result<Option<Reply>, String> {
+        let deadline = Instant::now() + timeout;
+        let mut replies = self.replies.lock().map_err(|e| e.to_string())?;
+        if let Some(reply) = replies.pending.remove(correlation_id) {
+            return Ok(Some(reply));
+        }
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match replies.received.recv_timeout(remaining) {
+                Ok((id, reply)) if id == correlation_id => return Ok(Some(reply)),
+                Ok((id, reply)) => {
+                    replies.pending.insert(id, reply);
+                }
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("the connection to the broker was closed".to_string())
+                }
+            }
+        }
+    }
+}
+
+/// The status of the header of the reply, as a number or a string of it
+fn status(properties: &BasicProperties) -> u16 {
+    let value = properties
+        .headers()
+        .as_ref()
+        .and_then(|headers| headers.inner().get(STATUS_HEADER));
+
+    let status = match value {
+        Some(AMQPValue::ShortUInt(status)) => Some(*status),
+        Some(AMQPValue::ShortInt(status)) => u16::try_from(*status).ok(),
+        Some(AMQPValue::LongUInt(status)) => u16::try_from(*status).ok(),
+        Some(AMQPValue::LongInt(status)) => u16::try_from(*status).ok(),
+        Some(AMQPValue::LongLongInt(status)) => u16::try_from(*status).ok(),
+        Some(AMQPValue::ShortString(status)) => status.as_str().parse().ok(),
+        Some(AMQPValue::LongString(status)) => {
+            String::from_utf8_lossy(status.as_bytes()).parse().ok()
+        }
+        _ => None,
+    };
+    status.unwrap_or(200)
+}
+
+#[cfg(test)]
+mod tests {
+    use lapin::types::{AMQPValue, FieldTable};
+    use lapin::BasicProperties;
+
+    use crate::amqp as sut;
+
+    #[test]
+    fn the_status_of_a_reply_is_read_from_its_header() {
+        let with_status = |value| {
+            let mut headers = FieldTable::default();
+            headers.insert(sut::STATUS_HEADER.into(), value);
+            BasicProperties::default().with_headers(headers)
+        };
+
+        assert_eq!(sut::status(&BasicProperties::default()), 200);
+        assert_eq!(sut::status(&with_status(AMQPValue::LongInt(404))), 404);
+        assert_eq!(
+            sut::status(&with_status(AMQPValue::LongString("500".into()))),
+            500
+        );
+        assert_eq!(sut::status(&with_status(AMQPValue::Boolean(true))), 200);
+    }
+}
@@ -5,9 +5,80 @@ pub enum Behaviour {
     StateMutation,
     StateIdentity,
     Property,
+    ResponseTime,
+    Payload,
+    Injection,
+    AuthBypass,
+    RoleMatrix,
+    SecurityAudit,
+    Soak,
+    Model,
+    Linearizability,
+    Differential,
+    Contract,
+    Chaos,
+    Boundary,
+    StaleCache,
+    ConditionalRequest,
+    IdempotentRetry,
 }
 
 impl Behaviour {
+    pub const ALL: [Behaviour; 21] = [
+        Behaviour::Property,
+        Behaviour::StateMutation,
+        Behaviour::StateIdentity,
+        Behaviour::ResponseEquality,
+        Behaviour::ResponseInequality,
+        Behaviour::ResponseTime,
+        Behaviour::Payload,
+        Behaviour::Injection,
+        Behaviour::AuthBypass,
+        Behaviour::RoleMatrix,
+        Behaviour::SecurityAudit,
+        Behaviour::Soak,
+        Behaviour::Model,
+        Behaviour::Linearizability,
+        Behaviour::Differential,
+        Behaviour::Contract,
+        Behaviour::Chaos,
+        Behaviour::Boundary,
+        Behaviour::StaleCache,
+        Behaviour::ConditionalRequest,
+        Behaviour::IdempotentRetry,
+    ];
+
+    /// The name of the behaviour in files, e.g. reports and configurations
+    pub fn name(&self) -> &'static str {
+        match self {
+            Behaviour::Property => "fuzz",
+            Behaviour::StateMutation => "state-mutation",
+            Behaviour::StateIdentity => "state-identity",
+            Behaviour::ResponseEquality => "response-equality",
+            Behaviour::ResponseInequality => "response-inequality",
+            Behaviour::ResponseTime => "response-time",
+            Behaviour::Payload => "payload",
+            Behaviour::Injection => "injection",
+            Behaviour::AuthBypass => "auth-bypass",
+            Behaviour::RoleMatrix => "role-matrix",
+            Behaviour::SecurityAudit => "security-audit",
+            Behaviour::Soak => "soak",
+            Behaviour::Model => "model",
+            Behaviour::Linearizability => "linearizability",
+            Behaviour::Differential => "differential",
+            Behaviour::Contract => "contract",
+            Behaviour::Chaos => "chaos",
+            Behaviour::Boundary => "boundary",
+            Behaviour::StaleCache => "stale-cache",
+            Behaviour::ConditionalRequest => "conditional-request",
+            Behaviour::IdempotentRetry => "idempotent-retry",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Behaviour> {
+        Behaviour::ALL.into_iter().find(|b| b.name() == name)
+    }
+
     // TODO: implement Display
     pub fn presentation(&self) -> String {
         match self {
@@ -16,6 +87,22 @@ impl Behaviour {
             Behaviour::StateMutation => "State mutation".to_string(),
             Behaviour::StateIdentity => "State identity".to_string(),
             Behaviour::Property => "Response Check".to_string(),
+            Behaviour::ResponseTime => "Response time".to_string(),
+            Behaviour::Payload => "Response payload".to_string(),
+            Behaviour::Injection => "Injection".to_string(),
+            Behaviour::AuthBypass => "Auth bypass".to_string(),
+            Behaviour::RoleMatrix => "Role matrix".to_string(),
+            Behaviour::SecurityAudit => "Security audit".to_string(),
+            Behaviour::Soak => "Soak".to_string(),
+            Behaviour::Model => "Model".to_string(),
+            Behaviour::Linearizability => "Linearizability".to_string(),
+            Behaviour::Differential => "Differential".to_string(),
+            Behaviour::Contract => "Contract".to_string(),
+            Behaviour::Chaos => "Chaos".to_string(),
+            Behaviour::Boundary => "Boundary values".to_string(),
+            Behaviour::StaleCache => "Stale cache".to_string(),
+            Behaviour::ConditionalRequest => "Conditional requests".to_string(),
+            Behaviour::IdempotentRetry => "Idempotent retries".to_string(),
         }
     }
 }
@@ -0,0 +1,424 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+use crate::amos::{GeneratorSpec, Operation, Parameter, Schema};
+use crate::amos_generation::{self, GeneratedOperation, GeneratedParameter, ParameterValue};
+use crate::exploration_settings::{BoundarySettings, ParameterCombinations};
+
+const EPOCH: &str = "1970-01-01T00:00:00Z";
+const FAR_FUTURE: &str = "9999-12-31T23:59:59Z";
+
+/// The combinations of the boundary values of the operation, in order, and
+///  how many combinations there are before the most combinations are taken
+pub fn combinations(
+    operation: &Operation,
+    limits: &BoundarySettings,
+) -> (Vec<GeneratedOperation>, usize) {
+    let candidates: Vec<(&Parameter, Vec<Option<ParameterValue>>)> = operation
+        .parameters
+        .iter()
+        .map(|parameter| (parameter, candidates(parameter, limits)))
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+    let sizes: Vec<usize> = candidates.iter().map(|(_, values)| values.len()).collect();
+    let rows = match limits.combinations {
+        ParameterCombinations::All => all(&sizes, limits.max_combinations),
+        ParameterCombinations::Pairwise => pairwise(&sizes),
+    };
+    let total = match limits.combinations {
+        ParameterCombinations::All => sizes.iter().fold(1usize, |n, size| n.saturating_mul(*size)),
+        ParameterCombinations::Pairwise => rows.len(),
+    };
+
+    let combinations = rows
+        .iter()
+        .take(limits.max_combinations)
+        .map(|row| GeneratedOperation {
+            name: operation.info.name.clone(),
+            parameters: candidates
+                .iter()
+                .zip(row)
+                .filter_map(|((parameter, values), idx)| {
+                    values[*idx].clone().map(|value| GeneratedParameter {
+                        name: parameter.name.clone(),
+                        value,
+                        ref_path: None,
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+
+    (combinations, total)
+}
+
+/// The indices of the values of every combination, at most `max` of them.
+///  Counts through the values, the last parameter changes the fastest.
+fn all(sizes: &[usize], max: usize) -> Vec<Vec<usize>> {
+    let mut row = vec![0; sizes.len()];
+    let mut rows = vec![];
+    while rows.len() < max {
+        rows.push(row.clone());
+
+        let mut carried = true;
+        for (idx, size) in row.iter_mut().zip(sizes).rev() {
+            *idx += 1;
+            if *idx < *size {
+                carried = false;
+                break;
+            }
+            *idx = 0;
+        }
+        if carried {
+            break;
+        }
+    }
+
+    rows
+}
+
+/// The indices of the values of combinations covering every pair of values
+///  of two parameters. Each combination starts from the first pair not yet
+///  covered, the other parameters get the value covering the most new pairs.
+fn pairwise(sizes: &[usize]) -> Vec<Vec<usize>> {
+    if sizes.len() < 2 {
+        return all(sizes, usize::MAX);
+    }
+
+    // The pairs as (parameter, value, later parameter, value)
+    let mut uncovered = BTreeSet::new();
+    for (i, size_i) in sizes.iter().enumerate() {
+        for (j, size_j) in sizes.iter().enumerate().skip(i + 1) {
+            for a in 0..*size_i {
+                for b in 0..*size_j {
+                    uncovered.insert((i, a, j, b));
+                }
+            }
+        }
+    }
+
+    let mut rows = vec![];
+    while let Some(&(i, a, j, b)) = uncovered.iter().next() {
+        let mut row = vec![None; sizes.len()];
+        row[i] = Some(a);
+        row[j] = Some(b);
+
+        for k in 0..sizes.len() {
+            if row[k].is_some() {
+                continue;
+            }
+            let covered = |value: usize| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(l, other)| {
+                        other.is_some_and(|other| {
+                            let pair = if *l < k {
+                                (*l, other, k, value)
+                            } else {
+                                (k, value, *l, other)
+                            };
+                            uncovered.contains(&pair)
+                        })
+                    })
+                    .count()
+            };
+            // The first of the values covering the most
+            row[k] = (0..sizes[k]).max_by_key(|value| (covered(*value), Reverse(*value)));
+        }
+
+        let row: Vec<usize> = row.into_iter().map(|value| value.unwrap_or(0)).collect();
+        for (i, a) in row.iter().enumerate() {
+            for (j, b) in row.iter().enumerate().skip(i + 1) {
+                uncovered.remove(&(i, *a, j, *b));
+            }
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// The values of the parameter in the combinations, an optional parameter is
+///  also left out. A parameter without boundary values has its first example,
+///  or is left out.
+fn candidates(parameter: &Parameter, limits: &BoundarySettings) -> Vec<Option<ParameterValue>> {
+    let boundaries = boundary_values(parameter, limits);
+    let mut values: Vec<Option<ParameterValue>> = if boundaries.is_empty() {
+        parameter
+            .examples
+            .iter()
+            .find_map(|example| amos_generation::example_value(&parameter.schema, example))
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        boundaries.into_iter().map(Some).collect()
+    };
+    if !parameter.required && !values.is_empty() {
+        values.push(None);
+    }
+
+    values
+}
+
+/// The boundary values of the parameter, of its enum or range generator or
+///  else of its schema
+pub fn boundary_values(parameter: &Parameter, limits: &BoundarySettings) -> Vec<ParameterValue> {
+    let of_schema = |values: &[String]| -> Vec<ParameterValue> {
+        values
+            .iter()
+            .filter_map(|value| amos_generation::example_value(&parameter.schema, value.trim()))
+            .collect()
+    };
+    match &parameter.generator {
+        Some(GeneratorSpec::Enum { values }) => return of_schema(values),
+        Some(GeneratorSpec::Range { min, max }) => {
+            return of_schema(&[min.to_string(), max.to_string()])
+        }
+        _ => (),
+    }
+
+    let longest = "x".repeat(limits.max_string_length.max(1));
+    match &parameter.schema {
+        Schema::String => strings(&["", &longest]),
+        Schema::StringNonEmpty => strings(&["x", &longest]),
+        Schema::StringDateTime | Schema::DateTime => strings(&[EPOCH, FAR_FUTURE]),
+        Schema::Int => ints(&[0, -1, i64::MIN, i64::MAX]),
+        Schema::Int32 => ints(&[0, -1, i32::MIN.into(), i32::MAX.into()]),
+        Schema::Int8 => ints(&[0, -1, i8::MIN.into(), i8::MAX.into()]),
+        Schema::Double | Schema::Number => doubles(&[0.0, -1.0, f64::MIN, f64::MAX]),
+        Schema::Float => doubles(&[0.0, -1.0, f32::MIN.into(), f32::MAX.into()]),
+        Schema::Bool => [false, true]
+            .into_iter()
+            .map(|value| ParameterValue::BoolValue {
+                value,
+                seed: 0,
+                active: false,
+            })
+            .collect(),
+        Schema::ArrayOfString => [vec![], vec![longest]]
+            .into_iter()
+            .map(|value| ParameterValue::ArrayOfString {
+                value,
+                seed: 0,
+                active: false,
+            })
+            .collect(),
+        Schema::IPV4 => [(0, 0, 0, 0), (255, 255, 255, 255)]
+            .into_iter()
+            .map(|value| ParameterValue::IPV4Value {
+                value,
+                seed: 0,
+                active: false,
+            })
+            .collect(),
+        // A regex has no boundaries of its own, nor do the references
+        Schema::StringRegex { .. }
+        | Schema::Reference(_)
+        | Schema::Object { .. }
+        | Schema::ArrayOfUniqueRefItems(_)
+        | Schema::ArrayOfRefItems(_)
+        | Schema::File
+        | Schema::Unsupported => vec![],
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<ParameterValue> {
+    values
+        .iter()
+        .map(|value| ParameterValue::StringValue {
+            value: value.to_string(),
+            seed: 0,
+            active: false,
+        })
+        .collect()
+}
+
+fn ints(values: &[i64]) -> Vec<ParameterValue> {
+    values
+        .iter()
+        .map(|value| ParameterValue::IntValue {
+            value: *value,
+            seed: 0,
+            active: false,
+        })
+        .collect()
+}
+
+fn doubles(values: &[f64]) -> Vec<ParameterValue> {
+    values
+        .iter()
+        .map(|value| ParameterValue::DoubleValue {
+            value: *value,
+            seed: 0,
+            active: false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::{
+        GeneratorSpec, Operation, OperationInfo, Parameter, ParameterOwnership, Schema,
+    };
+    use crate::amos_generation::{ParameterValue, RegexPattern};
+    use crate::boundary as sut;
+    use crate::exploration_settings::{BoundarySettings, ParameterCombinations};
+
+    fn param(name: &str, schema: Schema, required: bool) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            schema,
+            required,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        }
+    }
+
+    fn operation(parameters: Vec<Parameter>) -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: "postPerson".to_string(),
+                key: "postPerson".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters,
+            responses: vec![],
+            meta_data: None,
+        }
+    }
+
+    #[test]
+    fn boundary_values_of_the_schemas_and_generators() {
+        let limits = BoundarySettings {
+            max_string_length: 3,
+            ..BoundarySettings::new()
+        };
+        let strings: Vec<String> =
+            sut::boundary_values(&param("name", Schema::String, true), &limits)
+                .iter()
+                .map(ParameterValue::as_string_value)
+                .collect();
+        assert_eq!(strings, vec!["", "xxx"]);
+
+        let ints: Vec<i64> = sut::boundary_values(&param("age", Schema::Int32, true), &limits)
+            .iter()
+            .map(ParameterValue::as_int_value)
+            .collect();
+        assert_eq!(ints, vec![0, -1, i32::MIN as i64, i32::MAX as i64]);
+
+        let mut ranged = param("age", Schema::Int, true);
+        ranged.generator = Some(GeneratorSpec::Range { min: 1, max: 120 });
+        let ints: Vec<i64> = sut::boundary_values(&ranged, &limits)
+            .iter()
+            .map(ParameterValue::as_int_value)
+            .collect();
+        assert_eq!(ints, vec![1, 120]);
+
+        let regex = param(
+            "code",
+            Schema::StringRegex {
+                regex: RegexPattern::new("[A-Z]{3}"),
+            },
+            true,
+        );
+        assert!(sut::boundary_values(&regex, &limits).is_empty());
+    }
+
+    #[test]
+    fn combinations_of_the_boundary_values() {
+        let mut code = param(
+            "code",
+            Schema::StringRegex {
+                regex: RegexPattern::new("[A-Z]{3}"),
+            },
+            true,
+        );
+        code.examples = vec!["ABC".to_string()];
+        let op = operation(vec![
+            param("name", Schema::StringNonEmpty, true),
+            param("admin", Schema::Bool, false),
+            code,
+            param(
+                "unknown",
+                Schema::StringRegex {
+                    regex: RegexPattern::new("[a-z]+"),
+                },
+                true,
+            ),
+        ]);
+
+        let (combinations, total) = sut::combinations(&op, &BoundarySettings::new());
+        // Two names, the admin flags or none and the example code
+        assert_eq!(total, 6);
+        assert_eq!(combinations.len(), 6);
+        assert!(combinations.iter().all(|gen_op| gen_op.name == "postPerson"
+            && gen_op.parameters.iter().all(|p| p.name != "unknown")));
+        assert_eq!(
+            combinations[2]
+                .parameters
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["name", "code"]
+        );
+        assert_eq!(
+            combinations[3].parameters[0].value.as_string_value().len(),
+            255
+        );
+
+        let (combinations, total) = sut::combinations(
+            &op,
+            &BoundarySettings {
+                max_combinations: 4,
+                ..BoundarySettings::new()
+            },
+        );
+        assert_eq!((combinations.len(), total), (4, 6));
+    }
+
+    #[test]
+    fn pairwise_combinations_cover_every_pair() {
+        let sizes = [3, 3, 3, 3, 2];
+        let rows = sut::pairwise(&sizes);
+
+        for (i, size_i) in sizes.iter().enumerate() {
+            for (j, size_j) in sizes.iter().enumerate().skip(i + 1) {
+                for a in 0..*size_i {
+                    for b in 0..*size_j {
+                        assert!(
+                            rows.iter().any(|row| row[i] == a && row[j] == b),
+                            "{}={} and {}={} are not combined",
+                            i,
+                            a,
+                            j,
+                            b
+                        );
+                    }
+                }
+            }
+        }
+        // Far fewer than the 162 of all combinations
+        assert!(rows.len() <= 15, "{} combinations", rows.len());
+
+        let op = operation(
+            ["a", "b", "c", "d", "e", "f"]
+                .iter()
+                .map(|name| param(name, Schema::Bool, false))
+                .collect(),
+        );
+        let (combinations, total) = sut::combinations(
+            &op,
+            &BoundarySettings {
+                combinations: ParameterCombinations::Pairwise,
+                ..BoundarySettings::new()
+            },
+        );
+        assert_eq!(combinations.len(), total);
+        assert!(total < 3usize.pow(6));
+    }
+}
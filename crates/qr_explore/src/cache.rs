@@ -0,0 +1,557 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use qr_http_resource::http::{HTTPCall, HTTPMethod, HTTPResult, HTTPStatus};
+
+/// How the client cache took part in the response of an invocation
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum CacheOutcome {
+    /// Served from the cache, without a request, as it was still fresh
+    Fresh,
+    /// The target answered the revalidation with 304, the cached response was
+    ///  used
+    NotModified,
+    /// The state cached before the mutation of the operation was served
+    ///  again, instead of the state after it
+    Stale { mutation: String },
+}
+
+/// The directives of a Cache-Control header the client honors
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheControl {
+    pub no_store: bool,
+    /// Stored, but revalidated before each use
+    pub no_cache: bool,
+    pub max_age: Option<u64>,
+}
+
+impl CacheControl {
+    /// The directives of the headers, with lower case names
+    pub fn parse(headers: &[(String, String)]) -> Self {
+        let mut control = CacheControl::default();
+        let directives = headers
+            .iter()
+            .filter(|(name, _)| name == "cache-control")
+            .flat_map(|(_, value)| value.split(','));
+        for directive in directives {
+            let directive = directive.trim().to_lowercase();
+            match directive.split_once('=') {
+                Some(("max-age", age)) => control.max_age = age.trim_matches('"').parse().ok(),
+                None if directive == "no-store" => control.no_store = true,
+                None if directive == "no-cache" => control.no_cache = true,
+                _ => (),
+            }
+        }
+        control
+    }
+
+    fn freshness(&self) -> Duration {
+        match self.max_age {
+            Some(age) if !self.no_cache => Duration::from_secs(age),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    etag: Option<String>,
+    payload: String,
+    size: usize,
+    content_encoding: Option<String>,
+    headers: Vec<(String, String)>,
+    stored_at: Instant,
+    freshness: Duration,
+    /// The mutation of the resource since the response was stored, it is
+    ///  revalidated before it is used again
+    mutation: Option<Mutation>,
+}
+
+impl CacheEntry {
+    fn new(response: &HTTPResult) -> Self {
+        CacheEntry {
+            etag: header(&response.headers, "etag").map(str::to_string),
+            payload: response.payload.clone(),
+            size: response.size,
+            content_encoding: response.content_encoding.clone(),
+            headers: response.headers.clone(),
+            stored_at: Instant::now(),
+            freshness: CacheControl::parse(&response.headers).freshness(),
+            mutation: None,
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.mutation.is_none() && self.stored_at.elapsed() < self.freshness
+    }
+
+    fn response(&self) -> HTTPResult {
+        HTTPResult {
+            status: HTTPStatus::OK,
+            payload: self.payload.clone(),
+            success: true,
+            size: self.size,
+            content_encoding: self.content_encoding.clone(),
+            headers: self.headers.clone(),
+        }
+    }
+
+    /// If the response is the state of the entry from before the mutation
+    fn serves_old_state(&self, mutation: &Mutation, response: &HTTPResult) -> bool {
+        match response.status {
+            HTTPStatus::NotModified => self.changed_by(mutation),
+            HTTPStatus::OK => {
+                let same_etag = self.etag.is_some()
+                    && self.etag.as_deref() == header(&response.headers, "etag");
+                (same_etag && self.changed_by(mutation))
+                    || (mutation.deleted && self.payload == response.payload)
+            }
+            _ => false,
+        }
+    }
+
+    /// If the mutation changed the stored representation, a PUT only if one
+    ///  of the fields it set differs from the stored one
+    fn changed_by(&self, mutation: &Mutation) -> bool {
+        let Some(fields) = &mutation.fields else {
+            return true;
+        };
+        let Ok(serde_json::Value::Object(stored)) = serde_json::from_str(&self.payload) else {
+            return false;
+        };
+        fields.iter().any(|(name, value)| {
+            stored
+                .get(name)
+                .is_some_and(|stored| text(stored) != *value)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Mutation {
+    operation: String,
+    /// The resource of the entry was deleted, not only changed
+    deleted: bool,
+    /// The fields a PUT set, from its body or else from its response, none
+    ///  of another method
+    fields: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug)]
+struct PendingRequest {
+    method: HTTPMethod,
+    url: String,
+    fresh: bool,
+    body: Option<HashMap<String, String>>,
+}
+
+/// A private client cache of the responses of a sequence (RFC 9111). The
+///  responses to GET are stored by their URL, fresh responses are served
+///  without a request and the others are revalidated with their ETag. A
+///  successful POST, PUT or DELETE invalidates its URL and the URLs below it.
+#[derive(Debug, Default)]
+pub struct ClientCache {
+    entries: HashMap<String, CacheEntry>,
+    pending: Option<PendingRequest>,
+}
+
+impl ClientCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the responses, each sequence starts from an empty cache
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.pending = None;
+    }
+
+    /// Prepare the request before it is sent. A fresh response is returned
+    ///  to serve instead of sending the request, otherwise the ETag of a
+    ///  stored response is added to revalidate it.
+    pub fn prepare(&mut self, request: &mut HTTPCall) -> Option<HTTPResult> {
+        let entry = match request.method {
+            HTTPMethod::GET => self.entries.get(&request.url),
+            _ => None,
+        };
+        let fresh = entry.is_some_and(CacheEntry::is_fresh);
+        self.pending = Some(PendingRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            fresh,
+            body: request.parameters.body.clone(),
+        });
+
+        let entry = entry?;
+        if fresh {
+            return Some(entry.response());
+        }
+        if let Some(etag) = &entry.etag {
+            request
+                .headers
+                .push(("If-None-Match".to_string(), etag.clone()));
+        }
+        None
+    }
+
+    /// The response of the prepared request of the operation, as the client
+    ///  uses it, and how the cache took part in it
+    pub fn complete(
+        &mut self,
+        operation: &str,
+        response: HTTPResult,
+    ) -> (HTTPResult, Option<CacheOutcome>) {
+        let Some(pending) = self.pending.take() else {
+            return (response, None);
+        };
+        if pending.fresh {
+            return (response, Some(CacheOutcome::Fresh));
+        }
+
+        match pending.method {
+            HTTPMethod::GET => self.complete_query(&pending.url, response),
+            _ => {
+                // Any response but an error is a mutation of the resource
+                if response.success {
+                    let fields = (pending.method == HTTPMethod::PUT).then(|| {
+                        pending
+                            .body
+                            .unwrap_or_else(|| response_fields(&response.payload))
+                    });
+                    let mutation = Mutation {
+                        operation: operation.to_string(),
+                        deleted: pending.method == HTTPMethod::DELETE,
+                        fields,
+                    };
+                    self.invalidate(&pending.url, mutation);
+                }
+                (response, None)
+            }
+        }
+    }
+
+    fn complete_query(
+        &mut self,
+        url: &str,
+        response: HTTPResult,
+    ) -> (HTTPResult, Option<CacheOutcome>) {
+        let stale = self.entries.get(url).and_then(|entry| {
+            let mutation = entry.mutation.as_ref()?;
+            entry
+                .serves_old_state(mutation, &response)
+                .then(|| mutation.operation.clone())
+        });
+
+        if response.status == HTTPStatus::NotModified {
+            if let Some(entry) = self.entries.get_mut(url) {
+                // The headers of the 304 update those of the stored response
+                for (name, value) in &response.headers {
+                    entry.headers.retain(|(stored, _)| stored != name);
+                    entry.headers.push((name.clone(), value.clone()));
+                }
+                entry.stored_at = Instant::now();
+                entry.freshness = CacheControl::parse(&entry.headers).freshness();
+                let served = entry.response();
+                let outcome = match stale {
+                    Some(mutation) => CacheOutcome::Stale { mutation },
+                    None => CacheOutcome::NotModified,
+                };
+                return (served, Some(outcome));
+            }
+            return (response, None);
+        }
+
+        if response.status == HTTPStatus::OK && !CacheControl::parse(&response.headers).no_store {
+            self.entries
+                .insert(url.to_string(), CacheEntry::new(&response));
+        } else {
+            self.entries.remove(url);
+        }
+
+        let outcome = stale.map(|mutation| CacheOutcome::Stale { mutation });
+        (response, outcome)
+    }
+
+    /// Mark the response of the URL as mutated and revalidate the responses
+    ///  of the URLs below it
+    fn invalidate(&mut self, url: &str, mutation: Mutation) {
+        for (stored, entry) in self.entries.iter_mut() {
+            let below = stored
+                .strip_prefix(url)
+                .is_some_and(|rest| rest.starts_with(['/', '?']));
+            if stored == url {
+                entry.mutation = Some(mutation.clone());
+            } else if below {
+                // Only the representation of the URL is known to be changed,
+                //  the others are not flagged if they are served unchanged
+                entry.freshness = Duration::ZERO;
+            }
+        }
+    }
+}
+
+/// The fields of a JSON object response, none of another payload
+fn response_fields(payload: &str) -> HashMap<String, String> {
+    match serde_json::from_str(payload) {
+        Ok(serde_json::Value::Object(object)) => object
+            .iter()
+            .map(|(name, value)| (name.clone(), text(value)))
+            .collect(),
+        _ => HashMap::new(),
+    }
+}
+
+/// The value as the fields of a body are sent, a string without its quotes
+fn text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::{HTTPCall, HTTPMethod, HTTPParameters, HTTPResult, HTTPStatus};
+
+    use crate::cache as sut;
+
+    fn call(method: HTTPMethod, url: &str) -> HTTPCall {
+        HTTPCall {
+            url: url.to_string(),
+            method,
+            parameters: HTTPParameters {
+                url: url.to_string(),
+                form_data: None,
+                file_data: None,
+                body: None,
+            },
+            headers: vec![],
+        }
+    }
+
+    fn put(url: &str, body: &[(&str, &str)]) -> HTTPCall {
+        let mut call = call(HTTPMethod::PUT, url);
+        call.parameters.body = Some(
+            body.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        );
+        call
+    }
+
+    fn response(status: HTTPStatus, payload: &str, headers: &[(&str, &str)]) -> HTTPResult {
+        HTTPResult {
+            success: matches!(status, HTTPStatus::OK | HTTPStatus::NoContent),
+            status,
+            payload: payload.to_string(),
+            size: payload.len(),
+            content_encoding: None,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn cache_control_directives() {
+        let headers = vec![(
+            "cache-control".to_string(),
+            "public, Max-Age=60, no-cache".to_string(),
+        )];
+        assert_eq!(
+            sut::CacheControl::parse(&headers),
+            sut::CacheControl {
+                no_store: false,
+                no_cache: true,
+                max_age: Some(60),
+            }
+        );
+        assert_eq!(sut::CacheControl::parse(&[]), sut::CacheControl::default());
+    }
+
+    #[test]
+    fn responses_are_served_fresh_and_revalidated() {
+        let mut cache = sut::ClientCache::new();
+        let url = "http://localhost:8080/items/1";
+
+        let mut get = call(HTTPMethod::GET, url);
+        assert!(cache.prepare(&mut get).is_none());
+        let (_, outcome) = cache.complete(
+            "get_item",
+            response(
+                HTTPStatus::OK,
+                "{\"a\":1}",
+                &[("etag", "\"v1\""), ("cache-control", "max-age=60")],
+            ),
+        );
+        assert_eq!(outcome, None);
+
+        // Fresh, not sent again
+        let served = cache.prepare(&mut call(HTTPMethod::GET, url)).unwrap();
+        assert_eq!(served.payload, "{\"a\":1}");
+        let (_, outcome) = cache.complete("get_item", served);
+        assert_eq!(outcome, Some(sut::CacheOutcome::Fresh));
+
+        // A mutation of the item, the stored response is revalidated
+        cache.prepare(&mut put(url, &[("a", "2")]));
+        cache.complete("put_item", response(HTTPStatus::NoContent, "", &[]));
+
+        let mut get = call(HTTPMethod::GET, url);
+        assert!(cache.prepare(&mut get).is_none());
+        assert_eq!(
+            get.headers,
+            vec![("If-None-Match".to_string(), "\"v1\"".to_string())]
+        );
+
+        // The target answers that nothing was modified by the mutation
+        let (served, outcome) = cache.complete(
+            "get_item",
+            response(HTTPStatus::NotModified, "", &[("etag", "\"v1\"")]),
+        );
+        assert_eq!(served.status, HTTPStatus::OK);
+        assert_eq!(served.payload, "{\"a\":1}");
+        assert_eq!(
+            outcome,
+            Some(sut::CacheOutcome::Stale {
+                mutation: "put_item".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn a_new_state_after_a_mutation_is_not_stale() {
+        let mut cache = sut::ClientCache::new();
+        let url = "http://localhost:8080/items";
+
+        cache.prepare(&mut call(HTTPMethod::GET, url));
+        cache.complete(
+            "get_items",
+            response(HTTPStatus::OK, "[]", &[("etag", "\"v1\"")]),
+        );
+
+        // Without a freshness the stored response is revalidated
+        cache.prepare(&mut call(HTTPMethod::GET, url));
+        let (_, outcome) = cache.complete("get_items", response(HTTPStatus::NotModified, "", &[]));
+        assert_eq!(outcome, Some(sut::CacheOutcome::NotModified));
+
+        cache.prepare(&mut call(HTTPMethod::POST, url));
+        cache.complete("post_item", response(HTTPStatus::OK, "{}", &[]));
+
+        cache.prepare(&mut call(HTTPMethod::GET, url));
+        let (_, outcome) = cache.complete(
+            "get_items",
+            response(HTTPStatus::OK, "[{}]", &[("etag", "\"v2\"")]),
+        );
+        assert_eq!(outcome, None);
+
+        // The deleted item served with its old state
+        let item = "http://localhost:8080/items/1";
+        cache.prepare(&mut call(HTTPMethod::GET, item));
+        cache.complete("get_item", response(HTTPStatus::OK, "{}", &[]));
+        cache.prepare(&mut call(HTTPMethod::DELETE, item));
+        cache.complete("delete_item", response(HTTPStatus::NoContent, "", &[]));
+        cache.prepare(&mut call(HTTPMethod::GET, item));
+        let (_, outcome) = cache.complete("get_item", response(HTTPStatus::OK, "{}", &[]));
+        assert_eq!(
+            outcome,
+            Some(sut::CacheOutcome::Stale {
+                mutation: "delete_item".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn a_url_below_a_mutation_is_revalidated_but_not_stale() {
+        let mut cache = sut::ClientCache::new();
+        let item = "http://localhost:8080/items/1";
+
+        cache.prepare(&mut call(HTTPMethod::GET, item));
+        cache.complete(
+            "get_item",
+            response(
+                HTTPStatus::OK,
+                "{\"a\":1}",
+                &[("etag", "\"v1\""), ("cache-control", "max-age=60")],
+            ),
+        );
+
+        cache.prepare(&mut call(HTTPMethod::POST, "http://localhost:8080/items"));
+        cache.complete("post_item", response(HTTPStatus::OK, "{}", &[]));
+
+        // No longer served fresh, but the unchanged item is not stale
+        let mut get = call(HTTPMethod::GET, item);
+        assert!(cache.prepare(&mut get).is_none());
+        assert_eq!(
+            get.headers,
+            vec![("If-None-Match".to_string(), "\"v1\"".to_string())]
+        );
+        let (served, outcome) = cache.complete(
+            "get_item",
+            response(HTTPStatus::NotModified, "", &[("etag", "\"v1\"")]),
+        );
+        assert_eq!(served.payload, "{\"a\":1}");
+        assert_eq!(outcome, Some(sut::CacheOutcome::NotModified));
+    }
+
+    #[test]
+    fn only_a_put_that_changed_the_state_is_stale() {
+        let mut cache = sut::ClientCache::new();
+        let url = "http://localhost:8080/items/1";
+        let revalidated = |cache: &mut sut::ClientCache| {
+            cache.prepare(&mut call(HTTPMethod::GET, url));
+            cache
+                .complete(
+                    "get_item",
+                    response(HTTPStatus::NotModified, "", &[("etag", "\"v1\"")]),
+                )
+                .1
+        };
+
+        cache.prepare(&mut call(HTTPMethod::GET, url));
+        cache.complete(
+            "get_item",
+            response(
+                HTTPStatus::OK,
+                "{\"a\":1,\"b\":\"x\"}",
+                &[("etag", "\"v1\"")],
+            ),
+        );
+
+        // A PUT of the stored values changes nothing
+        cache.prepare(&mut put(url, &[("a", "1"), ("b", "x")]));
+        cache.complete("put_item", response(HTTPStatus::NoContent, "", &[]));
+        assert_eq!(
+            revalidated(&mut cache),
+            Some(sut::CacheOutcome::NotModified)
+        );
+
+        // A failed PUT is no mutation
+        cache.prepare(&mut put(url, &[("a", "2")]));
+        cache.complete("put_item", response(HTTPStatus::Conflict, "", &[]));
+        assert_eq!(
+            revalidated(&mut cache),
+            Some(sut::CacheOutcome::NotModified)
+        );
+
+        // Without a body the response tells what was set
+        cache.prepare(&mut call(HTTPMethod::PUT, url));
+        cache.complete(
+            "put_item",
+            response(HTTPStatus::OK, "{\"a\":2,\"b\":\"x\"}", &[]),
+        );
+        assert_eq!(
+            revalidated(&mut cache),
+            Some(sut::CacheOutcome::Stale {
+                mutation: "put_item".to_string()
+            })
+        );
+    }
+}
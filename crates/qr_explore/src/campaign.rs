@@ -0,0 +1,259 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::amos::{InvokeResult, Operation, ResultMetaData};
+use crate::amos_generation::{GenerationOperationWithParameters, ParameterValue};
+
+pub type SharedCampaign = Arc<Mutex<CampaignCorpus>>;
+
+/// A sequence of the corpus, with the statuses it was the first to produce
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CorpusEntry {
+    pub sequence: Vec<GenerationOperationWithParameters>,
+    /// The operations and the statuses they responded with
+    pub statuses: Vec<(String, String)>,
+}
+
+/// The sequences of a campaign that produced a status of an operation not
+///  seen before them, each saved to a file of the directory. The sequences
+///  of the earlier explorations seed the generation of the next ones,
+///  before any sequence is generated at random.
+#[derive(Debug)]
+pub struct CampaignCorpus {
+    dir: PathBuf,
+    seen: HashSet<(String, String)>,
+    seeds: VecDeque<Vec<GenerationOperationWithParameters>>,
+    entries: usize,
+}
+
+impl CampaignCorpus {
+    /// The corpus of the directory, created if it does not exist
+    pub fn load(dir: &Path) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let mut files = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<PathBuf>>>()?;
+        files.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        files.sort();
+
+        let mut corpus = CampaignCorpus {
+            dir: dir.to_path_buf(),
+            seen: HashSet::new(),
+            seeds: VecDeque::new(),
+            entries: files.len(),
+        };
+        for path in files {
+            let entry: CorpusEntry = serde_json::from_slice(&std::fs::read(&path)?)
+                .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))?;
+            corpus.seen.extend(entry.statuses);
+            corpus.seeds.push_back(entry.sequence);
+        }
+
+        Ok(corpus)
+    }
+
+    /// The number of sequences left to seed the generation
+    pub fn seeds(&self) -> usize {
+        self.seeds.len()
+    }
+
+    /// The next seed of only operations among the operations, with their
+    ///  current definitions. Seeds of other operations are kept for the
+    ///  explorations of them.
+    pub fn next_seed(
+        &mut self,
+        ops: &[Operation],
+    ) -> Option<Vec<GenerationOperationWithParameters>> {
+        let current = |(op, values): &GenerationOperationWithParameters| {
+            ops.iter()
+                .find(|current| {
+                    current.info.name == op.info.name && current.parameters.len() == values.len()
+                })
+                .map(|current| (current.clone(), values.clone()))
+        };
+
+        let (idx, seed) = self.seeds.iter().enumerate().find_map(|(idx, seed)| {
+            seed.iter()
+                .map(current)
+                .collect::<Option<Vec<GenerationOperationWithParameters>>>()
+                .map(|seed| (idx, seed))
+        })?;
+        self.seeds.remove(idx);
+
+        Some(seed)
+    }
+
+    /// Save the sequence, if its results have a status of an operation not
+    ///  seen before. Returns if it was saved.
+    pub fn record(
+        &mut self,
+        sequence: &[GenerationOperationWithParameters],
+        results: &[InvokeResult],
+    ) -> std::io::Result<bool> {
+        let statuses: Vec<(String, String)> = results
+            .iter()
+            .filter_map(|result| {
+                let ResultMetaData::HTTP { status, .. } = result.meta_data.as_ref()?;
+                Some((result.operation.name.clone(), status.to_string()))
+            })
+            .filter(|status| !self.seen.contains(status))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if sequence.is_empty() || statuses.is_empty() {
+            return Ok(false);
+        }
+
+        // The references are generated anew when the sequence is replayed
+        let entry = CorpusEntry {
+            sequence: sequence
+                .iter()
+                .map(|(op, values)| (op.clone(), values.iter().map(plain_value).collect()))
+                .collect(),
+            statuses,
+        };
+        let path = self.dir.join(format!("{:06}.json", self.entries));
+        std::fs::write(&path, serde_json::to_vec_pretty(&entry)?)?;
+
+        self.entries += 1;
+        self.seen.extend(entry.statuses);
+        Ok(true)
+    }
+}
+
+/// The value a reference falls back to, any other value as it is
+fn plain_value(value: &ParameterValue) -> ParameterValue {
+    match value {
+        ParameterValue::Reference { fallback, .. } => plain_value(fallback),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::HTTPStatus;
+
+    use crate::amos::{InvokeResult, Operation, OperationInfo, ResultMetaData};
+    use crate::amos_generation::{GeneratedOperation, ParameterValue};
+    use crate::amos_relations::{Relation, RelationInfo};
+    use crate::campaign as sut;
+
+    fn op(name: &str) -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        }
+    }
+
+    fn result(name: &str, status: HTTPStatus) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            String::new(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/".to_string(),
+                status,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn sequences_with_new_statuses_seed_the_next_campaign() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut corpus = sut::CampaignCorpus::load(dir.path()).unwrap();
+        assert_eq!(corpus.seeds(), 0);
+
+        let post = vec![(op("post_person"), vec![])];
+        assert!(corpus
+            .record(&post, &[result("post_person", HTTPStatus::OK)])
+            .unwrap());
+        // The status is seen already
+        assert!(!corpus
+            .record(&post, &[result("post_person", HTTPStatus::OK)])
+            .unwrap());
+
+        let mut delete = op("delete_person");
+        delete.parameters = vec![crate::amos::Parameter {
+            name: "id".to_string(),
+            schema: crate::amos::Schema::Int,
+            required: true,
+            ownership: crate::amos::ParameterOwnership::Dependency,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        }];
+        let id = ParameterValue::IntValue {
+            value: 7,
+            seed: 0,
+            active: true,
+        };
+        let reference = ParameterValue::Reference {
+            active: true,
+            idx: [0, 0],
+            relation: Relation::Response(RelationInfo {
+                operation: "post_person".to_string(),
+                name: "id".to_string(),
+                schema: crate::amos::Schema::Int,
+                strength: 1,
+                op_idx: 0,
+                idx: 0,
+                field: None,
+                reasons: vec![],
+            }),
+            fallback: Box::new(id.clone()),
+        };
+        let sequence = vec![
+            (op("post_person"), vec![]),
+            (delete.clone(), vec![reference]),
+        ];
+        assert!(corpus
+            .record(
+                &sequence,
+                &[
+                    result("post_person", HTTPStatus::OK),
+                    result("delete_person", HTTPStatus::NotFound)
+                ]
+            )
+            .unwrap());
+
+        // The next campaign replays the sequences, the seeds of operations it
+        //  does not explore are kept
+        let mut corpus = sut::CampaignCorpus::load(dir.path()).unwrap();
+        assert_eq!(corpus.seeds(), 2);
+        let seed = corpus.next_seed(&[delete.clone()]);
+        assert_eq!(corpus.seeds(), 2);
+        assert_eq!(seed, None);
+
+        let seed = corpus
+            .next_seed(&[op("post_person"), delete.clone()])
+            .unwrap();
+        assert_eq!(seed, vec![(op("post_person"), vec![])]);
+        let seed = corpus
+            .next_seed(&[op("post_person"), delete.clone()])
+            .unwrap();
+        assert_eq!(seed[1], (delete, vec![id]));
+        assert_eq!(corpus.seeds(), 0);
+
+        // Statuses of earlier campaigns are not new
+        assert!(!corpus
+            .record(&post, &[result("post_person", HTTPStatus::OK)])
+            .unwrap());
+    }
+}
@@ -0,0 +1,285 @@
+use std::cell::{Cell, RefCell};
+
+use proptest::prelude::Rng;
+use proptest::test_runner::{RngAlgorithm, TestRng};
+use qr_http_resource::http::HTTPStatus;
+
+use crate::amos::{InvokeResult, ResultMetaData};
+use crate::contract::{Contract, ContractViolation};
+use crate::exploration_settings::{ChaosSettings, StatusClass};
+
+/// A fault injected before a request
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ChaosFault {
+    Delay { ms: u64 },
+    Drop,
+    Repeat,
+}
+
+impl std::fmt::Display for ChaosFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChaosFault::Delay { ms } => write!(f, "delayed {} ms", ms),
+            ChaosFault::Drop => write!(f, "dropped"),
+            ChaosFault::Repeat => write!(f, "repeated"),
+        }
+    }
+}
+
+/// The fault injected before the request of the operation at the step
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct InjectedFault {
+    pub step: usize,
+    pub operation: String,
+    pub fault: ChaosFault,
+}
+
+impl std::fmt::Display for InjectedFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Step {} ({}): {}",
+            self.step + 1,
+            self.operation,
+            self.fault
+        )
+    }
+}
+
+/// Why the SUT was not consistent after the faults
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Inconsistency {
+    ServerError {
+        operation: String,
+        status: String,
+    },
+    /// The last response of the query operation did not conform to the first
+    QueryChanged {
+        violations: Vec<ContractViolation>,
+    },
+}
+
+impl std::fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Inconsistency::ServerError { operation, status } => {
+                write!(f, "{} responded {}", operation, status)
+            }
+            Inconsistency::QueryChanged { violations } => write!(
+                f,
+                "{}",
+                violations
+                    .iter()
+                    .map(|violation| violation.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Draws the faults injected into the sequences, with a generator of its
+///  own for the runner is borrowed while the sequences are invoked. Once a
+///  request is dropped the rest of the sequence is dropped, as by a client
+///  that went away.
+#[derive(Debug)]
+pub struct ChaosHooks {
+    settings: ChaosSettings,
+    rng: RefCell<TestRng>,
+    dropping: Cell<bool>,
+    faults: RefCell<Vec<InjectedFault>>,
+}
+
+impl ChaosHooks {
+    pub fn new(settings: ChaosSettings, seed: u64) -> Self {
+        // ChaCha takes a 32 byte seed
+        let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed.to_le_bytes().repeat(4));
+
+        Self {
+            settings,
+            rng: RefCell::new(rng),
+            dropping: Cell::new(false),
+            faults: RefCell::new(vec![]),
+        }
+    }
+
+    /// Forget the faults of the last sequence
+    pub fn start_sequence(&self) {
+        self.dropping.set(false);
+        self.faults.borrow_mut().clear();
+    }
+
+    /// The fault to inject before the request of the operation, if any
+    pub fn next_fault(&self, step: usize, operation: &str) -> Option<ChaosFault> {
+        let mut rng = self.rng.borrow_mut();
+        let fault = if self.dropping.get() || chance(&mut rng, self.settings.drop_probability) {
+            self.dropping.set(true);
+            ChaosFault::Drop
+        } else if chance(&mut rng, self.settings.repeat_probability) {
+            ChaosFault::Repeat
+        } else if self.settings.max_delay_ms > 0
+            && chance(&mut rng, self.settings.delay_probability)
+        {
+            ChaosFault::Delay {
+                ms: rng.gen_range(1..=self.settings.max_delay_ms),
+            }
+        } else {
+            return None;
+        };
+
+        self.faults.borrow_mut().push(InjectedFault {
+            step,
+            operation: operation.to_string(),
+            fault,
+        });
+        Some(fault)
+    }
+
+    /// The faults injected into the sequence since it was started
+    pub fn faults(&self) -> Vec<InjectedFault> {
+        self.faults.borrow().clone()
+    }
+}
+
+/// A probability out of range is clamped to it
+fn chance(rng: &mut TestRng, probability: f64) -> bool {
+    rng.gen_bool(probability.clamp(0.0, 1.0))
+}
+
+/// How the SUT was not consistent despite the faults of the client, if it
+///  was not. No operation may fail with a server error, and the last
+///  response of the query operation must have the status and the shape of
+///  the first. The dropped requests have no response to check.
+pub fn inconsistency(query: &str, results: &[InvokeResult]) -> Option<Inconsistency> {
+    let server_error = results.iter().find_map(|result| match &result.meta_data {
+        Some(ResultMetaData::HTTP { status, .. }) if StatusClass::ServerError.includes(status) => {
+            Some(Inconsistency::ServerError {
+                operation: result.operation.name.clone(),
+                status: status.to_string(),
+            })
+        }
+        _ => None,
+    });
+    if server_error.is_some() {
+        return server_error;
+    }
+
+    let mut queries = results
+        .iter()
+        .filter(|result| result.operation.name == query && status_of(result).is_some());
+    let (Some(first), Some(last)) = (queries.next(), queries.next_back()) else {
+        return None;
+    };
+
+    let mut contract = Contract::new();
+    contract.record(std::slice::from_ref(first));
+    let violations = contract.check(std::slice::from_ref(last));
+    if violations.is_empty() {
+        None
+    } else {
+        Some(Inconsistency::QueryChanged { violations })
+    }
+}
+
+fn status_of(result: &InvokeResult) -> Option<&HTTPStatus> {
+    let ResultMetaData::HTTP { status, .. } = result.meta_data.as_ref()?;
+    Some(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::{HTTPStatus, TransportError};
+
+    use crate::amos::{InvokeResult, ResultMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::chaos as sut;
+    use crate::chaos::{ChaosFault, Inconsistency};
+    use crate::exploration_settings::ChaosSettings;
+
+    fn op(name: &str) -> GeneratedOperation {
+        GeneratedOperation {
+            name: name.to_string(),
+            parameters: vec![],
+        }
+    }
+
+    fn result(name: &str, status: HTTPStatus, payload: &str) -> InvokeResult {
+        InvokeResult::new(
+            op(name),
+            payload.to_string(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/".to_string(),
+                status,
+                size: payload.len(),
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn the_rest_of_the_sequence_is_dropped() {
+        let hooks = sut::ChaosHooks::new(
+            ChaosSettings {
+                drop_probability: 1.0,
+                ..ChaosSettings::new()
+            },
+            7,
+        );
+
+        hooks.start_sequence();
+        assert_eq!(hooks.next_fault(0, "post_person"), Some(ChaosFault::Drop));
+        assert_eq!(hooks.next_fault(1, "put_person"), Some(ChaosFault::Drop));
+        assert_eq!(hooks.faults().len(), 2);
+
+        hooks.start_sequence();
+        assert_eq!(hooks.faults(), vec![]);
+
+        let hooks = sut::ChaosHooks::new(
+            ChaosSettings {
+                delay_probability: 1.0,
+                max_delay_ms: 5,
+                ..ChaosSettings::new()
+            },
+            7,
+        );
+        let Some(ChaosFault::Delay { ms }) = hooks.next_fault(0, "post_person") else {
+            panic!("Expected a delay, got: {:?}", hooks.faults());
+        };
+        assert!((1..=5).contains(&ms));
+    }
+
+    #[test]
+    fn the_query_responds_the_same_despite_the_faults() {
+        let first = result("get_persons", HTTPStatus::OK, r#"[{"id":1}]"#);
+        let dropped = InvokeResult::transport_failure(op("post_person"), TransportError::Dropped);
+
+        let results = vec![
+            first.clone(),
+            dropped.clone(),
+            result("get_persons", HTTPStatus::OK, r#"[{"id":1},{"id":2}]"#),
+        ];
+        assert_eq!(sut::inconsistency("get_persons", &results), None);
+
+        let results = vec![
+            first.clone(),
+            result("post_person", HTTPStatus::InternalServerError, ""),
+            result("get_persons", HTTPStatus::OK, r#"[{"id":1}]"#),
+        ];
+        assert!(matches!(
+            sut::inconsistency("get_persons", &results),
+            Some(Inconsistency::ServerError { operation, .. }) if operation == "post_person"
+        ));
+
+        let results = vec![
+            first,
+            dropped,
+            result("get_persons", HTTPStatus::OK, r#"[{"id":"1"}]"#),
+        ];
+        assert!(matches!(
+            sut::inconsistency("get_persons", &results),
+            Some(Inconsistency::QueryChanged { violations }) if violations[0].path == "/0/id"
+        ));
+    }
+}
@@ -0,0 +1,512 @@
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use percent_encoding::percent_decode_str;
+use qr_http_resource::http::{self, HTTPCall, HTTPMethod, HTTPStatus, TransportError};
+use tracing::{debug, warn};
+
+use crate::amos::{InvokeResult, Operation, ResultMetaData};
+use crate::amos_generation::GeneratedOperation;
+use crate::explore::{self, ExplorationContext, Target};
+use crate::http_translation::translate_generated_operation_to_http_call;
+
+pub const DEFAULT_PORT: u16 = 5683;
+
+/// How long the acknowledgement of a request is waited for before it is sent
+///  again, each time twice as long
+pub const ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+const MAX_RETRANSMIT: u32 = 2;
+const VERSION: u8 = 1;
+const PAYLOAD_MARKER: u8 = 0xff;
+
+const OPTION_URI_PATH: u16 = 11;
+const OPTION_CONTENT_FORMAT: u16 = 12;
+const OPTION_URI_QUERY: u16 = 15;
+const CONTENT_FORMAT_JSON: u8 = 50;
+
+/// The CoAP server the operations are sent to, the operations of the
+///  specification map to its resources and methods as to those of HTTP
+#[derive(Debug, Clone)]
+pub struct CoapConfiguration {
+    pub host: String,
+    pub port: u16,
+    /// The path the resources are relative to, e.g. '/api'
+    pub base_path: Option<String>,
+    pub ack_timeout: Duration,
+}
+
+impl CoapConfiguration {
+    pub fn new(host: String, port: u16) -> Self {
+        Self {
+            host,
+            port,
+            base_path: None,
+            ack_timeout: ACK_TIMEOUT,
+        }
+    }
+
+    /// The configuration of a URL such as 'coap://localhost:5683'
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid CoAP URL '{url}'");
+        let parsed = reqwest::Url::parse(url).map_err(|_| invalid())?;
+        if parsed.scheme() != "coap" {
+            return Err(invalid());
+        }
+        let host = parsed.host_str().ok_or_else(invalid)?.to_string();
+
+        Ok(Self::new(host, parsed.port().unwrap_or(DEFAULT_PORT)))
+    }
+
+    pub fn with_base_path(mut self, base_path: Option<String>) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    pub fn with_ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.ack_timeout = ack_timeout;
+        self
+    }
+
+    /// The operations are translated as for HTTP, then sent as CoAP requests
+    fn http_configuration(&self) -> http::HTTPConfiguration {
+        http::HTTPConfiguration::new(self.host.clone(), self.port, http::Protocol::HTTP)
+            .with_base_path(self.base_path.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageType {
+    Confirmable = 0,
+    NonConfirmable = 1,
+    Acknowledgement = 2,
+    Reset = 3,
+}
+
+/// A CoAP message as of RFC 7252
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub kind: MessageType,
+    /// The class in the upper 3 bits and the detail in the lower 5, e.g.
+    ///  0.01 for GET and 2.05 for Content
+    pub code: u8,
+    pub message_id: u16,
+    pub token: Vec<u8>,
+    /// By their number, in the order they are encoded
+    pub options: Vec<(u16, Vec<u8>)>,
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            VERSION << 6 | (self.kind as u8) << 4 | self.token.len() as u8,
+            self.code,
+        ];
+        bytes.extend(self.message_id.to_be_bytes());
+        bytes.extend(&self.token);
+
+        let mut options = self.options.clone();
+        options.sort_by_key(|(number, _)| *number);
+        let mut previous = 0;
+        for (number, value) in &options {
+            let (delta, delta_ext) = option_nibble(number - previous);
+            let (length, length_ext) = option_nibble(value.len() as u16);
+            bytes.push(delta << 4 | length);
+            bytes.extend(delta_ext);
+            bytes.extend(length_ext);
+            bytes.extend(value);
+            previous = *number;
+        }
+
+        if !self.payload.is_empty() {
+            bytes.push(PAYLOAD_MARKER);
+            bytes.extend(&self.payload);
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let [first, code, id_high, id_low, rest @ ..] = bytes else {
+            return Err("The message is shorter than its header".to_string());
+        };
+        if first >> 6 != VERSION {
+            return Err(format!("Unknown CoAP version {}", first >> 6));
+        }
+        let kind = match (first >> 4) & 0b11 {
+            0 => MessageType::Confirmable,
+            1 => MessageType::NonConfirmable,
+            2 => MessageType::Acknowledgement,
+            _ => MessageType::Reset,
+        };
+        let token_length = (first & 0x0f) as usize;
+        if token_length > 8 || rest.len() < token_length {
+            return Err("The token of the message is not valid".to_string());
+        }
+        let (token, mut rest) = rest.split_at(token_length);
+
+        let mut options = vec![];
+        let mut number: u16 = 0;
+        let mut payload = vec![];
+        while let [byte, tail @ ..] = rest {
+            if *byte == PAYLOAD_MARKER {
+                payload = tail.to_vec();
+                break;
+            }
+            let (delta, tail) = option_value(byte >> 4, tail)?;
+            let (length, tail) = option_value(byte & 0x0f, tail)?;
+            if tail.len() < length as usize {
+                return Err("An option is longer than the message".to_string());
+            }
+            let (value, tail) = tail.split_at(length as usize);
+            number = number
+                .checked_add(delta)
+                .ok_or("An option number of the message is not valid")?;
+            options.push((number, value.to_vec()));
+            rest = tail;
+        }
+
+        Ok(Message {
+            kind,
+            code: *code,
+            message_id: u16::from_be_bytes([*id_high, *id_low]),
+            token: token.to_vec(),
+            options,
+            payload,
+        })
+    }
+
+    /// The empty acknowledgement of the message
+    fn acknowledgement(&self) -> Message {
+        Message {
+            kind: MessageType::Acknowledgement,
+            code: 0,
+            message_id: self.message_id,
+            token: vec![],
+            options: vec![],
+            payload: vec![],
+        }
+    }
+}
+
+/// The nibble of an option delta or length and its extended bytes
+fn option_nibble(value: u16) -> (u8, Vec<u8>) {
+    match value {
+        0..=12 => (value as u8, vec![]),
+        13..=268 => (13, vec![(value - 13) as u8]),
+        _ => (14, (value - 269).to_be_bytes().to_vec()),
+    }
+}
+
+fn option_value(nibble: u8, bytes: &[u8]) -> Result<(u16, &[u8]), String> {
+    match (nibble, bytes) {
+        (0..=12, _) => Ok((nibble as u16, bytes)),
+        (13, [ext, rest @ ..]) => Ok((*ext as u16 + 13, rest)),
+        (14, [high, low, rest @ ..]) => u16::from_be_bytes([*high, *low])
+            .checked_add(269)
+            .map(|value| (value, rest))
+            .ok_or_else(|| "An option of the message is not valid".to_string()),
+        _ => Err("An option of the message is not valid".to_string()),
+    }
+}
+
+/// The status of the HTTP response the CoAP response code maps to, as of
+///  RFC 8075
+pub fn http_status(code: u8) -> u16 {
+    let (class, detail) = (code >> 5, code & 0x1f);
+    match (class, detail) {
+        (2, 1) => 201,
+        (2, 2) | (2, 4) => 204,
+        (2, 3) => 304,
+        (2, _) => 200,
+        // The client and server errors have the same numbers, e.g. 4.04
+        (4 | 5, detail) => class as u16 * 100 + detail as u16,
+        _ => 500,
+    }
+}
+
+/// The request of the HTTP call, the path and query are sent as options and
+///  the body as JSON
+pub fn translate_http_call(call: &HTTPCall, message_id: u16, token: &[u8]) -> Option<Message> {
+    let code = match call.method {
+        HTTPMethod::GET => 1,
+        HTTPMethod::POST => 2,
+        HTTPMethod::PUT => 3,
+        HTTPMethod::DELETE => 4,
+        HTTPMethod::Unsupported => return None,
+    };
+    let url = reqwest::Url::parse(&call.url).ok()?;
+
+    let mut options: Vec<(u16, Vec<u8>)> = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let segment = percent_decode_str(segment).collect::<Vec<u8>>();
+            (OPTION_URI_PATH, segment)
+        })
+        .collect();
+    options.extend(
+        url.query_pairs()
+            .map(|(key, value)| (OPTION_URI_QUERY, format!("{}={}", key, value).into_bytes())),
+    );
+
+    let fields = call
+        .parameters
+        .body
+        .iter()
+        .chain(&call.parameters.form_data)
+        .flatten()
+        .collect::<std::collections::BTreeMap<_, _>>();
+    let payload = if fields.is_empty() {
+        vec![]
+    } else {
+        options.push((OPTION_CONTENT_FORMAT, vec![CONTENT_FORMAT_JSON]));
+        serde_json::to_vec(&fields).unwrap_or_default()
+    };
+
+    Some(Message {
+        kind: MessageType::Confirmable,
+        code,
+        message_id,
+        token: token.to_vec(),
+        options,
+        payload,
+    })
+}
+
+/// Send the confirmable request and wait for its response, sending it again
+///  while it is not acknowledged. A response sent separately from the
+///  acknowledgement is acknowledged in turn.
+pub fn send(config: &CoapConfiguration, request: &Message) -> Result<Message, TransportError> {
+    let address: SocketAddr = (config.host.as_str(), config.port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or(TransportError::Connect)?;
+    let local = if address.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local).map_err(|_| TransportError::Connect)?;
+    socket
+        .connect(address)
+        .map_err(|_| TransportError::Connect)?;
+
+    let datagram = request.encode();
+    let mut acknowledged = false;
+    let mut timeout = config.ack_timeout;
+    let mut buffer = [0; 65535];
+
+    for _ in 0..=MAX_RETRANSMIT {
+        if !acknowledged {
+            socket
+                .send(&datagram)
+                .map_err(|_| TransportError::Connect)?;
+        }
+        let deadline = Instant::now() + timeout;
+
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if remaining.is_zero() {
+                break;
+            }
+            socket
+                .set_read_timeout(Some(remaining))
+                .map_err(|_| TransportError::Connect)?;
+            let received = match socket.recv(&mut buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                    return Err(TransportError::Connect)
+                }
+                Err(_) => break,
+            };
+            let response = match Message::decode(&buffer[..received]) {
+                Ok(response) => response,
+                Err(e) => {
+                    debug!("Ignored a datagram: {}", e);
+                    continue;
+                }
+            };
+
+            let is_reply = response.message_id == request.message_id;
+            match response.kind {
+                MessageType::Reset if is_reply => return Err(TransportError::Connect),
+                // Empty, the response follows separately
+                MessageType::Acknowledgement if is_reply && response.code == 0 => {
+                    acknowledged = true
+                }
+                MessageType::Acknowledgement if is_reply => return Ok(response),
+                MessageType::Confirmable | MessageType::NonConfirmable
+                    if response.token == request.token =>
+                {
+                    if response.kind == MessageType::Confirmable {
+                        let _ = socket.send(&response.acknowledgement().encode());
+                    }
+                    return Ok(response);
+                }
+                _ => (),
+            }
+        }
+        timeout *= 2;
+    }
+
+    Err(TransportError::Timeout)
+}
+
+/// Send the operations of a CoAP target in order, as confirmable requests
+pub fn invoke(
+    ctx: &ExplorationContext,
+    ops: &[Operation],
+    gen_ops: &[GeneratedOperation],
+) -> Option<Vec<InvokeResult>> {
+    let Target::CoAP { config } = ctx.current_target() else {
+        warn!("Only a CoAP target is invoked over CoAP");
+        return None;
+    };
+    let http_config = config.http_configuration();
+
+    explore::invoke_in_order(ctx, gen_ops, |gen_op, results, _id| {
+        let (call, url) =
+            translate_generated_operation_to_http_call(&http_config, ops, gen_op, results)?;
+        // Of the sequence and the operation in it, a late response to another
+        //  request is not taken as the response
+        let token = ctx.test_cases.get() << 16 | results.len() as u64;
+        let request = translate_http_call(
+            &call,
+            token as u16 ^ (token >> 16) as u16,
+            &token.to_be_bytes(),
+        )?;
+        let url = url.replacen("http://", "coap://", 1);
+
+        let result = match send(config, &request) {
+            Ok(response) => {
+                let status = http_status(response.code);
+                let payload = String::from_utf8_lossy(&response.payload).to_string();
+                InvokeResult::new(
+                    gen_op.clone(),
+                    payload,
+                    (200..300).contains(&status),
+                    Some(ResultMetaData::HTTP {
+                        url,
+                        status: HTTPStatus::from_code(status),
+                        size: response.payload.len(),
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                )
+            }
+            Err(error) => InvokeResult::transport_failure(gen_op.clone(), error),
+        };
+        Some(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::UdpSocket;
+    use std::time::Duration;
+
+    use qr_http_resource::http::{HTTPCall, HTTPMethod, HTTPParameters};
+
+    use crate::coap as sut;
+    use crate::coap::MessageType;
+
+    fn request() -> sut::Message {
+        let call = HTTPCall {
+            url: "http://localhost:5683/temperature-sensors/a%20b?unit=c".to_string(),
+            method: HTTPMethod::PUT,
+            parameters: HTTPParameters {
+                url: String::new(),
+                form_data: None,
+                file_data: None,
+                body: Some(HashMap::from([("value".to_string(), "21".to_string())])),
+            },
+            headers: vec![],
+        };
+        sut::translate_http_call(&call, 7, &[1, 2]).unwrap()
+    }
+
+    #[test]
+    fn requests_are_encoded_as_coap_messages() {
+        let request = request();
+        assert_eq!(request.code, 3);
+        assert_eq!(
+            request.options,
+            vec![
+                (11, b"temperature-sensors".to_vec()),
+                (11, b"a b".to_vec()),
+                (15, b"unit=c".to_vec()),
+                (12, vec![50]),
+            ]
+        );
+        assert_eq!(request.payload, br#"{"value":"21"}"#);
+
+        // The options are encoded in the order of their numbers
+        let decoded = sut::Message::decode(&request.encode()).unwrap();
+        let mut options = request.options.clone();
+        options.sort_by_key(|(number, _)| *number);
+        assert_eq!(decoded, sut::Message { options, ..request });
+        assert!(sut::Message::decode(&[0x40]).is_err());
+
+        assert_eq!(sut::http_status(0x45), 200);
+        assert_eq!(sut::http_status(0x41), 201);
+        assert_eq!(sut::http_status(0x44), 204);
+        assert_eq!(sut::http_status(0x84), 404);
+        assert_eq!(sut::http_status(0xa0), 500);
+    }
+
+    #[test]
+    fn a_separate_response_is_acknowledged() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = sut::CoapConfiguration::new(
+            "127.0.0.1".to_string(),
+            server.local_addr().unwrap().port(),
+        )
+        .with_ack_timeout(Duration::from_secs(1));
+        assert_eq!(
+            sut::CoapConfiguration::parse("coap://sensor").unwrap().port,
+            5683
+        );
+        assert!(sut::CoapConfiguration::parse("http://sensor").is_err());
+
+        let handle = std::thread::spawn(move || {
+            let mut buffer = [0; 1024];
+            let (received, client) = server.recv_from(&mut buffer).unwrap();
+            let request = sut::Message::decode(&buffer[..received]).unwrap();
+
+            // Acknowledged at once, the response follows
+            let empty = sut::Message {
+                kind: MessageType::Acknowledgement,
+                code: 0,
+                message_id: request.message_id,
+                token: vec![],
+                options: vec![],
+                payload: vec![],
+            };
+            server.send_to(&empty.encode(), client).unwrap();
+            let response = sut::Message {
+                kind: MessageType::Confirmable,
+                code: 0x45,
+                message_id: 99,
+                token: request.token,
+                options: vec![],
+                payload: b"21.5".to_vec(),
+            };
+            server.send_to(&response.encode(), client).unwrap();
+
+            let (received, _) = server.recv_from(&mut buffer).unwrap();
+            sut::Message::decode(&buffer[..received]).unwrap()
+        });
+
+        let response = sut::send(&config, &request()).unwrap();
+        assert_eq!(response.code, 0x45);
+        assert_eq!(response.payload, b"21.5");
+
+        let acknowledgement = handle.join().unwrap();
+        assert_eq!(acknowledgement.kind, MessageType::Acknowledgement);
+        assert_eq!(acknowledgement.message_id, 99);
+    }
+}
@@ -0,0 +1,183 @@
+use qr_http_resource::http::{HTTPMethod, HTTPStatus};
+
+use crate::amos::{InvokeResult, Operation, OperationMetaData, ResultMetaData};
+
+// The sequence of a conditional request is the read capturing the ETag, the
+//  conflicting update, any generated operations, the read observing the new
+//  ETag and the update replayed with the captured ETag
+const CAPTURE: usize = 0;
+const CONFLICT: usize = 1;
+const SEQUENCE_LENGTH: usize = 4;
+
+/// The GET of the resource the PUT updates, by the URL of both
+pub fn read_of<'a>(update: &Operation, operations: &'a [Operation]) -> Option<&'a Operation> {
+    let Some(OperationMetaData::HTTP {
+        url,
+        method: HTTPMethod::PUT,
+    }) = &update.meta_data
+    else {
+        return None;
+    };
+
+    operations.iter().find(|op| {
+        matches!(
+            &op.meta_data,
+            Some(OperationMetaData::HTTP { url: read, method: HTTPMethod::GET }) if read == url
+        )
+    })
+}
+
+/// The ETag of the response
+pub fn etag(result: &InvokeResult) -> Option<&str> {
+    match &result.meta_data {
+        Some(ResultMetaData::HTTP { headers, .. }) => headers
+            .iter()
+            .find(|(name, _)| name == "etag")
+            .map(|(_, value)| value.as_str()),
+        None => None,
+    }
+}
+
+/// The If-Match of the step of a sequence of the length, the ETag captured by
+///  the first read. Only the updates are conditional.
+pub fn if_match(step: usize, length: usize, results: &[InvokeResult]) -> Option<String> {
+    if length < SEQUENCE_LENGTH || (step != CONFLICT && step + 1 != length) {
+        return None;
+    }
+    at(results, CAPTURE).and_then(etag).map(str::to_string)
+}
+
+/// The replayed update, if it was not rejected as the precondition failed
+///  though the conflicting update changed the ETag of the resource. A 409
+///  Conflict is a rejection when it is accepted.
+pub fn stale_update(results: &[InvokeResult], accept_conflict: bool) -> Option<&InvokeResult> {
+    let last = results.iter().rev().find_map(|res| res.position)?.index;
+    if last + 1 < SEQUENCE_LENGTH {
+        return None;
+    }
+
+    let capture = at(results, CAPTURE)?;
+    let conflict = at(results, CONFLICT)?;
+    let observe = at(results, last - 1)?;
+    let replay = at(results, last)?;
+    // The sequence was cut short, e.g. by a transport error
+    if observe.operation.name != capture.operation.name
+        || replay.operation.name != conflict.operation.name
+    {
+        return None;
+    }
+
+    // Without a conflict there is no stale ETag to replay
+    let captured = etag(capture)?;
+    if !conflict.success || etag(observe).is_none_or(|current| current == captured) {
+        return None;
+    }
+
+    let rejected = match &replay.meta_data {
+        Some(ResultMetaData::HTTP { status, .. }) => {
+            *status == HTTPStatus::PreconditionFailed
+                || (accept_conflict && *status == HTTPStatus::Conflict)
+        }
+        None => true,
+    };
+    (!rejected && !replay.is_transport_error()).then_some(replay)
+}
+
+/// The result of the step of the sequence
+fn at(results: &[InvokeResult], step: usize) -> Option<&InvokeResult> {
+    results
+        .iter()
+        .find(|res| res.position.is_some_and(|position| position.index == step))
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::{HTTPMethod, HTTPStatus};
+
+    use crate::amos::{InvokeResult, Operation, OperationInfo, OperationMetaData, ResultMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::conditional as sut;
+
+    fn result(step: usize, name: &str, status: HTTPStatus, etag: Option<&str>) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            String::new(),
+            matches!(status, HTTPStatus::OK | HTTPStatus::NoContent),
+            Some(ResultMetaData::HTTP {
+                url: "http://localhost/items/1".to_string(),
+                status,
+                size: 0,
+                content_encoding: None,
+                headers: etag
+                    .map(|etag| vec![("etag".to_string(), etag.to_string())])
+                    .unwrap_or_default(),
+            }),
+        )
+        .with_position(1, step)
+    }
+
+    #[test]
+    fn the_update_of_a_read_resource() {
+        let op = |name: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/items/{id}".to_string(),
+                method,
+            }),
+        };
+        let operations = vec![
+            op("put_item", HTTPMethod::PUT),
+            op("get_item", HTTPMethod::GET),
+        ];
+
+        let read = sut::read_of(&operations[0], &operations).unwrap();
+        assert_eq!(read.info.name, "get_item");
+        assert!(sut::read_of(&operations[1], &operations).is_none());
+    }
+
+    #[test]
+    fn a_stale_update_is_rejected() {
+        let sequence = |replayed| {
+            vec![
+                result(0, "get_item", HTTPStatus::OK, Some("\"v1\"")),
+                result(1, "put_item", HTTPStatus::NoContent, None),
+                result(2, "get_item", HTTPStatus::OK, Some("\"v2\"")),
+                result(3, "put_item", replayed, None),
+            ]
+        };
+
+        // Only the updates are sent with the ETag of the first read
+        let results = sequence(HTTPStatus::OK);
+        assert_eq!(sut::if_match(0, 4, &results), None);
+        assert_eq!(
+            sut::if_match(1, 4, &results[..1]),
+            Some("\"v1\"".to_string())
+        );
+        assert_eq!(sut::if_match(2, 4, &results), None);
+        assert_eq!(sut::if_match(3, 4, &results), Some("\"v1\"".to_string()));
+
+        assert_eq!(sut::stale_update(&results, false), Some(&results[3]));
+        assert_eq!(
+            sut::stale_update(&sequence(HTTPStatus::PreconditionFailed), false),
+            None
+        );
+        let conflict = sequence(HTTPStatus::Conflict);
+        assert!(sut::stale_update(&conflict, false).is_some());
+        assert_eq!(sut::stale_update(&conflict, true), None);
+
+        // The conflicting update did not change the ETag, the replay is not stale
+        let mut unchanged = sequence(HTTPStatus::OK);
+        unchanged[2] = result(2, "get_item", HTTPStatus::OK, Some("\"v1\""));
+        assert_eq!(sut::stale_update(&unchanged, false), None);
+    }
+}
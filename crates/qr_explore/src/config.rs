@@ -0,0 +1,601 @@
+use std::path::Path;
+
+use qr_http_resource::http::{self, HTTPAuth};
+use regex::Regex;
+
+use crate::behaviours::Behaviour;
+use crate::exploration_settings::{
+    AuthSettings, CredentialSet, DeprecatedOperations, GeneratorOverrides, HealthSettings,
+    MockSettings, OperationWeights, RepeatedSequence, RunTagSettings, ShrinkStrategy,
+    StringGeneration, SutSettings,
+};
+use crate::secrets::{self, SecretError, SecretStore};
+use crate::value_provider::ValueProvider;
+
+/// The file a configuration is read from when none is given
+pub const DEFAULT_CONFIG_FILE: &str = "quickrest.toml";
+
+#[derive(Debug)]
+pub enum ConfigError {
+    ConfigFileFailure(std::io::Error),
+    ConfigParseFailure(toml::de::Error),
+    InvalidValue(String),
+    SecretsFailure(SecretError),
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(val: std::io::Error) -> Self {
+        Self::ConfigFileFailure(val)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(val: toml::de::Error) -> Self {
+        Self::ConfigParseFailure(val)
+    }
+}
+
+impl From<SecretError> for ConfigError {
+    fn from(val: SecretError) -> Self {
+        Self::SecretsFailure(val)
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::ConfigFileFailure(e) => write!(f, "Could not read config: {}", e),
+            ConfigError::ConfigParseFailure(e) => write!(f, "Invalid config: {}", e),
+            ConfigError::InvalidValue(msg) => write!(f, "Invalid config: {}", msg),
+            ConfigError::SecretsFailure(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The settings of a run, as read from a `quickrest.toml`. Every part is
+///  optional, the CLI flags override the values of the file.
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Names of the behaviours to explore, as in the names of their reports
+    pub behaviours: Vec<String>,
+    pub target: TargetConfig,
+    pub specification: SpecificationConfig,
+    pub auth: AuthConfig,
+    pub generation: GenerationConfig,
+    pub filters: OperationFilter,
+    /// The responses of dry runs
+    pub mock: MockSettings,
+    /// How the SUT is started before the run and stopped after it
+    pub sut: SutSettings,
+    /// The build of the SUT the runs are tagged with
+    pub tags: RunTagSettings,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TargetConfig {
+    /// E.g. 'http://localhost:8080'
+    pub url: Option<String>,
+    /// The path the operations are relative to, e.g. '/api/v2', instead of
+    ///  the base path of the specification
+    pub base_path: Option<String>,
+    /// How the paths of the requests are normalized before they are sent
+    pub paths: http::PathNormalization,
+    /// The parameters sent with a constant value and the static headers
+    pub pinning: http::RequestPinning,
+    /// The parameters and headers computed when the requests are sent, e.g.
+    ///  `X-Signature = "hmac(${secret:key}, body)"`
+    pub providers: http::ValueProviders,
+    /// E.g. 'X-Request-Id'
+    pub correlation_header: Option<String>,
+    /// E.g. 'Idempotency-Key', sent with a key of each POST
+    pub idempotency_header: Option<String>,
+    /// The endpoint probed before and during the explorations of the target
+    pub health: HealthSettings,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpecificationConfig {
+    pub files: Vec<String>,
+    pub url: Option<String>,
+    pub discover: bool,
+    /// A Postman collection, when there is no OpenAPI specification
+    pub postman: Option<String>,
+    /// The CoRE resources of a CoAP server, as served at '/.well-known/core'
+    pub link_format: Option<String>,
+}
+
+/// Credentials as 'Header: value', like the credentials of the CLI flags. A
+///  value may reference a secret of the store as `${secret:<name>}`.
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// The secrets store of the references, `quickrest.secrets` if not given
+    pub secrets: Option<String>,
+    pub credentials: Option<String>,
+    pub downgraded: Option<String>,
+    pub roles: Vec<RoleConfig>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoleConfig {
+    pub name: String,
+    pub credentials: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenerationConfig {
+    pub tests: Option<u16>,
+    pub min_length: Option<u8>,
+    pub max_length: Option<u8>,
+    pub coverage_guided: Option<bool>,
+    pub follow_links: Option<bool>,
+    pub harvest_responses: Option<bool>,
+    pub learn_precedence: Option<bool>,
+    pub mutate_sequences: Option<bool>,
+    pub honor_cache_headers: Option<bool>,
+    pub corpus_dir: Option<String>,
+    pub script_file: Option<String>,
+    pub deprecated: Option<DeprecatedOperations>,
+    pub strings: Option<StringGeneration>,
+    pub seed: Option<u64>,
+    pub max_shrink_iterations: Option<u32>,
+    pub max_shrink_time_secs: Option<u64>,
+    pub shrink_strategy: Option<ShrinkStrategy>,
+    pub repeated_sequences: Option<RepeatedSequence>,
+    pub weights: OperationWeights,
+    pub overrides: GeneratorOverrides,
+}
+
+impl GenerationConfig {
+    /// The same bounds as the flags of the CLI
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |msg: &str| Err(ConfigError::InvalidValue(msg.to_string()));
+
+        if self.tests.is_some_and(|tests| !(1..=1000).contains(&tests)) {
+            return invalid("tests must be within 1 to 1000");
+        }
+        for length in [self.min_length, self.max_length].into_iter().flatten() {
+            if !(1..=5).contains(&length) {
+                return invalid("sequence lengths must be within 1 to 5");
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return invalid("min_length must not exceed max_length");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Names of the operations to explore, '*' matches any characters. Without
+///  included names every operation not excluded is explored.
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperationFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl OperationFilter {
+    pub fn matches(&self, operation: &str) -> bool {
+        let matching = |patterns: &[String]| {
+            patterns.iter().any(|pattern| {
+                let pattern = format!("^{}$", regex::escape(pattern).replace("\\*", ".*"));
+                Regex::new(&pattern).is_ok_and(|re| re.is_match(operation))
+            })
+        };
+
+        (self.include.is_empty() || matching(&self.include)) && !matching(&self.exclude)
+    }
+}
+
+impl Config {
+    pub fn from_toml(content: &str) -> Result<Self, ConfigError> {
+        let mut config: Config = toml::from_str(content)?;
+        config.resolve_secrets()?;
+
+        // Report invalid values when the file is read, not when they are used
+        config.behaviours()?;
+        config.auth()?;
+        config.target()?;
+        config.providers()?;
+        config.generation.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, ConfigError> {
+        Config::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Replace the references of the credentials and the providers with the
+    ///  secrets, the store is only opened if there are references
+    fn resolve_secrets(&mut self) -> Result<(), ConfigError> {
+        let path = self
+            .auth
+            .secrets
+            .clone()
+            .unwrap_or_else(|| secrets::DEFAULT_SECRETS_FILE.to_string());
+        let auth = &mut self.auth;
+        let providers = &mut self.target.providers;
+        let credentials: Vec<&mut String> = auth
+            .credentials
+            .iter_mut()
+            .chain(auth.downgraded.iter_mut())
+            .chain(
+                auth.roles
+                    .iter_mut()
+                    .filter_map(|role| role.credentials.as_mut()),
+            )
+            .chain(providers.parameters.values_mut())
+            .chain(providers.headers.values_mut())
+            .chain(providers.operations.values_mut().flat_map(|operation| {
+                operation
+                    .parameters
+                    .values_mut()
+                    .chain(operation.headers.values_mut())
+            }))
+            .filter(|credentials| secrets::has_references(credentials))
+            .collect();
+        if credentials.is_empty() {
+            return Ok(());
+        }
+        if !Path::new(&path).exists() {
+            return Err(ConfigError::InvalidValue(format!(
+                "the credentials reference secrets, but there is no store '{path}'"
+            )));
+        }
+
+        let store = SecretStore::open(Path::new(&path), &secrets::passphrase()?)?;
+        for credentials in credentials {
+            *credentials = store.resolve(credentials)?;
+        }
+
+        Ok(())
+    }
+
+    /// The value providers of the target, by the names of the parameters and
+    ///  the headers
+    pub fn providers(&self) -> Result<Vec<(&str, ValueProvider)>, ConfigError> {
+        let providers = &self.target.providers;
+        providers
+            .parameters
+            .iter()
+            .chain(&providers.headers)
+            .chain(
+                providers
+                    .operations
+                    .values()
+                    .flat_map(|operation| operation.parameters.iter().chain(&operation.headers)),
+            )
+            .map(|(name, expression)| {
+                ValueProvider::parse(expression)
+                    .map(|provider| (name.as_str(), provider))
+                    .map_err(|e| ConfigError::InvalidValue(format!("provider of '{name}': {e}")))
+            })
+            .collect()
+    }
+
+    pub fn behaviours(&self) -> Result<Vec<Behaviour>, ConfigError> {
+        self.behaviours
+            .iter()
+            .map(|name| {
+                Behaviour::from_name(name)
+                    .ok_or_else(|| ConfigError::InvalidValue(format!("unknown behaviour '{name}'")))
+            })
+            .collect()
+    }
+
+    pub fn auth(&self) -> Result<AuthSettings, ConfigError> {
+        let parse = |credentials: &Option<String>| {
+            credentials
+                .as_deref()
+                .map(parse_auth_header)
+                .transpose()
+                .map_err(ConfigError::InvalidValue)
+        };
+
+        let mut roles = vec![];
+        for role in &self.auth.roles {
+            roles.push(CredentialSet::new(&role.name, parse(&role.credentials)?));
+        }
+
+        Ok(AuthSettings {
+            credentials: parse(&self.auth.credentials)?,
+            downgraded: parse(&self.auth.downgraded)?,
+            roles,
+        })
+    }
+
+    /// The protocol, host and port of the target URL, the port defaults to the
+    ///  one of the protocol
+    pub fn target(&self) -> Result<Option<(http::Protocol, String, u16)>, ConfigError> {
+        self.target
+            .url
+            .as_deref()
+            .map(parse_target_url)
+            .transpose()
+            .map_err(ConfigError::InvalidValue)
+    }
+}
+
+/// The protocol, host and port of a target URL like 'http://localhost:8080'
+pub fn parse_target_url(url: &str) -> Result<(http::Protocol, String, u16), String> {
+    let invalid = || format!("invalid target URL '{url}'");
+    let parsed = reqwest::Url::parse(url).map_err(|_| invalid())?;
+
+    let protocol = match parsed.scheme() {
+        "http" => http::Protocol::HTTP,
+        "https" => http::Protocol::HTTPS,
+        _ => return Err(invalid()),
+    };
+    let host = parsed.host_str().ok_or_else(invalid)?.to_string();
+    let port = parsed.port_or_known_default().ok_or_else(invalid)?;
+
+    Ok((protocol, host, port))
+}
+
+/// A credential header given as 'Header: value'
+pub fn parse_auth_header(s: &str) -> Result<HTTPAuth, String> {
+    let (header, value) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Expected <HEADER>: <VALUE>, got '{s}'"))?;
+
+    Ok(HTTPAuth::new(
+        header.trim().to_string(),
+        value.trim().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos::GeneratorSpec;
+    use crate::exploration_settings::SutLauncher;
+
+    #[test]
+    fn read_config() {
+        let config = Config::from_toml(
+            r#"
+            behaviours = ["fuzz", "state-mutation"]
+
+            [target]
+            url = "https://localhost"
+            base_path = "/api/v2"
+            correlation_header = "X-Request-Id"
+            idempotency_header = "Idempotency-Key"
+
+            [target.paths]
+            trailing_slash = "remove"
+
+            [target.pinning]
+            parameters = { tenantId = "42" }
+            headers = { X-Feature = "beta" }
+
+            [target.pinning.operations.get_orders]
+            parameters = { limit = "10" }
+
+            [target.health]
+            path = "/health"
+            interval_secs = 5
+
+            [specification]
+            files = ["persons.json"]
+
+            [auth]
+            credentials = "Authorization: Bearer abc"
+
+            [[auth.roles]]
+            name = "anonymous"
+
+            [generation]
+            tests = 50
+            weights = { post_person = 3 }
+            max_shrink_iterations = 200
+            shrink_strategy = "value-first"
+            deprecated = "exclude"
+            repeated_sequences = { kind = "reset_first", command = "./reset.sh" }
+
+            [generation.overrides.generators]
+            ids = { kind = "range", min = 1, max = 9 }
+
+            [generation.overrides.parameters]
+            post_person = { id = "ids" }
+
+            [filters]
+            exclude = ["delete_*"]
+
+            [mock]
+            crud = true
+
+            [mock.responses.get_persons]
+            status = 500
+            body = "oops"
+
+            [sut]
+            launcher = { kind = "compose", file = "docker-compose.yml" }
+            health_path = "/health"
+            reset_between_behaviours = true
+
+            [tags]
+            version_path = "/version"
+            environment = "staging"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.behaviours().unwrap(),
+            vec![Behaviour::Property, Behaviour::StateMutation]
+        );
+        assert_eq!(
+            config.target().unwrap(),
+            Some((http::Protocol::HTTPS, "localhost".to_string(), 443))
+        );
+        assert_eq!(
+            config.target.correlation_header.as_deref(),
+            Some("X-Request-Id")
+        );
+        assert_eq!(
+            config.target.idempotency_header.as_deref(),
+            Some("Idempotency-Key")
+        );
+        assert_eq!(config.target.base_path.as_deref(), Some("/api/v2"));
+        assert_eq!(
+            config.target.paths.trailing_slash,
+            http::TrailingSlash::Remove
+        );
+        assert_eq!(
+            config.target.pinning.parameter("get_orders", "tenantId"),
+            Some("42")
+        );
+        assert_eq!(
+            config.target.pinning.parameter("get_orders", "limit"),
+            Some("10")
+        );
+        assert_eq!(
+            config.target.pinning.headers("get_orders"),
+            vec![("X-Feature".to_string(), "beta".to_string())]
+        );
+        assert_eq!(config.target.health.path.as_deref(), Some("/health"));
+        assert_eq!(config.target.health.interval_secs, 5);
+        assert_eq!(
+            config.target.health.max_pause_secs,
+            HealthSettings::DEFAULT_MAX_PAUSE_SECS
+        );
+        assert_eq!(config.generation.tests, Some(50));
+        assert_eq!(config.generation.weights.weight("post_person"), 3);
+        assert_eq!(config.generation.max_shrink_iterations, Some(200));
+        assert_eq!(
+            config.generation.shrink_strategy,
+            Some(ShrinkStrategy::ValueFirst)
+        );
+        assert_eq!(
+            config.generation.deprecated,
+            Some(DeprecatedOperations::Exclude)
+        );
+        assert_eq!(
+            config.generation.repeated_sequences,
+            Some(RepeatedSequence::ResetFirst {
+                command: "./reset.sh".to_string()
+            })
+        );
+        assert_eq!(
+            config
+                .generation
+                .overrides
+                .generator_name("post_person", "id"),
+            Some("ids")
+        );
+        assert_eq!(
+            config.generation.overrides.generators.get("ids"),
+            Some(&GeneratorSpec::Range { min: 1, max: 9 })
+        );
+
+        let auth = config.auth().unwrap();
+        assert_eq!(auth.credentials, Some(HTTPAuth::bearer("abc")));
+        assert_eq!(auth.roles, vec![CredentialSet::new("anonymous", None)]);
+
+        assert!(config.filters.matches("get_persons"));
+        assert!(!config.filters.matches("delete_person"));
+
+        assert!(config.mock.crud);
+        assert_eq!(config.mock.responses["get_persons"].status, 500);
+
+        assert_eq!(
+            config.sut.launcher,
+            SutLauncher::Compose {
+                file: "docker-compose.yml".to_string()
+            }
+        );
+        assert_eq!(
+            config.sut.startup_timeout_secs,
+            SutSettings::DEFAULT_STARTUP_TIMEOUT_SECS
+        );
+        assert!(config.sut.reset_between_behaviours);
+
+        assert_eq!(config.tags.version_path.as_deref(), Some("/version"));
+        assert_eq!(config.tags.git_commit, None);
+        assert_eq!(config.tags.environment.as_deref(), Some("staging"));
+    }
+
+    #[test]
+    fn invalid_config() {
+        assert!(matches!(
+            Config::from_toml(r#"behaviours = ["fuz"]"#),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            Config::from_toml("[generation]\ntest = 50"),
+            Err(ConfigError::ConfigParseFailure(_))
+        ));
+        assert!(matches!(
+            Config::from_toml("[generation]\nmin_length = 3\nmax_length = 2"),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            Config::from_toml("[target.providers.headers]\nX-Signature = \"sign()\""),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn credentials_referencing_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.secrets");
+        let mut store = SecretStore::open(&path, "passphrase").unwrap();
+        store.set("token", "abc");
+        store.save().unwrap();
+        std::env::set_var(secrets::PASSPHRASE_VAR, "passphrase");
+
+        let config = Config::from_toml(&format!(
+            r#"
+            [auth]
+            secrets = '{}'
+            credentials = "Authorization: Bearer ${{secret:token}}"
+
+            [target.providers.headers]
+            X-Signature = "hmac(${{secret:token}}, body)"
+            "#,
+            path.display()
+        ))
+        .unwrap();
+        assert_eq!(
+            config.auth().unwrap().credentials,
+            Some(HTTPAuth::bearer("abc"))
+        );
+        assert_eq!(
+            config.providers().unwrap(),
+            vec![(
+                "X-Signature",
+                ValueProvider::Hmac {
+                    secret: "abc".to_string()
+                }
+            )]
+        );
+
+        assert!(matches!(
+            Config::from_toml(&format!(
+                "[auth]\nsecrets = '{}'\ncredentials = \"X-Api-Key: ${{secret:key}}\"",
+                path.display()
+            )),
+            Err(ConfigError::SecretsFailure(SecretError::UnknownSecret(_)))
+        ));
+        assert!(matches!(
+            Config::from_toml(
+                "[auth]\nsecrets = \"missing.secrets\"\ncredentials = \"X-Api-Key: ${secret:key}\""
+            ),
+            Err(ConfigError::InvalidValue(_))
+        ));
+    }
+}
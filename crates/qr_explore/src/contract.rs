@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::amos::{InvokeResult, ResultMetaData};
+
+/// The shape of a JSON response, the kinds of its values and the fields of
+///  its objects
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Shape {
+    Null,
+    Boolean,
+    Number,
+    String,
+    /// The shape of the items, `None` if only empty arrays were observed
+    Array {
+        items: Option<Box<Shape>>,
+    },
+    /// The fields observed in every object
+    Object {
+        fields: BTreeMap<String, Shape>,
+    },
+    /// Values of different kinds were observed
+    Any,
+}
+
+impl Shape {
+    pub fn of(value: &Value) -> Shape {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Boolean,
+            Value::Number(_) => Shape::Number,
+            Value::String(_) => Shape::String,
+            Value::Array(items) => Shape::Array {
+                items: items
+                    .iter()
+                    .map(Shape::of)
+                    .reduce(Shape::merge)
+                    .map(Box::new),
+            },
+            Value::Object(object) => Shape::Object {
+                fields: object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Shape::of(value)))
+                    .collect(),
+            },
+        }
+    }
+
+    /// The shape both shapes conform to
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Array { items }, Shape::Array { items: other }) => Shape::Array {
+                items: match (items, other) {
+                    (Some(items), Some(other)) => Some(Box::new(items.merge(*other))),
+                    (items, other) => items.or(other),
+                },
+            },
+            (Shape::Object { fields }, Shape::Object { fields: mut other }) => Shape::Object {
+                fields: fields
+                    .into_iter()
+                    .filter_map(|(key, shape)| {
+                        other.remove(&key).map(|other| (key, shape.merge(other)))
+                    })
+                    .collect(),
+            },
+            (shape, other) if shape == other => shape,
+            _ => Shape::Any,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Shape::Null => "null",
+            Shape::Boolean => "boolean",
+            Shape::Number => "number",
+            Shape::String => "string",
+            Shape::Array { .. } => "array",
+            Shape::Object { .. } => "object",
+            Shape::Any => "any",
+        }
+    }
+
+    /// The violations of the value, at the JSON pointer below the path
+    fn check(&self, value: &Value, path: &str, violations: &mut Vec<(String, Violation)>) {
+        let observed = Shape::of(value);
+        match (self, value) {
+            (Shape::Any, _) => (),
+            (Shape::Array { items: Some(items) }, Value::Array(values)) => {
+                for (idx, value) in values.iter().enumerate() {
+                    items.check(value, &format!("{}/{}", path, idx), violations);
+                }
+            }
+            (Shape::Array { items: None }, Value::Array(_)) => (),
+            (Shape::Object { fields }, Value::Object(object)) => {
+                for (key, shape) in fields {
+                    let path = format!("{}/{}", path, key);
+                    match object.get(key) {
+                        Some(value) => shape.check(value, &path, violations),
+                        None => violations.push((path, Violation::MissingField)),
+                    }
+                }
+            }
+            _ if self.kind() != observed.kind() => violations.push((
+                path.to_string(),
+                Violation::KindChanged {
+                    expected: self.kind().to_string(),
+                    observed: observed.kind().to_string(),
+                },
+            )),
+            _ => (),
+        }
+    }
+}
+
+/// How a response did not conform to the contract
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Violation {
+    /// The operation never responded with the status when it was captured
+    UnknownStatus,
+    MissingField,
+    KindChanged {
+        expected: String,
+        observed: String,
+    },
+}
+
+/// A response of an operation that did not conform to the contract
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ContractViolation {
+    pub operation: String,
+    pub status: String,
+    /// The JSON pointer of the value in the payload, empty for the status
+    pub path: String,
+    pub violation: Violation,
+}
+
+impl std::fmt::Display for ContractViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.violation {
+            Violation::UnknownStatus => {
+                write!(
+                    f,
+                    "{} responded {}, not in the contract",
+                    self.operation, self.status
+                )
+            }
+            Violation::MissingField => write!(
+                f,
+                "{} ({}): '{}' is missing",
+                self.operation, self.status, self.path
+            ),
+            Violation::KindChanged { expected, observed } => write!(
+                f,
+                "{} ({}): '{}' is {}, expected {}",
+                self.operation, self.status, self.path, observed, expected
+            ),
+        }
+    }
+}
+
+/// The statuses each operation responded with, and the shape of the payloads
+///  of each status, as observed of a version of the SUT known to be good.
+///  A payload that is not JSON has no shape, only its status is checked.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Contract {
+    pub operations: BTreeMap<String, BTreeMap<String, Option<Shape>>>,
+}
+
+impl Contract {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let contract = serde_json::from_slice(&std::fs::read(path)?)
+            .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))?;
+
+        Ok(contract)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Capture the statuses and shapes of the results, results without a
+    ///  response are left out
+    pub fn record(&mut self, results: &[InvokeResult]) {
+        for result in results {
+            let Some(status) = status_of(result) else {
+                continue;
+            };
+
+            let shape = serde_json::from_str::<Value>(&result.result)
+                .ok()
+                .map(|value| Shape::of(&value));
+            let statuses = self
+                .operations
+                .entry(result.operation.name.clone())
+                .or_default();
+            match statuses.remove(&status) {
+                Some(captured) => {
+                    let merged = match (captured, shape) {
+                        (Some(captured), Some(shape)) => Some(captured.merge(shape)),
+                        // A payload that is not always JSON has no shape
+                        _ => None,
+                    };
+                    statuses.insert(status, merged);
+                }
+                None => {
+                    statuses.insert(status, shape);
+                }
+            }
+        }
+    }
+
+    /// The violations of the results, operations not in the contract are
+    ///  not checked
+    pub fn check(&self, results: &[InvokeResult]) -> Vec<ContractViolation> {
+        let mut violations = vec![];
+
+        for result in results {
+            let (Some(status), Some(statuses)) = (
+                status_of(result),
+                self.operations.get(&result.operation.name),
+            ) else {
+                continue;
+            };
+
+            let mut found = vec![];
+            match statuses.get(&status) {
+                None => found.push((String::new(), Violation::UnknownStatus)),
+                Some(Some(shape)) => match serde_json::from_str::<Value>(&result.result) {
+                    Ok(value) => shape.check(&value, "", &mut found),
+                    Err(_) => found.push((
+                        String::new(),
+                        Violation::KindChanged {
+                            expected: shape.kind().to_string(),
+                            observed: "text".to_string(),
+                        },
+                    )),
+                },
+                Some(None) => (),
+            }
+
+            violations.extend(
+                found
+                    .into_iter()
+                    .map(|(path, violation)| ContractViolation {
+                        operation: result.operation.name.clone(),
+                        status: status.clone(),
+                        path,
+                        violation,
+                    }),
+            );
+        }
+
+        violations
+    }
+}
+
+fn status_of(result: &InvokeResult) -> Option<String> {
+    let ResultMetaData::HTTP { status, .. } = result.meta_data.as_ref()?;
+    Some(status.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::HTTPStatus;
+
+    use crate::amos::{InvokeResult, ResultMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::contract as sut;
+    use crate::contract::{Shape, Violation};
+
+    fn result(name: &str, status: HTTPStatus, payload: &str) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/".to_string(),
+                status,
+                size: payload.len(),
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn shapes_keep_the_fields_of_every_object() {
+        let shape = Shape::of(&serde_json::json!([
+            {"id": 1, "name": "a", "tag": "x"},
+            {"id": 2, "name": null}
+        ]));
+
+        let Shape::Array { items: Some(items) } = shape else {
+            panic!("Expected the shape of an array, got: {:?}", shape);
+        };
+        let Shape::Object { fields } = *items else {
+            panic!("Expected the shape of objects, got: {:?}", items);
+        };
+        assert_eq!(
+            fields.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("id".to_string(), Shape::Number),
+                ("name".to_string(), Shape::Any)
+            ]
+        );
+    }
+
+    #[test]
+    fn responses_not_conforming_to_the_captured_contract() {
+        let mut contract = sut::Contract::new();
+        contract.record(&[
+            result("get_person", HTTPStatus::OK, r#"{"id":1,"name":"a"}"#),
+            result("get_person", HTTPStatus::NotFound, "not found"),
+        ]);
+
+        // Extra fields and operations not captured conform
+        assert_eq!(
+            contract.check(&[
+                result(
+                    "get_person",
+                    HTTPStatus::OK,
+                    r#"{"id":2,"name":"b","age":3}"#
+                ),
+                result("get_persons", HTTPStatus::InternalServerError, ""),
+            ]),
+            vec![]
+        );
+
+        let violations = contract.check(&[
+            result("get_person", HTTPStatus::OK, r#"{"id":"2"}"#),
+            result("get_person", HTTPStatus::InternalServerError, ""),
+        ]);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| (v.path.as_str(), &v.violation))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/id",
+                    &Violation::KindChanged {
+                        expected: "number".to_string(),
+                        observed: "string".to_string()
+                    }
+                ),
+                ("/name", &Violation::MissingField),
+                ("", &Violation::UnknownStatus),
+            ]
+        );
+    }
+}
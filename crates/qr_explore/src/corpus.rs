@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use crate::amos::{InvokeResult, Parameter, Schema};
+use crate::amos_generation::{example_value, ParameterValue};
+
+/// Upper bound of the values kept of each kind, the first values seen are kept
+const MAX_VALUES_PER_KIND: usize = 256;
+
+pub type SharedCorpus = Arc<Mutex<ResponseCorpus>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValueKind {
+    String,
+    Integer,
+    Number,
+}
+
+impl ValueKind {
+    /// The kind of the values a parameter of the schema takes from the corpus.
+    ///  Schemas constraining the format of strings take none.
+    fn of(schema: &Schema) -> Option<ValueKind> {
+        match schema {
+            Schema::String | Schema::StringNonEmpty => Some(ValueKind::String),
+            Schema::Int | Schema::Int8 | Schema::Int32 => Some(ValueKind::Integer),
+            Schema::Number | Schema::Double | Schema::Float => Some(ValueKind::Number),
+            _ => None,
+        }
+    }
+}
+
+/// Values harvested from the responses of a run, by their kind and the name
+///  of the field they were found in
+#[derive(Debug, Default)]
+pub struct ResponseCorpus {
+    values: BTreeMap<ValueKind, Vec<(String, String)>>,
+}
+
+impl ResponseCorpus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Harvest the fields of a successful JSON response, returns how many new
+    ///  values that were added
+    pub fn harvest(&mut self, result: &InvokeResult) -> usize {
+        if !result.success {
+            return 0;
+        }
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(&result.result) else {
+            return 0;
+        };
+
+        let mut added = 0;
+        self.harvest_value("", &payload, &mut added);
+        added
+    }
+
+    fn harvest_value(&mut self, field: &str, value: &serde_json::Value, added: &mut usize) {
+        match value {
+            serde_json::Value::String(value) => self.insert(ValueKind::String, field, value, added),
+            serde_json::Value::Number(number) if number.is_i64() || number.is_u64() => {
+                let value = number.to_string();
+                self.insert(ValueKind::Integer, field, &value, added);
+                self.insert(ValueKind::Number, field, &value, added);
+            }
+            serde_json::Value::Number(number) => {
+                self.insert(ValueKind::Number, field, &number.to_string(), added)
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.harvest_value(field, item, added);
+                }
+            }
+            serde_json::Value::Object(fields) => {
+                for (name, value) in fields {
+                    self.harvest_value(name, value, added);
+                }
+            }
+            serde_json::Value::Bool(_) | serde_json::Value::Null => (),
+        }
+    }
+
+    fn insert(&mut self, kind: ValueKind, field: &str, value: &str, added: &mut usize) {
+        let values = self.values.entry(kind).or_default();
+        let is_new = !values.iter().any(|(f, v)| f == field && v == value);
+        if is_new && values.len() < MAX_VALUES_PER_KIND {
+            values.push((field.to_string(), value.to_string()));
+            *added += 1;
+        }
+    }
+
+    /// The values of the kind of the parameter. The values of the fields named
+    ///  like the parameter, e.g. `id` for `personId`, if there are any.
+    pub fn values(&self, param: &Parameter) -> Vec<ParameterValue> {
+        let Some(values) = ValueKind::of(&param.schema).and_then(|kind| self.values.get(&kind))
+        else {
+            return vec![];
+        };
+
+        let name = normalized(&param.name);
+        let is_named_like = |field: &str| {
+            let field = normalized(field);
+            !field.is_empty() && (name.ends_with(&field) || field.ends_with(&name))
+        };
+        let named: Vec<&str> = values
+            .iter()
+            .filter(|(field, _)| is_named_like(field))
+            .map(|(_, value)| value.as_str())
+            .collect();
+        let candidates = if named.is_empty() {
+            values.iter().map(|(_, value)| value.as_str()).collect()
+        } else {
+            named
+        };
+
+        candidates
+            .into_iter()
+            .filter_map(|value| example_value(&param.schema, value))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn normalized(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::{InvokeResult, Parameter, ParameterOwnership, Schema};
+    use crate::amos_generation::{GeneratedOperation, ParameterValue};
+    use crate::corpus as sut;
+
+    fn response(payload: &str, success: bool) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            success,
+            None,
+        )
+    }
+
+    fn param(name: &str, schema: Schema) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            schema,
+            required: true,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        }
+    }
+
+    fn strings(values: Vec<ParameterValue>) -> Vec<String> {
+        values
+            .iter()
+            .map(|value| match value {
+                ParameterValue::StringValue { value, .. } => value.clone(),
+                ParameterValue::IntValue { value, .. } => value.to_string(),
+                other => format!("{:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn harvests_the_fields_of_successful_responses() {
+        let mut corpus = sut::ResponseCorpus::new();
+
+        let payload = r#"[{"id": 7, "name": "Ada", "tags": ["x"]}, {"id": 7, "active": true}]"#;
+        assert_eq!(corpus.harvest(&response(payload, true)), 4);
+        assert_eq!(corpus.harvest(&response(r#"{"name": "Bob"}"#, false)), 0);
+        assert_eq!(corpus.harvest(&response("not json", true)), 0);
+
+        assert_eq!(
+            strings(corpus.values(&param("personId", Schema::Int))),
+            vec!["7"]
+        );
+        assert_eq!(
+            strings(corpus.values(&param("person_name", Schema::String))),
+            vec!["Ada"]
+        );
+    }
+
+    #[test]
+    fn without_a_named_field_any_value_of_the_kind_is_sampled() {
+        let mut corpus = sut::ResponseCorpus::new();
+        corpus.harvest(&response(r#"{"name": "Ada", "age": 36}"#, true));
+
+        assert_eq!(
+            strings(corpus.values(&param("query", Schema::String))),
+            vec!["Ada"]
+        );
+        assert_eq!(
+            strings(corpus.values(&param("limit", Schema::Int32))),
+            vec!["36"]
+        );
+        assert!(corpus
+            .values(&param("when", Schema::StringDateTime))
+            .is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use serde_json::Value;
+
+use crate::amos::{InvokeResult, ResultMetaData};
+use crate::json_diff;
+
+/// The first step of a sequence where the target and the reference target
+///  did not respond the same
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TargetDifference {
+    /// The index of the operation in the sequence
+    pub step: usize,
+    pub operation: String,
+    pub status: String,
+    pub reference_status: String,
+    /// The JSON pointers of the values of the payloads that differ, the root
+    ///  if a payload is not JSON
+    pub paths: Vec<String>,
+}
+
+impl std::fmt::Display for TargetDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Step {} ({}): {}, reference {}",
+            self.step + 1,
+            self.operation,
+            self.status,
+            self.reference_status
+        )?;
+        if !self.paths.is_empty() {
+            write!(f, ", differs at '{}'", self.paths.join("', '"))?;
+        }
+        Ok(())
+    }
+}
+
+/// The first difference of the responses of the same sequence, invoked on
+///  the target and on the reference. The payloads are compared without the
+///  fields of the names, e.g. timestamps and ids only one of them knows.
+///  A sequence with a transport error says nothing of the targets.
+pub fn first_difference(
+    results: &[InvokeResult],
+    reference: &[InvokeResult],
+    ignore_fields: &[String],
+) -> Option<TargetDifference> {
+    if results
+        .iter()
+        .chain(reference)
+        .any(|res| res.is_transport_error())
+    {
+        return None;
+    }
+
+    results
+        .iter()
+        .zip(reference)
+        .enumerate()
+        .find_map(|(step, (result, reference))| {
+            let status = status_of(result);
+            let reference_status = status_of(reference);
+            let paths = payload_differences(&result.result, &reference.result, ignore_fields);
+            if status == reference_status && paths.is_empty() {
+                return None;
+            }
+
+            Some(TargetDifference {
+                step,
+                operation: result.operation.name.clone(),
+                status,
+                reference_status,
+                paths,
+            })
+        })
+}
+
+fn status_of(result: &InvokeResult) -> String {
+    match &result.meta_data {
+        Some(ResultMetaData::HTTP { status, .. }) => status.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn payload_differences(payload: &str, reference: &str, ignore_fields: &[String]) -> Vec<String> {
+    match (
+        serde_json::from_str::<Value>(payload),
+        serde_json::from_str::<Value>(reference),
+    ) {
+        (Ok(mut payload), Ok(mut reference)) => {
+            json_diff::remove_fields(&mut payload, ignore_fields);
+            json_diff::remove_fields(&mut reference, ignore_fields);
+            json_diff::diff(&payload, &reference)
+                .into_iter()
+                .map(|difference| difference.path)
+                .collect()
+        }
+        _ if payload != reference => vec![String::new()],
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::HTTPStatus;
+
+    use crate::amos::{InvokeResult, ResultMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::differential as sut;
+
+    fn result(name: &str, status: HTTPStatus, payload: &str) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/".to_string(),
+                status,
+                size: payload.len(),
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn the_first_response_differing_from_the_reference() {
+        let results = vec![
+            result(
+                "post_person",
+                HTTPStatus::Created,
+                r#"{"id":1,"at":"10:00"}"#,
+            ),
+            result("get_person", HTTPStatus::OK, r#"{"name":"a","age":3}"#),
+        ];
+
+        // The order of the keys and the ignored fields are not differences
+        let reference = vec![
+            result(
+                "post_person",
+                HTTPStatus::Created,
+                r#"{"at":"11:00","id":1}"#,
+            ),
+            result("get_person", HTTPStatus::OK, r#"{"age":3,"name":"a"}"#),
+        ];
+        assert_eq!(
+            sut::first_difference(&results, &reference, &["at".to_string()]),
+            None
+        );
+
+        let reference = vec![
+            result(
+                "post_person",
+                HTTPStatus::Created,
+                r#"{"id":1,"at":"10:00"}"#,
+            ),
+            result("get_person", HTTPStatus::OK, r#"{"name":"a","age":4}"#),
+        ];
+        let difference = sut::first_difference(&results, &reference, &[]).unwrap();
+        assert_eq!(difference.step, 1);
+        assert_eq!(difference.operation, "get_person");
+        assert_eq!(difference.paths, vec!["/age"]);
+
+        let reference = vec![
+            result("post_person", HTTPStatus::BadRequest, "bad request"),
+            result("get_person", HTTPStatus::OK, r#"{"name":"a","age":3}"#),
+        ];
+        let difference = sut::first_difference(&results, &reference, &[]).unwrap();
+        assert_eq!(difference.step, 0);
+        assert_eq!(
+            difference.reference_status,
+            HTTPStatus::BadRequest.to_string()
+        );
+        assert_eq!(difference.paths, vec![""]);
+    }
+}
@@ -0,0 +1,29 @@
+/// Why the exploration of an operation failed, instead of panicking the
+///  thread of the exploration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExploreError {
+    /// A selected operation is not in the AMOS
+    UnknownOperation(String),
+    /// The query operation is not among the bucketed operations
+    MissingBucket(String),
+    /// The generator could not produce a sequence
+    Generation(String),
+}
+
+impl std::fmt::Display for ExploreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExploreError::UnknownOperation(name) => {
+                write!(f, "The operation '{}' is not in the specification", name)
+            }
+            ExploreError::MissingBucket(name) => {
+                write!(f, "The query operation '{}' has no bucket", name)
+            }
+            ExploreError::Generation(reason) => {
+                write!(f, "Could not generate a sequence: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExploreError {}
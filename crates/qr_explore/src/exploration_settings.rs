@@ -1,12 +1,70 @@
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+use std::collections::{BTreeMap, HashMap};
+
+use qr_http_resource::http::{HTTPAuth, HTTPParameterTarget, HTTPStatus};
+use tracing::warn;
+
+use crate::amos::{GeneratorSpec, Operation, ParameterMetaData, Schema};
+use crate::behaviours::Behaviour;
+use crate::idempotency;
+use crate::state_observer::StateObservation;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ExplorationSettings {
     pub state_mutation: StateMutationSettings,
+    #[serde(default)]
+    pub operation_weights: OperationWeights,
+    /// Observation of state that is not visible through the API
+    #[serde(default)]
+    pub state_observation: Option<StateObservation>,
+    #[serde(default)]
+    pub response_time: ResponseTimeSla,
+    #[serde(default)]
+    pub payload: PayloadLimits,
+    #[serde(default)]
+    pub injection: InjectionSettings,
+    #[serde(default)]
+    pub correlation: CorrelationSettings,
+    /// The endpoint probed before and during the explorations
+    #[serde(default)]
+    pub health: HealthSettings,
+    /// The build of the SUT the runs are tagged with
+    #[serde(default)]
+    pub tags: RunTagSettings,
+    #[serde(default)]
+    pub generators: GeneratorOverrides,
+    #[serde(default)]
+    pub model: ModelSettings,
+    #[serde(default)]
+    pub mock: MockSettings,
+    /// How the SUT is started and stopped, if it is managed
+    #[serde(default)]
+    pub sut: SutSettings,
+    /// The settings of the explored behaviour
+    #[serde(default)]
+    pub behaviour: BehaviourSettings,
+    /// Credentials are not saved with the settings
+    #[serde(skip)]
+    pub auth: AuthSettings,
 }
 
 impl ExplorationSettings {
     pub fn new() -> Self {
         ExplorationSettings {
             state_mutation: StateMutationSettings::new(),
+            operation_weights: OperationWeights::new(),
+            state_observation: None,
+            response_time: ResponseTimeSla::new(),
+            payload: PayloadLimits::new(),
+            injection: InjectionSettings::new(),
+            correlation: CorrelationSettings::new(),
+            health: HealthSettings::new(),
+            tags: RunTagSettings::new(),
+            generators: GeneratorOverrides::new(),
+            model: ModelSettings::new(),
+            mock: MockSettings::new(),
+            sut: SutSettings::new(),
+            behaviour: BehaviourSettings::None,
+            auth: AuthSettings::new(),
         }
     }
 }
@@ -17,13 +75,50 @@ impl Default for ExplorationSettings {
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct StateMutationSettings {
     pub number_of_tests: u16,
     pub min_length: u8,
     pub max_length: u8,
     pub query_operation_ids: Vec<usize>,
     pub selected_query_operation: Option<usize>,
+    /// Bias the generation towards operations not yet covered in the run
+    #[serde(default)]
+    pub coverage_guided: bool,
+    /// Explore GETs of the hypermedia links found in the responses
+    #[serde(default)]
+    pub follow_links: bool,
+    /// Sample the parameter values from the values harvested from the responses
+    #[serde(default)]
+    pub harvest_responses: bool,
+    /// Order the operations after the orders that succeeded earlier in the run
+    #[serde(default)]
+    pub learn_precedence: bool,
+    /// Mutate the sequences generated earlier in the run, e.g. swap adjacent
+    ///  operations, besides generating new ones
+    #[serde(default)]
+    pub mutate_sequences: bool,
+    /// Honor the cache headers of the responses within each sequence, see
+    ///  `cache::ClientCache`
+    #[serde(default)]
+    pub honor_cache_headers: bool,
+    /// Directory of the corpus of the campaign, the sequences with new
+    ///  statuses are saved to it and replayed first in later explorations
+    #[serde(default)]
+    pub corpus_dir: Option<String>,
+    /// File the values of the invoked sequences are recorded to, to replay
+    ///  them with the values they were sent with
+    #[serde(default)]
+    pub script_file: Option<String>,
+    #[serde(default)]
+    pub deprecated: DeprecatedOperations,
+    #[serde(default)]
+    pub strings: StringGeneration,
+    /// Seed of the generation, a random seed is drawn for each run if not set
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub shrinking: ShrinkSettings,
 }
 
 impl StateMutationSettings {
@@ -34,6 +129,18 @@ impl StateMutationSettings {
             max_length: 2,
             query_operation_ids: vec![],
             selected_query_operation: None,
+            coverage_guided: false,
+            follow_links: false,
+            harvest_responses: false,
+            learn_precedence: false,
+            mutate_sequences: false,
+            honor_cache_headers: false,
+            corpus_dir: None,
+            script_file: None,
+            deprecated: DeprecatedOperations::Include,
+            strings: StringGeneration::Ascii,
+            seed: None,
+            shrinking: ShrinkSettings::new(),
         }
     }
 
@@ -54,3 +161,1376 @@ impl Default for StateMutationSettings {
         Self::new()
     }
 }
+
+/// The strings generated for the string parameters without a generator of
+///  their own
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StringGeneration {
+    /// Lowercase ASCII letters
+    #[default]
+    Ascii,
+    /// International text, see `GeneratorSpec::Unicode`
+    Unicode,
+}
+
+impl StringGeneration {
+    /// Give the unicode generator to the plain string parameters
+    pub fn apply(&self, operations: &mut [Operation]) {
+        if *self != StringGeneration::Unicode {
+            return;
+        }
+
+        for operation in operations {
+            for parameter in &mut operation.parameters {
+                if parameter.generator.is_none()
+                    && matches!(parameter.schema, Schema::String | Schema::StringNonEmpty)
+                {
+                    parameter.generator = Some(GeneratorSpec::Unicode);
+                }
+            }
+        }
+    }
+}
+
+/// What the generation does with the operations the specification marks as
+///  deprecated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeprecatedOperations {
+    /// Generate them as any other operation
+    #[default]
+    Include,
+    /// Leave them out of the sequences
+    Exclude,
+    /// Only target them, e.g. to show that nothing depends on them before
+    ///  they are removed. The other operations may still set them up.
+    Only,
+}
+
+impl DeprecatedOperations {
+    /// If the operation is generated at all
+    pub fn generates(&self, operation: &Operation) -> bool {
+        *self != DeprecatedOperations::Exclude || !operation.info.deprecated
+    }
+
+    /// If the operation is one of the explored operations
+    pub fn targets(&self, operation: &Operation) -> bool {
+        match self {
+            DeprecatedOperations::Include => true,
+            DeprecatedOperations::Exclude => !operation.info.deprecated,
+            DeprecatedOperations::Only => operation.info.deprecated,
+        }
+    }
+}
+
+/// The order a failing sequence is simplified in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShrinkStrategy {
+    /// Remove operations first, then simplify the parameter values
+    #[default]
+    LengthFirst,
+    /// Simplify the parameter values first, then remove operations
+    ValueFirst,
+}
+
+/// What shrinking does with a sequence it already invoked. The check of a
+///  stateful SUT may not repeat, the state changed since the first invocation.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RepeatedSequence {
+    /// Reuse the check of the first invocation
+    #[default]
+    Reuse,
+    /// Invoke the sequence again, on the state the SUT has
+    Reinvoke,
+    /// Reset the SUT with the shell command, then invoke the sequence again
+    ResetFirst { command: String },
+}
+
+/// A budget of shrinking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ShrinkLimit {
+    Iterations(u32),
+    TimeSecs(u64),
+    /// The exploration was stopped before shrinking was done
+    Stopped,
+}
+
+impl std::fmt::Display for ShrinkLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShrinkLimit::Iterations(max) => write!(f, "the limit of {} iterations", max),
+            ShrinkLimit::TimeSecs(max) => write!(f, "the limit of {} s", max),
+            ShrinkLimit::Stopped => write!(f, "the stop of the exploration"),
+        }
+    }
+}
+
+/// How a failing example is shrunk. Shrinking is unbounded unless a budget is
+///  set, out of budget the simplest failing example so far is kept.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShrinkSettings {
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+    #[serde(default)]
+    pub max_time_secs: Option<u64>,
+    #[serde(default)]
+    pub strategy: ShrinkStrategy,
+    #[serde(default)]
+    pub repeated: RepeatedSequence,
+}
+
+impl ShrinkSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The budget that ran out after the iterations and the time shrinking
+    ///  took, if any
+    pub fn exhausted(&self, iterations: u32, elapsed: std::time::Duration) -> Option<ShrinkLimit> {
+        if let Some(max) = self.max_iterations.filter(|max| iterations >= *max) {
+            return Some(ShrinkLimit::Iterations(max));
+        }
+        self.max_time_secs
+            .filter(|max| elapsed.as_secs() >= *max)
+            .map(ShrinkLimit::TimeSecs)
+    }
+}
+
+/// A class of response statuses the response check fails on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusClass {
+    /// 4xx, the SUT rejected the request
+    ClientError,
+    /// 5xx, the SUT failed to handle the request
+    ServerError,
+}
+
+impl StatusClass {
+    pub const ALL: [StatusClass; 2] = [StatusClass::ClientError, StatusClass::ServerError];
+
+    pub fn includes(&self, status: &HTTPStatus) -> bool {
+        match (self, status.code()) {
+            (StatusClass::ClientError, Some(code)) => (400..=499).contains(&code),
+            (StatusClass::ServerError, Some(code)) => (500..=599).contains(&code),
+            (_, None) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for StatusClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusClass::ClientError => write!(f, "4xx"),
+            StatusClass::ServerError => write!(f, "5xx"),
+        }
+    }
+}
+
+/// The faults the chaos behaviour injects on the client side, the chance of
+///  each fault before a request that is not of a query operation
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChaosSettings {
+    /// Wait up to the longest delay before sending the request
+    #[serde(default)]
+    pub delay_probability: f64,
+    #[serde(default)]
+    pub max_delay_ms: u64,
+    /// Stop sending the requests of the sequence, the query operations are
+    ///  still sent
+    #[serde(default)]
+    pub drop_probability: f64,
+    /// Send the request twice, e.g. as a client retrying a request it got
+    ///  no response to
+    #[serde(default)]
+    pub repeat_probability: f64,
+}
+
+impl ChaosSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If any fault is injected
+    pub fn is_enabled(&self) -> bool {
+        (self.delay_probability > 0.0 && self.max_delay_ms > 0)
+            || self.drop_probability > 0.0
+            || self.repeat_probability > 0.0
+    }
+}
+
+/// Which combinations of the values of the parameters are invoked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ParameterCombinations {
+    /// Every combination of the values
+    #[default]
+    All,
+    /// Every pair of values of two parameters in at least one combination,
+    ///  far fewer combinations for an operation of many parameters
+    Pairwise,
+}
+
+/// The limits of the boundary values the boundary behaviour enumerates
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BoundarySettings {
+    /// The length of the longest string, and of the string in the longest
+    ///  array
+    #[serde(default = "BoundarySettings::default_max_string_length")]
+    pub max_string_length: usize,
+    /// The most combinations invoked of an operation, the rest are left out
+    #[serde(default = "BoundarySettings::default_max_combinations")]
+    pub max_combinations: usize,
+    #[serde(default)]
+    pub combinations: ParameterCombinations,
+}
+
+impl BoundarySettings {
+    pub const DEFAULT_MAX_STRING_LENGTH: usize = 255;
+    pub const DEFAULT_MAX_COMBINATIONS: usize = 256;
+
+    pub fn new() -> Self {
+        Self {
+            max_string_length: BoundarySettings::DEFAULT_MAX_STRING_LENGTH,
+            max_combinations: BoundarySettings::DEFAULT_MAX_COMBINATIONS,
+            combinations: ParameterCombinations::All,
+        }
+    }
+
+    fn default_max_string_length() -> usize {
+        BoundarySettings::DEFAULT_MAX_STRING_LENGTH
+    }
+
+    fn default_max_combinations() -> usize {
+        BoundarySettings::DEFAULT_MAX_COMBINATIONS
+    }
+}
+
+impl Default for BoundarySettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The settings only one behaviour has, the settings of the generation are
+///  shared by all behaviours
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BehaviourSettings {
+    #[default]
+    None,
+    /// The response equality and inequality behaviours compare the responses
+    ///  without the fields of the names, e.g. timestamps
+    ResponseEquality { ignore_fields: Vec<String> },
+    /// The response check fails on the statuses of the classes
+    ResponseCheck { failing_statuses: Vec<StatusClass> },
+    /// The state mutation observes all query operations together, before
+    ///  and after the same mutations, instead of one at a time
+    StateMutation { observe_all_queries: bool },
+    /// The state identity waits before the query operation observes the
+    ///  state again, for a SUT that changes its state eventually
+    StateIdentity { observation_delay_ms: u64 },
+    /// The differential behaviour invokes the sequences on the target and on
+    ///  the reference, e.g. the version being replaced, and compares the
+    ///  responses without the fields of the names
+    Differential {
+        reference_url: String,
+        ignore_fields: Vec<String>,
+    },
+    /// The contract behaviour captures the responses of a version known to
+    ///  be good to the contract file, or checks the responses against it
+    Contract {
+        contract_file: String,
+        capture: bool,
+    },
+    /// The chaos behaviour injects the faults between the operations of the
+    ///  sequences
+    Chaos { faults: ChaosSettings },
+    /// The boundary behaviour invokes the combinations of the boundary
+    ///  values of the parameters, instead of generated values
+    Boundary { limits: BoundarySettings },
+    /// The conditional request behaviour replays the updates with a stale
+    ///  ETag, a 409 Conflict rejects them as the 412 does if accepted
+    ConditionalRequest { accept_conflict: bool },
+    /// The idempotent retry behaviour sends the last POST of the sequences
+    ///  again, with the same key in the header
+    IdempotentRetry { header: String },
+}
+
+impl BehaviourSettings {
+    /// The default settings of the behaviour
+    pub fn new(behaviour: &Behaviour) -> Self {
+        match behaviour {
+            Behaviour::ResponseEquality | Behaviour::ResponseInequality => {
+                BehaviourSettings::ResponseEquality {
+                    ignore_fields: vec![],
+                }
+            }
+            Behaviour::Property => BehaviourSettings::ResponseCheck {
+                failing_statuses: vec![StatusClass::ServerError],
+            },
+            Behaviour::StateMutation => BehaviourSettings::StateMutation {
+                observe_all_queries: false,
+            },
+            Behaviour::StateIdentity => BehaviourSettings::StateIdentity {
+                observation_delay_ms: 0,
+            },
+            Behaviour::Differential => BehaviourSettings::Differential {
+                reference_url: String::new(),
+                ignore_fields: vec![],
+            },
+            Behaviour::Contract => BehaviourSettings::Contract {
+                contract_file: String::new(),
+                capture: false,
+            },
+            Behaviour::Chaos => BehaviourSettings::Chaos {
+                faults: ChaosSettings::new(),
+            },
+            Behaviour::Boundary => BehaviourSettings::Boundary {
+                limits: BoundarySettings::new(),
+            },
+            Behaviour::ConditionalRequest => BehaviourSettings::ConditionalRequest {
+                accept_conflict: false,
+            },
+            Behaviour::IdempotentRetry => BehaviourSettings::IdempotentRetry {
+                header: idempotency::DEFAULT_HEADER.to_string(),
+            },
+            _ => BehaviourSettings::None,
+        }
+    }
+
+    /// If the settings are of the behaviour
+    pub fn is_of(&self, behaviour: &Behaviour) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(&BehaviourSettings::new(behaviour))
+    }
+
+    pub fn ignore_fields(&self) -> &[String] {
+        match self {
+            BehaviourSettings::ResponseEquality { ignore_fields }
+            | BehaviourSettings::Differential { ignore_fields, .. } => ignore_fields,
+            _ => &[],
+        }
+    }
+
+    /// The URL of the target the differential behaviour compares with, if set
+    pub fn reference_url(&self) -> Option<&str> {
+        match self {
+            BehaviourSettings::Differential { reference_url, .. } if !reference_url.is_empty() => {
+                Some(reference_url)
+            }
+            _ => None,
+        }
+    }
+
+    /// The failing statuses, the server errors unless set
+    pub fn failing_statuses(&self) -> &[StatusClass] {
+        match self {
+            BehaviourSettings::ResponseCheck { failing_statuses } => failing_statuses,
+            _ => &[StatusClass::ServerError],
+        }
+    }
+
+    /// The contract file, and if the contract is captured to it instead of
+    ///  checked, if the file is set
+    pub fn contract(&self) -> Option<(&str, bool)> {
+        match self {
+            BehaviourSettings::Contract {
+                contract_file,
+                capture,
+            } if !contract_file.is_empty() => Some((contract_file, *capture)),
+            _ => None,
+        }
+    }
+
+    /// The faults of the chaos behaviour, if any fault is injected
+    pub fn chaos(&self) -> Option<&ChaosSettings> {
+        match self {
+            BehaviourSettings::Chaos { faults } if faults.is_enabled() => Some(faults),
+            _ => None,
+        }
+    }
+
+    /// The limits of the boundary values, the defaults unless set
+    pub fn boundary(&self) -> BoundarySettings {
+        match self {
+            BehaviourSettings::Boundary { limits } => *limits,
+            _ => BoundarySettings::new(),
+        }
+    }
+
+    /// If the updates are sent with the ETag of the first read, and if a 409
+    ///  rejects the stale one
+    pub fn conditional_request(&self) -> Option<bool> {
+        match self {
+            BehaviourSettings::ConditionalRequest { accept_conflict } => Some(*accept_conflict),
+            _ => None,
+        }
+    }
+
+    /// The header of the idempotency keys, if the last POST is retried
+    pub fn idempotent_retry(&self) -> Option<&str> {
+        match self {
+            BehaviourSettings::IdempotentRetry { header } => Some(header),
+            _ => None,
+        }
+    }
+
+    pub fn observes_all_queries(&self) -> bool {
+        matches!(
+            self,
+            BehaviourSettings::StateMutation {
+                observe_all_queries: true
+            }
+        )
+    }
+
+    pub fn observation_delay(&self) -> std::time::Duration {
+        match self {
+            BehaviourSettings::StateIdentity {
+                observation_delay_ms,
+            } => std::time::Duration::from_millis(*observation_delay_ms),
+            _ => std::time::Duration::ZERO,
+        }
+    }
+}
+
+/// Relative weights, by operation name, used when the generator selects
+///  operations. Operations without a weight have the default weight.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct OperationWeights {
+    weights: HashMap<String, u32>,
+}
+
+impl OperationWeights {
+    pub const DEFAULT_WEIGHT: u32 = 1;
+
+    pub fn new() -> Self {
+        Self {
+            weights: HashMap::new(),
+        }
+    }
+
+    pub fn weight(&self, operation: &str) -> u32 {
+        *self
+            .weights
+            .get(operation)
+            .unwrap_or(&OperationWeights::DEFAULT_WEIGHT)
+    }
+
+    pub fn set(&mut self, operation: &str, weight: u32) {
+        if weight == OperationWeights::DEFAULT_WEIGHT {
+            self.weights.remove(operation);
+        } else {
+            self.weights.insert(operation.to_string(), weight);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+}
+
+/// The longest time, in milliseconds, an operation may take to respond. An
+///  operation without a threshold of its own has the global threshold.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResponseTimeSla {
+    pub global_ms: u64,
+    #[serde(default)]
+    pub operations_ms: HashMap<String, u64>,
+}
+
+impl ResponseTimeSla {
+    pub const DEFAULT_THRESHOLD_MS: u64 = 1000;
+
+    pub fn new() -> Self {
+        Self {
+            global_ms: ResponseTimeSla::DEFAULT_THRESHOLD_MS,
+            operations_ms: HashMap::new(),
+        }
+    }
+
+    pub fn threshold(&self, operation: &str) -> std::time::Duration {
+        let ms = self.operations_ms.get(operation).unwrap_or(&self.global_ms);
+
+        std::time::Duration::from_millis(*ms)
+    }
+
+    pub fn is_exceeded(&self, operation: &str, duration: std::time::Duration) -> bool {
+        duration > self.threshold(operation)
+    }
+}
+
+impl Default for ResponseTimeSla {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The limits of the response payloads, by their size in bytes as received
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PayloadLimits {
+    pub max_size: usize,
+    /// Payloads of at least this size must be compressed, unset to allow
+    ///  any payload uncompressed
+    pub compress_from: Option<usize>,
+    /// Stress the SUT with large request payloads, see `PayloadStress`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stress: Option<PayloadStress>,
+}
+
+impl PayloadLimits {
+    pub const DEFAULT_MAX_SIZE: usize = 1024 * 1024;
+    pub const DEFAULT_COMPRESS_FROM: usize = 1024;
+
+    pub fn new() -> Self {
+        Self {
+            max_size: PayloadLimits::DEFAULT_MAX_SIZE,
+            compress_from: Some(PayloadLimits::DEFAULT_COMPRESS_FROM),
+            stress: None,
+        }
+    }
+
+    pub fn is_exceeded(&self, size: usize, content_encoding: Option<&str>) -> bool {
+        let uncompressed = content_encoding.is_none_or(|encoding| encoding == "identity");
+        let must_compress = self.compress_from.is_some_and(|from| size >= from);
+
+        size > self.max_size || (uncompressed && must_compress)
+    }
+}
+
+impl Default for PayloadLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Very large strings and arrays for the body parameters, to probe the limits
+///  of the request payloads. The response check accepts a 413 Payload Too
+///  Large, but not a server error nor a timeout.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PayloadStress {
+    #[serde(default = "PayloadStress::default_max_string_length")]
+    pub max_string_length: usize,
+    #[serde(default = "PayloadStress::default_max_array_length")]
+    pub max_array_length: usize,
+}
+
+impl PayloadStress {
+    pub const DEFAULT_MAX_STRING_LENGTH: usize = 1024 * 1024;
+    pub const DEFAULT_MAX_ARRAY_LENGTH: usize = 10_000;
+
+    pub fn new() -> Self {
+        Self {
+            max_string_length: PayloadStress::DEFAULT_MAX_STRING_LENGTH,
+            max_array_length: PayloadStress::DEFAULT_MAX_ARRAY_LENGTH,
+        }
+    }
+
+    fn default_max_string_length() -> usize {
+        PayloadStress::DEFAULT_MAX_STRING_LENGTH
+    }
+
+    fn default_max_array_length() -> usize {
+        PayloadStress::DEFAULT_MAX_ARRAY_LENGTH
+    }
+
+    /// Give the large generators to the string and array parameters sent in
+    ///  the bodies, the parameters with a generator of their own keep it
+    pub fn apply(&self, operations: &mut [Operation]) {
+        for operation in operations {
+            for parameter in &mut operation.parameters {
+                let in_body = matches!(
+                    parameter.meta_data,
+                    Some(ParameterMetaData::HTTP {
+                        target: HTTPParameterTarget::Body | HTTPParameterTarget::FormData
+                    })
+                );
+                if !in_body || parameter.generator.is_some() {
+                    continue;
+                }
+
+                let max_length = match parameter.schema {
+                    Schema::String | Schema::StringNonEmpty => self.max_string_length,
+                    Schema::ArrayOfString => self.max_array_length,
+                    _ => continue,
+                };
+                parameter.generator = Some(GeneratorSpec::Large { max_length });
+            }
+        }
+    }
+}
+
+impl Default for PayloadStress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The operations that attack payloads are injected into, none unless enabled
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InjectionSettings {
+    #[serde(default)]
+    pub operations: Vec<String>,
+}
+
+impl InjectionSettings {
+    pub fn new() -> Self {
+        Self { operations: vec![] }
+    }
+
+    pub fn is_enabled(&self, operation: &str) -> bool {
+        self.operations.iter().any(|op| op == operation)
+    }
+
+    pub fn set(&mut self, operation: &str, enabled: bool) {
+        if enabled && !self.is_enabled(operation) {
+            self.operations.push(operation.to_string());
+        } else if !enabled {
+            self.operations.retain(|op| op != operation);
+        }
+    }
+}
+
+impl Default for InjectionSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The header the correlation id of each sequence is sent in, e.g.
+///  'X-Request-Id'. Without a header the ids are only recorded.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CorrelationSettings {
+    #[serde(default)]
+    pub header: Option<String>,
+    /// The header a key of each POST is sent in, e.g. 'Idempotency-Key'.
+    ///  The keys are made of the correlation id of the sequence.
+    #[serde(default)]
+    pub idempotency_header: Option<String>,
+}
+
+impl CorrelationSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The header of the idempotency keys, the default unless set
+    pub fn idempotency_key_header(&self) -> &str {
+        self.idempotency_header
+            .as_deref()
+            .unwrap_or(idempotency::DEFAULT_HEADER)
+    }
+}
+
+/// The health endpoint of the target, e.g. '/health'. It is probed before
+///  an exploration starts and every interval while it runs, and after a
+///  request without a response. The invocations are paused while the target
+///  is unhealthy, for at most the longest pause.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct HealthSettings {
+    pub path: Option<String>,
+    pub interval_secs: u64,
+    pub max_pause_secs: u64,
+}
+
+impl HealthSettings {
+    pub const DEFAULT_INTERVAL_SECS: u64 = 10;
+    pub const DEFAULT_MAX_PAUSE_SECS: u64 = 300;
+
+    pub fn new() -> Self {
+        Self {
+            path: None,
+            interval_secs: HealthSettings::DEFAULT_INTERVAL_SECS,
+            max_pause_secs: HealthSettings::DEFAULT_MAX_PAUSE_SECS,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
+impl Default for HealthSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The build of the SUT a run is tagged with, to trace its findings to the
+///  build they were found against. The version is fetched from the version
+///  path as the run starts.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RunTagSettings {
+    /// E.g. '/version', responding the version or a JSON object with a
+    ///  `version` field
+    pub version_path: Option<String>,
+    pub git_commit: Option<String>,
+    /// E.g. 'staging'
+    pub environment: Option<String>,
+}
+
+impl RunTagSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Named generators, and the parameters they generate the values of in place
+///  of their schemas
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GeneratorOverrides {
+    pub generators: BTreeMap<String, GeneratorSpec>,
+    /// The name of the generator of a parameter, by operation and parameter
+    pub parameters: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl GeneratorOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn generator_name(&self, operation: &str, parameter: &str) -> Option<&str> {
+        self.parameters
+            .get(operation)
+            .and_then(|parameters| parameters.get(parameter))
+            .map(String::as_str)
+    }
+
+    /// Assign the named generator to the parameter, `None` returns the
+    ///  parameter to its schema
+    pub fn set(&mut self, operation: &str, parameter: &str, generator: Option<&str>) {
+        match generator {
+            Some(name) => {
+                self.parameters
+                    .entry(operation.to_string())
+                    .or_default()
+                    .insert(parameter.to_string(), name.to_string());
+            }
+            None => {
+                if let Some(parameters) = self.parameters.get_mut(operation) {
+                    parameters.remove(parameter);
+                    if parameters.is_empty() {
+                        self.parameters.remove(operation);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove the generator and its assignments
+    pub fn remove(&mut self, name: &str) {
+        self.generators.remove(name);
+        for parameters in self.parameters.values_mut() {
+            parameters.retain(|_, generator| generator != name);
+        }
+        self.parameters
+            .retain(|_, parameters| !parameters.is_empty());
+    }
+
+    /// The named generator, with a dictionary read into the values it holds
+    pub fn resolve(&self, name: &str) -> Option<GeneratorSpec> {
+        let Some(spec) = self.generators.get(name) else {
+            warn!("No generator named '{}'", name);
+            return None;
+        };
+
+        match spec {
+            GeneratorSpec::Dictionary { path } => match std::fs::read_to_string(path) {
+                Ok(content) => Some(GeneratorSpec::Enum {
+                    values: content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                }),
+                Err(e) => {
+                    warn!("Could not read dictionary '{}': {}", path, e);
+                    None
+                }
+            },
+            spec => Some(spec.clone()),
+        }
+    }
+
+    /// Set the generators of the parameters of the operations. A dictionary
+    ///  that cannot be read leaves the parameter to its schema.
+    pub fn apply(&self, operations: &mut [Operation]) {
+        for operation in operations {
+            for parameter in &mut operation.parameters {
+                if let Some(name) = self.generator_name(&operation.info.name, &parameter.name) {
+                    parameter.generator = self.resolve(name);
+                }
+            }
+        }
+    }
+}
+
+/// The source of the model the model behaviour checks the SUT against, see
+///  `model::Model`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ModelSettings {
+    #[serde(default)]
+    pub source: String,
+}
+
+impl ModelSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// A response the mock SUT gives every invocation of an operation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CannedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// The responses of the mock SUT of dry runs, see `mock::MockSut`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MockSettings {
+    /// Canned responses by operation name, before any emulation
+    #[serde(default)]
+    pub responses: BTreeMap<String, CannedResponse>,
+    /// Emulate collections of resources, created by a POST to the collection
+    #[serde(default)]
+    pub crud: bool,
+}
+
+impl MockSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// How the SUT is started, see `sut_manager::SutManager`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SutLauncher {
+    /// The SUT is not managed, it is expected to be running
+    #[default]
+    None,
+    /// A shell command running the SUT until it is stopped. The stop command
+    ///  is run first if set, then the process of the command is killed.
+    Command { start: String, stop: Option<String> },
+    /// The services of a docker compose file, started detached
+    Compose { file: String },
+}
+
+/// How the SUT is started before a run, waited for until it is healthy and
+///  torn down after the run
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SutSettings {
+    pub launcher: SutLauncher,
+    /// The path probed until it responds without a server error, else the
+    ///  SUT is healthy once it accepts connections
+    pub health_path: Option<String>,
+    pub startup_timeout_secs: u64,
+    /// Restart the SUT between the behaviours of a run, each behaviour starts
+    ///  from the initial state
+    pub reset_between_behaviours: bool,
+}
+
+impl SutSettings {
+    pub const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+    pub fn new() -> Self {
+        Self {
+            launcher: SutLauncher::None,
+            health_path: None,
+            startup_timeout_secs: SutSettings::DEFAULT_STARTUP_TIMEOUT_SECS,
+            reset_between_behaviours: false,
+        }
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.launcher != SutLauncher::None
+    }
+}
+
+impl Default for SutSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The credentials a request is sent with
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum AuthRole {
+    #[default]
+    Configured,
+    Downgraded,
+    Stripped,
+    /// One of the roles of the role matrix, by its index
+    Role(usize),
+}
+
+impl std::fmt::Display for AuthRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthRole::Configured => write!(f, "configured credentials"),
+            AuthRole::Downgraded => write!(f, "downgraded credentials"),
+            AuthRole::Stripped => write!(f, "no credentials"),
+            AuthRole::Role(idx) => write!(f, "role {}", idx),
+        }
+    }
+}
+
+/// The credentials of a named role, e.g. 'admin', a role without any is
+///  anonymous
+#[derive(Debug, Clone, PartialEq)]
+pub struct CredentialSet {
+    pub name: String,
+    pub auth: Option<HTTPAuth>,
+}
+
+impl CredentialSet {
+    pub fn new(name: &str, auth: Option<HTTPAuth>) -> Self {
+        Self {
+            name: name.to_string(),
+            auth,
+        }
+    }
+}
+
+/// The credentials of the requests, of a role with lesser privileges, and of
+///  the roles of the role matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthSettings {
+    pub credentials: Option<HTTPAuth>,
+    pub downgraded: Option<HTTPAuth>,
+    pub roles: Vec<CredentialSet>,
+}
+
+impl AuthSettings {
+    pub fn new() -> Self {
+        Self {
+            credentials: None,
+            downgraded: None,
+            roles: vec![],
+        }
+    }
+
+    pub fn credentials(&self, role: AuthRole) -> Option<&HTTPAuth> {
+        match role {
+            AuthRole::Configured => self.credentials.as_ref(),
+            AuthRole::Downgraded => self.downgraded.as_ref(),
+            AuthRole::Stripped => None,
+            AuthRole::Role(idx) => self.roles.get(idx).and_then(|role| role.auth.as_ref()),
+        }
+    }
+
+    /// The roles that should not be authorized where the configured one is
+    pub fn bypass_roles(&self) -> Vec<AuthRole> {
+        let mut roles = vec![AuthRole::Stripped];
+        if self.downgraded.is_some() {
+            roles.push(AuthRole::Downgraded);
+        }
+
+        roles
+    }
+}
+
+impl Default for AuthSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos_generation::RegexPattern;
+    use crate::exploration_settings::*;
+
+    #[test]
+    fn status_classes_include_every_code_of_the_class() {
+        let server_error = StatusClass::ServerError;
+        assert!(server_error.includes(&HTTPStatus::InternalServerError));
+        assert!(server_error.includes(&HTTPStatus::from_code(503)));
+        assert!(!server_error.includes(&HTTPStatus::NotFound));
+
+        assert!(StatusClass::ClientError.includes(&HTTPStatus::PayloadTooLarge));
+        assert!(StatusClass::ClientError.includes(&HTTPStatus::from_code(429)));
+        assert!(!StatusClass::ClientError.includes(&HTTPStatus::Default));
+    }
+
+    #[test]
+    fn operation_weights_default() {
+        let mut weights = OperationWeights::new();
+        weights.set("getFoo", 5);
+
+        assert_eq!(weights.weight("getFoo"), 5);
+        assert_eq!(weights.weight("getBar"), OperationWeights::DEFAULT_WEIGHT);
+    }
+
+    #[test]
+    fn operation_weights_set_default_removes() {
+        let mut weights = OperationWeights::new();
+        weights.set("getFoo", 5);
+        weights.set("getFoo", OperationWeights::DEFAULT_WEIGHT);
+
+        assert!(weights.is_empty());
+    }
+
+    #[test]
+    fn shrink_settings_exhausted() {
+        let unbounded = ShrinkSettings::new();
+        assert_eq!(
+            unbounded.exhausted(u32::MAX, std::time::Duration::from_secs(3600)),
+            None
+        );
+
+        let budget = ShrinkSettings {
+            max_iterations: Some(100),
+            max_time_secs: Some(30),
+            strategy: ShrinkStrategy::ValueFirst,
+            repeated: RepeatedSequence::Reuse,
+        };
+        assert_eq!(
+            budget.exhausted(99, std::time::Duration::from_secs(29)),
+            None
+        );
+        assert_eq!(
+            budget.exhausted(100, std::time::Duration::from_secs(0)),
+            Some(ShrinkLimit::Iterations(100))
+        );
+        assert_eq!(
+            budget.exhausted(1, std::time::Duration::from_secs(30)),
+            Some(ShrinkLimit::TimeSecs(30))
+        );
+    }
+
+    #[test]
+    fn operation_weights_serialize_as_map() {
+        let weights: OperationWeights = serde_json::from_str(r#"{"getFoo": 3}"#).unwrap();
+
+        assert_eq!(weights.weight("getFoo"), 3);
+        assert_eq!(serde_json::to_string(&weights).unwrap(), r#"{"getFoo":3}"#);
+    }
+
+    #[test]
+    fn response_time_sla_per_operation() {
+        let mut sla = ResponseTimeSla::new();
+        sla.global_ms = 100;
+        sla.operations_ms.insert("getFoo".to_string(), 500);
+
+        let duration = std::time::Duration::from_millis(200);
+        assert!(!sla.is_exceeded("getFoo", duration));
+        assert!(sla.is_exceeded("getBar", duration));
+    }
+
+    #[test]
+    fn payload_limits_size_and_compression() {
+        let limits = PayloadLimits {
+            max_size: 4096,
+            compress_from: Some(1024),
+            stress: None,
+        };
+
+        assert!(!limits.is_exceeded(512, None));
+        assert!(limits.is_exceeded(2048, None));
+        assert!(limits.is_exceeded(2048, Some("identity")));
+        assert!(!limits.is_exceeded(2048, Some("gzip")));
+        assert!(limits.is_exceeded(8192, Some("gzip")));
+
+        let uncompressed = PayloadLimits {
+            compress_from: None,
+            ..limits
+        };
+        assert!(!uncompressed.is_exceeded(2048, None));
+    }
+
+    #[test]
+    fn injection_settings_off_by_default() {
+        let mut injection = InjectionSettings::new();
+        assert!(!injection.is_enabled("getFoo"));
+
+        injection.set("getFoo", true);
+        injection.set("getFoo", true);
+        assert_eq!(injection.operations, vec!["getFoo".to_string()]);
+
+        injection.set("getFoo", false);
+        assert!(!injection.is_enabled("getFoo"));
+    }
+
+    #[test]
+    fn auth_settings_per_role() {
+        let mut auth = AuthSettings::new();
+        auth.credentials = Some(HTTPAuth::bearer("admin"));
+
+        assert_eq!(auth.bypass_roles(), vec![AuthRole::Stripped]);
+        assert_eq!(
+            auth.credentials(AuthRole::Configured)
+                .map(|a| a.value.as_str()),
+            Some("Bearer admin")
+        );
+        assert!(auth.credentials(AuthRole::Stripped).is_none());
+
+        auth.downgraded = Some(HTTPAuth::bearer("user"));
+        assert_eq!(
+            auth.bypass_roles(),
+            vec![AuthRole::Stripped, AuthRole::Downgraded]
+        );
+
+        auth.roles = vec![
+            CredentialSet::new("user", Some(HTTPAuth::bearer("user"))),
+            CredentialSet::new("anonymous", None),
+        ];
+        assert_eq!(
+            auth.credentials(AuthRole::Role(0))
+                .map(|a| a.value.as_str()),
+            Some("Bearer user")
+        );
+        assert!(auth.credentials(AuthRole::Role(1)).is_none());
+        assert!(auth.credentials(AuthRole::Role(2)).is_none());
+    }
+
+    #[test]
+    fn unicode_strings_are_given_to_plain_string_parameters() {
+        let parameter = |name: &str, schema: crate::amos::Schema| crate::amos::Parameter {
+            name: name.to_string(),
+            schema,
+            required: true,
+            ownership: crate::amos::ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let mut assigned = parameter("nick", Schema::String);
+        assigned.generator = Some(GeneratorSpec::Range { min: 0, max: 1 });
+        let mut operations = vec![Operation {
+            info: crate::amos::OperationInfo {
+                name: "postPerson".to_string(),
+                key: "postPerson".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                parameter("name", Schema::String),
+                parameter("age", Schema::Int),
+                parameter(
+                    "code",
+                    Schema::StringRegex {
+                        regex: RegexPattern::new("[A-Z]{3}"),
+                    },
+                ),
+                assigned,
+            ],
+            responses: vec![],
+            meta_data: None,
+        }];
+
+        StringGeneration::Ascii.apply(&mut operations);
+        assert_eq!(operations[0].parameters[0].generator, None);
+
+        StringGeneration::Unicode.apply(&mut operations);
+        let generators: Vec<_> = operations[0]
+            .parameters
+            .iter()
+            .map(|parameter| parameter.generator.clone())
+            .collect();
+        assert_eq!(
+            generators,
+            vec![
+                Some(GeneratorSpec::Unicode),
+                None,
+                None,
+                Some(GeneratorSpec::Range { min: 0, max: 1 })
+            ]
+        );
+    }
+
+    #[test]
+    fn large_payloads_are_given_to_the_body_parameters() {
+        let parameter = |name: &str, schema: crate::amos::Schema, target| crate::amos::Parameter {
+            name: name.to_string(),
+            schema,
+            required: true,
+            ownership: crate::amos::ParameterOwnership::Unknown,
+            meta_data: Some(ParameterMetaData::HTTP { target }),
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let mut operations = vec![Operation {
+            info: crate::amos::OperationInfo {
+                name: "postPerson".to_string(),
+                key: "postPerson".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                parameter("name", Schema::String, HTTPParameterTarget::Body),
+                parameter("tags", Schema::ArrayOfString, HTTPParameterTarget::FormData),
+                parameter("age", Schema::Int, HTTPParameterTarget::Body),
+                parameter("id", Schema::String, HTTPParameterTarget::Path),
+            ],
+            responses: vec![],
+            meta_data: None,
+        }];
+
+        PayloadStress {
+            max_string_length: 100,
+            max_array_length: 10,
+        }
+        .apply(&mut operations);
+        let generators: Vec<_> = operations[0]
+            .parameters
+            .iter()
+            .map(|parameter| parameter.generator.clone())
+            .collect();
+        assert_eq!(
+            generators,
+            vec![
+                Some(GeneratorSpec::Large { max_length: 100 }),
+                Some(GeneratorSpec::Large { max_length: 10 }),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn deprecated_operations_generated_and_targeted() {
+        let operation = |name: &str, deprecated: bool| Operation {
+            info: crate::amos::OperationInfo {
+                name: name.to_string(),
+                key: name.to_string(),
+                alias: None,
+                deprecated,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: None,
+        };
+        let current = operation("getPerson", false);
+        let old = operation("getPeople", true);
+
+        assert!(DeprecatedOperations::Include.generates(&old));
+        assert!(DeprecatedOperations::Include.targets(&old));
+        assert!(!DeprecatedOperations::Exclude.generates(&old));
+        assert!(!DeprecatedOperations::Exclude.targets(&old));
+        assert!(DeprecatedOperations::Exclude.targets(&current));
+        // The other operations may still set up the deprecated ones
+        assert!(DeprecatedOperations::Only.generates(&current));
+        assert!(!DeprecatedOperations::Only.targets(&current));
+        assert!(DeprecatedOperations::Only.targets(&old));
+    }
+
+    #[test]
+    fn generator_overrides_apply_to_parameters() {
+        let dir = tempfile::tempdir().unwrap();
+        let dictionary = dir.path().join("dictionary.txt");
+        std::fs::write(&dictionary, "alice\n\nbob\n").unwrap();
+
+        let mut overrides = GeneratorOverrides::new();
+        overrides.generators.insert(
+            "names".to_string(),
+            GeneratorSpec::Dictionary {
+                path: dictionary.display().to_string(),
+            },
+        );
+        overrides.generators.insert(
+            "ages".to_string(),
+            GeneratorSpec::Range { min: 0, max: 120 },
+        );
+        overrides.set("postPerson", "name", Some("names"));
+        overrides.set("postPerson", "age", Some("ages"));
+        overrides.set("postPerson", "id", Some("ages"));
+        overrides.set("postPerson", "id", None);
+
+        let parameter = |name: &str| crate::amos::Parameter {
+            name: name.to_string(),
+            schema: crate::amos::Schema::String,
+            required: true,
+            ownership: crate::amos::ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let mut operations = vec![Operation {
+            info: crate::amos::OperationInfo {
+                name: "postPerson".to_string(),
+                key: "postPerson".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![parameter("name"), parameter("age"), parameter("id")],
+            responses: vec![],
+            meta_data: None,
+        }];
+
+        overrides.apply(&mut operations);
+
+        let generators: Vec<_> = operations[0]
+            .parameters
+            .iter()
+            .map(|parameter| parameter.generator.clone())
+            .collect();
+        assert_eq!(
+            generators,
+            vec![
+                Some(GeneratorSpec::Enum {
+                    values: vec!["alice".to_string(), "bob".to_string()]
+                }),
+                Some(GeneratorSpec::Range { min: 0, max: 120 }),
+                None,
+            ]
+        );
+
+        overrides.remove("ages");
+        assert_eq!(overrides.generator_name("postPerson", "age"), None);
+        assert_eq!(
+            overrides.generator_name("postPerson", "name"),
+            Some("names")
+        );
+    }
+
+    #[test]
+    fn behaviour_settings_of_the_behaviour() {
+        use crate::behaviours::Behaviour;
+
+        let equality = BehaviourSettings::new(&Behaviour::ResponseInequality);
+        assert!(equality.is_of(&Behaviour::ResponseEquality));
+        assert!(!equality.is_of(&Behaviour::StateIdentity));
+        assert!(BehaviourSettings::new(&Behaviour::Soak).is_of(&Behaviour::Payload));
+
+        // Settings of another behaviour have the defaults
+        assert_eq!(
+            equality.failing_statuses(),
+            BehaviourSettings::new(&Behaviour::Property).failing_statuses()
+        );
+        assert_eq!(equality.observation_delay(), std::time::Duration::ZERO);
+
+        let identity = BehaviourSettings::StateIdentity {
+            observation_delay_ms: 250,
+        };
+        assert_eq!(
+            identity.observation_delay(),
+            std::time::Duration::from_millis(250)
+        );
+        let json = serde_json::to_string(&identity).unwrap();
+        assert_eq!(
+            json,
+            r#"{"kind":"state_identity","observation_delay_ms":250}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<BehaviourSettings>(&json).unwrap(),
+            identity
+        );
+    }
+}
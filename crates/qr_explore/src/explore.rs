@@ -1,26 +1,72 @@
-use crate::amos::{InvokeResult, Operation, OperationMetaData};
-use crate::amos_buckets::Buckets;
+use crate::amos::{InvokeResult, Operation, OperationMetaData, AMOS};
+use crate::amos_buckets::{self, Buckets};
 use crate::amos_generation::{
-    gen_pinned_operation_sequence_with_params, GeneratedOperation,
+    gen_buckets, gen_pinned_guided_operation_sequence_with_params,
+    gen_pinned_weighted_operation_sequence_with_params, GeneratedOperation, GenerationGuidance,
     GenerationOperationWithParameters,
 };
-use crate::amos_generation::{gen_static_operation_with_params, QueryOptions};
-use crate::http_translation::{translate_generated_operation_to_http_call, translate_http_result};
+use crate::amos_generation::{
+    gen_static_injection_operation_with_params, gen_static_operation_with_params, QueryOptions,
+};
+use crate::amos_result::SharedCoverage;
+use crate::boundary;
+use crate::cache::ClientCache;
+use crate::campaign::SharedCampaign;
+use crate::chaos::{self, ChaosFault, ChaosHooks, Inconsistency, InjectedFault};
+use crate::coap;
+use crate::conditional;
+use crate::contract::{Contract, ContractViolation};
+use crate::corpus::SharedCorpus;
+use crate::differential::{self, TargetDifference};
+use crate::error::ExploreError;
+use crate::exploration_settings::{
+    AuthRole, AuthSettings, BehaviourSettings, InjectionSettings, OperationWeights, PayloadLimits,
+    RepeatedSequence, ResponseTimeSla, ShrinkLimit, ShrinkSettings,
+};
+use crate::health::HealthCheck;
+use crate::http_translation::{
+    resolve_response_references, translate_generated_operation_to_http_call, translate_http_result,
+};
+use crate::hypermedia::SharedLinks;
+use crate::idempotency;
+use crate::injection;
+use crate::linearizability::{self, CrudKind, HistoryEntry, NonLinearizableWindow};
+use crate::manifest::RunManifest;
 use crate::meta_properties::{
     self, check_response_equality, check_response_inequality,
     check_state_identity_with_observation, check_state_mutation,
 };
+use crate::mock::{MockSut, FIXED_PAYLOAD};
+use crate::model::{Divergence, ModelChecker};
+use crate::mutation::SharedMutation;
+use crate::observer::{self, ExplorationObserver};
+use crate::precedence::SharedPrecedence;
+use crate::progress::RunTests;
+use crate::queue;
+use crate::role_matrix::RoleOutcome;
+use crate::script::{ReplayScript, SharedScript};
+use crate::security_audit::{self, SecurityFinding};
+use crate::soak::{OperationTrend, SoakLog, SOAK_WINDOWS};
+use crate::state_observer::{self, ObservationMode, StateObservation, OBSERVATION_OPERATION};
 use crate::synthesize::{
     synthesize_operations_for_response_equality, synthesize_operations_for_response_inequality,
     synthesize_operations_for_state_identity, synthesize_operations_for_state_mutation,
     synthesize_property_operations,
 };
-use proptest::strategy::{Strategy, ValueTree};
-use proptest::test_runner::{Config, FileFailurePersistence, TestRunner};
-use qr_http_resource::http::{self, HTTPCall, HTTPMethod};
+use proptest::prelude::RngCore;
+use proptest::strategy::{BoxedStrategy, Strategy, ValueTree};
+use proptest::test_runner::{Config, FileFailurePersistence, RngAlgorithm, TestRng, TestRunner};
+use qr_http_resource::http::{self, HTTPAuth, HTTPCall, HTTPMethod};
 use qr_http_resource::reqwest_http;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, span, trace, Level};
+use std::cell::{Cell, RefCell};
+use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::hash::{Hash, Hasher};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{debug, info, span, trace, warn, Level};
 
 #[derive(Debug, PartialEq)]
 pub enum LogLevel {
@@ -39,6 +85,11 @@ pub struct LogMessage {
 pub enum ControlEvent {
     Started,
     Finished,
+    /// The exploration stopped before it was done, e.g. for an unreachable
+    ///  target. Sent before `Finished`.
+    Aborted {
+        reason: String,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -63,6 +114,12 @@ pub enum Event {
     Result {
         result: ExplorationResult,
     },
+    /// A test was invoked and checked, the tests of the run completed so far
+    ///  of the total tests planned
+    TestCompleted {
+        completed: u32,
+        total: u32,
+    },
 
     /// Performance and understanding
     TimeLineStart {
@@ -85,6 +142,11 @@ pub enum Event {
         message: LogMessage,
     },
 
+    /// The configuration of the run, sent before it starts
+    Manifest {
+        manifest: Box<RunManifest>,
+    },
+
     /// Process control related - Start/Stop etc
     Control {
         event: ControlEvent,
@@ -109,76 +171,46 @@ impl Event {
     }
 }
 
-pub type InvokeFn =
-    fn(&ExplorationContext, &[Operation], &[GeneratedOperation]) -> Option<Vec<InvokeResult>>;
+/// Invokes the generated operations of a sequence, none if the sequence could
+///  not be invoked. A closure may hold the state of its backend, e.g. the
+///  client of a message queue, or count what it invokes.
+pub type InvokeFn<'a> = &'a dyn Fn(
+    &ExplorationContext,
+    &[Operation],
+    &[GeneratedOperation],
+) -> Option<Vec<InvokeResult>>;
+
+/// An invoker the thread of an exploration invokes with, instead of `invoke`
+pub type SharedInvoker = Arc<
+    dyn Fn(&ExplorationContext, &[Operation], &[GeneratedOperation]) -> Option<Vec<InvokeResult>>
+        + Send
+        + Sync,
+>;
+
+pub type InvokeConcurrentlyFn =
+    fn(&ExplorationContext, &[Operation], &[Vec<GeneratedOperation>]) -> Option<Vec<HistoryEntry>>;
+
+/// The processes invoking their sequences at the same time, in the
+///  linearizability behaviour
+pub const CONCURRENT_PROCESSES: usize = 3;
+
+/// Publish that the exploration of the behaviour started
+fn start_exploring(context: &ExplorationContext, behaviour: &str) {
+    let message = format!("Start exploring '{}'", behaviour);
+    context.publish_event(Event::log(LogLevel::Info, &message));
 
-pub fn response_check(
-    context: &ExplorationContext,
-    operations: Vec<Operation>,
-    explore_ops: Vec<Operation>,
-    invoke: InvokeFn,
-) -> Vec<ExplorationResult> {
-    context.publish_event(Event::log(
-        LogLevel::Info,
-        "Start exploring 'Response Check'",
-    ));
-
-    // Start up events
     context.publish_event(Event::TimeLineStart {
         enter: std::time::Instant::now(),
-        message: "Start exploring 'Response Check'".to_string(),
+        message,
     });
 
     context.publish_event(Event::Control {
         event: ControlEvent::Started,
     });
+}
 
-    // for each operation included
-    let result = explore_ops
-        .iter()
-        .map(|op| {
-            context.publish_event(Event::TimeLineProgress {
-                time: std::time::Instant::now(),
-                message: format!("Starting Exploration with operation: {}", op.info.name),
-            });
-            context.publish_event(Event::log(
-                LogLevel::Info,
-                &format!("Starting Exploration with operation: {}", op.info.name),
-            ));
-
-            let result_seq = explore(
-                context,
-                operations.clone(),
-                invoke,
-                gen_static_operation_with_params(op.clone()),
-                |_ctx, res| res,
-                meta_properties::check_response,
-                synthesize_property_operations,
-            );
-
-            let result = if let Some(minimal_seq) = result_seq {
-                ExplorationResult::ResponseCheck {
-                    operation: op.info.name.clone(),
-                    minimal_sequence: Some(minimal_seq),
-                }
-            } else {
-                ExplorationResult::NoExampleFound {
-                    operation: op.info.name.clone(),
-                }
-            };
-
-            context.publish_event(Event::Result {
-                result: result.clone(),
-            });
-
-            result
-        })
-        .collect::<Vec<ExplorationResult>>();
-    // generate op with param
-    // invoke
-    // check response
-
-    // Finish up events
+/// Publish that the exploration of the behaviour completed
+fn finish_exploring(context: &ExplorationContext) {
     context.publish_event(Event::TimeLineEnd {
         time: std::time::Instant::now(),
         message: "Completed Exploration".to_string(),
@@ -187,87 +219,189 @@ pub fn response_check(
     context.publish_event(Event::Control {
         event: ControlEvent::Finished,
     });
+}
+
+/// Explore each of the operations until the exploration is stopped, the
+///  results of an operation are published as it completes
+fn explore_operations<C>(
+    context: &mut C,
+    ops: &[Operation],
+    mut explore_op: impl FnMut(&mut C, &Operation) -> Vec<ExplorationResult>,
+) -> Vec<ExplorationResult>
+where
+    C: Deref<Target = ExplorationContext>,
+{
+    let mut result = vec![];
+    let tests = context.number_of_tests;
+    context
+        .tests
+        .set(context.tests.get().plan(ops.len(), tests));
+
+    for op in ops {
+        if context.checkpoint() {
+            break;
+        }
+
+        let message = format!(
+            "Starting Exploration with operation: {}",
+            op.info.display_name()
+        );
+        context.publish_event(Event::progress(message.clone()));
+        context.publish_event(Event::log(LogLevel::Info, &message));
+
+        context
+            .tests
+            .set(context.tests.get().start_operation(tests));
+        let op_results = explore_op(context, op);
+        context.update_tests(RunTests::complete_operation);
+
+        for op_result in op_results {
+            context.publish_event(Event::Result {
+                result: op_result.clone(),
+            });
+            result.push(op_result);
+        }
+    }
 
     result
 }
 
-pub fn explore_response_inequality(
+/// Explore the behaviour with each of the operations, `explore_op` explores
+///  one operation to its result. The context is either shared or mutable,
+///  for the behaviours that keep the operation they explore in it.
+fn explore_each<C>(
+    mut context: C,
+    ops: &[Operation],
+    behaviour: &str,
+    mut explore_op: impl FnMut(&mut C, &Operation) -> ExplorationResult,
+) -> Vec<ExplorationResult>
+where
+    C: Deref<Target = ExplorationContext>,
+{
+    start_exploring(&context, behaviour);
+    let result = explore_operations(&mut context, ops, |context, op| {
+        vec![explore_op(context, op)]
+    });
+    finish_exploring(&context);
+
+    result
+}
+
+pub fn response_check(
     context: &ExplorationContext,
     operations: Vec<Operation>,
     explore_ops: Vec<Operation>,
     invoke: InvokeFn,
 ) -> Vec<ExplorationResult> {
-    context.publish_event(Event::log(
-        LogLevel::Info,
-        "Start exploring 'Response Equality'",
-    ));
+    start_exploring(context, "Response Check");
 
-    // Start up events
-    context.publish_event(Event::TimeLineStart {
-        enter: std::time::Instant::now(),
-        message: "Start exploring 'Response Equality'".to_string(),
-    });
+    let mut operations = operations;
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Started,
-    });
+    // for each operation included
+    let mut result = response_check_operations(context, &operations, &explore_ops, invoke);
 
-    // Exploration
+    // Follow the links of the responses, the linked operations may link further
+    while let Some(links) = &context.links {
+        let linked = links.lock().unwrap().take_unexplored();
+        if linked.is_empty() || context.checkpoint() {
+            break;
+        }
 
-    let result = explore_ops
-        .iter()
-        .map(|op| {
-            context.publish_event(Event::TimeLineProgress {
-                time: std::time::Instant::now(),
-                message: format!("Starting Exploration with operation: {}", op.info.name),
-            });
-            context.publish_event(Event::log(
-                LogLevel::Info,
-                &format!("Starting Exploration with operation: {}", op.info.name),
-            ));
+        context.publish_event(Event::log(
+            LogLevel::Info,
+            &format!("Following {} response links", linked.len()),
+        ));
 
-            let result_seq = explore(
-                context,
-                // TODO: is this right for the behaviour?
-                //  - Well, think this belongs better in the context
-                operations.clone(),
-                invoke,
-                gen_static_operation_with_params(op.clone()),
-                |_ctx, res| res,
-                check_response_inequality,
-                synthesize_operations_for_response_inequality,
-            );
+        operations.extend(linked.iter().cloned());
+        result.extend(response_check_operations(
+            context,
+            &operations,
+            &linked,
+            invoke,
+        ));
+    }
+    // generate op with param
+    // invoke
+    // check response
 
-            let result = if let Some(minimal_seq) = result_seq {
-                ExplorationResult::ResponseInEquality {
-                    operation: op.info.name.clone(),
-                    minimal_sequence: Some(minimal_seq),
-                }
-            } else {
-                ExplorationResult::NoExampleFound {
-                    operation: op.info.name.clone(),
-                }
-            };
+    finish_exploring(context);
 
-            context.publish_event(Event::Result {
-                result: result.clone(),
-            });
+    result
+}
 
-            result
-        })
-        .collect::<Vec<ExplorationResult>>();
+fn response_check_operations(
+    mut context: &ExplorationContext,
+    operations: &[Operation],
+    explore_ops: &[Operation],
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_operations(&mut context, explore_ops, |context, op| {
+        let result_seq = match explore(
+            context,
+            operations.to_vec(),
+            invoke,
+            gen_static_operation_with_params(op.clone()),
+            // Only the results of a failing status are kept, a sequence without any passes
+            failing_status_results,
+            |failing_results| failing_results.is_empty(),
+            synthesize_property_operations,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return vec![context.fail(&op.info.name, error)],
+        };
 
-    // Finish up events
-    context.publish_event(Event::TimeLineEnd {
-        time: std::time::Instant::now(),
-        message: "Completed Exploration".to_string(),
-    });
+        let result = if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::ResponseCheck {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        };
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Finished,
-    });
+        vec![result]
+    })
+}
 
-    result
+pub fn explore_response_inequality(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(context, &explore_ops, "Response Equality", |context, op| {
+        let result_seq = match explore(
+            context,
+            // TODO: is this right for the behaviour?
+            //  - Well, think this belongs better in the context
+            operations.clone(),
+            invoke,
+            gen_static_operation_with_params(op.clone()),
+            equality_results,
+            check_response_inequality,
+            synthesize_operations_for_response_inequality,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
+
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::ResponseInEquality {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
 }
 
 pub fn explore_response_equality(
@@ -276,182 +410,553 @@ pub fn explore_response_equality(
     explore_ops: Vec<Operation>,
     invoke: InvokeFn,
 ) -> Vec<ExplorationResult> {
-    context.publish_event(Event::log(
-        LogLevel::Info,
-        "Start exploring 'Response Equality'",
-    ));
+    explore_each(context, &explore_ops, "Response Equality", |context, op| {
+        let result_seq = match explore(
+            context,
+            // TODO: is this right for the behaviour?
+            //  - Well, think this belongs better in the context
+            operations.clone(),
+            invoke,
+            gen_static_operation_with_params(op.clone()),
+            equality_results,
+            check_response_equality,
+            synthesize_operations_for_response_equality,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
 
-    // Start up events
-    context.publish_event(Event::TimeLineStart {
-        enter: std::time::Instant::now(),
-        message: "Start exploring 'Response Equality'".to_string(),
-    });
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::ResponseEquality {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
+}
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Started,
-    });
+pub fn explore_response_time(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(context, &explore_ops, "Response Time", |context, op| {
+        // Only the slow results are kept, a sequence without any passes
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_static_operation_with_params(op.clone()),
+            response_time_results,
+            |slow_results| slow_results.is_empty(),
+            synthesize_property_operations,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
 
-    // Exploration
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::ResponseTime {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
+}
 
-    let result = explore_ops
-        .iter()
-        .map(|op| {
-            context.publish_event(Event::TimeLineProgress {
-                time: std::time::Instant::now(),
-                message: format!("Starting Exploration with operation: {}", op.info.name),
-            });
-            context.publish_event(Event::log(
-                LogLevel::Info,
-                &format!("Starting Exploration with operation: {}", op.info.name),
-            ));
+pub fn explore_payload(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(context, &explore_ops, "Response Payload", |context, op| {
+        // Only the results exceeding the limits are kept, a sequence without any passes
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_static_operation_with_params(op.clone()),
+            payload_results,
+            |exceeding| exceeding.is_empty(),
+            synthesize_property_operations,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
 
-            let result_seq = explore(
-                context,
-                // TODO: is this right for the behaviour?
-                //  - Well, think this belongs better in the context
-                operations.clone(),
-                invoke,
-                gen_static_operation_with_params(op.clone()),
-                |_ctx, res| res,
-                check_response_equality,
-                synthesize_operations_for_response_equality,
-            );
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::PayloadCheck {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
+}
 
-            let result = if let Some(minimal_seq) = result_seq {
-                ExplorationResult::ResponseEquality {
-                    operation: op.info.name.clone(),
-                    minimal_sequence: Some(minimal_seq),
-                }
-            } else {
-                ExplorationResult::NoExampleFound {
-                    operation: op.info.name.clone(),
-                }
-            };
+/// Inject attack payloads into the string parameters of the operations with
+///  injection enabled, the other operations are skipped
+pub fn explore_injection(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    let (enabled_ops, disabled_ops): (Vec<Operation>, Vec<Operation>) = explore_ops
+        .into_iter()
+        .partition(|op| context.injection.is_enabled(&op.info.name));
+
+    for op in disabled_ops {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            &format!(
+                "Injection is not enabled for operation: {}",
+                op.info.display_name()
+            ),
+        ));
+    }
 
-            context.publish_event(Event::Result {
-                result: result.clone(),
-            });
+    explore_each(context, &enabled_ops, "Injection", |context, op| {
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_static_injection_operation_with_params(op.clone()),
+            injection_results,
+            |failed_results| failed_results.is_empty(),
+            synthesize_property_operations,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
 
-            result
-        })
-        .collect::<Vec<ExplorationResult>>();
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::Injection {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
+}
 
-    // Finish up events
-    context.publish_event(Event::TimeLineEnd {
-        time: std::time::Instant::now(),
-        message: "Completed Exploration".to_string(),
-    });
+/// Replay the sequences that succeed with the configured credentials, without
+///  them and with the downgraded ones. An operation that still succeeds may
+///  lack authorization.
+pub fn explore_auth_bypass(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    // Without credentials every request is already stripped of them
+    let explore_ops = if context.auth.credentials.is_none() {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            "No credentials are configured, there is no auth to bypass",
+        ));
+        vec![]
+    } else {
+        explore_ops
+    };
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Finished,
-    });
+    let roles = context.auth.bypass_roles();
 
-    result
+    explore_each(context, &explore_ops, "Auth Bypass", |context, op| {
+        let mut bypass = None;
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
+
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
+
+            let gen_ops =
+                match generate_sequence(context, gen_pinned_sequence(context, op, &operations)) {
+                    Ok(gen_ops) => gen_ops,
+                    Err(error) => return context.fail(&op.info.name, error),
+                };
+
+            context.auth_role.set(AuthRole::Configured);
+            let authorized = invoke(context, &operations, &gen_ops)
+                .is_some_and(|results| results.iter().all(|res| res.success));
+
+            // Only a sequence that is authorized can be bypassed
+            let bypass_role = if authorized {
+                roles.iter().copied().find_map(|role| {
+                    context.auth_role.set(role);
+                    invoke(context, &operations, &gen_ops)
+                        .filter(|results| meta_properties::bypasses_auth(&op.info.name, results))
+                        .map(|results| (role, first_correlation_id(&results)))
+                })
+            } else {
+                None
+            };
+            context.auth_role.set(AuthRole::Configured);
+            context.complete_test();
+
+            if let Some((role, correlation_id)) = bypass_role {
+                context.publish_event(Event::log(
+                    LogLevel::Info,
+                    &format!("{} succeeded with {}", op.info.name, role),
+                ));
+                bypass = Some((role, gen_ops, correlation_id));
+                break;
+            }
+        }
+
+        if let Some((role, seq, correlation_id)) = bypass {
+            ExplorationResult::AuthBypass {
+                operation: op.info.name.clone(),
+                role,
+                sequence: Some(seq),
+                correlation_id,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
 }
 
-pub fn explore_state_mutation(
-    context: &mut ExplorationContext,
+/// Invoke the operation alone with the combinations of the boundary values of
+///  its parameters, instead of generated values. A deterministic pass to run
+///  before the properties are explored, the first combination of a failing
+///  status is the example.
+pub fn explore_boundary(
+    context: &ExplorationContext,
     operations: Vec<Operation>,
-    query_ops: &[Operation],
+    explore_ops: Vec<Operation>,
     invoke: InvokeFn,
 ) -> Vec<ExplorationResult> {
-    context.publish_event(Event::log(
-        LogLevel::Info,
-        "Start exploring 'State Mutation'",
-    ));
-
-    context.publish_event(Event::TimeLineStart {
-        enter: std::time::Instant::now(),
-        message: "Start exploring 'State Mutation'".to_string(),
-    });
+    let limits = context.behaviour_settings.boundary();
+
+    explore_each(context, &explore_ops, "Boundary Values", |context, op| {
+        let (combinations, total) = boundary::combinations(op, &limits);
+        context.publish_event(Event::log(
+            LogLevel::Info,
+            &format!(
+                "Invoking {} of {} boundary combinations of {}",
+                combinations.len(),
+                total,
+                op.info.display_name()
+            ),
+        ));
 
-    // For this behaviour only state accreating operations are relevant.
-    let valid_ops = operations
-        .clone()
-        .into_iter()
-        .filter(|op| {
-            if let Some(OperationMetaData::HTTP { method, .. }) = &op.meta_data {
-                matches!(method, HTTPMethod::POST | HTTPMethod::PUT)
-            } else {
-                // If the operation do not have HTTP MetaData, allow it as we
-                //  don't know if it can alter state or not
-                true
+        let mut failing = None;
+        for (n, gen_op) in combinations.into_iter().enumerate() {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
             }
-        })
-        .collect::<Vec<Operation>>();
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Started,
-    });
+            context.publish_event(Event::progress(format!("Start combination {}", n)));
 
-    let result = query_ops
-        .iter()
-        .map(|query_op| {
-            context.publish_event(Event::TimeLineProgress {
-                time: std::time::Instant::now(),
-                message: format!(
-                    "Starting Exploration with query operation: {}",
-                    query_op.info.name
-                ),
-            });
-            context.publish_event(Event::log(
-                LogLevel::Info,
-                &format!(
-                    "Starting Exploration with query operation: {}",
-                    query_op.info.name
-                ),
-            ));
-            context.query_operation = Some(query_op.clone());
-            let result_seq = explore(
-                context,
-                operations.clone(),
-                invoke,
-                gen_pinned_operation_sequence_with_params(
-                    query_op.clone(),
-                    valid_ops.clone(),
-                    context.min_length,
-                    context.max_length,
-                ),
-                |ctx, invoke_result| {
-                    invoke_result.map(|r| {
-                        r.into_iter()
-                            .filter(|res| {
-                                res.operation.name
-                                    == ctx.query_operation.as_ref().unwrap().info.name
-                            })
-                            .collect::<Vec<InvokeResult>>()
-                    })
-                },
-                check_state_mutation,
-                synthesize_operations_for_state_mutation,
-            );
+            let sequence = vec![gen_op];
+            let failing_results =
+                failing_status_results(context, invoke(context, &operations, &sequence));
+            context.complete_test();
+            if let Some(results) = failing_results.filter(|results| !results.is_empty()) {
+                failing = Some((sequence, first_correlation_id(&results)));
+                break;
+            }
+        }
 
-            let result = if let Some(minimal_seq) = result_seq {
-                ExplorationResult::StateMutation {
-                    query_operation: query_op.info.name.clone(),
-                    minimal_sequence: Some(minimal_seq),
-                }
-            } else {
-                ExplorationResult::NoExampleFound {
-                    operation: query_op.info.name.clone(),
+        if let Some((sequence, correlation_id)) = failing {
+            ExplorationResult::ResponseCheck {
+                operation: op.info.name.clone(),
+                minimal_sequence: Some(sequence),
+                correlation_id,
+                shrink_truncation: None,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
+}
+
+/// Invoke the same sequences as each of the roles, recording how often the
+///  operation succeeds as which role
+pub fn explore_role_matrix(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    let explore_ops = if context.auth.roles.is_empty() {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            "No roles are configured, there is no matrix to explore",
+        ));
+        vec![]
+    } else {
+        explore_ops
+    };
+
+    explore_each(context, &explore_ops, "Role Matrix", |context, op| {
+        let mut outcomes: Vec<RoleOutcome> = context
+            .auth
+            .roles
+            .iter()
+            .map(|role| RoleOutcome::new(&role.name))
+            .collect();
+        let mut sequence = None;
+
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
+
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
+
+            let gen_ops =
+                match generate_sequence(context, gen_pinned_sequence(context, op, &operations)) {
+                    Ok(gen_ops) => gen_ops,
+                    Err(error) => return context.fail(&op.info.name, error),
+                };
+
+            for (idx, outcome) in outcomes.iter_mut().enumerate() {
+                context.auth_role.set(AuthRole::Role(idx));
+                let op_result = invoke(context, &operations, &gen_ops).and_then(|results| {
+                    results
+                        .into_iter()
+                        .find(|res| res.operation.name == op.info.name)
+                });
+
+                // A broken sequence says nothing of the role
+                if let Some(op_result) = op_result.filter(|res| !res.is_transport_error()) {
+                    outcome.record(op_result.success);
                 }
+            }
+            context.auth_role.set(AuthRole::Configured);
+            context.complete_test();
+
+            if sequence.is_none() {
+                sequence = Some(gen_ops);
+            }
+        }
+
+        ExplorationResult::RoleMatrix {
+            operation: op.info.name.clone(),
+            outcomes,
+            sequence,
+        }
+    })
+}
+
+/// Invoke each operation, the number of tests times, and report what its
+///  responses reveal. The findings are of the operation, nothing is shrunk.
+pub fn explore_security_audit(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(context, &explore_ops, "Security Audit", |context, op| {
+        let generator = gen_static_operation_with_params(op.clone());
+        let mut findings = vec![];
+        let mut sequence = None;
+        let mut correlation_id = None;
+
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
+
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
+
+            let gen_ops = match generate_sequence(context, &generator) {
+                Ok(gen_ops) => gen_ops,
+                Err(error) => return context.fail(&op.info.name, error),
             };
 
-            context.publish_event(Event::Result {
-                result: result.clone(),
-            });
+            let invoke_result = invoke(context, &operations, &gen_ops);
+            context.complete_test();
+            let Some(invoke_result) = invoke_result else {
+                continue;
+            };
 
-            result
+            for res in invoke_result
+                .iter()
+                .filter(|res| res.operation.name == op.info.name)
+            {
+                let new_findings = security_audit::audit(res);
+                if sequence.is_none() && !new_findings.is_empty() {
+                    sequence = Some(gen_ops.clone());
+                    correlation_id = res.correlation_id.clone();
+                }
+                security_audit::merge_findings(&mut findings, new_findings);
+            }
+        }
+
+        if findings.is_empty() {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        } else {
+            ExplorationResult::SecurityAudit {
+                operation: op.info.name.clone(),
+                findings,
+                sequence,
+                correlation_id,
+            }
+        }
+    })
+}
+
+pub fn explore_state_mutation(
+    context: &mut ExplorationContext,
+    operations: Vec<Operation>,
+    query_ops: &[Operation],
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    // For this behaviour only state accreating operations are relevant.
+    let valid_ops = operations
+        .clone()
+        .into_iter()
+        .filter(|op| {
+            if let Some(OperationMetaData::HTTP { method, .. }) = &op.meta_data {
+                matches!(method, HTTPMethod::POST | HTTPMethod::PUT)
+            } else {
+                // If the operation do not have HTTP MetaData, allow it as we
+                //  don't know if it can alter state or not
+                true
+            }
         })
-        .collect::<Vec<ExplorationResult>>();
+        .collect::<Vec<Operation>>();
 
-    context.publish_event(Event::TimeLineEnd {
+    if context.behaviour_settings.observes_all_queries() && query_ops.len() > 1 {
+        start_exploring(context, "State Mutation");
+        let result =
+            explore_joint_state_mutation(context, operations, query_ops, &valid_ops, invoke);
+        finish_exploring(context);
+
+        return vec![result];
+    }
+
+    explore_each(context, query_ops, "State Mutation", |context, query_op| {
+        context.query_operation = Some(query_op.clone());
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_pinned_sequence(context, query_op, &valid_ops),
+            state_query_results,
+            check_state_mutation,
+            synthesize_operations_for_state_mutation,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&query_op.info.name, error),
+        };
+
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::StateMutation {
+                query_operation: query_op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: query_op.info.name.clone(),
+            }
+        }
+    })
+}
+
+/// State mutation where all the query operations observe the state before and
+///  after the same mutations. Any of them observing a change is an example,
+///  reported for all of them.
+fn explore_joint_state_mutation(
+    context: &mut ExplorationContext,
+    operations: Vec<Operation>,
+    query_ops: &[Operation],
+    valid_ops: &[Operation],
+    invoke: InvokeFn,
+) -> ExplorationResult {
+    let message = format!(
+        "Starting Exploration with query operations: {}",
+        query_ops
+            .iter()
+            .map(|query_op| query_op.info.display_name())
+            .collect::<Vec<&str>>()
+            .join(", ")
+    );
+    context.publish_event(Event::TimeLineProgress {
         time: std::time::Instant::now(),
-        message: "Completed Exploration".to_string(),
+        message: message.clone(),
     });
+    context.publish_event(Event::log(LogLevel::Info, &message));
+
+    let tests = context.number_of_tests;
+    context
+        .tests
+        .set(context.tests.get().plan(1, tests).start_operation(tests));
+
+    context.query_operation = Some(query_ops[0].clone());
+    context.joint_query_operations = query_ops[1..].to_vec();
+    let result_seq = explore(
+        context,
+        operations,
+        invoke,
+        gen_queries_pinned_sequence(context, query_ops, valid_ops),
+        state_query_results,
+        check_state_mutation,
+        synthesize_operations_for_state_mutation,
+    );
+    context.joint_query_operations.clear();
+    context.update_tests(RunTests::complete_operation);
+
+    let names = query_ops
+        .iter()
+        .map(|query_op| query_op.info.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let result = match result_seq {
+        Ok(Some((minimal_seq, correlation_id, shrink_truncation))) => {
+            ExplorationResult::StateMutation {
+                query_operation: names,
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        }
+        Ok(None) => ExplorationResult::NoExampleFound { operation: names },
+        Err(error) => context.fail(&names, error),
+    };
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Finished,
+    context.publish_event(Event::Result {
+        result: result.clone(),
     });
 
     result
@@ -463,546 +968,5458 @@ pub fn explore_state_identity(
     query_ops: &[Operation],
     invoke: InvokeFn,
 ) -> Vec<ExplorationResult> {
-    context.publish_event(Event::log(
-        LogLevel::Info,
-        "Start exploring 'State Identity'",
-    ));
-
-    context.publish_event(Event::TimeLineStart {
-        enter: std::time::Instant::now(),
-        message: "Start exploring 'State Identity'".to_string(),
-    });
-
-    context.publish_event(Event::Control {
-        event: ControlEvent::Started,
-    });
+    let buckets = context.buckets(&operations);
 
-    // TODO: put this in context an only do it once
-    let buckets = Buckets::new(&operations);
+    explore_each(context, query_ops, "State Identity", |context, query_op| {
+        context.query_operation = Some(query_op.clone());
 
-    let result = query_ops
-        .iter()
-        .map(|query_op| {
-            context.publish_event(Event::TimeLineProgress {
-                time: std::time::Instant::now(),
-                message: format!(
-                    "Starting Exploration with query operation: {}",
-                    query_op.info.name
-                ),
-            });
-            context.publish_event(Event::log(
-                LogLevel::Info,
-                &format!(
-                    "Starting Exploration with query operation: {}",
-                    query_op.info.name
-                ),
-            ));
+        // what precedence do the query op have?
+        // TODO: how certain are we that the op is in there?
+        let Some(query_bucket) = buckets.find(query_op) else {
+            let error = ExploreError::MissingBucket(query_op.info.name.clone());
+            return context.fail(&query_op.info.name, error);
+        };
 
-            context.query_operation = Some(query_op.clone());
+        ////
+        // Make buckets based on the suggested len
+        let suggested_seq_len = query_bucket.precedence + 2;
+        let query_precedence = query_bucket.precedence;
+        info!("Query Precedence: {query_precedence}, Suggested sequence len: {suggested_seq_len}");
+
+        let query_options = QueryOptions {
+            precedence: query_bucket.precedence,
+            slack_min: 0,
+            slack_max: 2,
+            min_length: context.min_length,
+            max_length: context.max_length,
+        };
 
-            // what precedence do the query op have?
-            // TODO: how certain are we that the op is in there?
-            let query_bucket = buckets.find(query_op).unwrap();
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_buckets(
+                // State Identity want the Query OP earlier
+                query_op.clone(),
+                query_options,
+                (*buckets).clone(),
+                amos_buckets::bucketize_for_state_identity_strategy,
+                operations.clone(),
+                &context.operation_weights,
+                &generation_guidance(context),
+            ),
+            state_query_results,
+            check_state_identity_with_observation,
+            synthesize_operations_for_state_identity,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&query_op.info.name, error),
+        };
 
-            ////
-            // Make buckets based on the suggested len
-            let suggested_seq_len = query_bucket.precedence + 2;
-            let query_precedence = query_bucket.precedence;
-            info!(
-                "Query Precedence: {query_precedence}, Suggested sequence len: {suggested_seq_len}"
-            );
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::StateIdentity {
+                query_operation: query_op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: query_op.info.name.clone(),
+            }
+        }
+    })
+}
 
-            let _query_options = QueryOptions {
-                precedence: query_bucket.precedence,
-                slack_min: 0,
-                slack_max: 2,
+/// Repeat a generated sequence of each operation, the number of tests times,
+///  and report the operations of the sequence whose latency degrades over the
+///  repetitions
+pub fn explore_soak(
+    mut context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    start_exploring(context, "Soak");
+
+    // The operations of the sequence degrade on their own, each is a result
+    let result = explore_operations(&mut context, &explore_ops, |context, op| {
+        // The same sequence, with the same values, is repeated the whole run
+        let gen_ops =
+            match generate_sequence(context, gen_pinned_sequence(context, op, &operations)) {
+                Ok(gen_ops) => gen_ops,
+                Err(error) => return vec![context.fail(&op.info.name, error)],
             };
 
-            // TODO: Consolidate with buckets
-            // For this behaviour only state accreating operations are relevant.
-            let valid_ops = operations
-                .clone()
-                .into_iter()
-                .filter(|op| {
-                    if let Some(OperationMetaData::HTTP { method, .. }) = &op.meta_data {
-                        matches!(method, HTTPMethod::POST | HTTPMethod::DELETE)
-                    } else {
-                        // If the operation do not have HTTP MetaData, allow it as we
-                        //  don't know if it can alter state or not
-                        true
-                    }
-                })
-                .collect::<Vec<Operation>>();
+        let mut log = SoakLog::new();
+        for repetition in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
 
-            let result_seq = explore(
-                context,
-                operations.clone(),
-                invoke,
-                gen_pinned_operation_sequence_with_params(
-                    query_op.clone(),
-                    valid_ops.clone(),
-                    context.min_length,
-                    context.max_length,
-                ),
-                // gen_buckets_5(
-                //     // State Identity want the Query OP earlier
-                //     query_op.clone(),
-                //     query_options,
-                //     buckets.clone(),
-                //     amos_buckets::bucketize_for_state_identity_strategy,
-                //     //amos_buckets::bucketize_for_state_identity_update_strategy,
-                //     operations.clone(),
-                // ),
-                |ctx, invoke_result| {
-                    invoke_result.map(|r| {
-                        r.into_iter()
-                            .filter(|res| {
-                                res.operation.name
-                                    == ctx.query_operation.as_ref().unwrap().info.name
-                            })
-                            .collect::<Vec<InvokeResult>>()
-                    })
-                },
-                check_state_identity_with_observation,
-                synthesize_operations_for_state_identity,
-            );
+            context.publish_event(Event::progress(format!("Start repetition {}", repetition)));
+            if let Some(invoke_result) = invoke(context, &operations, &gen_ops) {
+                log.record(&invoke_result);
+            }
+            context.complete_test();
+        }
 
-            let result = if let Some(minimal_seq) = result_seq {
-                ExplorationResult::StateIdentity {
-                    query_operation: query_op.info.name.clone(),
-                    minimal_sequence: Some(minimal_seq),
-                }
-            } else {
-                ExplorationResult::NoExampleFound {
-                    operation: query_op.info.name.clone(),
-                }
-            };
+        let degrading: Vec<ExplorationResult> = log
+            .trends(SOAK_WINDOWS)
+            .into_iter()
+            .filter(|trend| trend.is_degrading())
+            .map(|trend| ExplorationResult::LatencyDegradation {
+                operation: trend.operation.clone(),
+                sequence: Some(gen_ops.clone()),
+                trend,
+            })
+            .collect();
+
+        if degrading.is_empty() {
+            vec![ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }]
+        } else {
+            degrading
+        }
+    });
 
-            context.publish_event(Event::Result {
-                result: result.clone(),
-            });
+    finish_exploring(context);
 
-            result
-        })
-        .collect::<Vec<ExplorationResult>>();
+    result
+}
 
-    context.publish_event(Event::TimeLineEnd {
-        time: std::time::Instant::now(),
-        message: "Completed Exploration".to_string(),
-    });
+/// Sequences starting with each of the operations are checked against the model,
+///  the first divergent step of the minimal sequence is reported
+pub fn explore_model(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    let explore_ops = if context.model.is_none() {
+        context.publish_event(Event::log(
+            LogLevel::Error,
+            "No model to explore, set the model of the exploration",
+        ));
+        vec![]
+    } else {
+        explore_ops
+    };
 
-    context.publish_event(Event::Control {
-        event: ControlEvent::Finished,
-    });
+    explore_each(context, &explore_ops, "Model", |context, op| {
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_pinned_sequence(context, op, &operations),
+            model_results,
+            |divergent| divergent.is_empty(),
+            synthesize_property_operations,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&op.info.name, error),
+        };
 
-    result
+        // A stopped exploration leaves the divergence of its last sequence
+        let divergence = context
+            .model
+            .as_ref()
+            .and_then(|checker| checker.take_divergence());
+
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::ModelDivergence {
+                operation: op.info.name.clone(),
+                divergence,
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
 }
 
-#[derive(Debug, Clone)]
-pub enum Target {
-    HTTP { config: http::HTTPConfiguration },
+/// Each process reads the collection of the query operation, changes it, and
+///  reads it again, at the same time as the other processes. The first history
+///  of each operation that cannot be ordered as the collection is reported.
+pub fn explore_linearizability(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeConcurrentlyFn,
+) -> Vec<ExplorationResult> {
+    explore_each(context, &explore_ops, "Linearizability", |context, op| {
+        let collection: Vec<Operation> = operations
+            .iter()
+            .filter(|other| in_collection(op, other))
+            .cloned()
+            .collect();
+        let kind = |result: &InvokeResult| crud_kind(op, &collection, result);
+
+        for test in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
+
+            let processes: Result<Vec<Vec<GeneratedOperation>>, ExploreError> = (0
+                ..CONCURRENT_PROCESSES)
+                .map(|_| {
+                    let mut seq =
+                        generate_sequence(context, gen_pinned_sequence(context, op, &collection))?;
+                    // The pinned read is first, a read after the changes sees them
+                    seq.push(seq[0].clone());
+                    Ok(seq)
+                })
+                .collect();
+            let processes = match processes {
+                Ok(processes) => processes,
+                Err(error) => return context.fail(&op.info.name, error),
+            };
+
+            context.publish_event(Event::progress(format!("Start test {}", test)));
+            let history = invoke(context, &operations, &processes);
+            context.complete_test();
+            let Some(history) = history else {
+                continue;
+            };
+
+            let windows = linearizability::check(&history, kind);
+            if !windows.is_empty() {
+                return ExplorationResult::NonLinearizable {
+                    operation: op.info.name.clone(),
+                    windows,
+                    processes,
+                    correlation_id: history
+                        .first()
+                        .and_then(|entry| entry.result.correlation_id.clone()),
+                };
+            }
+        }
+
+        ExplorationResult::NoExampleFound {
+            operation: op.info.name.clone(),
+        }
+    })
 }
 
-pub struct ExplorationContext {
-    pub http_client: reqwest::blocking::Client,
-    pub http_send_fn: fn(&ExplorationContext, HTTPCall) -> Option<http::HTTPResult>,
+/// Invoke each sequence on the target and then on the reference target, and
+///  find the first sequence the two respond to differently
+pub fn explore_differential(
+    context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    let explore_ops = if context.reference.is_none() {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            "No reference target is configured, there is nothing to compare with",
+        ));
+        vec![]
+    } else {
+        explore_ops
+    };
 
-    pub target: Target,
+    let fields = context.behaviour_settings.ignore_fields();
 
-    // TODO: Index into the ops?
-    pub query_operation: Option<Operation>,
+    explore_each(context, &explore_ops, "Differential", |context, op| {
+        let mut found = None;
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
 
-    pub number_of_tests: u16,
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
 
-    pub tx: Option<std::sync::mpsc::Sender<Event>>,
+            let gen_ops =
+                match generate_sequence(context, gen_pinned_sequence(context, op, &operations)) {
+                    Ok(gen_ops) => gen_ops,
+                    Err(error) => return context.fail(&op.info.name, error),
+                };
 
-    pub min_length: u8,
-    pub max_length: u8,
-}
+            let Some(results) = invoke(context, &operations, &gen_ops) else {
+                context.complete_test();
+                continue;
+            };
+            context.at_reference.set(true);
+            let reference = invoke(context, &operations, &gen_ops);
+            context.at_reference.set(false);
+            context.complete_test();
+
+            let difference = reference
+                .and_then(|reference| differential::first_difference(&results, &reference, fields));
+            if let Some(difference) = difference {
+                context.publish_event(Event::log(
+                    LogLevel::Info,
+                    &format!("The reference responded differently: {}", difference),
+                ));
+                found = Some((difference, gen_ops, first_correlation_id(&results)));
+                break;
+            }
+        }
 
-impl ExplorationContext {
-    pub fn publish_event(&self, event: Event) {
-        if let Some(tx) = &self.tx {
-            tx.send(event).unwrap();
-        };
-    }
+        if let Some((difference, seq, correlation_id)) = found {
+            ExplorationResult::TargetDivergence {
+                operation: op.info.name.clone(),
+                difference,
+                sequence: Some(seq),
+                correlation_id,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        }
+    })
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub enum ExplorationResult {
-    NoExampleFound {
-        operation: String,
-    },
-    StateMutation {
-        query_operation: String,
-        minimal_sequence: Option<Vec<GeneratedOperation>>,
-    },
-    StateIdentity {
-        query_operation: String,
-        minimal_sequence: Option<Vec<GeneratedOperation>>,
-    },
-    ResponseEquality {
-        operation: String,
-        minimal_sequence: Option<Vec<GeneratedOperation>>,
-    },
-    ResponseInEquality {
-        operation: String,
-        minimal_sequence: Option<Vec<GeneratedOperation>>,
-    },
-    ResponseCheck {
-        operation: String,
-        minimal_sequence: Option<Vec<GeneratedOperation>>,
-    },
-}
+/// Invoke the sequences of each operation and capture their responses to the
+///  contract file, or find the first sequence whose responses do not conform
+///  to the contract
+pub fn explore_contract(
+    mut context: &ExplorationContext,
+    operations: Vec<Operation>,
+    explore_ops: Vec<Operation>,
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    start_exploring(context, "Contract");
 
-type ProcessResultFn =
-    fn(&ExplorationContext, Option<Vec<InvokeResult>>) -> Option<Vec<InvokeResult>>;
+    let (explore_ops, mut capture) = match context.behaviour_settings.contract() {
+        Some((file, true)) => (explore_ops, Some((file, Contract::new()))),
+        Some(_) if context.contract.is_some() => (explore_ops, None),
+        _ => {
+            context.publish_event(Event::log(
+                LogLevel::Warning,
+                "No contract file is configured, there is no contract to capture or check",
+            ));
+            (vec![], None)
+        }
+    };
 
-pub fn explore(
-    ctx: &ExplorationContext,
+    let result = explore_operations(&mut context, &explore_ops, |context, op| {
+        let mut found = None;
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
+
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
+
+            let gen_ops =
+                match generate_sequence(context, gen_pinned_sequence(context, op, &operations)) {
+                    Ok(gen_ops) => gen_ops,
+                    Err(error) => return vec![context.fail(&op.info.name, error)],
+                };
+
+            let results = invoke(context, &operations, &gen_ops);
+            context.complete_test();
+            let Some(results) = results else {
+                continue;
+            };
+
+            match (&mut capture, &context.contract) {
+                (Some((_, captured)), _) => captured.record(&results),
+                (None, Some(contract)) => {
+                    let violations = contract.check(&results);
+                    if !violations.is_empty() {
+                        found = Some((violations, gen_ops, first_correlation_id(&results)));
+                        break;
+                    }
+                }
+                (None, None) => (),
+            }
+        }
+
+        let result = if let Some((violations, seq, correlation_id)) = found {
+            ExplorationResult::ContractViolation {
+                operation: op.info.name.clone(),
+                violations,
+                sequence: Some(seq),
+                correlation_id,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: op.info.name.clone(),
+            }
+        };
+
+        vec![result]
+    });
+
+    if let Some((file, captured)) = capture {
+        let message = match captured.save(std::path::Path::new(file)) {
+            Ok(()) => Event::log(
+                LogLevel::Info,
+                &format!(
+                    "Captured the contract of {} operations to {}",
+                    captured.operations.len(),
+                    file
+                ),
+            ),
+            Err(e) => Event::log(
+                LogLevel::Error,
+                &format!("The contract could not be saved to {}: {}", file, e),
+            ),
+        };
+        context.publish_event(message);
+    }
+
+    finish_exploring(context);
+
+    result
+}
+
+/// Invoke the sequences of the state mutating operations between two
+///  invocations of each query operation, with the faults injected between
+///  them, and find the first sequence the SUT was not consistent after
+pub fn explore_chaos(
+    context: &mut ExplorationContext,
     operations: Vec<Operation>,
+    query_ops: &[Operation],
     invoke: InvokeFn,
-    generator: impl Strategy<Value = (u8, Vec<GenerationOperationWithParameters>)>,
-    process_result: ProcessResultFn,
-    check: fn(&[InvokeResult]) -> bool,
-    synthesize_operations: fn(u8, &[GenerationOperationWithParameters]) -> Vec<GeneratedOperation>,
-) -> Option<Vec<GeneratedOperation>> {
-    // TODO: put this in the context, no reason to re-creating it
-    let mut runner = TestRunner::new(Config {
-        failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
-        ..Config::default()
-    });
+) -> Vec<ExplorationResult> {
+    let query_ops = if context.chaos.is_none() {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            "No faults are configured, there is no chaos to inject",
+        ));
+        &[]
+    } else {
+        query_ops
+    };
 
-    for test_n in 0..ctx.number_of_tests {
-        ctx.publish_event(Event::progress(format!("Start test {}", test_n,)));
+    explore_each(context, query_ops, "Chaos", |context, query_op| {
+        context.query_operation = Some(query_op.clone());
 
-        // Invoke the generator to get a new generated sequence
-        let mut val = generator.new_tree(&mut runner).unwrap();
+        let mut found = None;
+        for test_n in 0..context.number_of_tests {
+            if context.checkpoint() {
+                context.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+                break;
+            }
 
-        // Synthesize to operations, Gen.ops -> Actual ops.
-        ctx.publish_event(Event::progress(format!(
-            "Generating operations for test {} starting..",
-            test_n,
-        )));
-        let (query_pos, op_seq) = &val.current();
-        let gen_ops = synthesize_operations(*query_pos, op_seq);
-        ctx.publish_event(Event::progress(format!(
-            "Generating operations for test {} done",
-            test_n,
-        )));
+            context.publish_event(Event::progress(format!("Start test {}", test_n)));
 
-        // Invoke the operations
-        ctx.publish_event(Event::progress(format!(
-            "Invoke of operations for test {} starting..",
-            test_n,
-        )));
-        let invoke_result = invoke(ctx, &operations, &gen_ops);
-        ctx.publish_event(Event::progress(format!(
-            "Invoke of operations for test {} done",
-            test_n,
-        )));
+            let gen_ops = match generate_synthesized_sequence(
+                context,
+                gen_pinned_sequence(context, query_op, &operations),
+                synthesize_operations_for_state_mutation,
+            ) {
+                Ok(gen_ops) => gen_ops,
+                Err(error) => return context.fail(&query_op.info.name, error),
+            };
 
-        // Let the behaviour process the result before the check
-        let potential_query_results = process_result(ctx, invoke_result);
+            let results = invoke(context, &operations, &gen_ops);
+            context.complete_test();
+            let Some(results) = results else {
+                continue;
+            };
+            // Only the dropped requests are expected to get no response
+            let transport_error = results.iter().any(|res| {
+                res.transport_error
+                    .is_some_and(|error| error != http::TransportError::Dropped)
+            });
+            if transport_error {
+                continue;
+            }
 
-        // Check if we could produce a result
-        let Some(query_results) = potential_query_results else {
-            continue;
-        };
+            if let Some(inconsistency) = chaos::inconsistency(&query_op.info.name, &results) {
+                let faults = context
+                    .chaos
+                    .as_ref()
+                    .map(|chaos| chaos.faults())
+                    .unwrap_or_default();
+                context.publish_event(Event::log(
+                    LogLevel::Info,
+                    &format!(
+                        "Not consistent after {} faults: {}",
+                        faults.len(),
+                        inconsistency
+                    ),
+                ));
+                found = Some((
+                    inconsistency,
+                    faults,
+                    gen_ops,
+                    first_correlation_id(&results),
+                ));
+                break;
+            }
+        }
 
-        // Check if the result fit the behaviour or not
-        if check(&query_results) {
-            // Test passed
-            continue;
+        if let Some((inconsistency, faults, seq, correlation_id)) = found {
+            ExplorationResult::ChaosInconsistency {
+                query_operation: query_op.info.name.clone(),
+                inconsistency,
+                faults,
+                sequence: Some(seq),
+                correlation_id,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: query_op.info.name.clone(),
+            }
         }
+    })
+}
 
-        // FAILED check, i.e, we match the behaviour
-        ctx.publish_event(Event::progress(
-            "Found failing sequence, start Shrinking".to_string(),
+/// Explore the query operations before and after the mutations, through the
+///  client cache. An example is a sequence where the query operation serves
+///  the state cached before a mutation of its resource, e.g. the target
+///  answers the revalidation with 304 though the resource was changed.
+pub fn explore_stale_cache(
+    context: &mut ExplorationContext,
+    operations: Vec<Operation>,
+    query_ops: &[Operation],
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    let query_ops = if context.cache.is_none() {
+        context.publish_event(Event::log(
+            LogLevel::Warning,
+            "The responses are not cached, there is no stale state to find",
         ));
+        &[]
+    } else {
+        query_ops
+    };
 
-        // NOTE: Dealing with state-ful systems we should not use the same
-        //  value twice, hence do NOT use val.current, simplify first
-        let mut shrink_count = 0;
-        val.simplify();
-        loop {
-            shrink_count += 1;
-            // Run the simplified sequence
-            let (query_pos, op_seq) = &val.current();
-            let gen_ops = synthesize_operations(*query_pos, op_seq);
-            let invoke_result = invoke(ctx, &operations, &gen_ops);
-            // Let the behaviour process the result before the check
-            let potential_query_results = process_result(ctx, invoke_result);
+    explore_each(context, query_ops, "Stale Cache", |context, query_op| {
+        context.query_operation = Some(query_op.clone());
+
+        // Only the stale results are kept, a sequence without any passes
+        let result_seq = match explore(
+            context,
+            operations.clone(),
+            invoke,
+            gen_pinned_sequence(context, query_op, &operations),
+            stale_cache_results,
+            |stale| stale.is_empty(),
+            synthesize_operations_for_state_mutation,
+        ) {
+            Ok(result_seq) => result_seq,
+            Err(error) => return context.fail(&query_op.info.name, error),
+        };
 
-            // Check if we could produce a result
-            let Some(query_results) = potential_query_results else {
-                val.complicate();
-                continue;
+        if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+            ExplorationResult::StaleCache {
+                operation: query_op.info.name.clone(),
+                minimal_sequence: Some(minimal_seq),
+                correlation_id,
+                shrink_truncation,
+            }
+        } else {
+            ExplorationResult::NoExampleFound {
+                operation: query_op.info.name.clone(),
+            }
+        }
+    })
+}
+
+/// Explore the POST operations, the last POST of each sequence is sent
+///  again with the same idempotency key. An example is a sequence where the
+///  SUT responded to the retry with another body than to the request, e.g.
+///  with the id of a second resource it created.
+pub fn explore_idempotent_retry(
+    context: &mut ExplorationContext,
+    operations: Vec<Operation>,
+    creation_ops: &[Operation],
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(
+        context,
+        creation_ops,
+        "Idempotent Retry",
+        |context, creation_op| {
+            context.query_operation = Some(creation_op.clone());
+
+            // Only the duplicating retry is kept, a sequence without one passes
+            let result_seq = match explore(
+                context,
+                operations.clone(),
+                invoke,
+                gen_pinned_sequence(context, creation_op, &operations),
+                idempotent_retry_results,
+                |duplicates| duplicates.is_empty(),
+                synthesize_operations_for_state_mutation,
+            ) {
+                Ok(result_seq) => result_seq,
+                Err(error) => return context.fail(&creation_op.info.name, error),
             };
 
-            // Check the simplified result
-            if !check(&query_results) {
-                // Still failing, find a simpler example if we can
-                ctx.publish_event(Event::progress(format!(
-                    "Simpler sequence failed, keep Shrinking - {shrink_count}",
-                )));
-                if !val.simplify() {
-                    break;
+            if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+                ExplorationResult::IdempotentRetry {
+                    operation: creation_op.info.name.clone(),
+                    minimal_sequence: Some(minimal_seq),
+                    correlation_id,
+                    shrink_truncation,
                 }
-            } else if !val.complicate() {
-                // Passed this input, back up
-                break;
+            } else {
+                ExplorationResult::NoExampleFound {
+                    operation: creation_op.info.name.clone(),
+                }
+            }
+        },
+    )
+}
+
+/// Explore the updates of the resources that are read with a GET. The
+///  sequence reads the ETag of the resource and updates it, and after the
+///  generated operations reads it again and replays the update with the
+///  ETag of the first read. An example is a sequence where the replayed
+///  update is not rejected with 412, the stale update overwrote the state.
+pub fn explore_conditional_request(
+    context: &mut ExplorationContext,
+    operations: Vec<Operation>,
+    update_ops: &[Operation],
+    invoke: InvokeFn,
+) -> Vec<ExplorationResult> {
+    explore_each(
+        context,
+        update_ops,
+        "Conditional Request",
+        |context, update_op| {
+            let Some(read_op) = conditional::read_of(update_op, &operations).cloned() else {
+                context.publish_event(Event::log(
+                    LogLevel::Warning,
+                    &format!(
+                        "There is no GET of the resource updated by {}",
+                        update_op.info.display_name()
+                    ),
+                ));
+                return ExplorationResult::NoExampleFound {
+                    operation: update_op.info.name.clone(),
+                };
+            };
+            context.query_operation = Some(read_op.clone());
+
+            // Only the replayed update is kept, a sequence where it was
+            //  rejected passes
+            let result_seq = match explore(
+                context,
+                operations.clone(),
+                invoke,
+                gen_queries_pinned_sequence(context, &[read_op, update_op.clone()], &operations),
+                conditional_request_results,
+                |stale| stale.is_empty(),
+                synthesize_operations_for_state_mutation,
+            ) {
+                Ok(result_seq) => result_seq,
+                Err(error) => return context.fail(&update_op.info.name, error),
             };
+
+            if let Some((minimal_seq, correlation_id, shrink_truncation)) = result_seq {
+                ExplorationResult::ConditionalRequest {
+                    operation: update_op.info.name.clone(),
+                    minimal_sequence: Some(minimal_seq),
+                    correlation_id,
+                    shrink_truncation,
+                }
+            } else {
+                ExplorationResult::NoExampleFound {
+                    operation: update_op.info.name.clone(),
+                }
+            }
+        },
+    )
+}
+
+fn http_url(op: &Operation) -> Option<(&str, &HTTPMethod)> {
+    match &op.meta_data {
+        Some(OperationMetaData::HTTP { url, method }) => Some((url, method)),
+        None => None,
+    }
+}
+
+/// If the operation is on the collection of the query operation, or on one of
+///  its resources
+fn in_collection(query_op: &Operation, op: &Operation) -> bool {
+    match (http_url(query_op), http_url(op)) {
+        (Some((collection, _)), Some((url, _))) => {
+            url == collection || url.starts_with(&format!("{}/", collection))
+        }
+        _ => false,
+    }
+}
+
+fn crud_kind(query_op: &Operation, collection: &[Operation], result: &InvokeResult) -> CrudKind {
+    if result.operation.name == query_op.info.name {
+        return CrudKind::Read;
+    }
+
+    let method = collection
+        .iter()
+        .find(|op| op.info.name == result.operation.name)
+        .and_then(http_url)
+        .map(|(_, method)| method);
+    match method {
+        Some(HTTPMethod::POST) => CrudKind::Create,
+        Some(HTTPMethod::DELETE) => CrudKind::Delete,
+        _ => CrudKind::Other,
+    }
+}
+
+/// The generator for sequences pinned to the query operation, weighted by the
+///  operation weights of the context. The generation is guided by the
+///  coverage, the corpus and the precedence learning the context has.
+fn gen_pinned_sequence(
+    context: &ExplorationContext,
+    query_op: &Operation,
+    ops: &[Operation],
+) -> BoxedStrategy<(u8, Vec<GenerationOperationWithParameters>)> {
+    gen_queries_pinned_sequence(context, std::slice::from_ref(query_op), ops)
+}
+
+/// Same as `gen_pinned_sequence`, but pinned to all the query operations, in
+///  order. The generated precedence is the position of the last of them.
+fn gen_queries_pinned_sequence(
+    context: &ExplorationContext,
+    query_ops: &[Operation],
+    ops: &[Operation],
+) -> BoxedStrategy<(u8, Vec<GenerationOperationWithParameters>)> {
+    let guidance = generation_guidance(context);
+
+    if guidance.coverage.is_none()
+        && guidance.corpus.is_none()
+        && guidance.precedence.is_none()
+        && guidance.mutation.is_none()
+        && guidance.campaign.is_none()
+    {
+        gen_pinned_weighted_operation_sequence_with_params(
+            query_ops.to_vec(),
+            ops.to_vec(),
+            &context.operation_weights,
+            context.min_length,
+            context.max_length,
+            context.shrinking.strategy,
+        )
+        .boxed()
+    } else {
+        gen_pinned_guided_operation_sequence_with_params(
+            query_ops.to_vec(),
+            ops.to_vec(),
+            &context.operation_weights,
+            context.min_length,
+            context.max_length,
+            &guidance,
+            context.shrinking.strategy,
+        )
+        .boxed()
+    }
+}
+
+/// The guidance of the generation by what the run has seen so far
+fn generation_guidance(context: &ExplorationContext) -> GenerationGuidance {
+    GenerationGuidance {
+        coverage: context.coverage.clone(),
+        corpus: context.corpus.clone(),
+        precedence: context.precedence.clone(),
+        mutation: context.mutation.clone(),
+        campaign: context.campaign.clone(),
+    }
+}
+
+/// A sequence from the generator, with the runner of the context
+fn generate_sequence(
+    context: &ExplorationContext,
+    generator: impl Strategy<Value = (u8, Vec<GenerationOperationWithParameters>)>,
+) -> Result<Vec<GeneratedOperation>, ExploreError> {
+    generate_synthesized_sequence(context, generator, synthesize_property_operations)
+}
+
+/// Same as `generate_sequence`, with the operations synthesized for the
+///  behaviour
+fn generate_synthesized_sequence(
+    context: &ExplorationContext,
+    generator: impl Strategy<Value = (u8, Vec<GenerationOperationWithParameters>)>,
+    synthesize_operations: fn(u8, &[GenerationOperationWithParameters]) -> Vec<GeneratedOperation>,
+) -> Result<Vec<GeneratedOperation>, ExploreError> {
+    let mut runner = context.runner.borrow_mut();
+    let val = generator
+        .new_tree(&mut runner)
+        .map_err(|reason| ExploreError::Generation(reason.to_string()))?;
+    let (query_pos, op_seq) = &val.current();
+    Ok(synthesize_operations(*query_pos, op_seq))
+}
+
+#[derive(Debug, Clone)]
+pub enum Target {
+    HTTP {
+        config: http::HTTPConfiguration,
+    },
+    /// The operations are published as messages, by the broker of the
+    ///  embedder
+    Queue {
+        config: queue::QueueConfiguration,
+    },
+    CoAP {
+        config: coap::CoapConfiguration,
+    },
+}
+
+impl Target {
+    /// The target with the base path of the specification, unless it has a
+    ///  base path of its own
+    pub fn resolve_base_path(mut self, amos: &AMOS) -> Self {
+        match &mut self {
+            Target::HTTP { config } => {
+                if config.base_path.is_none() {
+                    config.base_path.clone_from(&amos.base_path);
+                }
+            }
+            Target::CoAP { config } => {
+                if config.base_path.is_none() {
+                    config.base_path.clone_from(&amos.base_path);
+                }
+            }
+            Target::Queue { .. } => (),
+        }
+        self
+    }
+}
+
+pub struct ExplorationContext {
+    pub http_client: reqwest::blocking::Client,
+    /// Sends a request with the client, without the context the concurrent
+    ///  invocations send from their own threads
+    pub http_send_fn:
+        fn(&reqwest::blocking::Client, HTTPCall) -> Result<http::HTTPResult, http::TransportError>,
+
+    pub target: Target,
+
+    // TODO: Index into the ops?
+    pub query_operation: Option<Operation>,
+    /// The query operations observed together with the query operation, in
+    ///  the same sequence
+    pub joint_query_operations: Vec<Operation>,
+
+    pub number_of_tests: u16,
+
+    pub observer: Option<Box<dyn ExplorationObserver>>,
+
+    pub min_length: u8,
+    pub max_length: u8,
+
+    /// Coverage of the run, when set the generation is guided by it
+    pub coverage: Option<SharedCoverage>,
+
+    pub operation_weights: OperationWeights,
+
+    /// The runner driving generation, shared by all explorations of the context
+    pub runner: RefCell<TestRunner>,
+
+    /// The buckets of the explored operations, created on first use, by the
+    ///  key of the operations they were created of
+    pub buckets: Option<(u64, Arc<Buckets>)>,
+
+    /// Raised from outside the exploration to stop it as soon as possible
+    pub stop: Option<StopSignal>,
+    /// Raised from outside the exploration to hold it between test cases
+    pub pause: Option<PauseSignal>,
+
+    /// Observation of the state, made after each invocation of the query operation
+    pub observation: Option<StateObservation>,
+
+    /// The operations linked from the responses, when set the links are followed
+    pub links: Option<SharedLinks>,
+
+    /// Values harvested from the responses, when set the generation samples them
+    pub corpus: Option<SharedCorpus>,
+
+    /// Outcomes of the orders of the operations, when set the generation
+    ///  orders the operations after what was learned
+    pub precedence: Option<SharedPrecedence>,
+
+    /// The sequences generated earlier in the run, when set the generation
+    ///  mutates them into new sequences
+    pub mutation: Option<SharedMutation>,
+
+    /// The corpus of the campaign, when set the generation replays its
+    ///  sequences first and the sequences with new statuses are saved to it
+    pub campaign: Option<SharedCampaign>,
+
+    /// The order and the budget of shrinking the failing examples
+    pub shrinking: ShrinkSettings,
+
+    /// The settings of the explored behaviour
+    pub behaviour_settings: BehaviourSettings,
+
+    /// The response times the SLA behaviour checks the invocations against
+    pub response_time: ResponseTimeSla,
+
+    /// The limits the payload behaviour checks the responses against
+    pub payload: PayloadLimits,
+
+    /// The operations the injection behaviour injects attack payloads into
+    pub injection: InjectionSettings,
+
+    /// The credentials of the requests
+    pub auth: AuthSettings,
+
+    /// The role the requests are sent as, only changed while replaying with
+    ///  lesser credentials
+    pub auth_role: Cell<AuthRole>,
+
+    /// The target the differential behaviour compares the responses of the
+    ///  target with
+    pub reference: Option<Target>,
+    /// If the requests are sent to the reference, only while the differential
+    ///  behaviour invokes a sequence on it
+    pub at_reference: Cell<bool>,
+
+    /// The contract the contract behaviour checks the responses against
+    pub contract: Option<Contract>,
+
+    /// The faults the chaos behaviour injects between the operations
+    pub chaos: Option<ChaosHooks>,
+
+    /// The transport errors tolerated before the exploration is aborted
+    pub error_budget: ErrorBudget,
+
+    /// The health endpoint of the target, the invocations are paused while
+    ///  it is unhealthy
+    pub health: Option<HealthCheck>,
+
+    /// The ids of the sequences, sent to the SUT if a header is set
+    pub correlation: CorrelationIds,
+
+    /// The number of sequences invoked so far
+    pub test_cases: Cell<u64>,
+
+    /// The tests of the run, for the progress of the exploration
+    pub tests: Cell<RunTests>,
+
+    /// The model the model behaviour checks the sequences against
+    pub model: Option<ModelChecker>,
+
+    /// Answers the requests instead of the target when set, e.g. in dry runs
+    pub mock: Option<Arc<MockSut>>,
+
+    /// The values of the invoked sequences are recorded to it when set, to
+    ///  replay them with `replay`
+    pub script: Option<SharedScript>,
+
+    /// The client cache of the responses when set, the cache headers of the
+    ///  responses are honored within each sequence
+    pub cache: Option<RefCell<ClientCache>>,
+
+    /// How long the exploration was paused, by a pause signal or while the
+    ///  target was unhealthy. It does not count against the time budgets.
+    pub paused: Cell<std::time::Duration>,
+}
+
+pub type StopSignal = Arc<AtomicBool>;
+
+/// Set while the exploration is paused, the SUT is left alone until it is
+///  cleared and the exploration continues where it was
+pub type PauseSignal = Arc<AtomicBool>;
+
+/// How an exploration is controlled from outside its thread, none of it is
+///  needed to only run it
+#[derive(Default, Clone)]
+pub struct ExplorationControl {
+    pub stop: Option<StopSignal>,
+    pub pause: Option<PauseSignal>,
+    /// Invokes the sequences instead of the invoker of the target
+    pub invoker: Option<SharedInvoker>,
+}
+
+impl ExplorationControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_signals(mut self, stop: StopSignal, pause: PauseSignal) -> Self {
+        self.stop = Some(stop);
+        self.pause = Some(pause);
+        self
+    }
+
+    pub fn with_invoker(mut self, invoker: Option<SharedInvoker>) -> Self {
+        self.invoker = invoker;
+        self
+    }
+}
+
+/// How often a paused exploration checks if it is resumed
+pub const PAUSE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Consecutive requests without a response tolerated before an exploration is
+///  aborted, a target that is down fails every request
+pub const DEFAULT_ERROR_BUDGET: usize = 10;
+
+/// How long the preflight waits for a connection to the target
+pub const PREFLIGHT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Counts the consecutive transport errors, any response resets the count
+#[derive(Debug)]
+pub struct ErrorBudget {
+    limit: usize,
+    consecutive: Cell<usize>,
+}
+
+impl ErrorBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            consecutive: Cell::new(0),
+        }
+    }
+
+    /// Record the outcome of a request. Returns true for the error exhausting
+    ///  the budget, later errors do not exhaust it again.
+    pub fn record(&self, responded: bool) -> bool {
+        if responded {
+            self.consecutive.set(0);
+            return false;
+        }
+
+        let consecutive = self.consecutive.get() + 1;
+        self.consecutive.set(consecutive);
+        consecutive == self.limit
+    }
+
+    /// Exhaust the budget, as if every request failed
+    pub fn exhaust(&self) {
+        self.consecutive.set(self.limit.max(self.consecutive.get()));
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.consecutive.get() >= self.limit
+    }
+}
+
+impl Default for ErrorBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_ERROR_BUDGET)
+    }
+}
+
+/// Gives each sequence an id, to find its requests in the logs of the SUT.
+///  The ids start with the id of the run.
+#[derive(Debug, Default)]
+pub struct CorrelationIds {
+    header: Option<String>,
+    run: u32,
+    idempotency_header: Option<String>,
+}
+
+impl CorrelationIds {
+    pub fn new(header: Option<String>, run: u32) -> Self {
+        Self {
+            header,
+            run,
+            idempotency_header: None,
+        }
+    }
+
+    /// Send a key of each POST in the header, made of the id of its sequence
+    pub fn with_idempotency_header(mut self, header: Option<String>) -> Self {
+        self.idempotency_header = header;
+        self
+    }
+
+    pub fn header(&self) -> Option<&str> {
+        self.header.as_deref()
+    }
+
+    pub fn idempotency_header(&self) -> Option<&str> {
+        self.idempotency_header.as_deref()
+    }
+
+    /// The id of the sequence with the number in the run
+    pub fn id(&self, test_case: u64) -> String {
+        format!("qr-{:08x}-{}", self.run, test_case)
+    }
+}
+
+/// Check that a connection to the target can be opened, before any time is
+///  spent generating requests for it
+pub fn preflight(target: &Target) -> Result<(), String> {
+    let address = match target {
+        Target::HTTP { config } => format!("{}:{}", config.base_url, config.port),
+        Target::Queue { config } => format!("{}:{}", config.host, config.port),
+        // Datagrams need no connection, only the address is resolved
+        Target::CoAP { config } => {
+            return match (config.host.as_str(), config.port).to_socket_addrs() {
+                Ok(_) => Ok(()),
+                Err(e) => Err(format!("Could not resolve target {}: {}", config.host, e)),
+            }
+        }
+    };
+
+    let addresses = address
+        .to_socket_addrs()
+        .map_err(|e| format!("Could not resolve target {}: {}", address, e))?;
+
+    let mut error = format!("Could not resolve target {}", address);
+    for socket in addresses {
+        match TcpStream::connect_timeout(&socket, PREFLIGHT_TIMEOUT) {
+            Ok(_) => return Ok(()),
+            Err(e) => error = format!("Could not connect to target {}: {}", address, e),
+        }
+    }
+
+    Err(error)
+}
+
+/// The key of the operations the buckets are created of, their names and
+///  their URLs and methods
+fn buckets_key(operations: &[Operation]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for op in operations {
+        op.info.name.hash(&mut hasher);
+        if let Some(OperationMetaData::HTTP { url, method }) = &op.meta_data {
+            url.hash(&mut hasher);
+            method.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// A runner for an exploration context
+pub fn exploration_runner() -> RefCell<TestRunner> {
+    RefCell::new(TestRunner::new(Config {
+        failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
+        ..Config::default()
+    }))
+}
+
+/// A runner generating from the seed, runners with the same seed generate the
+///  same values
+pub fn seeded_runner(seed: u64) -> RefCell<TestRunner> {
+    // ChaCha takes a 32 byte seed
+    let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed.to_le_bytes().repeat(4));
+
+    RefCell::new(TestRunner::new_with_rng(
+        Config {
+            failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
+            rng_algorithm: RngAlgorithm::ChaCha,
+            ..Config::default()
+        },
+        rng,
+    ))
+}
+
+/// A seed for a run that was not given one
+pub fn random_seed() -> u64 {
+    exploration_runner().borrow_mut().rng().next_u64()
+}
+
+impl ExplorationContext {
+    /// A context sending the requests of a test case of one operation to the
+    ///  target, the rest of the settings are the defaults. A behaviour sets
+    ///  the fields it explores with.
+    pub fn new(target: Target) -> Self {
+        ExplorationContext {
+            http_client: reqwest::blocking::Client::new(),
+            http_send_fn: invoke_with_reqwest,
+            target,
+            query_operation: None,
+            joint_query_operations: vec![],
+            number_of_tests: 1,
+            observer: None,
+            min_length: 1,
+            max_length: 1,
+            coverage: None,
+            operation_weights: Default::default(),
+            runner: exploration_runner(),
+            buckets: None,
+            stop: None,
+            pause: None,
+            observation: None,
+            links: None,
+            corpus: None,
+            precedence: None,
+            mutation: None,
+            campaign: None,
+            shrinking: Default::default(),
+            behaviour_settings: Default::default(),
+            response_time: Default::default(),
+            payload: Default::default(),
+            injection: Default::default(),
+            auth: Default::default(),
+            auth_role: Default::default(),
+            reference: None,
+            at_reference: Default::default(),
+            contract: None,
+            chaos: None,
+            error_budget: Default::default(),
+            health: None,
+            correlation: Default::default(),
+            test_cases: Default::default(),
+            tests: Default::default(),
+            model: None,
+            mock: None,
+            script: None,
+            cache: None,
+            paused: Default::default(),
+        }
+    }
+
+    pub fn with_send_fn(
+        mut self,
+        http_send_fn: fn(
+            &reqwest::blocking::Client,
+            HTTPCall,
+        ) -> Result<http::HTTPResult, http::TransportError>,
+    ) -> Self {
+        self.http_send_fn = http_send_fn;
+        self
+    }
+
+    pub fn with_observer(mut self, observer: Box<dyn ExplorationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// The number of test cases and the lengths of their sequences
+    pub fn with_tests(mut self, number_of_tests: u16, min_length: u8, max_length: u8) -> Self {
+        self.number_of_tests = number_of_tests;
+        self.min_length = min_length;
+        self.max_length = max_length;
+        self
+    }
+
+    /// Generate the test cases from the seed, the same seed generates the same
+    ///  test cases
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.runner = seeded_runner(seed);
+        self
+    }
+
+    pub fn with_signals(mut self, stop: Option<StopSignal>, pause: Option<PauseSignal>) -> Self {
+        self.stop = stop;
+        self.pause = pause;
+        self
+    }
+
+    pub fn with_observation(mut self, observation: Option<StateObservation>) -> Self {
+        self.observation = observation;
+        self
+    }
+
+    pub fn with_coverage(mut self, coverage: Option<SharedCoverage>) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    pub fn with_operation_weights(mut self, operation_weights: OperationWeights) -> Self {
+        self.operation_weights = operation_weights;
+        self
+    }
+
+    pub fn with_links(mut self, links: Option<SharedLinks>) -> Self {
+        self.links = links;
+        self
+    }
+
+    pub fn with_corpus(mut self, corpus: Option<SharedCorpus>) -> Self {
+        self.corpus = corpus;
+        self
+    }
+
+    pub fn with_precedence(mut self, precedence: Option<SharedPrecedence>) -> Self {
+        self.precedence = precedence;
+        self
+    }
+
+    pub fn with_mutation(mut self, mutation: Option<SharedMutation>) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    pub fn with_shrinking(mut self, shrinking: ShrinkSettings) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+
+    pub fn with_behaviour_settings(mut self, behaviour_settings: BehaviourSettings) -> Self {
+        self.behaviour_settings = behaviour_settings;
+        self
+    }
+
+    pub fn with_response_time(mut self, response_time: ResponseTimeSla) -> Self {
+        self.response_time = response_time;
+        self
+    }
+
+    pub fn with_payload(mut self, payload: PayloadLimits) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    pub fn with_injection(mut self, injection: InjectionSettings) -> Self {
+        self.injection = injection;
+        self
+    }
+
+    pub fn with_auth(mut self, auth: AuthSettings) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub fn with_health(mut self, health: Option<HealthCheck>) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_correlation(mut self, correlation: CorrelationIds) -> Self {
+        self.correlation = correlation;
+        self
+    }
+
+    pub fn with_mock(mut self, mock: Option<Arc<MockSut>>) -> Self {
+        self.mock = mock;
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Option<RefCell<ClientCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// The buckets of the operations, only created again if the operations
+    ///  are not the ones of the last buckets
+    pub fn buckets(&mut self, operations: &[Operation]) -> Arc<Buckets> {
+        let key = buckets_key(operations);
+        match &self.buckets {
+            Some((cached, buckets)) if *cached == key => buckets.clone(),
+            _ => {
+                let buckets = Arc::new(Buckets::new(operations));
+                self.buckets = Some((key, buckets.clone()));
+                buckets
+            }
+        }
+    }
+
+    /// The target the requests are sent to, the reference while the
+    ///  differential behaviour invokes a sequence on it
+    pub fn current_target(&self) -> &Target {
+        match &self.reference {
+            Some(reference) if self.at_reference.get() => reference,
+            _ => &self.target,
+        }
+    }
+
+    /// Save the sequence to the corpus of the campaign, if it has one. The
+    ///  operations up until the query precedence are pinned by the
+    ///  exploration, only the generated ones after them are saved.
+    fn record_in_campaign(
+        &self,
+        query_pos: u8,
+        op_seq: &[GenerationOperationWithParameters],
+        results: &[InvokeResult],
+    ) {
+        let Some(campaign) = &self.campaign else {
+            return;
+        };
+
+        let generated = &op_seq[(query_pos as usize + 1).min(op_seq.len())..];
+        if let Err(e) = campaign.lock().unwrap().record(generated, results) {
+            self.publish_event(Event::log(
+                LogLevel::Warning,
+                &format!("The sequence could not be saved to the corpus: {}", e),
+            ));
+        }
+    }
+
+    /// Append the sequence to the script of the run, if it has one
+    fn record_script(&self, sequence: &[GeneratedOperation]) {
+        let Some(script) = &self.script else {
+            return;
+        };
+
+        if let Err(e) = script.lock().unwrap().record(sequence) {
+            self.publish_event(Event::log(
+                LogLevel::Warning,
+                &format!("The sequence could not be recorded to the script: {}", e),
+            ));
+        }
+    }
+
+    /// If the operation is the query operation, or one observed together
+    ///  with it
+    pub fn is_query(&self, name: &str) -> bool {
+        self.query_operation
+            .iter()
+            .chain(&self.joint_query_operations)
+            .any(|query_op| query_op.info.name == name)
+    }
+
+    /// Probe the health of the target when the check is due, or at once
+    ///  after a request without a response. An unhealthy target pauses the
+    ///  invocations until it is healthy again, an exploration paused for
+    ///  longer than the longest pause is aborted. Returns if it was paused.
+    pub fn pause_while_unhealthy(&self, at_once: bool) -> bool {
+        let Some(health) = &self.health else {
+            return false;
+        };
+        // The reference of the differential behaviour has no health endpoint
+        if self.at_reference.get() || !(at_once || health.is_due()) {
+            return false;
+        }
+        let Err(mut reason) = health.probe(&self.target) else {
+            return false;
+        };
+
+        self.publish_event(Event::log(
+            LogLevel::Warning,
+            &format!(
+                "The target is unhealthy, invocations are paused: {}",
+                reason
+            ),
+        ));
+        let paused = std::time::Instant::now();
+        while !self.is_stopped() {
+            if paused.elapsed() >= health.max_pause() {
+                self.abort(&format!(
+                    "Aborted after the target was unhealthy for {} s: {}",
+                    health.max_pause().as_secs(),
+                    reason
+                ));
+                break;
+            }
+
+            std::thread::sleep(HealthCheck::RETRY_INTERVAL);
+            match health.probe(&self.target) {
+                Ok(()) => {
+                    self.publish_event(Event::log(
+                        LogLevel::Info,
+                        "The target is healthy again, invocations resume",
+                    ));
+                    break;
+                }
+                Err(e) => reason = e,
+            }
+        }
+        self.paused.set(self.paused.get() + paused.elapsed());
+        true
+    }
+
+    /// If the exploration is requested to stop, or is aborted. An ongoing
+    ///  invocation is not interrupted.
+    pub fn is_stopped(&self) -> bool {
+        self.error_budget.is_exhausted() || self.is_stop_requested()
+    }
+
+    fn is_stop_requested(&self) -> bool {
+        self.stop
+            .as_ref()
+            .is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
+
+    /// Count a test of the explored operation as completed, and publish the
+    ///  progress of the run
+    pub fn complete_test(&self) {
+        self.update_tests(RunTests::complete_test);
+    }
+
+    fn update_tests(&self, update: impl FnOnce(RunTests) -> RunTests) {
+        let tests = update(self.tests.get());
+        self.tests.set(tests);
+        self.publish_event(Event::TestCompleted {
+            completed: tests.completed,
+            total: tests.total,
+        });
+    }
+
+    /// If the exploration is to stop, after waiting while it is paused. It is
+    ///  the check between test cases and shrink iterations.
+    pub fn checkpoint(&self) -> bool {
+        self.wait_while_paused();
+        self.is_stopped()
+    }
+
+    /// Wait here until the paused exploration is resumed or stopped
+    fn wait_while_paused(&self) {
+        let is_paused = || {
+            self.pause
+                .as_ref()
+                .is_some_and(|pause| pause.load(Ordering::Relaxed))
+        };
+        if !is_paused() {
+            return;
+        }
+
+        self.publish_event(Event::log(LogLevel::Info, "Exploration paused"));
+        let paused = std::time::Instant::now();
+        while is_paused() && !self.is_stop_requested() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+        self.paused.set(self.paused.get() + paused.elapsed());
+        self.publish_event(Event::log(LogLevel::Info, "Exploration resumed"));
+    }
+
+    /// Stop the exploration and tell why, the behaviour still sends its
+    ///  `Finished` event
+    pub fn abort(&self, reason: &str) {
+        self.error_budget.exhaust();
+        self.publish_event(Event::log(LogLevel::Error, reason));
+        self.publish_event(Event::Control {
+            event: ControlEvent::Aborted {
+                reason: reason.to_string(),
+            },
+        });
+    }
+
+    /// Tell why the exploration of the operation failed, the failed result is
+    ///  returned for the behaviour to publish with its results
+    pub fn fail(&self, operation: &str, error: ExploreError) -> ExplorationResult {
+        self.publish_event(Event::log(
+            LogLevel::Error,
+            &format!("Exploration of {} failed: {}", operation, error),
+        ));
+        ExplorationResult::Failed {
+            operation: operation.to_string(),
+            reason: error.to_string(),
+        }
+    }
+
+    pub fn publish_event(&self, event: Event) {
+        if let Some(observer) = &self.observer {
+            observer::notify(observer.as_ref(), event);
+        };
+    }
+
+    /// The credentials of the role the requests are currently sent as
+    pub fn request_auth(&self) -> Option<&HTTPAuth> {
+        self.auth.credentials(self.auth_role.get())
+    }
+
+    /// The headers of the run, added to every request of the sequence
+    pub(crate) fn add_request_headers(&self, request: &mut HTTPCall, correlation_id: &str) {
+        if let Some(auth) = self.request_auth() {
+            request
+                .headers
+                .push((auth.header.clone(), auth.value.clone()));
+        }
+        if let Some(header) = self.correlation.header() {
+            request
+                .headers
+                .push((header.to_string(), correlation_id.to_string()));
+        }
+    }
+
+    /// The idempotency key of a POST of the step, in the header of the
+    ///  idempotent retry behaviour or else of the run
+    pub(crate) fn add_idempotency_key(
+        &self,
+        request: &mut HTTPCall,
+        correlation_id: &str,
+        step: usize,
+    ) {
+        let header = self
+            .behaviour_settings
+            .idempotent_retry()
+            .or(self.correlation.idempotency_header());
+        if let (Some(header), HTTPMethod::POST) = (header, &request.method) {
+            request
+                .headers
+                .push((header.to_string(), idempotency::key(correlation_id, step)));
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum ExplorationResult {
+    NoExampleFound {
+        operation: String,
+    },
+    /// The exploration of the operation failed, instead of finding an example
+    Failed {
+        operation: String,
+        reason: String,
+    },
+    StateMutation {
+        query_operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    StateIdentity {
+        query_operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    ResponseEquality {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    ResponseInEquality {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    ResponseCheck {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    ResponseTime {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    PayloadCheck {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    Injection {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    /// The query operation served the state from before a mutation, after it
+    StaleCache {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    /// The update replayed with the ETag from before a conflicting update
+    ///  was not rejected with 412 Precondition Failed
+    ConditionalRequest {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    /// The POST retried with the same idempotency key created a duplicate,
+    ///  the SUT responded with another body
+    IdempotentRetry {
+        operation: String,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    /// A row of the role matrix
+    RoleMatrix {
+        operation: String,
+        outcomes: Vec<RoleOutcome>,
+        /// The first of the sequences invoked as every role
+        sequence: Option<Vec<GeneratedOperation>>,
+    },
+    AuthBypass {
+        operation: String,
+        /// The role the operation succeeded as
+        role: AuthRole,
+        sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    SecurityAudit {
+        operation: String,
+        findings: Vec<SecurityFinding>,
+        /// The first sequence with any of the findings
+        sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    LatencyDegradation {
+        operation: String,
+        sequence: Option<Vec<GeneratedOperation>>,
+        trend: OperationTrend,
+    },
+    ModelDivergence {
+        operation: String,
+        /// The step of the minimal sequence the SUT diverged from the model at
+        divergence: Option<Divergence>,
+        minimal_sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shrink_truncation: Option<ShrinkTruncation>,
+    },
+    NonLinearizable {
+        operation: String,
+        /// The windows of the first history that could not be ordered
+        windows: Vec<NonLinearizableWindow>,
+        /// The sequence of each process of the history
+        processes: Vec<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    /// The target and the reference responded differently to the sequence
+    TargetDivergence {
+        operation: String,
+        difference: TargetDifference,
+        sequence: Option<Vec<GeneratedOperation>>,
+        /// The correlation id of the requests to the target
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    ContractViolation {
+        operation: String,
+        /// The violations of the responses of the first sequence with any
+        violations: Vec<ContractViolation>,
+        sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+    ChaosInconsistency {
+        query_operation: String,
+        inconsistency: Inconsistency,
+        /// The faults injected into the sequence
+        faults: Vec<InjectedFault>,
+        sequence: Option<Vec<GeneratedOperation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<String>,
+    },
+}
+
+impl ExplorationResult {
+    /// The correlation id the SUT got the requests of the example with
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            ExplorationResult::StateMutation { correlation_id, .. }
+            | ExplorationResult::StateIdentity { correlation_id, .. }
+            | ExplorationResult::ResponseEquality { correlation_id, .. }
+            | ExplorationResult::ResponseInEquality { correlation_id, .. }
+            | ExplorationResult::ResponseCheck { correlation_id, .. }
+            | ExplorationResult::ResponseTime { correlation_id, .. }
+            | ExplorationResult::PayloadCheck { correlation_id, .. }
+            | ExplorationResult::Injection { correlation_id, .. }
+            | ExplorationResult::StaleCache { correlation_id, .. }
+            | ExplorationResult::ConditionalRequest { correlation_id, .. }
+            | ExplorationResult::IdempotentRetry { correlation_id, .. }
+            | ExplorationResult::AuthBypass { correlation_id, .. }
+            | ExplorationResult::SecurityAudit { correlation_id, .. }
+            | ExplorationResult::ModelDivergence { correlation_id, .. }
+            | ExplorationResult::NonLinearizable { correlation_id, .. }
+            | ExplorationResult::TargetDivergence { correlation_id, .. }
+            | ExplorationResult::ContractViolation { correlation_id, .. }
+            | ExplorationResult::ChaosInconsistency { correlation_id, .. } => {
+                correlation_id.as_deref()
+            }
+            ExplorationResult::NoExampleFound { .. }
+            | ExplorationResult::Failed { .. }
+            | ExplorationResult::RoleMatrix { .. }
+            | ExplorationResult::LatencyDegradation { .. } => None,
+        }
+    }
+
+    /// The sequence of the example, shrunk to a minimal one if the property
+    ///  shrinks its examples. A history of concurrent processes is not one.
+    pub fn sequence(&self) -> Option<&[GeneratedOperation]> {
+        let sequence = match self {
+            ExplorationResult::StateMutation {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::StateIdentity {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ResponseEquality {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ResponseInEquality {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ResponseCheck {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ResponseTime {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::PayloadCheck {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::Injection {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::StaleCache {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ConditionalRequest {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::IdempotentRetry {
+                minimal_sequence, ..
+            }
+            | ExplorationResult::ModelDivergence {
+                minimal_sequence, ..
+            } => minimal_sequence,
+            ExplorationResult::RoleMatrix { sequence, .. }
+            | ExplorationResult::AuthBypass { sequence, .. }
+            | ExplorationResult::SecurityAudit { sequence, .. }
+            | ExplorationResult::LatencyDegradation { sequence, .. }
+            | ExplorationResult::TargetDivergence { sequence, .. }
+            | ExplorationResult::ContractViolation { sequence, .. }
+            | ExplorationResult::ChaosInconsistency { sequence, .. } => sequence,
+            ExplorationResult::NoExampleFound { .. }
+            | ExplorationResult::Failed { .. }
+            | ExplorationResult::NonLinearizable { .. } => return None,
+        };
+        sequence.as_deref()
+    }
+
+    /// The budget shrinking the example ran out of, its sequence may not be
+    ///  minimal
+    pub fn shrink_truncation(&self) -> Option<&ShrinkTruncation> {
+        match self {
+            ExplorationResult::StateMutation {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::StateIdentity {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ResponseEquality {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ResponseInEquality {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ResponseCheck {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ResponseTime {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::PayloadCheck {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::Injection {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::StaleCache {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ConditionalRequest {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::IdempotentRetry {
+                shrink_truncation, ..
+            }
+            | ExplorationResult::ModelDivergence {
+                shrink_truncation, ..
+            } => shrink_truncation.as_ref(),
+            ExplorationResult::NoExampleFound { .. }
+            | ExplorationResult::Failed { .. }
+            | ExplorationResult::RoleMatrix { .. }
+            | ExplorationResult::AuthBypass { .. }
+            | ExplorationResult::SecurityAudit { .. }
+            | ExplorationResult::LatencyDegradation { .. }
+            | ExplorationResult::NonLinearizable { .. }
+            | ExplorationResult::TargetDivergence { .. }
+            | ExplorationResult::ContractViolation { .. }
+            | ExplorationResult::ChaosInconsistency { .. } => None,
+        }
+    }
+}
+
+/// Shrinking ran out of budget, the example is the simplest found so far
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShrinkTruncation {
+    pub limit: ShrinkLimit,
+    pub iterations: u32,
+}
+
+/// The minimal sequence of an example, the correlation id of its requests and
+///  the budget shrinking ran out of, if it did
+pub type MinimalExample = (
+    Vec<GeneratedOperation>,
+    Option<String>,
+    Option<ShrinkTruncation>,
+);
+
+type ProcessResultFn =
+    fn(&ExplorationContext, Option<Vec<InvokeResult>>) -> Option<Vec<InvokeResult>>;
+
+/// The results up to the first step diverging from the model
+fn model_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    let checker = ctx.model.as_ref()?;
+    invoke_result.map(|r| checker.divergent_results(r))
+}
+
+/// The results exceeding the response time SLA of their operation
+fn response_time_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(|res| meta_properties::exceeds_response_time(&ctx.response_time, res))
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results with a status the response check fails on
+fn failing_status_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    let classes = ctx.behaviour_settings.failing_statuses();
+    let stressed = ctx.payload.stress.is_some();
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(|res| {
+                if stressed {
+                    meta_properties::fails_under_stress(classes, res)
+                } else {
+                    meta_properties::has_failing_status(classes, res)
+                }
+            })
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results without the fields the response equalities ignore
+fn equality_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    let fields = ctx.behaviour_settings.ignore_fields();
+    if fields.is_empty() {
+        return invoke_result;
+    }
+    invoke_result.map(|r| {
+        r.iter()
+            .map(|res| meta_properties::without_fields(res, fields))
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results with a payload exceeding the limits
+fn payload_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(|res| meta_properties::exceeds_payload(&ctx.payload, res))
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results of the query operations serving the state from before a
+///  mutation
+fn stale_cache_results(
+    _ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(meta_properties::serves_stale_cache)
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The update replayed with a stale ETag, if the SUT did not reject it
+fn conditional_request_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    let accept_conflict = ctx
+        .behaviour_settings
+        .conditional_request()
+        .unwrap_or(false);
+
+    invoke_result.map(|r| {
+        conditional::stale_update(&r, accept_conflict)
+            .cloned()
+            .into_iter()
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The retry of the last POST, if the SUT created a duplicate
+fn idempotent_retry_results(
+    _ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    invoke_result.map(|r| {
+        idempotency::duplicate(&r)
+            .cloned()
+            .into_iter()
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results where an injected payload crashed the SUT or was reflected
+fn injection_results(
+    _ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(injection::is_injection_failure)
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+/// The results the state behaviours check, the query operation results and/or
+///  the state observations
+fn state_query_results(
+    ctx: &ExplorationContext,
+    invoke_result: Option<Vec<InvokeResult>>,
+) -> Option<Vec<InvokeResult>> {
+    let mode = ctx.observation.as_ref().map(|observation| observation.mode);
+
+    invoke_result.map(|r| {
+        r.into_iter()
+            .filter(|res| {
+                let is_query = ctx.is_query(&res.operation.name);
+                let is_observation = res.operation.name == OBSERVATION_OPERATION;
+
+                match mode {
+                    None => is_query,
+                    Some(ObservationMode::InsteadOfQuery) => is_observation,
+                    Some(ObservationMode::AlongsideQuery) => is_query || is_observation,
+                }
+            })
+            .collect::<Vec<InvokeResult>>()
+    })
+}
+
+fn first_correlation_id(results: &[InvokeResult]) -> Option<String> {
+    results.iter().find_map(|res| res.correlation_id.clone())
+}
+
+/// Reset the SUT before a sequence shrinking already invoked is invoked
+///  again, if the reset failed the earlier check is reused
+fn reset_before_repeat(ctx: &ExplorationContext, command: &str) -> bool {
+    match state_observer::run_command(command) {
+        Ok(()) => true,
+        Err(e) => {
+            ctx.publish_event(Event::log(
+                LogLevel::Error,
+                &format!("Reset failed, reusing the earlier check: {}", e),
+            ));
+            false
+        }
+    }
+}
+
+/// The key of the sequence in the checks of a shrink, none if the values of
+///  the sequence have no JSON form
+fn sequence_key(ops: &[GeneratedOperation]) -> Option<String> {
+    serde_json::to_string(ops).ok()
+}
+
+/// The results of a sequence, if every request of it got a response. A
+///  sequence with a transport error neither passes nor fails a check.
+fn conclusive(results: Option<Vec<InvokeResult>>) -> Option<Vec<InvokeResult>> {
+    results.filter(|results| !results.iter().any(InvokeResult::is_transport_error))
+}
+
+pub fn explore(
+    ctx: &ExplorationContext,
+    operations: Vec<Operation>,
+    invoke: InvokeFn,
+    generator: impl Strategy<Value = (u8, Vec<GenerationOperationWithParameters>)>,
+    process_result: ProcessResultFn,
+    check: fn(&[InvokeResult]) -> bool,
+    synthesize_operations: fn(u8, &[GenerationOperationWithParameters]) -> Vec<GeneratedOperation>,
+) -> Result<Option<MinimalExample>, ExploreError> {
+    let mut runner = ctx.runner.borrow_mut();
+
+    for test_n in 0..ctx.number_of_tests {
+        if ctx.checkpoint() {
+            ctx.publish_event(Event::log(LogLevel::Info, "Exploration stopped"));
+            return Ok(None);
+        }
+
+        ctx.publish_event(Event::progress(format!("Start test {}", test_n,)));
+
+        // Invoke the generator to get a new generated sequence
+        let mut val = generator
+            .new_tree(&mut runner)
+            .map_err(|reason| ExploreError::Generation(reason.to_string()))?;
+
+        // Synthesize to operations, Gen.ops -> Actual ops.
+        ctx.publish_event(Event::progress(format!(
+            "Generating operations for test {} starting..",
+            test_n,
+        )));
+        let (query_pos, op_seq) = &val.current();
+        let gen_ops = synthesize_operations(*query_pos, op_seq);
+        ctx.publish_event(Event::progress(format!(
+            "Generating operations for test {} done",
+            test_n,
+        )));
+
+        // Invoke the operations
+        ctx.publish_event(Event::progress(format!(
+            "Invoke of operations for test {} starting..",
+            test_n,
+        )));
+        let invoke_result = conclusive(invoke(ctx, &operations, &gen_ops));
+        ctx.publish_event(Event::progress(format!(
+            "Invoke of operations for test {} done",
+            test_n,
+        )));
+        if let Some(results) = &invoke_result {
+            ctx.record_in_campaign(*query_pos, op_seq, results);
+        }
+
+        // Let the behaviour process the result before the check
+        let potential_query_results = process_result(ctx, invoke_result);
+        ctx.complete_test();
+
+        // Check if we could produce a result
+        let Some(query_results) = potential_query_results else {
+            continue;
+        };
+
+        // Check if the result fit the behaviour or not
+        if check(&query_results) {
+            // Test passed
+            continue;
+        }
+
+        // FAILED check, i.e, we match the behaviour
+        ctx.publish_event(Event::progress(
+            "Found failing sequence, start Shrinking".to_string(),
+        ));
+        let mut correlation_id = first_correlation_id(&query_results);
+        let mut truncation = None;
+
+        // NOTE: Dealing with state-ful systems we should not use the same
+        //  value twice, hence do NOT use val.current, simplify first
+        let mut shrink_count = 0;
+        let shrink_start = std::time::Instant::now();
+        let paused_before = ctx.paused.get();
+        let mut minimal_ops = gen_ops.clone();
+        // Complicating backs up to sequences already invoked, their checks are
+        //  reused instead of invoking them again. None if inconclusive.
+        let mut checked: HashMap<String, Option<bool>> = sequence_key(&gen_ops)
+            .map(|key| (key, Some(false)))
+            .into_iter()
+            .collect();
+        val.simplify();
+        loop {
+            // Stopped or out of budget the simplest failing sequence so far is
+            //  the example
+            let exhausted = if ctx.checkpoint() {
+                Some(ShrinkLimit::Stopped)
+            } else {
+                // The time paused is not spent shrinking
+                let paused = ctx.paused.get() - paused_before;
+                ctx.shrinking
+                    .exhausted(shrink_count, shrink_start.elapsed().saturating_sub(paused))
+            };
+            if let Some(limit) = exhausted {
+                ctx.publish_event(Event::log(
+                    LogLevel::Warning,
+                    &format!(
+                        "Shrinking stopped after {} iterations, at {}",
+                        shrink_count, limit
+                    ),
+                ));
+                truncation = Some(ShrinkTruncation {
+                    limit,
+                    iterations: shrink_count,
+                });
+                break;
+            }
+
+            shrink_count += 1;
+            // Run the simplified sequence
+            let (query_pos, op_seq) = &val.current();
+            let gen_ops = synthesize_operations(*query_pos, op_seq);
+            let key = sequence_key(&gen_ops);
+            let mut cached = key.as_ref().and_then(|key| checked.get(key)).copied();
+            if cached.is_some() {
+                match &ctx.shrinking.repeated {
+                    RepeatedSequence::Reuse => (),
+                    RepeatedSequence::Reinvoke => cached = None,
+                    RepeatedSequence::ResetFirst { command } => {
+                        if reset_before_repeat(ctx, command) {
+                            cached = None;
+                        }
+                    }
+                }
+            }
+            let passed = match cached {
+                Some(passed) => passed,
+                None => {
+                    let invoke_result = conclusive(invoke(ctx, &operations, &gen_ops));
+                    // Let the behaviour process the result before the check
+                    let passed = process_result(ctx, invoke_result).map(|query_results| {
+                        let passed = check(&query_results);
+                        if !passed {
+                            correlation_id = first_correlation_id(&query_results);
+                        }
+                        passed
+                    });
+                    if let Some(key) = key {
+                        checked.insert(key, passed);
+                    }
+                    passed
+                }
+            };
+
+            match passed {
+                // Could not produce a result, back up
+                None => {
+                    if !val.complicate() {
+                        break;
+                    }
+                }
+                Some(false) => {
+                    minimal_ops = gen_ops;
+                    // Still failing, find a simpler example if we can
+                    ctx.publish_event(Event::progress(format!(
+                        "Simpler sequence failed, keep Shrinking - {shrink_count}",
+                    )));
+                    if !val.simplify() {
+                        break;
+                    }
+                }
+                Some(true) => {
+                    // Passed this input, back up
+                    if !val.complicate() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Shrinking is done, the last failing sequence is the smallest
+        ctx.publish_event(Event::progress("Shrinking done".to_string()));
+
+        // The id of the last failing invocation, to find it in the logs of the SUT
+        return Ok(Some((minimal_ops, correlation_id, truncation)));
+    }
+
+    // Didn't find any example
+    Ok(None)
+}
+
+pub fn invoke_with_reqwest(
+    client: &reqwest::blocking::Client,
+    http_operation: HTTPCall,
+) -> Result<http::HTTPResult, http::TransportError> {
+    reqwest_http::invoke_with_reqwest(client, http_operation)
+}
+
+pub fn invoke_dry(
+    _client: &reqwest::blocking::Client,
+    _http_operation: HTTPCall,
+) -> Result<http::HTTPResult, http::TransportError> {
+    let payload = FIXED_PAYLOAD.to_string();
+
+    Ok(http::HTTPResult {
+        status: http::HTTPStatus::OK,
+        size: payload.len(),
+        payload,
+        success: true,
+        content_encoding: None,
+        headers: vec![],
+    })
+}
+
+pub fn invoke(
+    ctx: &ExplorationContext,
+    ops: &[Operation],
+    gen_ops: &[GeneratedOperation],
+) -> Option<Vec<InvokeResult>> {
+    let span = span!(Level::TRACE, "HTTP invoke span");
+    let _enter = span.enter();
+
+    let Target::HTTP { config } = ctx.current_target() else {
+        warn!("Only an HTTP target is invoked over HTTP");
+        return None;
+    };
+
+    let span_start_time = std::time::Instant::now();
+    ctx.publish_event(Event::InvocationSpanEnter {
+        enter: span_start_time,
+    });
+
+    // The operations as they were sent, for the script of the run
+    let mut sent = Vec::with_capacity(gen_ops.len());
+    let results = invoke_http(ctx, config, ops, gen_ops, &mut sent);
+
+    ctx.record_script(&sent);
+
+    debug!("Invocation span exit");
+    ctx.publish_event(Event::InvocationSpanExit {
+        duration: span_start_time.elapsed(),
+    });
+    results
+}
+
+/// Invoke the operations in order over HTTP, within the span of `invoke`. An
+///  operation that can not be translated discards the sequence.
+fn invoke_http(
+    ctx: &ExplorationContext,
+    config: &http::HTTPConfiguration,
+    ops: &[Operation],
+    gen_ops: &[GeneratedOperation],
+    sent: &mut Vec<GeneratedOperation>,
+) -> Option<Vec<InvokeResult>> {
+    let mut results = Vec::with_capacity(gen_ops.len());
+    let test_case = ctx.test_cases.get() + 1;
+    ctx.test_cases.set(test_case);
+    let correlation_id = ctx.correlation.id(test_case);
+
+    if let Some(chaos) = &ctx.chaos {
+        chaos.start_sequence();
+    }
+    if let Some(cache) = &ctx.cache {
+        cache.borrow_mut().clear();
+    }
+
+    // The idempotent retry sends the last POST again, with the key of the
+    //  request it retries
+    let with_retry = ctx
+        .behaviour_settings
+        .idempotent_retry()
+        .and_then(|_| idempotency::retried(ops, gen_ops));
+    let retry = with_retry.as_ref().map(|retried| retried.len() - 1);
+    let gen_ops = with_retry.as_deref().unwrap_or(gen_ops);
+
+    for (idx, gen_op) in gen_ops.iter().enumerate() {
+        ctx.pause_while_unhealthy(false);
+        if ctx.error_budget.is_exhausted() {
+            break;
+        }
+
+        debug!(operation_name = gen_op.name,);
+        debug!("Invoke: {gen_op:#?}");
+
+        // The query operation observes the state again after the delay
+        let is_query = ctx.is_query(&gen_op.name);
+        let delay = ctx.behaviour_settings.observation_delay();
+        if is_query && idx > 0 && !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+
+        // A repeated request is sent the same way, with the same key
+        let send = || {
+            let (mut final_request, url) =
+                translate_generated_operation_to_http_call(config, ops, gen_op, &results)?;
+            trace!("{final_request:#?}");
+
+            // Added after the trace, to keep the credentials out of the logs
+            ctx.add_request_headers(&mut final_request, &correlation_id);
+            let key_step = if retry == Some(idx) { idx - 1 } else { idx };
+            ctx.add_idempotency_key(&mut final_request, &correlation_id, key_step);
+            if ctx.behaviour_settings.conditional_request().is_some() {
+                if let Some(etag) = conditional::if_match(idx, gen_ops.len(), &results) {
+                    final_request.headers.push(("If-Match".to_string(), etag));
+                }
+            }
+
+            // A fresh response is served from the cache, the request is not sent
+            if let Some(cache) = &ctx.cache {
+                if let Some(fresh) = cache.borrow_mut().prepare(&mut final_request) {
+                    return Some((Ok(fresh), url, std::time::Duration::ZERO));
+                }
+            }
+
+            let request_start_time = std::time::Instant::now();
+            let http_resp = match &ctx.mock {
+                Some(mock) => Ok(mock.respond(&gen_op.name, &final_request)),
+                None => (ctx.http_send_fn)(&ctx.http_client, final_request),
+            };
+            Some((http_resp, url, request_start_time.elapsed()))
+        };
+        let complete = |http_resp| match &ctx.cache {
+            Some(cache) => cache.borrow_mut().complete(&gen_op.name, http_resp),
+            None => (http_resp, None),
+        };
+
+        // The faults are injected before the requests of the other
+        //  operations, the query operations observe the state left behind
+        let fault = match &ctx.chaos {
+            Some(chaos) if !is_query => chaos.next_fault(idx, &gen_op.name),
+            _ => None,
+        };
+        match fault {
+            Some(ChaosFault::Drop) => {
+                // A dropped request does not count against the error budget
+                let resp =
+                    InvokeResult::transport_failure(gen_op.clone(), http::TransportError::Dropped)
+                        .with_correlation_id(&correlation_id)
+                        .with_position(test_case, idx);
+                ctx.publish_event(Event::Invocation {
+                    result: resp.clone(),
+                    sut_invocation_duration: std::time::Duration::ZERO,
+                });
+                results.push(resp);
+                continue;
+            }
+            Some(ChaosFault::Delay { ms }) => {
+                std::thread::sleep(std::time::Duration::from_millis(ms))
+            }
+            Some(ChaosFault::Repeat) => {
+                // Only the response of the repeated request is kept
+                let (http_resp, url, request_duration) = send()?;
+                let result = match http_resp {
+                    Ok(http_resp) => {
+                        let (http_resp, cache_outcome) = complete(http_resp);
+                        translate_http_result(http_resp, gen_op, url).with_cache(cache_outcome)
+                    }
+                    Err(error) => InvokeResult::transport_failure(gen_op.clone(), error),
+                };
+                ctx.publish_event(Event::Invocation {
+                    result: result
+                        .with_duration(request_duration)
+                        .with_correlation_id(&correlation_id)
+                        .with_position(test_case, idx),
+                    sut_invocation_duration: request_duration,
+                });
+            }
+            None => (),
+        }
+
+        let (mut http_resp, mut url, mut request_duration) = send()?;
+        if ctx.script.is_some() {
+            sent.push(resolve_response_references(ops, gen_op, &results));
+        }
+        // A request failing while the target was unhealthy is sent again
+        //  once it recovered, instead of counting against the error budget
+        if http_resp.is_err() && ctx.pause_while_unhealthy(true) {
+            if ctx.error_budget.is_exhausted() {
+                break;
+            }
+            (http_resp, url, request_duration) = send()?;
+        }
+
+        if ctx.error_budget.record(http_resp.is_ok()) {
+            ctx.abort(&format!(
+                "Aborted after {} consecutive requests without a response from the target",
+                ctx.error_budget.limit
+            ));
+        }
+
+        let invoke_result = match http_resp {
+            Ok(invoke_result) => invoke_result,
+            Err(error) => {
+                // Kept to show in the invocations, the checks skip the sequence
+                let resp = InvokeResult::transport_failure(gen_op.clone(), error)
+                    .with_correlation_id(&correlation_id)
+                    .with_position(test_case, idx);
+                ctx.publish_event(Event::Invocation {
+                    result: resp.clone(),
+                    sut_invocation_duration: request_duration,
+                });
+                results.push(resp);
+                continue;
+            }
+        };
+
+        let (invoke_result, cache_outcome) = complete(invoke_result);
+
+        let resp = translate_http_result(invoke_result, gen_op, url)
+            .with_duration(request_duration)
+            .with_correlation_id(&correlation_id)
+            .with_position(test_case, idx)
+            .with_cache(cache_outcome);
+        if let Some(coverage) = &ctx.coverage {
+            coverage.lock().unwrap().record(&resp);
+        }
+        if let Some(corpus) = &ctx.corpus {
+            corpus.lock().unwrap().harvest(&resp);
+        }
+        if let Some(links) = &ctx.links {
+            links.lock().unwrap().discover(config, &resp);
+        }
+        ctx.publish_event(Event::Invocation {
+            result: resp.clone(),
+            sut_invocation_duration: request_duration,
+        });
+        results.push(resp);
+        if let Some(precedence) = &ctx.precedence {
+            precedence.lock().unwrap().record(&results);
+        }
+
+        // The state is observed at the same points as the query operation. A
+        //  state that could not be observed leaves nothing to compare, the
+        //  sequence is discarded.
+        if let Some(observation) = &ctx.observation {
+            if is_query {
+                match observation.observer.observation_result() {
+                    Ok(observed) => results.push(observed),
+                    Err(e) => {
+                        ctx.publish_event(Event::log(
+                            LogLevel::Warning,
+                            &format!(
+                                "State observation failed, the sequence is not checked: {}",
+                                e
+                            ),
+                        ));
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(results)
+}
+
+/// Invoke the operations in order with a backend other than HTTP. The send
+///  gets the results of the operations before it and the id of the
+///  invocation, a result without a response counts against the error budget.
+///  An operation the send can not translate discards the sequence.
+pub fn invoke_in_order(
+    ctx: &ExplorationContext,
+    gen_ops: &[GeneratedOperation],
+    mut send: impl FnMut(&GeneratedOperation, &[InvokeResult], &str) -> Option<InvokeResult>,
+) -> Option<Vec<InvokeResult>> {
+    let span_start_time = std::time::Instant::now();
+    ctx.publish_event(Event::InvocationSpanEnter {
+        enter: span_start_time,
+    });
+
+    let mut results: Vec<InvokeResult> = Vec::with_capacity(gen_ops.len());
+    let test_case = ctx.test_cases.get() + 1;
+    ctx.test_cases.set(test_case);
+    let correlation_id = ctx.correlation.id(test_case);
+
+    let mut discarded = false;
+    for (idx, gen_op) in gen_ops.iter().enumerate() {
+        if ctx.error_budget.is_exhausted() {
+            break;
+        }
+
+        let start_time = std::time::Instant::now();
+        let Some(result) = send(gen_op, &results, &format!("{}-{}", correlation_id, idx)) else {
+            discarded = true;
+            break;
+        };
+        let duration = start_time.elapsed();
+
+        if ctx.error_budget.record(!result.is_transport_error()) {
+            ctx.abort(&format!(
+                "Aborted after {} consecutive requests without a response from the target",
+                ctx.error_budget.limit
+            ));
+        }
+
+        let result = result
+            .with_duration(duration)
+            .with_correlation_id(&correlation_id)
+            .with_position(test_case, idx);
+        ctx.publish_event(Event::Invocation {
+            result: result.clone(),
+            sut_invocation_duration: duration,
+        });
+        results.push(result);
+    }
+
+    ctx.publish_event(Event::InvocationSpanExit {
+        duration: span_start_time.elapsed(),
+    });
+    (!discarded).then_some(results)
+}
+
+/// Invoke the sequences of the script again, with the values they were
+///  recorded with instead of generated ones. Returns the results of each
+///  sequence, a sequence that could not be translated has none.
+pub fn replay(
+    ctx: &ExplorationContext,
+    ops: &[Operation],
+    script: &ReplayScript,
+) -> Result<Vec<Vec<InvokeResult>>, ExploreError> {
+    if let Some(unknown) = script
+        .sequences
+        .iter()
+        .flatten()
+        .find(|gen_op| !ops.iter().any(|op| op.info.name == gen_op.name))
+    {
+        return Err(ExploreError::UnknownOperation(unknown.name.clone()));
+    }
+
+    let mut replayed = Vec::with_capacity(script.sequences.len());
+    for sequence in &script.sequences {
+        if ctx.checkpoint() {
+            break;
+        }
+        replayed.push(invoke(ctx, ops, sequence).unwrap_or_default());
+    }
+
+    Ok(replayed)
+}
+
+/// Invoke the sequences of the processes at the same time, each from a thread
+///  of its own. The invocations are published once every process is done,
+///  in the order they were called.
+pub fn invoke_concurrently(
+    ctx: &ExplorationContext,
+    ops: &[Operation],
+    processes: &[Vec<GeneratedOperation>],
+) -> Option<Vec<HistoryEntry>> {
+    let Target::HTTP { config } = &ctx.target else {
+        warn!("Only an HTTP target is invoked over HTTP");
+        return None;
+    };
+
+    let span_start_time = std::time::Instant::now();
+    ctx.publish_event(Event::InvocationSpanEnter {
+        enter: span_start_time,
+    });
+
+    let test_case = ctx.test_cases.get() + 1;
+    ctx.test_cases.set(test_case);
+    let correlation_id = ctx.correlation.id(test_case);
+
+    // The threads only get what is safe to share of the context
+    let client = &ctx.http_client;
+    let send = ctx.http_send_fn;
+    let mock = ctx.mock.as_deref();
+    let auth = ctx.request_auth();
+    let correlation_header = ctx.correlation.header();
+    let correlation_id = &correlation_id;
+
+    let histories = std::thread::scope(|scope| {
+        let handles: Vec<_> = processes
+            .iter()
+            .enumerate()
+            .map(|(process, gen_ops)| {
+                scope.spawn(move || {
+                    let mut results: Vec<InvokeResult> = vec![];
+                    let mut history = vec![];
+                    for gen_op in gen_ops {
+                        let (mut request, url) = translate_generated_operation_to_http_call(
+                            config, ops, gen_op, &results,
+                        )?;
+                        if let Some(auth) = auth {
+                            request
+                                .headers
+                                .push((auth.header.clone(), auth.value.clone()));
+                        }
+                        if let Some(header) = correlation_header {
+                            request
+                                .headers
+                                .push((header.to_string(), correlation_id.clone()));
+                        }
+
+                        let call = span_start_time.elapsed();
+                        let http_resp = match mock {
+                            Some(mock) => Ok(mock.respond(&gen_op.name, &request)),
+                            None => send(client, request),
+                        };
+                        let ret = span_start_time.elapsed();
+
+                        let result = match http_resp {
+                            Ok(http_resp) => translate_http_result(http_resp, gen_op, url),
+                            Err(error) => InvokeResult::transport_failure(gen_op.clone(), error),
+                        }
+                        .with_duration(ret - call)
+                        .with_correlation_id(correlation_id);
+                        results.push(result.clone());
+                        history.push(HistoryEntry {
+                            process,
+                            call,
+                            ret,
+                            result,
+                        });
+                    }
+                    Some(history)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Process thread panicked"))
+            .collect::<Option<Vec<Vec<HistoryEntry>>>>()
+    });
+    let Some(histories) = histories else {
+        ctx.publish_event(Event::InvocationSpanExit {
+            duration: span_start_time.elapsed(),
+        });
+        return None;
+    };
+
+    let mut history: Vec<HistoryEntry> = histories.into_iter().flatten().collect();
+    history.sort_by_key(|entry| entry.call);
+
+    for (idx, entry) in history.iter_mut().enumerate() {
+        entry.result = entry.result.clone().with_position(test_case, idx);
+
+        if ctx
+            .error_budget
+            .record(entry.result.transport_error.is_none())
+        {
+            ctx.abort(&format!(
+                "Aborted after {} consecutive requests without a response from the target",
+                ctx.error_budget.limit
+            ));
+        }
+        if let Some(coverage) = &ctx.coverage {
+            coverage.lock().unwrap().record(&entry.result);
+        }
+        if let Some(corpus) = &ctx.corpus {
+            corpus.lock().unwrap().harvest(&entry.result);
+        }
+        ctx.publish_event(Event::Invocation {
+            result: entry.result.clone(),
+            sut_invocation_duration: entry.ret - entry.call,
+        });
+    }
+
+    ctx.publish_event(Event::InvocationSpanExit {
+        duration: span_start_time.elapsed(),
+    });
+    Some(history)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::cell::Cell;
+    use std::thread;
+
+    use qr_http_resource::http::{self, HTTPMethod};
+
+    use crate::{
+        amos::{
+            InvokeResult, Operation, OperationInfo, OperationMetaData, Parameter,
+            ParameterOwnership, Response, ResultMetaData, Schema,
+        },
+        amos_generation::{GeneratedOperation, GeneratedParameter, ParameterValue},
+        exploration_settings::{
+            BehaviourSettings, HealthSettings, RepeatedSequence, ShrinkLimit, ShrinkSettings,
+            ShrinkStrategy, StatusClass,
+        },
+        explore as sut,
+        health::HealthCheck,
+    };
+
+    #[test]
+    fn buckets_are_created_again_for_other_operations() {
+        let op = |name: &str, url: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{name}"),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method: HTTPMethod::POST,
+            }),
+        };
+        let persons = vec![op("post_person", "/persons")];
+        let pets = vec![op("post_pet", "/persons/{id}/pets")];
+
+        let mut ctx = sut::ExplorationContext::new(sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        });
+
+        let buckets = ctx.buckets(&persons);
+        assert!(std::sync::Arc::ptr_eq(&buckets, &ctx.buckets(&persons)));
+
+        let other = ctx.buckets(&pets);
+        assert!(other.find(&pets[0]).is_some());
+        assert!(other.find(&persons[0]).is_none());
+    }
+
+    #[test]
+    fn invoke_with_dry() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, _exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let generated = vec![
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+        ];
+
+        let operations = vec![op.clone()];
+
+        let invoke_result = sut::invoke(&ctx, &operations, &generated);
+
+        assert_eq!(
+            invoke_result,
+            Some(vec![
+                InvokeResult {
+                    operation: GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![]
+                    },
+                    result: "[\"Fake result\"]".to_string(),
+                    success: true,
+                    meta_data: Some(ResultMetaData::HTTP {
+                        url: "http://foo:123/persons".to_string(),
+                        status: http::HTTPStatus::OK,
+                        size: 15,
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                    duration: None,
+                    transport_error: None,
+                    correlation_id: None,
+                    position: None,
+                    cache: None
+                },
+                InvokeResult {
+                    operation: GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![]
+                    },
+                    result: "[\"Fake result\"]".to_string(),
+                    success: true,
+                    meta_data: Some(ResultMetaData::HTTP {
+                        url: "http://foo:123/persons".to_string(),
+                        status: http::HTTPStatus::OK,
+                        size: 15,
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                    duration: None,
+                    transport_error: None,
+                    correlation_id: None,
+                    position: None,
+                    cache: None
+                }
+            ])
+        )
+    }
+
+    #[test]
+    fn explore_response_inequality_no_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+                // Setup equal result, i.e., no example found for this exploration
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_response_inequality(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string()
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn stopped_exploration_invokes_nothing() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            number_of_tests: 10,
+            stop: Some(std::sync::Arc::new(std::sync::atomic::AtomicBool::new(
+                true,
+            ))),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let invoke = |_ctx: &sut::ExplorationContext,
+                      _ops: &[Operation],
+                      _gen_ops: &[GeneratedOperation]|
+         -> Option<Vec<InvokeResult>> {
+            panic!("A stopped exploration must not invoke")
+        };
+
+        let results = sut::response_check(&ctx, vec![op.clone()], vec![op], &invoke);
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn explore_response_inequality_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+                // Setup unequal result, i.e., example found for this exploration
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Totally not Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_response_inequality(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::ResponseInEquality {
+                    operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        },
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        }
+                    ]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_response_equality_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_response_equality(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::ResponseEquality {
+                    operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        },
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        }
+                    ]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_response_equality_no_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake rddddddesult\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "udddrl".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_response_equality(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string()
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_state_mutation_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let post_op = Operation {
+            info: OperationInfo {
+                name: "post_person".to_string(),
+                key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                Parameter {
+                    name: "name".to_string(),
+                    schema: Schema::String,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+                Parameter {
+                    name: "age".to_string(),
+                    schema: Schema::Int8,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+            ],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::Reference("person".to_string()),
+                status: None,
+            }],
+            meta_data: None,
+        };
+
+        let get_op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![post_op.clone()];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Another Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        let query_ops = vec![get_op.clone()];
+
+        thread::spawn(move || {
+            sut::explore_state_mutation(&mut ctx, operations, &query_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        //assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::StateMutation {
+                    query_operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        },
+                        GeneratedOperation {
+                            name: "post_person".to_string(),
+                            parameters: vec![
+                                GeneratedParameter {
+                                    name: "name".to_string(),
+                                    value: ParameterValue::StringValue {
+                                        value: "".to_string(),
+                                        seed: 1,
+                                        active: false
+                                    },
+                                    ref_path: None
+                                },
+                                GeneratedParameter {
+                                    name: "age".to_string(),
+                                    value: ParameterValue::IntValue {
+                                        value: 0,
+                                        seed: 1,
+                                        active: false
+                                    },
+                                    ref_path: None
+                                }
+                            ]
+                        },
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        }
+                    ]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                }
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_state_mutation_observes_all_query_operations_together() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            behaviour_settings: BehaviourSettings::StateMutation {
+                observe_all_queries: true,
+            },
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = |name: &str, method: HTTPMethod| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method,
+            }),
+        };
+        let operations = vec![op("post_person", HTTPMethod::POST)];
+        let query_ops = vec![
+            op("get_persons", HTTPMethod::GET),
+            op("get_count", HTTPMethod::GET),
+        ];
+
+        // Only the count observes the posted person
+        let invoke =
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let mut posted = 0;
+                let results = gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        let result = match gen_op.name.as_str() {
+                            "get_count" => posted.to_string(),
+                            "post_person" => {
+                                posted += 1;
+                                String::new()
+                            }
+                            _ => "[]".to_string(),
+                        };
+                        InvokeResult::new(gen_op.clone(), result, true, None)
+                    })
+                    .collect();
+
+                Some(results)
+            };
+
+        thread::spawn(move || {
+            sut::explore_state_mutation(&mut ctx, operations, &query_ops, &invoke);
+        });
+
+        let results: Vec<sut::ExplorationResult> = exploration_log_rx
+            .iter()
+            .filter_map(|event| match event {
+                sut::Event::Result { result } => Some(result),
+                _ => None,
+            })
+            .collect();
+
+        // One exploration for both query operations, before and after the post
+        assert_eq!(results.len(), 1);
+        let sut::ExplorationResult::StateMutation {
+            query_operation,
+            minimal_sequence: Some(sequence),
+            ..
+        } = &results[0]
+        else {
+            panic!("Expected a state mutation, got {:?}", results[0]);
+        };
+        assert_eq!(query_operation, "get_persons, get_count");
+        let names: Vec<&str> = sequence.iter().map(|op| op.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "get_persons",
+                "get_count",
+                "post_person",
+                "get_persons",
+                "get_count"
+            ]
+        );
+    }
+
+    #[test]
+    fn explore_state_mutation_no_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let post_op = Operation {
+            info: OperationInfo {
+                name: "post_person".to_string(),
+                key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                Parameter {
+                    name: "name".to_string(),
+                    schema: Schema::String,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+                Parameter {
+                    name: "age".to_string(),
+                    schema: Schema::Int8,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+            ],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::Reference("person".to_string()),
+                status: None,
+            }],
+            meta_data: None,
+        };
+
+        let get_op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![post_op.clone()];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        let query_ops = vec![get_op.clone()];
+
+        thread::spawn(move || {
+            sut::explore_state_mutation(&mut ctx, operations, &query_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        //assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string()
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_state_identity_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let post_op = Operation {
+            info: OperationInfo {
+                name: "post_person".to_string(),
+                key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                Parameter {
+                    name: "name".to_string(),
+                    schema: Schema::String,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+                Parameter {
+                    name: "age".to_string(),
+                    schema: Schema::Int8,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+            ],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::Reference("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::POST,
+            }),
+        };
+
+        let get_op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![get_op.clone(), post_op.clone()];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Changed Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        let query_ops = vec![get_op.clone()];
+
+        thread::spawn(move || {
+            sut::explore_state_identity(&mut ctx, operations, &query_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        //assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::StateIdentity {
+                    query_operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        },
+                        GeneratedOperation {
+                            name: "post_person".to_string(),
+                            parameters: vec![
+                                GeneratedParameter {
+                                    name: "name".to_string(),
+                                    value: ParameterValue::StringValue {
+                                        value: "".to_string(),
+                                        seed: 1,
+                                        active: false
+                                    },
+                                    ref_path: None
+                                },
+                                GeneratedParameter {
+                                    name: "age".to_string(),
+                                    value: ParameterValue::IntValue {
+                                        value: 0,
+                                        seed: 1,
+                                        active: false
+                                    },
+                                    ref_path: None
+                                }
+                            ]
+                        },
+                        GeneratedOperation {
+                            name: "get_persons".to_string(),
+                            parameters: vec![]
+                        }
+                    ]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                }
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn explore_state_identity_no_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let post_op = Operation {
+            info: OperationInfo {
+                name: "post_person".to_string(),
+                key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                Parameter {
+                    name: "name".to_string(),
+                    schema: Schema::String,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+                Parameter {
+                    name: "age".to_string(),
+                    schema: Schema::Int8,
+                    required: true,
+                    ownership: ParameterOwnership::Owned,
+                    meta_data: None,
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                },
+            ],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::Reference("person".to_string()),
+                status: None,
+            }],
+            meta_data: None,
+        };
+
+        let get_op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![get_op.clone(), post_op.clone()];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                    InvokeResult::new(
+                        gen_ops[0].clone(),
+                        "[\"Fake result\"]".to_string(),
+                        true,
+                        Some(ResultMetaData::HTTP {
+                            url: "url".to_string(),
+                            status: http::HTTPStatus::OK,
+                            size: 0,
+                            content_encoding: None,
+                            headers: vec![],
+                        }),
+                    ),
+                ];
+
+                Some(result)
+            };
+
+        let query_ops = vec![get_op.clone()];
+
+        thread::spawn(move || {
+            sut::explore_state_identity(&mut ctx, operations, &query_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        //assert_eq!(messages, vec![]);
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string()
+                },
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn response_check_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                ctx.publish_event(sut::Event::Log {
+                    message: sut::LogMessage {
+                        level: sut::LogLevel::Info,
+                        message: "Invoke".to_string(),
+                    },
+                });
+
+                let result: Vec<InvokeResult> = vec![InvokeResult::new(
+                    gen_ops[0].clone(),
+                    "[\"Fake result\"]".to_string(),
+                    true,
+                    Some(ResultMetaData::HTTP {
+                        url: "url".to_string(),
+                        status: http::HTTPStatus::InternalServerError,
+                        size: 0,
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                )];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::response_check(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::ResponseCheck {
+                    operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![]
+                    }]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                }
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn response_check_fails_on_the_status_classes() {
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        // The SUT does not know the persons
+        let invoke =
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                Some(vec![InvokeResult::new(
+                    gen_ops[0].clone(),
+                    String::new(),
+                    false,
+                    Some(ResultMetaData::HTTP {
+                        url: "url".to_string(),
+                        status: http::HTTPStatus::NotFound,
+                        size: 0,
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                )])
+            };
+
+        let found = |failing_statuses: Vec<StatusClass>| {
+            let ctx = sut::ExplorationContext {
+                http_send_fn: sut::invoke_dry,
+                behaviour_settings: BehaviourSettings::ResponseCheck { failing_statuses },
+                ..sut::ExplorationContext::new(sut::Target::HTTP {
+                    config: http::HTTPConfiguration::new(
+                        "foo".to_string(),
+                        123,
+                        http::Protocol::HTTP,
+                    ),
+                })
+            };
+
+            let results = sut::response_check(&ctx, vec![op.clone()], vec![op.clone()], &invoke);
+            matches!(
+                results.as_slice(),
+                [sut::ExplorationResult::ResponseCheck { .. }]
+            )
+        };
+
+        assert!(!found(vec![StatusClass::ServerError]));
+        assert!(found(vec![
+            StatusClass::ClientError,
+            StatusClass::ServerError
+        ]));
+    }
+
+    #[test]
+    fn response_check_with_no_example() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![Response {
+                name: "successful operation".to_string(),
+                schema: Schema::ArrayOfRefItems("person".to_string()),
+                status: None,
+            }],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        // The invoker keeps state of its own, as an embedded backend would
+        let invoked = Arc::new(AtomicUsize::new(0));
+        let counted = invoked.clone();
+        let invoke = move |ctx: &sut::ExplorationContext,
+                           _ops: &[Operation],
+                           gen_ops: &[GeneratedOperation]| {
+            counted.fetch_add(1, Ordering::Relaxed);
+            ctx.publish_event(sut::Event::Log {
+                message: sut::LogMessage {
+                    level: sut::LogLevel::Info,
+                    message: "Invoke".to_string(),
+                },
+            });
+
+            let result: Vec<InvokeResult> = vec![InvokeResult::new(
+                gen_ops[0].clone(),
+                "[\"Fake result\"]".to_string(),
+                true,
+                Some(ResultMetaData::HTTP {
+                    url: "url".to_string(),
+                    status: http::HTTPStatus::OK,
+                    size: 0,
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            )];
+
+            Some(result)
+        };
+
+        thread::spawn(move || {
+            sut::response_check(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string()
+                },
+            }),
+            result
+        );
+        assert_eq!(invoked.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn response_time_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut response_time = crate::exploration_settings::ResponseTimeSla::new();
+        response_time.global_ms = 100;
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            response_time,
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op.clone()];
+        let explore_ops = vec![op];
+
+        // Every invocation is slower than the SLA
+        let invoke =
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let result: Vec<InvokeResult> =
+                    vec![
+                        InvokeResult::new(gen_ops[0].clone(), "[]".to_string(), true, None)
+                            .with_duration(std::time::Duration::from_millis(250)),
+                    ];
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_response_time(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
+
+        assert_eq!(
+            Some(&sut::Event::Result {
+                result: sut::ExplorationResult::ResponseTime {
+                    operation: "get_persons".to_string(),
+                    minimal_sequence: Some(vec![GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![]
+                    }]),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                }
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn injection_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut injection = crate::exploration_settings::InjectionSettings::new();
+        injection.set("find_person", true);
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 100,
+            injection,
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let find_op = Operation {
+            info: OperationInfo {
+                name: "find_person".to_string(),
+                key: "operation/find_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![Parameter {
+                name: "name".to_string(),
+                schema: Schema::String,
+                required: true,
+                ownership: ParameterOwnership::Unknown,
+                meta_data: None,
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            }],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let get_op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![find_op.clone(), get_op.clone()];
+        let explore_ops = vec![find_op, get_op];
+
+        // Any attack payload crashes the SUT
+        let invoke = |_ctx: &sut::ExplorationContext,
+                      _ops: &[Operation],
+                      gen_ops: &[GeneratedOperation]| {
+            let is_attack = gen_ops[0].parameters.iter().any(|p| match &p.value {
+                ParameterValue::StringValue { value, .. } => crate::injection::is_payload(value),
+                _ => false,
+            });
+            let status = if is_attack {
+                http::HTTPStatus::InternalServerError
+            } else {
+                http::HTTPStatus::OK
+            };
+
+            let result: Vec<InvokeResult> = vec![InvokeResult::new(
+                gen_ops[0].clone(),
+                "[]".to_string(),
+                !is_attack,
+                Some(ResultMetaData::HTTP {
+                    url: "/persons".to_string(),
+                    status,
+                    size: 2,
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            )];
+
+            Some(result)
+        };
+
+        thread::spawn(move || {
+            sut::explore_injection(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let results = messages
+            .iter()
+            .filter_map(|m| match m {
+                sut::Event::Result { result } => Some(result),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // The operation without injection enabled is skipped
+        assert_eq!(results.len(), 1);
+        match results[0] {
+            sut::ExplorationResult::Injection {
+                operation,
+                minimal_sequence: Some(seq),
+                ..
+            } => {
+                assert_eq!(operation, "find_person");
+                assert!(crate::injection::is_payload(
+                    &seq[0].parameters[0].value.as_string_value()
+                ));
+            }
+            other => panic!("Expected an injection example, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn auth_bypass_with_example() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 3,
+            min_length: 0,
+            max_length: 0,
+            auth: crate::exploration_settings::AuthSettings {
+                credentials: Some(http::HTTPAuth::bearer("admin")),
+                downgraded: Some(http::HTTPAuth::bearer("user")),
+                roles: vec![],
+            },
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op("get_secret"), op("get_leaky")];
+        let explore_ops = operations.clone();
+
+        // Only the secret is authorized, and only for the admin
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let is_admin = ctx
+                    .request_auth()
+                    .is_some_and(|auth| auth.value == "Bearer admin");
+
+                let result: Vec<InvokeResult> = gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        let success = gen_op.name == "get_leaky" || is_admin;
+                        InvokeResult::new(gen_op.clone(), "[]".to_string(), success, None)
+                    })
+                    .collect();
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_auth_bypass(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let results = messages
+            .iter()
+            .filter_map(|m| match m {
+                sut::Event::Result { result } => Some(result.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                sut::ExplorationResult::NoExampleFound {
+                    operation: "get_secret".to_string()
+                },
+                sut::ExplorationResult::AuthBypass {
+                    operation: "get_leaky".to_string(),
+                    role: crate::exploration_settings::AuthRole::Stripped,
+                    sequence: Some(vec![GeneratedOperation {
+                        name: "get_leaky".to_string(),
+                        parameters: vec![]
+                    }]),
+                    correlation_id: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn differential_reports_the_diverging_response() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 3,
+            min_length: 0,
+            max_length: 0,
+            reference: Some(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("bar".to_string(), 123, http::Protocol::HTTP),
+            }),
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op("get_same"), op("get_changed")];
+        let explore_ops = operations.clone();
+
+        // The reference responds with an item more to the changed operation
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let at_reference = matches!(
+                    ctx.current_target(),
+                    sut::Target::HTTP { config } if config.base_url == "bar"
+                );
+
+                let result: Vec<InvokeResult> = gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        let payload = if gen_op.name == "get_changed" && at_reference {
+                            "[1]"
+                        } else {
+                            "[]"
+                        };
+                        InvokeResult::new(gen_op.clone(), payload.to_string(), true, None)
+                    })
+                    .collect();
+
+                Some(result)
+            };
+
+        thread::spawn(move || {
+            sut::explore_differential(&ctx, operations, explore_ops, &invoke);
+        });
+
+        let mut messages: Vec<sut::Event> = vec![];
+        while let Ok(value) = exploration_log_rx.recv() {
+            messages.push(value)
+        }
+
+        let results = messages
+            .iter()
+            .filter_map(|m| match m {
+                sut::Event::Result { result } => Some(result.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                sut::ExplorationResult::NoExampleFound {
+                    operation: "get_same".to_string()
+                },
+                sut::ExplorationResult::TargetDivergence {
+                    operation: "get_changed".to_string(),
+                    difference: crate::differential::TargetDifference {
+                        step: 0,
+                        operation: "get_changed".to_string(),
+                        status: "-".to_string(),
+                        reference_status: "-".to_string(),
+                        paths: vec!["/0".to_string()],
+                    },
+                    sequence: Some(vec![GeneratedOperation {
+                        name: "get_changed".to_string(),
+                        parameters: vec![]
+                    }]),
+                    correlation_id: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn contract_captured_and_then_violated() {
+        let context = |behaviour_settings, contract, observer| sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(observer),
+            number_of_tests: 3,
+            min_length: 0,
+            max_length: 0,
+            behaviour_settings,
+            contract,
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method: HTTPMethod::GET,
+            }),
+        };
+        let operations = vec![op("get_person")];
+
+        fn respond(gen_ops: &[GeneratedOperation], payload: &str) -> Option<Vec<InvokeResult>> {
+            Some(
+                gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        InvokeResult::new(
+                            gen_op.clone(),
+                            payload.to_string(),
+                            true,
+                            Some(ResultMetaData::HTTP {
+                                url: "/".to_string(),
+                                status: http::HTTPStatus::OK,
+                                size: payload.len(),
+                                content_encoding: None,
+                                headers: vec![],
+                            }),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("contract.json");
+        let settings = |capture| crate::exploration_settings::BehaviourSettings::Contract {
+            contract_file: file.display().to_string(),
+            capture,
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = context(settings(true), None, Box::new(tx));
+        let (ops, explore_ops) = (operations.clone(), operations.clone());
+        thread::spawn(move || {
+            sut::explore_contract(&ctx, ops, explore_ops, &|_, _, gen_ops| {
+                respond(gen_ops, r#"{"name":"a"}"#)
+            });
+        });
+        while rx.recv().is_ok() {}
+
+        let contract = crate::contract::Contract::load(&file).unwrap();
+        assert!(contract.operations.contains_key("get_person"));
+
+        // The new version of the SUT renamed the field
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = context(settings(false), Some(contract), Box::new(tx));
+        let (ops, explore_ops) = (operations.clone(), operations.clone());
+        thread::spawn(move || {
+            sut::explore_contract(&ctx, ops, explore_ops, &|_, _, gen_ops| {
+                respond(gen_ops, r#"{"full_name":"a"}"#)
+            });
+        });
+
+        let mut results = vec![];
+        while let Ok(event) = rx.recv() {
+            if let sut::Event::Result { result } = event {
+                results.push(result);
+            }
+        }
+
+        match &results[..] {
+            [sut::ExplorationResult::ContractViolation { violations, .. }] => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].path, "/name");
+            }
+            other => panic!("Expected a contract violation, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn boundary_combinations_find_the_failing_value() {
+        use crate::amos::ParameterMetaData;
+        use qr_http_resource::http::HTTPParameterTarget;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(tx)),
+            number_of_tests: 3,
+            min_length: 0,
+            max_length: 0,
+            behaviour_settings: BehaviourSettings::new(&crate::behaviours::Behaviour::Boundary),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
+
+        let param = |name: &str, schema, required| Parameter {
+            name: name.to_string(),
+            schema,
+            required,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::Body,
+            }),
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let op = |name: &str, parameters| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: name.to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters,
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method: HTTPMethod::POST,
+            }),
+        };
+        let operations = vec![
+            op(
+                "post_person",
+                vec![
+                    param("age", Schema::Int, true),
+                    param("admin", Schema::Bool, false),
+                ],
+            ),
+            op("post_reset", vec![]),
+        ];
+
+        // The SUT crashes on the smallest age
+        fn invoke(
+            _: &sut::ExplorationContext,
+            _: &[Operation],
+            gen_ops: &[GeneratedOperation],
+        ) -> Option<Vec<InvokeResult>> {
+            Some(
+                gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        let crashes = gen_op.parameters.iter().any(|p| {
+                            p.value
+                                == ParameterValue::IntValue {
+                                    value: i64::MIN,
+                                    seed: 0,
+                                    active: false,
+                                }
+                        });
+                        let status = if crashes {
+                            http::HTTPStatus::InternalServerError
+                        } else {
+                            http::HTTPStatus::OK
+                        };
+                        InvokeResult::new(
+                            gen_op.clone(),
+                            "".to_string(),
+                            !crashes,
+                            Some(ResultMetaData::HTTP {
+                                url: "/".to_string(),
+                                status,
+                                size: 0,
+                                content_encoding: None,
+                                headers: vec![],
+                            }),
+                        )
+                    })
+                    .collect(),
+            )
         }
 
-        // Shrinking is done, take the smallest sequence and make it into actual ops
-        ctx.publish_event(Event::progress("Shrinking done".to_string()));
-        let (query_pos, op_seq) = &val.current();
-        let minimal_ops = synthesize_operations(*query_pos, op_seq);
+        let (ops, explore_ops) = (operations.clone(), operations.clone());
+        thread::spawn(move || {
+            sut::explore_boundary(&ctx, ops, explore_ops, &invoke);
+        });
+
+        let mut results = vec![];
+        while let Ok(event) = rx.recv() {
+            if let sut::Event::Result { result } = event {
+                results.push(result);
+            }
+        }
 
-        return Some(minimal_ops);
+        match &results[..] {
+            [sut::ExplorationResult::ResponseCheck {
+                operation,
+                minimal_sequence: Some(sequence),
+                ..
+            }, sut::ExplorationResult::NoExampleFound { operation: passed }] => {
+                assert_eq!(operation, "post_person");
+                assert_eq!(passed, "post_reset");
+                // The first failing combination, the admin flag is false
+                assert_eq!(sequence.len(), 1);
+                assert_eq!(sequence[0].parameters[0].value.as_int_value(), i64::MIN);
+                assert_eq!(
+                    sequence[0].parameters[1].value,
+                    ParameterValue::BoolValue {
+                        value: false,
+                        seed: 0,
+                        active: false
+                    }
+                );
+            }
+            other => panic!("Expected a failing combination, got: {:?}", other),
+        }
     }
 
-    // Didn't find any example
-    None
-}
+    #[test]
+    fn stale_cache_revalidated_after_a_mutation() {
+        use crate::cache::ClientCache;
+        use std::cell::RefCell;
+
+        // The SUT answers every revalidation with 304, also after a POST
+        let context = |cache: Option<RefCell<ClientCache>>, observer| sut::ExplorationContext {
+            http_send_fn: |_client, call| {
+                let revalidation = call.headers.iter().any(|(name, _)| name == "If-None-Match");
+                let status = match call.method {
+                    HTTPMethod::GET if revalidation => http::HTTPStatus::NotModified,
+                    HTTPMethod::GET | HTTPMethod::POST => http::HTTPStatus::OK,
+                    _ => http::HTTPStatus::NotFound,
+                };
+                Ok(http::HTTPResult {
+                    success: status == http::HTTPStatus::OK,
+                    status,
+                    size: 2,
+                    payload: "[]".to_string(),
+                    content_encoding: None,
+                    headers: vec![("etag".to_string(), "\"v1\"".to_string())],
+                })
+            },
+            observer: Some(observer),
+            // Enough tests for a POST to be generated
+            number_of_tests: 20,
+            cache,
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
 
-pub fn invoke_with_reqwest(
-    ctx: &ExplorationContext,
-    http_operation: HTTPCall,
-) -> Option<http::HTTPResult> {
-    reqwest_http::invoke_with_reqwest(&ctx.http_client, http_operation)
-}
+        let op = |name: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method,
+            }),
+        };
+        let operations = vec![
+            op("get_persons", HTTPMethod::GET),
+            op("post_person", HTTPMethod::POST),
+        ];
 
-pub fn invoke_dry(
-    _ctx: &ExplorationContext,
-    _http_operation: HTTPCall,
-) -> Option<http::HTTPResult> {
-    Some(http::HTTPResult {
-        status: http::HTTPStatus::OK,
-        payload: "[\"Fake result\"]".to_string(),
-        success: true,
-    })
-}
+        let explore_with = |cache| {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let mut ctx = context(cache, Box::new(tx));
+            let (ops, query_ops) = (operations.clone(), vec![operations[0].clone()]);
+            thread::spawn(move || {
+                sut::explore_stale_cache(&mut ctx, ops, &query_ops, &sut::invoke);
+            });
 
-pub fn invoke(
-    ctx: &ExplorationContext,
-    ops: &[Operation],
-    gen_ops: &[GeneratedOperation],
-) -> Option<Vec<InvokeResult>> {
-    let span = span!(Level::TRACE, "HTTP invoke span");
-    let _enter = span.enter();
+            let mut results = vec![];
+            while let Ok(event) = rx.recv() {
+                if let sut::Event::Result { result } = event {
+                    results.push(result);
+                }
+            }
+            results
+        };
 
-    let span_start_time = std::time::Instant::now();
-    ctx.publish_event(Event::InvocationSpanEnter {
-        enter: span_start_time,
-    });
+        // Without the cache there is nothing to revalidate
+        assert_eq!(explore_with(None), vec![]);
+
+        match &explore_with(Some(RefCell::new(ClientCache::new())))[..] {
+            [sut::ExplorationResult::StaleCache {
+                operation,
+                minimal_sequence: Some(sequence),
+                ..
+            }] => {
+                assert_eq!(operation, "get_persons");
+                let names: Vec<&str> = sequence.iter().map(|op| op.name.as_str()).collect();
+                assert_eq!(names, vec!["get_persons", "post_person", "get_persons"]);
+            }
+            other => panic!("Expected a stale cache, got: {:?}", other),
+        }
+    }
 
-    let mut results = Vec::with_capacity(gen_ops.len());
+    #[test]
+    fn conditional_update_with_a_stale_etag_overwrites() {
+        use crate::exploration_settings::BehaviourSettings;
+        use std::sync::atomic::{AtomicUsize, Ordering};
 
-    let config = match &ctx.target {
-        Target::HTTP { config } => config,
-    };
+        // Every update changes the ETag, but the If-Match of it is ignored
+        static VERSION: AtomicUsize = AtomicUsize::new(0);
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: |_client, call| {
+                let (status, version) = match call.method {
+                    HTTPMethod::GET => (http::HTTPStatus::OK, VERSION.load(Ordering::SeqCst)),
+                    HTTPMethod::PUT => (
+                        http::HTTPStatus::NoContent,
+                        VERSION.fetch_add(1, Ordering::SeqCst) + 1,
+                    ),
+                    _ => (http::HTTPStatus::NotFound, 0),
+                };
+                Ok(http::HTTPResult {
+                    success: status != http::HTTPStatus::NotFound,
+                    status,
+                    size: 2,
+                    payload: "{}".to_string(),
+                    content_encoding: None,
+                    headers: vec![("etag".to_string(), format!("\"v{}\"", version))],
+                })
+            },
+            observer: Some(Box::new(tx)),
+            number_of_tests: 10,
+            behaviour_settings: BehaviourSettings::ConditionalRequest {
+                accept_conflict: false,
+            },
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
 
-    for gen_op in gen_ops {
-        debug!(operation_name = gen_op.name,);
-        debug!("Invoke: {gen_op:#?}");
+        let op = |name: &str, url: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        };
+        let operations = vec![
+            op("get_item", "/item", HTTPMethod::GET),
+            op("put_item", "/item", HTTPMethod::PUT),
+            op("put_other", "/other", HTTPMethod::PUT),
+        ];
 
-        let (final_request, url) =
-            translate_generated_operation_to_http_call(config, ops, gen_op, &results)?;
-        trace!("{final_request:#?}");
+        let (ops, update_ops) = (operations.clone(), operations[1..].to_vec());
+        thread::spawn(move || {
+            sut::explore_conditional_request(&mut ctx, ops, &update_ops, &sut::invoke);
+        });
 
-        let request_start_time = std::time::Instant::now();
-        let http_resp = (ctx.http_send_fn)(ctx, final_request);
-        let request_duration = request_start_time.elapsed();
+        let mut results = vec![];
+        while let Ok(event) = rx.recv() {
+            if let sut::Event::Result { result } = event {
+                results.push(result);
+            }
+        }
 
-        if let Some(invoke_result) = http_resp {
-            let resp = translate_http_result(invoke_result, gen_op, url);
-            ctx.publish_event(Event::Invocation {
-                result: resp.clone(),
-                sut_invocation_duration: request_duration,
-            });
-            results.push(resp);
+        match &results[..] {
+            [sut::ExplorationResult::ConditionalRequest {
+                operation,
+                minimal_sequence: Some(sequence),
+                ..
+            }, sut::ExplorationResult::NoExampleFound { operation: unread }] => {
+                assert_eq!(operation, "put_item");
+                // Without a GET of its resource there is no ETag to replay
+                assert_eq!(unread, "put_other");
+                // The update is replayed after the generated operation
+                let names: Vec<&str> = sequence.iter().map(|op| op.name.as_str()).collect();
+                assert_eq!(names.len(), 5);
+                assert_eq!(names[..2], ["get_item", "put_item"]);
+                assert_eq!(names[3..], ["get_item", "put_item"]);
+            }
+            other => panic!("Expected a stale update, got: {:?}", other),
         }
     }
 
-    debug!("Invocation span exit");
-    ctx.publish_event(Event::InvocationSpanExit {
-        duration: span_start_time.elapsed(),
-    });
-    Some(results)
-}
+    #[test]
+    fn a_retry_with_the_same_key_creates_a_duplicate() {
+        use crate::behaviours::Behaviour;
+        use crate::idempotency;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        // The orders are created once for each key, the notes for every request
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        static CREATED: Mutex<Vec<(String, usize)>> = Mutex::new(Vec::new());
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut ctx = sut::ExplorationContext {
+            http_send_fn: |_client, call| {
+                let key = call
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == idempotency::DEFAULT_HEADER)
+                    .map(|(_, key)| key.clone())
+                    .unwrap_or_default();
+                let mut created = CREATED.lock().unwrap();
+                let id = match created.iter().find(|(created, _)| *created == key) {
+                    Some((_, id)) if call.url.ends_with("/orders") => *id,
+                    _ => {
+                        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+                        created.push((key, id));
+                        id
+                    }
+                };
+                Ok(http::HTTPResult {
+                    success: true,
+                    status: http::HTTPStatus::Created,
+                    size: 2,
+                    payload: format!("{{\"id\": {}}}", id),
+                    content_encoding: None,
+                    headers: vec![],
+                })
+            },
+            observer: Some(Box::new(tx)),
+            number_of_tests: 10,
+            behaviour_settings: BehaviourSettings::new(&Behaviour::IdempotentRetry),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
 
-#[cfg(test)]
-mod tests {
+        let op = |name: &str, url: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        };
+        let operations = vec![
+            op("post_order", "/orders", HTTPMethod::POST),
+            op("post_note", "/notes", HTTPMethod::POST),
+        ];
 
-    use std::thread;
+        let (ops, creation_ops) = (operations.clone(), operations.clone());
+        thread::spawn(move || {
+            sut::explore_idempotent_retry(&mut ctx, ops, &creation_ops, &sut::invoke);
+        });
 
-    use qr_http_resource::http::{self, HTTPMethod};
+        let mut results = vec![];
+        while let Ok(event) = rx.recv() {
+            if let sut::Event::Result { result } = event {
+                results.push(result);
+            }
+        }
 
-    use crate::{
-        amos::{
-            InvokeResult, Operation, OperationInfo, OperationMetaData, Parameter,
-            ParameterOwnership, Response, ResultMetaData, Schema,
-        },
-        amos_generation::{GeneratedOperation, GeneratedParameter, ParameterValue},
-        explore as sut,
-    };
+        match &results[..] {
+            [sut::ExplorationResult::NoExampleFound { operation: keyed }, sut::ExplorationResult::IdempotentRetry {
+                operation,
+                minimal_sequence: Some(sequence),
+                ..
+            }] => {
+                assert_eq!(keyed, "post_order");
+                assert_eq!(operation, "post_note");
+                // The retry is not a step of the sequence
+                assert_eq!(sequence.last().unwrap().name, "post_note");
+            }
+            other => panic!("Expected a duplicate, got: {:?}", other),
+        }
+    }
 
     #[test]
-    fn invoke_with_dry() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
-        };
-
-        let (exploration_log_tx, _exploration_log_rx) = std::sync::mpsc::channel();
-
-        let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+    fn chaos_repeated_requests_crash_the_sut() {
+        use crate::chaos::{ChaosFault, ChaosHooks, Inconsistency};
+        use crate::exploration_settings::{BehaviourSettings, ChaosSettings};
+
+        // The SUT fails on every POST, unless the POST is never sent
+        let context = |faults: ChaosSettings, observer| sut::ExplorationContext {
+            http_send_fn: |_client, call| {
+                let status = match call.method {
+                    HTTPMethod::POST => http::HTTPStatus::InternalServerError,
+                    _ => http::HTTPStatus::OK,
+                };
+                Ok(http::HTTPResult {
+                    status,
+                    size: 2,
+                    payload: "[]".to_string(),
+                    success: true,
+                    content_encoding: None,
+                    headers: vec![],
+                })
+            },
+            observer: Some(observer),
+            // Enough tests for a POST to be generated
+            number_of_tests: 20,
+            behaviour_settings: BehaviourSettings::Chaos {
+                faults: faults.clone(),
+            },
+            chaos: Some(ChaosHooks::new(faults, 1)),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
         };
 
-        let op = Operation {
+        let op = |name: &str, method| Operation {
             info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
-                url: "/persons".to_string(),
-                method: HTTPMethod::GET,
+                url: format!("/{}", name),
+                method,
             }),
         };
-
-        let generated = vec![
-            GeneratedOperation {
-                name: "get_persons".to_string(),
-                parameters: vec![],
-            },
-            GeneratedOperation {
-                name: "get_persons".to_string(),
-                parameters: vec![],
-            },
+        let operations = vec![
+            op("get_persons", HTTPMethod::GET),
+            op("post_person", HTTPMethod::POST),
         ];
 
-        let operations = vec![op.clone()];
+        let explore_with = |faults| {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let mut ctx = context(faults, Box::new(tx));
+            let (ops, query_ops) = (operations.clone(), vec![operations[0].clone()]);
+            thread::spawn(move || {
+                sut::explore_chaos(&mut ctx, ops, &query_ops, &sut::invoke);
+            });
 
-        let invoke_result = sut::invoke(&ctx, &operations, &generated);
+            let mut results = vec![];
+            while let Ok(event) = rx.recv() {
+                if let sut::Event::Result { result } = event {
+                    results.push(result);
+                }
+            }
+            results
+        };
 
+        // The POSTs are dropped, the SUT never fails
+        let results = explore_with(ChaosSettings {
+            drop_probability: 1.0,
+            ..ChaosSettings::new()
+        });
         assert_eq!(
-            invoke_result,
-            Some(vec![
-                InvokeResult {
-                    operation: GeneratedOperation {
-                        name: "get_persons".to_string(),
-                        parameters: vec![]
-                    },
-                    result: "[\"Fake result\"]".to_string(),
-                    success: true,
-                    meta_data: Some(ResultMetaData::HTTP {
-                        url: "http://foo:123/persons".to_string(),
-                        status: http::HTTPStatus::OK
-                    })
-                },
-                InvokeResult {
-                    operation: GeneratedOperation {
-                        name: "get_persons".to_string(),
-                        parameters: vec![]
-                    },
-                    result: "[\"Fake result\"]".to_string(),
-                    success: true,
-                    meta_data: Some(ResultMetaData::HTTP {
-                        url: "http://foo:123/persons".to_string(),
-                        status: http::HTTPStatus::OK
-                    })
-                }
-            ])
-        )
+            results,
+            vec![sut::ExplorationResult::NoExampleFound {
+                operation: "get_persons".to_string()
+            }]
+        );
+
+        let results = explore_with(ChaosSettings {
+            repeat_probability: 1.0,
+            ..ChaosSettings::new()
+        });
+        match &results[..] {
+            [sut::ExplorationResult::ChaosInconsistency {
+                inconsistency: Inconsistency::ServerError { operation, .. },
+                faults,
+                ..
+            }] => {
+                assert_eq!(operation, "post_person");
+                assert_eq!(faults[0].fault, ChaosFault::Repeat);
+            }
+            other => panic!("Expected a chaos inconsistency, got: {:?}", other),
+        }
     }
 
     #[test]
-    fn explore_response_inequality_no_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
-        };
-
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+    fn chaos_repeats_are_sent_with_the_key_of_the_request() {
+        use crate::chaos::ChaosHooks;
+        use crate::exploration_settings::ChaosSettings;
 
+        let (tx, rx) = std::sync::mpsc::channel();
         let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            // Respond with the idempotency key as sent
+            http_send_fn: |_client, call| {
+                let payload = call
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == "Idempotency-Key")
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default();
+
+                Ok(http::HTTPResult {
+                    status: http::HTTPStatus::OK,
+                    size: payload.len(),
+                    payload,
+                    success: true,
+                    content_encoding: None,
+                    headers: vec![],
+                })
+            },
+            observer: Some(Box::new(tx)),
+            correlation: sut::CorrelationIds::new(None, 1)
+                .with_idempotency_header(Some("Idempotency-Key".to_string())),
+            chaos: Some(ChaosHooks::new(
+                ChaosSettings {
+                    repeat_probability: 1.0,
+                    ..ChaosSettings::new()
+                },
+                1,
+            )),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
         };
 
-        let op = Operation {
+        let operations = vec![Operation {
             info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
+                name: "post_person".to_string(),
+                key: "operation/post_person".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
-                method: HTTPMethod::GET,
+                method: HTTPMethod::POST,
             }),
-        };
+        }];
+        let generated = vec![GeneratedOperation {
+            name: "post_person".to_string(),
+            parameters: vec![],
+        }];
+        sut::invoke(&ctx, &operations, &generated).unwrap();
+
+        drop(ctx);
+        let keys = rx
+            .iter()
+            .filter_map(|event| match event {
+                sut::Event::Invocation { result, .. } => Some(result.result),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(keys.len(), 2);
+        assert!(!keys[0].is_empty());
+        assert_eq!(keys[0], keys[1]);
+    }
 
-        let operations = vec![op.clone()];
-        let explore_ops = vec![op];
+    #[test]
+    fn role_matrix_per_operation() {
+        use crate::exploration_settings::{AuthSettings, CredentialSet};
+        use crate::role_matrix::RoleOutcome;
 
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-                // Setup equal result, i.e., no example found for this exploration
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let mut auth = AuthSettings::new();
+        auth.roles = vec![
+            CredentialSet::new("admin", Some(http::HTTPAuth::bearer("admin"))),
+            CredentialSet::new("anonymous", None),
+        ];
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 2,
+            min_length: 0,
+            max_length: 0,
+            auth,
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let op = |name: &str| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: format!("/{}", name),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let operations = vec![op("get_secret"), op("get_public")];
+        let explore_ops = operations.clone();
+
+        let invoke =
+            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let has_credentials = ctx.request_auth().is_some();
+
+                let result: Vec<InvokeResult> = gen_ops
+                    .iter()
+                    .map(|gen_op| {
+                        let success = gen_op.name == "get_public" || has_credentials;
+                        InvokeResult::new(gen_op.clone(), "[]".to_string(), success, None)
+                    })
+                    .collect();
 
                 Some(result)
             };
 
         thread::spawn(move || {
-            sut::explore_response_inequality(&ctx, operations, explore_ops, invoke);
+            sut::explore_role_matrix(&ctx, operations, explore_ops, &invoke);
         });
 
         let mut messages: Vec<sut::Event> = vec![];
@@ -1010,23 +6427,32 @@ mod tests {
             messages.push(value)
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
-
+        let outcomes = messages
+            .iter()
+            .filter_map(|m| match m {
+                sut::Event::Result {
+                    result: sut::ExplorationResult::RoleMatrix { outcomes, .. },
+                } => Some(outcomes.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let outcome = |role: &str, successes| RoleOutcome {
+            role: role.to_string(),
+            successes,
+            attempts: 2,
+        };
         assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::NoExampleFound {
-                    operation: "get_persons".to_string()
-                },
-            }),
-            result
+            outcomes,
+            vec![
+                vec![outcome("admin", 2), outcome("anonymous", 0)],
+                vec![outcome("admin", 2), outcome("anonymous", 2)],
+            ]
         );
     }
 
     #[test]
-    fn explore_response_inequality_with_example() {
+    fn security_audit_with_findings() {
         let target = sut::Target::HTTP {
             config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
         };
@@ -1034,26 +6460,21 @@ mod tests {
         let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
         let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
             http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 3,
+            ..sut::ExplorationContext::new(target)
         };
 
         let op = Operation {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
                 method: HTTPMethod::GET,
@@ -1063,41 +6484,30 @@ mod tests {
         let operations = vec![op.clone()];
         let explore_ops = vec![op];
 
+        // Every response allows any origin, and sets nosniff
         let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-                // Setup unequal result, i.e., example found for this exploration
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Totally not Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let result: Vec<InvokeResult> = vec![InvokeResult::new(
+                    gen_ops[0].clone(),
+                    "[]".to_string(),
+                    true,
+                    Some(ResultMetaData::HTTP {
+                        url: "/persons".to_string(),
+                        status: http::HTTPStatus::OK,
+                        size: 2,
+                        content_encoding: None,
+                        headers: vec![
+                            ("access-control-allow-origin".to_string(), "*".to_string()),
+                            ("x-content-type-options".to_string(), "nosniff".to_string()),
+                        ],
+                    }),
+                )];
 
                 Some(result)
             };
 
         thread::spawn(move || {
-            sut::explore_response_inequality(&ctx, operations, explore_ops, invoke);
+            sut::explore_security_audit(&ctx, operations, explore_ops, &invoke);
         });
 
         let mut messages: Vec<sut::Event> = vec![];
@@ -1105,33 +6515,39 @@ mod tests {
             messages.push(value)
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
+        let result = messages
+            .iter()
+            .find(|m| matches!(m, sut::Event::Result { .. }));
 
         assert_eq!(
             Some(&sut::Event::Result {
-                result: sut::ExplorationResult::ResponseInEquality {
+                result: sut::ExplorationResult::SecurityAudit {
                     operation: "get_persons".to_string(),
-                    minimal_sequence: Some(vec![
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        },
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        }
-                    ])
-                },
+                    findings: vec![crate::security_audit::SecurityFinding::CorsWildcard],
+                    sequence: Some(vec![GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![]
+                    }]),
+                    correlation_id: None
+                }
             }),
             result
         );
+        // Each test of the audit counts to the progress of the run
+        let completed: Vec<(u32, u32)> = messages
+            .iter()
+            .filter_map(|m| match m {
+                sut::Event::TestCompleted { completed, total } => Some((*completed, *total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(completed, vec![(1, 3), (2, 3), (3, 3), (3, 3)]);
     }
 
     #[test]
-    fn explore_response_equality_with_example() {
+    fn soak_reports_degrading_latency() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
         let target = sut::Target::HTTP {
             config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
         };
@@ -1139,26 +6555,21 @@ mod tests {
         let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
         let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
             http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 10,
+            ..sut::ExplorationContext::new(target)
         };
 
         let op = Operation {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
                 method: HTTPMethod::GET,
@@ -1168,41 +6579,25 @@ mod tests {
         let operations = vec![op.clone()];
         let explore_ops = vec![op];
 
+        // Each invocation is slower than the one before
+        static INVOCATIONS: AtomicU64 = AtomicU64::new(0);
         let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let slower = INVOCATIONS.fetch_add(1, Ordering::Relaxed);
 
-                Some(result)
+                Some(vec![InvokeResult::new(
+                    gen_ops[0].clone(),
+                    "[]".to_string(),
+                    true,
+                    None,
+                )
+                .with_duration(std::time::Duration::from_millis(
+                    10 + slower * 10,
+                ))])
             };
 
         thread::spawn(move || {
-            sut::explore_response_equality(&ctx, operations, explore_ops, invoke);
+            sut::explore_soak(&ctx, operations, explore_ops, &invoke);
         });
 
         let mut messages: Vec<sut::Event> = vec![];
@@ -1210,104 +6605,97 @@ mod tests {
             messages.push(value)
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
-
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::ResponseEquality {
-                    operation: "get_persons".to_string(),
-                    minimal_sequence: Some(vec![
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        },
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        }
-                    ])
-                },
-            }),
-            result
-        );
+        let result = messages
+            .iter()
+            .find_map(|m| match m {
+                sut::Event::Result { result } => Some(result),
+                _ => None,
+            })
+            .unwrap();
+
+        let sut::ExplorationResult::LatencyDegradation {
+            operation, trend, ..
+        } = result
+        else {
+            panic!("Expected a latency degradation, got {:?}", result);
+        };
+        assert_eq!(operation, "get_persons");
+        assert_eq!(trend.latency_ms, vec![15.0, 35.0, 55.0, 75.0, 95.0]);
     }
 
     #[test]
-    fn explore_response_equality_no_example() {
+    fn model_reports_the_divergent_step() {
         let target = sut::Target::HTTP {
             config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
         };
 
         let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
+        let model = crate::model::Model::parse(
+            "state persons = 0\n\
+             on post_person when 201: persons += 1\n\
+             on get_persons: length == persons",
+        )
+        .unwrap();
+
         let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
             http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 100,
+            model: Some(crate::model::ModelChecker::new(model)),
+            ..sut::ExplorationContext::new(target)
         };
 
-        let op = Operation {
+        let operation = |name: &str, method| Operation {
             info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
-                method: HTTPMethod::GET,
+                method,
             }),
         };
 
-        let operations = vec![op.clone()];
-        let explore_ops = vec![op];
+        let post = operation("post_person", HTTPMethod::POST);
+        let operations = vec![post.clone(), operation("get_persons", HTTPMethod::GET)];
+        let explore_ops = vec![post];
 
+        // A created person is never in the list of persons
         let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake rddddddesult\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "udddrl".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
+            |_ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
+                let results = gen_ops
+                    .iter()
+                    .map(|op| {
+                        let status = if op.name == "post_person" {
+                            http::HTTPStatus::Created
+                        } else {
+                            http::HTTPStatus::OK
+                        };
+                        InvokeResult::new(
+                            op.clone(),
+                            "[]".to_string(),
+                            true,
+                            Some(ResultMetaData::HTTP {
+                                url: "/persons".to_string(),
+                                status,
+                                size: 2,
+                                content_encoding: None,
+                                headers: vec![],
+                            }),
+                        )
+                    })
+                    .collect();
 
-                Some(result)
+                Some(results)
             };
 
         thread::spawn(move || {
-            sut::explore_response_equality(&ctx, operations, explore_ops, invoke);
+            sut::explore_model(&ctx, operations, explore_ops, &invoke);
         });
 
         let mut messages: Vec<sut::Event> = vec![];
@@ -1315,123 +6703,157 @@ mod tests {
             messages.push(value)
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
-
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::NoExampleFound {
-                    operation: "get_persons".to_string()
-                },
-            }),
-            result
-        );
+        let result = messages
+            .iter()
+            .find_map(|m| match m {
+                sut::Event::Result { result } => Some(result),
+                _ => None,
+            })
+            .unwrap();
+
+        let sut::ExplorationResult::ModelDivergence {
+            operation,
+            divergence: Some(divergence),
+            ..
+        } = result
+        else {
+            panic!("Expected a model divergence, got {:?}", result);
+        };
+        assert_eq!(operation, "post_person");
+        assert_eq!(divergence.step, 1);
+        assert_eq!(divergence.operation, "get_persons");
+        assert_eq!(divergence.expected, "length == 1");
+        assert_eq!(divergence.observed, "length 0");
     }
 
     #[test]
-    fn explore_state_mutation_with_example() {
+    fn invoke_concurrently_records_every_process() {
         let target = sut::Target::HTTP {
             config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
         };
 
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
-
-        let mut ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
-        };
-
-        let post_op = Operation {
-            info: OperationInfo {
-                name: "post_person".to_string(),
-                key: "operation/post_person".to_string(),
-            },
-            parameters: vec![
-                Parameter {
-                    name: "name".to_string(),
-                    schema: Schema::String,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-                Parameter {
-                    name: "age".to_string(),
-                    schema: Schema::Int8,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-            ],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::Reference("person".to_string()),
-            }],
-            meta_data: None,
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 10,
+            ..sut::ExplorationContext::new(target)
         };
 
-        let get_op = Operation {
+        let operation = |name: &str, url: &str, method| Operation {
             info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
-                url: "/persons".to_string(),
-                method: HTTPMethod::GET,
+                url: url.to_string(),
+                method,
             }),
         };
 
-        let operations = vec![post_op.clone()];
+        let operations = vec![
+            operation("get_persons", "/persons", HTTPMethod::GET),
+            operation("post_person", "/persons", HTTPMethod::POST),
+        ];
+        let gen_op = |name: &str| GeneratedOperation {
+            name: name.to_string(),
+            parameters: vec![],
+        };
+        let processes = vec![
+            vec![gen_op("get_persons"), gen_op("post_person")],
+            vec![gen_op("post_person")],
+        ];
 
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
+        let history = sut::invoke_concurrently(&ctx, &operations, &processes).unwrap();
+        drop(ctx);
 
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Another Fake result\"]".to_string(),
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().filter(|entry| entry.process == 0).count(), 2);
+        assert!(history.windows(2).all(|w| w[0].call <= w[1].call));
+        assert!(history.iter().all(|entry| entry.call <= entry.ret));
+
+        let invocations = exploration_log_rx
+            .iter()
+            .filter(|m| matches!(m, sut::Event::Invocation { .. }))
+            .count();
+        assert_eq!(invocations, 3);
+    }
+
+    #[test]
+    fn linearizability_reports_the_window() {
+        let target = sut::Target::HTTP {
+            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+        };
+
+        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(exploration_log_tx)),
+            number_of_tests: 10,
+            ..sut::ExplorationContext::new(target)
+        };
+
+        let operation = |name: &str, url: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method,
+            }),
+        };
+
+        let get = operation("get_persons", "/persons", HTTPMethod::GET);
+        let operations = vec![
+            get.clone(),
+            operation("post_person", "/persons", HTTPMethod::POST),
+            operation("get_orders", "/orders", HTTPMethod::GET),
+        ];
+        let explore_ops = vec![get];
+
+        // The first process creates a person the last read of the second
+        //  process misses, after the first read of the third saw it
+        let invoke = |_ctx: &sut::ExplorationContext,
+                      _ops: &[Operation],
+                      _processes: &[Vec<GeneratedOperation>]| {
+            let entry = |process, call, ret, name: &str, payload: &str| {
+                crate::linearizability::HistoryEntry {
+                    process,
+                    call: std::time::Duration::from_millis(call),
+                    ret: std::time::Duration::from_millis(ret),
+                    result: InvokeResult::new(
+                        GeneratedOperation {
+                            name: name.to_string(),
+                            parameters: vec![],
+                        },
+                        payload.to_string(),
                         true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
+                        None,
                     ),
-                ];
-
-                Some(result)
+                }
             };
 
-        let query_ops = vec![get_op.clone()];
+            Some(vec![
+                entry(0, 0, 1, "get_persons", "[]"),
+                entry(0, 2, 10, "post_person", "{}"),
+                entry(2, 3, 4, "get_persons", "[1]"),
+                entry(1, 5, 6, "get_persons", "[]"),
+            ])
+        };
 
         thread::spawn(move || {
-            sut::explore_state_mutation(&mut ctx, operations, &query_ops, invoke);
+            sut::explore_linearizability(&ctx, operations, explore_ops, invoke);
         });
 
         let mut messages: Vec<sut::Event> = vec![];
@@ -1439,654 +6861,1034 @@ mod tests {
             messages.push(value)
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
+        let result = messages
+            .iter()
+            .find_map(|m| match m {
+                sut::Event::Result { result } => Some(result),
+                _ => None,
+            })
+            .unwrap();
+
+        let sut::ExplorationResult::NonLinearizable {
+            operation,
+            windows,
+            processes,
+            ..
+        } = result
+        else {
+            panic!("Expected a non-linearizable history, got {:?}", result);
+        };
+        assert_eq!(operation, "get_persons");
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].operations.len(), 3);
+        assert_eq!(processes.len(), sut::CONCURRENT_PROCESSES);
+        // Only the operations of the collection are generated
+        assert!(processes.iter().flatten().all(|op| op.name != "get_orders"));
+    }
 
-        //assert_eq!(messages, vec![]);
+    #[test]
+    fn seeded_runners_generate_the_same_values() {
+        use proptest::strategy::{Strategy, ValueTree};
+
+        let strategy = proptest::collection::vec(0..1000u32, 10);
+
+        let generate = |seed| {
+            let runner = super::seeded_runner(seed);
+            // Bound, for the borrow to end before the runner is dropped
+            let value = strategy
+                .new_tree(&mut runner.borrow_mut())
+                .unwrap()
+                .current();
+            value
+        };
 
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::StateMutation {
-                    query_operation: "get_persons".to_string(),
-                    minimal_sequence: Some(vec![
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        },
-                        GeneratedOperation {
-                            name: "post_person".to_string(),
-                            parameters: vec![
-                                GeneratedParameter {
-                                    name: "name".to_string(),
-                                    value: ParameterValue::StringValue {
-                                        value: "".to_string(),
-                                        seed: 1,
-                                        active: false
-                                    },
-                                    ref_path: None
-                                },
-                                GeneratedParameter {
-                                    name: "age".to_string(),
-                                    value: ParameterValue::IntValue {
-                                        value: 0,
-                                        seed: 1,
-                                        active: false
-                                    },
-                                    ref_path: None
-                                }
-                            ]
-                        },
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        }
-                    ])
-                }
-            }),
-            result
-        );
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7), generate(8));
     }
 
     #[test]
-    fn explore_state_mutation_no_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+    fn error_budget_counts_consecutive_errors() {
+        let budget = sut::ErrorBudget::new(2);
+
+        assert!(!budget.record(false));
+        assert!(!budget.record(true));
+        assert!(!budget.record(false));
+        assert!(budget.record(false));
+        assert!(budget.is_exhausted());
+        // Exhausted once only
+        assert!(!budget.record(false));
+    }
+
+    #[test]
+    fn transport_errors_are_inconclusive() {
+        let op = GeneratedOperation {
+            name: "get_persons".to_string(),
+            parameters: vec![],
         };
+        let response = InvokeResult::new(op.clone(), "[]".to_string(), false, None);
+        let failure = InvokeResult::transport_failure(op, http::TransportError::Timeout);
 
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+        assert_eq!(
+            super::conclusive(Some(vec![response.clone()])),
+            Some(vec![response.clone()])
+        );
+        assert_eq!(super::conclusive(Some(vec![response, failure])), None);
+    }
 
-        let mut ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
-        };
+    #[test]
+    fn abort_after_transport_errors() {
+        let (tx, rx) = std::sync::mpsc::channel();
 
-        let post_op = Operation {
-            info: OperationInfo {
-                name: "post_person".to_string(),
-                key: "operation/post_person".to_string(),
-            },
-            parameters: vec![
-                Parameter {
-                    name: "name".to_string(),
-                    schema: Schema::String,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-                Parameter {
-                    name: "age".to_string(),
-                    schema: Schema::Int8,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-            ],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::Reference("person".to_string()),
-            }],
-            meta_data: None,
+        let ctx = sut::ExplorationContext {
+            http_send_fn: |_client, _call| Err(http::TransportError::Connect),
+            observer: Some(Box::new(tx)),
+            error_budget: sut::ErrorBudget::new(3),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    123,
+                    http::Protocol::HTTP,
+                ),
+            })
         };
 
-        let get_op = Operation {
+        let op = Operation {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
                 method: HTTPMethod::GET,
             }),
         };
-
-        let operations = vec![post_op.clone()];
-
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
-
-                Some(result)
+        let generated = vec![
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
             };
+            5
+        ];
 
-        let query_ops = vec![get_op.clone()];
+        // The requests after the budget is exhausted are not sent
+        let results = sut::invoke(&ctx, &[op], &generated).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|res| res.is_transport_error()));
+        assert!(ctx.is_stopped());
+        drop(ctx);
+
+        let aborts: Vec<sut::Event> = rx
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    sut::Event::Control {
+                        event: sut::ControlEvent::Aborted { .. }
+                    }
+                )
+            })
+            .collect();
+        assert_eq!(aborts.len(), 1);
+    }
 
-        thread::spawn(move || {
-            sut::explore_state_mutation(&mut ctx, operations, &query_ops, invoke);
+    #[test]
+    fn pause_while_the_target_is_unhealthy() {
+        use std::io::{BufRead, Write};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // The health endpoint is healthy, unhealthy once and healthy again
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            for status in ["200 OK", "503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
         });
 
-        let mut messages: Vec<sut::Event> = vec![];
-        while let Ok(value) = exploration_log_rx.recv() {
-            messages.push(value)
-        }
+        // The first request fails while the target is unhealthy
+        static FAILED: AtomicBool = AtomicBool::new(false);
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = sut::ExplorationContext {
+            http_send_fn: |client, call| {
+                if FAILED.swap(true, Ordering::Relaxed) {
+                    sut::invoke_dry(client, call)
+                } else {
+                    Err(http::TransportError::Connect)
+                }
+            },
+            observer: Some(Box::new(tx)),
+            error_budget: sut::ErrorBudget::new(1),
+            health: HealthCheck::new(&HealthSettings {
+                path: Some("/health".to_string()),
+                ..HealthSettings::new()
+            }),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "127.0.0.1".to_string(),
+                    port,
+                    http::Protocol::HTTP,
+                ),
+            })
+        };
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+        let generated = vec![GeneratedOperation {
+            name: "get_persons".to_string(),
+            parameters: vec![],
+        }];
+
+        // The failed request is sent again once the target is healthy, it
+        //  does not exhaust the budget
+        let results = sut::invoke(&ctx, &[op], &generated).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].is_transport_error());
+        assert!(!ctx.is_stopped());
+        drop(ctx);
+        server.join().unwrap();
+
+        let warnings: Vec<sut::Event> = rx
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    sut::Event::Log {
+                        message: sut::LogMessage {
+                            level: sut::LogLevel::Warning,
+                            ..
+                        }
+                    }
+                )
+            })
+            .collect();
+        assert_eq!(warnings.len(), 1);
+    }
 
-        //assert_eq!(messages, vec![]);
+    #[test]
+    fn a_paused_exploration_waits_until_resumed() {
+        use std::sync::atomic::Ordering;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let stop = sut::StopSignal::default();
+        let pause = sut::PauseSignal::new(true.into());
+
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(tx)),
+            stop: Some(stop.clone()),
+            pause: Some(pause.clone()),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    123,
+                    http::Protocol::HTTP,
+                ),
+            })
+        };
 
+        let resume = {
+            let pause = pause.clone();
+            thread::spawn(move || {
+                thread::sleep(std::time::Duration::from_millis(200));
+                pause.store(false, Ordering::Relaxed);
+            })
+        };
+        let paused = std::time::Instant::now();
+        assert!(!ctx.checkpoint());
+        assert!(paused.elapsed() >= std::time::Duration::from_millis(200));
+        assert!(ctx.paused.get() >= std::time::Duration::from_millis(200));
+        resume.join().unwrap();
+
+        // A stop ends the pause
+        pause.store(true, Ordering::Relaxed);
+        stop.store(true, Ordering::Relaxed);
+        assert!(ctx.checkpoint());
+        drop(ctx);
+
+        let messages: Vec<String> = rx
+            .iter()
+            .filter_map(|event| match event {
+                sut::Event::Log { message } => Some(message.message),
+                _ => None,
+            })
+            .collect();
         assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::NoExampleFound {
-                    operation: "get_persons".to_string()
-                },
-            }),
-            result
+            messages,
+            vec![
+                "Exploration paused",
+                "Exploration resumed",
+                "Exploration paused",
+                "Exploration resumed"
+            ]
         );
     }
 
     #[test]
-    fn explore_state_identity_with_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+    fn preflight_unreachable_target() {
+        // A port nothing listens on, once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let target = |port| sut::Target::HTTP {
+            config: http::HTTPConfiguration::new(
+                "127.0.0.1".to_string(),
+                port,
+                http::Protocol::HTTP,
+            ),
         };
 
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+        assert!(sut::preflight(&target(port)).is_ok());
+        drop(listener);
+        assert!(sut::preflight(&target(port)).is_err());
+    }
 
-        let mut ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
-        };
+    #[test]
+    fn correlation_ids_of_a_run() {
+        let ids = sut::CorrelationIds::new(Some("X-Request-Id".to_string()), 0xabc);
 
-        let post_op = Operation {
-            info: OperationInfo {
-                name: "post_person".to_string(),
-                key: "operation/post_person".to_string(),
+        assert_eq!(ids.header(), Some("X-Request-Id"));
+        assert_eq!(ids.id(1), "qr-00000abc-1");
+        assert_eq!(ids.id(12), "qr-00000abc-12");
+    }
+
+    #[test]
+    fn invoke_tags_the_sequence() {
+        let (tx, _rx) = std::sync::mpsc::channel();
+
+        let ctx = sut::ExplorationContext {
+            // Respond with the correlation header as sent
+            http_send_fn: |_client, call| {
+                let payload = call
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name == "X-Request-Id")
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default();
+
+                Ok(http::HTTPResult {
+                    status: http::HTTPStatus::OK,
+                    size: payload.len(),
+                    payload,
+                    success: true,
+                    content_encoding: None,
+                    headers: vec![],
+                })
             },
-            parameters: vec![
-                Parameter {
-                    name: "name".to_string(),
-                    schema: Schema::String,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-                Parameter {
-                    name: "age".to_string(),
-                    schema: Schema::Int8,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-            ],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::Reference("person".to_string()),
-            }],
-            meta_data: None,
+            observer: Some(Box::new(tx)),
+            correlation: sut::CorrelationIds::new(Some("X-Request-Id".to_string()), 1),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    123,
+                    http::Protocol::HTTP,
+                ),
+            })
         };
 
-        let get_op = Operation {
+        let op = Operation {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
                 method: HTTPMethod::GET,
             }),
         };
-
-        let operations = vec![get_op.clone(), post_op.clone()];
-
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Changed Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
-
-                Some(result)
+        let generated = vec![
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
             };
+            2
+        ];
+        let operations = vec![op];
 
-        let query_ops = vec![get_op.clone()];
-
-        thread::spawn(move || {
-            sut::explore_state_identity(&mut ctx, operations, &query_ops, invoke);
-        });
-
-        let mut messages: Vec<sut::Event> = vec![];
-        while let Ok(value) = exploration_log_rx.recv() {
-            messages.push(value)
+        // Every request of a sequence has the id of the sequence
+        let first = sut::invoke(&ctx, &operations, &generated).unwrap();
+        for res in &first {
+            assert_eq!(res.result, "qr-00000001-1");
+            assert_eq!(res.correlation_id.as_deref(), Some("qr-00000001-1"));
         }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
-
-        //assert_eq!(messages, vec![]);
-
         assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::StateIdentity {
-                    query_operation: "get_persons".to_string(),
-                    minimal_sequence: Some(vec![
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        },
-                        GeneratedOperation {
-                            name: "post_person".to_string(),
-                            parameters: vec![
-                                GeneratedParameter {
-                                    name: "name".to_string(),
-                                    value: ParameterValue::StringValue {
-                                        value: "".to_string(),
-                                        seed: 1,
-                                        active: false
-                                    },
-                                    ref_path: None
-                                },
-                                GeneratedParameter {
-                                    name: "age".to_string(),
-                                    value: ParameterValue::IntValue {
-                                        value: 0,
-                                        seed: 1,
-                                        active: false
-                                    },
-                                    ref_path: None
-                                }
-                            ]
-                        },
-                        GeneratedOperation {
-                            name: "get_persons".to_string(),
-                            parameters: vec![]
-                        }
-                    ])
-                }
-            }),
-            result
+            first[1].position,
+            Some(crate::amos::SequencePosition {
+                test_case: 1,
+                index: 1
+            })
         );
+
+        let second = sut::invoke(&ctx, &operations, &generated).unwrap();
+        assert_eq!(second[0].correlation_id.as_deref(), Some("qr-00000001-2"));
+        assert_eq!(second[0].position.map(|p| p.test_case), Some(2));
     }
 
     #[test]
-    fn explore_state_identity_no_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+    fn invoke_span_is_exited_when_the_sequence_is_discarded() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            observer: Some(Box::new(tx)),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
         };
 
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+        // Not in the specification, it can not be translated
+        let generated = vec![GeneratedOperation {
+            name: "get_persons".to_string(),
+            parameters: vec![],
+        }];
+        assert_eq!(sut::invoke(&ctx, &[], &generated), None);
+
+        drop(ctx);
+        let events: Vec<sut::Event> = rx.iter().collect();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                sut::Event::InvocationSpanEnter { .. },
+                sut::Event::InvocationSpanExit { .. }
+            ]
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_failed_observation_discards_the_sequence() {
+        use crate::state_observer::{ObservationMode, StateObservation, StateObserver};
 
+        let (tx, rx) = std::sync::mpsc::channel();
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+        let observation = |command: &str| StateObservation {
+            observer: StateObserver::Command {
+                command: command.to_string(),
+            },
+            mode: ObservationMode::InsteadOfQuery,
+        };
         let mut ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
             http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            observer: Some(Box::new(tx)),
+            query_operation: Some(op.clone()),
+            observation: Some(observation("exit 3")),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
+        let generated = vec![GeneratedOperation {
+            name: "get_persons".to_string(),
+            parameters: vec![],
+        }];
+        let operations = vec![op];
+
+        assert_eq!(sut::invoke(&ctx, &operations, &generated), None);
+
+        ctx.observation = Some(observation("echo '[]'"));
+        let results = sut::invoke(&ctx, &operations, &generated).unwrap();
+        assert_eq!(results[1].result, "[]");
+
+        drop(ctx);
+        let warnings = rx
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    sut::Event::Log {
+                        message: sut::LogMessage {
+                            level: sut::LogLevel::Warning,
+                            ..
+                        }
+                    }
+                )
+            })
+            .count();
+        assert_eq!(warnings, 1);
+    }
+
+    #[test]
+    fn replayed_sequences_are_sent_with_the_recorded_values() {
+        use crate::amos::ParameterMetaData;
+        use crate::amos_relations::{Relation, RelationInfo};
+        use crate::script::{ReplayScript, ScriptRecorder};
+        use qr_http_resource::http::HTTPParameterTarget;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replay.jsonl");
+        let (tx, _rx) = std::sync::mpsc::channel();
+
+        let mut ctx = sut::ExplorationContext {
+            // The names of the persons, any other request responds its URL
+            http_send_fn: |_client, call| {
+                let payload = if call.url.ends_with("/persons") {
+                    r#"["ada"]"#.to_string()
+                } else {
+                    call.url.clone()
+                };
+
+                Ok(http::HTTPResult {
+                    status: http::HTTPStatus::OK,
+                    size: payload.len(),
+                    payload,
+                    success: true,
+                    content_encoding: None,
+                    headers: vec![],
+                })
+            },
+            observer: Some(Box::new(tx)),
+            script: Some(std::sync::Arc::new(std::sync::Mutex::new(
+                ScriptRecorder::open(&path).unwrap(),
+            ))),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    123,
+                    http::Protocol::HTTP,
+                ),
+            })
         };
 
-        let post_op = Operation {
+        let op = |name: &str, url: &str, parameters| Operation {
             info: OperationInfo {
-                name: "post_person".to_string(),
-                key: "operation/post_person".to_string(),
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
             },
-            parameters: vec![
-                Parameter {
+            parameters,
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: url.to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+        let operations = vec![
+            op("get_persons", "/persons", vec![]),
+            op(
+                "get_person",
+                "/persons/{name}",
+                vec![Parameter {
                     name: "name".to_string(),
                     schema: Schema::String,
                     required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-                Parameter {
-                    name: "age".to_string(),
-                    schema: Schema::Int8,
-                    required: true,
-                    ownership: ParameterOwnership::Owned,
-                    meta_data: None,
-                },
-            ],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::Reference("person".to_string()),
-            }],
-            meta_data: None,
+                    ownership: ParameterOwnership::Dependency,
+                    meta_data: Some(ParameterMetaData::HTTP {
+                        target: HTTPParameterTarget::Path,
+                    }),
+                    examples: vec![],
+                    relation_hints: vec![],
+                    generator: None,
+                }],
+            ),
+        ];
+
+        // The name is a reference to the names the SUT responds
+        let generated = vec![
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+            GeneratedOperation {
+                name: "get_person".to_string(),
+                parameters: vec![GeneratedParameter {
+                    name: "name".to_string(),
+                    value: ParameterValue::Reference {
+                        active: true,
+                        idx: [0, 0],
+                        fallback: Box::new(ParameterValue::StringValue {
+                            value: "fallback".to_string(),
+                            seed: 1,
+                            active: true,
+                        }),
+                        relation: Relation::Response(RelationInfo {
+                            operation: "get_persons".to_string(),
+                            name: "successful operation".to_string(),
+                            schema: Schema::ArrayOfString,
+                            strength: 1,
+                            op_idx: 0,
+                            idx: 0,
+                            field: None,
+                            reasons: vec![],
+                        }),
+                    },
+                    ref_path: None,
+                }],
+            },
+        ];
+        sut::invoke(&ctx, &operations, &generated).unwrap();
+
+        // The script has the value the reference resolved to
+        let script = ReplayScript::load(&path).unwrap();
+        assert_eq!(script.sequences.len(), 1);
+        assert!(matches!(
+            &script.sequences[0][1].parameters[0].value,
+            ParameterValue::StringValue { value, .. } if value == "ada"
+        ));
+
+        ctx.script = None;
+        let replayed = sut::replay(&ctx, &operations, &script).unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0][1].result, "http://localhost:123/persons/ada");
+
+        let unknown = ReplayScript {
+            sequences: vec![vec![GeneratedOperation {
+                name: "delete_person".to_string(),
+                parameters: vec![],
+            }]],
         };
+        assert!(sut::replay(&ctx, &operations, &unknown).is_err());
+    }
+
+    thread_local! {
+        /// The sequences the scripted SUT was sent, as the names of their operations
+        static SUBMITTED: std::cell::RefCell<Vec<Vec<String>>> = Default::default();
+    }
+
+    /// The creates a delete is invoked after before the scripted SUT fails it
+    const CREATES_BEFORE_FAILURE: usize = 2;
+
+    /// A 500 for each 'delete_person' after enough 'post_person' in the sequence,
+    ///  a 200 for everything else
+    fn scripted_results(gen_ops: &[GeneratedOperation]) -> Vec<InvokeResult> {
+        let mut creates = 0;
+        gen_ops
+            .iter()
+            .map(|op| {
+                if op.name == "post_person" {
+                    creates += 1;
+                }
+                let status = if op.name == "delete_person" && creates >= CREATES_BEFORE_FAILURE {
+                    http::HTTPStatus::InternalServerError
+                } else {
+                    http::HTTPStatus::OK
+                };
+                InvokeResult::new(
+                    op.clone(),
+                    "[]".to_string(),
+                    status == http::HTTPStatus::OK,
+                    Some(ResultMetaData::HTTP {
+                        url: "/persons".to_string(),
+                        status,
+                        size: 2,
+                        content_encoding: None,
+                        headers: vec![],
+                    }),
+                )
+            })
+            .collect()
+    }
 
-        let get_op = Operation {
+    fn scripted_invoke(
+        _ctx: &sut::ExplorationContext,
+        _ops: &[Operation],
+        gen_ops: &[GeneratedOperation],
+    ) -> Option<Vec<InvokeResult>> {
+        SUBMITTED.with(|submitted| {
+            submitted
+                .borrow_mut()
+                .push(gen_ops.iter().map(|op| op.name.clone()).collect())
+        });
+        Some(scripted_results(gen_ops))
+    }
+
+    fn no_server_error(results: &[InvokeResult]) -> bool {
+        !results.iter().any(|res| {
+            matches!(
+                res.meta_data,
+                Some(ResultMetaData::HTTP {
+                    status: http::HTTPStatus::InternalServerError,
+                    ..
+                })
+            )
+        })
+    }
+
+    /// Explore the scripted SUT with sequences pinned to 'get_persons', from
+    ///  the seed. The minimal example and what the SUT was sent are returned.
+    fn explore_scripted(
+        seed: u64,
+        shrinking: ShrinkSettings,
+    ) -> (Option<sut::MinimalExample>, Vec<Vec<String>>) {
+        explore_scripted_until(seed, shrinking, |_| ())
+    }
+
+    /// Explores the scripted SUT, controlled at each failure, e.g. stopped
+    ///  with its stop signal
+    fn explore_scripted_until(
+        seed: u64,
+        shrinking: ShrinkSettings,
+        on_failure: impl Fn(&sut::ExplorationContext),
+    ) -> (Option<sut::MinimalExample>, Vec<Vec<String>>) {
+        let operation = |name: &str, method| Operation {
             info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
+            responses: vec![],
             meta_data: Some(OperationMetaData::HTTP {
                 url: "/persons".to_string(),
-                method: HTTPMethod::GET,
+                method,
             }),
         };
+        let query = operation("get_persons", HTTPMethod::GET);
+        let operations = vec![
+            query.clone(),
+            operation("post_person", HTTPMethod::POST),
+            operation("delete_person", HTTPMethod::DELETE),
+        ];
 
-        let operations = vec![get_op.clone(), post_op.clone()];
+        let ctx = sut::ExplorationContext {
+            http_send_fn: sut::invoke_dry,
+            number_of_tests: 100,
+            min_length: 0,
+            max_length: 8,
+            runner: sut::seeded_runner(seed),
+            shrinking,
+            stop: Some(Default::default()),
+            pause: Some(Default::default()),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
+        };
+        let invoke = |ctx: &sut::ExplorationContext, ops: &[Operation], gen_ops: &[_]| {
+            let results = scripted_invoke(ctx, ops, gen_ops);
+            if results
+                .as_ref()
+                .is_some_and(|results| !no_server_error(results))
+            {
+                on_failure(ctx);
+            }
+            results
+        };
 
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
+        SUBMITTED.with(|submitted| submitted.borrow_mut().clear());
+        let minimal = sut::explore(
+            &ctx,
+            operations.clone(),
+            &invoke,
+            super::gen_pinned_sequence(&ctx, &query, &operations),
+            |_ctx, res| res,
+            no_server_error,
+            crate::synthesize::synthesize_property_operations,
+        )
+        .unwrap();
 
-                let result: Vec<InvokeResult> = vec![
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                    InvokeResult::new(
-                        gen_ops[0].clone(),
-                        "[\"Fake result\"]".to_string(),
-                        true,
-                        Some(ResultMetaData::HTTP {
-                            url: "url".to_string(),
-                            status: http::HTTPStatus::OK,
-                        }),
-                    ),
-                ];
+        (minimal, SUBMITTED.with(|submitted| submitted.take()))
+    }
 
-                Some(result)
-            };
+    /// The index of the first failing sequence the scripted SUT was sent
+    fn first_failing(submitted: &[Vec<String>]) -> usize {
+        submitted
+            .iter()
+            .position(|names| {
+                let ops: Vec<GeneratedOperation> = names
+                    .iter()
+                    .map(|name| GeneratedOperation {
+                        name: name.clone(),
+                        parameters: vec![],
+                    })
+                    .collect();
+                !no_server_error(&scripted_results(&ops))
+            })
+            .unwrap()
+    }
 
-        let query_ops = vec![get_op.clone()];
+    #[test]
+    fn shrunk_sequences_are_minimal() {
+        for strategy in [ShrinkStrategy::LengthFirst, ShrinkStrategy::ValueFirst] {
+            let shrinking = ShrinkSettings {
+                strategy,
+                ..Default::default()
+            };
 
-        thread::spawn(move || {
-            sut::explore_state_identity(&mut ctx, operations, &query_ops, invoke);
-        });
+            let mut found = 0;
+            for seed in 0..32 {
+                let (Some((minimal, _, truncation)), _) = explore_scripted(seed, shrinking.clone())
+                else {
+                    continue;
+                };
+                found += 1;
+
+                assert_eq!(truncation, None);
+                assert!(!no_server_error(&scripted_results(&minimal)));
+                // The pinned operation stays, removing any other makes the check pass
+                for idx in 1..minimal.len() {
+                    let mut reduced = minimal.clone();
+                    reduced.remove(idx);
+                    assert!(
+                        no_server_error(&scripted_results(&reduced)),
+                        "Seed {}, {:?}: {:?} is not minimal",
+                        seed,
+                        strategy,
+                        minimal.iter().map(|op| &op.name).collect::<Vec<_>>()
+                    );
+                }
+                assert_eq!(minimal.len(), CREATES_BEFORE_FAILURE + 2);
+            }
 
-        let mut messages: Vec<sut::Event> = vec![];
-        while let Ok(value) = exploration_log_rx.recv() {
-            messages.push(value)
+            assert!(found > 0);
         }
+    }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
+    #[test]
+    fn shrinking_stops_at_the_budget() {
+        let shrinking = ShrinkSettings {
+            max_iterations: Some(2),
+            ..Default::default()
+        };
 
-        //assert_eq!(messages, vec![]);
+        let mut truncated = 0;
+        for seed in 0..32 {
+            let (Some((minimal, _, truncation)), submitted) =
+                explore_scripted(seed, shrinking.clone())
+            else {
+                continue;
+            };
 
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::NoExampleFound {
-                    operation: "get_persons".to_string()
-                },
-            }),
-            result
-        );
+            // The example is the last failing sequence, even if not minimal
+            assert!(!no_server_error(&scripted_results(&minimal)));
+            if let Some(truncation) = truncation {
+                truncated += 1;
+                assert_eq!(truncation.limit, ShrinkLimit::Iterations(2));
+                assert_eq!(truncation.iterations, 2);
+
+                assert!(submitted.len() - first_failing(&submitted) <= 3);
+            }
+        }
+
+        assert!(truncated > 0);
     }
 
     #[test]
-    fn response_check_with_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
-        };
+    fn a_stop_while_shrinking_keeps_the_failing_example() {
+        let mut found = 0;
+        for seed in 0..32 {
+            let stop = |ctx: &sut::ExplorationContext| {
+                let stop = ctx.stop.as_ref().unwrap();
+                stop.store(true, std::sync::atomic::Ordering::Relaxed);
+            };
+            let (Some((minimal, _, truncation)), submitted) =
+                explore_scripted_until(seed, ShrinkSettings::default(), stop)
+            else {
+                continue;
+            };
+            found += 1;
+
+            // Nothing is shrunk, the example is the first failing sequence
+            assert!(!no_server_error(&scripted_results(&minimal)));
+            assert_eq!(
+                truncation,
+                Some(sut::ShrinkTruncation {
+                    limit: ShrinkLimit::Stopped,
+                    iterations: 0,
+                })
+            );
+            assert_eq!(first_failing(&submitted), submitted.len() - 1);
+        }
 
-        let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
+        assert!(found > 0);
+    }
 
-        let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+    #[test]
+    fn the_time_paused_is_not_spent_shrinking() {
+        use std::sync::atomic::Ordering;
+
+        let shrinking = ShrinkSettings {
+            max_time_secs: Some(1),
+            ..Default::default()
+        };
+        // Paused for longer than the budget at the first failure
+        let paused = Cell::new(false);
+        let pause = |ctx: &sut::ExplorationContext| {
+            if !paused.replace(true) {
+                let pause = ctx.pause.clone().unwrap();
+                pause.store(true, Ordering::Relaxed);
+                thread::spawn(move || {
+                    thread::sleep(std::time::Duration::from_millis(1200));
+                    pause.store(false, Ordering::Relaxed);
+                });
+            }
         };
 
-        let op = Operation {
-            info: OperationInfo {
-                name: "get_persons".to_string(),
-                key: "operation/get_persons".to_string(),
-            },
-            parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
-            meta_data: Some(OperationMetaData::HTTP {
-                url: "/persons".to_string(),
-                method: HTTPMethod::GET,
-            }),
+        let seed = (0..32)
+            .find(|seed| explore_scripted(*seed, Default::default()).0.is_some())
+            .unwrap();
+        let (Some((_, _, truncation)), _) = explore_scripted_until(seed, shrinking, pause) else {
+            panic!("No failing example of seed {}", seed);
         };
 
-        let operations = vec![op.clone()];
-        let explore_ops = vec![op];
+        assert!(paused.get());
+        assert_eq!(truncation, None);
+    }
 
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
+    #[test]
+    fn shrinking_never_resubmits_a_sequence() {
+        for seed in 0..32 {
+            let (Some(_), submitted) = explore_scripted(seed, Default::default()) else {
+                continue;
+            };
 
-                let result: Vec<InvokeResult> = vec![InvokeResult::new(
-                    gen_ops[0].clone(),
-                    "[\"Fake result\"]".to_string(),
-                    true,
-                    Some(ResultMetaData::HTTP {
-                        url: "url".to_string(),
-                        status: http::HTTPStatus::InternalServerError,
-                    }),
-                )];
+            // Against a stateful SUT the same sequence may not fail twice, each
+            //  sequence from the first failing one on is sent once only
+            let first_failing = first_failing(&submitted);
+            let shrinking = &submitted[first_failing..];
+            for (idx, names) in shrinking.iter().enumerate() {
+                assert!(
+                    !shrinking[..idx].contains(names),
+                    "Seed {}: {:?} was submitted twice while shrinking",
+                    seed,
+                    names
+                );
+            }
+        }
+    }
 
-                Some(result)
-            };
+    #[cfg(unix)]
+    #[test]
+    fn repeated_sequences_are_invoked_again_unless_reused() {
+        let repeated = |repeated| ShrinkSettings {
+            repeated,
+            ..Default::default()
+        };
+        let reset = |command: &str| RepeatedSequence::ResetFirst {
+            command: command.to_string(),
+        };
 
-        thread::spawn(move || {
-            sut::response_check(&ctx, operations, explore_ops, invoke);
-        });
+        let mut resubmitted = 0;
+        // Each reset runs a shell, fewer seeds than the other shrinking tests
+        for seed in 0..4 {
+            let (reused, reused_submitted) = explore_scripted(seed, Default::default());
+            let (reinvoked, reinvoked_submitted) =
+                explore_scripted(seed, repeated(RepeatedSequence::Reinvoke));
+            if reinvoked.is_none() {
+                continue;
+            }
 
-        let mut messages: Vec<sut::Event> = vec![];
-        while let Ok(value) = exploration_log_rx.recv() {
-            messages.push(value)
-        }
+            let shrinking = &reinvoked_submitted[first_failing(&reinvoked_submitted)..];
+            if (1..shrinking.len()).any(|idx| shrinking[..idx].contains(&shrinking[idx])) {
+                resubmitted += 1;
+            }
 
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
-        });
+            // A successful reset is followed by the invocation, a failed one
+            //  reuses the check
+            let (reset_first, reset_submitted) = explore_scripted(seed, repeated(reset("true")));
+            assert_eq!(reset_first, reinvoked);
+            assert_eq!(reset_submitted, reinvoked_submitted);
 
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::ResponseCheck {
-                    operation: "get_persons".to_string(),
-                    minimal_sequence: Some(vec![GeneratedOperation {
-                        name: "get_persons".to_string(),
-                        parameters: vec![]
-                    }])
-                }
-            }),
-            result
-        );
+            let (failed_reset, failed_submitted) =
+                explore_scripted(seed, repeated(reset("exit 1")));
+            assert_eq!(failed_reset, reused);
+            assert_eq!(failed_submitted, reused_submitted);
+        }
+
+        assert!(resubmitted > 0);
     }
 
     #[test]
-    fn response_check_with_no_example() {
-        let target = sut::Target::HTTP {
-            config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
-        };
+    fn a_failing_generator_fails_the_operation() {
+        use proptest::strategy::Strategy;
 
         let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
-
         let ctx = sut::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
             http_send_fn: sut::invoke_dry,
-            target,
-            query_operation: None,
-            tx: Some(exploration_log_tx),
-            number_of_tests: 1,
-            min_length: 1,
-            max_length: 1,
+            observer: Some(Box::new(exploration_log_tx)),
+            ..sut::ExplorationContext::new(sut::Target::HTTP {
+                config: http::HTTPConfiguration::new("foo".to_string(), 123, http::Protocol::HTTP),
+            })
         };
 
+        // Every sequence is rejected, the generator gives up
+        let generator = proptest::strategy::Just((0, vec![])).prop_filter("never", |_| false);
+        let error = sut::explore(
+            &ctx,
+            vec![],
+            &scripted_invoke,
+            generator,
+            |_ctx, res| res,
+            no_server_error,
+            crate::synthesize::synthesize_property_operations,
+        )
+        .unwrap_err();
+        assert!(matches!(error, crate::error::ExploreError::Generation(_)));
+
         let op = Operation {
             info: OperationInfo {
                 name: "get_persons".to_string(),
                 key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
             },
             parameters: vec![],
-            responses: vec![Response {
-                name: "successful operation".to_string(),
-                schema: Schema::ArrayOfRefItems("person".to_string()),
-            }],
-            meta_data: Some(OperationMetaData::HTTP {
-                url: "/persons".to_string(),
-                method: HTTPMethod::GET,
-            }),
+            responses: vec![],
+            meta_data: None,
         };
-
-        let operations = vec![op.clone()];
-        let explore_ops = vec![op];
-
-        let invoke =
-            |ctx: &sut::ExplorationContext, _ops: &[Operation], gen_ops: &[GeneratedOperation]| {
-                ctx.publish_event(sut::Event::Log {
-                    message: sut::LogMessage {
-                        level: sut::LogLevel::Info,
-                        message: "Invoke".to_string(),
-                    },
-                });
-
-                let result: Vec<InvokeResult> = vec![InvokeResult::new(
-                    gen_ops[0].clone(),
-                    "[\"Fake result\"]".to_string(),
-                    true,
-                    Some(ResultMetaData::HTTP {
-                        url: "url".to_string(),
-                        status: http::HTTPStatus::OK,
-                    }),
-                )];
-
-                Some(result)
-            };
-
-        thread::spawn(move || {
-            sut::response_check(&ctx, operations, explore_ops, invoke);
-        });
-
-        let mut messages: Vec<sut::Event> = vec![];
-        while let Ok(value) = exploration_log_rx.recv() {
-            messages.push(value)
-        }
-
-        let result = messages.iter().find(|m| match m {
-            sut::Event::Result { .. } => true,
-            _ => false,
+        // The behaviour publishes the failed result with its other results
+        let mut error = Some(error);
+        let results = sut::explore_each(&ctx, &[op], "Failing", |ctx, op| {
+            ctx.fail(&op.info.name, error.take().unwrap())
         });
+        let [result] = &results[..] else {
+            panic!("Expected one result, got {:?}", results);
+        };
+        assert!(matches!(
+            result,
+            sut::ExplorationResult::Failed { operation, .. } if operation == "get_persons"
+        ));
+        drop(ctx);
 
-        assert_eq!(
-            Some(&sut::Event::Result {
-                result: sut::ExplorationResult::NoExampleFound {
-                    operation: "get_persons".to_string()
-                },
-            }),
-            result
-        );
+        let events: Vec<sut::Event> = exploration_log_rx.iter().collect();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            sut::Event::Log { message } if message.level == sut::LogLevel::Error
+        )));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, sut::Event::Result { result: r } if r == result)));
     }
 }
@@ -0,0 +1,157 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use crate::exploration_settings::HealthSettings;
+use crate::explore::{self, Target};
+use crate::http_translation;
+
+/// Probes the health endpoint of the target, at most once per interval
+///  unless asked to probe at once
+#[derive(Debug)]
+pub struct HealthCheck {
+    path: String,
+    interval: Duration,
+    max_pause: Duration,
+    last_probe: Cell<Option<Instant>>,
+}
+
+impl HealthCheck {
+    /// How often an unhealthy target is probed again
+    pub const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// The check of the settings, if they have a health endpoint
+    pub fn new(settings: &HealthSettings) -> Option<Self> {
+        Some(Self {
+            path: settings.path.clone()?,
+            interval: Duration::from_secs(settings.interval_secs),
+            max_pause: Duration::from_secs(settings.max_pause_secs),
+            last_probe: Cell::new(None),
+        })
+    }
+
+    pub fn max_pause(&self) -> Duration {
+        self.max_pause
+    }
+
+    /// If the interval passed since the last probe
+    pub fn is_due(&self) -> bool {
+        self.last_probe
+            .get()
+            .is_none_or(|last| last.elapsed() >= self.interval)
+    }
+
+    pub fn probe(&self, target: &Target) -> Result<(), String> {
+        self.last_probe.set(Some(Instant::now()));
+        probe(target, &self.path)
+    }
+}
+
+/// The target is healthy if the path responds without a server error. The
+///  path is relative to the base path of the target, as the operations are.
+pub fn probe(target: &Target, path: &str) -> Result<(), String> {
+    probe_within(target, path, explore::PREFLIGHT_TIMEOUT)
+}
+
+fn probe_within(target: &Target, path: &str, timeout: Duration) -> Result<(), String> {
+    let config = match target {
+        Target::HTTP { config } => config,
+        // Only the broker of a queue target can be probed
+        Target::Queue { .. } | Target::CoAP { .. } => return explore::preflight(target),
+    };
+    let url = http_translation::target_url(config, path);
+    let response = reqwest::blocking::Client::new()
+        .get(&url)
+        .timeout(timeout)
+        .send()
+        .map_err(|e| format!("{}: {}", url, e))?;
+
+    if response.status().is_server_error() {
+        Err(format!("{} responded {}", url, response.status()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use qr_http_resource::http;
+
+    use crate::explore::Target;
+    use crate::health as sut;
+
+    fn target(port: u16) -> Target {
+        Target::HTTP {
+            config: http::HTTPConfiguration::new(
+                "127.0.0.1".to_string(),
+                port,
+                http::Protocol::HTTP,
+            )
+            .with_base_path(Some("/api".to_string())),
+        }
+    }
+
+    /// Respond to one request with the status, the request line is sent back
+    fn serve(status: &'static str) -> (u16, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            )
+            .unwrap();
+            tx.send(request_line.trim().to_string()).unwrap();
+        });
+
+        (port, rx)
+    }
+
+    #[test]
+    fn healthy_below_the_base_path() {
+        let (port, requests) = serve("200 OK");
+
+        assert_eq!(sut::probe(&target(port), "/health"), Ok(()));
+        assert_eq!(requests.recv().unwrap(), "GET /api/health HTTP/1.1");
+    }
+
+    #[test]
+    fn unhealthy_on_a_server_error() {
+        let (port, _requests) = serve("503 Service Unavailable");
+
+        let error = sut::probe(&target(port), "/health").unwrap_err();
+        assert!(error.contains("503"), "{}", error);
+    }
+
+    #[test]
+    fn unhealthy_without_a_response_in_time() {
+        // Accepts the connection, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        thread::spawn(move || {
+            let _stream = listener.accept().unwrap();
+            let _ = done_rx.recv();
+        });
+
+        let probed = sut::probe_within(&target(port), "/health", Duration::from_millis(200));
+        assert!(probed.is_err());
+        drop(done_tx);
+    }
+}
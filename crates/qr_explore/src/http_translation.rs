@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use qr_http_resource::http;
 use qr_http_resource::http::HTTPCall;
 use qr_http_resource::http::HTTPParameters;
@@ -15,12 +16,28 @@ use crate::amos::OperationMetaData;
 use crate::amos::Parameter;
 use crate::amos::ParameterMetaData;
 use crate::amos::Schema;
+use crate::amos_generation;
 use crate::amos_generation::GeneratedOperation;
 use crate::amos_generation::{GeneratedParameter, ParameterValue};
 use crate::amos_relations::Relation;
 use crate::amos_relations::RelationInfo;
+use crate::value_provider::ValueProvider;
 use qr_http_resource::http::{HTTPConfiguration, HTTPParameterTarget};
 
+/// The characters of a value that are sent as they are in a path segment or a
+///  query, the unreserved characters of URLs
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The value percent-encoded as UTF-8, a '/' or '?' of the value does not
+///  change the URL
+fn url_encoded(value: &str) -> String {
+    utf8_percent_encode(value, UNRESERVED).to_string()
+}
+
 pub fn parameters_to_form_data(params: &[GeneratedParameter]) -> HashMap<String, String> {
     let mut form_data = HashMap::new();
 
@@ -35,7 +52,10 @@ pub fn parameters_to_form_data(params: &[GeneratedParameter]) -> HashMap<String,
             ParameterValue::File { value, .. } => {
                 form_data.insert(p.name.clone(), value.to_string());
             }
-            _ => panic!("Unsupported parameter to form data conversion: {p:#?}"),
+            _ => warn!(
+                p.name,
+                "Unsupported parameter to form data conversion, not sent"
+            ),
         }
     }
 
@@ -46,21 +66,19 @@ pub fn parameters_to_json_str(params: &[GeneratedParameter]) -> String {
     let mut json_str = "{".to_owned();
 
     for p in params {
+        let value = match &p.value {
+            ParameterValue::StringValue { value, .. } => format!("\"{value}\""),
+            ParameterValue::IntValue { value, .. } => value.to_string(),
+            _ => {
+                warn!(p.name, "Unsupported parameter to json conversion, not sent");
+                continue;
+            }
+        };
         json_str.push('"');
         json_str.push_str(&p.name);
         json_str.push('"');
         json_str.push(':');
-        match &p.value {
-            ParameterValue::StringValue { value, .. } => {
-                json_str.push('"');
-                json_str.push_str(value);
-                json_str.push('"');
-            }
-            ParameterValue::IntValue { value, .. } => {
-                json_str.push_str(&value.to_string());
-            }
-            _ => panic!("Unsupported parameter to json conversion: {p:#?}"),
-        }
+        json_str.push_str(&value);
     }
 
     json_str.push('}');
@@ -82,37 +100,71 @@ fn parse_response(
         debug!("Refered response was unsuccessfull, using fallback value");
         return fallback.clone();
     }
+
+    if let Some(field) = &info.field {
+        return response_field_value(param, &result.result, field)
+            .unwrap_or_else(|| fallback.clone());
+    }
     // TODO: would be nice if we could leverage the response schema
-    let content: Value =
-        serde_json::from_str(&result.result).expect("Could not parse result as JSON");
+    let Ok(content) = serde_json::from_str::<Value>(&result.result) else {
+        debug!("Refered response is not JSON, using fallback value");
+        return fallback.clone();
+    };
 
     debug!("JSON: {content:#?}");
 
-    match content {
-        Value::Array(ref items) => {
-            if items.is_empty() {
-                return fallback.clone();
-            }
-            // TODO: use a seed to select value
-            let candidate = &items[0];
-
-            match candidate {
-                Value::String(s) => {
-                    if param.schema == Schema::String {
-                        return ParameterValue::StringValue {
-                            value: s.clone(),
-                            seed: 0,
-                            active: false,
-                        };
-                    }
-                }
-                _ => panic!("Unsupported JSON value"),
-            }
+    // TODO: use a seed to select value
+    match content.as_array().and_then(|items| items.first()) {
+        Some(Value::String(s)) if param.schema == Schema::String => ParameterValue::StringValue {
+            value: s.clone(),
+            seed: 0,
+            active: false,
+        },
+        _ => {
+            debug!("Refered response has no value of the parameter, using fallback value");
+            fallback.clone()
         }
-        _ => panic!("Unsupported content"),
     }
+}
+
+/// The value the reference refers to in the results, the fallback of the
+///  reference if the result is not among them
+fn referred_value(
+    param: &Parameter,
+    results: &[InvokeResult],
+    info: &RelationInfo,
+    fallback: &ParameterValue,
+) -> ParameterValue {
+    match results.get(info.op_idx) {
+        Some(result) => parse_response(param, result, info, fallback),
+        None => fallback.clone(),
+    }
+}
+
+/// The value of the field of a JSON response, as a value of the parameter. A
+///  key of an array is looked up in its first item, e.g. `id` of `[{"id": 1}]`.
+fn response_field_value(param: &Parameter, payload: &str, field: &str) -> Option<ParameterValue> {
+    let content: Value = serde_json::from_str(payload).ok()?;
+
+    let mut value = &content;
+    for key in field.split('.') {
+        value = match value {
+            Value::Array(items) => match key.parse::<usize>() {
+                Ok(idx) => items.get(idx)?,
+                Err(_) => items.first()?.get(key)?,
+            },
+            Value::Object(_) => value.get(key)?,
+            _ => return None,
+        };
+    }
+
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(_) | Value::Bool(_) => value.to_string(),
+        _ => return None,
+    };
 
-    fallback.clone()
+    amos_generation::example_value(&param.schema, &text)
 }
 
 pub fn translate_generated_operation_to_http_call(
@@ -122,9 +174,13 @@ pub fn translate_generated_operation_to_http_call(
     results: &[InvokeResult],
 ) -> Option<(HTTPCall, String)> {
     // TODO: Fix this meta crap
-    let matching_op = ops.iter().find(|op| op.info.name == gen_op.name);
-
-    let amos_op = matching_op.unwrap();
+    let Some(amos_op) = ops.iter().find(|op| op.info.name == gen_op.name) else {
+        warn!(
+            gen_op.name,
+            "Operation is not in the specification, disscarded"
+        );
+        return None;
+    };
     let op_meta = amos_op.meta_data.clone();
 
     let http_operation = translate_operation(config, gen_op, &op_meta, amos_op, results)?;
@@ -135,6 +191,48 @@ pub fn translate_generated_operation_to_http_call(
     Some((http_operation, url))
 }
 
+/// The operation with its references to responses replaced by the values
+///  they refer to in the results, the values the requests are sent with
+pub fn resolve_response_references(
+    ops: &[Operation],
+    gen_op: &GeneratedOperation,
+    results: &[InvokeResult],
+) -> GeneratedOperation {
+    let amos_op = ops.iter().find(|op| op.info.name == gen_op.name);
+
+    let parameters = gen_op
+        .parameters
+        .iter()
+        .map(|p| {
+            let ParameterValue::Reference {
+                fallback,
+                relation: Relation::Response(info),
+                ..
+            } = &p.value
+            else {
+                return p.clone();
+            };
+
+            let amos_param =
+                amos_op.and_then(|op| op.parameters.iter().find(|ap| ap.name == p.name));
+            let value = match amos_param {
+                Some(amos_param) => referred_value(amos_param, results, info, fallback),
+                None => *fallback.clone(),
+            };
+            GeneratedParameter {
+                name: p.name.clone(),
+                value,
+                ref_path: p.ref_path.clone(),
+            }
+        })
+        .collect();
+
+    GeneratedOperation {
+        name: gen_op.name.clone(),
+        parameters,
+    }
+}
+
 pub fn translate_operation(
     config: &HTTPConfiguration,
     gen_op: &GeneratedOperation,
@@ -142,25 +240,122 @@ pub fn translate_operation(
     amos_op: &Operation,
     results: &[InvokeResult],
 ) -> Option<HTTPCall> {
-    match op_meta.clone().unwrap() {
-        OperationMetaData::HTTP { url, method } => {
-            if let Some(call) =
-                translate_parameters(&gen_op.parameters, &amos_op.parameters, results, &url)
-            {
-                let url = format!(
-                    "{}{}:{}{}",
-                    config.protocol, config.base_url, config.port, call.url
-                );
-                Some(HTTPCall {
-                    url,
-                    method,
-                    parameters: call,
-                })
-            } else {
-                // Could not create a valid URL, consider the SEQ as broken
-                warn!(gen_op.name, "Disscarded operation");
-                None
+    match op_meta.clone() {
+        None => {
+            warn!(gen_op.name, "Operation without HTTP meta data, disscarded");
+            None
+        }
+        Some(OperationMetaData::HTTP { url, method }) => {
+            let url = config.paths.template(&url);
+            let translate = |parameters: &[GeneratedParameter]| {
+                let call = translate_parameters(parameters, &amos_op.parameters, results, &url);
+                if call.is_none() {
+                    // Could not create a valid URL, consider the SEQ as broken
+                    warn!(gen_op.name, "Disscarded operation");
+                }
+                call
+            };
+            let provided = |name: &str| provider(config.providers.parameter(&gen_op.name, name)?);
+
+            let mut parameters = gen_op.parameters.clone();
+            pin_parameters(&mut parameters, amos_op, |name| {
+                config
+                    .pinning
+                    .parameter(&gen_op.name, name)
+                    .map(str::to_string)
+            });
+            pin_parameters(&mut parameters, amos_op, |name| {
+                provided(name)
+                    .filter(|provider| !provider.signs_body())
+                    .map(|provider| provider.value(None))
+            });
+            let mut call = translate(&parameters)?;
+
+            let body = call
+                .body
+                .as_ref()
+                .map(|body| serde_json::to_string(body).unwrap_or_default());
+            let signs = |name: &str| provided(name).filter(ValueProvider::signs_body);
+            if amos_op.parameters.iter().any(|p| signs(&p.name).is_some()) {
+                pin_parameters(&mut parameters, amos_op, |name| {
+                    signs(name).map(|provider| provider.value(body.as_deref()))
+                });
+                // The body that was signed is the one sent
+                let signed = call.body.take();
+                call = translate(&parameters)?;
+                call.body = signed;
             }
+
+            let mut headers = config.pinning.headers(&gen_op.name);
+            headers.extend(
+                config
+                    .providers
+                    .headers(&gen_op.name)
+                    .into_iter()
+                    .filter_map(|(name, expression)| {
+                        provider(&expression)
+                            .map(|provider| (name, provider.value(body.as_deref())))
+                    }),
+            );
+
+            Some(HTTPCall {
+                url: target_url(config, &call.url),
+                method,
+                parameters: call,
+                headers,
+            })
+        }
+    }
+}
+
+/// The URL of the path relative to the base path of the target, normalized
+///  by the settings of the target
+pub fn target_url(config: &HTTPConfiguration, path: &str) -> String {
+    let base_path = config.base_path.as_deref().unwrap_or("");
+    let path = config.paths.template(base_path.trim_end_matches('/')) + path;
+    format!(
+        "{}{}:{}{}",
+        config.protocol,
+        config.base_url,
+        config.port,
+        config.paths.normalize(&path)
+    )
+}
+
+/// The provider of the expression, the expressions are validated when they
+///  are configured
+fn provider(expression: &str) -> Option<ValueProvider> {
+    ValueProvider::parse(expression)
+        .map_err(|e| warn!(expression, "Invalid value provider: {e}"))
+        .ok()
+}
+
+/// The parameters with the values of the names, a parameter with a value
+///  that was not generated is sent as well
+fn pin_parameters(
+    parameters: &mut Vec<GeneratedParameter>,
+    amos_op: &Operation,
+    value_of: impl Fn(&str) -> Option<String>,
+) {
+    for amos_param in &amos_op.parameters {
+        let Some(pinned) = value_of(&amos_param.name) else {
+            continue;
+        };
+        // A value that is not of the schema is sent as it is
+        let value = amos_generation::example_value(&amos_param.schema, &pinned).unwrap_or(
+            ParameterValue::StringValue {
+                value: pinned,
+                seed: 0,
+                active: false,
+            },
+        );
+        match parameters.iter_mut().find(|p| p.name == amos_param.name) {
+            Some(parameter) => parameter.value = value,
+            None => parameters.push(GeneratedParameter {
+                name: amos_param.name.clone(),
+                value,
+                ref_path: None,
+            }),
         }
     }
 }
@@ -177,6 +372,9 @@ pub fn translate_http_result(
         Some(amos::ResultMetaData::HTTP {
             url,
             status: http_response.status,
+            size: http_response.size,
+            content_encoding: http_response.content_encoding,
+            headers: http_response.headers,
         }),
     )
 }
@@ -199,181 +397,222 @@ pub fn translate_parameters(
     for p in params {
         let amos_param = amos_params.iter().find(|ap| ap.name == p.name);
         debug!("AMOS param: {:#?}", amos_param);
-        match amos_param.unwrap().meta_data.clone().unwrap() {
-            ParameterMetaData::HTTP { target } => match target {
-                HTTPParameterTarget::Body => {
-                    debug!("Body param: {:?}", p);
-                    match &p.value {
-                        ParameterValue::Reference{
-                            fallback,
-                            relation,
-                            ..
-                        } => {
-                            match relation {
-                                Relation::Response(info) => {
-                                    let ref_result = &results[info.op_idx];
-                                    let ref_parameter = parse_response(amos_param.unwrap(), ref_result, info, fallback);
-                                    body_params.push(
-                                        GeneratedParameter { name: p.name.clone(), value: ref_parameter, ref_path: p.ref_path.clone() }
-                                    )
-                                }
-                                Relation::Parameter(info) => panic!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}")
-                            }
-                        }
-                        _ => body_params.push(p.clone())
-                    }
-                }
-                HTTPParameterTarget::Path => {
-                    debug!("Parameter value: {0:#?}", p.value);
-                    match &p.value {
-                        ParameterValue::StringValue { value, .. } => {
-                            // If any path string parameter is empty (""), we cannot build
-                            //  a valid URL
-                            if value.is_empty() {
-                                return None;
-                            }
-
-                            translated_url =
-                                translated_url.replace(&("{".to_owned() + &p.name + "}"), value)
-                        }
-                        ParameterValue::BoolValue { value, .. } => {
-                            translated_url = translated_url
-                                .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
-                        }
-                        ParameterValue::DoubleValue { value, .. } => {
-                            translated_url = translated_url
-                                .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
-                        }
-                        ParameterValue::ArrayOfString { value, .. } => {
-                            translated_url = translated_url
-                                .replace(&("{".to_owned() + &p.name + "}"), &value.join(","))
-                        }
-                        ParameterValue::IntValue { value, .. } => {
-                            translated_url = translated_url
-                                .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
+        let Some((amos_param, Some(ParameterMetaData::HTTP { target }))) =
+            amos_param.map(|ap| (ap, ap.meta_data.clone()))
+        else {
+            warn!(
+                p.name,
+                "Parameter without HTTP meta data, operation disscarded"
+            );
+            return None;
+        };
+        match target {
+            HTTPParameterTarget::Body => {
+                debug!("Body param: {:?}", p);
+                match &p.value {
+                    ParameterValue::Reference {
+                        fallback, relation, ..
+                    } => match relation {
+                        Relation::Response(info) => {
+                            let ref_parameter = referred_value(amos_param, results, info, fallback);
+                            body_params.push(GeneratedParameter {
+                                name: p.name.clone(),
+                                value: ref_parameter,
+                                ref_path: p.ref_path.clone(),
+                            })
                         }
-                        ParameterValue::IPV4Value { value, .. } => {
-                            let (a, b, c, d) = value;
-                            let ip_str = format!("{}.{}.{}.{}", a, b, c, d);
-                            translated_url =
-                                translated_url.replace(&("{".to_owned() + &p.name + "}"), &ip_str)
+                        Relation::Parameter(info) => {
+                            warn!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}");
+                            return None;
                         }
-                        ParameterValue::Empty => {
+                    },
+                    _ => body_params.push(p.clone()),
+                }
+            }
+            HTTPParameterTarget::Path => {
+                debug!("Parameter value: {0:#?}", p.value);
+                match &p.value {
+                    ParameterValue::StringValue { value, .. } => {
+                        // If any path string parameter is empty (""), we cannot build
+                        //  a valid URL
+                        if value.is_empty() {
                             return None;
                         }
-                        ParameterValue::Reference {
-                            fallback, relation, ..
-                        } => {
-                            // a reference at this point means a reference to a response,
-                            //  a ref to a parameter should have been resolved already
-                            match relation {
-                                Relation::Response(info) => {
-                                    debug!("translate response reference: {info:#?}");
-                                    // a reference to a response, is a ref to a value in
-                                    //  the 'results'
-                                    let ref_result = &results[info.op_idx];
-                                    //info!("Find a ref value from: {ref_result:#?}");
-                                    let ref_parameter = parse_response(amos_param.unwrap(), ref_result, info, fallback);
-                                    match &ref_parameter {
-                                       ParameterValue::StringValue { value, .. } => {
-                                           // If any path string parameter is empty (""), we cannot build
-                                           //  a valid URL
-                                           if value.is_empty() {
-                                               return None;
-                                           }
-
-                                           translated_url =
-                                               translated_url.replace(&("{".to_owned() + &p.name + "}"), value)
-                                       }
-                                        ParameterValue::IntValue { value, .. } => {
-                                            translated_url = translated_url
-                                                .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
-                                        },
-                                        _ => panic!("work todo")
+
+                        translated_url = translated_url
+                            .replace(&("{".to_owned() + &p.name + "}"), &url_encoded(value))
+                    }
+                    ParameterValue::BoolValue { value, .. } => {
+                        translated_url = translated_url
+                            .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
+                    }
+                    ParameterValue::DoubleValue { value, .. } => {
+                        translated_url = translated_url
+                            .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
+                    }
+                    ParameterValue::ArrayOfString { value, .. } => {
+                        translated_url = translated_url
+                            .replace(&("{".to_owned() + &p.name + "}"), &value.join(","))
+                    }
+                    ParameterValue::IntValue { value, .. } => {
+                        translated_url = translated_url
+                            .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
+                    }
+                    ParameterValue::IPV4Value { value, .. } => {
+                        let (a, b, c, d) = value;
+                        let ip_str = format!("{}.{}.{}.{}", a, b, c, d);
+                        translated_url =
+                            translated_url.replace(&("{".to_owned() + &p.name + "}"), &ip_str)
+                    }
+                    ParameterValue::Empty => {
+                        return None;
+                    }
+                    ParameterValue::Reference {
+                        fallback, relation, ..
+                    } => {
+                        // a reference at this point means a reference to a response,
+                        //  a ref to a parameter should have been resolved already
+                        match relation {
+                            Relation::Response(info) => {
+                                debug!("translate response reference: {info:#?}");
+                                // a reference to a response, is a ref to a value in
+                                //  the 'results'
+                                let ref_parameter =
+                                    referred_value(amos_param, results, info, fallback);
+                                match &ref_parameter {
+                                    ParameterValue::StringValue { value, .. } => {
+                                        // If any path string parameter is empty (""), we cannot build
+                                        //  a valid URL
+                                        if value.is_empty() {
+                                            return None;
+                                        }
+
+                                        translated_url = translated_url.replace(
+                                            &("{".to_owned() + &p.name + "}"),
+                                            &url_encoded(value),
+                                        )
+                                    }
+                                    ParameterValue::IntValue { value, .. } => {
+                                        translated_url = translated_url.replace(
+                                            &("{".to_owned() + &p.name + "}"),
+                                            &value.to_string(),
+                                        )
+                                    }
+                                    _ => {
+                                        warn!(
+                                            p.name,
+                                            "Unsupported referred value, operation disscarded"
+                                        );
+                                        return None;
                                     }
-                                },
-                                Relation::Parameter(info) => panic!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}")
+                                }
+                            }
+                            Relation::Parameter(info) => {
+                                warn!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}");
+                                return None;
                             }
                         }
-                        _ => panic!("Unsupported parameter value: {:#?}", p),
+                    }
+                    _ => {
+                        warn!(
+                            p.name,
+                            "Unsupported path parameter value, operation disscarded"
+                        );
+                        return None;
                     }
                 }
-                HTTPParameterTarget::FormData => {
-                    debug!("Parameter with FormData");
-                    match &p.value {
-                        ParameterValue::Reference{
-                            fallback,
-                            relation,
-                            ..
-                        } => {
-                            match relation {
-                                Relation::Response(info) => {
-                                    let ref_result = &results[info.op_idx];
-                                    let ref_parameter = parse_response(amos_param.unwrap(), ref_result, info, fallback);
-                                    form_params.push(
-                                        GeneratedParameter { name: p.name.clone(), value: ref_parameter, ref_path: p.ref_path.clone() }
-                                    )
-                                }
-                                Relation::Parameter(info) => panic!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}")
-                            }
+            }
+            HTTPParameterTarget::FormData => {
+                debug!("Parameter with FormData");
+                match &p.value {
+                    ParameterValue::Reference {
+                        fallback, relation, ..
+                    } => match relation {
+                        Relation::Response(info) => {
+                            let ref_parameter = referred_value(amos_param, results, info, fallback);
+                            form_params.push(GeneratedParameter {
+                                name: p.name.clone(),
+                                value: ref_parameter,
+                                ref_path: p.ref_path.clone(),
+                            })
                         }
-                        ParameterValue::File {..} => {
-                            file_params.push(p.clone())
+                        Relation::Parameter(info) => {
+                            warn!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}");
+                            return None;
                         }
-                        _ => form_params.push(p.clone())
-                    }
+                    },
+                    ParameterValue::File { .. } => file_params.push(p.clone()),
+                    _ => form_params.push(p.clone()),
                 }
-                HTTPParameterTarget::Query => {
-                    match &p.value {
-                        ParameterValue::Reference {
-                            fallback, relation, ..
-                        } => {
-                            // a reference at this point means a reference to a response,
-                            //  a ref to a parameter should have been resolved already
-                            match relation {
-                                Relation::Response(info) => {
-                                    debug!("translate response reference: {info:#?}");
-                                    // a reference to a response, is a ref to a value in
-                                    //  the 'results'
-                                    let ref_result = &results[info.op_idx];
-                                    //info!("Find a ref value from: {ref_result:#?}");
-                                    let ref_parameter = parse_response(amos_param.unwrap(), ref_result, info, fallback);
-                                    match &ref_parameter {
-                                       ParameterValue::StringValue { value, .. } => {
-                                           // If any path string parameter is empty (""), we cannot build
-                                           //  a valid URL
-                                           if value.is_empty() {
-                                               return None;
-                                           }
-
-                                           translated_url =
-                                               translated_url.replace(&("{".to_owned() + &p.name + "}"), value)
-                                       }
-                                        ParameterValue::IntValue { value, .. } => {
-                                            translated_url = translated_url
-                                                .replace(&("{".to_owned() + &p.name + "}"), &value.to_string())
-                                        },
-                                        _ => panic!("work todo")
+            }
+            HTTPParameterTarget::Query => {
+                match &p.value {
+                    ParameterValue::Reference {
+                        fallback, relation, ..
+                    } => {
+                        // a reference at this point means a reference to a response,
+                        //  a ref to a parameter should have been resolved already
+                        match relation {
+                            Relation::Response(info) => {
+                                debug!("translate response reference: {info:#?}");
+                                // a reference to a response, is a ref to a value in
+                                //  the 'results'
+                                let ref_parameter =
+                                    referred_value(amos_param, results, info, fallback);
+                                match &ref_parameter {
+                                    ParameterValue::StringValue { value, .. } => {
+                                        // If any path string parameter is empty (""), we cannot build
+                                        //  a valid URL
+                                        if value.is_empty() {
+                                            return None;
+                                        }
+
+                                        translated_url = translated_url.replace(
+                                            &("{".to_owned() + &p.name + "}"),
+                                            &url_encoded(value),
+                                        )
                                     }
-                                },
-                                Relation::Parameter(info) => panic!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}")
-                            }
-                        }
-                        _ => match &p.value {
-                            ParameterValue::StringValue { value, .. } => {
-                                query_params.push(format!("{}={}", p.name, value))
+                                    ParameterValue::IntValue { value, .. } => {
+                                        translated_url = translated_url.replace(
+                                            &("{".to_owned() + &p.name + "}"),
+                                            &value.to_string(),
+                                        )
+                                    }
+                                    _ => {
+                                        warn!(
+                                            p.name,
+                                            "Unsupported referred value, operation disscarded"
+                                        );
+                                        return None;
+                                    }
+                                }
                             }
-                            ParameterValue::IntValue { value, .. } => {
-                                query_params.push(format!("{}={}", p.name, &value.to_string()))
+                            Relation::Parameter(info) => {
+                                warn!("Parameter relations should be resolved before runtime translation. Offending reference: {info:#?}");
+                                return None;
                             }
-                            _ => todo!(),
-                        },
+                        }
                     }
+                    _ => match &p.value {
+                        ParameterValue::StringValue { value, .. } => {
+                            query_params.push(format!("{}={}", p.name, url_encoded(value)))
+                        }
+                        ParameterValue::IntValue { value, .. } => {
+                            query_params.push(format!("{}={}", p.name, &value.to_string()))
+                        }
+                        _ => {
+                            warn!(
+                                p.name,
+                                "Unsupported query parameter value, operation disscarded"
+                            );
+                            return None;
+                        }
+                    },
                 }
-                HTTPParameterTarget::Unsupported => {
-                    error!("Unsupported HTTP Parameter target")
-                }
-            },
+            }
+            HTTPParameterTarget::Unsupported => {
+                error!("Unsupported HTTP Parameter target")
+            }
         };
     }
 
@@ -417,14 +656,26 @@ pub fn translate_parameters(
 mod tests {
     use std::collections::HashMap;
 
+    use crate::amos::InvokeResult;
     use crate::amos::OperationMetaData::HTTP;
-    use crate::amos::{Parameter, ParameterMetaData, ParameterOwnership, Schema};
+    use crate::amos::{
+        Operation, OperationInfo, Parameter, ParameterMetaData, ParameterOwnership, Schema,
+    };
     use crate::amos_generation::ParameterValue;
     use crate::amos_generation::{GeneratedOperation, GeneratedParameter};
-    use crate::http_translation::{parameters_to_json_str, translate_parameters};
+    use crate::amos_relations::RelationInfo;
+    use crate::http_translation::{
+        parameters_to_json_str, parse_response, response_field_value, translate_operation,
+        translate_parameters,
+    };
+    use crate::value_provider::ValueProvider;
 
-    use qr_http_resource::http::HTTPMethod::DELETE;
+    use qr_http_resource::http::HTTPMethod::{DELETE, GET, POST};
     use qr_http_resource::http::HTTPParameterTarget::{Body, FormData, Path, Query};
+    use qr_http_resource::http::{
+        HTTPConfiguration, OperationPinning, PathNormalization, Protocol, RequestPinning,
+        TrailingSlash, ValueProviders,
+    };
 
     #[test]
     fn parameters_to_json_str_conversion() {
@@ -492,6 +743,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: FormData }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
             Parameter {
                 name: "configurationName".to_string(),
@@ -499,6 +753,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Path }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
         ];
 
@@ -520,6 +777,260 @@ mod tests {
         )
     }
 
+    #[test]
+    fn urls_are_relative_to_the_base_path() {
+        let amos_op = Operation {
+            info: OperationInfo {
+                name: "getProducts".to_string(),
+                key: "operation/getProducts".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(HTTP {
+                url: "/products".to_string(),
+                method: GET,
+            }),
+        };
+        let gen_op = GeneratedOperation {
+            name: "getProducts".to_string(),
+            parameters: vec![],
+        };
+        let url = |base_path: Option<&str>| {
+            let config = HTTPConfiguration::new("localhost".to_string(), 8080, Protocol::HTTP)
+                .with_base_path(base_path.map(str::to_string));
+            translate_operation(&config, &gen_op, &amos_op.meta_data, &amos_op, &[])
+                .unwrap()
+                .url
+        };
+
+        assert_eq!(url(None), "http://localhost:8080/products");
+        assert_eq!(
+            url(Some("/api/v2")),
+            "http://localhost:8080/api/v2/products"
+        );
+        // The root instead of the base path of the specification
+        assert_eq!(url(Some("/")), "http://localhost:8080/products");
+    }
+
+    #[test]
+    fn paths_are_normalized_as_configured() {
+        let amos_op = Operation {
+            info: OperationInfo {
+                name: "getPersons".to_string(),
+                key: "operation/getPersons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![Parameter {
+                name: "personName".to_string(),
+                schema: Schema::String,
+                required: true,
+                ownership: ParameterOwnership::Owned,
+                meta_data: Some(ParameterMetaData::HTTP { target: Path }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            }],
+            responses: vec![],
+            meta_data: Some(HTTP {
+                url: "/Persons//{personName}/".to_string(),
+                method: GET,
+            }),
+        };
+        let gen_op = GeneratedOperation {
+            name: "getPersons".to_string(),
+            parameters: vec![GeneratedParameter {
+                name: "personName".to_string(),
+                value: ParameterValue::StringValue {
+                    value: "Ada".to_string(),
+                    seed: 1,
+                    active: true,
+                },
+                ref_path: None,
+            }],
+        };
+        let url = |paths: PathNormalization| {
+            let config = HTTPConfiguration::new("localhost".to_string(), 8080, Protocol::HTTP)
+                .with_paths(paths);
+            translate_operation(&config, &gen_op, &amos_op.meta_data, &amos_op, &[])
+                .unwrap()
+                .url
+        };
+
+        assert_eq!(
+            url(PathNormalization::new()),
+            "http://localhost:8080/Persons//Ada/"
+        );
+        assert_eq!(
+            url(PathNormalization {
+                collapse_slashes: true,
+                trailing_slash: TrailingSlash::Remove,
+                lowercase: true,
+            }),
+            "http://localhost:8080/persons/Ada"
+        );
+        assert_eq!(
+            PathNormalization {
+                trailing_slash: TrailingSlash::Add,
+                ..PathNormalization::new()
+            }
+            .normalize("/persons?name=Ada"),
+            "/persons/?name=Ada"
+        );
+        // The root keeps its slash
+        assert_eq!(
+            PathNormalization {
+                trailing_slash: TrailingSlash::Remove,
+                ..PathNormalization::new()
+            }
+            .normalize("/"),
+            "/"
+        );
+    }
+
+    #[test]
+    fn pinned_parameters_and_headers_are_sent() {
+        let parameter = |name: &str, schema, target| Parameter {
+            name: name.to_string(),
+            schema,
+            required: false,
+            ownership: ParameterOwnership::Owned,
+            meta_data: Some(ParameterMetaData::HTTP { target }),
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let amos_op = Operation {
+            info: OperationInfo {
+                name: "getOrders".to_string(),
+                key: "operation/getOrders".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                parameter("tenantId", Schema::String, Path),
+                parameter("limit", Schema::Int, Query),
+            ],
+            responses: vec![],
+            meta_data: Some(HTTP {
+                url: "/tenants/{tenantId}/orders".to_string(),
+                method: GET,
+            }),
+        };
+        let gen_op = GeneratedOperation {
+            name: "getOrders".to_string(),
+            parameters: vec![GeneratedParameter {
+                name: "tenantId".to_string(),
+                value: ParameterValue::StringValue {
+                    value: "generated".to_string(),
+                    seed: 1,
+                    active: true,
+                },
+                ref_path: None,
+            }],
+        };
+        let pinning = RequestPinning {
+            parameters: [("tenantId".to_string(), "42".to_string())].into(),
+            headers: [("X-Feature".to_string(), "off".to_string())].into(),
+            operations: [(
+                "getOrders".to_string(),
+                OperationPinning {
+                    parameters: [("limit".to_string(), "10".to_string())].into(),
+                    headers: [("X-Feature".to_string(), "on".to_string())].into(),
+                },
+            )]
+            .into(),
+        };
+        let config = HTTPConfiguration::new("localhost".to_string(), 8080, Protocol::HTTP)
+            .with_pinning(pinning.clone());
+
+        let call =
+            translate_operation(&config, &gen_op, &amos_op.meta_data, &amos_op, &[]).unwrap();
+
+        // The pinned parameter that was not generated is sent as well
+        assert_eq!(call.url, "http://localhost:8080/tenants/42/orders?limit=10");
+        // The header of the operation replaces the one of all operations
+        assert_eq!(
+            call.headers,
+            vec![("X-Feature".to_string(), "on".to_string())]
+        );
+        assert_eq!(pinning.parameter("getPersons", "limit"), None);
+        assert_eq!(pinning.parameter("getPersons", "tenantId"), Some("42"));
+    }
+
+    #[test]
+    fn provided_values_are_computed_when_translated() {
+        let parameter = |name: &str, schema, target| Parameter {
+            name: name.to_string(),
+            schema,
+            required: false,
+            ownership: ParameterOwnership::Owned,
+            meta_data: Some(ParameterMetaData::HTTP { target }),
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let amos_op = Operation {
+            info: OperationInfo {
+                name: "postPayment".to_string(),
+                key: "operation/postPayment".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![
+                parameter("amount", Schema::Int, Body),
+                parameter("key", Schema::String, Body),
+                parameter("signature", Schema::String, Query),
+            ],
+            responses: vec![],
+            meta_data: Some(HTTP {
+                url: "/payments".to_string(),
+                method: POST,
+            }),
+        };
+        let gen_op = GeneratedOperation {
+            name: "postPayment".to_string(),
+            parameters: vec![GeneratedParameter {
+                name: "amount".to_string(),
+                value: ParameterValue::IntValue {
+                    value: 5,
+                    seed: 1,
+                    active: true,
+                },
+                ref_path: None,
+            }],
+        };
+        let providers = ValueProviders {
+            parameters: [
+                ("key".to_string(), "uuid()".to_string()),
+                ("signature".to_string(), "hmac(s3cret, body)".to_string()),
+            ]
+            .into(),
+            headers: [("X-Signature".to_string(), "hmac(s3cret, body)".to_string())].into(),
+            operations: Default::default(),
+        };
+        let config = HTTPConfiguration::new("localhost".to_string(), 8080, Protocol::HTTP)
+            .with_providers(providers);
+
+        let call =
+            translate_operation(&config, &gen_op, &amos_op.meta_data, &amos_op, &[]).unwrap();
+
+        let body = call.parameters.body.as_ref().unwrap();
+        assert_eq!(body["key"].len(), 36);
+        // The body is signed as it is sent, with the values of the providers
+        let signature = ValueProvider::Hmac {
+            secret: "s3cret".to_string(),
+        }
+        .value(Some(&serde_json::to_string(body).unwrap()));
+        assert_eq!(
+            call.url,
+            format!("http://localhost:8080/payments?signature={signature}")
+        );
+        assert_eq!(call.headers, vec![("X-Signature".to_string(), signature)]);
+    }
+
     #[test]
     fn translate_params_path() {
         // Generated operation with parameters
@@ -560,6 +1071,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Path }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
             Parameter {
                 name: "configurationName".to_string(),
@@ -567,6 +1081,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Path }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
         ];
 
@@ -583,6 +1100,49 @@ mod tests {
         )
     }
 
+    #[test]
+    fn path_and_query_values_are_percent_encoded() {
+        let string_value = |value: &str| ParameterValue::StringValue {
+            value: value.to_string(),
+            seed: 1,
+            active: true,
+        };
+        let gen_op = GeneratedOperation {
+            name: "getPerson".to_string(),
+            parameters: vec![
+                GeneratedParameter {
+                    name: "name".to_string(),
+                    value: string_value("a/b?😀"),
+                    ref_path: None,
+                },
+                GeneratedParameter {
+                    name: "filter".to_string(),
+                    value: string_value("x&y=\u{0}日本"),
+                    ref_path: None,
+                },
+            ],
+        };
+        let param = |name: &str, target| Parameter {
+            name: name.to_string(),
+            schema: Schema::String,
+            required: true,
+            ownership: ParameterOwnership::Owned,
+            meta_data: Some(ParameterMetaData::HTTP { target }),
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let amos_params = vec![param("name", Path), param("filter", Query)];
+
+        let translation =
+            translate_parameters(&gen_op.parameters, &amos_params, &[], "/persons/{name}");
+
+        assert_eq!(
+            translation.unwrap().url,
+            "/persons/a%2Fb%3F%F0%9F%98%80?filter=x%26y%3D%00%E6%97%A5%E6%9C%AC"
+        );
+    }
+
     #[test]
     fn translate_query_params() {
         // Generated operation with parameters
@@ -618,6 +1178,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Query }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
             Parameter {
                 name: "configurationName".to_string(),
@@ -625,6 +1188,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Query }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
         ];
 
@@ -677,6 +1243,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Body }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
             Parameter {
                 name: "configurationName".to_string(),
@@ -684,6 +1253,9 @@ mod tests {
                 required: true,
                 ownership: ParameterOwnership::Owned,
                 meta_data: Some(ParameterMetaData::HTTP { target: Body }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
             },
         ];
 
@@ -703,4 +1275,94 @@ mod tests {
             ])
         )
     }
+
+    #[test]
+    fn response_field_values() {
+        let param = |schema: Schema| Parameter {
+            name: "customerId".to_string(),
+            schema,
+            required: true,
+            ownership: ParameterOwnership::Dependency,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+
+        let payload = r#"{"id": 7, "data": [{"name": "Alice"}]}"#;
+
+        assert_eq!(
+            response_field_value(&param(Schema::Int), payload, "id"),
+            Some(ParameterValue::IntValue {
+                value: 7,
+                seed: 0,
+                active: false
+            })
+        );
+        assert_eq!(
+            response_field_value(&param(Schema::String), payload, "data.name"),
+            Some(ParameterValue::StringValue {
+                value: "Alice".to_string(),
+                seed: 0,
+                active: false
+            })
+        );
+        assert_eq!(
+            response_field_value(&param(Schema::Int), payload, "data.name"),
+            None
+        );
+        assert_eq!(
+            response_field_value(&param(Schema::Int), payload, "missing"),
+            None
+        );
+    }
+
+    #[test]
+    fn responses_without_a_value_use_the_fallback() {
+        let param = Parameter {
+            name: "name".to_string(),
+            schema: Schema::String,
+            required: true,
+            ownership: ParameterOwnership::Dependency,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        };
+        let info = RelationInfo {
+            operation: "get_persons".to_string(),
+            name: "name".to_string(),
+            schema: Schema::String,
+            strength: 1,
+            op_idx: 0,
+            idx: 0,
+            field: None,
+            reasons: vec![],
+        };
+        let fallback = ParameterValue::StringValue {
+            value: "fallback".to_string(),
+            seed: 0,
+            active: false,
+        };
+        let parsed = |payload: &str| {
+            let op = GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            };
+            let result = InvokeResult::new(op, payload.to_string(), true, None);
+            parse_response(&param, &result, &info, &fallback)
+        };
+
+        assert_eq!(
+            parsed(r#"["Alice"]"#),
+            ParameterValue::StringValue {
+                value: "Alice".to_string(),
+                seed: 0,
+                active: false
+            }
+        );
+        assert_eq!(parsed(r#"{"name": "Alice"}"#), fallback);
+        assert_eq!(parsed(r#"[{"name": "Alice"}]"#), fallback);
+        assert_eq!(parsed("Internal error"), fallback);
+    }
 }
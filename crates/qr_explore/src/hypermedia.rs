@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use qr_http_resource::http::{HTTPConfiguration, HTTPMethod};
+use reqwest::Url;
+
+use crate::amos::{InvokeResult, Operation, OperationInfo, OperationMetaData};
+
+/// Prefix of the names of operations created from response links, keeping
+///  them apart from the operations of the specification
+pub const LINK_OPERATION_PREFIX: &str = "link:";
+
+/// Upper bound of operations created from links, a SUT that generates new
+///  links in every response would otherwise be crawled forever
+const MAX_LINKED_OPERATIONS: usize = 256;
+
+pub type SharedLinks = Arc<Mutex<LinkedOperations>>;
+
+/// The follow-up GET operations of the hypermedia links (HAL `_links`, or any
+///  `href` field) found in the responses of a run
+#[derive(Debug, Default)]
+pub struct LinkedOperations {
+    operations: Vec<Operation>,
+    paths: HashSet<String>,
+    explored: usize,
+}
+
+impl LinkedOperations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create operations for the links of the result that are new, returns
+    ///  how many that were created
+    pub fn discover(&mut self, config: &HTTPConfiguration, result: &InvokeResult) -> usize {
+        let before = self.operations.len();
+
+        for href in extract_links(&result.result) {
+            if self.operations.len() >= MAX_LINKED_OPERATIONS {
+                break;
+            }
+
+            if let Some(path) = link_path(config, &href) {
+                if self.paths.insert(path.clone()) {
+                    self.operations.push(link_operation(&path));
+                }
+            }
+        }
+
+        self.operations.len() - before
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// The operations discovered since the last call
+    pub fn take_unexplored(&mut self) -> Vec<Operation> {
+        let unexplored = self.operations[self.explored..].to_vec();
+        self.explored = self.operations.len();
+
+        unexplored
+    }
+}
+
+/// The `href` values of a JSON payload, in order of appearance
+pub fn extract_links(payload: &str) -> Vec<String> {
+    fn collect(value: &serde_json::Value, links: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(object) => {
+                for (key, value) in object {
+                    match value {
+                        serde_json::Value::String(href) if key == "href" => {
+                            links.push(href.clone())
+                        }
+                        _ => collect(value, links),
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for value in values {
+                    collect(value, links)
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut links = vec![];
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(payload) {
+        collect(&value, &mut links);
+    }
+
+    links
+}
+
+/// The path, including any query, of a link to the target relative to its
+///  base path, the base path is prepended again when the link is requested.
+///  Links to other hosts, outside the base path and templated links are not
+///  followed.
+pub fn link_path(config: &HTTPConfiguration, href: &str) -> Option<String> {
+    let base_path = config.paths.template(
+        config
+            .base_path
+            .as_deref()
+            .unwrap_or("")
+            .trim_end_matches('/'),
+    );
+    let target = Url::parse(&format!(
+        "{}{}:{}{}/",
+        config.protocol, config.base_url, config.port, base_path
+    ))
+    .ok()?;
+
+    let path = resolve_link(&target, href)?;
+    path.strip_prefix(&base_path)
+        .filter(|relative| relative.starts_with('/'))
+        .map(str::to_string)
+}
+
+/// The path, including any query, of a link resolved against the target URL
+pub fn resolve_link(target: &Url, href: &str) -> Option<String> {
+    // Templated links, e.g. `/persons{?page}`, need values we do not have
+    if href.contains('{') {
+        return None;
+    }
+
+    let url = target.join(href).ok()?;
+
+    if url.host() != target.host() || url.port_or_known_default() != target.port_or_known_default()
+    {
+        return None;
+    }
+
+    match url.query() {
+        Some(query) => Some(format!("{}?{}", url.path(), query)),
+        None => Some(url.path().to_string()),
+    }
+}
+
+fn link_operation(path: &str) -> Operation {
+    Operation {
+        info: OperationInfo {
+            name: format!("{}GET {}", LINK_OPERATION_PREFIX, path),
+            key: format!("operation/{}{}", LINK_OPERATION_PREFIX, path),
+            alias: None,
+            deprecated: false,
+        },
+        parameters: vec![],
+        responses: vec![],
+        meta_data: Some(OperationMetaData::HTTP {
+            url: path.to_string(),
+            method: HTTPMethod::GET,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::GeneratedOperation;
+    use crate::http_translation::translate_operation;
+    use qr_http_resource::http::Protocol;
+
+    fn config() -> HTTPConfiguration {
+        HTTPConfiguration::new("localhost".to_string(), 8080, Protocol::HTTP)
+    }
+
+    #[test]
+    fn extract_hal_and_nested_links() {
+        let payload = r#"{
+            "_links": { "self": { "href": "/persons/1" } },
+            "friends": [ { "name": "Bob", "href": "/persons/2" } ],
+            "href_count": 2
+        }"#;
+
+        assert_eq!(
+            extract_links(payload),
+            vec!["/persons/1".to_string(), "/persons/2".to_string()]
+        );
+        assert!(extract_links("not json").is_empty());
+    }
+
+    #[test]
+    fn only_links_to_the_target_are_followed() {
+        let config = config();
+
+        assert_eq!(
+            link_path(&config, "/persons/1"),
+            Some("/persons/1".to_string())
+        );
+        assert_eq!(
+            link_path(&config, "http://localhost:8080/persons?page=2"),
+            Some("/persons?page=2".to_string())
+        );
+        assert_eq!(link_path(&config, "http://example.com/persons"), None);
+        assert_eq!(link_path(&config, "/persons{?page}"), None);
+    }
+
+    #[test]
+    fn links_are_relative_to_the_base_path() {
+        let config = config().with_base_path(Some("/api/v1/".to_string()));
+
+        assert_eq!(
+            link_path(&config, "/api/v1/persons/1"),
+            Some("/persons/1".to_string())
+        );
+        assert_eq!(
+            link_path(&config, "http://localhost:8080/api/v1/persons?page=2"),
+            Some("/persons?page=2".to_string())
+        );
+        assert_eq!(
+            link_path(&config, "persons/2"),
+            Some("/persons/2".to_string())
+        );
+        assert_eq!(link_path(&config, "/api/v10/persons"), None);
+        assert_eq!(link_path(&config, "/persons/1"), None);
+
+        // The link is requested at the path it was found at
+        let path = link_path(&config, "/api/v1/persons/1").unwrap();
+        let operation = link_operation(&path);
+        let gen_op = GeneratedOperation {
+            name: operation.info.name.clone(),
+            parameters: vec![],
+        };
+        let call =
+            translate_operation(&config, &gen_op, &operation.meta_data, &operation, &[]).unwrap();
+        assert_eq!(call.url, "http://localhost:8080/api/v1/persons/1");
+    }
+
+    #[test]
+    fn discover_each_link_once() {
+        let config = config();
+        let mut links = LinkedOperations::new();
+
+        let result = InvokeResult::new(
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+            r#"[{"href": "/persons/1"}, {"href": "/persons/2"}, {"href": "/persons/1"}]"#
+                .to_string(),
+            true,
+            None,
+        );
+
+        assert_eq!(links.discover(&config, &result), 2);
+        assert_eq!(links.discover(&config, &result), 0);
+
+        let unexplored = links.take_unexplored();
+        assert_eq!(unexplored.len(), 2);
+        assert_eq!(unexplored[0].info.name, "link:GET /persons/1");
+        assert!(links.take_unexplored().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use qr_http_resource::http::HTTPMethod;
+
+use crate::amos::{InvokeResult, Operation, OperationMetaData};
+use crate::amos_generation::GeneratedOperation;
+
+/// The header of the idempotency keys, unless another header is set
+pub const DEFAULT_HEADER: &str = "Idempotency-Key";
+
+/// The idempotency key of the step of the sequence, unique in the run
+pub fn key(correlation_id: &str, step: usize) -> String {
+    format!("{}-{}", correlation_id, step)
+}
+
+/// The sequence with its last operation sent again, if it is a POST. The
+///  retry is the last step and has the key of the step it retries.
+pub fn retried(
+    operations: &[Operation],
+    gen_ops: &[GeneratedOperation],
+) -> Option<Vec<GeneratedOperation>> {
+    let last = gen_ops.last()?;
+    let is_post = operations.iter().any(|op| {
+        op.info.name == last.name
+            && matches!(
+                op.meta_data,
+                Some(OperationMetaData::HTTP {
+                    method: HTTPMethod::POST,
+                    ..
+                })
+            )
+    });
+
+    is_post.then(|| [gen_ops, std::slice::from_ref(last)].concat())
+}
+
+/// The retry, if the SUT responded to it with another body than to the
+///  request it retries, it created a duplicate instead of responding again
+pub fn duplicate(results: &[InvokeResult]) -> Option<&InvokeResult> {
+    // The observations of the state have no position in the sequence
+    let mut invoked = results.iter().filter_map(|res| Some((res, res.position?)));
+    let (retry, retried) = invoked.next_back()?;
+    let (request, position) = invoked.next_back()?;
+    if retried.index != position.index + 1 || retry.operation != request.operation {
+        return None;
+    }
+
+    // A rejected retry did not create anything
+    let duplicated = request.success && retry.success && retry.result != request.result;
+    duplicated.then_some(retry)
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::HTTPMethod;
+
+    use crate::amos::{InvokeResult, Operation, OperationInfo, OperationMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::idempotency as sut;
+
+    fn gen_op(name: &str) -> GeneratedOperation {
+        GeneratedOperation {
+            name: name.to_string(),
+            parameters: vec![],
+        }
+    }
+
+    fn result(step: usize, body: &str, success: bool) -> InvokeResult {
+        InvokeResult::new(gen_op("post_order"), body.to_string(), success, None)
+            .with_position(1, step)
+    }
+
+    #[test]
+    fn the_last_post_is_retried() {
+        let op = |name: &str, method| Operation {
+            info: OperationInfo {
+                name: name.to_string(),
+                key: format!("operation/{}", name),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/orders".to_string(),
+                method,
+            }),
+        };
+        let operations = vec![
+            op("post_order", HTTPMethod::POST),
+            op("get_orders", HTTPMethod::GET),
+        ];
+
+        let retried = sut::retried(&operations, &[gen_op("get_orders"), gen_op("post_order")]);
+        assert_eq!(
+            retried,
+            Some(vec![
+                gen_op("get_orders"),
+                gen_op("post_order"),
+                gen_op("post_order")
+            ])
+        );
+        assert_eq!(sut::retried(&operations, &[gen_op("get_orders")]), None);
+        assert_eq!(sut::key("qr-1", 2), "qr-1-2");
+    }
+
+    #[test]
+    fn a_retry_responded_to_again_is_no_duplicate() {
+        let created = result(0, r#"{"id": 1}"#, true);
+        assert_eq!(
+            sut::duplicate(&[created.clone(), result(1, r#"{"id": 1}"#, true)]),
+            None
+        );
+        assert_eq!(
+            sut::duplicate(&[created.clone(), result(1, r#"{"id": 1}"#, false)]),
+            None
+        );
+
+        let duplicate = result(1, r#"{"id": 2}"#, true);
+        assert_eq!(
+            sut::duplicate(&[created, duplicate.clone()]),
+            Some(&duplicate)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::sync::OnceLock;
+
+use crate::amos::{InvokeResult, ResultMetaData};
+use crate::amos_generation::ParameterValue;
+
+/// Length of the oversized strings of the corpus
+pub const OVERSIZED_LENGTH: usize = 10_000;
+
+/// Attack payloads mixed into the generated strings, the oversized strings are
+///  added by `payloads`
+const CORPUS: &[&str] = &[
+    // SQL injection
+    "' OR '1'='1",
+    "'; DROP TABLE users; --",
+    "1 UNION SELECT NULL, NULL --",
+    "\" OR \"\"=\"",
+    // Path traversal
+    "../../../../etc/passwd",
+    "..\\..\\..\\windows\\win.ini",
+    "%2e%2e%2f%2e%2e%2fetc%2fpasswd",
+    // Format strings
+    "%s%s%s%s%s%n",
+    "%x%x%x%x",
+    "{0}{1}{2}",
+    "${7*7}",
+    // Markup, run by the browser of the client when reflected as is
+    "<script>alert(1)</script>",
+    "\"><img src=x onerror=alert(1)>",
+    // Unicode edge cases
+    "\u{0}",
+    "\u{feff}",
+    "\u{202e}gnp.exe",
+    "Z\u{0351}\u{036b}\u{0343}\u{036a}",
+    "\u{1f4a9}\u{1f4a9}",
+    "\u{fffd}\u{ffff}",
+];
+
+/// The payloads of the corpus, built once
+pub fn payloads() -> &'static [String] {
+    static PAYLOADS: OnceLock<Vec<String>> = OnceLock::new();
+    PAYLOADS.get_or_init(|| {
+        let mut payloads: Vec<String> = CORPUS.iter().map(|p| p.to_string()).collect();
+        payloads.push("A".repeat(OVERSIZED_LENGTH));
+        payloads.push("\u{1f600}".repeat(OVERSIZED_LENGTH / 4));
+
+        payloads
+    })
+}
+
+/// If the payload is one of the corpus
+pub fn is_payload(value: &str) -> bool {
+    CORPUS.contains(&value)
+        || (value.len() >= OVERSIZED_LENGTH && payloads().iter().any(|p| p == value))
+}
+
+/// The payloads of the corpus the request of the result was sent with
+fn sent_payloads(result: &InvokeResult) -> impl Iterator<Item = &str> {
+    result
+        .operation
+        .parameters
+        .iter()
+        .filter_map(|param| match &param.value {
+            ParameterValue::StringValue { value, .. } => Some(value.as_str()),
+            _ => None,
+        })
+        .filter(|value| !value.is_empty() && is_payload(value))
+}
+
+/// The injected payload found as is in the response, JSON responses quote the
+///  strings they hold and are not a reflection
+pub fn reflected_payload(result: &InvokeResult) -> Option<&str> {
+    let is_json = result
+        .meta_data
+        .as_ref()
+        .and_then(|meta_data| meta_data.header("content-type"))
+        .is_some_and(|content_type| content_type.contains("json"));
+
+    if is_json {
+        return None;
+    }
+
+    sent_payloads(result).find(|value| result.result.contains(value))
+}
+
+/// If an injected payload crashed the SUT or was reflected in the response,
+///  a request without one is not an injection
+pub fn is_injection_failure(result: &InvokeResult) -> bool {
+    if sent_payloads(result).next().is_none() {
+        return false;
+    }
+
+    let server_error = matches!(
+        &result.meta_data,
+        Some(ResultMetaData::HTTP { status, .. })
+            if status.code().is_some_and(|code| (500..=599).contains(&code))
+    );
+
+    server_error || reflected_payload(result).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::{GeneratedOperation, GeneratedParameter};
+    use qr_http_resource::http::HTTPStatus;
+
+    fn result(status: HTTPStatus, name: &str, payload: &str, content_type: &str) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![GeneratedParameter {
+                    name: "name".to_string(),
+                    value: ParameterValue::StringValue {
+                        value: name.to_string(),
+                        seed: 1,
+                        active: false,
+                    },
+                    ref_path: None,
+                }],
+            },
+            payload.to_string(),
+            status == HTTPStatus::OK,
+            Some(ResultMetaData::HTTP {
+                url: "/persons".to_string(),
+                status,
+                size: payload.len(),
+                content_encoding: None,
+                headers: vec![("content-type".to_string(), content_type.to_string())],
+            }),
+        )
+    }
+
+    #[test]
+    fn reflected_payloads() {
+        let xss = "<script>alert(1)</script>";
+
+        let html = result(
+            HTTPStatus::OK,
+            xss,
+            &format!("<p>No person named {}</p>", xss),
+            "text/html",
+        );
+        assert_eq!(reflected_payload(&html), Some(xss));
+        assert!(is_injection_failure(&html));
+
+        let json = result(
+            HTTPStatus::OK,
+            xss,
+            &format!("{{\"name\": \"{}\"}}", xss),
+            "application/json",
+        );
+        assert!(!is_injection_failure(&json));
+
+        // A generated value that is not a payload is not a reflection
+        let echo = result(HTTPStatus::OK, "alice", "<p>alice</p>", "text/html");
+        assert!(!is_injection_failure(&echo));
+    }
+
+    #[test]
+    fn server_errors_are_failures() {
+        let crash = result(
+            HTTPStatus::InternalServerError,
+            "' OR '1'='1",
+            "error",
+            "text/plain",
+        );
+        assert!(is_injection_failure(&crash));
+
+        let unavailable = result(HTTPStatus::from_code(503), "' OR '1'='1", "", "text/plain");
+        assert!(is_injection_failure(&unavailable));
+
+        assert!(payloads().iter().all(|payload| is_payload(payload)));
+    }
+
+    #[test]
+    fn failures_without_a_payload_are_not_injections() {
+        let crash = result(HTTPStatus::InternalServerError, "alice", "", "text/plain");
+        assert!(!is_injection_failure(&crash));
+    }
+}
@@ -0,0 +1,667 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use qr_http_resource::http::{HTTPStatus, TransportError};
+use tracing::error;
+
+use crate::amos::{InvokeResult, ResultMetaData, SequencePosition};
+
+/// Number of invocations spilled to, and loaded from, the log at a time
+const PAGE_SIZE: usize = 1024;
+
+/// Number of spilled pages kept loaded, a range of results may straddle the
+///  boundary of two pages
+const LOADED_PAGES: usize = 2;
+
+/// The parts of an invocation needed to list it, kept in memory for the whole run
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvocationSummary {
+    pub operation: String,
+    pub success: bool,
+    pub status: Option<HTTPStatus>,
+    pub url: Option<String>,
+    pub duration: Duration,
+    /// Size in bytes of the payload as received
+    pub size: Option<usize>,
+    pub content_encoding: Option<String>,
+    pub transport_error: Option<TransportError>,
+    /// Links the invocation to the sequence it was made in
+    pub position: Option<SequencePosition>,
+    pub correlation_id: Option<String>,
+}
+
+impl InvocationSummary {
+    pub fn new(result: &InvokeResult, duration: Duration) -> Self {
+        let (status, url, size, content_encoding) = match &result.meta_data {
+            Some(ResultMetaData::HTTP {
+                url,
+                status,
+                size,
+                content_encoding,
+                ..
+            }) => (
+                Some(status.clone()),
+                Some(url.clone()),
+                Some(*size),
+                content_encoding.clone(),
+            ),
+            None => (None, None, None, None),
+        };
+
+        InvocationSummary {
+            operation: result.operation.name.clone(),
+            success: result.success,
+            status,
+            url,
+            duration,
+            size,
+            content_encoding,
+            transport_error: result.transport_error,
+            position: result.position,
+            correlation_id: result.correlation_id.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Successful,
+    Redirection,
+    ClientError,
+    ServerError,
+    /// Requests without a response
+    TransportError,
+}
+
+impl StatusFilter {
+    /// The filters in the order they are offered
+    pub const ALL: [StatusFilter; 6] = [
+        StatusFilter::All,
+        StatusFilter::Successful,
+        StatusFilter::Redirection,
+        StatusFilter::ClientError,
+        StatusFilter::ServerError,
+        StatusFilter::TransportError,
+    ];
+
+    pub fn matches(&self, summary: &InvocationSummary) -> bool {
+        let code = summary.status.as_ref().and_then(HTTPStatus::code);
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Successful => matches!(code, Some(200..=299)),
+            StatusFilter::Redirection => matches!(code, Some(300..=399)),
+            StatusFilter::ClientError => matches!(code, Some(400..=499)),
+            StatusFilter::ServerError => matches!(code, Some(500..=599)),
+            StatusFilter::TransportError => summary.transport_error.is_some(),
+        }
+    }
+}
+
+impl std::fmt::Display for StatusFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            StatusFilter::All => "All",
+            StatusFilter::Successful => "2xx",
+            StatusFilter::Redirection => "3xx",
+            StatusFilter::ClientError => "4xx",
+            StatusFilter::ServerError => "5xx",
+            StatusFilter::TransportError => "No response",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Which invocations to list. The text is matched, ignoring case, against the
+///  operation name and the URL.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InvocationFilter {
+    pub text: String,
+    pub status: StatusFilter,
+}
+
+impl InvocationFilter {
+    pub fn matches(&self, summary: &InvocationSummary) -> bool {
+        if !self.status.matches(summary) {
+            return false;
+        }
+
+        if self.text.is_empty() {
+            return true;
+        }
+
+        let text = self.text.to_lowercase();
+        summary.operation.to_lowercase().contains(&text)
+            || summary
+                .url
+                .as_ref()
+                .is_some_and(|url| url.to_lowercase().contains(&text))
+    }
+}
+
+/// The indices of the invocations matching a filter. Only the invocations added
+///  since the last update are checked, unless the filter changes.
+#[derive(Debug, Default)]
+pub struct FilteredInvocations {
+    filter: InvocationFilter,
+    checked: usize,
+    indices: Vec<usize>,
+}
+
+impl FilteredInvocations {
+    pub fn new() -> Self {
+        FilteredInvocations::default()
+    }
+
+    pub fn update(
+        &mut self,
+        filter: &InvocationFilter,
+        summaries: &[InvocationSummary],
+    ) -> &[usize] {
+        if *filter != self.filter || summaries.len() < self.checked {
+            self.filter = filter.clone();
+            self.checked = 0;
+            self.indices.clear();
+        }
+
+        for (idx, summary) in summaries.iter().enumerate().skip(self.checked) {
+            if filter.matches(summary) {
+                self.indices.push(idx);
+            }
+        }
+        self.checked = summaries.len();
+
+        &self.indices
+    }
+}
+
+/// The number of invocations in each bin of the payload sizes. The bins are
+///  powers of two, a bin holds the sizes up to and including its bound.
+pub fn size_histogram(summaries: &[InvocationSummary]) -> Vec<(usize, usize)> {
+    let mut bins: Vec<(usize, usize)> = vec![];
+
+    for size in summaries.iter().filter_map(|summary| summary.size) {
+        let bound = size.max(1).next_power_of_two();
+        match bins.binary_search_by_key(&bound, |(b, _)| *b) {
+            Ok(idx) => bins[idx].1 += 1,
+            Err(idx) => bins.insert(idx, (bound, 1)),
+        }
+    }
+
+    bins
+}
+
+#[derive(Debug)]
+struct Page {
+    index: usize,
+    results: Vec<InvokeResult>,
+}
+
+/// The invocation results of a run. The latest results are kept in memory while
+///  older ones are spilled, a page at a time, to a log on disk and loaded back
+///  when asked for.
+#[derive(Debug)]
+pub struct InvocationLog {
+    path: PathBuf,
+    log: Option<File>,
+    // If the log can not be written, everything is kept in memory
+    spill_failed: bool,
+    // Byte offset of each spilled page in the log
+    page_offsets: Vec<u64>,
+    written: u64,
+    recent: VecDeque<InvokeResult>,
+    capacity: usize,
+    summaries: Vec<InvocationSummary>,
+    // The pages last loaded, the last used last
+    loaded_pages: RefCell<VecDeque<Page>>,
+}
+
+impl InvocationLog {
+    /// A log keeping at least the latest `capacity` results in memory, spilling
+    ///  older results to the file at `path`
+    pub fn new(path: &Path, capacity: usize) -> Self {
+        InvocationLog {
+            path: path.to_path_buf(),
+            log: None,
+            spill_failed: false,
+            page_offsets: vec![],
+            written: 0,
+            recent: VecDeque::new(),
+            capacity,
+            summaries: vec![],
+            loaded_pages: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub fn push(&mut self, result: InvokeResult, duration: Duration) {
+        self.summaries
+            .push(InvocationSummary::new(&result, duration));
+        self.recent.push_back(result);
+
+        if !self.spill_failed && self.recent.len() >= self.capacity + PAGE_SIZE {
+            self.spill_page();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.summaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summaries.is_empty()
+    }
+
+    pub fn summaries(&self) -> &[InvocationSummary] {
+        &self.summaries
+    }
+
+    /// The result at the index, loaded from the log if it has been spilled
+    pub fn get(&self, idx: usize) -> Option<InvokeResult> {
+        let spilled = self.spilled();
+        if idx >= spilled {
+            return self.recent.get(idx - spilled).cloned();
+        }
+
+        let page_index = idx / PAGE_SIZE;
+        let mut loaded_pages = self.loaded_pages.borrow_mut();
+        match loaded_pages
+            .iter()
+            .position(|page| page.index == page_index)
+        {
+            Some(loaded) => {
+                let page = loaded_pages.remove(loaded)?;
+                loaded_pages.push_back(page);
+            }
+            None => {
+                let page = self.load_page(page_index).map(|results| Page {
+                    index: page_index,
+                    results,
+                })?;
+                loaded_pages.push_back(page);
+                if loaded_pages.len() > LOADED_PAGES {
+                    loaded_pages.pop_front();
+                }
+            }
+        }
+
+        loaded_pages
+            .back()
+            .and_then(|page| page.results.get(idx % PAGE_SIZE).cloned())
+    }
+
+    pub fn range(&self, range: RangeInclusive<usize>) -> Vec<InvokeResult> {
+        range.filter_map(|idx| self.get(idx)).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.summaries.clear();
+        self.recent.clear();
+        self.page_offsets.clear();
+        self.written = 0;
+        self.loaded_pages.borrow_mut().clear();
+        // A new log is tried for the next results
+        self.spill_failed = false;
+
+        if self.log.take().is_some() {
+            self.remove_log();
+        }
+    }
+
+    fn spilled(&self) -> usize {
+        self.page_offsets.len() * PAGE_SIZE
+    }
+
+    fn spill_page(&mut self) {
+        let mut payload = vec![];
+        for result in self.recent.iter().take(PAGE_SIZE) {
+            match serde_json::to_vec(result) {
+                Ok(line) => {
+                    payload.extend(line);
+                    payload.push(b'\n');
+                }
+                Err(e) => {
+                    error!("Could not serialize invocation: {}", e);
+                    self.spill_failed = true;
+                    return;
+                }
+            }
+        }
+
+        if self.log.is_none() {
+            match File::create(&self.path) {
+                Ok(file) => self.log = Some(file),
+                Err(e) => {
+                    error!(
+                        "Could not create invocation log {}: {}",
+                        self.path.display(),
+                        e
+                    );
+                    self.spill_failed = true;
+                    return;
+                }
+            }
+        }
+
+        let written = self
+            .log
+            .as_mut()
+            .map(|log| log.write_all(&payload).and_then(|_| log.flush()));
+
+        if let Some(Err(e)) = written {
+            error!(
+                "Could not write invocation log {}: {}",
+                self.path.display(),
+                e
+            );
+            self.spill_failed = true;
+            return;
+        }
+
+        self.page_offsets.push(self.written);
+        self.written += payload.len() as u64;
+        self.recent.drain(0..PAGE_SIZE);
+    }
+
+    fn load_page(&self, page_index: usize) -> Option<Vec<InvokeResult>> {
+        let load = || -> std::io::Result<Vec<InvokeResult>> {
+            let mut file = File::open(&self.path)?;
+            file.seek(SeekFrom::Start(self.page_offsets[page_index]))?;
+
+            BufReader::new(file)
+                .lines()
+                .take(PAGE_SIZE)
+                .map(|line| Ok(serde_json::from_str(&line?)?))
+                .collect()
+        };
+
+        match load() {
+            Ok(results) => Some(results),
+            Err(e) => {
+                error!(
+                    "Could not load invocations from {}: {}",
+                    self.path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn remove_log(&self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            error!(
+                "Could not remove invocation log {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+impl Drop for InvocationLog {
+    fn drop(&mut self) {
+        if self.log.is_some() {
+            self.remove_log();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::GeneratedOperation;
+
+    fn result(idx: usize) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: format!("op_{idx}"),
+                parameters: vec![],
+            },
+            format!("result {idx}"),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: format!("/op/{idx}"),
+                status: HTTPStatus::OK,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    fn log_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("qr_{}_{}.log", name, std::process::id()))
+    }
+
+    #[test]
+    fn spilled_results_are_loaded_from_the_log() {
+        let path = log_path("spilled_results");
+        let mut log = InvocationLog::new(&path, 10);
+
+        for idx in 0..(3 * PAGE_SIZE) {
+            log.push(result(idx), Duration::from_millis(1));
+        }
+
+        assert_eq!(log.len(), 3 * PAGE_SIZE);
+        assert!(log.recent.len() < 10 + PAGE_SIZE);
+        assert!(path.exists());
+
+        assert_eq!(log.get(0), Some(result(0)));
+        assert_eq!(log.get(PAGE_SIZE + 5), Some(result(PAGE_SIZE + 5)));
+        assert_eq!(log.get(3 * PAGE_SIZE - 1), Some(result(3 * PAGE_SIZE - 1)));
+        assert_eq!(log.get(3 * PAGE_SIZE), None);
+
+        assert_eq!(log.summaries()[7].operation, "op_7");
+        assert_eq!(
+            log.range(PAGE_SIZE - 1..=PAGE_SIZE),
+            vec![result(PAGE_SIZE - 1), result(PAGE_SIZE)]
+        );
+
+        drop(log);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn a_range_across_pages_is_loaded_once() {
+        let path = log_path("across_pages");
+        let mut log = InvocationLog::new(&path, 0);
+
+        for idx in 0..(2 * PAGE_SIZE) {
+            log.push(result(idx), Duration::from_millis(1));
+        }
+        let across = PAGE_SIZE - 1..=PAGE_SIZE;
+        assert_eq!(log.range(across.clone()).len(), 2);
+
+        // Both pages are still loaded, the log is not read again
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            log.range(across),
+            vec![result(PAGE_SIZE - 1), result(PAGE_SIZE)]
+        );
+        log.log = None;
+    }
+
+    #[test]
+    fn clear_removes_the_log() {
+        let path = log_path("clear_log");
+        let mut log = InvocationLog::new(&path, 0);
+
+        for idx in 0..PAGE_SIZE {
+            log.push(result(idx), Duration::from_millis(1));
+        }
+        assert!(path.exists());
+
+        log.clear();
+
+        assert!(log.is_empty());
+        assert_eq!(log.get(0), None);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn clear_tries_a_new_log_after_a_failed_spill() {
+        let path = log_path("no_such_dir").join("invocations.log");
+        let mut log = InvocationLog::new(&path, 0);
+
+        for idx in 0..PAGE_SIZE {
+            log.push(result(idx), Duration::from_millis(1));
+        }
+        assert!(log.spill_failed);
+        assert_eq!(log.get(PAGE_SIZE - 1), Some(result(PAGE_SIZE - 1)));
+
+        log.clear();
+        assert!(!log.spill_failed);
+    }
+
+    #[test]
+    fn filter_on_text_and_status() {
+        let summary = |operation: &str, url: &str, status: HTTPStatus| InvocationSummary {
+            operation: operation.to_string(),
+            success: true,
+            status: Some(status),
+            url: Some(url.to_string()),
+            duration: Duration::from_millis(1),
+            size: None,
+            content_encoding: None,
+            transport_error: None,
+            position: None,
+            correlation_id: None,
+        };
+
+        let summaries = vec![
+            summary("getProduct", "/products/1", HTTPStatus::OK),
+            summary("addProduct", "/products", HTTPStatus::BadRequest),
+            summary("getFeature", "/features/1", HTTPStatus::InternalServerError),
+        ];
+
+        let mut filtered = FilteredInvocations::new();
+        let mut filter = InvocationFilter {
+            text: "PRODUCT".to_string(),
+            status: StatusFilter::All,
+        };
+
+        assert_eq!(filtered.update(&filter, &summaries), &[0, 1]);
+
+        filter.status = StatusFilter::ClientError;
+        assert_eq!(filtered.update(&filter, &summaries), &[1]);
+
+        filter.text = "features".to_string();
+        filter.status = StatusFilter::ServerError;
+        assert_eq!(filtered.update(&filter, &summaries), &[2]);
+    }
+
+    #[test]
+    fn filter_on_the_status_class() {
+        let summary = |code: u16| InvocationSummary {
+            operation: "getProduct".to_string(),
+            success: false,
+            status: Some(HTTPStatus::from_code(code)),
+            url: None,
+            duration: Duration::from_millis(1),
+            size: None,
+            content_encoding: None,
+            transport_error: None,
+            position: None,
+            correlation_id: None,
+        };
+
+        let summaries: Vec<_> = [201, 304, 413, 429, 500, 502, 503].map(summary).into();
+        let mut filtered = FilteredInvocations::new();
+        let mut filter = InvocationFilter {
+            text: "".to_string(),
+            status: StatusFilter::Successful,
+        };
+        assert_eq!(filtered.update(&filter, &summaries), &[0]);
+
+        filter.status = StatusFilter::Redirection;
+        assert_eq!(filtered.update(&filter, &summaries), &[1]);
+
+        filter.status = StatusFilter::ClientError;
+        assert_eq!(filtered.update(&filter, &summaries), &[2, 3]);
+
+        filter.status = StatusFilter::ServerError;
+        assert_eq!(filtered.update(&filter, &summaries), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn filter_transport_errors() {
+        let failure = InvokeResult::transport_failure(
+            GeneratedOperation {
+                name: "getProduct".to_string(),
+                parameters: vec![],
+            },
+            TransportError::Connect,
+        );
+        let summaries = vec![
+            InvocationSummary::new(&result(0), Duration::ZERO),
+            InvocationSummary::new(&failure, Duration::ZERO),
+        ];
+
+        let filter = InvocationFilter {
+            text: "".to_string(),
+            status: StatusFilter::TransportError,
+        };
+        assert_eq!(FilteredInvocations::new().update(&filter, &summaries), &[1]);
+        assert_eq!(summaries[1].status, None);
+    }
+
+    #[test]
+    fn filtered_invocations_follow_added_invocations() {
+        let summary = |operation: &str| InvocationSummary {
+            operation: operation.to_string(),
+            success: true,
+            status: None,
+            url: None,
+            duration: Duration::from_millis(1),
+            size: None,
+            content_encoding: None,
+            transport_error: None,
+            position: None,
+            correlation_id: None,
+        };
+
+        let filter = InvocationFilter {
+            text: "get".to_string(),
+            status: StatusFilter::All,
+        };
+        let mut summaries = vec![summary("get"), summary("add")];
+        let mut filtered = FilteredInvocations::new();
+
+        assert_eq!(filtered.update(&filter, &summaries), &[0]);
+
+        summaries.push(summary("get"));
+        assert_eq!(filtered.update(&filter, &summaries), &[0, 2]);
+    }
+
+    #[test]
+    fn size_histogram_in_powers_of_two() {
+        let summary = |size: Option<usize>| InvocationSummary {
+            operation: "get".to_string(),
+            success: true,
+            status: None,
+            url: None,
+            duration: Duration::from_millis(1),
+            size,
+            content_encoding: None,
+            transport_error: None,
+            position: None,
+            correlation_id: None,
+        };
+
+        let summaries = vec![
+            summary(Some(1000)),
+            summary(Some(0)),
+            summary(Some(1024)),
+            summary(None),
+            summary(Some(3)),
+        ];
+
+        assert_eq!(size_histogram(&summaries), vec![(1, 1), (4, 1), (1024, 2)]);
+    }
+}
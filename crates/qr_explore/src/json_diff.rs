@@ -0,0 +1,137 @@
+use serde_json::Value;
+
+/// A value that differs between two JSON documents. The path is a JSON
+///  pointer, a value missing from one side is `None`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Difference {
+    pub path: String,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+/// The differences between the documents, objects are compared by key and
+///  arrays by index
+pub fn diff(left: &Value, right: &Value) -> Vec<Difference> {
+    let mut differences = vec![];
+    diff_at("", Some(left), Some(right), &mut differences);
+    differences
+}
+
+fn diff_at(
+    path: &str,
+    left: Option<&Value>,
+    right: Option<&Value>,
+    differences: &mut Vec<Difference>,
+) {
+    match (left, right) {
+        (Some(Value::Object(left)), Some(Value::Object(right))) => {
+            for (key, value) in left {
+                diff_at(
+                    &pointer(path, key),
+                    Some(value),
+                    right.get(key),
+                    differences,
+                );
+            }
+            for (key, value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                diff_at(&pointer(path, key), None, Some(value), differences);
+            }
+        }
+        (Some(Value::Array(left)), Some(Value::Array(right))) => {
+            for idx in 0..left.len().max(right.len()) {
+                diff_at(
+                    &pointer(path, &idx.to_string()),
+                    left.get(idx),
+                    right.get(idx),
+                    differences,
+                );
+            }
+        }
+        (left, right) if left != right => differences.push(Difference {
+            path: path.to_string(),
+            left: left.cloned(),
+            right: right.cloned(),
+        }),
+        _ => (),
+    }
+}
+
+/// Remove the fields of the names from the objects of the document, at any
+///  depth
+pub fn remove_fields(value: &mut Value, names: &[String]) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !names.contains(key));
+            for value in object.values_mut() {
+                remove_fields(value, names);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                remove_fields(item, names);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// The path of the key below the parent, escaped as in RFC 6901
+fn pointer(parent: &str, key: &str) -> String {
+    format!("{}/{}", parent, key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_objects_and_arrays() {
+        let left = json!({"id": 1, "name": "a", "tags": ["x", "y"], "a/b": true});
+        let right = json!({"id": 1, "name": "b", "tags": ["x"], "age": 3, "a/b": true});
+
+        assert_eq!(
+            diff(&left, &right),
+            vec![
+                Difference {
+                    path: "/name".to_string(),
+                    left: Some(json!("a")),
+                    right: Some(json!("b")),
+                },
+                Difference {
+                    path: "/tags/1".to_string(),
+                    left: Some(json!("y")),
+                    right: None,
+                },
+                Difference {
+                    path: "/age".to_string(),
+                    left: None,
+                    right: Some(json!(3)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_fields_at_any_depth() {
+        let mut doc = json!([{"id": 1, "updated": "t1", "owner": {"name": "a", "updated": "t2"}}]);
+        remove_fields(&mut doc, &["updated".to_string()]);
+
+        assert_eq!(doc, json!([{"id": 1, "owner": {"name": "a"}}]));
+    }
+
+    #[test]
+    fn diff_equal_and_replaced_documents() {
+        let doc = json!([{"id": 1}, {"id": 2}]);
+        assert!(diff(&doc, &doc).is_empty());
+
+        assert_eq!(
+            diff(&doc, &json!({"id": 1})),
+            vec![Difference {
+                path: String::new(),
+                left: Some(doc.clone()),
+                right: Some(json!({"id": 1})),
+            }]
+        );
+    }
+}
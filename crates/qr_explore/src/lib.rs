@@ -1,5 +1,8 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
+use qr_http_resource::http;
 use tracing::info;
 
 pub mod amos;
@@ -7,53 +10,205 @@ pub mod amos_buckets;
 pub mod amos_generation;
 pub mod amos_relations;
 pub mod amos_result;
+#[cfg(feature = "amqp")]
+pub mod amqp;
 pub mod behaviours;
+pub mod boundary;
+pub mod cache;
+pub mod campaign;
+pub mod chaos;
+pub mod coap;
+pub mod conditional;
+pub mod config;
+pub mod contract;
+pub mod corpus;
+pub mod differential;
+pub mod error;
 pub mod exploration_settings;
 pub mod explore;
+pub mod health;
 pub mod http_translation;
+pub mod hypermedia;
+pub mod idempotency;
+pub mod injection;
+pub mod invocation_log;
+pub mod json_diff;
+pub mod linearizability;
+pub mod manifest;
+pub mod manual;
 pub mod meta_properties;
+pub mod mock;
+pub mod model;
+pub mod mutation;
+pub mod notification;
+pub mod observer;
+pub mod precedence;
+pub mod progress;
+pub mod queue;
+pub mod role_matrix;
+pub mod script;
+pub mod secrets;
+pub mod security_audit;
 pub mod sequence;
+pub mod soak;
+pub mod state_observer;
+pub mod sut_manager;
 pub mod synthesize;
+pub mod value_provider;
 
+/// Spawn the exploration of the behaviour with the settings, the state
+///  mutation and behaviour settings of which are the ones of the behaviour
 pub fn spawn_exploration(
     target: &explore::Target,
     is_dry_run: bool,
     amos: &amos::AMOS,
-    channel: std::sync::mpsc::Sender<explore::Event>,
-    ops: Vec<amos::Operation>,
+    observer: Box<dyn observer::ExplorationObserver>,
     behaviour: &behaviours::Behaviour,
-    parameters: &exploration_settings::StateMutationSettings,
+    settings: &exploration_settings::ExplorationSettings,
+    control: explore::ExplorationControl,
 ) -> std::thread::JoinHandle<()> {
+    let exploration_settings::ExplorationSettings {
+        state_mutation: parameters,
+        operation_weights,
+        state_observation: observation,
+        response_time,
+        payload,
+        injection,
+        correlation,
+        health,
+        tags,
+        generators,
+        model,
+        mock,
+        behaviour: behaviour_settings,
+        auth,
+        ..
+    } = settings;
+    let explore::ExplorationControl {
+        stop,
+        pause,
+        invoker,
+    } = control;
+    let ops = &amos.operations;
+    let observation = observation.clone();
+
+    let target = &target.clone().resolve_base_path(amos);
+
     // Resolve operations relevant to this exploration,
     //  i.e., any operations depending on defintions will be
     //  resolved into actual parameters based on the definition
     //let exploration_ops = ops.clone();
-    let exploration_ops: Vec<amos::Operation> = ops
+    // An operation that is not in the AMOS fails the exploration, from its thread
+    let resolve = |name: &str| {
+        amos.resolve_operation(name)
+            .ok_or_else(|| error::ExploreError::UnknownOperation(name.to_string()))
+    };
+    let resolved = ops
         .iter()
-        .map(|op| {
-            amos.resolve_operation(&op.info.name)
-                .expect("Selected operations must be in AMOS")
-        })
-        .collect();
-
-    let selected_query_ops: Vec<amos::Operation> = parameters
-        .query_operation_ids
-        .iter()
-        .map(|idx| {
-            let op = &ops[*idx];
-            amos.resolve_operation(&op.info.name)
-                .expect("Operation must be in AMOS")
-        })
-        .collect();
+        .map(|op| resolve(&op.info.name))
+        .collect::<Result<Vec<amos::Operation>, error::ExploreError>>()
+        .and_then(|mut exploration_ops| {
+            let mut selected_query_ops = parameters
+                .query_operation_ids
+                .iter()
+                .map(|idx| match ops.get(*idx) {
+                    Some(op) => resolve(&op.info.name),
+                    None => Err(error::ExploreError::UnknownOperation(format!("#{}", idx))),
+                })
+                .collect::<Result<Vec<amos::Operation>, error::ExploreError>>()?;
+
+            // The deprecated operations are left out, or the only ones targeted
+            exploration_ops.retain(|op| parameters.deprecated.generates(op));
+            selected_query_ops.retain(|op| parameters.deprecated.targets(op));
+
+            // The user defined generators take the place of the schemas
+            generators.apply(&mut exploration_ops);
+            generators.apply(&mut selected_query_ops);
+            // The large payloads of the bodies before the strings of the rest
+            if let Some(stress) = &payload.stress {
+                stress.apply(&mut exploration_ops);
+                stress.apply(&mut selected_query_ops);
+            }
+            parameters.strings.apply(&mut exploration_ops);
+            parameters.strings.apply(&mut selected_query_ops);
+            Ok((exploration_ops, selected_query_ops))
+        });
 
     let number_of_tests = parameters.number_of_tests;
     let min_length = parameters.min_length;
     let max_length = parameters.max_length;
+    let shrinking = parameters.shrinking.clone();
+    let coverage = if parameters.coverage_guided {
+        Some(Arc::new(Mutex::new(amos_result::CoverageMap::new())))
+    } else {
+        None
+    };
+    let links = parameters
+        .follow_links
+        .then(|| Arc::new(Mutex::new(hypermedia::LinkedOperations::new())));
+    let corpus = parameters
+        .harvest_responses
+        .then(|| Arc::new(Mutex::new(corpus::ResponseCorpus::new())));
+    let precedence = parameters
+        .learn_precedence
+        .then(|| Arc::new(Mutex::new(precedence::PrecedenceLearning::new())));
+    let mutation = parameters
+        .mutate_sequences
+        .then(|| Arc::new(Mutex::new(mutation::SequenceMutation::new())));
+    let campaign = parameters
+        .corpus_dir
+        .as_ref()
+        .map(|dir| campaign::CampaignCorpus::load(std::path::Path::new(dir)));
+    let script = parameters
+        .script_file
+        .as_ref()
+        .map(|file| script::ScriptRecorder::open(std::path::Path::new(file)));
+
+    // The stale cache behaviour observes the responses through the cache
+    let honor_cache_headers =
+        parameters.honor_cache_headers || *behaviour == behaviours::Behaviour::StaleCache;
 
     info!("Explore Behaviour: {:?}", behaviour);
 
+    let seed = parameters.seed.unwrap_or_else(explore::random_seed);
+    let mut manifest = manifest::RunManifest::new(
+        target,
+        amos,
+        behaviour,
+        exploration_settings::ExplorationSettings {
+            state_mutation: exploration_settings::StateMutationSettings {
+                seed: Some(seed),
+                ..parameters.clone()
+            },
+            sut: exploration_settings::SutSettings::new(),
+            auth: exploration_settings::AuthSettings::new(),
+            ..settings.clone()
+        },
+        seed,
+        manifest::RunTags::new(tags),
+    );
+
+    let operation_weights = operation_weights.clone();
+    let response_time = response_time.clone();
+    let payload = payload.clone();
+    let injection = injection.clone();
+    let auth = auth.clone();
+    let correlation_header = correlation.header.clone();
+    let idempotency_header = correlation.idempotency_header.clone();
+    // A dry run has no target to probe, nor has an invoker of the embedder
+    let health = (!is_dry_run && invoker.is_none())
+        .then(|| health::HealthCheck::new(health))
+        .flatten();
+    // Only the model behaviour has a model, an invalid model aborts it
+    let model =
+        (*behaviour == behaviours::Behaviour::Model).then(|| model::Model::parse(&model.source));
+    // A dry run is answered by the mock, a mock of its own for each exploration
+    let mock = is_dry_run.then(|| Arc::new(mock::MockSut::new(mock.clone())));
     let bhvr = behaviour.clone();
+    let behaviour_settings = behaviour_settings.clone();
     let sut_target = target.clone();
+    // A dry run has no SUT to ask the version of
+    let version_path = tags.version_path.clone().filter(|_| !is_dry_run);
 
     thread::spawn(move || {
         // TODO: this is spread out, fix
@@ -63,32 +218,231 @@ pub fn spawn_exploration(
             explore::invoke_dry
         };
 
-        let mut context = explore::ExplorationContext {
-            http_client: reqwest::blocking::Client::new(),
-            http_send_fn,
-            target: sut_target,
-            query_operation: None,
-            tx: Some(channel),
-            number_of_tests,
+        let cache = honor_cache_headers.then(|| RefCell::new(cache::ClientCache::new()));
+        let mut context = explore::ExplorationContext::new(sut_target)
+            .with_send_fn(http_send_fn)
+            .with_observer(observer)
             // TODO: Adapt to the different properties
-            min_length,
-            max_length,
+            .with_tests(number_of_tests, min_length, max_length)
+            .with_seed(seed)
+            .with_signals(stop, pause)
+            .with_observation(observation)
+            .with_coverage(coverage)
+            .with_operation_weights(operation_weights)
+            .with_links(links)
+            .with_corpus(corpus)
+            .with_precedence(precedence)
+            .with_mutation(mutation)
+            .with_shrinking(shrinking)
+            .with_behaviour_settings(behaviour_settings)
+            .with_response_time(response_time)
+            .with_payload(payload)
+            .with_injection(injection)
+            .with_auth(auth)
+            .with_health(health)
+            .with_correlation(
+                explore::CorrelationIds::new(correlation_header, explore::random_seed() as u32)
+                    .with_idempotency_header(idempotency_header),
+            )
+            .with_mock(mock)
+            .with_cache(cache);
+
+        if let Some(path) = &version_path {
+            match manifest::fetch_version(&context.target, path) {
+                Ok(version) => manifest.tags.sut_version = Some(version),
+                Err(e) => context.publish_event(explore::Event::log(
+                    explore::LogLevel::Warning,
+                    &format!("The version of the SUT is unknown: {}", e),
+                )),
+            }
+        }
+
+        context.publish_event(explore::Event::Manifest {
+            manifest: Box::new(manifest),
+        });
+
+        match model {
+            Some(Ok(model)) => context.model = Some(model::ModelChecker::new(model)),
+            Some(Err(e)) => context.abort(&e.to_string()),
+            None => (),
+        }
+
+        match campaign {
+            Some(Ok(campaign)) => {
+                context.publish_event(explore::Event::log(
+                    explore::LogLevel::Info,
+                    &format!("Loaded {} sequences of the corpus", campaign.seeds()),
+                ));
+                context.campaign = Some(Arc::new(Mutex::new(campaign)));
+            }
+            Some(Err(e)) => context.abort(&format!("The corpus could not be loaded: {}", e)),
+            None => (),
+        }
+
+        match script {
+            Some(Ok(script)) => context.script = Some(Arc::new(Mutex::new(script))),
+            Some(Err(e)) => context.abort(&format!("The script could not be created: {}", e)),
+            None => (),
+        }
+
+        // The differential behaviour compares the target with the reference
+        match context
+            .behaviour_settings
+            .reference_url()
+            .map(config::parse_target_url)
+        {
+            Some(Ok((protocol, host, port))) => match &context.target {
+                // Another deployment of the same API, at the same base path
+                explore::Target::HTTP { config } => {
+                    context.reference = Some(explore::Target::HTTP {
+                        config: http::HTTPConfiguration::new(host, port, protocol)
+                            .with_base_path(config.base_path.clone()),
+                    })
+                }
+                _ => context.abort("Only an HTTP target is compared with a reference"),
+            },
+            Some(Err(e)) => context.abort(&format!("The reference target is not valid: {}", e)),
+            None => (),
+        }
+
+        // The contract behaviour checks against the contract captured before
+        if let Some((file, false)) = context.behaviour_settings.contract() {
+            match contract::Contract::load(std::path::Path::new(file)) {
+                Ok(loaded) => context.contract = Some(loaded),
+                Err(e) => context.abort(&format!("The contract could not be loaded: {}", e)),
+            }
+        }
+
+        // The faults of the chaos behaviour are drawn from the seed of the run
+        context.chaos = context
+            .behaviour_settings
+            .chaos()
+            .map(|faults| chaos::ChaosHooks::new(faults.clone(), seed));
+
+        // A queue target is invoked by the broker of the embedder, or else by
+        //  the client of its protocol
+        let invoker = match (&context.target, invoker) {
+            (explore::Target::Queue { config }, None) => match queue::default_invoker(config) {
+                Ok(invoker) => Some(invoker),
+                Err(e) => {
+                    context.abort(&format!("The queue target has no broker: {}", e));
+                    None
+                }
+            },
+            (_, invoker) => invoker,
         };
 
-        let query_ops = selected_query_ops;
+        // The behaviour stops at once, instead of a transport error per request
+        if !is_dry_run && invoker.is_none() {
+            if let Err(reason) = explore::preflight(&context.target) {
+                context.abort(&reason);
+            }
+            if let Some(reference) = &context.reference {
+                if let Err(reason) = explore::preflight(reference) {
+                    context.abort(&reason);
+                }
+            }
+            if !context.is_stopped() {
+                context.pause_while_unhealthy(true);
+            }
+        }
 
-        let invoke = explore::invoke;
+        let (exploration_ops, query_ops) = match resolved {
+            Ok(resolved) => resolved,
+            Err(error) => {
+                let operation = match &error {
+                    error::ExploreError::UnknownOperation(name) => name.clone(),
+                    _ => String::new(),
+                };
+                let result = context.fail(&operation, error);
+                context.publish_event(explore::Event::Result { result });
+                context.publish_event(explore::Event::Control {
+                    event: explore::ControlEvent::Finished,
+                });
+                return;
+            }
+        };
+
+        // The operations are sent over HTTP, unless the embedder invokes them
+        let invoker = invoker.unwrap_or_else(|| match context.target {
+            explore::Target::CoAP { .. } => Arc::new(coap::invoke),
+            _ => Arc::new(explore::invoke),
+        });
+        let invoke = invoker.as_ref();
 
         match bhvr {
             behaviours::Behaviour::Property => {
                 explore::response_check(&context, exploration_ops.clone(), query_ops, invoke)
             }
+            behaviours::Behaviour::Payload => {
+                explore::explore_payload(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::Injection => {
+                explore::explore_injection(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::RoleMatrix => {
+                explore::explore_role_matrix(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::AuthBypass => {
+                explore::explore_auth_bypass(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::SecurityAudit => explore::explore_security_audit(
+                &context,
+                exploration_ops.clone(),
+                query_ops,
+                invoke,
+            ),
+            behaviours::Behaviour::Model => {
+                explore::explore_model(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::Soak => {
+                explore::explore_soak(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::Differential => {
+                explore::explore_differential(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::Contract => {
+                explore::explore_contract(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::Linearizability => explore::explore_linearizability(
+                &context,
+                exploration_ops.clone(),
+                query_ops,
+                explore::invoke_concurrently,
+            ),
+            behaviours::Behaviour::ResponseTime => {
+                explore::explore_response_time(&context, exploration_ops.clone(), query_ops, invoke)
+            }
             behaviours::Behaviour::StateMutation => explore::explore_state_mutation(
                 &mut context,
                 exploration_ops.clone(),
                 &query_ops,
                 invoke,
             ),
+            behaviours::Behaviour::Chaos => {
+                explore::explore_chaos(&mut context, exploration_ops.clone(), &query_ops, invoke)
+            }
+            behaviours::Behaviour::Boundary => {
+                explore::explore_boundary(&context, exploration_ops.clone(), query_ops, invoke)
+            }
+            behaviours::Behaviour::ConditionalRequest => explore::explore_conditional_request(
+                &mut context,
+                exploration_ops.clone(),
+                &query_ops,
+                invoke,
+            ),
+            behaviours::Behaviour::IdempotentRetry => explore::explore_idempotent_retry(
+                &mut context,
+                exploration_ops.clone(),
+                &query_ops,
+                invoke,
+            ),
+            behaviours::Behaviour::StaleCache => explore::explore_stale_cache(
+                &mut context,
+                exploration_ops.clone(),
+                &query_ops,
+                invoke,
+            ),
             behaviours::Behaviour::StateIdentity => explore::explore_state_identity(
                 &mut context,
                 exploration_ops.clone(),
@@ -0,0 +1,349 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::amos::InvokeResult;
+use crate::model::array_length;
+
+/// Operations in a window past this many are not searched for an order, the
+///  orders grow with the factorial of the operations
+pub const MAX_WINDOW_OPERATIONS: usize = 16;
+
+/// An invocation of a concurrent run, made by one of its processes. The call
+///  and the return are the time since the start of the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub process: usize,
+    pub call: Duration,
+    pub ret: Duration,
+    pub result: InvokeResult,
+}
+
+/// What an operation does to the resources of a collection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrudKind {
+    Create,
+    Read,
+    Delete,
+    /// Neither changes nor reads the number of resources
+    Other,
+}
+
+/// An invocation of a window that could not be ordered
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowOperation {
+    pub process: usize,
+    pub operation: String,
+    pub call_ms: f64,
+    pub return_ms: f64,
+    /// The status, and the length of a read
+    pub observed: String,
+}
+
+/// Invocations overlapping in time, with no order in which each takes effect
+///  at once that explains what the reads observed
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NonLinearizableWindow {
+    pub start_ms: f64,
+    pub end_ms: f64,
+    pub operations: Vec<WindowOperation>,
+    /// The operations before the window, since the last read, that the
+    ///  number of resources the window starts from follows from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub earlier: Vec<WindowOperation>,
+}
+
+impl std::fmt::Display for NonLinearizableWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let listed = |operations: &[WindowOperation]| {
+            operations
+                .iter()
+                .map(|op| format!("p{} {} ({})", op.process, op.operation, op.observed))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "Not linearizable between {:.1} and {:.1} ms: {}",
+            self.start_ms,
+            self.end_ms,
+            listed(&self.operations)
+        )?;
+        if !self.earlier.is_empty() {
+            write!(f, ", after {}", listed(&self.earlier))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Effect {
+    Add(i64),
+    Observe(i64),
+}
+
+/// The effect of a result on the number of resources in the collection. A
+///  write that failed had no effect, a read without an array observed nothing.
+fn effect(kind: CrudKind, result: &InvokeResult) -> Option<Effect> {
+    match kind {
+        CrudKind::Create if result.success => Some(Effect::Add(1)),
+        CrudKind::Delete if result.success => Some(Effect::Add(-1)),
+        CrudKind::Read if result.success => array_length(&result.result).map(Effect::Observe),
+        _ => None,
+    }
+}
+
+/// Check the history against the semantics of a collection, where a create
+///  adds a resource, a delete removes one, and a read lists them. The history
+///  is split into windows of overlapping invocations, the windows that cannot
+///  be ordered are returned. The number of resources is unknown until read.
+pub fn check(
+    history: &[HistoryEntry],
+    kind: impl Fn(&InvokeResult) -> CrudKind,
+) -> Vec<NonLinearizableWindow> {
+    let mut entries: Vec<&HistoryEntry> = history.iter().collect();
+    entries.sort_by_key(|entry| entry.call);
+
+    let mut windows: Vec<Vec<&HistoryEntry>> = vec![];
+    let mut window_end = Duration::ZERO;
+    for entry in entries {
+        match windows.last_mut() {
+            Some(window) if entry.call <= window_end => window.push(entry),
+            _ => windows.push(vec![entry]),
+        }
+        window_end = window_end.max(entry.ret);
+    }
+
+    let mut state = None;
+    // The entries the state follows from, from the window of the last read
+    let mut earlier: Vec<&HistoryEntry> = vec![];
+    let mut non_linearizable = vec![];
+    for window in windows {
+        // Without a response it is unknown if a write took effect
+        if window
+            .iter()
+            .any(|entry| entry.result.transport_error.is_some())
+        {
+            state = None;
+            earlier.clear();
+            continue;
+        }
+
+        let effects: Vec<(&HistoryEntry, Effect)> = window
+            .iter()
+            .filter_map(|entry| effect(kind(&entry.result), &entry.result).map(|e| (*entry, e)))
+            .collect();
+        if effects.len() > MAX_WINDOW_OPERATIONS {
+            state = None;
+            earlier.clear();
+            continue;
+        }
+
+        match linearize(&effects, state) {
+            Some(after) => {
+                state = after;
+                let reads = effects.iter().any(|(_, e)| matches!(e, Effect::Observe(_)));
+                if state.is_none() || reads {
+                    earlier.clear();
+                }
+                if state.is_some() {
+                    earlier.extend(window);
+                }
+            }
+            None => {
+                non_linearizable.push(window_of(&window, &earlier));
+                state = None;
+                earlier.clear();
+            }
+        }
+    }
+
+    non_linearizable
+}
+
+/// The state after an order of the invocations that is consistent with their
+///  real-time order and the reads, none if there is no such order
+fn linearize(effects: &[(&HistoryEntry, Effect)], state: Option<i64>) -> Option<Option<i64>> {
+    fn search(
+        effects: &[(&HistoryEntry, Effect)],
+        remaining: u32,
+        state: Option<i64>,
+        failed: &mut HashSet<(u32, Option<i64>)>,
+    ) -> Option<Option<i64>> {
+        if remaining == 0 {
+            return Some(state);
+        }
+        if failed.contains(&(remaining, state)) {
+            return None;
+        }
+
+        let is_remaining = |idx: usize| remaining & (1 << idx) != 0;
+        for (idx, (entry, effect)) in effects.iter().enumerate() {
+            // An invocation returning before this one was called goes first
+            let preceded = (0..effects.len())
+                .any(|other| is_remaining(other) && effects[other].0.ret < entry.call);
+            if !is_remaining(idx) || preceded {
+                continue;
+            }
+
+            let next = match (effect, state) {
+                (Effect::Add(n), Some(count)) => Some(count + n),
+                (Effect::Add(_), None) => None,
+                (Effect::Observe(length), Some(count)) if *length != count => continue,
+                (Effect::Observe(length), _) => Some(*length),
+            };
+            if let Some(after) = search(effects, remaining & !(1 << idx), next, failed) {
+                return Some(after);
+            }
+        }
+
+        failed.insert((remaining, state));
+        None
+    }
+
+    let all = (1u32 << effects.len()).wrapping_sub(1);
+    search(effects, all, state, &mut HashSet::new())
+}
+
+fn ms(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
+
+fn window_operation(entry: &HistoryEntry) -> WindowOperation {
+    let status = match &entry.result.meta_data {
+        Some(crate::amos::ResultMetaData::HTTP { status, .. }) => status.to_string(),
+        None => "none".to_string(),
+    };
+    let observed = match array_length(&entry.result.result) {
+        Some(length) => format!("{}, length {}", status, length),
+        None => status,
+    };
+    WindowOperation {
+        process: entry.process,
+        operation: entry.result.operation.name.clone(),
+        call_ms: ms(entry.call),
+        return_ms: ms(entry.ret),
+        observed,
+    }
+}
+
+fn window_of(window: &[&HistoryEntry], earlier: &[&HistoryEntry]) -> NonLinearizableWindow {
+    NonLinearizableWindow {
+        start_ms: window
+            .iter()
+            .map(|entry| ms(entry.call))
+            .fold(f64::MAX, f64::min),
+        end_ms: window.iter().map(|entry| ms(entry.ret)).fold(0.0, f64::max),
+        operations: window.iter().map(|entry| window_operation(entry)).collect(),
+        earlier: earlier
+            .iter()
+            .map(|entry| window_operation(entry))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use qr_http_resource::http;
+
+    use crate::amos::{InvokeResult, ResultMetaData};
+    use crate::amos_generation::GeneratedOperation;
+    use crate::linearizability as sut;
+
+    fn entry(process: usize, call: u64, ret: u64, name: &str, payload: &str) -> sut::HistoryEntry {
+        sut::HistoryEntry {
+            process,
+            call: Duration::from_millis(call),
+            ret: Duration::from_millis(ret),
+            result: InvokeResult::new(
+                GeneratedOperation {
+                    name: name.to_string(),
+                    parameters: vec![],
+                },
+                payload.to_string(),
+                true,
+                Some(ResultMetaData::HTTP {
+                    url: "/persons".to_string(),
+                    status: http::HTTPStatus::OK,
+                    size: payload.len(),
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            ),
+        }
+    }
+
+    fn kind(result: &InvokeResult) -> sut::CrudKind {
+        match result.operation.name.as_str() {
+            "post_person" => sut::CrudKind::Create,
+            "delete_person" => sut::CrudKind::Delete,
+            "get_persons" => sut::CrudKind::Read,
+            _ => sut::CrudKind::Other,
+        }
+    }
+
+    #[test]
+    fn overlapping_reads_may_see_a_create_or_not() {
+        let history = vec![
+            entry(0, 0, 1, "get_persons", "[]"),
+            entry(0, 2, 10, "post_person", "{}"),
+            entry(1, 3, 4, "get_persons", "[1]"),
+            entry(2, 5, 6, "get_persons", "[]"),
+        ];
+
+        // The second read is not linearizable, it missed a create the first saw
+        let windows = sut::check(&history, kind);
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start_ms, 2.0);
+        assert_eq!(windows[0].operations.len(), 3);
+
+        let history = vec![
+            entry(0, 0, 1, "get_persons", "[]"),
+            entry(0, 2, 10, "post_person", "{}"),
+            entry(1, 3, 4, "get_persons", "[]"),
+            entry(2, 5, 6, "get_persons", "[1]"),
+            entry(1, 11, 12, "get_persons", "[1]"),
+        ];
+        assert!(sut::check(&history, kind).is_empty());
+    }
+
+    #[test]
+    fn a_read_after_a_return_sees_its_effect() {
+        let history = vec![
+            entry(0, 0, 1, "get_persons", "[1]"),
+            entry(1, 2, 3, "delete_person", ""),
+            entry(0, 4, 5, "get_persons", "[1]"),
+        ];
+
+        let windows = sut::check(&history, kind);
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].operations[0].operation, "get_persons");
+        assert_eq!(windows[0].operations[0].observed, "200, length 1");
+
+        // The stale read is reported with the read and the delete before it
+        let earlier: Vec<&str> = windows[0]
+            .earlier
+            .iter()
+            .map(|op| op.operation.as_str())
+            .collect();
+        assert_eq!(earlier, vec!["get_persons", "delete_person"]);
+        assert_eq!(
+            windows[0].to_string(),
+            "Not linearizable between 4.0 and 5.0 ms: p0 get_persons (200, length 1), \
+             after p0 get_persons (200, length 1), p1 delete_person (200)"
+        );
+    }
+
+    #[test]
+    fn the_count_is_unknown_until_read() {
+        let history = vec![
+            entry(0, 0, 1, "post_person", "{}"),
+            entry(1, 2, 3, "get_persons", "[1, 2, 3]"),
+            entry(0, 4, 5, "post_person", "{}"),
+            entry(1, 6, 7, "get_persons", "[1, 2, 3, 4]"),
+        ];
+
+        assert!(sut::check(&history, kind).is_empty());
+    }
+}
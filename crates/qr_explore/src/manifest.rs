@@ -0,0 +1,251 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::amos::AMOS;
+use crate::behaviours::Behaviour;
+use crate::exploration_settings::{ExplorationSettings, RunTagSettings};
+use crate::explore::{self, Target};
+
+/// The build of the SUT a run explored, as far as it is known
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RunTags {
+    pub sut_version: Option<String>,
+    pub git_commit: Option<String>,
+    pub environment: Option<String>,
+}
+
+impl RunTags {
+    /// The tags of the settings, the version is fetched as the run starts
+    pub fn new(settings: &RunTagSettings) -> Self {
+        Self {
+            sut_version: None,
+            git_commit: settings.git_commit.clone(),
+            environment: settings.environment.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sut_version.is_none() && self.git_commit.is_none() && self.environment.is_none()
+    }
+}
+
+impl std::fmt::Display for RunTags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tags: Vec<String> = [
+            ("version", &self.sut_version),
+            ("commit", &self.git_commit),
+            ("environment", &self.environment),
+        ]
+        .into_iter()
+        .filter_map(|(name, tag)| tag.as_ref().map(|tag| format!("{} {}", name, tag)))
+        .collect();
+
+        write!(f, "{}", tags.join(", "))
+    }
+}
+
+/// The configuration of a run, stored with its results so runs can be audited
+///  and compared
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RunManifest {
+    pub tool_version: String,
+    pub target: String,
+    /// Runs with the same hash explored the same AMOS
+    pub amos_hash: String,
+    pub behaviour: Behaviour,
+    /// The settings of the run, credentials are never recorded
+    pub settings: ExplorationSettings,
+    /// Seed of the generation, a run with the same seed and settings generates
+    ///  the same values
+    pub seed: u64,
+    /// Manifests written before runs were tagged have no tags
+    #[serde(default)]
+    pub tags: RunTags,
+    /// Seconds since the UNIX epoch
+    pub started: u64,
+    pub finished: Option<u64>,
+}
+
+impl RunManifest {
+    pub fn new(
+        target: &Target,
+        amos: &AMOS,
+        behaviour: &Behaviour,
+        settings: ExplorationSettings,
+        seed: u64,
+        tags: RunTags,
+    ) -> Self {
+        let target = match target {
+            Target::HTTP { config } => {
+                format!(
+                    "{}{}:{}{}",
+                    config.protocol,
+                    config.base_url,
+                    config.port,
+                    config.base_path.as_deref().unwrap_or("")
+                )
+            }
+            Target::Queue { config } => config.url(config.topic_prefix.as_deref().unwrap_or("")),
+            Target::CoAP { config } => format!(
+                "coap://{}:{}{}",
+                config.host,
+                config.port,
+                config.base_path.as_deref().unwrap_or("")
+            ),
+        };
+
+        RunManifest {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            target,
+            amos_hash: amos_hash(amos),
+            behaviour: behaviour.clone(),
+            settings,
+            seed,
+            tags,
+            started: now(),
+            finished: None,
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.finished = Some(now());
+    }
+}
+
+/// The version the SUT responds at the path
+pub fn fetch_version(target: &Target, path: &str) -> Result<String, String> {
+    let config = match target {
+        Target::HTTP { config } => config,
+        _ => return Err("Only an HTTP target has a version path".to_string()),
+    };
+    let url = format!(
+        "{}{}:{}{}",
+        config.protocol, config.base_url, config.port, path
+    );
+    let response = reqwest::blocking::Client::new()
+        .get(&url)
+        .timeout(explore::PREFLIGHT_TIMEOUT)
+        .send()
+        .map_err(|e| format!("{}: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("{} responded {}", url, response.status()));
+    }
+    let body = response.text().map_err(|e| format!("{}: {}", url, e))?;
+
+    version_of(&body).ok_or_else(|| format!("{} responded no version", url))
+}
+
+/// The `version` field of a JSON object, or else the trimmed body
+fn version_of(body: &str) -> Option<String> {
+    let version = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(object)) => match object.get("version")? {
+            serde_json::Value::String(version) => version.clone(),
+            version => version.to_string(),
+        },
+        Ok(serde_json::Value::String(version)) => version,
+        _ => body.trim().to_string(),
+    };
+
+    (!version.is_empty()).then_some(version)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn amos_hash(amos: &AMOS) -> String {
+    stable_hash(&serde_json::to_string(amos).unwrap_or_default())
+}
+
+/// FNV-1a of the data, hex encoded. Unlike the std hasher it is the same across
+///  builds, so hashes written by different versions can be compared.
+pub fn stable_hash(data: &str) -> String {
+    let hash = data.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_http_resource::http;
+
+    #[test]
+    fn manifest_of_run() {
+        let target = Target::HTTP {
+            config: http::HTTPConfiguration::new(
+                "localhost".to_string(),
+                8080,
+                http::Protocol::HTTP,
+            ),
+        };
+        let mut amos = AMOS::new();
+
+        let mut manifest = RunManifest::new(
+            &target,
+            &amos,
+            &Behaviour::Property,
+            ExplorationSettings::new(),
+            42,
+            RunTags::default(),
+        );
+        manifest.finish();
+
+        assert_eq!(manifest.target, "http://localhost:8080");
+        assert!(manifest.finished.is_some_and(|f| f >= manifest.started));
+        assert_eq!(manifest.amos_hash, amos_hash(&amos));
+
+        amos.name = "other".to_string();
+        assert_ne!(manifest.amos_hash, amos_hash(&amos));
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RunManifest>(&json).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn tags_of_run() {
+        let mut tags = RunTags::new(&RunTagSettings {
+            version_path: Some("/version".to_string()),
+            git_commit: Some("4f2a91c".to_string()),
+            environment: None,
+        });
+        assert_eq!(tags.to_string(), "commit 4f2a91c");
+
+        tags.sut_version = version_of(r#"{"version": "1.4.2", "build": 7}"#);
+        assert_eq!(tags.to_string(), "version 1.4.2, commit 4f2a91c");
+
+        assert_eq!(version_of("1.4.2\n").as_deref(), Some("1.4.2"));
+        assert_eq!(version_of(r#""1.4.2""#).as_deref(), Some("1.4.2"));
+        assert_eq!(version_of(r#"{"build": 7}"#), None);
+        assert_eq!(version_of(" "), None);
+
+        // Manifests written before the tags have none
+        let mut json = serde_json::to_value(RunManifest::new(
+            &Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    8080,
+                    http::Protocol::HTTP,
+                ),
+            },
+            &AMOS::new(),
+            &Behaviour::Property,
+            ExplorationSettings::new(),
+            42,
+            tags,
+        ))
+        .unwrap();
+        json.as_object_mut().unwrap().remove("tags");
+        let manifest: RunManifest = serde_json::from_value(json).unwrap();
+        assert!(manifest.tags.is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+use std::net::Ipv4Addr;
+
+use proptest::strategy::{Strategy, ValueTree};
+
+use crate::amos::{Operation, Parameter, Schema};
+use crate::amos_generation::{
+    self, example_value, GeneratedOperation, GeneratedParameter, ParameterValue,
+};
+use crate::explore;
+
+/// The value as the text it is filled in as, a reference as its fallback
+pub fn value_text(value: &ParameterValue) -> String {
+    match value {
+        ParameterValue::StringValue { value, .. } => value.clone(),
+        ParameterValue::IntValue { value, .. } => value.to_string(),
+        ParameterValue::BoolValue { value, .. } => value.to_string(),
+        ParameterValue::DoubleValue { value, .. } => value.to_string(),
+        ParameterValue::IPV4Value {
+            value: (a, b, c, d),
+            ..
+        } => format!("{a}.{b}.{c}.{d}"),
+        ParameterValue::ArrayOfString { value, .. } => value.join(","),
+        ParameterValue::File { value, .. } => value.to_string(),
+        ParameterValue::Reference { fallback, .. } => value_text(fallback),
+        ParameterValue::Empty => String::new(),
+    }
+}
+
+/// One draw of the generators of the parameters of the operation, as text.
+///  The same seed draws the same values.
+pub fn draw_values(operation: &Operation, seed: u64) -> Vec<String> {
+    let mut runner = explore::seeded_runner(seed).into_inner();
+    match amos_generation::gen_param_array(&operation.parameters).new_tree(&mut runner) {
+        Ok(tree) => tree.current().iter().map(value_text).collect(),
+        Err(_) => vec![String::new(); operation.parameters.len()],
+    }
+}
+
+/// The text of the value of the parameter as a value of its schema
+fn parse_value(param: &Parameter, text: &str) -> Option<ParameterValue> {
+    let value = match param.schema {
+        Schema::IPV4 => {
+            let [a, b, c, d] = text.trim().parse::<Ipv4Addr>().ok()?.octets();
+            ParameterValue::IPV4Value {
+                value: (a, b, c, d),
+                seed: 0,
+                active: false,
+            }
+        }
+        Schema::ArrayOfString => ParameterValue::ArrayOfString {
+            value: text
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .collect(),
+            seed: 0,
+            active: false,
+        },
+        Schema::File => ParameterValue::File {
+            value: text.trim().parse().ok()?,
+            seed: 0,
+            active: false,
+        },
+        _ => example_value(&param.schema, text)?,
+    };
+
+    Some(value)
+}
+
+/// The operation with the values filled in for its parameters. A parameter
+///  that is not required is left out when its value is empty.
+pub fn manual_operation(
+    operation: &Operation,
+    values: &[String],
+) -> Result<GeneratedOperation, String> {
+    let mut parameters = vec![];
+    for (param, text) in operation.parameters.iter().zip(values) {
+        if text.is_empty() && !param.required {
+            continue;
+        }
+
+        let value = parse_value(param, text).ok_or(format!(
+            "'{}' is not a value of {} for {}",
+            text, param.schema, param.name
+        ))?;
+        parameters.push(GeneratedParameter {
+            name: param.name.clone(),
+            value,
+            ref_path: None,
+        });
+    }
+
+    Ok(GeneratedOperation {
+        name: operation.info.name.clone(),
+        parameters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::{Operation, OperationInfo, Parameter, ParameterOwnership, Schema};
+    use crate::amos_generation::ParameterValue;
+    use crate::manual as sut;
+
+    fn param(name: &str, schema: Schema, required: bool) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            schema,
+            required,
+            ownership: ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: vec![],
+            relation_hints: vec![],
+            generator: None,
+        }
+    }
+
+    fn operation(parameters: Vec<Parameter>) -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters,
+            responses: vec![],
+            meta_data: None,
+        }
+    }
+
+    #[test]
+    fn drawn_values_are_filled_in_as_they_were_drawn() {
+        let op = operation(vec![
+            param("name", Schema::String, true),
+            param("limit", Schema::Int32, true),
+            param("ip", Schema::IPV4, true),
+        ]);
+
+        let values = sut::draw_values(&op, 3);
+        assert_eq!(values, sut::draw_values(&op, 3));
+
+        let generated = sut::manual_operation(&op, &values).unwrap();
+        let texts: Vec<String> = generated
+            .parameters
+            .iter()
+            .map(|p| sut::value_text(&p.value))
+            .collect();
+        assert_eq!(texts, values);
+    }
+
+    #[test]
+    fn filled_in_values_are_values_of_the_schema() {
+        let op = operation(vec![
+            param("limit", Schema::Int32, true),
+            param("tags", Schema::ArrayOfString, false),
+            param("ip", Schema::IPV4, false),
+        ]);
+
+        let generated =
+            sut::manual_operation(&op, &["10".to_string(), "a, b".to_string(), String::new()])
+                .unwrap();
+        assert_eq!(generated.parameters.len(), 2);
+        assert_eq!(generated.parameters[0].value.as_int_value(), 10);
+        assert!(matches!(
+            &generated.parameters[1].value,
+            ParameterValue::ArrayOfString { value, .. } if value == &["a", "b"]
+        ));
+
+        assert_eq!(
+            sut::manual_operation(&op, &["ten".to_string(), String::new(), String::new()]),
+            Err("'ten' is not a value of Int32 for limit".to_string())
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use crate::amos::{InvokeResult, ResultMetaData};
+use crate::cache::CacheOutcome;
+use crate::conditional;
+use crate::exploration_settings::{PayloadLimits, ResponseTimeSla, StatusClass};
+use crate::idempotency;
+use crate::injection;
+use crate::json_diff;
+use crate::security_audit;
 
-use qr_http_resource::http::HTTPStatus;
+use qr_http_resource::http::{HTTPStatus, TransportError};
 
 pub fn check_response_inequality(invocation_result: &[InvokeResult]) -> bool {
     invocation_result
@@ -15,21 +22,39 @@ pub fn check_response_equality(invocation_result: &[InvokeResult]) -> bool {
 pub fn check_state_mutation(invocation_result: &[InvokeResult]) -> bool {
     // TODO: query op should be inserted first and last
 
-    invocation_result
+    // The query operation results and any state observations are checked apart
+    by_operation(invocation_result)
         .iter()
-        .all(|res| res == &invocation_result[0])
+        .all(|results| results.iter().all(|res| *res == results[0]))
 }
 
 pub fn check_state_identity_with_observation(invocation_result: &[InvokeResult]) -> bool {
-    // first and last should be equal, the 'identity'
-    if invocation_result.len() > 1
-        && invocation_result[0] == invocation_result[invocation_result.len() - 1]
-    {
-        // check for an observation of a state change, 'mutation'
-        check_state_mutation(invocation_result)
-    } else {
-        true
+    by_operation(invocation_result).iter().all(|results| {
+        // first and last should be equal, the 'identity'
+        if results.len() > 1 && results[0] == results[results.len() - 1] {
+            // check for an observation of a state change, 'mutation'
+            results.iter().all(|res| *res == results[0])
+        } else {
+            true
+        }
+    })
+}
+
+/// The results grouped by operation, in order of the first result of each
+fn by_operation(invocation_result: &[InvokeResult]) -> Vec<Vec<&InvokeResult>> {
+    let mut groups: Vec<Vec<&InvokeResult>> = vec![];
+
+    for result in invocation_result {
+        match groups
+            .iter_mut()
+            .find(|group| group[0].operation.name == result.operation.name)
+        {
+            Some(group) => group.push(result),
+            None => groups.push(vec![result]),
+        }
     }
+
+    groups
 }
 
 ////////////////////////////////////////
@@ -50,6 +75,128 @@ pub fn check_response(invocation_result: &[InvokeResult]) -> bool {
     true
 }
 
+/// If the status of the result is of one of the failing classes
+pub fn has_failing_status(classes: &[StatusClass], result: &InvokeResult) -> bool {
+    match &result.meta_data {
+        Some(ResultMetaData::HTTP { status, .. }) => {
+            classes.iter().any(|class| class.includes(status))
+        }
+        None => false,
+    }
+}
+
+/// If the result fails when stressed with large payloads, a failing status
+///  other than 413 Payload Too Large or a request that timed out. Rejecting
+///  a payload as too large is what a SUT is expected to do.
+pub fn fails_under_stress(classes: &[StatusClass], result: &InvokeResult) -> bool {
+    match &result.meta_data {
+        Some(ResultMetaData::HTTP {
+            status: HTTPStatus::PayloadTooLarge,
+            ..
+        }) => false,
+        _ => {
+            result.transport_error == Some(TransportError::Timeout)
+                || has_failing_status(classes, result)
+        }
+    }
+}
+
+/// The result without the fields of the names in its JSON payload, the size
+///  is the size of what is left. A payload that is not JSON is kept as is.
+pub fn without_fields(result: &InvokeResult, fields: &[String]) -> InvokeResult {
+    let mut result = result.clone();
+    let Ok(mut payload) = serde_json::from_str::<serde_json::Value>(&result.result) else {
+        return result;
+    };
+
+    json_diff::remove_fields(&mut payload, fields);
+    result.result = payload.to_string();
+    if let Some(ResultMetaData::HTTP { size, .. }) = &mut result.meta_data {
+        *size = result.result.len();
+    }
+    result
+}
+
+pub fn check_response_time(sla: &ResponseTimeSla, invocation_result: &[InvokeResult]) -> bool {
+    !invocation_result
+        .iter()
+        .any(|res| exceeds_response_time(sla, res))
+}
+
+/// If the SUT took longer than the threshold of the operation to respond,
+///  results without a duration are never too slow
+pub fn exceeds_response_time(sla: &ResponseTimeSla, result: &InvokeResult) -> bool {
+    result
+        .duration
+        .is_some_and(|duration| sla.is_exceeded(&result.operation.name, duration))
+}
+
+/// If the operation succeeded in the results of a replay with lesser credentials
+pub fn bypasses_auth(operation: &str, invocation_result: &[InvokeResult]) -> bool {
+    invocation_result
+        .iter()
+        .any(|res| res.operation.name == operation && res.success)
+}
+
+/// Passes if the operation of results replayed with lesser credentials is
+///  never authorized
+pub fn check_auth_bypass(operation: &str, invocation_result: &[InvokeResult]) -> bool {
+    !bypasses_auth(operation, invocation_result)
+}
+
+pub fn check_injection(invocation_result: &[InvokeResult]) -> bool {
+    !invocation_result
+        .iter()
+        .any(injection::is_injection_failure)
+}
+
+pub fn check_security_headers(invocation_result: &[InvokeResult]) -> bool {
+    invocation_result
+        .iter()
+        .all(|res| security_audit::audit(res).is_empty())
+}
+
+pub fn check_payload(limits: &PayloadLimits, invocation_result: &[InvokeResult]) -> bool {
+    !invocation_result
+        .iter()
+        .any(|res| exceeds_payload(limits, res))
+}
+
+pub fn check_stale_cache(invocation_result: &[InvokeResult]) -> bool {
+    !invocation_result.iter().any(serves_stale_cache)
+}
+
+/// If the SUT responded to the retried POST as to the request it retries
+pub fn check_idempotent_retry(invocation_result: &[InvokeResult]) -> bool {
+    idempotency::duplicate(invocation_result).is_none()
+}
+
+/// If the SUT rejected the update replayed with a stale ETag
+pub fn check_conditional_request(
+    accept_conflict: bool,
+    invocation_result: &[InvokeResult],
+) -> bool {
+    conditional::stale_update(invocation_result, accept_conflict).is_none()
+}
+
+/// If the response is the state from before a mutation of the resource,
+///  e.g. a 304 to the revalidation of the response cached before it
+pub fn serves_stale_cache(result: &InvokeResult) -> bool {
+    matches!(result.cache, Some(CacheOutcome::Stale { .. }))
+}
+
+/// If the payload is too large, or is not compressed though it should be
+pub fn exceeds_payload(limits: &PayloadLimits, result: &InvokeResult) -> bool {
+    match &result.meta_data {
+        Some(ResultMetaData::HTTP {
+            size,
+            content_encoding,
+            ..
+        }) => limits.is_exceeded(*size, content_encoding.as_deref()),
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -72,6 +219,9 @@ mod tests {
             Some(ResultMetaData::HTTP {
                 url: "udddrl".to_string(),
                 status: http::HTTPStatus::OK,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
             }),
         );
         let res_2 = InvokeResult::new(
@@ -84,6 +234,9 @@ mod tests {
             Some(ResultMetaData::HTTP {
                 url: "udddrl".to_string(),
                 status: http::HTTPStatus::OK,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
             }),
         );
 
@@ -91,7 +244,7 @@ mod tests {
 
         let res = sut::check_response_equality(&results);
 
-        assert_eq!(res, false)
+        assert!(!res)
     }
 
     #[test]
@@ -106,6 +259,9 @@ mod tests {
             Some(ResultMetaData::HTTP {
                 url: "udddrl".to_string(),
                 status: http::HTTPStatus::OK,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
             }),
         );
         let res_2 = InvokeResult::new(
@@ -118,6 +274,9 @@ mod tests {
             Some(ResultMetaData::HTTP {
                 url: "udddrl".to_string(),
                 status: http::HTTPStatus::OK,
+                size: 0,
+                content_encoding: None,
+                headers: vec![],
             }),
         );
 
@@ -125,6 +284,152 @@ mod tests {
 
         let res = sut::check_response_equality(&results);
 
-        assert_eq!(res, true)
+        assert!(res)
+    }
+
+    fn result(name: &str, payload: &str) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            true,
+            None,
+        )
+    }
+
+    #[test]
+    fn check_state_mutation_per_operation() {
+        // The query and the observation differ from each other, but neither changes
+        let unchanged = vec![
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+        ];
+        assert!(sut::check_state_mutation(&unchanged));
+
+        // Only the observation sees the mutation
+        let observed = vec![
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+            result("get_persons", "[]"),
+            result("state_observation", "{\"persons\":1}"),
+        ];
+        assert!(!sut::check_state_mutation(&observed));
+    }
+
+    #[test]
+    fn check_state_identity_per_operation() {
+        let identity = vec![
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+            result("get_persons", "[]"),
+            result("state_observation", "{\"persons\":1}"),
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+        ];
+        assert!(!sut::check_state_identity_with_observation(&identity));
+
+        let no_identity = vec![
+            result("get_persons", "[]"),
+            result("state_observation", "{}"),
+            result("get_persons", "[]"),
+            result("state_observation", "{\"persons\":1}"),
+        ];
+        assert!(sut::check_state_identity_with_observation(&no_identity));
+    }
+
+    #[test]
+    fn check_response_time_against_the_sla() {
+        let mut sla = crate::exploration_settings::ResponseTimeSla::new();
+        sla.global_ms = 100;
+        sla.operations_ms.insert("post_report".to_string(), 1000);
+
+        let slow = std::time::Duration::from_millis(500);
+        let results = vec![
+            result("post_report", "{}").with_duration(slow),
+            // A result without a duration, e.g. an observation
+            result("state_observation", "{}"),
+        ];
+        assert!(sut::check_response_time(&sla, &results));
+
+        let results = vec![result("get_persons", "[]").with_duration(slow)];
+        assert!(!sut::check_response_time(&sla, &results));
+    }
+
+    #[test]
+    fn a_payload_too_large_does_not_fail_under_stress() {
+        let operation = || GeneratedOperation {
+            name: "post_person".to_string(),
+            parameters: vec![],
+        };
+        let result = |status| {
+            InvokeResult::new(
+                operation(),
+                "".to_string(),
+                true,
+                Some(ResultMetaData::HTTP {
+                    url: "url".to_string(),
+                    status,
+                    size: 0,
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            )
+        };
+        let classes = crate::exploration_settings::StatusClass::ALL;
+
+        assert!(!sut::fails_under_stress(
+            &classes,
+            &result(http::HTTPStatus::PayloadTooLarge)
+        ));
+        assert!(!sut::fails_under_stress(
+            &classes,
+            &result(http::HTTPStatus::Created)
+        ));
+        assert!(sut::fails_under_stress(
+            &classes,
+            &result(http::HTTPStatus::InternalServerError)
+        ));
+        assert!(sut::fails_under_stress(
+            &classes,
+            &InvokeResult::transport_failure(operation(), http::TransportError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn compare_the_responses_without_the_ignored_fields() {
+        let result = |payload: &str| {
+            InvokeResult::new(
+                GeneratedOperation {
+                    name: "get_persons".to_string(),
+                    parameters: vec![],
+                },
+                payload.to_string(),
+                true,
+                Some(ResultMetaData::HTTP {
+                    url: "url".to_string(),
+                    status: http::HTTPStatus::OK,
+                    size: payload.len(),
+                    content_encoding: None,
+                    headers: vec![],
+                }),
+            )
+        };
+        let results = vec![
+            result(r#"{"name": "a", "updated": 9}"#),
+            result(r#"{"name": "a", "updated": 10}"#),
+        ];
+        assert!(!sut::check_response_inequality(&results));
+
+        let fields = vec!["updated".to_string()];
+        let without: Vec<InvokeResult> = results
+            .iter()
+            .map(|res| sut::without_fields(res, &fields))
+            .collect();
+        assert!(sut::check_response_inequality(&without));
+        assert_eq!(without[0].result, r#"{"name":"a"}"#);
     }
 }
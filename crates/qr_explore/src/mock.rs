@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use qr_http_resource::http::{HTTPCall, HTTPMethod, HTTPResult, HTTPStatus};
+
+use crate::exploration_settings::{CannedResponse, MockSettings};
+
+/// The payload of the operations without a canned response or an emulation
+pub const FIXED_PAYLOAD: &str = "[\"Fake result\"]";
+
+#[derive(Debug, Default)]
+struct Collection {
+    last_id: u64,
+    resources: BTreeMap<u64, serde_json::Value>,
+}
+
+/// An in-process SUT answering the requests of dry runs. An operation with a
+///  canned response always gets it. Otherwise, if CRUD is emulated, a POST to
+///  a path creates a resource with the next id, and the path with the id
+///  reads, replaces or deletes it. Anything else gets a fixed payload.
+#[derive(Debug)]
+pub struct MockSut {
+    settings: MockSettings,
+    collections: Mutex<BTreeMap<String, Collection>>,
+}
+
+impl MockSut {
+    pub fn new(settings: MockSettings) -> Self {
+        MockSut {
+            settings,
+            collections: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn respond(&self, operation: &str, call: &HTTPCall) -> HTTPResult {
+        if let Some(canned) = self.settings.responses.get(operation) {
+            return canned_result(canned);
+        }
+        if self.settings.crud {
+            return self.emulate(call);
+        }
+
+        result(HTTPStatus::OK, FIXED_PAYLOAD.to_string())
+    }
+
+    fn emulate(&self, call: &HTTPCall) -> HTTPResult {
+        let path = call.parameters.url.split('?').next().unwrap_or_default();
+        let path = path.trim_end_matches('/');
+        let item = path
+            .rsplit_once('/')
+            .and_then(|(collection, id)| Some((collection, id.parse::<u64>().ok()?)));
+
+        let mut collections = self.collections.lock().unwrap();
+        match (&call.method, item) {
+            (HTTPMethod::POST, None) => {
+                let collection = collections.entry(path.to_string()).or_default();
+                collection.last_id += 1;
+                let resource = with_id(body(call), collection.last_id);
+                collection
+                    .resources
+                    .insert(collection.last_id, resource.clone());
+                result(HTTPStatus::Created, resource.to_string())
+            }
+            (HTTPMethod::GET, None) => {
+                let resources: Vec<serde_json::Value> = collections
+                    .get(path)
+                    .map(|collection| collection.resources.values().cloned().collect())
+                    .unwrap_or_default();
+                result(
+                    HTTPStatus::OK,
+                    serde_json::Value::Array(resources).to_string(),
+                )
+            }
+            (method, Some((collection, id))) => {
+                let resources = collections
+                    .get_mut(collection)
+                    .map(|collection| &mut collection.resources);
+                let Some(resources) = resources.filter(|r| r.contains_key(&id)) else {
+                    return result(HTTPStatus::NotFound, String::new());
+                };
+
+                match method {
+                    HTTPMethod::GET => result(HTTPStatus::OK, resources[&id].to_string()),
+                    HTTPMethod::PUT => {
+                        let resource = with_id(body(call), id);
+                        resources.insert(id, resource.clone());
+                        result(HTTPStatus::OK, resource.to_string())
+                    }
+                    HTTPMethod::DELETE => {
+                        resources.remove(&id);
+                        result(HTTPStatus::NoContent, String::new())
+                    }
+                    _ => result(HTTPStatus::MethodNotAllowed, String::new()),
+                }
+            }
+            _ => result(HTTPStatus::MethodNotAllowed, String::new()),
+        }
+    }
+}
+
+/// The body parameters of the call, as a JSON object
+fn body(call: &HTTPCall) -> serde_json::Map<String, serde_json::Value> {
+    call.parameters
+        .body
+        .iter()
+        .flatten()
+        .map(|(name, value)| {
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.clone()));
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn with_id(mut resource: serde_json::Map<String, serde_json::Value>, id: u64) -> serde_json::Value {
+    resource.insert("id".to_string(), serde_json::Value::from(id));
+    serde_json::Value::Object(resource)
+}
+
+fn result(status: HTTPStatus, payload: String) -> HTTPResult {
+    HTTPResult {
+        success: matches!(
+            status,
+            HTTPStatus::OK | HTTPStatus::Created | HTTPStatus::NoContent
+        ),
+        status,
+        size: payload.len(),
+        payload,
+        content_encoding: None,
+        headers: vec![],
+    }
+}
+
+fn canned_result(canned: &CannedResponse) -> HTTPResult {
+    HTTPResult {
+        headers: canned
+            .headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect(),
+        success: (200..300).contains(&canned.status),
+        ..result(HTTPStatus::from_code(canned.status), canned.body.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use qr_http_resource::http::{HTTPCall, HTTPMethod, HTTPParameters, HTTPStatus};
+
+    use crate::exploration_settings::{CannedResponse, MockSettings};
+    use crate::mock as sut;
+
+    fn call(method: HTTPMethod, url: &str, body: &[(&str, &str)]) -> HTTPCall {
+        HTTPCall {
+            url: format!("http://localhost:80{}", url),
+            method,
+            parameters: HTTPParameters {
+                url: url.to_string(),
+                form_data: None,
+                file_data: None,
+                body: (!body.is_empty()).then(|| {
+                    body.iter()
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect::<HashMap<String, String>>()
+                }),
+            },
+            headers: vec![],
+        }
+    }
+
+    #[test]
+    fn canned_responses_come_first() {
+        let mock = sut::MockSut::new(MockSettings {
+            responses: BTreeMap::from([(
+                "get_persons".to_string(),
+                CannedResponse {
+                    status: 500,
+                    body: "oops".to_string(),
+                    headers: BTreeMap::from([("X-Trace".to_string(), "1".to_string())]),
+                },
+            )]),
+            crud: true,
+        });
+
+        let result = mock.respond("get_persons", &call(HTTPMethod::GET, "/persons", &[]));
+        assert_eq!(result.status, HTTPStatus::InternalServerError);
+        assert!(!result.success);
+        assert_eq!(result.payload, "oops");
+        assert_eq!(
+            result.headers,
+            vec![("x-trace".to_string(), "1".to_string())]
+        );
+
+        let result = mock.respond("get_orders", &call(HTTPMethod::GET, "/orders", &[]));
+        assert_eq!(result.payload, "[]");
+    }
+
+    #[test]
+    fn crud_emulates_collections() {
+        let mock = sut::MockSut::new(MockSettings {
+            crud: true,
+            ..MockSettings::new()
+        });
+        let respond = |method, url, body| mock.respond("op", &call(method, url, body));
+
+        let created = respond(HTTPMethod::POST, "/persons", &[("name", "\"Ada\"")]);
+        assert_eq!(created.status, HTTPStatus::Created);
+        assert_eq!(created.payload, r#"{"id":1,"name":"Ada"}"#);
+        respond(HTTPMethod::POST, "/persons", &[("age", "36")]);
+
+        let listed = respond(HTTPMethod::GET, "/persons?limit=10", &[]);
+        assert_eq!(
+            listed.payload,
+            r#"[{"id":1,"name":"Ada"},{"age":36,"id":2}]"#
+        );
+
+        let replaced = respond(HTTPMethod::PUT, "/persons/2", &[("age", "37")]);
+        assert_eq!(replaced.payload, r#"{"age":37,"id":2}"#);
+
+        assert_eq!(
+            respond(HTTPMethod::DELETE, "/persons/1", &[]).status,
+            HTTPStatus::NoContent
+        );
+        assert_eq!(
+            respond(HTTPMethod::GET, "/persons/1", &[]).status,
+            HTTPStatus::NotFound
+        );
+        assert_eq!(
+            respond(HTTPMethod::GET, "/persons", &[]).payload,
+            r#"[{"age":37,"id":2}]"#
+        );
+    }
+
+    #[test]
+    fn without_settings_every_response_is_fixed() {
+        let mock = sut::MockSut::new(MockSettings::new());
+
+        let result = mock.respond("post_person", &call(HTTPMethod::POST, "/persons", &[]));
+        assert_eq!(result.status, HTTPStatus::OK);
+        assert_eq!(result.payload, sut::FIXED_PAYLOAD);
+    }
+}
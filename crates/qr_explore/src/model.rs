@@ -0,0 +1,548 @@
+use std::cell::RefCell;
+
+use crate::amos::InvokeResult;
+
+#[derive(Debug, PartialEq)]
+pub enum ModelError {
+    InvalidLine { line: usize, reason: String },
+}
+
+impl std::fmt::Display for ModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::InvalidLine { line, reason } => {
+                write!(f, "Invalid model at line {}: {}", line, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    const ALL: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ];
+
+    fn holds(&self, left: i64, right: i64) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    State(String),
+    Value(i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Clause {
+    /// The status of the response, e.g. '201' or '2xx'
+    Status(String),
+    /// The number of items of the response array
+    Length {
+        comparison: Comparison,
+        operand: Operand,
+    },
+    Add {
+        state: String,
+        value: i64,
+    },
+    Set {
+        state: String,
+        value: i64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    operation: String,
+    when: Option<String>,
+    clauses: Vec<Clause>,
+    /// The clauses as written, for the divergences
+    text: String,
+}
+
+/// The first step of a sequence where the SUT did not respond as modelled
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Divergence {
+    /// The index of the operation in the sequence
+    pub step: usize,
+    pub operation: String,
+    pub rule: String,
+    pub expected: String,
+    pub observed: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Step {} ({}): expected {}, observed {}",
+            self.step + 1,
+            self.operation,
+            self.expected,
+            self.observed
+        )
+    }
+}
+
+/// An explicit model of the abstract state of the SUT, checked against the
+///  responses of each operation. A model is written as lines of
+///
+/// ```text
+/// # The persons of the SUT, unknown until observed
+/// state persons
+/// on post_person when 201: persons += 1
+/// on delete_person when 2xx: persons -= 1
+/// on get_persons: status 200, length == persons
+/// ```
+///
+/// A rule applies to the responses of its operation, with a status matching
+///  the `when` pattern if it has one. The expectations of the rule, the
+///  status and the length of the response array, are checked before the
+///  state is updated. Expecting an unknown state to equal the length sets it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    /// The states and their initial values, `None` until observed
+    states: Vec<(String, Option<i64>)>,
+    rules: Vec<Rule>,
+}
+
+impl Model {
+    pub fn parse(source: &str) -> Result<Model, ModelError> {
+        let mut model = Model {
+            states: vec![],
+            rules: vec![],
+        };
+
+        for (idx, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason: &str| ModelError::InvalidLine {
+                line: idx + 1,
+                reason: reason.to_string(),
+            };
+
+            if let Some(state) = line.strip_prefix("state ") {
+                let (name, initial) = match state.split_once('=') {
+                    Some((name, value)) => {
+                        let value = value
+                            .trim()
+                            .parse()
+                            .map_err(|_| invalid("the initial value must be an integer"))?;
+                        (name.trim(), Some(value))
+                    }
+                    None => (state.trim(), None),
+                };
+                if !is_identifier(name) {
+                    return Err(invalid("expected a state name"));
+                }
+                model.states.push((name.to_string(), initial));
+            } else if let Some(rule) = line.strip_prefix("on ") {
+                let rule = model.parse_rule(rule).map_err(|reason| invalid(&reason))?;
+                model.rules.push(rule);
+            } else {
+                return Err(invalid("expected 'state' or 'on'"));
+            }
+        }
+
+        Ok(model)
+    }
+
+    fn parse_rule(&self, rule: &str) -> Result<Rule, String> {
+        let (head, body) = rule
+            .split_once(':')
+            .ok_or("expected ':' after the operation")?;
+
+        let (operation, when) = match head.split_once(" when ") {
+            Some((operation, status)) => (operation.trim(), Some(parse_status(status.trim())?)),
+            None => (head.trim(), None),
+        };
+        if operation.is_empty() {
+            return Err("expected an operation".to_string());
+        }
+
+        let clauses = body
+            .split(',')
+            .map(|clause| self.parse_clause(clause.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Rule {
+            operation: operation.to_string(),
+            when,
+            clauses,
+            text: format!("on {}:{}", head.trim(), body),
+        })
+    }
+
+    fn parse_clause(&self, clause: &str) -> Result<Clause, String> {
+        if let Some(status) = clause.strip_prefix("status ") {
+            return Ok(Clause::Status(parse_status(status.trim())?));
+        }
+
+        if let Some(length) = clause.strip_prefix("length") {
+            let length = length.trim();
+            let (token, comparison) = Comparison::ALL
+                .into_iter()
+                .find(|(token, _)| length.starts_with(token))
+                .ok_or("expected a comparison after 'length'")?;
+            let operand = length[token.len()..].trim();
+            let operand = match operand.parse() {
+                Ok(value) => Operand::Value(value),
+                Err(_) => Operand::State(self.state_name(operand)?),
+            };
+            return Ok(Clause::Length {
+                comparison,
+                operand,
+            });
+        }
+
+        for (token, sign) in [("+=", 1), ("-=", -1), ("=", 0)] {
+            if let Some((state, value)) = clause.split_once(token) {
+                let state = self.state_name(state.trim())?;
+                let value: i64 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("expected an integer in '{}'", clause))?;
+                return Ok(match sign {
+                    0 => Clause::Set { state, value },
+                    sign => Clause::Add {
+                        state,
+                        value: sign * value,
+                    },
+                });
+            }
+        }
+
+        Err(format!("unknown clause '{}'", clause))
+    }
+
+    fn state_name(&self, name: &str) -> Result<String, String> {
+        if self.states.iter().any(|(state, _)| state == name) {
+            Ok(name.to_string())
+        } else {
+            Err(format!("unknown state '{}'", name))
+        }
+    }
+
+    /// The operations the rules are about
+    pub fn operations(&self) -> Vec<&str> {
+        let mut operations: Vec<&str> = vec![];
+        for rule in &self.rules {
+            if !operations.contains(&rule.operation.as_str()) {
+                operations.push(&rule.operation);
+            }
+        }
+        operations
+    }
+
+    /// Check the results of a sequence from the initial states. Results
+    ///  without a response say nothing of the SUT and are skipped.
+    pub fn check(&self, results: &[InvokeResult]) -> Option<Divergence> {
+        let mut states: Vec<(&str, Option<i64>)> = self
+            .states
+            .iter()
+            .map(|(name, initial)| (name.as_str(), *initial))
+            .collect();
+
+        for (step, result) in results.iter().enumerate() {
+            if result.transport_error.is_some() {
+                continue;
+            }
+            let status = status_of(result);
+
+            for rule in self
+                .rules
+                .iter()
+                .filter(|rule| rule.operation == result.operation.name)
+            {
+                if rule
+                    .when
+                    .as_ref()
+                    .is_some_and(|when| !status_matches(when, &status))
+                {
+                    continue;
+                }
+
+                let diverged = |expected: String, observed: String| Divergence {
+                    step,
+                    operation: rule.operation.clone(),
+                    rule: rule.text.clone(),
+                    expected,
+                    observed,
+                };
+
+                for clause in &rule.clauses {
+                    match clause {
+                        Clause::Status(expected) if !status_matches(expected, &status) => {
+                            return Some(diverged(
+                                format!("status {}", expected),
+                                format!("status {}", status),
+                            ));
+                        }
+                        Clause::Length {
+                            comparison,
+                            operand,
+                        } => {
+                            let Some(length) = array_length(&result.result) else {
+                                return Some(diverged(
+                                    "an array".to_string(),
+                                    "no array".to_string(),
+                                ));
+                            };
+                            let expected = match operand {
+                                Operand::Value(value) => Some(*value),
+                                Operand::State(name) => state_value(&states, name),
+                            };
+                            match expected {
+                                Some(expected) if !comparison.holds(length, expected) => {
+                                    let token = Comparison::ALL
+                                        .iter()
+                                        .find(|(_, c)| c == comparison)
+                                        .map_or("", |(token, _)| token);
+                                    return Some(diverged(
+                                        format!("length {} {}", token, expected),
+                                        format!("length {}", length),
+                                    ));
+                                }
+                                Some(_) => (),
+                                // An unknown state is learnt from the SUT
+                                None => {
+                                    if let (Comparison::Eq, Operand::State(name)) =
+                                        (comparison, operand)
+                                    {
+                                        set_state(&mut states, name, Some(length));
+                                    }
+                                }
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
+                for clause in &rule.clauses {
+                    match clause {
+                        Clause::Add { state, value } => {
+                            let updated = state_value(&states, state).map(|v| v + value);
+                            set_state(&mut states, state, updated);
+                        }
+                        Clause::Set { state, value } => {
+                            set_state(&mut states, state, Some(*value));
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The model of an exploration, with the divergence of the last sequence
+///  that diverged from it
+#[derive(Debug)]
+pub struct ModelChecker {
+    pub model: Model,
+    last_divergence: RefCell<Option<Divergence>>,
+}
+
+impl ModelChecker {
+    pub fn new(model: Model) -> Self {
+        ModelChecker {
+            model,
+            last_divergence: RefCell::new(None),
+        }
+    }
+
+    /// The results up to and including the first divergent step, none if
+    ///  the sequence followed the model
+    pub fn divergent_results(&self, results: Vec<InvokeResult>) -> Vec<InvokeResult> {
+        match self.model.check(&results) {
+            Some(divergence) => {
+                let step = divergence.step;
+                *self.last_divergence.borrow_mut() = Some(divergence);
+                results.into_iter().take(step + 1).collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// Taken by the behaviour once a sequence is shrunk, the last sequence
+    ///  that diverged is the minimal one
+    pub fn take_divergence(&self) -> Option<Divergence> {
+        self.last_divergence.borrow_mut().take()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// A status pattern is three digits, where 'x' matches any digit
+fn parse_status(status: &str) -> Result<String, String> {
+    let status = status.to_lowercase();
+    if status.len() == 3 && status.chars().all(|c| c.is_ascii_digit() || c == 'x') {
+        Ok(status)
+    } else {
+        Err(format!("invalid status '{}', e.g. '201' or '2xx'", status))
+    }
+}
+
+fn status_matches(pattern: &str, status: &str) -> bool {
+    pattern.len() == status.len()
+        && pattern
+            .chars()
+            .zip(status.chars())
+            .all(|(p, s)| p == 'x' || p == s)
+}
+
+fn status_of(result: &InvokeResult) -> String {
+    match &result.meta_data {
+        Some(crate::amos::ResultMetaData::HTTP { status, .. }) => status.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+pub(crate) fn array_length(payload: &str) -> Option<i64> {
+    match serde_json::from_str(payload).ok()? {
+        serde_json::Value::Array(items) => Some(items.len() as i64),
+        _ => None,
+    }
+}
+
+fn state_value(states: &[(&str, Option<i64>)], name: &str) -> Option<i64> {
+    states
+        .iter()
+        .find(|(state, _)| *state == name)
+        .and_then(|(_, value)| *value)
+}
+
+fn set_state(states: &mut [(&str, Option<i64>)], name: &str, value: Option<i64>) {
+    if let Some((_, state)) = states.iter_mut().find(|(state, _)| *state == name) {
+        *state = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos::ResultMetaData;
+    use crate::amos_generation::GeneratedOperation;
+    use qr_http_resource::http::HTTPStatus;
+
+    const PERSONS: &str = "
+        # Persons, unknown until listed
+        state persons
+        on post_person when 201: persons += 1
+        on delete_person when 2xx: persons -= 1
+        on get_persons: status 200, length == persons
+    ";
+
+    fn result(operation: &str, status: HTTPStatus, payload: &str) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: operation.to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            true,
+            Some(ResultMetaData::HTTP {
+                url: "/persons".to_string(),
+                status,
+                size: payload.len(),
+                content_encoding: None,
+                headers: vec![],
+            }),
+        )
+    }
+
+    #[test]
+    fn parse_model() {
+        let model = Model::parse(PERSONS).unwrap();
+
+        assert_eq!(model.states, vec![("persons".to_string(), None)]);
+        assert_eq!(
+            model.operations(),
+            vec!["post_person", "delete_person", "get_persons"]
+        );
+
+        assert_eq!(
+            Model::parse("state persons\non get_persons: length == people"),
+            Err(ModelError::InvalidLine {
+                line: 2,
+                reason: "unknown state 'people'".to_string()
+            })
+        );
+        assert!(Model::parse("on get_persons when 2?0: status 200").is_err());
+        assert!(Model::parse("persons = 1").is_err());
+    }
+
+    #[test]
+    fn check_follows_the_model() {
+        let model = Model::parse(PERSONS).unwrap();
+
+        let results = vec![
+            result("get_persons", HTTPStatus::OK, "[1]"),
+            result("post_person", HTTPStatus::Created, "{}"),
+            // Not a success, the state is left as is
+            result("post_person", HTTPStatus::BadRequest, "{}"),
+            result("get_persons", HTTPStatus::OK, "[1, 2]"),
+            result("delete_person", HTTPStatus::NoContent, ""),
+            result("get_persons", HTTPStatus::OK, "[1]"),
+        ];
+        assert_eq!(model.check(&results), None);
+    }
+
+    #[test]
+    fn check_reports_the_first_divergence() {
+        let model = Model::parse(PERSONS).unwrap();
+
+        let results = vec![
+            result("get_persons", HTTPStatus::OK, "[]"),
+            result("post_person", HTTPStatus::Created, "{}"),
+            // The created person is not listed
+            result("get_persons", HTTPStatus::OK, "[]"),
+            result("get_persons", HTTPStatus::InternalServerError, "[]"),
+        ];
+        let divergence = model.check(&results).unwrap();
+
+        assert_eq!(divergence.step, 2);
+        assert_eq!(divergence.expected, "length == 1");
+        assert_eq!(divergence.observed, "length 0");
+        assert_eq!(
+            divergence.to_string(),
+            "Step 3 (get_persons): expected length == 1, observed length 0"
+        );
+
+        let checker = ModelChecker::new(model);
+        assert_eq!(checker.divergent_results(results).len(), 3);
+        assert_eq!(checker.take_divergence(), Some(divergence));
+        assert_eq!(checker.take_divergence(), None);
+    }
+}
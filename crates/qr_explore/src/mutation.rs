@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use proptest::prelude::Rng;
+
+use crate::amos_generation::GenerationOperationWithParameters;
+
+pub type SharedMutation = Arc<Mutex<SequenceMutation>>;
+
+/// How a sequence generated earlier is changed into a new one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MutationOperator {
+    /// Swap two adjacent operations, the order of the operations matters
+    SwapAdjacent,
+    /// Invoke an operation twice in a row
+    Duplicate,
+    /// Drop the operations that set up the state of the later ones
+    DropPrefix,
+    /// The values of an operation as another sequence had them
+    ReuseValues,
+}
+
+impl MutationOperator {
+    pub const ALL: [MutationOperator; 4] = [
+        MutationOperator::SwapAdjacent,
+        MutationOperator::Duplicate,
+        MutationOperator::DropPrefix,
+        MutationOperator::ReuseValues,
+    ];
+
+    /// The sequence changed at the position, the position wraps around the
+    ///  sequence. `None` if the operator does not apply to the sequence.
+    pub fn apply(
+        &self,
+        sequence: &[GenerationOperationWithParameters],
+        position: usize,
+        other: &[GenerationOperationWithParameters],
+    ) -> Option<Vec<GenerationOperationWithParameters>> {
+        if sequence.is_empty() {
+            return None;
+        }
+
+        let mut mutated = sequence.to_vec();
+        match self {
+            MutationOperator::SwapAdjacent => {
+                if sequence.len() < 2 {
+                    return None;
+                }
+                let idx = position % (sequence.len() - 1);
+                mutated.swap(idx, idx + 1);
+            }
+            MutationOperator::Duplicate => {
+                let idx = position % sequence.len();
+                mutated.insert(idx + 1, sequence[idx].clone());
+            }
+            MutationOperator::DropPrefix => {
+                if sequence.len() < 2 {
+                    return None;
+                }
+                mutated.drain(0..=position % (sequence.len() - 1));
+            }
+            MutationOperator::ReuseValues => {
+                let idx = position % sequence.len();
+                let (op, values) = &mut mutated[idx];
+                let (_, reused) = other.iter().find(|(other_op, other_values)| {
+                    other_op.info.name == op.info.name && other_values != values
+                })?;
+                *values = reused.clone();
+            }
+        }
+
+        Some(mutated)
+    }
+}
+
+/// The most recent sequences generated in a run, mutated into new sequences
+///  instead of generating them from scratch
+#[derive(Debug, Default, Clone)]
+pub struct SequenceMutation {
+    sequences: VecDeque<Vec<GenerationOperationWithParameters>>,
+}
+
+impl SequenceMutation {
+    /// The number of sequences kept to mutate
+    pub const POOL_SIZE: usize = 32;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, sequence: Vec<GenerationOperationWithParameters>) {
+        if sequence.is_empty() {
+            return;
+        }
+
+        self.sequences.push_back(sequence);
+        if self.sequences.len() > SequenceMutation::POOL_SIZE {
+            self.sequences.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// One of the sequences, changed by one of the operators. `None` if
+    ///  there are no sequences or the operator does not apply.
+    pub fn mutate(&self, rng: &mut impl Rng) -> Option<Vec<GenerationOperationWithParameters>> {
+        if self.sequences.is_empty() {
+            return None;
+        }
+
+        let sequence = &self.sequences[rng.gen_range(0..self.sequences.len())];
+        let other = &self.sequences[rng.gen_range(0..self.sequences.len())];
+        let operator = MutationOperator::ALL[rng.gen_range(0..MutationOperator::ALL.len())];
+        operator.apply(sequence, rng.gen(), other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::{Operation, OperationInfo};
+    use crate::amos_generation::{GenerationOperationWithParameters, ParameterValue};
+    use crate::mutation as sut;
+    use crate::mutation::MutationOperator;
+
+    fn op(name: &str, value: i64) -> GenerationOperationWithParameters {
+        (
+            Operation {
+                info: OperationInfo {
+                    name: name.to_string(),
+                    key: format!("operation/{}", name),
+                    alias: None,
+                    deprecated: false,
+                },
+                parameters: vec![],
+                responses: vec![],
+                meta_data: None,
+            },
+            vec![ParameterValue::IntValue {
+                value,
+                seed: 0,
+                active: false,
+            }],
+        )
+    }
+
+    fn names(sequence: &[GenerationOperationWithParameters]) -> Vec<&str> {
+        sequence
+            .iter()
+            .map(|(op, _)| op.info.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn operators_change_the_order_and_values_of_the_sequence() {
+        let sequence = vec![op("post", 1), op("put", 2), op("delete", 3)];
+
+        let swapped = MutationOperator::SwapAdjacent
+            .apply(&sequence, 1, &[])
+            .unwrap();
+        assert_eq!(names(&swapped), vec!["post", "delete", "put"]);
+
+        let duplicated = MutationOperator::Duplicate
+            .apply(&sequence, 3, &[])
+            .unwrap();
+        assert_eq!(names(&duplicated), vec!["post", "post", "put", "delete"]);
+
+        // At least the last operation is kept
+        let dropped = MutationOperator::DropPrefix
+            .apply(&sequence, 1, &[])
+            .unwrap();
+        assert_eq!(names(&dropped), vec!["delete"]);
+
+        let other = vec![op("put", 7)];
+        let reused = MutationOperator::ReuseValues
+            .apply(&sequence, 1, &other)
+            .unwrap();
+        assert_eq!(reused[1].1, other[0].1);
+        assert_eq!(
+            MutationOperator::ReuseValues.apply(&sequence, 0, &other),
+            None
+        );
+
+        let single = vec![op("post", 1)];
+        assert_eq!(MutationOperator::SwapAdjacent.apply(&single, 0, &[]), None);
+        assert_eq!(MutationOperator::DropPrefix.apply(&single, 0, &[]), None);
+    }
+
+    #[test]
+    fn only_the_most_recent_sequences_are_mutated() {
+        let mut mutation = sut::SequenceMutation::new();
+        let mut runner = proptest::test_runner::TestRunner::deterministic();
+        assert_eq!(mutation.mutate(runner.rng()), None);
+
+        mutation.record(vec![]);
+        assert!(mutation.is_empty());
+
+        for value in 0..(sut::SequenceMutation::POOL_SIZE as i64 + 8) {
+            mutation.record(vec![op("post", value), op("put", value)]);
+        }
+        assert_eq!(mutation.len(), sut::SequenceMutation::POOL_SIZE);
+
+        for _ in 0..20 {
+            if let Some(mutated) = mutation.mutate(runner.rng()) {
+                assert!(mutated
+                    .iter()
+                    .all(|(_, values)| values[0].as_int_value() >= 8));
+            }
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::thread;
+
+use tracing::warn;
+
+use crate::explore::{ControlEvent, Event, ExplorationResult};
+
+/// How the user is notified when a run finishes or finds a counterexample
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NotificationSettings {
+    /// Show a native desktop notification
+    pub desktop: bool,
+    /// POST the notification as JSON, `{"text": ...}`, e.g. to a Slack webhook
+    pub webhook_url: Option<String>,
+}
+
+impl NotificationSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.desktop || self.webhook_url.as_ref().is_some_and(|url| !url.is_empty())
+    }
+}
+
+/// The notification message for an event, if the event is worth a notification
+pub fn notification_message(event: &Event) -> Option<String> {
+    match event {
+        Event::Control {
+            event: ControlEvent::Finished,
+        } => Some("Exploration finished".to_string()),
+        Event::Control {
+            event: ControlEvent::Aborted { reason },
+        } => Some(format!("Exploration aborted: {}", reason)),
+        Event::Result {
+            result: ExplorationResult::Failed { operation, reason },
+        } => Some(format!("Exploration of {} failed: {}", operation, reason)),
+        Event::Result { result } => counterexample_operation(result)
+            .map(|operation| format!("Found a counterexample for {}", operation)),
+        _ => None,
+    }
+}
+
+/// The operation of the result, if the result holds a counterexample
+fn counterexample_operation(result: &ExplorationResult) -> Option<&str> {
+    match result {
+        ExplorationResult::NoExampleFound { .. } | ExplorationResult::Failed { .. } => None,
+        ExplorationResult::StateMutation {
+            query_operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::StateIdentity {
+            query_operation,
+            minimal_sequence,
+            ..
+        } => minimal_sequence.as_ref().map(|_| query_operation.as_str()),
+        ExplorationResult::ResponseEquality {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::ResponseInEquality {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::ResponseCheck {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::ResponseTime {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::PayloadCheck {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::Injection {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::StaleCache {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::ConditionalRequest {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::IdempotentRetry {
+            operation,
+            minimal_sequence,
+            ..
+        }
+        | ExplorationResult::ModelDivergence {
+            operation,
+            minimal_sequence,
+            ..
+        } => minimal_sequence.as_ref().map(|_| operation.as_str()),
+        ExplorationResult::LatencyDegradation {
+            operation,
+            sequence,
+            ..
+        } => sequence.as_ref().map(|_| operation.as_str()),
+        ExplorationResult::AuthBypass {
+            operation,
+            sequence,
+            ..
+        }
+        | ExplorationResult::TargetDivergence {
+            operation,
+            sequence,
+            ..
+        }
+        | ExplorationResult::ContractViolation {
+            operation,
+            sequence,
+            ..
+        } => sequence.as_ref().map(|_| operation.as_str()),
+        ExplorationResult::ChaosInconsistency {
+            query_operation,
+            sequence,
+            ..
+        } => sequence.as_ref().map(|_| query_operation.as_str()),
+        // Roles are expected to differ, the matrix is not a counterexample
+        ExplorationResult::RoleMatrix { .. } => None,
+        // The findings are the example, a sequence is not needed
+        ExplorationResult::SecurityAudit { operation, .. }
+        | ExplorationResult::NonLinearizable { operation, .. } => Some(operation.as_str()),
+    }
+}
+
+/// Send the notification in the background, a failed notification is only
+///  logged since it must never disturb the exploration
+pub fn notify(settings: &NotificationSettings, message: String) {
+    if !settings.is_enabled() {
+        return;
+    }
+
+    let settings = settings.clone();
+    thread::spawn(move || {
+        if settings.desktop {
+            if let Err(e) = desktop_notification(&message) {
+                warn!("Failed to show desktop notification: {}", e);
+            }
+        }
+
+        if let Some(url) = settings.webhook_url.filter(|url| !url.is_empty()) {
+            let response = reqwest::blocking::Client::new()
+                .post(&url)
+                .json(&serde_json::json!({ "text": message }))
+                .send();
+
+            match response {
+                Ok(r) if !r.status().is_success() => {
+                    warn!("Notification webhook responded with {}", r.status())
+                }
+                Ok(_) => (),
+                Err(e) => warn!("Failed to call notification webhook: {}", e),
+            }
+        }
+    });
+}
+
+fn desktop_notification(message: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title \"QuickREST\"",
+            apple_script_string(message)
+        ));
+        command
+    } else if cfg!(target_os = "linux") {
+        let mut command = std::process::Command::new("notify-send");
+        command.arg("QuickREST").arg(message);
+        command
+    } else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "desktop notifications are not supported on this platform",
+        ));
+    };
+
+    let status = command.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "notifier exited with {}",
+            status
+        )))
+    }
+}
+
+/// The text quoted as an AppleScript string, only backslashes and quotes are
+///  escaped in one
+fn apple_script_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        if c == '\\' || c == '"' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::GeneratedOperation;
+
+    #[test]
+    fn apple_script_strings_escape_backslashes_and_quotes() {
+        assert_eq!(apple_script_string("Finished"), "\"Finished\"");
+        assert_eq!(
+            apple_script_string("Failed: \"C:\\tmp\"\n\u{e9}"),
+            "\"Failed: \\\"C:\\\\tmp\\\"\n\u{e9}\""
+        );
+    }
+
+    #[test]
+    fn notifies_on_finish_and_counterexamples_only() {
+        let finished = Event::Control {
+            event: ControlEvent::Finished,
+        };
+        assert_eq!(
+            notification_message(&finished),
+            Some("Exploration finished".to_string())
+        );
+
+        let started = Event::Control {
+            event: ControlEvent::Started,
+        };
+        assert_eq!(notification_message(&started), None);
+
+        let aborted = Event::Control {
+            event: ControlEvent::Aborted {
+                reason: "Target is down".to_string(),
+            },
+        };
+        assert_eq!(
+            notification_message(&aborted),
+            Some("Exploration aborted: Target is down".to_string())
+        );
+
+        let no_example = Event::Result {
+            result: ExplorationResult::NoExampleFound {
+                operation: "get_persons".to_string(),
+            },
+        };
+        assert_eq!(notification_message(&no_example), None);
+
+        let failed = Event::Result {
+            result: ExplorationResult::Failed {
+                operation: "get_persons".to_string(),
+                reason: "No bucket".to_string(),
+            },
+        };
+        assert_eq!(
+            notification_message(&failed),
+            Some("Exploration of get_persons failed: No bucket".to_string())
+        );
+
+        let counterexample = Event::Result {
+            result: ExplorationResult::StateMutation {
+                query_operation: "get_persons".to_string(),
+                minimal_sequence: Some(vec![GeneratedOperation {
+                    name: "post_person".to_string(),
+                    parameters: vec![],
+                }]),
+                correlation_id: None,
+                shrink_truncation: None,
+            },
+        };
+        assert_eq!(
+            notification_message(&counterexample),
+            Some("Found a counterexample for get_persons".to_string())
+        );
+    }
+
+    #[test]
+    fn disabled_without_a_notifier() {
+        let mut settings = NotificationSettings::new();
+        assert!(!settings.is_enabled());
+
+        settings.webhook_url = Some("".to_string());
+        assert!(!settings.is_enabled());
+
+        settings.webhook_url = Some("https://hooks.example.com/1".to_string());
+        assert!(settings.is_enabled());
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::mpsc;
+
+use crate::amos::InvokeResult;
+use crate::explore::{Event, ExplorationResult};
+
+/// Receives the events of an exploration, e.g. to write them to a log file or
+///  a database. Every event is given to `on_event`, results and invocations
+///  are also given to their own methods first.
+pub trait ExplorationObserver: Send {
+    fn on_event(&self, _event: Event) {}
+
+    fn on_result(&self, _result: &ExplorationResult) {}
+
+    fn on_invocation(&self, _result: &InvokeResult) {}
+}
+
+/// Forwards the events to a receiver, as the viewer and the CLI process them
+impl ExplorationObserver for mpsc::Sender<Event> {
+    fn on_event(&self, event: Event) {
+        // Nobody follows the exploration once the receiver is dropped, the
+        //  events are dropped too instead of panicking the exploration
+        let _ = self.send(event);
+    }
+}
+
+/// Give the event to the methods of the observer it is for
+pub fn notify(observer: &dyn ExplorationObserver, event: Event) {
+    match &event {
+        Event::Result { result } => observer.on_result(result),
+        Event::Invocation { result, .. } => observer.on_invocation(result),
+        _ => (),
+    }
+
+    observer.on_event(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::GeneratedOperation;
+    use crate::explore::{LogLevel, LogMessage};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl ExplorationObserver for Recorder {
+        fn on_event(&self, event: Event) {
+            let name = match event {
+                Event::Result { .. } => "event: result",
+                Event::Invocation { .. } => "event: invocation",
+                _ => "event",
+            };
+            self.events.lock().unwrap().push(name.to_string());
+        }
+
+        fn on_result(&self, result: &ExplorationResult) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("result: {:?}", result));
+        }
+
+        fn on_invocation(&self, result: &InvokeResult) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("invocation: {}", result.operation.name));
+        }
+    }
+
+    #[test]
+    fn notify_observer() {
+        let recorder = Recorder::default();
+
+        notify(
+            &recorder,
+            Event::Invocation {
+                result: InvokeResult::new(
+                    GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![],
+                    },
+                    "[]".to_string(),
+                    true,
+                    None,
+                ),
+                sut_invocation_duration: std::time::Duration::ZERO,
+            },
+        );
+        notify(
+            &recorder,
+            Event::Result {
+                result: ExplorationResult::NoExampleFound {
+                    operation: "get_persons".to_string(),
+                },
+            },
+        );
+        notify(&recorder, Event::log(LogLevel::Info, "done"));
+
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                "invocation: get_persons",
+                "event: invocation",
+                "result: NoExampleFound { operation: \"get_persons\" }",
+                "event: result",
+                "event",
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_to_channel() {
+        let (tx, rx) = mpsc::channel();
+
+        notify(&tx, Event::log(LogLevel::Info, "done"));
+
+        assert_eq!(
+            rx.recv().unwrap(),
+            Event::Log {
+                message: LogMessage {
+                    level: LogLevel::Info,
+                    message: "done".to_string()
+                }
+            }
+        );
+    }
+}
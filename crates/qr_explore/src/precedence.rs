@@ -0,0 +1,147 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::amos::InvokeResult;
+
+pub type SharedPrecedence = Arc<Mutex<PrecedenceLearning>>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Outcomes {
+    succeeded: u32,
+    invoked: u32,
+}
+
+impl Outcomes {
+    /// The success rate, an operation never invoked is as likely to succeed
+    ///  as to fail
+    fn rate(&self) -> f64 {
+        (self.succeeded as f64 + 1.0) / (self.invoked as f64 + 2.0)
+    }
+}
+
+/// The outcomes of the operations of a run, by whether another operation was
+///  invoked before them in the same sequence. Learns e.g. that a GET of a
+///  resource more often succeeds after the POST creating it.
+#[derive(Debug, Default, Clone)]
+pub struct PrecedenceLearning {
+    operations: BTreeSet<String>,
+    // By the other operation and the operation, without and with the other before
+    outcomes: HashMap<(String, String), [Outcomes; 2]>,
+}
+
+impl PrecedenceLearning {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of the last invocation of the sequence, given the
+    ///  invocations before it. Without a response there is no outcome.
+    pub fn record(&mut self, sequence: &[InvokeResult]) {
+        let Some((last, before)) = sequence.split_last() else {
+            return;
+        };
+        if last.transport_error.is_some() {
+            return;
+        }
+
+        let operation = &last.operation.name;
+        for result in sequence {
+            self.operations.insert(result.operation.name.clone());
+        }
+        for other in &self.operations {
+            if other == operation {
+                continue;
+            }
+
+            let preceded = before.iter().any(|result| &result.operation.name == other);
+            let outcomes = self
+                .outcomes
+                .entry((other.clone(), operation.clone()))
+                .or_default();
+            let outcome = &mut outcomes[preceded as usize];
+            outcome.invoked += 1;
+            if last.success {
+                outcome.succeeded += 1;
+            }
+        }
+    }
+
+    /// How much more often the operation succeeds with the other invoked
+    ///  before it than without, within -1 and 1
+    pub fn precedence(&self, other: &str, operation: &str) -> f64 {
+        match self
+            .outcomes
+            .get(&(other.to_string(), operation.to_string()))
+        {
+            Some([without, with]) => with.rate() - without.rate(),
+            None => 0.0,
+        }
+    }
+
+    /// The place of the operation among the others, the operations that
+    ///  succeed more often after the others get a higher score
+    pub fn score(&self, operation: &str, others: &[&str]) -> f64 {
+        others
+            .iter()
+            .filter(|other| **other != operation)
+            .map(|other| self.precedence(other, operation) - self.precedence(operation, other))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos::InvokeResult;
+    use crate::amos_generation::GeneratedOperation;
+    use crate::precedence as sut;
+
+    fn result(name: &str, success: bool) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            String::new(),
+            success,
+            None,
+        )
+    }
+
+    #[test]
+    fn learns_what_succeeds_after_what() {
+        let mut learning = sut::PrecedenceLearning::new();
+
+        for _ in 0..10 {
+            // The GET succeeds only after the POST, the POST always succeeds
+            learning.record(&[result("post_person", true)]);
+            learning.record(&[result("post_person", true), result("get_person", true)]);
+            learning.record(&[result("get_person", false)]);
+            learning.record(&[result("get_person", false), result("post_person", true)]);
+        }
+
+        assert!(learning.precedence("post_person", "get_person") > 0.5);
+        assert!(learning.precedence("get_person", "post_person").abs() < 0.1);
+
+        let ops = ["post_person", "get_person"];
+        assert!(learning.score("get_person", &ops) > learning.score("post_person", &ops));
+        assert_eq!(learning.score("delete_person", &ops), 0.0);
+    }
+
+    #[test]
+    fn an_invocation_without_a_response_has_no_outcome() {
+        let mut learning = sut::PrecedenceLearning::new();
+        learning.record(&[result("post_person", true)]);
+        learning.record(&[
+            result("post_person", true),
+            InvokeResult::transport_failure(
+                GeneratedOperation {
+                    name: "get_person".to_string(),
+                    parameters: vec![],
+                },
+                qr_http_resource::http::TransportError::Timeout,
+            ),
+        ]);
+
+        assert_eq!(learning.precedence("post_person", "get_person"), 0.0);
+    }
+}
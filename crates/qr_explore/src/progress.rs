@@ -0,0 +1,143 @@
+use std::time::{Duration, Instant};
+
+/// The tests of the run, completed and planned. The tests of each explored
+///  operation are planned, an operation done before its last test counts
+///  the tests it did not run as completed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RunTests {
+    pub completed: u32,
+    pub total: u32,
+    operation_end: u32,
+}
+
+impl RunTests {
+    /// Plan the tests of the operations about to be explored
+    pub fn plan(self, operations: usize, tests: u16) -> Self {
+        let planned = u32::try_from(operations)
+            .unwrap_or(u32::MAX)
+            .saturating_mul(u32::from(tests));
+        Self {
+            total: self.total.saturating_add(planned),
+            ..self
+        }
+    }
+
+    /// Start the tests of the next operation
+    pub fn start_operation(self, tests: u16) -> Self {
+        Self {
+            operation_end: self
+                .completed
+                .saturating_add(u32::from(tests))
+                .min(self.total),
+            ..self
+        }
+    }
+
+    /// A test of the operation completed, it does not count past the tests
+    ///  of the operation
+    pub fn complete_test(self) -> Self {
+        Self {
+            completed: (self.completed + 1).min(self.operation_end),
+            ..self
+        }
+    }
+
+    /// The operation is done, with the tests it did not run
+    pub fn complete_operation(self) -> Self {
+        Self {
+            completed: self.completed.max(self.operation_end),
+            ..self
+        }
+    }
+}
+
+/// The progress of the tests of the run, and the time they are estimated to
+///  take to complete
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestProgress {
+    pub completed: u32,
+    pub total: u32,
+    started: Instant,
+    last: Instant,
+}
+
+impl TestProgress {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            completed: 0,
+            total: 0,
+            started,
+            last: started,
+        }
+    }
+
+    /// Record the tests completed of the total tests of the run
+    pub fn record(&mut self, completed: u32, total: u32, at: Instant) {
+        self.completed = completed;
+        self.total = total;
+        self.last = at;
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.completed.min(self.total) as f32 / self.total as f32
+        }
+    }
+
+    /// The tests left at the mean time of the completed tests
+    pub fn remaining(&self) -> Option<Duration> {
+        if self.completed == 0 {
+            return None;
+        }
+
+        let per_test = (self.last - self.started) / self.completed;
+        Some(per_test * self.total.saturating_sub(self.completed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::progress as sut;
+
+    #[test]
+    fn remaining_time_of_the_tests() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut progress = sut::TestProgress::new(start);
+        assert_eq!(progress.remaining(), None);
+
+        progress.record(1, 4, at(2));
+        progress.record(2, 4, at(4));
+        assert_eq!(progress.fraction(), 0.5);
+        assert_eq!(progress.remaining(), Some(Duration::from_secs(4)));
+
+        // The tests of the next operation count on from those of the last
+        progress.record(3, 8, at(6));
+        assert_eq!(progress.remaining(), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn tests_of_the_run_span_the_operations() {
+        let tests = sut::RunTests::default().plan(2, 3).start_operation(3);
+        assert_eq!(tests.total, 6);
+
+        // The first operation found an example at its second test
+        let tests = tests.complete_test().complete_test().complete_operation();
+        assert_eq!(tests.completed, 3);
+
+        // A test past the tests of the operation is not counted
+        let tests = (0..5)
+            .fold(tests.start_operation(3), |tests, _| tests.complete_test())
+            .complete_operation();
+        assert_eq!(tests.completed, 6);
+
+        // The operations followed later add to the run
+        let tests = tests.plan(1, 3).start_operation(3).complete_test();
+        assert_eq!((tests.completed, tests.total), (7, 9));
+    }
+}
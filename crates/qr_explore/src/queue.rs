@@ -0,0 +1,331 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use qr_http_resource::http::{self, HTTPStatus};
+use tracing::warn;
+
+use crate::amos::{InvokeResult, Operation, ResultMetaData};
+use crate::amos_generation::{GeneratedOperation, ParameterValue};
+use crate::explore::{self, ExplorationContext, SharedInvoker, Target};
+use crate::http_translation::resolve_response_references;
+
+/// How long the reply to a message is waited for, unless configured
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum QueueProtocol {
+    AMQP,
+    Kafka,
+}
+
+impl QueueProtocol {
+    pub fn default_port(&self) -> u16 {
+        match self {
+            QueueProtocol::AMQP => 5672,
+            QueueProtocol::Kafka => 9092,
+        }
+    }
+}
+
+impl std::fmt::Display for QueueProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueueProtocol::AMQP => write!(f, "amqp://"),
+            QueueProtocol::Kafka => write!(f, "kafka://"),
+        }
+    }
+}
+
+/// The broker the operations are published to, a message per operation
+#[derive(Debug, Clone)]
+pub struct QueueConfiguration {
+    pub protocol: QueueProtocol,
+    pub host: String,
+    pub port: u16,
+    /// The topics of the operations are prefixed with it, e.g. 'orders' for
+    ///  'orders.post_order'
+    pub topic_prefix: Option<String>,
+    pub reply_timeout: Duration,
+}
+
+impl QueueConfiguration {
+    pub fn new(protocol: QueueProtocol, host: String, port: u16) -> Self {
+        Self {
+            protocol,
+            host,
+            port,
+            topic_prefix: None,
+            reply_timeout: REPLY_TIMEOUT,
+        }
+    }
+
+    /// The configuration of a URL such as 'amqp://localhost:5672/orders', the
+    ///  path of the URL is the prefix of the topics
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid queue URL '{url}'");
+        let parsed = reqwest::Url::parse(url).map_err(|_| invalid())?;
+
+        let protocol = match parsed.scheme() {
+            "amqp" => QueueProtocol::AMQP,
+            "kafka" => QueueProtocol::Kafka,
+            _ => return Err(invalid()),
+        };
+        let host = parsed.host_str().ok_or_else(invalid)?.to_string();
+        let port = parsed.port().unwrap_or(protocol.default_port());
+        let prefix = parsed.path().trim_matches('/');
+
+        Ok(Self::new(protocol, host, port)
+            .with_topic_prefix((!prefix.is_empty()).then(|| prefix.to_string())))
+    }
+
+    pub fn with_topic_prefix(mut self, topic_prefix: Option<String>) -> Self {
+        self.topic_prefix = topic_prefix;
+        self
+    }
+
+    pub fn with_reply_timeout(mut self, reply_timeout: Duration) -> Self {
+        self.reply_timeout = reply_timeout;
+        self
+    }
+
+    /// The topic the operation of the name is published to
+    pub fn topic(&self, operation: &str) -> String {
+        match &self.topic_prefix {
+            Some(prefix) => format!("{}.{}", prefix, operation),
+            None => operation.to_string(),
+        }
+    }
+
+    pub fn url(&self, topic: &str) -> String {
+        format!("{}{}:{}/{}", self.protocol, self.host, self.port, topic)
+    }
+}
+
+/// A generated operation as it is published
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Message {
+    pub topic: String,
+    /// The name of the operation
+    pub key: String,
+    /// The id the reply to the message is correlated by
+    pub correlation_id: String,
+    /// The values of the parameters as a JSON object
+    pub payload: String,
+}
+
+/// The reply of the service to a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    /// The outcome of the message as the status of an HTTP response, the
+    ///  checks of the behaviours apply to the replies as they are
+    pub status: u16,
+    pub payload: String,
+}
+
+impl Reply {
+    pub fn new(status: u16, payload: String) -> Self {
+        Self { status, payload }
+    }
+}
+
+/// A client of the broker, e.g. of AMQP or Kafka, provided by the embedder
+///  unless it is the one of AMQP of the 'amqp' feature. Publishes the
+///  messages and receives the replies correlated to them.
+pub trait Broker: Send + Sync {
+    fn publish(&self, message: &Message) -> Result<(), String>;
+
+    /// The reply to the message of the id, none if the service did not reply
+    ///  within the timeout
+    fn reply(&self, correlation_id: &str, timeout: Duration) -> Result<Option<Reply>, String>;
+}
+
+/// The message of the operation, with its references to the responses
+///  resolved from the results
+pub fn translate_generated_operation_to_message(
+    config: &QueueConfiguration,
+    ops: &[Operation],
+    gen_op: &GeneratedOperation,
+    results: &[InvokeResult],
+    correlation_id: &str,
+) -> Message {
+    let resolved = resolve_response_references(ops, gen_op, results);
+    let values: serde_json::Map<_, _> = resolved
+        .parameters
+        .iter()
+        .filter_map(|p| Some((p.name.clone(), json_value(&p.value)?)))
+        .collect();
+
+    Message {
+        topic: config.topic(&gen_op.name),
+        key: gen_op.name.clone(),
+        correlation_id: correlation_id.to_string(),
+        payload: serde_json::to_string(&values).unwrap_or_default(),
+    }
+}
+
+/// Invokes the operations of a queue target by publishing them with the
+///  broker, to explore the target with the behaviours as an HTTP target
+pub fn invoker(broker: Arc<dyn Broker>) -> SharedInvoker {
+    Arc::new(move |ctx, ops, gen_ops| invoke(broker.as_ref(), ctx, ops, gen_ops))
+}
+
+/// The invoker of a queue target the embedder gives no broker for, a client
+///  of the broker of its protocol
+pub fn default_invoker(config: &QueueConfiguration) -> Result<SharedInvoker, String> {
+    match config.protocol {
+        #[cfg(feature = "amqp")]
+        QueueProtocol::AMQP => Ok(invoker(Arc::new(crate::amqp::AmqpBroker::connect(config)?))),
+        #[cfg(not(feature = "amqp"))]
+        QueueProtocol::AMQP => Err("a client of AMQP needs the 'amqp' feature".to_string()),
+        QueueProtocol::Kafka => Err("there is no client of Kafka, a broker is given".to_string()),
+    }
+}
+
+/// Publish the operations in order, each after the reply to the one before.
+///  A command the service does not reply to is taken as accepted, its effect
+///  is observed by the query operations, which are expected to reply.
+pub fn invoke(
+    broker: &dyn Broker,
+    ctx: &ExplorationContext,
+    ops: &[Operation],
+    gen_ops: &[GeneratedOperation],
+) -> Option<Vec<InvokeResult>> {
+    let Target::Queue { config } = ctx.current_target() else {
+        warn!("Only a queue target is invoked with a broker");
+        return None;
+    };
+
+    explore::invoke_in_order(ctx, gen_ops, |gen_op, results, id| {
+        let message = translate_generated_operation_to_message(config, ops, gen_op, results, id);
+        let reply = broker
+            .publish(&message)
+            .and_then(|()| broker.reply(&message.correlation_id, config.reply_timeout));
+
+        let result = match reply {
+            Ok(Some(reply)) => translate_reply(reply, gen_op, config.url(&message.topic)),
+            Ok(None) if ctx.is_query(&gen_op.name) => {
+                InvokeResult::transport_failure(gen_op.clone(), http::TransportError::Timeout)
+            }
+            Ok(None) => translate_reply(
+                Reply::new(204, String::new()),
+                gen_op,
+                config.url(&message.topic),
+            ),
+            Err(e) => {
+                warn!("The message of {} was not published: {}", gen_op.name, e);
+                InvokeResult::transport_failure(gen_op.clone(), http::TransportError::Connect)
+            }
+        };
+        Some(result)
+    })
+}
+
+/// The value as a JSON value of its type, an empty value is left out
+fn json_value(value: &ParameterValue) -> Option<serde_json::Value> {
+    let json = match value {
+        ParameterValue::StringValue { value, .. } => serde_json::Value::from(value.clone()),
+        ParameterValue::IntValue { value, .. } => serde_json::Value::from(*value),
+        ParameterValue::BoolValue { value, .. } => serde_json::Value::from(*value),
+        ParameterValue::DoubleValue { value, .. } => serde_json::Value::from(*value),
+        ParameterValue::IPV4Value {
+            value: (a, b, c, d),
+            ..
+        } => serde_json::Value::from(format!("{a}.{b}.{c}.{d}")),
+        ParameterValue::ArrayOfString { value, .. } => serde_json::Value::from(value.clone()),
+        ParameterValue::File { value, .. } => serde_json::Value::from(value.to_string()),
+        ParameterValue::Reference { fallback, .. } => return json_value(fallback),
+        ParameterValue::Empty => return None,
+    };
+    Some(json)
+}
+
+fn translate_reply(reply: Reply, gen_op: &GeneratedOperation, url: String) -> InvokeResult {
+    InvokeResult::new(
+        gen_op.clone(),
+        reply.payload.clone(),
+        (200..300).contains(&reply.status),
+        Some(ResultMetaData::HTTP {
+            url,
+            status: HTTPStatus::from_code(reply.status),
+            size: reply.payload.len(),
+            content_encoding: None,
+            headers: vec![],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos_generation::{GeneratedOperation, GeneratedParameter, ParameterValue};
+    use crate::queue as sut;
+
+    #[test]
+    fn operations_are_translated_to_messages_of_the_queue() {
+        let config = sut::QueueConfiguration::parse("kafka://broker/orders").unwrap();
+        assert_eq!(config.protocol, sut::QueueProtocol::Kafka);
+        assert_eq!(config.port, 9092);
+        assert_eq!(config.topic("post_order"), "orders.post_order");
+        assert_eq!(
+            sut::QueueConfiguration::parse("amqp://broker:5673")
+                .unwrap()
+                .url("post_order"),
+            "amqp://broker:5673/post_order"
+        );
+        assert!(sut::QueueConfiguration::parse("http://broker").is_err());
+
+        let parameter = |name: &str, value| GeneratedParameter {
+            name: name.to_string(),
+            value,
+            ref_path: None,
+        };
+        let gen_op = GeneratedOperation {
+            name: "post_order".to_string(),
+            parameters: vec![
+                parameter(
+                    "item",
+                    ParameterValue::StringValue {
+                        value: "book".to_string(),
+                        seed: 1,
+                        active: true,
+                    },
+                ),
+                parameter(
+                    "count",
+                    ParameterValue::IntValue {
+                        value: 2,
+                        seed: 1,
+                        active: true,
+                    },
+                ),
+                parameter(
+                    "gift",
+                    ParameterValue::BoolValue {
+                        value: true,
+                        seed: 1,
+                        active: true,
+                    },
+                ),
+                parameter("note", ParameterValue::Empty),
+            ],
+        };
+
+        let message =
+            sut::translate_generated_operation_to_message(&config, &[], &gen_op, &[], "qr-1-0");
+        assert_eq!(
+            message,
+            sut::Message {
+                topic: "orders.post_order".to_string(),
+                key: "post_order".to_string(),
+                correlation_id: "qr-1-0".to_string(),
+                payload: r#"{"count":2,"gift":true,"item":"book"}"#.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn a_kafka_queue_is_only_invoked_by_the_broker_of_the_embedder() {
+        let config = sut::QueueConfiguration::parse("kafka://localhost/orders").unwrap();
+
+        assert!(sut::default_invoker(&config).is_err());
+    }
+}
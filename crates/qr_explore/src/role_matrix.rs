@@ -0,0 +1,155 @@
+use crate::explore::ExplorationResult;
+
+/// How often an operation succeeded when invoked as a role
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RoleOutcome {
+    pub role: String,
+    pub successes: u16,
+    pub attempts: u16,
+}
+
+impl RoleOutcome {
+    pub fn new(role: &str) -> Self {
+        Self {
+            role: role.to_string(),
+            successes: 0,
+            attempts: 0,
+        }
+    }
+
+    pub fn record(&mut self, success: bool) {
+        self.attempts += 1;
+        if success {
+            self.successes += 1;
+        }
+    }
+
+    /// If the role was authorized for the operation in any attempt
+    pub fn is_authorized(&self) -> bool {
+        self.successes > 0
+    }
+}
+
+impl std::fmt::Display for RoleOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.successes, self.attempts)
+    }
+}
+
+/// If the roles differ in being authorized for the operation
+pub fn roles_differ(outcomes: &[RoleOutcome]) -> bool {
+    outcomes
+        .windows(2)
+        .any(|w| w[0].is_authorized() != w[1].is_authorized())
+}
+
+/// The outcomes of the operations by role, collected from the results
+#[derive(Debug, PartialEq)]
+pub struct RoleMatrix {
+    pub roles: Vec<String>,
+    pub rows: Vec<(String, Vec<RoleOutcome>)>,
+}
+
+impl RoleMatrix {
+    /// The matrix of the role results, the roles are those of the first row as
+    ///  every row is explored with the same roles
+    pub fn from_results(results: &[ExplorationResult]) -> Self {
+        let rows: Vec<(String, Vec<RoleOutcome>)> = results
+            .iter()
+            .filter_map(|result| match result {
+                ExplorationResult::RoleMatrix {
+                    operation,
+                    outcomes,
+                    ..
+                } => Some((operation.clone(), outcomes.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let roles = rows
+            .first()
+            .map(|(_, outcomes)| outcomes.iter().map(|o| o.role.clone()).collect())
+            .unwrap_or_default();
+
+        RoleMatrix { roles, rows }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl std::fmt::Display for RoleMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|(operation, _)| operation.len())
+            .max()
+            .unwrap_or(0)
+            .max("operation".len());
+
+        write!(f, "{:width$}", "operation")?;
+        for role in &self.roles {
+            write!(f, " | {}", role)?;
+        }
+        writeln!(f)?;
+
+        for (operation, outcomes) in &self.rows {
+            write!(f, "{:width$}", operation)?;
+            for (role, outcome) in self.roles.iter().zip(outcomes) {
+                write!(f, " | {:>w$}", outcome.to_string(), w = role.len())?;
+            }
+            if roles_differ(outcomes) {
+                write!(f, " *")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(role: &str, successes: u16, attempts: u16) -> RoleOutcome {
+        RoleOutcome {
+            role: role.to_string(),
+            successes,
+            attempts,
+        }
+    }
+
+    #[test]
+    fn matrix_from_results() {
+        let results = vec![
+            ExplorationResult::RoleMatrix {
+                operation: "get_users".to_string(),
+                outcomes: vec![outcome("admin", 3, 3), outcome("anonymous", 0, 3)],
+                sequence: None,
+            },
+            ExplorationResult::NoExampleFound {
+                operation: "get_health".to_string(),
+            },
+            ExplorationResult::RoleMatrix {
+                operation: "get_me".to_string(),
+                outcomes: vec![outcome("admin", 3, 3), outcome("anonymous", 1, 3)],
+                sequence: None,
+            },
+        ];
+
+        let matrix = RoleMatrix::from_results(&results);
+        assert_eq!(matrix.roles, vec!["admin", "anonymous"]);
+        assert!(roles_differ(&matrix.rows[0].1));
+        assert!(!roles_differ(&matrix.rows[1].1));
+
+        assert_eq!(
+            matrix.to_string(),
+            "operation | admin | anonymous\n\
+             get_users |   3/3 |       0/3 *\n\
+             get_me    |   3/3 |       1/3\n"
+        );
+    }
+}
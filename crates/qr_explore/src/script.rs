@@ -0,0 +1,109 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::amos_generation::GeneratedOperation;
+
+pub type SharedScript = Arc<Mutex<ScriptRecorder>>;
+
+/// The values of the invoked sequences, as they were sent. Written as one
+///  JSON array of operations per line, a line per sequence.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayScript {
+    pub sequences: Vec<Vec<GeneratedOperation>>,
+}
+
+impl ReplayScript {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let mut sequences = vec![];
+        for (idx, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let sequence = serde_json::from_str(&line).map_err(|e| {
+                std::io::Error::other(format!("{}:{}: {}", path.display(), idx + 1, e))
+            })?;
+            sequences.push(sequence);
+        }
+
+        Ok(ReplayScript { sequences })
+    }
+}
+
+/// Records the invoked sequences to the script file as they are invoked, so
+///  the script holds the sequences of a run that did not finish
+#[derive(Debug)]
+pub struct ScriptRecorder {
+    file: File,
+    sequences: usize,
+}
+
+impl ScriptRecorder {
+    /// The sequences are appended to the script of the path, e.g. the
+    ///  explorations of several behaviours to the same script
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(ScriptRecorder {
+            file: File::options().create(true).append(true).open(path)?,
+            sequences: 0,
+        })
+    }
+
+    /// The number of sequences recorded so far
+    pub fn sequences(&self) -> usize {
+        self.sequences
+    }
+
+    pub fn record(&mut self, sequence: &[GeneratedOperation]) -> std::io::Result<()> {
+        if sequence.is_empty() {
+            return Ok(());
+        }
+
+        let mut line = serde_json::to_vec(sequence)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.sequences += 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::amos_generation::{GeneratedOperation, GeneratedParameter, ParameterValue};
+    use crate::script as sut;
+
+    #[test]
+    fn recorded_sequences_are_loaded_as_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("script.jsonl");
+        let operation = |name: &str, value: &str| GeneratedOperation {
+            name: name.to_string(),
+            parameters: vec![GeneratedParameter {
+                name: "name".to_string(),
+                value: ParameterValue::StringValue {
+                    value: value.to_string(),
+                    seed: 3,
+                    active: true,
+                },
+                ref_path: None,
+            }],
+        };
+        let sequences = vec![
+            vec![operation("post_person", "a"), operation("get_person", "a")],
+            vec![operation("delete_person", "b")],
+        ];
+
+        let mut recorder = sut::ScriptRecorder::open(&path).unwrap();
+        for sequence in &sequences {
+            recorder.record(sequence).unwrap();
+        }
+        // Nothing was invoked, nothing to replay
+        recorder.record(&[]).unwrap();
+        assert_eq!(recorder.sequences(), 2);
+
+        let script = sut::ReplayScript::load(&path).unwrap();
+        assert_eq!(script.sequences, sequences);
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// The store of a config when it names none
+pub const DEFAULT_SECRETS_FILE: &str = "quickrest.secrets";
+
+/// The environment variable the passphrase of the store is read from
+pub const PASSPHRASE_VAR: &str = "QUICKREST_SECRETS_PASSPHRASE";
+
+const ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+const REFERENCE_START: &str = "${secret:";
+/// Sealed into every store, opening it verifies the passphrase when the
+///  store has no secrets
+const CHECK: &str = "quickrest-secrets";
+
+#[derive(Debug)]
+pub enum SecretError {
+    StoreFileFailure(std::io::Error),
+    InvalidStore(String),
+    MissingPassphrase,
+    /// The passphrase does not open the secrets of the store
+    WrongPassphrase,
+    UnknownSecret(String),
+}
+
+impl From<std::io::Error> for SecretError {
+    fn from(val: std::io::Error) -> Self {
+        Self::StoreFileFailure(val)
+    }
+}
+
+impl std::fmt::Display for SecretError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretError::StoreFileFailure(e) => write!(f, "Could not read secrets: {}", e),
+            SecretError::InvalidStore(msg) => write!(f, "Invalid secrets store: {}", msg),
+            SecretError::MissingPassphrase => {
+                write!(
+                    f,
+                    "The passphrase of the secrets is not set in {}",
+                    PASSPHRASE_VAR
+                )
+            }
+            SecretError::WrongPassphrase => write!(f, "The passphrase does not open the secrets"),
+            SecretError::UnknownSecret(name) => write!(f, "Unknown secret '{}'", name),
+        }
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+/// The passphrase of the environment
+pub fn passphrase() -> Result<String, SecretError> {
+    std::env::var(PASSPHRASE_VAR).map_err(|_| SecretError::MissingPassphrase)
+}
+
+/// The store as written to its file, the names of the secrets are readable
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoreFile {
+    /// Of the key derived from the passphrase
+    salt: String,
+    iterations: u32,
+    /// The nonce and the sealed check, base64 encoded. Stores written
+    ///  before the check was added have none.
+    #[serde(default)]
+    check: Option<String>,
+    /// The nonce and the sealed value of each secret, base64 encoded
+    secrets: BTreeMap<String, String>,
+}
+
+/// Named secrets, e.g. tokens and API keys, in a file encrypted with a key
+///  derived from a passphrase. Configs reference them by name, so the
+///  configs can be committed without them.
+pub struct SecretStore {
+    path: PathBuf,
+    salt: Vec<u8>,
+    iterations: u32,
+    key: LessSafeKey,
+    secrets: BTreeMap<String, String>,
+}
+
+impl SecretStore {
+    /// Open the store, a store whose file does not exist is empty
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self, SecretError> {
+        if !path.exists() {
+            let mut salt = vec![0; SALT_LEN];
+            fill_random(&mut salt)?;
+            let key = derive_key(passphrase, &salt, ITERATIONS);
+
+            return Ok(Self {
+                path: path.to_path_buf(),
+                salt,
+                iterations: ITERATIONS,
+                key,
+                secrets: BTreeMap::new(),
+            });
+        }
+
+        let file: StoreFile = serde_json::from_slice(&std::fs::read(path)?)
+            .map_err(|e| SecretError::InvalidStore(format!("{}: {}", path.display(), e)))?;
+        let salt = decode(&file.salt)?;
+        let key = derive_key(passphrase, &salt, file.iterations);
+
+        if let Some(check) = file.check {
+            if open_value(&key, CHECK, &decode(&check)?)? != CHECK {
+                return Err(SecretError::WrongPassphrase);
+            }
+        }
+
+        let mut secrets = BTreeMap::new();
+        for (name, sealed) in file.secrets {
+            let value = open_value(&key, &name, &decode(&sealed)?)?;
+            secrets.insert(name, value);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            salt,
+            iterations: file.iterations,
+            key,
+            secrets,
+        })
+    }
+
+    /// Write the store, every secret is sealed with a new nonce
+    pub fn save(&self) -> Result<(), SecretError> {
+        let mut secrets = BTreeMap::new();
+        for (name, value) in &self.secrets {
+            secrets.insert(
+                name.clone(),
+                BASE64.encode(seal_value(&self.key, name, value)?),
+            );
+        }
+
+        let file = StoreFile {
+            salt: BASE64.encode(&self.salt),
+            iterations: self.iterations,
+            check: Some(BASE64.encode(seal_value(&self.key, CHECK, CHECK)?)),
+            secrets,
+        };
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|e| SecretError::InvalidStore(e.to_string()))?;
+        std::fs::write(&self.path, json)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.secrets.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.secrets.insert(name.to_string(), value.to_string());
+    }
+
+    /// Returns if there was a secret of the name
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.secrets.remove(name).is_some()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.secrets.keys().map(String::as_str)
+    }
+
+    /// The text with each reference `${secret:<name>}` replaced by the secret
+    pub fn resolve(&self, text: &str) -> Result<String, SecretError> {
+        let mut resolved = String::new();
+        let mut rest = text;
+
+        while let Some((before, name, after)) = next_reference(rest) {
+            let secret = self
+                .get(name)
+                .ok_or_else(|| SecretError::UnknownSecret(name.to_string()))?;
+            resolved.push_str(before);
+            resolved.push_str(secret);
+            rest = after;
+        }
+        resolved.push_str(rest);
+
+        Ok(resolved)
+    }
+}
+
+/// If the text references any secret
+pub fn has_references(text: &str) -> bool {
+    next_reference(text).is_some()
+}
+
+/// The text before the first reference, the name it references and the text
+///  after it
+fn next_reference(text: &str) -> Option<(&str, &str, &str)> {
+    let start = text.find(REFERENCE_START)?;
+    let name_start = start + REFERENCE_START.len();
+    let end = name_start + text[name_start..].find('}')?;
+
+    Some((&text[..start], &text[name_start..end], &text[end + 1..]))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> LessSafeKey {
+    let mut key = [0; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+
+    LessSafeKey::new(UnboundKey::new(&aead::AES_256_GCM, &key).expect("A key of 32 bytes"))
+}
+
+fn fill_random(bytes: &mut [u8]) -> Result<(), SecretError> {
+    SystemRandom::new()
+        .fill(bytes)
+        .map_err(|_| SecretError::InvalidStore("no random source".to_string()))
+}
+
+fn decode(data: &str) -> Result<Vec<u8>, SecretError> {
+    BASE64
+        .decode(data)
+        .map_err(|e| SecretError::InvalidStore(e.to_string()))
+}
+
+/// The nonce followed by the sealed value, the name is authenticated with it
+///  so a value can not be moved to another name
+fn seal_value(key: &LessSafeKey, name: &str, value: &str) -> Result<Vec<u8>, SecretError> {
+    let mut nonce = [0; aead::NONCE_LEN];
+    fill_random(&mut nonce)?;
+
+    let mut sealed = value.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(name.as_bytes()),
+        &mut sealed,
+    )
+    .map_err(|_| SecretError::InvalidStore(format!("secret '{}' could not be sealed", name)))?;
+
+    Ok([nonce.to_vec(), sealed].concat())
+}
+
+fn open_value(key: &LessSafeKey, name: &str, data: &[u8]) -> Result<String, SecretError> {
+    if data.len() < aead::NONCE_LEN {
+        return Err(SecretError::InvalidStore(format!(
+            "secret '{}' is truncated",
+            name
+        )));
+    }
+    let (nonce, sealed) = data.split_at(aead::NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| SecretError::InvalidStore(format!("secret '{}' is truncated", name)))?;
+
+    let mut sealed = sealed.to_vec();
+    let value = key
+        .open_in_place(nonce, Aad::from(name.as_bytes()), &mut sealed)
+        .map_err(|_| SecretError::WrongPassphrase)?;
+
+    String::from_utf8(value.to_vec())
+        .map_err(|_| SecretError::InvalidStore(format!("secret '{}' is not text", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::secrets as sut;
+    use crate::secrets::SecretError;
+
+    #[test]
+    fn secrets_are_only_opened_with_the_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.secrets");
+
+        let mut store = sut::SecretStore::open(&path, "correct horse").unwrap();
+        store.set("api_token", "abc123");
+        store.set("admin_token", "def456");
+        store.save().unwrap();
+
+        // The values are not in the file
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("api_token"));
+        assert!(!written.contains("abc123"));
+
+        let mut store = sut::SecretStore::open(&path, "correct horse").unwrap();
+        assert_eq!(store.get("api_token"), Some("abc123"));
+        assert!(store.remove("admin_token"));
+        assert_eq!(store.names().collect::<Vec<_>>(), vec!["api_token"]);
+
+        assert!(matches!(
+            sut::SecretStore::open(&path, "wrong horse"),
+            Err(SecretError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn an_empty_store_is_only_opened_with_the_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.secrets");
+
+        sut::SecretStore::open(&path, "correct horse")
+            .unwrap()
+            .save()
+            .unwrap();
+
+        assert!(sut::SecretStore::open(&path, "correct horse").is_ok());
+        assert!(matches!(
+            sut::SecretStore::open(&path, "wrong horse"),
+            Err(SecretError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn references_are_replaced_by_the_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not_saved.secrets");
+        let mut store = sut::SecretStore::open(&path, "passphrase").unwrap();
+        store.set("token", "abc123");
+        store.set("key", "k");
+
+        assert!(sut::has_references("Authorization: Bearer ${secret:token}"));
+        assert!(!sut::has_references("Authorization: Bearer abc123"));
+        assert_eq!(
+            store
+                .resolve("Authorization: Bearer ${secret:token}")
+                .unwrap(),
+            "Authorization: Bearer abc123"
+        );
+        assert_eq!(
+            store.resolve("${secret:key}:${secret:token}!").unwrap(),
+            "k:abc123!"
+        );
+        assert!(matches!(
+            store.resolve("X-Api-Key: ${secret:other}"),
+            Err(SecretError::UnknownSecret(name)) if name == "other"
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::amos::InvokeResult;
+
+/// Parts of a payload only found in stack traces, or the error pages of
+///  frameworks printing them
+const STACK_TRACE_MARKERS: &[&str] = &[
+    "Traceback (most recent call last)",
+    "Exception in thread",
+    "\tat java.",
+    "\tat org.",
+    ".java:",
+    "   at System.",
+    "node_modules/",
+    "stack trace:",
+    "\"stack\":",
+    "\"stackTrace\":",
+];
+
+/// What a response reveals, or fails to protect against
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub enum SecurityFinding {
+    /// Any origin may read the response
+    CorsWildcard,
+    /// The browser may sniff a content type other than the declared one
+    MissingContentTypeOptions,
+    /// The server software and its version, from the header with the name
+    VerboseServerBanner { header: String, value: String },
+    /// An error response with a stack trace of the server
+    StackTrace,
+}
+
+impl std::fmt::Display for SecurityFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecurityFinding::CorsWildcard => write!(f, "CORS allows any origin"),
+            SecurityFinding::MissingContentTypeOptions => {
+                write!(f, "Missing 'X-Content-Type-Options: nosniff'")
+            }
+            SecurityFinding::VerboseServerBanner { header, value } => {
+                write!(f, "Verbose '{}' header: {}", header, value)
+            }
+            SecurityFinding::StackTrace => write!(f, "Stack trace in error response"),
+        }
+    }
+}
+
+/// The findings of the headers and payload of a response, results without
+///  HTTP meta data have nothing to audit
+pub fn audit(result: &InvokeResult) -> Vec<SecurityFinding> {
+    let Some(meta_data) = &result.meta_data else {
+        return vec![];
+    };
+
+    let mut findings = vec![];
+
+    if meta_data.header("access-control-allow-origin") == Some("*") {
+        findings.push(SecurityFinding::CorsWildcard);
+    }
+
+    let nosniff = meta_data
+        .header("x-content-type-options")
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("nosniff"));
+    if !nosniff {
+        findings.push(SecurityFinding::MissingContentTypeOptions);
+    }
+
+    // A version in the banner tells which vulnerabilities apply
+    for header in ["server", "x-powered-by", "x-aspnet-version"] {
+        if let Some(value) = meta_data.header(header) {
+            if value.chars().any(|c| c.is_ascii_digit()) {
+                findings.push(SecurityFinding::VerboseServerBanner {
+                    header: header.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+    }
+
+    if !result.success
+        && STACK_TRACE_MARKERS
+            .iter()
+            .any(|marker| result.result.contains(marker))
+    {
+        findings.push(SecurityFinding::StackTrace);
+    }
+
+    findings
+}
+
+/// Add the findings not already found
+pub fn merge_findings(findings: &mut Vec<SecurityFinding>, new: Vec<SecurityFinding>) {
+    for finding in new {
+        if !findings.contains(&finding) {
+            findings.push(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos::ResultMetaData;
+    use crate::amos_generation::GeneratedOperation;
+    use qr_http_resource::http::HTTPStatus;
+
+    fn result(success: bool, payload: &str, headers: &[(&str, &str)]) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: "get_persons".to_string(),
+                parameters: vec![],
+            },
+            payload.to_string(),
+            success,
+            Some(ResultMetaData::HTTP {
+                url: "/persons".to_string(),
+                status: HTTPStatus::OK,
+                size: payload.len(),
+                content_encoding: None,
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+            }),
+        )
+    }
+
+    #[test]
+    fn audit_headers() {
+        let findings = audit(&result(
+            true,
+            "[]",
+            &[
+                ("access-control-allow-origin", "*"),
+                ("server", "Apache/2.4.41 (Ubuntu)"),
+            ],
+        ));
+
+        assert_eq!(
+            findings,
+            vec![
+                SecurityFinding::CorsWildcard,
+                SecurityFinding::MissingContentTypeOptions,
+                SecurityFinding::VerboseServerBanner {
+                    header: "server".to_string(),
+                    value: "Apache/2.4.41 (Ubuntu)".to_string()
+                },
+            ]
+        );
+
+        let hardened = result(
+            true,
+            "[]",
+            &[("X-Content-Type-Options", "nosniff"), ("server", "nginx")],
+        );
+        assert!(audit(&hardened).is_empty());
+    }
+
+    #[test]
+    fn audit_stack_trace_in_errors_only() {
+        let trace = "java.lang.NullPointerException\n\tat org.example.PersonController";
+        let headers = [("x-content-type-options", "nosniff")];
+
+        assert_eq!(
+            audit(&result(false, trace, &headers)),
+            vec![SecurityFinding::StackTrace]
+        );
+        assert!(audit(&result(true, trace, &headers)).is_empty());
+    }
+}
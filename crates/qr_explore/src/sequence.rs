@@ -3,25 +3,40 @@ use proptest::{
     test_runner::{Config, FileFailurePersistence, TestRunner},
 };
 
+use qr_http_resource::reqwest_http::{self, RequestPreview};
+
 use crate::{
     amos::{InvokeResult, Operation},
-    amos_generation::{self, gen_banana_cake_value},
-    explore::{self, ControlEvent, Event, ExplorationContext, LogLevel},
+    amos_generation::{self, GeneratedOperation},
+    explore::{self, ControlEvent, Event, ExplorationContext, LogLevel, Target},
+    http_translation::translate_generated_operation_to_http_call,
     synthesize::synthesize_operation,
 };
 
-// EXPERIMENT: make a path UI -> gen
-pub fn banana_cakes_generation(expr: &str) -> amos_generation::ParameterValue {
-    let mut runner = TestRunner::new(Config {
+fn sequence_runner() -> TestRunner {
+    TestRunner::new(Config {
         failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
         ..Config::default()
-    });
+    })
+}
+
+fn generate_operations(
+    runner: &mut TestRunner,
+    operations_to_invoke: Vec<Operation>,
+) -> Vec<GeneratedOperation> {
+    let mut syn_ops = vec![];
+    for op in operations_to_invoke {
+        let gen = amos_generation::gen_param_array(&op.parameters)
+            .new_tree(runner)
+            .unwrap();
 
-    let gen = gen_banana_cake_value(expr.to_string())
-        .new_tree(&mut runner)
-        .unwrap();
+        let v = gen.current();
 
-    gen.current()
+        // NOTE: if references should be supported, we need to potential seq of
+        //  operations to reference
+        syn_ops.push(synthesize_operation(&[], (op.clone(), v)));
+    }
+    syn_ops
 }
 
 pub fn sequence_invoke(
@@ -37,23 +52,7 @@ pub fn sequence_invoke(
         message: "Start invocation".to_string(),
     });
 
-    let mut runner = TestRunner::new(Config {
-        failure_persistence: Some(Box::new(FileFailurePersistence::Off)),
-        ..Config::default()
-    });
-
-    let mut syn_ops = vec![];
-    for op in operations_to_invoke {
-        let gen = amos_generation::gen_param_array(&op.parameters)
-            .new_tree(&mut runner)
-            .unwrap();
-
-        let v = gen.current();
-
-        // NOTE: if references should be supported, we need to potential seq of
-        //  operations to reference
-        syn_ops.push(synthesize_operation(&[], (op.clone(), v)));
-    }
+    let syn_ops = generate_operations(&mut sequence_runner(), operations_to_invoke);
 
     let results = invoke(ctx, &operations, &syn_ops);
 
@@ -68,3 +67,166 @@ pub fn sequence_invoke(
 
     results
 }
+
+/// The requests the operations would be sent as, with values generated as
+///  for an invocation. Nothing is sent.
+pub fn sequence_preview(
+    ctx: &ExplorationContext,
+    operations: &[Operation],
+    operations_to_invoke: Vec<Operation>,
+) -> Vec<(String, Result<RequestPreview, String>)> {
+    let Target::HTTP { config } = &ctx.target else {
+        return operations_to_invoke
+            .into_iter()
+            .map(|op| {
+                let preview = Err("The operations are not sent as HTTP requests".to_string());
+                (op.info.name, preview)
+            })
+            .collect();
+    };
+    let correlation_id = ctx.correlation.id(ctx.test_cases.get() + 1);
+
+    generate_operations(&mut sequence_runner(), operations_to_invoke)
+        .into_iter()
+        .map(|gen_op| {
+            let preview = match translate_generated_operation_to_http_call(
+                config,
+                operations,
+                &gen_op,
+                &[],
+            ) {
+                Some((mut request, _)) => {
+                    ctx.add_request_headers(&mut request, &correlation_id);
+                    reqwest_http::preview_request(&ctx.http_client, &request)
+                }
+                None => Err("The parameters could not be translated to a request".to_string()),
+            };
+            (gen_op.name, preview)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use qr_http_resource::http::{self, HTTPAuth, HTTPMethod, HTTPParameterTarget};
+
+    use crate::{
+        amos::{
+            Operation, OperationInfo, OperationMetaData, Parameter, ParameterMetaData,
+            ParameterOwnership, Schema,
+        },
+        amos_generation::RegexPattern,
+        exploration_settings::AuthSettings,
+        explore::{self, CorrelationIds, ExplorationContext, Target},
+        sequence as sut,
+    };
+
+    #[test]
+    fn preview_shows_the_request_without_sending_it() {
+        let mut auth = AuthSettings::new();
+        auth.credentials = Some(HTTPAuth::new(
+            "Authorization".to_string(),
+            "Bearer secret".to_string(),
+        ));
+
+        let ctx = ExplorationContext {
+            http_send_fn: explore::invoke_dry,
+            auth,
+            correlation: CorrelationIds::new(Some("X-Correlation-Id".to_string()), 7),
+            ..ExplorationContext::new(Target::HTTP {
+                config: http::HTTPConfiguration::new(
+                    "localhost".to_string(),
+                    1,
+                    http::Protocol::HTTP,
+                ),
+            })
+        };
+
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![Parameter {
+                name: "name".to_string(),
+                schema: Schema::StringRegex {
+                    regex: RegexPattern::new("ada"),
+                },
+                required: true,
+                ownership: ParameterOwnership::Unknown,
+                meta_data: Some(ParameterMetaData::HTTP {
+                    target: HTTPParameterTarget::Query,
+                }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            }],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+
+        let previews = sut::sequence_preview(&ctx, std::slice::from_ref(&op), vec![op.clone()]);
+
+        assert_eq!(previews.len(), 1);
+        let (name, preview) = &previews[0];
+        assert_eq!(name, "get_persons");
+        let preview = preview.as_ref().unwrap();
+        assert_eq!(preview.method, "GET");
+        assert_eq!(preview.url, "http://localhost:1/persons?name=ada");
+        assert!(preview
+            .headers
+            .contains(&("authorization".to_string(), "Bearer secret".to_string())));
+        assert!(preview
+            .headers
+            .contains(&("x-correlation-id".to_string(), "qr-00000007-1".to_string())));
+        assert_eq!(preview.body, None);
+        // Nothing was invoked
+        assert_eq!(ctx.test_cases.get(), 0);
+    }
+
+    #[test]
+    fn preview_requests_an_encoding_unless_one_is_set() {
+        let op = Operation {
+            info: OperationInfo {
+                name: "get_persons".to_string(),
+                key: "operation/get_persons".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        };
+        let accept_encodings = |config: http::HTTPConfiguration| {
+            let ctx = ExplorationContext::new(Target::HTTP { config });
+            let previews = sut::sequence_preview(&ctx, std::slice::from_ref(&op), vec![op.clone()]);
+            let preview = previews[0].1.clone().unwrap();
+            preview
+                .headers
+                .into_iter()
+                .filter(|(name, _)| name == "accept-encoding")
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>()
+        };
+
+        let config = http::HTTPConfiguration::new("localhost".to_string(), 1, http::Protocol::HTTP);
+        assert_eq!(accept_encodings(config.clone()), vec!["gzip, deflate"]);
+
+        let pinning = http::RequestPinning {
+            headers: [("Accept-Encoding".to_string(), "identity".to_string())].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            accept_encodings(config.with_pinning(pinning)),
+            vec!["identity"]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::amos::InvokeResult;
+
+/// Number of windows a soak run is split into, a trend has the mean of each
+pub const SOAK_WINDOWS: usize = 5;
+
+/// How much slower the last window must be than the first, for the increase
+///  not to be noise
+pub const MIN_DEGRADATION: f64 = 1.1;
+
+/// The response time and error rate of an operation over a soak run
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OperationTrend {
+    pub operation: String,
+    /// Mean response time in milliseconds of each window
+    pub latency_ms: Vec<f64>,
+    /// Share of unsuccessful results in each window
+    pub error_rate: Vec<f64>,
+}
+
+impl OperationTrend {
+    /// If the latency increases in every window, a sign of a leak in the SUT
+    pub fn is_degrading(&self) -> bool {
+        let (Some(first), Some(last)) = (self.latency_ms.first(), self.latency_ms.last()) else {
+            return false;
+        };
+
+        self.latency_ms.len() > 1
+            && self.latency_ms.windows(2).all(|w| w[1] > w[0])
+            && *last >= first * MIN_DEGRADATION
+    }
+}
+
+/// The results of the repetitions of a soak run, in order of invocation
+#[derive(Debug, Default)]
+pub struct SoakLog {
+    samples: Vec<(String, Vec<(Duration, bool)>)>,
+}
+
+impl SoakLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the results of one repetition, results without a duration are
+    ///  not from the SUT and are skipped
+    pub fn record(&mut self, results: &[InvokeResult]) {
+        for result in results {
+            let Some(duration) = result.duration else {
+                continue;
+            };
+
+            let sample = (duration, result.success);
+            match self
+                .samples
+                .iter_mut()
+                .find(|(name, _)| *name == result.operation.name)
+            {
+                Some((_, samples)) => samples.push(sample),
+                None => self
+                    .samples
+                    .push((result.operation.name.clone(), vec![sample])),
+            }
+        }
+    }
+
+    /// The trend of each operation with enough samples to fill the windows
+    pub fn trends(&self, windows: usize) -> Vec<OperationTrend> {
+        self.samples
+            .iter()
+            .filter(|(_, samples)| windows > 0 && samples.len() >= windows)
+            .map(|(operation, samples)| {
+                let size = samples.len() / windows;
+                let chunks: Vec<&[(Duration, bool)]> =
+                    samples.chunks_exact(size).take(windows).collect();
+
+                OperationTrend {
+                    operation: operation.clone(),
+                    latency_ms: chunks
+                        .iter()
+                        .map(|chunk| {
+                            let total: Duration = chunk.iter().map(|(d, _)| *d).sum();
+                            total.as_secs_f64() * 1000.0 / chunk.len() as f64
+                        })
+                        .collect(),
+                    error_rate: chunks
+                        .iter()
+                        .map(|chunk| {
+                            let errors = chunk.iter().filter(|(_, success)| !success).count();
+                            errors as f64 / chunk.len() as f64
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amos_generation::GeneratedOperation;
+
+    fn result(name: &str, ms: u64, success: bool) -> InvokeResult {
+        InvokeResult::new(
+            GeneratedOperation {
+                name: name.to_string(),
+                parameters: vec![],
+            },
+            "[]".to_string(),
+            success,
+            None,
+        )
+        .with_duration(Duration::from_millis(ms))
+    }
+
+    #[test]
+    fn trends_per_window() {
+        let mut log = SoakLog::new();
+        for n in 0..10 {
+            // One repetition of the sequence
+            log.record(&[
+                result("post_person", 10 + n * 5, n < 8),
+                result("get_persons", 10, true),
+            ]);
+        }
+
+        let trends = log.trends(SOAK_WINDOWS);
+
+        let post = &trends[0];
+        assert_eq!(post.operation, "post_person");
+        assert_eq!(post.latency_ms, vec![12.5, 22.5, 32.5, 42.5, 52.5]);
+        assert_eq!(post.error_rate, vec![0.0, 0.0, 0.0, 0.0, 1.0]);
+        assert!(post.is_degrading());
+
+        assert!(!trends[1].is_degrading());
+    }
+
+    #[test]
+    fn degradation_must_exceed_noise() {
+        let trend = OperationTrend {
+            operation: "get_persons".to_string(),
+            latency_ms: vec![10.0, 10.1, 10.2, 10.3, 10.4],
+            error_rate: vec![0.0; 5],
+        };
+        assert!(!trend.is_degrading());
+
+        // Too few samples for a trend
+        let mut log = SoakLog::new();
+        log.record(&[result("get_persons", 10, true)]);
+        assert!(log.trends(SOAK_WINDOWS).is_empty());
+    }
+}
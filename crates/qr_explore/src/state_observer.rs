@@ -0,0 +1,142 @@
+use crate::amos::InvokeResult;
+use crate::amos_generation::GeneratedOperation;
+
+/// The operation name of the results produced by a state observer
+pub const OBSERVATION_OPERATION: &str = "state_observation";
+
+/// Observes state of the SUT that is not visible through its API
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum StateObserver {
+    /// A shell command printing the state as JSON on stdout. A database is
+    ///  observed through its CLI, e.g.
+    ///  `sqlite3 -json app.db "SELECT * FROM persons ORDER BY id"`
+    Command { command: String },
+}
+
+/// How the observations are used by the state behaviours
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ObservationMode {
+    /// Only the observations are checked, the query operation just marks when
+    ///  to observe
+    InsteadOfQuery,
+    /// Both the query operation results and the observations are checked
+    AlongsideQuery,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StateObservation {
+    pub observer: StateObserver,
+    pub mode: ObservationMode,
+}
+
+impl StateObserver {
+    /// The observed state as canonical JSON, i.e. the key order of the output does
+    ///  not affect the comparison of two observations
+    pub fn observe(&self) -> Result<String, String> {
+        match self {
+            StateObserver::Command { command } => {
+                let output = shell(command)
+                    .output()
+                    .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+
+                if !output.status.success() {
+                    return Err(format!(
+                        "'{}' exited with {}: {}",
+                        command,
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+
+                let state: serde_json::Value = serde_json::from_slice(&output.stdout)
+                    .map_err(|e| format!("'{}' did not print JSON: {}", command, e))?;
+
+                Ok(state.to_string())
+            }
+        }
+    }
+
+    /// Observe the state as a result of the sequence. A failed observation is
+    ///  no state of the SUT, it is returned as its error.
+    pub fn observation_result(&self) -> Result<InvokeResult, String> {
+        let operation = GeneratedOperation {
+            name: OBSERVATION_OPERATION.to_string(),
+            parameters: vec![],
+        };
+
+        self.observe()
+            .map(|state| InvokeResult::new(operation, state, true, None))
+    }
+}
+
+/// Run a shell command for its effect on the SUT, e.g. to reset its state
+pub fn run_command(command: &str) -> Result<(), String> {
+    run_process(shell(command), command)
+}
+
+/// Run the process to its end, `description` names it in the errors
+pub(crate) fn run_process(
+    mut process: std::process::Command,
+    description: &str,
+) -> Result<(), String> {
+    let output = process
+        .output()
+        .map_err(|e| format!("Failed to run '{}': {}", description, e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' exited with {}: {}",
+            description,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub(crate) fn shell(command: &str) -> std::process::Command {
+    if cfg!(target_os = "windows") {
+        let mut shell = std::process::Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = std::process::Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_observation_is_canonical_json() {
+        let observer = StateObserver::Command {
+            command: r#"echo '{"name": "Alice", "id": 1}'"#.to_string(),
+        };
+
+        assert_eq!(
+            observer.observe(),
+            Ok(r#"{"id":1,"name":"Alice"}"#.to_string())
+        );
+
+        let result = observer.observation_result().unwrap();
+        assert!(result.success);
+        assert_eq!(result.operation.name, OBSERVATION_OPERATION);
+    }
+
+    #[test]
+    fn failed_command_observation() {
+        let not_json = StateObserver::Command {
+            command: "echo not json".to_string(),
+        };
+        assert!(not_json.observe().is_err());
+
+        let failing = StateObserver::Command {
+            command: "exit 3".to_string(),
+        };
+        assert!(failing.observation_result().is_err());
+    }
+}
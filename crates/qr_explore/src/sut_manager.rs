@@ -0,0 +1,239 @@
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::exploration_settings::{SutLauncher, SutSettings};
+use crate::explore::{self, Target};
+use crate::health;
+use crate::state_observer::{run_command, run_process, shell};
+
+/// Starts the SUT of the settings, waits until it is healthy and tears it
+///  down. An unmanaged SUT is expected to be running, starting and stopping
+///  it does nothing. A SUT still running when the manager is dropped is
+///  stopped.
+#[derive(Debug)]
+pub struct SutManager {
+    settings: SutSettings,
+    /// The process of the start command, while it runs the SUT
+    child: Option<Child>,
+    running: bool,
+}
+
+impl SutManager {
+    /// How often the health of a starting SUT is probed
+    pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    pub fn new(settings: SutSettings) -> Self {
+        Self {
+            settings,
+            child: None,
+            running: false,
+        }
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.settings.is_managed()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn resets_between_behaviours(&self) -> bool {
+        self.is_managed() && self.settings.reset_between_behaviours
+    }
+
+    /// Start the SUT and wait until it is healthy, a SUT that never gets
+    ///  healthy is stopped again
+    pub fn start(&mut self, target: &Target) -> Result<(), String> {
+        if !self.is_managed() || self.running {
+            return Ok(());
+        }
+
+        match &self.settings.launcher {
+            SutLauncher::None => (),
+            SutLauncher::Command { start, .. } => {
+                let child = shell(start)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .map_err(|e| format!("Failed to run '{}': {}", start, e))?;
+                self.child = Some(child);
+            }
+            SutLauncher::Compose { file } => compose(file, &["up", "-d"])?,
+        }
+        self.running = true;
+
+        if let Err(reason) = self.wait_until_healthy(target) {
+            if let Err(e) = self.stop() {
+                warn!("{}", e);
+            }
+            return Err(reason);
+        }
+        Ok(())
+    }
+
+    /// Stop the SUT, if it was started
+    pub fn stop(&mut self) -> Result<(), String> {
+        if !self.running {
+            return Ok(());
+        }
+        self.running = false;
+
+        let stopped = match &self.settings.launcher {
+            SutLauncher::None => Ok(()),
+            SutLauncher::Command { stop, .. } => stop.as_deref().map_or(Ok(()), run_command),
+            SutLauncher::Compose { file } => compose(file, &["down"]),
+        };
+
+        // Killed in case the stop command left it running, or it exited already
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        stopped
+    }
+
+    /// Stop and start the SUT again, to bring it back to its initial state
+    pub fn reset(&mut self, target: &Target) -> Result<(), String> {
+        self.stop()?;
+        self.start(target)
+    }
+
+    fn wait_until_healthy(&mut self, target: &Target) -> Result<(), String> {
+        let timeout = Duration::from_secs(self.settings.startup_timeout_secs);
+        let started = Instant::now();
+
+        loop {
+            if let Some(child) = &mut self.child {
+                if let Ok(Some(status)) = child.try_wait() {
+                    return Err(format!("The SUT exited with {} while starting", status));
+                }
+            }
+
+            let reason = match self.probe(target) {
+                Ok(()) => return Ok(()),
+                Err(reason) => reason,
+            };
+            if started.elapsed() >= timeout {
+                return Err(format!(
+                    "The SUT was not healthy within {} s: {}",
+                    timeout.as_secs(),
+                    reason
+                ));
+            }
+
+            std::thread::sleep(SutManager::POLL_INTERVAL);
+        }
+    }
+
+    fn probe(&self, target: &Target) -> Result<(), String> {
+        match &self.settings.health_path {
+            Some(path) => health::probe(target, path),
+            None => explore::preflight(target),
+        }
+    }
+}
+
+impl Drop for SutManager {
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            warn!("{}", e);
+        }
+    }
+}
+
+/// Run docker compose on the file, the file is passed as it is and never
+///  through a shell
+fn compose(file: &str, args: &[&str]) -> Result<(), String> {
+    let mut process = Command::new("docker");
+    process.args(["compose", "-f", file]).args(args);
+
+    run_process(
+        process,
+        &format!("docker compose -f {} {}", file, args.join(" ")),
+    )
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use qr_http_resource::http;
+
+    use crate::exploration_settings::{SutLauncher, SutSettings};
+    use crate::explore::Target;
+    use crate::sut_manager as sut;
+
+    fn target(port: u16) -> Target {
+        Target::HTTP {
+            config: http::HTTPConfiguration::new(
+                "127.0.0.1".to_string(),
+                port,
+                http::Protocol::HTTP,
+            ),
+        }
+    }
+
+    fn settings(start: &str, stop: Option<&str>) -> SutSettings {
+        SutSettings {
+            launcher: SutLauncher::Command {
+                start: start.to_string(),
+                stop: stop.map(str::to_string),
+            },
+            startup_timeout_secs: 2,
+            ..SutSettings::new()
+        }
+    }
+
+    #[test]
+    fn started_until_stopped() {
+        // Stands in for the SUT accepting connections
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let target = target(listener.local_addr().unwrap().port());
+
+        let mut manager = sut::SutManager::new(settings("sleep 30", None));
+        manager.start(&target).unwrap();
+        assert!(manager.is_running());
+        manager.reset(&target).unwrap();
+        manager.stop().unwrap();
+        assert!(!manager.is_running());
+
+        // The stop command stops the SUT
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("stopped");
+        let stop = format!("touch '{}'", marker.display());
+        let mut manager = sut::SutManager::new(settings("sleep 30", Some(&stop)));
+        manager.start(&target).unwrap();
+        drop(manager);
+        assert!(marker.exists());
+
+        // An unmanaged SUT is expected to be running
+        let mut manager = sut::SutManager::new(SutSettings::new());
+        manager.start(&target).unwrap();
+        assert!(!manager.is_running());
+    }
+
+    #[test]
+    fn the_compose_file_is_not_run_by_a_shell() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("injected");
+        let file = format!("x'; touch '{}'; '", marker.display());
+
+        assert!(sut::compose(&file, &["down"]).is_err());
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn a_sut_exiting_while_starting_is_not_healthy() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let mut manager = sut::SutManager::new(settings("exit 3", None));
+        let error = manager.start(&target(port)).unwrap_err();
+        assert!(error.contains("exited"), "{}", error);
+        assert!(!manager.is_running());
+    }
+}
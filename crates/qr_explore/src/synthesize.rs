@@ -1,4 +1,4 @@
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{
     amos::Operation,
@@ -7,8 +7,8 @@ use crate::{
 };
 
 pub fn synthesize_operation(
-    ops: &[(Operation, [ParameterValue; 10])],
-    generated_op: (Operation, [ParameterValue; 10]),
+    ops: &[(Operation, Vec<ParameterValue>)],
+    generated_op: (Operation, Vec<ParameterValue>),
 ) -> GeneratedOperation {
     let mut sparams = Vec::with_capacity(generated_op.0.parameters.len());
 
@@ -49,6 +49,7 @@ pub fn synthesize_operation(
                     match relation {
                         Relation::Parameter(info) => {
                             let mut ref_info = info.clone();
+                            let mut broken = false;
 
                             loop {
                                 // resolve the next step in the chain
@@ -71,7 +72,11 @@ pub fn synthesize_operation(
                                             continue;
                                         }
                                     },
-                                    ParameterValue::Empty => panic!("Broken reference chain"),
+                                    ParameterValue::Empty => {
+                                        warn!("Broken reference chain: {:#?}", resolved_params);
+                                        broken = true;
+                                        break;
+                                    }
                                     _ => {
                                         // We are done
                                         break;
@@ -81,18 +86,23 @@ pub fn synthesize_operation(
 
                             // collect the finally resolved info
                             let (resolved_op, resolved_params) = &ops[ref_info.op_idx];
-                            let resolved_param = resolved_params[ref_info.idx].clone();
-
-                            // an Empty value is always wrong at this point
-                            assert!(resolved_param != ParameterValue::Empty);
+                            // a broken chain has no value, the fallback is sent instead
+                            let resolved_param = if broken {
+                                *fallback.clone()
+                            } else {
+                                resolved_params[ref_info.idx].clone()
+                            };
 
                             GeneratedParameter {
                                 // the name of the current parameter we are resolving
                                 name: generated_op.0.parameters[i].name.clone(),
                                 value: resolved_param,
                                 ref_path: Some(format!(
-                                    "REFERENCE - Active - {}[{}]/{}",
-                                    resolved_op.info.name, ref_info.op_idx, ref_info.name,
+                                    "REFERENCE - Active - {}[{}]/{} ({})",
+                                    resolved_op.info.name,
+                                    ref_info.op_idx,
+                                    ref_info.name,
+                                    info.explanation(),
                                 )),
                             }
                         }
@@ -105,8 +115,11 @@ pub fn synthesize_operation(
                                 name: generated_op.0.parameters[i].name.clone(),
                                 value: v.clone(),
                                 ref_path: Some(format!(
-                                    "RSP REFERENCE - Active - {}[{}]/{}",
-                                    info.operation, info.op_idx, info.idx,
+                                    "RSP REFERENCE - Active - {}[{}]/{} ({})",
+                                    info.operation,
+                                    info.op_idx,
+                                    info.idx,
+                                    info.explanation(),
                                 )),
                             }
                         }
@@ -133,7 +146,7 @@ pub fn synthesize_operation(
     }
 }
 
-pub fn synthesize_operations(ops: &[(Operation, [ParameterValue; 10])]) -> Vec<GeneratedOperation> {
+pub fn synthesize_operations(ops: &[(Operation, Vec<ParameterValue>)]) -> Vec<GeneratedOperation> {
     ops.iter()
         .map(|op| synthesize_operation(ops, op.clone()))
         .collect()
@@ -141,14 +154,14 @@ pub fn synthesize_operations(ops: &[(Operation, [ParameterValue; 10])]) -> Vec<G
 
 pub fn synthesize_property_operations(
     _query_precedence: u8,
-    ops: &[(Operation, [ParameterValue; 10])],
+    ops: &[(Operation, Vec<ParameterValue>)],
 ) -> Vec<GeneratedOperation> {
     synthesize_operations(ops)
 }
 
 pub fn synthesize_operations_for_response_equality(
     _query_precedence: u8,
-    ops: &[(Operation, [ParameterValue; 10])],
+    ops: &[(Operation, Vec<ParameterValue>)],
 ) -> Vec<GeneratedOperation> {
     // The first generated operation is the OP that should be repeated
     vec![
@@ -159,27 +172,31 @@ pub fn synthesize_operations_for_response_equality(
 
 pub fn synthesize_operations_for_response_inequality(
     query_precedence: u8,
-    ops: &[(Operation, [ParameterValue; 10])],
+    ops: &[(Operation, Vec<ParameterValue>)],
 ) -> Vec<GeneratedOperation> {
     // The first generated operation is the OP that should be repeated
     synthesize_operations_for_response_equality(query_precedence, ops)
 }
 
 pub fn synthesize_operations_for_state_mutation(
-    _query_precedence: u8,
-    ops: &[(Operation, [ParameterValue; 10])],
+    query_precedence: u8,
+    ops: &[(Operation, Vec<ParameterValue>)],
 ) -> Vec<GeneratedOperation> {
     let mut synth_ops = vec![];
     synth_ops.extend(synthesize_operations(ops));
 
-    // The first generated operation is the query OP and it should also be the last
-    synth_ops.push(synthesize_operation(ops, ops[0].clone()));
+    // The generated operations up until the query precedence are the query OPs
+    //  and they should also be the last
+    let queries = (query_precedence as usize + 1).min(ops.len());
+    for op in &ops[0..queries] {
+        synth_ops.push(synthesize_operation(ops, op.clone()));
+    }
     synth_ops
 }
 
 pub fn synthesize_operations_for_state_identity(
     query_precedence: u8,
-    ops: &[(Operation, [ParameterValue; 10])],
+    ops: &[(Operation, Vec<ParameterValue>)],
 ) -> Vec<GeneratedOperation> {
     if ops.is_empty() {
         return vec![];
@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// The values of `counter()`, sequential in the process
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A value computed when a request is translated, instead of a generated
+///  one, e.g. a signature of the body or an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueProvider {
+    /// `now()`, the seconds since the Unix epoch
+    Now,
+    /// `uuid()`, a random UUID (version 4)
+    Uuid,
+    /// `counter()`, 1, 2, 3, ...
+    Counter,
+    /// `hmac(<secret>, body)`, the HMAC-SHA256 of the JSON body as it is
+    ///  sent, hex encoded
+    Hmac { secret: String },
+}
+
+impl ValueProvider {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!("Expected now(), uuid(), counter() or hmac(<secret>, body), got '{expression}'")
+        };
+        let (name, arguments) = expression
+            .trim()
+            .strip_suffix(')')
+            .and_then(|call| call.split_once('('))
+            .ok_or_else(invalid)?;
+
+        match (name.trim(), arguments.trim()) {
+            ("now", "") => Ok(ValueProvider::Now),
+            ("uuid", "") => Ok(ValueProvider::Uuid),
+            ("counter", "") => Ok(ValueProvider::Counter),
+            ("hmac", arguments) => match arguments.rsplit_once(',') {
+                Some((secret, body)) if body.trim() == "body" && !secret.trim().is_empty() => {
+                    Ok(ValueProvider::Hmac {
+                        secret: secret.trim().to_string(),
+                    })
+                }
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+
+    /// If the value is computed from the body, it is provided once the rest
+    ///  of the request is translated
+    pub fn signs_body(&self) -> bool {
+        matches!(self, ValueProvider::Hmac { .. })
+    }
+
+    /// The value, of the JSON body of the request if it signs it
+    pub fn value(&self, body: Option<&str>) -> String {
+        match self {
+            ValueProvider::Now => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since| since.as_secs())
+                .unwrap_or_default()
+                .to_string(),
+            ValueProvider::Uuid => uuid(),
+            ValueProvider::Counter => (COUNTER.fetch_add(1, Ordering::Relaxed) + 1).to_string(),
+            ValueProvider::Hmac { secret } => {
+                let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+                hex(hmac::sign(&key, body.unwrap_or("").as_bytes()).as_ref())
+            }
+        }
+    }
+}
+
+fn uuid() -> String {
+    let mut bytes = [0u8; 16];
+    // Without a random source the UUID is still well formed
+    let _ = SystemRandom::new().fill(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_provider as sut;
+
+    #[test]
+    fn parse_the_providers() {
+        assert_eq!(
+            sut::ValueProvider::parse("now()"),
+            Ok(sut::ValueProvider::Now)
+        );
+        assert_eq!(
+            sut::ValueProvider::parse(" uuid( ) "),
+            Ok(sut::ValueProvider::Uuid)
+        );
+        assert_eq!(
+            sut::ValueProvider::parse("hmac(a,b, body)"),
+            Ok(sut::ValueProvider::Hmac {
+                secret: "a,b".to_string()
+            })
+        );
+        assert!(sut::ValueProvider::parse("hmac(body)").is_err());
+        assert!(sut::ValueProvider::parse("counter(1)").is_err());
+        assert!(sut::ValueProvider::parse("42").is_err());
+    }
+
+    #[test]
+    fn provide_the_values() {
+        let first: u64 = sut::ValueProvider::Counter.value(None).parse().unwrap();
+        let second: u64 = sut::ValueProvider::Counter.value(None).parse().unwrap();
+        assert!(second > first);
+
+        let uuid = sut::ValueProvider::Uuid.value(None);
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, sut::ValueProvider::Uuid.value(None));
+
+        // RFC 4231, test case 2
+        let signature = sut::ValueProvider::Hmac {
+            secret: "Jefe".to_string(),
+        };
+        assert_eq!(
+            signature.value(Some("what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -1,34 +1,94 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum HTTPStatus {
-    OK = 200,
-    Created = 201,
-    NoContent = 204,
-    BadRequest = 400,
-    Unauthorized = 401,
-    Forbidden = 403,
-    NotFound = 404,
-    MethodNotAllowed = 405,
-    UnsupportedMediaType = 415,
-    InternalServerError = 500,
+    OK,
+    Created,
+    NoContent,
+    NotModified,
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    Conflict,
+    PreconditionFailed,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    InternalServerError,
+    /// A valid code without a variant of its own
+    Other(u16),
     Default,
     Unsupported,
 }
 
+impl HTTPStatus {
+    /// The status of the code, unsupported if it is not a valid code
+    pub fn from_code(code: u16) -> HTTPStatus {
+        match code {
+            200 => HTTPStatus::OK,
+            201 => HTTPStatus::Created,
+            204 => HTTPStatus::NoContent,
+            304 => HTTPStatus::NotModified,
+            400 => HTTPStatus::BadRequest,
+            401 => HTTPStatus::Unauthorized,
+            403 => HTTPStatus::Forbidden,
+            404 => HTTPStatus::NotFound,
+            405 => HTTPStatus::MethodNotAllowed,
+            409 => HTTPStatus::Conflict,
+            412 => HTTPStatus::PreconditionFailed,
+            413 => HTTPStatus::PayloadTooLarge,
+            415 => HTTPStatus::UnsupportedMediaType,
+            500 => HTTPStatus::InternalServerError,
+            100..=599 => HTTPStatus::Other(code),
+            _ => HTTPStatus::Unsupported,
+        }
+    }
+
+    /// The numeric code of the status, if it has one
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            HTTPStatus::OK => Some(200),
+            HTTPStatus::Created => Some(201),
+            HTTPStatus::NoContent => Some(204),
+            HTTPStatus::NotModified => Some(304),
+            HTTPStatus::BadRequest => Some(400),
+            HTTPStatus::Unauthorized => Some(401),
+            HTTPStatus::Forbidden => Some(403),
+            HTTPStatus::NotFound => Some(404),
+            HTTPStatus::MethodNotAllowed => Some(405),
+            HTTPStatus::Conflict => Some(409),
+            HTTPStatus::PreconditionFailed => Some(412),
+            HTTPStatus::PayloadTooLarge => Some(413),
+            HTTPStatus::UnsupportedMediaType => Some(415),
+            HTTPStatus::InternalServerError => Some(500),
+            HTTPStatus::Other(code) => Some(*code),
+            HTTPStatus::Default | HTTPStatus::Unsupported => None,
+        }
+    }
+}
+
 impl fmt::Display for HTTPStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             HTTPStatus::OK => "200",
             HTTPStatus::Created => "201",
             HTTPStatus::NoContent => "204",
+            HTTPStatus::NotModified => "304",
             HTTPStatus::BadRequest => "400",
             HTTPStatus::Unauthorized => "401",
             HTTPStatus::Forbidden => "403",
             HTTPStatus::NotFound => "404",
             HTTPStatus::MethodNotAllowed => "405",
+            HTTPStatus::Conflict => "409",
+            HTTPStatus::PreconditionFailed => "412",
+            HTTPStatus::PayloadTooLarge => "413",
             HTTPStatus::UnsupportedMediaType => "415",
             HTTPStatus::InternalServerError => "500",
+            HTTPStatus::Other(code) => return write!(f, "{}", code),
             HTTPStatus::Default => "Default",
             HTTPStatus::Unsupported => "Unsupported",
         };
@@ -75,7 +135,7 @@ impl MimeData {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 pub enum HTTPMethod {
     GET,
     DELETE,
@@ -84,6 +144,32 @@ pub enum HTTPMethod {
     Unsupported,
 }
 
+impl HTTPMethod {
+    /// The methods an operation can be invoked with
+    pub const SUPPORTED: [HTTPMethod; 4] = [
+        HTTPMethod::GET,
+        HTTPMethod::POST,
+        HTTPMethod::PUT,
+        HTTPMethod::DELETE,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HTTPMethod::GET => "GET",
+            HTTPMethod::DELETE => "DELETE",
+            HTTPMethod::POST => "POST",
+            HTTPMethod::PUT => "PUT",
+            HTTPMethod::Unsupported => "Unsupported",
+        }
+    }
+}
+
+impl fmt::Display for HTTPMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum HTTPParameterTarget {
     Path,
@@ -113,6 +199,11 @@ pub struct HTTPConfiguration {
     pub base_url: String,
     pub port: u16,
     pub protocol: Protocol,
+    /// The path the URLs of the operations are relative to, e.g. '/api/v2'
+    pub base_path: Option<String>,
+    pub paths: PathNormalization,
+    pub pinning: RequestPinning,
+    pub providers: ValueProviders,
 }
 
 impl HTTPConfiguration {
@@ -121,10 +212,239 @@ impl HTTPConfiguration {
             base_url,
             port,
             protocol,
+            base_path: None,
+            paths: PathNormalization::new(),
+            pinning: RequestPinning::new(),
+            providers: ValueProviders::new(),
+        }
+    }
+
+    pub fn with_base_path(mut self, base_path: Option<String>) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    pub fn with_paths(mut self, paths: PathNormalization) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    pub fn with_pinning(mut self, pinning: RequestPinning) -> Self {
+        self.pinning = pinning;
+        self
+    }
+
+    pub fn with_providers(mut self, providers: ValueProviders) -> Self {
+        self.providers = providers;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrailingSlash {
+    /// As the specification and the values make the path
+    #[default]
+    Keep,
+    Add,
+    Remove,
+}
+
+/// How the paths of the requests are normalized before they are sent, some
+///  targets treat '/persons' and '/persons/' differently. By default a path
+///  is sent as the specification and the values make it.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathNormalization {
+    /// Send '//' as '/'
+    pub collapse_slashes: bool,
+    pub trailing_slash: TrailingSlash,
+    /// Lowercase the paths of the specification, the values keep their case
+    pub lowercase: bool,
+}
+
+impl PathNormalization {
+    pub fn new() -> Self {
+        Self {
+            collapse_slashes: false,
+            trailing_slash: TrailingSlash::Keep,
+            lowercase: false,
+        }
+    }
+
+    /// The path of the specification, its parameters keep their case
+    pub fn template(&self, path: &str) -> String {
+        if !self.lowercase {
+            return path.to_string();
+        }
+
+        let mut in_parameter = false;
+        path.chars()
+            .map(|c| {
+                match c {
+                    '{' => in_parameter = true,
+                    '}' => in_parameter = false,
+                    _ => (),
+                }
+                if in_parameter {
+                    c
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect()
+    }
+
+    /// The path as it is sent, the query of the path is left as it is
+    pub fn normalize(&self, path: &str) -> String {
+        let (path, query) = match path.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (path, None),
+        };
+
+        let mut path = path.to_string();
+        if self.collapse_slashes {
+            while path.contains("//") {
+                path = path.replace("//", "/");
+            }
+        }
+        match self.trailing_slash {
+            TrailingSlash::Keep => (),
+            TrailingSlash::Add if !path.ends_with('/') => path.push('/'),
+            TrailingSlash::Add => (),
+            // The root keeps its slash
+            TrailingSlash::Remove => path.truncate(path.trim_end_matches('/').len().max(1)),
+        }
+
+        match query {
+            Some(query) => format!("{}?{}", path, query),
+            None => path,
         }
     }
 }
 
+impl Default for PathNormalization {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The parameters pinned to a constant value and the static headers of an
+///  operation, added to those of all operations
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperationPinning {
+    pub parameters: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>,
+}
+
+/// The parameters sent with a constant value instead of the generated one,
+///  e.g. `tenantId = "42"`, and the headers sent with every request. Keeps an
+///  exploration within a tenant or a configuration of feature flags.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestPinning {
+    pub parameters: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>,
+    /// By the name of the operation, its values take precedence
+    pub operations: BTreeMap<String, OperationPinning>,
+}
+
+impl RequestPinning {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty() && self.headers.is_empty() && self.operations.is_empty()
+    }
+
+    /// The value the parameter of the operation is pinned to
+    pub fn parameter(&self, operation: &str, name: &str) -> Option<&str> {
+        self.operations
+            .get(operation)
+            .and_then(|pinning| pinning.parameters.get(name))
+            .or_else(|| self.parameters.get(name))
+            .map(String::as_str)
+    }
+
+    /// The static headers of the operation
+    pub fn headers(&self, operation: &str) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if let Some(pinning) = self.operations.get(operation) {
+            headers.extend(pinning.headers.clone());
+        }
+        headers.into_iter().collect()
+    }
+}
+
+/// The expressions of the providers of an operation, added to those of all
+///  operations
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperationProviders {
+    pub parameters: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>,
+}
+
+/// The parameters and headers computed when a request is translated, by the
+///  expressions of their providers, e.g. `now()` or `hmac(<secret>, body)`.
+///  Unlike a pinned value, the value of a provider differs between requests.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValueProviders {
+    pub parameters: BTreeMap<String, String>,
+    pub headers: BTreeMap<String, String>,
+    /// By the name of the operation, its expressions take precedence
+    pub operations: BTreeMap<String, OperationProviders>,
+}
+
+impl ValueProviders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty() && self.headers.is_empty() && self.operations.is_empty()
+    }
+
+    /// The expression of the provider of the parameter of the operation
+    pub fn parameter(&self, operation: &str, name: &str) -> Option<&str> {
+        self.operations
+            .get(operation)
+            .and_then(|providers| providers.parameters.get(name))
+            .or_else(|| self.parameters.get(name))
+            .map(String::as_str)
+    }
+
+    /// The provided headers of the operation, by the expressions of their
+    ///  providers
+    pub fn headers(&self, operation: &str) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if let Some(providers) = self.operations.get(operation) {
+            headers.extend(providers.headers.clone());
+        }
+        headers.into_iter().collect()
+    }
+}
+
+/// A credential sent as a request header
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct HTTPAuth {
+    pub header: String,
+    pub value: String,
+}
+
+impl HTTPAuth {
+    pub fn new(header: String, value: String) -> Self {
+        Self { header, value }
+    }
+
+    pub fn bearer(token: &str) -> Self {
+        Self::new("Authorization".to_string(), format!("Bearer {}", token))
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct HTTPParameters {
     pub url: String,
@@ -138,6 +458,38 @@ pub struct HTTPCall {
     pub url: String,
     pub method: HTTPMethod,
     pub parameters: HTTPParameters,
+    /// Headers added to the request, e.g. credentials
+    pub headers: Vec<(String, String)>,
+}
+
+/// Why a request got no response, unlike a response with an error status
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum TransportError {
+    Connect,
+    Timeout,
+    Redirect,
+    /// The response was cut off or its body could not be read
+    Body,
+    /// The request was never sent, the client dropped it on purpose
+    Dropped,
+    /// The request could not be built, e.g. of an unsupported method
+    Unsupported,
+    Other,
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TransportError::Connect => "connection failed",
+            TransportError::Timeout => "timed out",
+            TransportError::Redirect => "too many redirects",
+            TransportError::Body => "unreadable response",
+            TransportError::Dropped => "dropped by the client",
+            TransportError::Unsupported => "unsupported request",
+            TransportError::Other => "transport error",
+        };
+        write!(f, "{}", s)
+    }
 }
 
 #[derive(Debug)]
@@ -145,4 +497,10 @@ pub struct HTTPResult {
     pub status: HTTPStatus,
     pub payload: String,
     pub success: bool,
+    /// Size in bytes of the payload as received, i.e., before any decoding
+    pub size: usize,
+    /// The Content-Encoding the payload was received with
+    pub content_encoding: Option<String>,
+    /// The response headers, with lower case names
+    pub headers: Vec<(String, String)>,
 }
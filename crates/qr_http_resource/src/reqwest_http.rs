@@ -1,17 +1,22 @@
+use std::io::Read;
+
 use tracing::{debug, error, warn};
 
-use crate::http::{HTTPCall, HTTPMethod, HTTPResult, HTTPStatus};
+use crate::http::{HTTPCall, HTTPMethod, HTTPResult, HTTPStatus, TransportError};
 
 pub fn build_reqwest_request(
     client: &reqwest::blocking::Client,
     http_operation: &HTTPCall,
-) -> reqwest::blocking::RequestBuilder {
+) -> Result<reqwest::blocking::RequestBuilder, TransportError> {
     let con_method = match http_operation.method {
         HTTPMethod::GET => reqwest::Method::GET,
         HTTPMethod::POST => reqwest::Method::POST,
         HTTPMethod::DELETE => reqwest::Method::DELETE,
         HTTPMethod::PUT => reqwest::Method::PUT,
-        _ => todo!(),
+        HTTPMethod::Unsupported => {
+            warn!(http_operation.url, "Request of an unsupported method");
+            return Err(TransportError::Unsupported);
+        }
     };
     let init_request = client.request(con_method, http_operation.url.clone());
 
@@ -35,7 +40,7 @@ pub fn build_reqwest_request(
             payload = reqwest::blocking::multipart::Part::bytes(v.as_bytes().to_owned())
                 .file_name("foo.bar")
                 .mime_str("application/octet-stream")
-                .unwrap();
+                .map_err(|e| transport_error(&e))?;
             param_name = k.to_string();
         }
 
@@ -45,30 +50,115 @@ pub fn build_reqwest_request(
         request_with_form_data
     };
 
-    if let Some(body) = &http_operation.parameters.body {
+    let request_with_body = if let Some(body) = &http_operation.parameters.body {
         // TODO: respect the operations "consumes" mime type
         request_with_form_and_file.json(&body)
     } else {
         request_with_form_and_file
+    };
+
+    Ok(http_operation
+        .headers
+        .iter()
+        .fold(request_with_body, |request, (name, value)| {
+            request.header(name, value)
+        }))
+}
+
+/// The request as it is sent
+fn prepared_request(
+    client: &reqwest::blocking::Client,
+    http_operation: &HTTPCall,
+) -> Result<reqwest::blocking::RequestBuilder, TransportError> {
+    let request = build_reqwest_request(client, http_operation)?;
+
+    // The payload is decoded here, to know the size and encoding it was sent
+    //  with. An encoding set in the headers of the call is sent as it is.
+    let has_accept_encoding = http_operation
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(reqwest::header::ACCEPT_ENCODING.as_str()));
+    if has_accept_encoding {
+        Ok(request)
+    } else {
+        Ok(request.header(reqwest::header::ACCEPT_ENCODING, "gzip, deflate"))
     }
 }
 
 pub fn invoke_with_reqwest(
     client: &reqwest::blocking::Client,
     http_operation: HTTPCall,
-) -> Option<HTTPResult> {
-    let request = build_reqwest_request(client, &http_operation);
+) -> Result<HTTPResult, TransportError> {
+    let request = prepared_request(client, &http_operation)?;
     let resp = request.send();
     process_reqwest_response(resp)
 }
 
+/// A request exactly as it would be sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestPreview {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// Build the request of the call without sending it. A multipart body is
+///  streamed when sent, and not shown.
+pub fn preview_request(
+    client: &reqwest::blocking::Client,
+    http_operation: &HTTPCall,
+) -> Result<RequestPreview, String> {
+    let request = prepared_request(client, http_operation)
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    let body = request.body().map(|body| match body.as_bytes() {
+        Some(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        None => "<multipart form>".to_string(),
+    });
+
+    Ok(RequestPreview {
+        method: request.method().to_string(),
+        url: request.url().to_string(),
+        headers,
+        body,
+    })
+}
+
+/// The kind of the error of a request that got no response
+fn transport_error(e: &reqwest::Error) -> TransportError {
+    if e.is_timeout() {
+        TransportError::Timeout
+    } else if e.is_connect() {
+        TransportError::Connect
+    } else if e.is_redirect() {
+        TransportError::Redirect
+    } else if e.is_body() || e.is_decode() {
+        TransportError::Body
+    } else {
+        TransportError::Other
+    }
+}
+
 fn process_reqwest_response(
     response: Result<reqwest::blocking::Response, reqwest::Error>,
-) -> Option<HTTPResult> {
+) -> Result<HTTPResult, TransportError> {
     match response {
         Err(e) => {
             error!("HTTP Invoke error: {}", e);
-            None
+            Err(transport_error(&e))
         }
         Ok(r) => {
             debug!("Response: {:#?}", r);
@@ -76,30 +166,63 @@ fn process_reqwest_response(
             let _server_error = &r.status().is_server_error();
             let success = &r.status().is_success();
 
-            if let Ok(t) = &r.text() {
-                Some(HTTPResult {
-                    status: match status.as_u16() {
-                        200 => HTTPStatus::OK,
-                        201 => HTTPStatus::Created,
-                        204 => HTTPStatus::NoContent,
-                        400 => HTTPStatus::BadRequest,
-                        401 => HTTPStatus::Unauthorized,
-                        403 => HTTPStatus::Forbidden,
-                        404 => HTTPStatus::NotFound,
-                        405 => HTTPStatus::MethodNotAllowed,
-                        415 => HTTPStatus::UnsupportedMediaType,
-                        500 => HTTPStatus::InternalServerError,
-                        _ => {
-                            warn!("Unsupported status code: {}", status.as_u16());
-                            HTTPStatus::Unsupported
-                        }
-                    },
-                    payload: t.clone(),
-                    success: *success,
+            let content_encoding = r
+                .headers()
+                .get(reqwest::header::CONTENT_ENCODING)
+                .and_then(|encoding| encoding.to_str().ok())
+                .map(|encoding| encoding.to_lowercase());
+            let headers = r
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.as_str().to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                    )
                 })
-            } else {
-                None
+                .collect();
+
+            match r.bytes() {
+                Ok(bytes) => {
+                    let payload = decode_payload(&bytes, content_encoding.as_deref());
+                    Ok(HTTPResult {
+                        status: match HTTPStatus::from_code(status.as_u16()) {
+                            HTTPStatus::Unsupported => {
+                                warn!("Unsupported status code: {}", status.as_u16());
+                                HTTPStatus::Unsupported
+                            }
+                            status => status,
+                        },
+                        payload,
+                        success: *success,
+                        size: bytes.len(),
+                        content_encoding,
+                        headers,
+                    })
+                }
+                Err(e) => {
+                    error!("HTTP Invoke error: {}", e);
+                    Err(transport_error(&e))
+                }
             }
         }
     }
 }
+
+/// The payload as text, decoded from the content encoding if supported
+fn decode_payload(bytes: &[u8], content_encoding: Option<&str>) -> String {
+    let mut decoded = vec![];
+    let result = match content_encoding {
+        Some("gzip") => flate2::read::GzDecoder::new(bytes).read_to_end(&mut decoded),
+        Some("deflate") => flate2::read::ZlibDecoder::new(bytes).read_to_end(&mut decoded),
+        _ => return String::from_utf8_lossy(bytes).into_owned(),
+    };
+
+    match result {
+        Ok(_) => String::from_utf8_lossy(&decoded).into_owned(),
+        Err(e) => {
+            warn!("Could not decode {:?} payload: {}", content_encoding, e);
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    }
+}
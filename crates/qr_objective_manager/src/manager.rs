@@ -2,12 +2,30 @@ use qr_explore::amos;
 use qr_explore::behaviours;
 use qr_explore::exploration_settings;
 use qr_explore::explore;
+use qr_explore::state_observer;
 
 use std::sync::mpsc;
 use std::thread::JoinHandle;
 
 pub struct Options {
     pub is_dry_run: bool,
+    pub operation_weights: exploration_settings::OperationWeights,
+    pub state_observation: Option<state_observer::StateObservation>,
+    pub response_time: exploration_settings::ResponseTimeSla,
+    pub payload: exploration_settings::PayloadLimits,
+    pub injection: exploration_settings::InjectionSettings,
+    pub auth: exploration_settings::AuthSettings,
+    pub correlation: exploration_settings::CorrelationSettings,
+    pub generators: exploration_settings::GeneratorOverrides,
+    pub model: exploration_settings::ModelSettings,
+    /// The responses of a dry run
+    pub mock: exploration_settings::MockSettings,
+    /// The health endpoint of the target
+    pub health: exploration_settings::HealthSettings,
+    /// The build of the SUT the run is tagged with
+    pub tags: exploration_settings::RunTagSettings,
+    /// Invokes the operations instead of sending them over HTTP
+    pub invoker: Option<explore::SharedInvoker>,
 }
 
 // TODO: options and settings should be fixed
@@ -18,17 +36,36 @@ pub fn explore(
     //process_events: fn(mpsc::Receiver<explore::Event>),
     behaviour: &behaviours::Behaviour,
     settings: &exploration_settings::StateMutationSettings,
+    behaviour_settings: &exploration_settings::BehaviourSettings,
 ) -> (JoinHandle<()>, mpsc::Receiver<explore::Event>) {
     let (exploration_log_tx, exploration_log_rx) = std::sync::mpsc::channel();
 
+    let exploration_settings = exploration_settings::ExplorationSettings {
+        state_mutation: settings.clone(),
+        operation_weights: options.operation_weights.clone(),
+        state_observation: options.state_observation.clone(),
+        response_time: options.response_time.clone(),
+        payload: options.payload.clone(),
+        injection: options.injection.clone(),
+        correlation: options.correlation.clone(),
+        health: options.health.clone(),
+        tags: options.tags.clone(),
+        generators: options.generators.clone(),
+        model: options.model.clone(),
+        mock: options.mock.clone(),
+        sut: exploration_settings::SutSettings::new(),
+        behaviour: behaviour_settings.clone(),
+        auth: options.auth.clone(),
+    };
+
     let handle = qr_explore::spawn_exploration(
         target,
         options.is_dry_run,
         amos,
-        exploration_log_tx.clone(),
-        amos.operations.clone(),
+        Box::new(exploration_log_tx.clone()),
         behaviour,
-        settings,
+        &exploration_settings,
+        explore::ExplorationControl::new().with_invoker(options.invoker.clone()),
     );
 
     //process_events(exploration_log_rx);
@@ -54,6 +54,9 @@ pub struct OperationParameter {
     pub kind: DataType,
     pub target: ParameterIn,
     pub required: bool,
+    /// The `example`, `x-example` and `default` values, of the parameter or
+    ///  its schema
+    pub examples: Vec<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -80,6 +83,28 @@ pub struct OperationResponse {
     pub status: HTTPStatus,
     pub description: String,
     pub schema: Option<DataType>,
+    /// The `links` of OpenAPI 3, to the operations the response feeds
+    pub links: Vec<ResponseLink>,
+}
+
+/// A link of a response to the operation of its `operationId`. The values of
+///  the parameters of the operation are runtime expressions, e.g.
+///  `$response.body#/id` or `$request.path.userId`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseLink {
+    pub name: String,
+    pub operation_id: String,
+    /// The parameters of the linked operation, with the expressions of their values
+    pub parameters: Vec<(String, String)>,
+}
+
+/// A callback of OpenAPI 3, an operation the SUT sends to the URL of the
+///  runtime expression, e.g. `{$request.body#/callbackUrl}`, after the
+///  operation declaring it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Callback {
+    pub name: String,
+    pub operation: Operation,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -91,6 +116,8 @@ pub struct Operation {
     pub consumes: Vec<MimeData>,
     pub parameters: Vec<OperationParameter>,
     pub responses: Vec<OperationResponse>,
+    pub deprecated: bool,
+    pub callbacks: Vec<Callback>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -309,6 +336,28 @@ fn parse_parmeter_in(context: &mut ParseContext, param: &Map<String, Value>) ->
     }
 }
 
+/// The example values of a parameter or a property, and of its schema. Only
+///  the values that are not objects or arrays are examples of a value.
+fn parse_examples(object: &Map<String, Value>) -> Vec<String> {
+    let schema = object.get("schema").and_then(Value::as_object);
+    let mut examples: Vec<String> = vec![];
+
+    for object in std::iter::once(object).chain(schema) {
+        for key in ["example", "x-example", "default"] {
+            let example = match object.get(key) {
+                Some(Value::String(example)) => example.clone(),
+                Some(example @ (Value::Number(_) | Value::Bool(_))) => example.to_string(),
+                _ => continue,
+            };
+            if !examples.contains(&example) {
+                examples.push(example);
+            }
+        }
+    }
+
+    examples
+}
+
 fn parse_method_parameter(context: &mut ParseContext, param: &Value) -> OperationParameter {
     let param_object = param.as_object().expect("Could not parse parameter");
 
@@ -317,27 +366,27 @@ fn parse_method_parameter(context: &mut ParseContext, param: &Value) -> Operatio
         kind: parse_parameter_type(context, param_object),
         target: parse_parmeter_in(context, param_object),
         required: param_object["required"].as_bool().unwrap(),
+        examples: parse_examples(param_object),
     }
 }
 
 fn parse_status_code(context: &mut ParseContext, status_code: &str) -> HTTPStatus {
-    match status_code {
-        "200" => HTTPStatus::OK,
-        "201" => HTTPStatus::Created,
-        "204" => HTTPStatus::NoContent,
-        "400" => HTTPStatus::BadRequest,
-        "401" => HTTPStatus::Unauthorized,
-        "403" => HTTPStatus::Forbidden,
-        "404" => HTTPStatus::NotFound,
-        "405" => HTTPStatus::MethodNotAllowed,
+    let status = match status_code {
         "default" => HTTPStatus::Default,
-        _ => {
+        code => code
+            .parse()
+            .map_or(HTTPStatus::Unsupported, HTTPStatus::from_code),
+    };
+
+    match status {
+        HTTPStatus::Unsupported => {
             context.push_warning(ParseMessage::new(format!(
                 "Unsupported status : {}",
                 status_code
             )));
             HTTPStatus::Unsupported
         }
+        status => status,
     }
 }
 
@@ -383,9 +432,56 @@ fn parse_response(
             .expect("Could not parse response description")
             .to_string(),
         schema: parse_schema(context, response_obj),
+        links: parse_links(context, response_obj),
     }
 }
 
+fn parse_links(context: &mut ParseContext, response_obj: &Map<String, Value>) -> Vec<ResponseLink> {
+    let Some(links) = response_obj.get("links").and_then(Value::as_object) else {
+        return vec![];
+    };
+
+    links
+        .iter()
+        .filter_map(|(name, link)| {
+            // A link by 'operationRef' would need the references resolved
+            let Some(operation_id) = link.get("operationId").and_then(Value::as_str) else {
+                context.push_warning(ParseMessage::new(format!(
+                    "Unsupported link without an 'operationId' : {}",
+                    name
+                )));
+                return None;
+            };
+
+            let parameters = link
+                .get("parameters")
+                .and_then(Value::as_object)
+                .map(|parameters| {
+                    parameters
+                        .iter()
+                        .filter_map(|(parameter, value)| match value.as_str() {
+                            Some(expression) => Some((parameter.clone(), expression.to_string())),
+                            None => {
+                                context.push_warning(ParseMessage::new(format!(
+                                    "Unsupported constant of link parameter : {}/{}",
+                                    name, parameter
+                                )));
+                                None
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(ResponseLink {
+                name: name.clone(),
+                operation_id: operation_id.to_string(),
+                parameters,
+            })
+        })
+        .collect()
+}
+
 fn parse_method_responses(
     context: &mut ParseContext,
     response: &Map<String, Value>,
@@ -475,6 +571,47 @@ fn parse_method_parameters(
     }
 }
 
+/// The operations of the callbacks of the method, each of a runtime expression
+///  and the methods sent to it
+fn parse_callbacks(context: &mut ParseContext, method_info: &Map<String, Value>) -> Vec<Callback> {
+    let Some(callbacks) = method_info.get("callbacks").and_then(Value::as_object) else {
+        return vec![];
+    };
+
+    // The callbacks are parsed as paths, the context is of the declaring method
+    let (path, operation, method) = (
+        context.current_path.clone(),
+        context.current_operation.clone(),
+        context.current_method.clone(),
+    );
+    let mut parsed = vec![];
+    for (name, expressions) in callbacks {
+        let Some(expressions) = expressions.as_object() else {
+            context.push_warning(ParseMessage::new(format!(
+                "Could not parse callback {} as object",
+                name
+            )));
+            continue;
+        };
+
+        for expression in expressions {
+            parsed.extend(
+                parse_path(context, expression)
+                    .into_iter()
+                    .map(|operation| Callback {
+                        name: name.clone(),
+                        operation,
+                    }),
+            );
+        }
+    }
+    context.current_path = path;
+    context.current_operation = operation;
+    context.current_method = method;
+
+    parsed
+}
+
 fn parse_operation_method(
     context: &mut ParseContext,
     path: &String,
@@ -519,12 +656,17 @@ fn parse_operation_method(
         url: path.to_string(),
         method: parse_method_str(context, method),
         id: operation_id,
-        produces: method_info["produces"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|x| parse_mime_types_string(context, x.as_str().unwrap()))
-            .collect(),
+        // OpenAPI 3 declares the types of the content instead, as do callbacks
+        produces: method_info
+            .get("produces")
+            .and_then(Value::as_array)
+            .map(|produces| {
+                produces
+                    .iter()
+                    .map(|x| parse_mime_types_string(context, x.as_str().unwrap()))
+                    .collect()
+            })
+            .unwrap_or_default(),
         consumes,
         parameters,
         responses: parse_method_responses(
@@ -533,6 +675,11 @@ fn parse_operation_method(
                 .as_object()
                 .expect("Could not parse method responses"),
         ),
+        deprecated: method_info
+            .get("deprecated")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        callbacks: parse_callbacks(context, method_info),
     }
 }
 
@@ -559,6 +706,8 @@ fn parse_path(context: &mut ParseContext, (path, value): (&String, &Value)) -> V
 pub struct Property {
     pub name: String,
     pub kind: DataType,
+    /// The `example` and `default` values of the property
+    pub examples: Vec<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -627,6 +776,7 @@ fn parse_defintion_property(
     Property {
         name: name.to_string(),
         kind: parse_property_type_and_format(context, property_obj),
+        examples: parse_examples(property_obj),
     }
 }
 
@@ -659,6 +809,8 @@ fn parse_definition(context: &mut ParseContext, (name, value): (&String, &Value)
 pub struct ParseResult {
     pub operations: Vec<Operation>,
     pub definitions: Vec<Definition>,
+    /// The path the paths of the operations are relative to, e.g. '/api/v2'
+    pub base_path: Option<String>,
     pub warnings: Vec<ParseMessage>,
 }
 
@@ -671,8 +823,49 @@ pub fn parse_definitions(ctx: &mut ParseContext, definitions_json: &Value) -> Ve
         .collect()
 }
 
+/// The `basePath` of Swagger, or the path of the URL of the first server of
+///  OpenAPI 3. A root path is no base path.
+fn parse_base_path(
+    ctx: &mut ParseContext,
+    open_api_object: &serde_json::Map<String, Value>,
+) -> Option<String> {
+    let path = match open_api_object.get("basePath") {
+        Some(base_path) => base_path.as_str()?.to_string(),
+        None => {
+            let url = open_api_object
+                .get("servers")?
+                .get(0)?
+                .get("url")?
+                .as_str()?;
+            // An absolute URL, or a URL relative to the specification
+            match url.split_once("://") {
+                Some((_, rest)) => rest.find('/').map_or("", |idx| &rest[idx..]).to_string(),
+                None => url.to_string(),
+            }
+        }
+    };
+
+    if path.contains('{') {
+        ctx.push_warning(ParseMessage::new(format!(
+            "Base path with server variables is not supported: {}",
+            path
+        )));
+        return None;
+    }
+
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        None
+    } else if path.starts_with('/') {
+        Some(path.to_string())
+    } else {
+        Some(format!("/{}", path))
+    }
+}
+
 pub fn parse_json_object(open_api_object: &serde_json::Map<String, Value>) -> Result<ParseResult> {
     let mut ctx = ParseContext::new();
+    let base_path = parse_base_path(&mut ctx, open_api_object);
 
     // Parse the operations defined in paths
     let mut operations: Vec<Operation> = vec![];
@@ -722,6 +915,7 @@ pub fn parse_json_object(open_api_object: &serde_json::Map<String, Value>) -> Re
     Ok(ParseResult {
         operations,
         definitions,
+        base_path,
         warnings: ctx.warnings,
     })
 }
@@ -766,7 +960,7 @@ mod tests {
         let parsed = parse_operation_method(
             &mut ctx,
             &"foo-path".to_string(),
-            (&"get".to_string(), &json_object),
+            (&"get".to_string(), json_object),
         );
 
         assert_eq!(
@@ -788,11 +982,66 @@ mod tests {
                     status: HTTPStatus::OK,
                     description: "OK".to_string(),
                     schema: Some(DataType::ArrayOfStrings),
+                    links: vec![],
                 },],
+                deprecated: false,
+                callbacks: vec![],
             }
         )
     }
 
+    #[test]
+    fn parse_base_path_of_swagger_and_servers() {
+        let base_path = |data: &str| {
+            let json_value = serde_json::from_str::<Value>(data).unwrap();
+            parse_json_object(json_value.as_object().unwrap())
+                .unwrap()
+                .base_path
+        };
+
+        assert_eq!(
+            base_path(r#"{"basePath": "/api/v2/", "paths": {}}"#),
+            Some("/api/v2".to_string())
+        );
+        assert_eq!(base_path(r#"{"basePath": "/", "paths": {}}"#), None);
+        assert_eq!(
+            base_path(r#"{"servers": [{"url": "https://example.com/v1"}], "paths": {}}"#),
+            Some("/v1".to_string())
+        );
+        assert_eq!(
+            base_path(r#"{"servers": [{"url": "v1"}], "paths": {}}"#),
+            Some("/v1".to_string())
+        );
+        assert_eq!(
+            base_path(r#"{"servers": [{"url": "https://example.com"}], "paths": {}}"#),
+            None
+        );
+        assert_eq!(base_path(r#"{"paths": {}}"#), None);
+    }
+
+    #[test]
+    fn parse_deprecated_operation() {
+        let data = r##"
+        {"get": {
+           "operationId": "OpId",
+           "deprecated": true,
+           "produces": [],
+           "responses": {}
+        }
+       }
+        "##;
+
+        let json_value = serde_json::from_str::<Value>(data).unwrap();
+        let mut ctx = ParseContext::new();
+        let parsed = parse_operation_method(
+            &mut ctx,
+            &"foo-path".to_string(),
+            (&"get".to_string(), &json_value["get"]),
+        );
+
+        assert!(parsed.deprecated);
+    }
+
     #[test]
     fn parse_mime_types() {
         let mut ctx = ParseContext::new();
@@ -866,7 +1115,7 @@ mod tests {
         let parsed = parse_operation_method(
             &mut ctx,
             &"foo-path".to_string(),
-            (&"get".to_string(), &json_object),
+            (&"get".to_string(), json_object),
         );
 
         assert_eq!(
@@ -885,7 +1134,10 @@ mod tests {
                     status: HTTPStatus::OK,
                     description: "OK".to_string(),
                     schema: Some(DataType::ArrayOfStrings),
+                    links: vec![],
                 },],
+                deprecated: false,
+                callbacks: vec![],
             }
         )
     }
@@ -934,7 +1186,7 @@ mod tests {
         let parsed = parse_operation_method(
             &mut ctx,
             &"foo-path".to_string(),
-            (&"get".to_string(), &json_object),
+            (&"get".to_string(), json_object),
         );
 
         assert_eq!(
@@ -954,23 +1206,29 @@ mod tests {
                         status: HTTPStatus::OK,
                         description: "OK".to_string(),
                         schema: Some(DataType::ArrayOfStrings),
+                        links: vec![],
                     },
                     OperationResponse {
                         status: HTTPStatus::Unauthorized,
                         description: "Unauthorized".to_string(),
                         schema: None,
+                        links: vec![],
                     },
                     OperationResponse {
                         status: HTTPStatus::Forbidden,
                         description: "Forbidden".to_string(),
                         schema: None,
+                        links: vec![],
                     },
                     OperationResponse {
                         status: HTTPStatus::NotFound,
                         description: "Not Found".to_string(),
                         schema: None,
+                        links: vec![],
                     },
                 ],
+                deprecated: false,
+                callbacks: vec![],
             }
         )
     }
@@ -993,7 +1251,7 @@ mod tests {
         let json_object = json_value["responses"].as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_responses(&mut ctx, &json_object);
+        let parsed = parse_method_responses(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1003,10 +1261,46 @@ mod tests {
                 schema: Some(DataType::Schema(Schema::Ref(
                     "#/definitions/ProductConfiguration".to_string()
                 ))),
+                links: vec![],
             }]
         )
     }
 
+    #[test]
+    fn parse_response_links() {
+        let data = r##"
+        {"responses" : {
+           "201" : {
+             "description" : "Created",
+             "links" : {
+               "GetUser" : {
+                 "operationId" : "getUser",
+                 "parameters" : { "userId" : "$response.body#/id", "verbose" : true }
+               },
+               "GetGroup" : { "operationRef" : "#/paths/~1groups/get" }
+             }
+           }
+         }
+        }"##;
+
+        let json_value = serde_json::from_str::<Value>(data).unwrap();
+        let json_object = json_value["responses"].as_object().unwrap();
+
+        let mut ctx = ParseContext::new();
+        let parsed = parse_method_responses(&mut ctx, json_object);
+
+        assert_eq!(
+            parsed[0].links,
+            vec![ResponseLink {
+                name: "GetUser".to_string(),
+                operation_id: "getUser".to_string(),
+                parameters: vec![("userId".to_string(), "$response.body#/id".to_string())],
+            }]
+        );
+        // The constant and the link by reference are not supported
+        assert_eq!(ctx.warnings.len(), 2);
+    }
+
     #[test]
     fn parse_response_schema_array_of_string_test() {
         let data = r##"
@@ -1028,7 +1322,7 @@ mod tests {
         let json_object = json_value["responses"].as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_responses(&mut ctx, &json_object);
+        let parsed = parse_method_responses(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1036,6 +1330,7 @@ mod tests {
                 status: HTTPStatus::OK,
                 description: "successful operation".to_string(),
                 schema: Some(DataType::ArrayOfStrings),
+                links: vec![],
             }]
         )
     }
@@ -1062,7 +1357,7 @@ mod tests {
         let json_object = json_value["responses"].as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_responses(&mut ctx, &json_object);
+        let parsed = parse_method_responses(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1072,6 +1367,7 @@ mod tests {
                 schema: Some(DataType::ArrayOfUniqueRefItems(
                     "#/definitions/Feature".to_string()
                 )),
+                links: vec![],
             }]
         )
     }
@@ -1094,7 +1390,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1102,7 +1398,8 @@ mod tests {
                 name: "productName".to_string(),
                 kind: DataType::ArrayOfRefItems("#/definitions/Foo".to_string()),
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             }]
         )
     }
@@ -1123,7 +1420,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1131,7 +1428,8 @@ mod tests {
                 name: "productName".to_string(),
                 kind: DataType::Unsupported,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             }]
         )
     }
@@ -1154,7 +1452,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1162,7 +1460,8 @@ mod tests {
                 name: "productName".to_string(),
                 kind: DataType::Unsupported,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             }]
         )
     }
@@ -1203,7 +1502,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(ctx.warnings.len(), 1);
         assert_eq!(
@@ -1213,25 +1512,29 @@ mod tests {
                     name: "foo".to_string(),
                     kind: DataType::IPV4,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "bar".to_string(),
                     kind: DataType::StringDateTime,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "baz".to_string(),
                     kind: DataType::String,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "gizmo".to_string(),
                     kind: DataType::String,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 }
             ]
         )
@@ -1266,7 +1569,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1275,19 +1578,22 @@ mod tests {
                     name: "foo".to_string(),
                     kind: DataType::Number,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "bar".to_string(),
                     kind: DataType::Double,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "baz".to_string(),
                     kind: DataType::Float,
                     target: ParameterIn::Path,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
             ]
         )
@@ -1308,7 +1614,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1316,7 +1622,8 @@ mod tests {
                 name: "foo".to_string(),
                 kind: DataType::File,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             },]
         )
     }
@@ -1337,7 +1644,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(ctx.warnings,
                    vec![
@@ -1349,7 +1656,8 @@ mod tests {
                 name: "foo".to_string(),
                 kind: DataType::Unsupported,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             },]
         )
     }
@@ -1369,7 +1677,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(ctx.warnings.len(), 1);
         assert_eq!(
@@ -1378,7 +1686,8 @@ mod tests {
                 name: "foo".to_string(),
                 kind: DataType::Unsupported,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             },]
         )
     }
@@ -1397,7 +1706,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             ctx.warnings,
@@ -1415,11 +1724,61 @@ mod tests {
                 name: "foo".to_string(),
                 kind: DataType::Unsupported,
                 target: ParameterIn::Path,
-                required: true
+                required: true,
+                examples: vec![],
             },]
         )
     }
 
+    #[test]
+    fn parse_parameter_examples() {
+        let data = r##"
+        {"parameters" : [{
+           "name" : "name",
+           "in" : "query",
+           "required" : true,
+           "type" : "string",
+           "x-example" : "Ada",
+           "default" : "Bob"
+         }, {
+           "name" : "age",
+           "in" : "formData",
+           "required" : false,
+           "type" : "integer",
+           "example" : 36,
+           "default" : 36
+         }, {
+           "name" : "body",
+           "in" : "body",
+           "required" : true,
+           "schema" : { "type" : "string", "example" : "payload" }
+         }, {
+           "name" : "tags",
+           "in" : "query",
+           "required" : false,
+           "type" : "string",
+           "example" : ["a", "b"]
+         }]
+        }"##;
+
+        let json_value = serde_json::from_str::<Value>(data).unwrap();
+        let json_object = json_value.as_object().unwrap();
+
+        let mut ctx = ParseContext::new();
+        let parsed = parse_method_parameters(&mut ctx, json_object);
+
+        let examples: Vec<Vec<String>> = parsed.into_iter().map(|p| p.examples).collect();
+        assert_eq!(
+            examples,
+            vec![
+                vec!["Ada".to_string(), "Bob".to_string()],
+                vec!["36".to_string()],
+                vec!["payload".to_string()],
+                vec![],
+            ]
+        );
+    }
+
     #[test]
     fn parse_parameter_in() {
         let data = r##"
@@ -1441,7 +1800,7 @@ mod tests {
         let json_object = json_value.as_object().unwrap();
 
         let mut ctx = ParseContext::new();
-        let parsed = parse_method_parameters(&mut ctx, &json_object);
+        let parsed = parse_method_parameters(&mut ctx, json_object);
 
         assert_eq!(
             ctx.warnings,
@@ -1459,13 +1818,15 @@ mod tests {
                     name: "foo".to_string(),
                     kind: DataType::Number,
                     target: ParameterIn::Header,
-                    required: true
+                    required: true,
+                    examples: vec![],
                 },
                 OperationParameter {
                     name: "bar".to_string(),
                     kind: DataType::String,
                     target: ParameterIn::Unsupported("crap".to_string()),
-                    required: true
+                    required: true,
+                    examples: vec![],
                 }
             ]
         )
@@ -1497,7 +1858,7 @@ mod tests {
 
         let mut ctx = ParseContext::new();
 
-        let parsed = parse_definitions(&mut ctx, &json_object);
+        let parsed = parse_definitions(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1510,15 +1871,18 @@ mod tests {
                 properties: Some(vec![
                     Property {
                         name: "description".to_string(),
-                        kind: DataType::String
+                        kind: DataType::String,
+                        examples: vec![],
                     },
                     Property {
                         name: "id".to_string(),
-                        kind: DataType::Integer64
+                        kind: DataType::Integer64,
+                        examples: vec![],
                     },
                     Property {
                         name: "name".to_string(),
-                        kind: DataType::String
+                        kind: DataType::String,
+                        examples: vec![],
                     }
                 ])
             }]
@@ -1549,7 +1913,7 @@ mod tests {
 
         let mut ctx = ParseContext::new();
 
-        let parsed = parse_definitions(&mut ctx, &json_object);
+        let parsed = parse_definitions(&mut ctx, json_object);
 
         assert_eq!(
             parsed,
@@ -1564,9 +1928,11 @@ mod tests {
                     kind: DataType::Schema(Schema::Inline {
                         properties: vec![Property {
                             name: "Name".to_string(),
-                            kind: DataType::String
+                            kind: DataType::String,
+                            examples: vec![],
                         }]
-                    })
+                    }),
+                    examples: vec![],
                 },])
             }]
         )
@@ -0,0 +1,97 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::comparison::Finding;
+use crate::Result;
+
+/// A finding accepted as known, by the hash of the finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnownFinding {
+    pub hash: String,
+    /// For the reader of the file, only the hash is compared
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Known findings, a run checked against the baseline only fails on findings
+///  not in it
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub findings: Vec<KnownFinding>,
+}
+
+impl Baseline {
+    pub fn new(findings: &BTreeSet<Finding>) -> Self {
+        Baseline {
+            findings: findings
+                .iter()
+                .map(|finding| KnownFinding {
+                    hash: finding.hash(),
+                    description: finding.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_known(&self, finding: &Finding) -> bool {
+        let hash = finding.hash();
+        self.findings.iter().any(|known| known.hash == hash)
+    }
+
+    /// The findings not in the baseline
+    pub fn new_findings<'a>(&self, findings: &'a BTreeSet<Finding>) -> Vec<&'a Finding> {
+        findings.iter().filter(|f| !self.is_known(f)).collect()
+    }
+}
+
+pub fn read_baseline(path: &str) -> Result<Baseline> {
+    let raw_baseline = fs::read(path)?;
+
+    let baseline: Baseline = serde_json::from_slice(&raw_baseline)?;
+
+    Ok(baseline)
+}
+
+pub fn write_baseline(path: &str, baseline: &Baseline) -> Result<()> {
+    let json_baseline = serde_json::to_string_pretty(baseline)?;
+
+    fs::write(path, json_baseline.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(operation: &str, sequence: &[&str]) -> Finding {
+        Finding {
+            behaviour: "fuzz".to_string(),
+            operation: operation.to_string(),
+            sequence: sequence.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn only_unknown_findings_are_new() {
+        let known = finding("get_persons", &["post_person", "get_persons"]);
+        let baseline = Baseline::new(&BTreeSet::from([known.clone()]));
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        let baseline: Baseline = serde_json::from_str(&json).unwrap();
+
+        let unknown = finding("get_persons", &["delete_person", "get_persons"]);
+        let findings = BTreeSet::from([known, unknown.clone()]);
+        assert_eq!(baseline.new_findings(&findings), vec![&unknown]);
+
+        // A baseline edited by hand may leave out the descriptions
+        let baseline: Baseline = serde_json::from_str(&format!(
+            r#"{{"findings": [{{"hash": "{}"}}]}}"#,
+            unknown.hash()
+        ))
+        .unwrap();
+        assert!(baseline.is_known(&unknown));
+    }
+}
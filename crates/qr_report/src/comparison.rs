@@ -0,0 +1,202 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use qr_explore::behaviours::Behaviour;
+use qr_explore::manifest;
+use serde::{Deserialize, Serialize};
+
+use crate::report::{self, Report};
+use crate::Result;
+
+/// A finding of a run, normalized so runs finding the same failure have the
+///  same finding. Only the operations of the minimal sequence are kept, the
+///  generated values differ between runs.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Finding {
+    pub behaviour: String,
+    pub operation: String,
+    pub sequence: Vec<String>,
+}
+
+impl Finding {
+    /// Identifies the finding across runs and versions of the tool
+    pub fn hash(&self) -> String {
+        manifest::stable_hash(&self.to_string())
+    }
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {}: {}",
+            self.behaviour,
+            self.operation,
+            self.sequence.join(" -> ")
+        )
+    }
+}
+
+/// The findings of the reports, without duplicates. Sequences without
+///  operations are operations no example was found for.
+pub fn findings(reports: &[Report]) -> BTreeSet<Finding> {
+    reports
+        .iter()
+        // The role matrix has a row for every operation, not failures
+        .filter(|report| report.behaviour != Behaviour::RoleMatrix)
+        .flat_map(|report| {
+            report
+                .sequences
+                .iter()
+                .filter(|sequence| !sequence.operations.is_empty())
+                .map(|sequence| Finding {
+                    behaviour: report.behaviour.name().to_string(),
+                    operation: sequence.root_operation.clone(),
+                    sequence: sequence
+                        .operations
+                        .iter()
+                        .map(|op| op.name.clone())
+                        .collect(),
+                })
+        })
+        .collect()
+}
+
+/// The findings of a run compared to those of a baseline run
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    /// Found by the baseline only
+    pub fixed: Vec<Finding>,
+    /// Found by the current run only, the regressions
+    pub new: Vec<Finding>,
+    pub persisting: Vec<Finding>,
+}
+
+impl Comparison {
+    /// Findings of behaviours the current run did not explore are neither
+    ///  fixed nor persisting, they were not looked for
+    pub fn new(baseline: &[Report], current: &[Report]) -> Self {
+        let explored: BTreeSet<&str> = current
+            .iter()
+            .map(|report| report.behaviour.name())
+            .collect();
+
+        let baseline = findings(baseline);
+        let current = findings(current);
+
+        Comparison {
+            fixed: baseline
+                .iter()
+                .filter(|f| explored.contains(f.behaviour.as_str()) && !current.contains(f))
+                .cloned()
+                .collect(),
+            new: current.difference(&baseline).cloned().collect(),
+            persisting: current.intersection(&baseline).cloned().collect(),
+        }
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        !self.new.is_empty()
+    }
+}
+
+/// The reports at the path, a report file or a directory of report files
+pub fn read_reports(path: &str) -> Result<Vec<Report>> {
+    if !Path::new(path).is_dir() {
+        return Ok(vec![report::read_results_for_test(path)?]);
+    }
+
+    let mut paths = vec![];
+    for entry in fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        if entry_path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(entry_path);
+        }
+    }
+    // In the same order on every platform
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| report::read_results_for_test(&path.display().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Operation, Sequence};
+    use qr_explore::amos::AMOS;
+    use qr_http_resource::http::HTTPMethod;
+
+    fn report(behaviour: Behaviour, sequences: &[&[&str]]) -> Report {
+        Report {
+            behaviour,
+            sequences: sequences
+                .iter()
+                .map(|ops| Sequence {
+                    root_operation: ops.last().unwrap_or(&"get_persons").to_string(),
+                    operations: ops
+                        .iter()
+                        .map(|name| Operation {
+                            name: name.to_string(),
+                            url: "/persons".to_string(),
+                            method: HTTPMethod::GET,
+                            parameters: vec![],
+                        })
+                        .collect(),
+                    correlation_id: None,
+                    shrink_truncation: None,
+                })
+                .collect(),
+            amos: AMOS::new(),
+            manifest: None,
+        }
+    }
+
+    #[test]
+    fn compare_runs() {
+        let baseline = vec![
+            report(
+                Behaviour::Property,
+                &[&["post_person", "get_persons"], &["delete_person"]],
+            ),
+            report(Behaviour::Payload, &[&["get_persons"]]),
+        ];
+        let current = vec![
+            report(
+                Behaviour::Property,
+                // The same failure twice and an operation without an example
+                &[
+                    &["post_person", "get_persons"],
+                    &["post_person", "get_persons"],
+                    &["get_person"],
+                    &[],
+                ],
+            ),
+            // Rows of the matrix are not findings
+            report(Behaviour::RoleMatrix, &[&["get_persons"]]),
+        ];
+
+        let comparison = Comparison::new(&baseline, &current);
+
+        let names = |findings: &[Finding]| -> Vec<String> {
+            findings.iter().map(|f| f.to_string()).collect()
+        };
+        // The payload finding is not fixed, the current run did not explore it
+        assert_eq!(
+            names(&comparison.fixed),
+            vec!["[fuzz] delete_person: delete_person"]
+        );
+        assert_eq!(
+            names(&comparison.new),
+            vec!["[fuzz] get_person: get_person"]
+        );
+        assert_eq!(
+            names(&comparison.persisting),
+            vec!["[fuzz] get_persons: post_person -> get_persons"]
+        );
+        assert!(comparison.has_regressions());
+    }
+}
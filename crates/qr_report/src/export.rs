@@ -0,0 +1,447 @@
+use std::fs;
+use std::time::Duration;
+
+use qr_explore::amos_generation::GeneratedOperation;
+use qr_explore::explore::ExplorationResult;
+use qr_explore::invocation_log::InvocationSummary;
+
+use crate::Result;
+
+/// A CSV field, quoted when it holds a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_row(fields: &[String]) -> String {
+    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    format!("{}\n", row.join(","))
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}", duration.as_secs_f64() * 1000.0)
+}
+
+/// One row per invocation, in the order they were made
+pub fn invocations_csv(summaries: &[InvocationSummary]) -> String {
+    let mut csv = csv_row(&[
+        "operation".to_string(),
+        "success".to_string(),
+        "status".to_string(),
+        "url".to_string(),
+        "duration_ms".to_string(),
+        "size".to_string(),
+        "content_encoding".to_string(),
+        "transport_error".to_string(),
+        "test_case".to_string(),
+    ]);
+
+    for summary in summaries {
+        csv.push_str(&csv_row(&[
+            summary.operation.clone(),
+            summary.success.to_string(),
+            summary
+                .status
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            summary.url.clone().unwrap_or_default(),
+            millis(summary.duration),
+            summary.size.map(|s| s.to_string()).unwrap_or_default(),
+            summary.content_encoding.clone().unwrap_or_default(),
+            summary
+                .transport_error
+                .map(|e| e.to_string())
+                .unwrap_or_default(),
+            summary
+                .position
+                .map(|p| p.test_case.to_string())
+                .unwrap_or_default(),
+        ]));
+    }
+
+    csv
+}
+
+/// One row per operation, in the order they were first invoked
+pub fn statistics_csv(summaries: &[InvocationSummary]) -> String {
+    let mut operations: Vec<(&str, Vec<&InvocationSummary>)> = vec![];
+    for summary in summaries {
+        match operations
+            .iter_mut()
+            .find(|(name, _)| *name == summary.operation)
+        {
+            Some((_, op_summaries)) => op_summaries.push(summary),
+            None => operations.push((&summary.operation, vec![summary])),
+        }
+    }
+
+    let mut csv = csv_row(&[
+        "operation".to_string(),
+        "invocations".to_string(),
+        "successes".to_string(),
+        "failures".to_string(),
+        "mean_duration_ms".to_string(),
+        "max_duration_ms".to_string(),
+    ]);
+
+    for (operation, op_summaries) in operations {
+        let successes = op_summaries.iter().filter(|s| s.success).count();
+        let total: Duration = op_summaries.iter().map(|s| s.duration).sum();
+        let max = op_summaries
+            .iter()
+            .map(|s| s.duration)
+            .max()
+            .unwrap_or_default();
+
+        csv.push_str(&csv_row(&[
+            operation.to_string(),
+            op_summaries.len().to_string(),
+            successes.to_string(),
+            (op_summaries.len() - successes).to_string(),
+            millis(total / op_summaries.len() as u32),
+            millis(max),
+        ]));
+    }
+
+    csv
+}
+
+/// The kind, operation, sequence, details and correlation id of a result
+fn result_row(result: &ExplorationResult) -> [String; 5] {
+    let (kind, operation, sequence, details) = match result {
+        ExplorationResult::NoExampleFound { operation } => {
+            ("no-example", operation, &None, String::new())
+        }
+        ExplorationResult::Failed { operation, reason } => {
+            ("failed", operation, &None, reason.clone())
+        }
+        ExplorationResult::ResponseCheck {
+            operation,
+            minimal_sequence,
+            ..
+        } => ("response-check", operation, minimal_sequence, String::new()),
+        ExplorationResult::ResponseTime {
+            operation,
+            minimal_sequence,
+            ..
+        } => ("response-time", operation, minimal_sequence, String::new()),
+        ExplorationResult::PayloadCheck {
+            operation,
+            minimal_sequence,
+            ..
+        } => ("payload", operation, minimal_sequence, String::new()),
+        ExplorationResult::Injection {
+            operation,
+            minimal_sequence,
+            ..
+        } => ("injection", operation, minimal_sequence, String::new()),
+        ExplorationResult::StaleCache {
+            operation,
+            minimal_sequence,
+            ..
+        } => ("stale-cache", operation, minimal_sequence, String::new()),
+        ExplorationResult::ConditionalRequest {
+            operation,
+            minimal_sequence,
+            ..
+        } => (
+            "conditional-request",
+            operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::IdempotentRetry {
+            operation,
+            minimal_sequence,
+            ..
+        } => (
+            "idempotent-retry",
+            operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::ModelDivergence {
+            operation,
+            divergence,
+            minimal_sequence,
+            ..
+        } => (
+            "model",
+            operation,
+            minimal_sequence,
+            divergence
+                .as_ref()
+                .map_or(String::new(), |divergence| divergence.to_string()),
+        ),
+        ExplorationResult::ResponseEquality {
+            operation,
+            minimal_sequence,
+            ..
+        } => (
+            "response-equality",
+            operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::ResponseInEquality {
+            operation,
+            minimal_sequence,
+            ..
+        } => (
+            "response-inequality",
+            operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::StateMutation {
+            query_operation,
+            minimal_sequence,
+            ..
+        } => (
+            "state-mutation",
+            query_operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::StateIdentity {
+            query_operation,
+            minimal_sequence,
+            ..
+        } => (
+            "state-identity",
+            query_operation,
+            minimal_sequence,
+            String::new(),
+        ),
+        ExplorationResult::LatencyDegradation {
+            operation,
+            sequence,
+            trend,
+        } => {
+            let latency: Vec<String> = trend
+                .latency_ms
+                .iter()
+                .map(|ms| format!("{:.1}", ms))
+                .collect();
+            (
+                "latency-degradation",
+                operation,
+                sequence,
+                format!("mean ms per window: {}", latency.join("; ")),
+            )
+        }
+        ExplorationResult::SecurityAudit {
+            operation,
+            findings,
+            sequence,
+            ..
+        } => {
+            let findings: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+            ("security-audit", operation, sequence, findings.join("; "))
+        }
+        ExplorationResult::AuthBypass {
+            operation,
+            role,
+            sequence,
+            ..
+        } => (
+            "auth-bypass",
+            operation,
+            sequence,
+            format!("succeeded with {}", role),
+        ),
+        ExplorationResult::TargetDivergence {
+            operation,
+            difference,
+            sequence,
+            ..
+        } => ("differential", operation, sequence, difference.to_string()),
+        ExplorationResult::ContractViolation {
+            operation,
+            violations,
+            sequence,
+            ..
+        } => {
+            let violations: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+            ("contract", operation, sequence, violations.join("; "))
+        }
+        ExplorationResult::ChaosInconsistency {
+            query_operation,
+            inconsistency,
+            faults,
+            sequence,
+            ..
+        } => {
+            let faults: Vec<String> = faults.iter().map(|f| f.to_string()).collect();
+            (
+                "chaos",
+                query_operation,
+                sequence,
+                format!("{}; after {}", inconsistency, faults.join("; ")),
+            )
+        }
+        ExplorationResult::RoleMatrix {
+            operation,
+            outcomes,
+            sequence,
+        } => {
+            let outcomes: Vec<String> = outcomes
+                .iter()
+                .map(|o| format!("{} {}", o.role, o))
+                .collect();
+            ("role-matrix", operation, sequence, outcomes.join("; "))
+        }
+        // The windows name the invocations of each process, there is no one sequence
+        ExplorationResult::NonLinearizable {
+            operation, windows, ..
+        } => {
+            let windows: Vec<String> = windows.iter().map(|w| w.to_string()).collect();
+            ("linearizability", operation, &None, windows.join("; "))
+        }
+    };
+
+    [
+        kind.to_string(),
+        operation.clone(),
+        sequence_names(sequence),
+        details,
+        result.correlation_id().unwrap_or_default().to_string(),
+    ]
+}
+
+fn sequence_names(sequence: &Option<Vec<GeneratedOperation>>) -> String {
+    sequence
+        .as_ref()
+        .map(|seq| {
+            seq.iter()
+                .map(|op| op.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(" -> ")
+        })
+        .unwrap_or_default()
+}
+
+/// One row per result, the sequence is given by the names of its operations
+pub fn results_csv(results: &[ExplorationResult]) -> String {
+    let mut csv = csv_row(&[
+        "kind".to_string(),
+        "operation".to_string(),
+        "sequence".to_string(),
+        "details".to_string(),
+        "correlation_id".to_string(),
+    ]);
+
+    for result in results {
+        csv.push_str(&csv_row(&result_row(result)));
+    }
+
+    csv
+}
+
+/// Write the invocations, their statistics and the results as CSV files to
+///  the directory
+pub fn write_csv_export(
+    dir_path: &str,
+    summaries: &[InvocationSummary],
+    results: &[ExplorationResult],
+) -> Result<()> {
+    fs::create_dir_all(dir_path)?;
+
+    fs::write(
+        format!("{dir_path}/invocations.csv"),
+        invocations_csv(summaries),
+    )?;
+    fs::write(
+        format!("{dir_path}/statistics.csv"),
+        statistics_csv(summaries),
+    )?;
+    fs::write(format!("{dir_path}/results.csv"), results_csv(results))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_explore::amos::SequencePosition;
+    use qr_http_resource::http::HTTPStatus;
+
+    fn summary(operation: &str, success: bool, ms: u64) -> InvocationSummary {
+        InvocationSummary {
+            operation: operation.to_string(),
+            success,
+            status: Some(if success {
+                HTTPStatus::OK
+            } else {
+                HTTPStatus::InternalServerError
+            }),
+            url: Some("/persons?name=a,b".to_string()),
+            duration: Duration::from_millis(ms),
+            size: Some(2),
+            content_encoding: None,
+            transport_error: None,
+            position: Some(SequencePosition {
+                test_case: 1,
+                index: 0,
+            }),
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn invocations_and_statistics() {
+        let summaries = vec![
+            summary("get_persons", true, 10),
+            summary("post_person", false, 30),
+            summary("get_persons", false, 20),
+        ];
+
+        assert_eq!(
+            invocations_csv(&summaries[..1]),
+            "operation,success,status,url,duration_ms,size,content_encoding,transport_error,test_case\n\
+             get_persons,true,200,\"/persons?name=a,b\",10.0,2,,,1\n"
+        );
+
+        assert_eq!(
+            statistics_csv(&summaries),
+            "operation,invocations,successes,failures,mean_duration_ms,max_duration_ms\n\
+             get_persons,2,1,1,15.0,20.0\n\
+             post_person,1,0,1,30.0,30.0\n"
+        );
+    }
+
+    #[test]
+    fn results_with_sequences() {
+        let results = vec![
+            ExplorationResult::ResponseCheck {
+                operation: "get_persons".to_string(),
+                minimal_sequence: Some(vec![
+                    GeneratedOperation {
+                        name: "post_person".to_string(),
+                        parameters: vec![],
+                    },
+                    GeneratedOperation {
+                        name: "get_persons".to_string(),
+                        parameters: vec![],
+                    },
+                ]),
+                correlation_id: Some("qr-00000001-3".to_string()),
+                shrink_truncation: None,
+            },
+            ExplorationResult::NoExampleFound {
+                operation: "say \"hi\"".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            results_csv(&results),
+            "kind,operation,sequence,details,correlation_id\n\
+             response-check,get_persons,post_person -> get_persons,,qr-00000001-3\n\
+             no-example,\"say \"\"hi\"\"\",,,\n"
+        );
+    }
+}
@@ -1,4 +1,8 @@
+pub mod baseline;
+pub mod comparison;
 pub mod error;
+pub mod export;
 pub mod report;
+pub mod summary;
 
 pub use self::error::{Error, Result};
@@ -5,6 +5,7 @@ use qr_explore::amos::OperationMetaData;
 use qr_explore::amos::AMOS;
 use qr_explore::amos_generation::GeneratedOperation;
 use qr_explore::amos_generation::GeneratedParameter;
+use qr_explore::manifest::RunManifest;
 use qr_http_resource::http::HTTPMethod;
 use serde::Deserialize;
 use serde::Serialize;
@@ -29,12 +30,21 @@ pub struct Report {
     pub sequences: Vec<Sequence>,
     // To be self sufficient, for now, include the AMOS
     pub amos: AMOS,
+    /// The configuration of the run, reports written before manifests have none
+    #[serde(default)]
+    pub manifest: Option<RunManifest>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Sequence {
     pub root_operation: String,
     pub operations: Vec<Operation>,
+    /// The id the SUT got the requests of the sequence with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// The budget shrinking ran out of, the sequence may not be minimal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shrink_truncation: Option<explore::ShrinkTruncation>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -85,20 +95,14 @@ fn process_results(
     out_seq
 }
 
-pub fn write_results_for_test(
-    dir_path: &str,
+/// The report of the results, with the URL and method of the operations for
+///  the sequences to be replayed from the report alone
+pub fn report_for_test(
     behaviour: &behaviours::Behaviour,
     amos: &AMOS,
     results: &[explore::ExplorationResult],
-) -> Result<()> {
-    let behaviour_name = match behaviour {
-        behaviours::Behaviour::Property => "fuzz",
-        behaviours::Behaviour::StateMutation => "state-mutation",
-        behaviours::Behaviour::StateIdentity => "state-identity",
-        behaviours::Behaviour::ResponseEquality => "response-equality",
-        behaviours::Behaviour::ResponseInequality => "response-inequality",
-    };
-
+    manifest: Option<&RunManifest>,
+) -> Report {
     let mut sequences = vec![];
 
     for result in results {
@@ -107,18 +111,92 @@ pub fn write_results_for_test(
             explore::ExplorationResult::ResponseCheck {
                 operation,
                 minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::ResponseTime {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::PayloadCheck {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::Injection {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::StaleCache {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::ConditionalRequest {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::IdempotentRetry {
+                operation,
+                minimal_sequence,
+                ..
             } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::ModelDivergence {
+                operation,
+                minimal_sequence,
+                ..
+            } => (operation.clone(), process_results(amos, minimal_sequence)),
+            explore::ExplorationResult::LatencyDegradation {
+                operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::SecurityAudit {
+                operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::AuthBypass {
+                operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::TargetDivergence {
+                operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::ContractViolation {
+                operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::ChaosInconsistency {
+                query_operation: operation,
+                sequence,
+                ..
+            }
+            | explore::ExplorationResult::RoleMatrix {
+                operation,
+                sequence,
+                ..
+            } => (operation.clone(), process_results(amos, sequence)),
             explore::ExplorationResult::ResponseEquality {
                 operation,
                 minimal_sequence,
+                ..
             } => (operation.clone(), process_results(amos, minimal_sequence)),
             explore::ExplorationResult::ResponseInEquality {
                 operation,
                 minimal_sequence,
+                ..
             } => (operation.clone(), process_results(amos, minimal_sequence)),
             explore::ExplorationResult::StateMutation {
                 query_operation,
                 minimal_sequence,
+                ..
             } => (
                 query_operation.clone(),
                 process_results(amos, minimal_sequence),
@@ -126,24 +204,49 @@ pub fn write_results_for_test(
             explore::ExplorationResult::StateIdentity {
                 query_operation,
                 minimal_sequence,
+                ..
             } => (
                 query_operation.clone(),
                 process_results(amos, minimal_sequence),
             ),
+            // The processes are replayed one after the other
+            explore::ExplorationResult::NonLinearizable {
+                operation,
+                processes,
+                ..
+            } => (
+                operation.clone(),
+                process_results(amos, &Some(processes.concat())),
+            ),
             explore::ExplorationResult::NoExampleFound { operation } => (operation.clone(), vec![]), //_ => todo!("TODO: {:?}", result),
+            explore::ExplorationResult::Failed { operation, .. } => (operation.clone(), vec![]),
         };
 
         sequences.push(Sequence {
             root_operation,
             operations: out_seq,
+            correlation_id: result.correlation_id().map(String::from),
+            shrink_truncation: result.shrink_truncation().cloned(),
         })
     }
 
-    let report = Report {
+    Report {
         sequences,
         behaviour: behaviour.clone(),
         amos: amos.clone(),
-    };
+        manifest: manifest.cloned(),
+    }
+}
+
+pub fn write_results_for_test(
+    dir_path: &str,
+    behaviour: &behaviours::Behaviour,
+    amos: &AMOS,
+    results: &[explore::ExplorationResult],
+    manifest: Option<&RunManifest>,
+) -> Result<()> {
+    let behaviour_name = behaviour.name();
+    let report = report_for_test(behaviour, amos, results, manifest);
 
     let file_path = format!("{dir_path}/{behaviour_name}.json");
 
@@ -165,13 +268,7 @@ pub fn write_results(
 ) -> Result<()> {
     let json_result = serde_json::to_string_pretty(results)?;
 
-    let behaviour_name = match behaviour {
-        behaviours::Behaviour::Property => "fuzz",
-        behaviours::Behaviour::StateMutation => "state-mutation",
-        behaviours::Behaviour::StateIdentity => "state-identity",
-        behaviours::Behaviour::ResponseEquality => "response-equality",
-        behaviours::Behaviour::ResponseInequality => "response-inequality",
-    };
+    let behaviour_name = behaviour.name();
 
     let file_path = format!("{dir_path}/{behaviour_name}.json");
 
@@ -200,10 +297,82 @@ pub fn exploration_coverage(
     let mut covered_operations = HashSet::new();
     for result in results {
         match result {
-            explore::ExplorationResult::NoExampleFound { .. } => continue,
+            explore::ExplorationResult::NoExampleFound { .. }
+            | explore::ExplorationResult::Failed { .. } => continue,
             explore::ExplorationResult::ResponseCheck {
                 operation,
                 minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::ResponseTime {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::PayloadCheck {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::Injection {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::StaleCache {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::ConditionalRequest {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::IdempotentRetry {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::ModelDivergence {
+                operation,
+                minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::LatencyDegradation {
+                operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::SecurityAudit {
+                operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::AuthBypass {
+                operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::TargetDivergence {
+                operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::ContractViolation {
+                operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::ChaosInconsistency {
+                query_operation: operation,
+                sequence: minimal_sequence,
+                ..
+            }
+            | explore::ExplorationResult::RoleMatrix {
+                operation,
+                sequence: minimal_sequence,
+                ..
             } => {
                 covered_operations.insert(operation.clone());
                 if let Some(seq) = minimal_sequence {
@@ -212,9 +381,20 @@ pub fn exploration_coverage(
                     }
                 }
             }
+            explore::ExplorationResult::NonLinearizable {
+                operation,
+                processes,
+                ..
+            } => {
+                covered_operations.insert(operation.clone());
+                for op in processes.iter().flatten() {
+                    covered_operations.insert(op.name.clone());
+                }
+            }
             explore::ExplorationResult::StateIdentity {
                 query_operation,
                 minimal_sequence,
+                ..
             } => {
                 covered_operations.insert(query_operation.clone());
                 if let Some(seq) = minimal_sequence {
@@ -226,6 +406,7 @@ pub fn exploration_coverage(
             explore::ExplorationResult::StateMutation {
                 query_operation,
                 minimal_sequence,
+                ..
             } => {
                 covered_operations.insert(query_operation.clone());
                 if let Some(seq) = minimal_sequence {
@@ -237,6 +418,7 @@ pub fn exploration_coverage(
             explore::ExplorationResult::ResponseEquality {
                 operation,
                 minimal_sequence,
+                ..
             } => {
                 covered_operations.insert(operation.clone());
                 if let Some(seq) = minimal_sequence {
@@ -248,6 +430,7 @@ pub fn exploration_coverage(
             explore::ExplorationResult::ResponseInEquality {
                 operation,
                 minimal_sequence,
+                ..
             } => {
                 covered_operations.insert(operation.clone());
                 if let Some(seq) = minimal_sequence {
@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::comparison::Finding;
+use crate::Result;
+
+/// The outcome of a run, for scripts to read instead of the log
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub exit_code: i32,
+    pub findings: usize,
+    /// Findings not in the baseline, if the run was checked against one
+    pub new_findings: Option<usize>,
+    /// Findings of each behaviour run, behaviours without findings count zero
+    pub behaviours: BTreeMap<String, usize>,
+    /// Findings of each operation with findings
+    pub operations: BTreeMap<String, usize>,
+}
+
+impl RunSummary {
+    pub fn new(
+        exit_code: i32,
+        behaviours: &[&str],
+        findings: &BTreeSet<Finding>,
+        new_findings: Option<usize>,
+    ) -> Self {
+        let mut behaviour_counts: BTreeMap<String, usize> = behaviours
+            .iter()
+            .map(|behaviour| (behaviour.to_string(), 0))
+            .collect();
+        let mut operation_counts = BTreeMap::new();
+
+        for finding in findings {
+            *behaviour_counts
+                .entry(finding.behaviour.clone())
+                .or_default() += 1;
+            *operation_counts
+                .entry(finding.operation.clone())
+                .or_default() += 1;
+        }
+
+        RunSummary {
+            exit_code,
+            findings: findings.len(),
+            new_findings,
+            behaviours: behaviour_counts,
+            operations: operation_counts,
+        }
+    }
+}
+
+pub fn write_summary(path: &str, summary: &RunSummary) -> Result<()> {
+    let json_summary = serde_json::to_string_pretty(summary)?;
+
+    fs::write(path, json_summary.as_bytes())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_per_behaviour_and_operation() {
+        let finding = |behaviour: &str, operation: &str| Finding {
+            behaviour: behaviour.to_string(),
+            operation: operation.to_string(),
+            sequence: vec![operation.to_string()],
+        };
+        let findings = BTreeSet::from([
+            finding("fuzz", "get_persons"),
+            finding("payload", "get_persons"),
+            finding("fuzz", "post_person"),
+        ]);
+
+        let summary = RunSummary::new(1, &["fuzz", "payload", "soak"], &findings, None);
+
+        assert_eq!(summary.findings, 3);
+        assert_eq!(
+            summary.behaviours,
+            BTreeMap::from([
+                ("fuzz".to_string(), 2),
+                ("payload".to_string(), 1),
+                ("soak".to_string(), 0),
+            ])
+        );
+        assert_eq!(
+            summary.operations,
+            BTreeMap::from([
+                ("get_persons".to_string(), 2),
+                ("post_person".to_string(), 1)
+            ])
+        );
+    }
+}
@@ -0,0 +1,712 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use qr_explore::amos::{self, Operation, OperationMetaData, ParameterMetaData, TranslationMessage};
+use qr_http_resource::http::{HTTPMethod, HTTPParameterTarget};
+use reqwest::Url;
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::inference::{push_examples, OperationTemplate};
+
+/// Headers that belong to a single connection and are not forwarded
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "content-length",
+    "host",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// A request made by a client through the capture proxy
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedRequest {
+    pub method: HTTPMethod,
+    /// The path, including any query
+    pub path: String,
+    pub body: Option<String>,
+}
+
+/// A proxy in front of the SUT, forwarding the requests of real clients and
+///  recording the ones the SUT accepts
+pub struct CaptureProxy {
+    address: SocketAddr,
+    requests: Arc<Mutex<Vec<CapturedRequest>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CaptureProxy {
+    /// Start forwarding the requests made to the listen address, e.g.
+    ///  `127.0.0.1:8888`, to the target
+    pub fn start(listen: &str, target: &Url) -> Result<Self> {
+        let listener = TcpListener::bind(listen)
+            .map_err(|e| Error::CaptureFailed(format!("Failed to listen on {}: {}", listen, e)))?;
+        let address = listener
+            .local_addr()
+            .map_err(|e| Error::CaptureFailed(e.to_string()))?;
+        // Accept without blocking, to notice when the proxy is stopped
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| Error::CaptureFailed(e.to_string()))?;
+
+        let requests = Arc::new(Mutex::new(vec![]));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let client = reqwest::blocking::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| Error::CaptureFailed(e.to_string()))?;
+
+        let handle = {
+            let requests = requests.clone();
+            let stop = stop.clone();
+            let target = target.clone();
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let client = client.clone();
+                            let target = target.clone();
+                            let requests = requests.clone();
+
+                            thread::spawn(move || {
+                                if let Err(e) = forward(stream, &client, &target, &requests) {
+                                    debug!("Failed to forward request: {}", e);
+                                }
+                            });
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(20))
+                        }
+                        Err(e) => warn!("Capture proxy failed to accept: {}", e),
+                    }
+                }
+            })
+        };
+
+        info!(
+            "Capture proxy listening on {}, forwarding to {}",
+            address, target
+        );
+
+        Ok(CaptureProxy {
+            address,
+            requests,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The requests recorded so far
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Stop the proxy, returns the recorded requests
+    pub fn stop(mut self) -> Vec<CapturedRequest> {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+
+        self.requests()
+    }
+}
+
+impl Drop for CaptureProxy {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Forward one request of the connection to the target and write back the
+///  response. The connection is closed after the response.
+fn forward(
+    stream: TcpStream,
+    client: &reqwest::blocking::Client,
+    target: &Url,
+    requests: &Mutex<Vec<CapturedRequest>>,
+) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(std::io::Error::other("malformed request line"));
+    };
+
+    let mut headers = vec![];
+    let mut content_length = 0;
+    let mut transfer_encoding = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.trim_end().split_once(':') {
+            let (name, value) = (name.trim().to_string(), value.trim().to_string());
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                transfer_encoding = Some(value.to_lowercase());
+            }
+            headers.push((name, value));
+        }
+    }
+
+    // Only a request for a path of the target is forwarded, any other target
+    //  would relay the request to another host
+    let Some(url) = origin_form_url(target, path) else {
+        return reject(stream, "400 Bad Request", "request target is not a path");
+    };
+
+    let body = match transfer_encoding.as_deref() {
+        None => {
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body)?;
+            body
+        }
+        Some("chunked") => read_chunked(&mut reader)?,
+        Some(_) => {
+            return reject(
+                stream,
+                "501 Not Implemented",
+                "transfer encoding is not supported",
+            )
+        }
+    };
+
+    let request_method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let mut request = client.request(request_method, url);
+    for (name, value) in headers
+        .iter()
+        .filter(|(name, _)| !HOP_BY_HOP_HEADERS.contains(&name.to_lowercase().as_str()))
+    {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .body(body.clone())
+        .send()
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let status = response.status();
+    let response_headers = response.headers().clone();
+    let payload = response
+        .bytes()
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let mut writer = stream;
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or("")
+    )?;
+    for (name, value) in response_headers.iter() {
+        if !HOP_BY_HOP_HEADERS.contains(&name.as_str()) {
+            writer.write_all(name.as_str().as_bytes())?;
+            writer.write_all(b": ")?;
+            writer.write_all(value.as_bytes())?;
+            writer.write_all(b"\r\n")?;
+        }
+    }
+    write!(
+        writer,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        payload.len()
+    )?;
+    writer.write_all(&payload)?;
+    writer.flush()?;
+
+    // Only requests the SUT accepts are examples of how it is used
+    match (status.is_success(), http_method(method)) {
+        (true, Some(method)) => {
+            debug!("Captured: {:?} {}", method, path);
+            requests.lock().unwrap().push(CapturedRequest {
+                method,
+                path: path.to_string(),
+                body: if body.is_empty() {
+                    None
+                } else {
+                    Some(String::from_utf8_lossy(&body).to_string())
+                },
+            });
+        }
+        (true, None) => warn!(
+            "Forwarded {} {} without capturing it, the method is not supported",
+            method, path
+        ),
+        (false, _) => (),
+    }
+
+    Ok(())
+}
+
+/// The URL of the request target at the target, if it is a path of it
+fn origin_form_url(target: &Url, path: &str) -> Option<Url> {
+    if !path.starts_with('/') {
+        return None;
+    }
+
+    target
+        .join(path)
+        .ok()
+        .filter(|url| url.origin() == target.origin())
+}
+
+/// The body of a chunked request, the trailers are dropped
+fn read_chunked(reader: &mut impl BufRead) -> std::io::Result<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| std::io::Error::other(format!("malformed chunk size {:?}", size)))?;
+
+        if size == 0 {
+            // Discard the trailers up to the empty line ending the body
+            loop {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                    return Ok(body);
+                }
+            }
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+
+        let mut end = [0; 2];
+        reader.read_exact(&mut end)?;
+        if &end != b"\r\n" {
+            return Err(std::io::Error::other("malformed chunk"));
+        }
+    }
+}
+
+/// Answer the request with the status without forwarding it
+fn reject(mut stream: TcpStream, status: &str, reason: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )?;
+    stream.flush()?;
+
+    Err(std::io::Error::other(reason.to_string()))
+}
+
+pub(crate) fn http_method(method: &str) -> Option<HTTPMethod> {
+    match method.to_uppercase().as_str() {
+        "GET" => Some(HTTPMethod::GET),
+        "DELETE" => Some(HTTPMethod::DELETE),
+        "POST" => Some(HTTPMethod::POST),
+        "PUT" => Some(HTTPMethod::PUT),
+        _ => None,
+    }
+}
+
+/// Merge what the captured requests show of the API into the AMOS. Requests to
+///  operations of the AMOS add the values sent as examples of its parameters,
+///  the other requests are inferred as new operations.
+pub fn learn_from_requests(
+    mut amos: amos::AMOS,
+    requests: &[CapturedRequest],
+) -> amos::TranslationResult {
+    let mut templates: Vec<OperationTemplate> = vec![];
+    let mut learned_parameters = HashSet::new();
+
+    for request in requests {
+        match amos
+            .operations
+            .iter_mut()
+            .find(|op| matching_path_values(op, &request.method, &request.path).is_some())
+        {
+            Some(operation) => {
+                for name in add_examples(operation, request) {
+                    learned_parameters.insert((operation.info.name.clone(), name));
+                }
+            }
+            None => {
+                let template = OperationTemplate::infer(
+                    request.method.clone(),
+                    &request.path,
+                    request.body.as_deref(),
+                );
+
+                match templates.iter_mut().find(|t| t.same_operation(&template)) {
+                    Some(existing) => existing.merge(template),
+                    None => templates.push(template),
+                }
+            }
+        }
+    }
+
+    let new_operations = templates.len();
+    let mut names = amos
+        .operations
+        .iter()
+        .map(|op| op.info.name.clone())
+        .collect();
+    for template in templates {
+        let operation = template.into_operation(&mut names);
+        amos.push_operation(operation);
+    }
+
+    amos::TranslationResult {
+        warnings: vec![TranslationMessage::new(format!(
+            "Learned {} new operations and examples of {} parameters from {} requests",
+            new_operations,
+            learned_parameters.len(),
+            requests.len()
+        ))],
+        amos,
+        errors: vec![],
+    }
+}
+
+/// The values of the path parameters, if the request is to the operation
+fn matching_path_values(
+    operation: &Operation,
+    method: &HTTPMethod,
+    path: &str,
+) -> Option<Vec<(String, String)>> {
+    let Some(OperationMetaData::HTTP {
+        url,
+        method: operation_method,
+    }) = &operation.meta_data
+    else {
+        return None;
+    };
+
+    if operation_method != method {
+        return None;
+    }
+
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let pattern: Vec<&str> = url.split('/').filter(|s| !s.is_empty()).collect();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    if pattern.len() != segments.len() {
+        return None;
+    }
+
+    let mut values = vec![];
+    for (pattern, segment) in pattern.iter().zip(segments) {
+        match pattern.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+            Some(name) => values.push((name.to_string(), segment.to_string())),
+            None if *pattern == segment => (),
+            None => return None,
+        }
+    }
+
+    Some(values)
+}
+
+/// Add the values of the request as examples of the parameters of the
+///  operation, returns the names of the parameters given examples
+fn add_examples(operation: &mut Operation, request: &CapturedRequest) -> Vec<String> {
+    let mut values: Vec<(String, HTTPParameterTarget, String)> = vec![];
+
+    if let Some(path_values) = matching_path_values(operation, &request.method, &request.path) {
+        values.extend(
+            path_values
+                .into_iter()
+                .map(|(name, value)| (name, HTTPParameterTarget::Path, value)),
+        );
+    }
+
+    if let Some((_, query)) = request.path.split_once('?') {
+        if let Ok(url) = Url::parse(&format!("http://localhost/?{}", query)) {
+            values.extend(
+                url.query_pairs()
+                    .map(|(k, v)| (k.to_string(), HTTPParameterTarget::Query, v.to_string())),
+            );
+        }
+    }
+
+    if let Some(serde_json::Value::Object(fields)) = request
+        .body
+        .as_deref()
+        .and_then(|b| serde_json::from_str::<serde_json::Value>(b).ok())
+    {
+        for (name, value) in fields {
+            let example = match value {
+                serde_json::Value::String(s) => s,
+                serde_json::Value::Bool(_) | serde_json::Value::Number(_) => value.to_string(),
+                _ => continue,
+            };
+            values.push((name, HTTPParameterTarget::Body, example));
+        }
+    }
+
+    let mut learned = vec![];
+    for (name, target, value) in values {
+        let parameter = operation.parameters.iter_mut().find(|p| {
+            p.name == name
+                && p.meta_data
+                    == Some(ParameterMetaData::HTTP {
+                        target: target.clone(),
+                    })
+        });
+
+        if let Some(parameter) = parameter {
+            push_examples(&mut parameter.examples, vec![value]);
+            learned.push(name);
+        }
+    }
+
+    learned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_explore::amos::{OperationInfo, Parameter, ParameterOwnership, Schema};
+
+    fn get_person() -> Operation {
+        Operation {
+            info: OperationInfo {
+                name: "get_person".to_string(),
+                key: "operation/get_person".to_string(),
+                alias: None,
+                deprecated: false,
+            },
+            parameters: vec![Parameter {
+                name: "id".to_string(),
+                schema: Schema::Int,
+                required: true,
+                ownership: ParameterOwnership::Dependency,
+                meta_data: Some(ParameterMetaData::HTTP {
+                    target: HTTPParameterTarget::Path,
+                }),
+                examples: vec![],
+                relation_hints: vec![],
+                generator: None,
+            }],
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: "/persons/{id}".to_string(),
+                method: HTTPMethod::GET,
+            }),
+        }
+    }
+
+    #[test]
+    fn learn_examples_and_new_operations() {
+        let mut amos = amos::AMOS::new();
+        amos.push_operation(get_person());
+
+        let requests = vec![
+            CapturedRequest {
+                method: HTTPMethod::GET,
+                path: "/persons/7".to_string(),
+                body: None,
+            },
+            CapturedRequest {
+                method: HTTPMethod::GET,
+                path: "/persons/7?verbose=true".to_string(),
+                body: None,
+            },
+            CapturedRequest {
+                method: HTTPMethod::POST,
+                path: "/persons".to_string(),
+                body: Some(r#"{"name": "Alice"}"#.to_string()),
+            },
+        ];
+
+        let learned = learn_from_requests(amos, &requests).amos;
+
+        assert_eq!(learned.operations.len(), 2);
+        // Repeated values are kept once
+        assert_eq!(learned.operations[0].parameters[0].examples, vec!["7"]);
+
+        let post = &learned.operations[1];
+        assert_eq!(post.info.name, "post_persons");
+        assert_eq!(post.parameters[0].name, "name");
+        assert_eq!(post.parameters[0].examples, vec!["Alice"]);
+    }
+
+    #[test]
+    fn proxy_records_accepted_requests() {
+        // A SUT answering the first request with 200 and the second with 404
+        let sut = TcpListener::bind("127.0.0.1:0").unwrap();
+        let sut_url = Url::parse(&format!("http://{}", sut.local_addr().unwrap())).unwrap();
+        thread::spawn(move || {
+            for status in ["200 OK", "404 Not Found"] {
+                let (mut stream, _) = sut.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                    line.clear();
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]",
+                    status
+                )
+                .unwrap();
+            }
+        });
+
+        let proxy = CaptureProxy::start("127.0.0.1:0", &sut_url).unwrap();
+        let proxy_url = format!("http://{}", proxy.address());
+
+        let client = reqwest::blocking::Client::new();
+        let found = client
+            .get(format!("{}/persons?page=1", proxy_url))
+            .send()
+            .unwrap();
+        assert_eq!(found.status(), 200);
+        assert_eq!(found.text().unwrap(), "[]");
+
+        let missing = client.get(format!("{}/other", proxy_url)).send().unwrap();
+        assert_eq!(missing.status(), 404);
+
+        assert_eq!(
+            proxy.stop(),
+            vec![CapturedRequest {
+                method: HTTPMethod::GET,
+                path: "/persons?page=1".to_string(),
+                body: None
+            }]
+        );
+    }
+
+    /// Send the raw request to the proxy, returns the raw response
+    fn send_raw(proxy: &CaptureProxy, request: &str) -> String {
+        let mut stream = TcpStream::connect(proxy.address()).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn proxy_rejects_targets_of_other_hosts() {
+        let sut = TcpListener::bind("127.0.0.1:0").unwrap();
+        sut.set_nonblocking(true).unwrap();
+        let sut_url = Url::parse(&format!("http://{}", sut.local_addr().unwrap())).unwrap();
+        let proxy = CaptureProxy::start("127.0.0.1:0", &sut_url).unwrap();
+
+        for target in [
+            "http://example.com/persons",
+            "//example.com/persons",
+            "/\\example.com/persons",
+            "persons",
+        ] {
+            let response = send_raw(
+                &proxy,
+                &format!("GET {} HTTP/1.1\r\nHost: example.com\r\n\r\n", target),
+            );
+            assert!(
+                response.starts_with("HTTP/1.1 400"),
+                "{}: {}",
+                target,
+                response
+            );
+        }
+
+        assert!(proxy.stop().is_empty());
+        // Nothing reached the SUT
+        assert_eq!(
+            sut.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn proxy_forwards_chunked_bodies_and_unsupported_methods() {
+        // A SUT answering 201 to the POST and 200 to the PATCH, sending back
+        //  the body it got
+        let sut = TcpListener::bind("127.0.0.1:0").unwrap();
+        let sut_url = Url::parse(&format!("http://{}", sut.local_addr().unwrap())).unwrap();
+        let (bodies, received) = std::sync::mpsc::channel();
+        thread::spawn(move || {
+            for status in ["201 Created", "200 OK"] {
+                let (mut stream, _) = sut.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.send(String::from_utf8(body).unwrap()).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
+        });
+
+        let proxy = CaptureProxy::start("127.0.0.1:0", &sut_url).unwrap();
+
+        let created = send_raw(
+            &proxy,
+            "POST /persons HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+             7\r\n{\"name\"\r\n8;last\r\n: \"Ada\"}\r\n0\r\n\r\n",
+        );
+        assert!(created.starts_with("HTTP/1.1 201"), "{}", created);
+        assert_eq!(received.recv().unwrap(), r#"{"name": "Ada"}"#);
+
+        let patched = reqwest::blocking::Client::new()
+            .patch(format!("http://{}/persons/1", proxy.address()))
+            .body("{}")
+            .send()
+            .unwrap();
+        assert_eq!(patched.status(), 200);
+        assert_eq!(received.recv().unwrap(), "{}");
+
+        // The PATCH is forwarded but not captured
+        assert_eq!(
+            proxy.stop(),
+            vec![CapturedRequest {
+                method: HTTPMethod::POST,
+                path: "/persons".to_string(),
+                body: Some(r#"{"name": "Ada"}"#.to_string())
+            }]
+        );
+    }
+
+    #[test]
+    fn proxy_rejects_unsupported_transfer_encodings() {
+        let sut = TcpListener::bind("127.0.0.1:0").unwrap();
+        let sut_url = Url::parse(&format!("http://{}", sut.local_addr().unwrap())).unwrap();
+        let proxy = CaptureProxy::start("127.0.0.1:0", &sut_url).unwrap();
+
+        let response = send_raw(
+            &proxy,
+            "POST /persons HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\n0\r\n\r\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 501"), "{}", response);
+        assert!(proxy.stop().is_empty());
+    }
+}
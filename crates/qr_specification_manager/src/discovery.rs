@@ -0,0 +1,223 @@
+use std::collections::{HashSet, VecDeque};
+
+use qr_explore::amos::{self, TranslationMessage};
+use qr_explore::hypermedia;
+use qr_http_resource::http::HTTPMethod;
+use reqwest::Url;
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+use crate::inference::OperationTemplate;
+
+/// Common entry points of an API, probed before any links are known
+pub const PROBE_PATHS: &[&str] = &["/", "/api", "/api/v1", "/v1", "/health", "/status"];
+
+#[derive(Debug, Clone)]
+pub struct DiscoveryOptions {
+    /// Upper bound of requests made by the crawl
+    pub max_requests: usize,
+}
+
+impl DiscoveryOptions {
+    pub fn new() -> Self {
+        DiscoveryOptions { max_requests: 200 }
+    }
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build an AMOS of the API at the base URL, when there is no specification.
+///  The common paths are probed, and the links of the JSON responses are
+///  followed, to find the GET operations of the API.
+pub fn discover_from_url(
+    base: &Url,
+    options: &DiscoveryOptions,
+) -> Result<amos::TranslationResult> {
+    info!("Discover the API at: {}", base);
+
+    let client = reqwest::blocking::Client::new();
+    let paths = crawl(base, options, |url| {
+        let response = client.get(url.clone()).send().ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+
+        response.text().ok()
+    });
+
+    if paths.is_empty() {
+        return Err(Error::DiscoveryFailed(format!(
+            "No JSON responses from {}",
+            base
+        )));
+    }
+
+    Ok(amos_from_paths(base.as_str(), &paths))
+}
+
+/// The paths, in order of discovery, that responded with JSON. The fetch
+///  function returns the payload of a successful response.
+pub fn crawl(
+    base: &Url,
+    options: &DiscoveryOptions,
+    mut fetch: impl FnMut(&Url) -> Option<String>,
+) -> Vec<String> {
+    let mut queue: VecDeque<String> = PROBE_PATHS.iter().map(|p| p.to_string()).collect();
+    let mut seen: HashSet<String> = queue.iter().cloned().collect();
+    let mut discovered = vec![];
+    let mut requests = 0;
+
+    while let Some(path) = queue.pop_front() {
+        if requests >= options.max_requests {
+            info!("Discovery stopped after {} requests", requests);
+            break;
+        }
+
+        let Ok(url) = base.join(&path) else {
+            continue;
+        };
+
+        requests += 1;
+        let Some(payload) = fetch(&url) else {
+            continue;
+        };
+
+        // Only JSON responses are considered part of the API
+        if serde_json::from_str::<serde_json::Value>(&payload).is_err() {
+            continue;
+        }
+
+        debug!("Discovered: {}", path);
+        discovered.push(path);
+
+        for href in hypermedia::extract_links(&payload) {
+            if let Some(link) = hypermedia::resolve_link(base, &href) {
+                if seen.insert(link.clone()) {
+                    queue.push_back(link);
+                }
+            }
+        }
+    }
+
+    discovered
+}
+
+/// An AMOS of the GET operations inferred from the paths, paths of the same
+///  resource share one operation
+pub fn amos_from_paths(name: &str, paths: &[String]) -> amos::TranslationResult {
+    let mut templates: Vec<OperationTemplate> = vec![];
+
+    for path in paths {
+        let template = OperationTemplate::infer(HTTPMethod::GET, path, None);
+
+        match templates.iter_mut().find(|t| t.same_operation(&template)) {
+            Some(existing) => existing.merge(template),
+            None => templates.push(template),
+        }
+    }
+
+    let mut amos = amos::AMOS::new();
+    amos.name = format!("Discovered from {}", name);
+
+    let mut names = HashSet::new();
+    for template in templates {
+        let operation = template.into_operation(&mut names);
+        amos.push_operation(operation);
+    }
+
+    amos::TranslationResult {
+        warnings: vec![TranslationMessage::new(format!(
+            "Inferred {} operations from {} discovered paths",
+            amos.operations.len(),
+            paths.len()
+        ))],
+        amos,
+        errors: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_explore::amos::{OperationMetaData, ParameterMetaData, Schema};
+    use qr_http_resource::http::HTTPParameterTarget;
+    use std::collections::HashMap;
+
+    #[test]
+    fn crawl_follows_links_from_the_probes() {
+        let base = Url::parse("http://localhost:8080").unwrap();
+        let responses: HashMap<&str, &str> = HashMap::from([
+            (
+                "/api",
+                r#"{"_links": {"persons": {"href": "/api/persons"}}}"#,
+            ),
+            (
+                "/api/persons",
+                r#"[{"href": "/api/persons/1"}, {"href": "http://example.com/other"}]"#,
+            ),
+            ("/api/persons/1", r#"{"name": "Alice"}"#),
+            ("/health", "OK"),
+        ]);
+
+        let paths = crawl(&base, &DiscoveryOptions::new(), |url| {
+            responses.get(url.path()).map(|r| r.to_string())
+        });
+
+        assert_eq!(paths, vec!["/api", "/api/persons", "/api/persons/1"]);
+    }
+
+    #[test]
+    fn crawl_is_bounded() {
+        let base = Url::parse("http://localhost:8080").unwrap();
+        let options = DiscoveryOptions { max_requests: 3 };
+
+        let mut requests = 0;
+        crawl(&base, &options, |url| {
+            requests += 1;
+            // Every response links to a new page
+            Some(format!(r#"{{"href": "{}/next"}}"#, url.path()))
+        });
+
+        assert_eq!(requests, 3);
+    }
+
+    #[test]
+    fn infer_operations_with_parameters() {
+        let paths = vec![
+            "/api/persons?page=1".to_string(),
+            "/api/persons/1".to_string(),
+            "/api/persons/2".to_string(),
+            "/api/persons/3f2c8a1e-1b4b-4c4e-9d5e-a1b2c3d4e5f6".to_string(),
+        ];
+
+        let amos = amos_from_paths("http://localhost", &paths).amos;
+
+        assert_eq!(amos.operations.len(), 2);
+
+        let persons = &amos.operations[0];
+        assert_eq!(persons.info.name, "get_api_persons");
+        assert_eq!(persons.parameters[0].name, "page");
+        assert_eq!(
+            persons.parameters[0].meta_data,
+            Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::Query
+            })
+        );
+
+        let person = &amos.operations[1];
+        assert_eq!(person.info.name, "get_api_persons_person_id");
+        assert_eq!(
+            person.meta_data,
+            Some(OperationMetaData::HTTP {
+                url: "/api/persons/{person_id}".to_string(),
+                method: HTTPMethod::GET
+            })
+        );
+        // One of the identifiers is a UUID
+        assert_eq!(person.parameters[0].schema, Schema::StringNonEmpty);
+    }
+}
@@ -5,6 +5,14 @@ pub enum Error {
     OpenAPIFetchFailed(qr_specification_resource_access::Error),
 
     OpenAPIParseFailed(qr_open_api::Error),
+
+    DiscoveryFailed(String),
+
+    CaptureFailed(String),
+
+    PostmanImportFailed(String),
+
+    LinkFormatImportFailed(String),
 }
 
 impl From<qr_specification_resource_access::Error> for Error {
@@ -0,0 +1,351 @@
+use std::collections::HashSet;
+
+use qr_explore::amos::{
+    Operation, OperationInfo, OperationMetaData, Parameter, ParameterMetaData, ParameterOwnership,
+    Schema,
+};
+use qr_http_resource::http::{HTTPMethod, HTTPParameterTarget};
+use reqwest::Url;
+
+/// Upper bound of examples kept for a parameter
+pub const MAX_EXAMPLES: usize = 32;
+
+/// An operation inferred from the requests made to it. Path segments that look
+///  like identifiers, or are templated as in `{id}`, become path parameters,
+///  query keys become query parameters
+///  and the fields of a JSON object body become body parameters, so requests to
+///  the same resource share one template.
+#[derive(Debug)]
+pub(crate) struct OperationTemplate {
+    pub(crate) method: HTTPMethod,
+    pub(crate) url: String,
+    parameters: Vec<InferredParameter>,
+}
+
+#[derive(Debug)]
+struct InferredParameter {
+    name: String,
+    schema: Schema,
+    target: HTTPParameterTarget,
+    required: bool,
+    examples: Vec<String>,
+}
+
+impl OperationTemplate {
+    /// Infer the template of a request, the path includes any query
+    pub(crate) fn infer(method: HTTPMethod, path: &str, body: Option<&str>) -> Self {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+
+        let mut segments = vec![];
+        let mut parameters: Vec<InferredParameter> = vec![];
+        let mut previous = "id";
+
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            if let Some(name) = template_name(segment) {
+                segments.push(segment.to_string());
+                if !parameters.iter().any(|p| p.name == name) {
+                    parameters.push(InferredParameter {
+                        name: name.to_string(),
+                        schema: Schema::StringNonEmpty,
+                        target: HTTPParameterTarget::Path,
+                        required: true,
+                        examples: vec![],
+                    });
+                }
+            } else if is_identifier(segment) {
+                let mut name = format!("{}_id", previous.trim_end_matches('s'));
+                if parameters.iter().any(|p| p.name == name) {
+                    name = format!("{}_{}", name, parameters.len());
+                }
+
+                let schema = if segment.chars().all(|c| c.is_ascii_digit()) {
+                    Schema::Int
+                } else {
+                    Schema::StringNonEmpty
+                };
+
+                segments.push(format!("{{{}}}", name));
+                parameters.push(InferredParameter {
+                    name,
+                    schema,
+                    target: HTTPParameterTarget::Path,
+                    required: true,
+                    examples: vec![segment.to_string()],
+                });
+            } else {
+                segments.push(segment.to_string());
+                previous = segment;
+            }
+        }
+
+        // The query is parsed against any base, only to decode its pairs
+        if let Ok(url) = Url::parse(&format!("http://localhost/?{}", query)) {
+            for (key, value) in url.query_pairs() {
+                if key.is_empty() || parameters.iter().any(|p| p.name == key) {
+                    continue;
+                }
+
+                parameters.push(InferredParameter {
+                    name: key.to_string(),
+                    schema: Schema::String,
+                    target: HTTPParameterTarget::Query,
+                    required: false,
+                    examples: vec![value.to_string()],
+                });
+            }
+        }
+
+        if let Some(serde_json::Value::Object(fields)) =
+            body.and_then(|b| serde_json::from_str::<serde_json::Value>(b).ok())
+        {
+            for (key, value) in fields {
+                // Only scalar fields have a schema to generate from
+                let (schema, example) = match value {
+                    serde_json::Value::String(s) => (Schema::String, s),
+                    serde_json::Value::Bool(b) => (Schema::Bool, b.to_string()),
+                    serde_json::Value::Number(n) if n.is_i64() => (Schema::Int, n.to_string()),
+                    serde_json::Value::Number(n) => (Schema::Double, n.to_string()),
+                    _ => continue,
+                };
+
+                parameters.push(InferredParameter {
+                    name: key,
+                    schema,
+                    target: HTTPParameterTarget::Body,
+                    required: true,
+                    examples: vec![example],
+                });
+            }
+        }
+
+        OperationTemplate {
+            method,
+            url: format!("/{}", segments.join("/")),
+            parameters,
+        }
+    }
+
+    /// Add a parameter of the request, or the example to the parameter of the
+    ///  template with the name and target
+    pub(crate) fn push_parameter(
+        &mut self,
+        name: &str,
+        target: HTTPParameterTarget,
+        example: Option<String>,
+    ) {
+        let examples = example.into_iter().collect();
+        match self
+            .parameters
+            .iter_mut()
+            .find(|p| p.name == name && p.target == target)
+        {
+            Some(param) => push_examples(&mut param.examples, examples),
+            None => self.parameters.push(InferredParameter {
+                name: name.to_string(),
+                schema: Schema::String,
+                target,
+                required: true,
+                examples,
+            }),
+        }
+    }
+
+    /// If both templates are requests to the same operation
+    pub(crate) fn same_operation(&self, other: &OperationTemplate) -> bool {
+        self.method == other.method && self.url == other.url
+    }
+
+    pub(crate) fn merge(&mut self, other: OperationTemplate) {
+        // Fields missing from some of the requests are not required
+        for param in self.parameters.iter_mut() {
+            if !other
+                .parameters
+                .iter()
+                .any(|p| p.name == param.name && p.target == param.target)
+            {
+                param.required = false;
+            }
+        }
+
+        for other_param in other.parameters {
+            match self
+                .parameters
+                .iter_mut()
+                .find(|p| p.name == other_param.name && p.target == other_param.target)
+            {
+                Some(param) => {
+                    param.schema = merge_schema(&param.schema, &other_param.schema);
+                    push_examples(&mut param.examples, other_param.examples);
+                }
+                None => self.parameters.push(InferredParameter {
+                    required: false,
+                    ..other_param
+                }),
+            }
+        }
+    }
+
+    pub(crate) fn into_operation(self, names: &mut HashSet<String>) -> Operation {
+        let words: Vec<&str> = self
+            .url
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.trim_matches(|c| c == '{' || c == '}'))
+            .collect();
+
+        let method = format!("{:?}", self.method).to_lowercase();
+        let base_name = if words.is_empty() {
+            format!("{}_root", method)
+        } else {
+            format!("{}_{}", method, words.join("_"))
+        };
+
+        let mut name = base_name.clone();
+        let mut n = 1;
+        while !names.insert(name.clone()) {
+            n += 1;
+            name = format!("{}_{}", base_name, n);
+        }
+
+        let ownership = match self.method {
+            HTTPMethod::GET | HTTPMethod::DELETE => ParameterOwnership::Dependency,
+            _ => ParameterOwnership::Unknown,
+        };
+
+        let parameters = self
+            .parameters
+            .into_iter()
+            .map(|param| Parameter {
+                name: param.name,
+                schema: param.schema,
+                required: param.required,
+                // Identifiers in the path refer to existing resources
+                ownership: if param.target == HTTPParameterTarget::Path {
+                    ParameterOwnership::Dependency
+                } else {
+                    ownership.clone()
+                },
+                meta_data: Some(ParameterMetaData::HTTP {
+                    target: param.target,
+                }),
+                examples: param.examples,
+                relation_hints: vec![],
+                generator: None,
+            })
+            .collect();
+
+        Operation {
+            info: OperationInfo {
+                key: format!("operation/{}", name),
+                name,
+                alias: None,
+                deprecated: false,
+            },
+            parameters,
+            responses: vec![],
+            meta_data: Some(OperationMetaData::HTTP {
+                url: self.url,
+                method: self.method,
+            }),
+        }
+    }
+}
+
+/// Add the examples that are new, keeping at most `MAX_EXAMPLES`
+pub(crate) fn push_examples(examples: &mut Vec<String>, new: Vec<String>) {
+    for example in new {
+        if examples.len() >= MAX_EXAMPLES {
+            break;
+        }
+
+        if !examples.contains(&example) {
+            examples.push(example);
+        }
+    }
+}
+
+/// The schema of values seen as both schemas, e.g. an identifier that is not
+///  always a number is a string
+fn merge_schema(schema: &Schema, other: &Schema) -> Schema {
+    match (schema, other) {
+        (a, b) if a == b => a.clone(),
+        (Schema::Int, Schema::Double) | (Schema::Double, Schema::Int) => Schema::Double,
+        (Schema::StringNonEmpty, _) | (_, Schema::StringNonEmpty) => Schema::StringNonEmpty,
+        _ => Schema::String,
+    }
+}
+
+/// The name of the parameter of a templated path segment, as in `{id}`
+fn template_name(segment: &str) -> Option<&str> {
+    segment
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .filter(|name| !name.is_empty())
+}
+
+/// If a path segment is an identifier of a resource, a number, a UUID or a
+///  long hex string (e.g. a Mongo object id)
+fn is_identifier(segment: &str) -> bool {
+    let is_number = segment.chars().all(|c| c.is_ascii_digit());
+    let is_uuid = segment.len() == 36
+        && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        && segment.matches('-').count() == 4;
+    let is_hex = segment.len() >= 24 && segment.chars().all(|c| c.is_ascii_hexdigit());
+
+    is_number || is_uuid || is_hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_and_merge_body_parameters() {
+        let mut template = OperationTemplate::infer(
+            HTTPMethod::POST,
+            "/persons",
+            Some(r#"{"name": "Alice", "age": 30, "tags": ["a"]}"#),
+        );
+        template.merge(OperationTemplate::infer(
+            HTTPMethod::POST,
+            "/persons",
+            Some(r#"{"name": "Bob", "age": 30.5}"#),
+        ));
+
+        let operation = template.into_operation(&mut HashSet::new());
+        assert_eq!(operation.info.name, "post_persons");
+
+        let names: Vec<&str> = operation
+            .parameters
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        // The array field has no scalar schema
+        assert_eq!(names, vec!["age", "name"]);
+
+        let age = &operation.parameters[0];
+        assert_eq!(age.schema, Schema::Double);
+        assert_eq!(age.examples, vec!["30".to_string(), "30.5".to_string()]);
+        assert_eq!(
+            age.meta_data,
+            Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::Body
+            })
+        );
+    }
+
+    #[test]
+    fn optional_parameters_and_decoded_examples() {
+        let mut template =
+            OperationTemplate::infer(HTTPMethod::GET, "/persons?name=Alice%20B&page=1", None);
+        template.merge(OperationTemplate::infer(HTTPMethod::GET, "/persons", None));
+
+        let operation = template.into_operation(&mut HashSet::new());
+
+        assert_eq!(
+            operation.parameters[0].examples,
+            vec!["Alice B".to_string()]
+        );
+        assert!(operation.parameters.iter().all(|p| !p.required));
+    }
+}
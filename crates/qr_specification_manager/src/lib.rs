@@ -1,4 +1,10 @@
+pub mod capture;
+pub mod discovery;
 pub mod error;
+pub mod inference;
+pub mod link_format;
+pub mod lint;
 pub mod manager;
+pub mod postman;
 
 pub use self::error::{Error, Result};
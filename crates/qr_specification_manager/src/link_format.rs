@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+
+use qr_explore::amos::{self, TranslationMessage};
+use qr_http_resource::http::{HTTPMethod, HTTPParameterTarget};
+use tracing::info;
+
+use crate::capture::http_method;
+use crate::error::{Error, Result};
+use crate::inference::OperationTemplate;
+
+pub fn load_link_format_file_path(file_path: &str) -> Result<amos::TranslationResult> {
+    info!(file_path, "Import CoRE resources");
+
+    let contents = std::fs::read_to_string(file_path).map_err(|e| {
+        Error::LinkFormatImportFailed(format!("Failed to read {}: {}", file_path, e))
+    })?;
+
+    link_format_to_amos(&contents)
+}
+
+/// A resource of a link, with its attributes
+#[derive(Debug, PartialEq)]
+struct Link {
+    target: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Link {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The methods of the `methods` attribute, e.g. "GET PUT", or else those
+    ///  of the interface of the resource
+    fn methods(&self) -> Vec<&str> {
+        if let Some(methods) = self.attribute("methods") {
+            return methods
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|method| !method.is_empty())
+                .collect();
+        }
+
+        match self.attribute("if") {
+            Some("core.a") => vec!["GET", "PUT", "POST"],
+            Some("core.p") => vec!["GET", "PUT"],
+            _ => vec!["GET"],
+        }
+    }
+}
+
+/// An AMOS of the resources of a CoRE Link Format document (RFC 6690), as
+///  served at '/.well-known/core'. The methods of a resource are those of
+///  its `methods` attribute or of its interface, e.g. `if="core.a"` for an
+///  actuator. The `example` attribute is a JSON body of its PUT and POST.
+pub fn link_format_to_amos(document: &str) -> Result<amos::TranslationResult> {
+    let links = parse_links(document).map_err(Error::LinkFormatImportFailed)?;
+    if links.is_empty() {
+        return Err(Error::LinkFormatImportFailed(
+            "There are no links to resources".to_string(),
+        ));
+    }
+
+    let mut templates: Vec<OperationTemplate> = vec![];
+    let mut warnings = vec![];
+    for link in &links {
+        // The description of the resources is not a resource to explore
+        if link.target == "/.well-known/core" {
+            continue;
+        }
+
+        for method in link.methods() {
+            let Some(method) = http_method(method) else {
+                warnings.push(TranslationMessage::new(format!(
+                    "Skipped {} of {}: the method is not supported",
+                    method, link.target
+                )));
+                continue;
+            };
+
+            let body = match method {
+                HTTPMethod::PUT | HTTPMethod::POST => link.attribute("example"),
+                _ => None,
+            };
+            let mut template = OperationTemplate::infer(method.clone(), &link.target, body);
+            // A body without an example is a single value
+            if body.is_none() && matches!(method, HTTPMethod::PUT | HTTPMethod::POST) {
+                template.push_parameter("value", HTTPParameterTarget::Body, None);
+            }
+
+            match templates.iter_mut().find(|t| t.same_operation(&template)) {
+                Some(existing) => existing.merge(template),
+                None => templates.push(template),
+            }
+        }
+    }
+
+    let mut amos = amos::AMOS::new();
+    amos.name = "CoRE resources".to_string();
+
+    let mut names = HashSet::new();
+    for template in templates {
+        amos.push_operation(template.into_operation(&mut names));
+    }
+
+    warnings.insert(
+        0,
+        TranslationMessage::new(format!(
+            "Imported {} operations from {} links",
+            amos.operations.len(),
+            links.len()
+        )),
+    );
+
+    Ok(amos::TranslationResult {
+        amos,
+        warnings,
+        errors: vec![],
+    })
+}
+
+/// The links of the document, as in `</sensors/temp>;rt="temperature";if=sensor`
+///  separated by commas
+fn parse_links(document: &str) -> core::result::Result<Vec<Link>, String> {
+    let mut links = vec![];
+    let mut rest = document.trim();
+
+    while !rest.is_empty() {
+        let Some(after_start) = rest.strip_prefix('<') else {
+            return Err(format!("Expected a link, got '{}'", rest));
+        };
+        let end = after_start
+            .find('>')
+            .ok_or_else(|| format!("The link is not closed '{}'", rest))?;
+        let target = after_start[..end].to_string();
+        rest = after_start[end + 1..].trim_start();
+
+        let mut attributes = vec![];
+        while let Some(after) = rest.strip_prefix(';') {
+            let after = after.trim_start();
+            let name_end = after.find(['=', ';', ',']).unwrap_or(after.len());
+            let name = after[..name_end].trim().to_string();
+            rest = &after[name_end..];
+
+            let value = match rest.strip_prefix('=') {
+                Some(value) => {
+                    let (value, after) = attribute_value(value.trim_start())?;
+                    rest = after;
+                    value
+                }
+                None => String::new(),
+            };
+            attributes.push((name, value));
+            rest = rest.trim_start();
+        }
+
+        links.push(Link { target, attributes });
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+
+    Ok(links)
+}
+
+/// The value of an attribute, quoted or a token, and the text after it
+fn attribute_value(text: &str) -> core::result::Result<(String, &str), String> {
+    let Some(quoted) = text.strip_prefix('"') else {
+        let end = text.find([';', ',']).unwrap_or(text.len());
+        return Ok((text[..end].trim().to_string(), &text[end..]));
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+            '"' => return Ok((value, &quoted[idx + 1..])),
+            c => value.push(c),
+        }
+    }
+
+    Err(format!("The value is not closed '{}'", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_explore::amos::{OperationMetaData, ParameterMetaData, Schema};
+
+    const DOCUMENT: &str = r#"</.well-known/core>;ct=40,
+        </sensors/temp>;rt="temperature-c";if="core.s";obs,
+        </actuators/{id}/led>;if="core.a";example="{\"on\": true}",
+        </config/interval>;if=core.p,
+        </firmware>;methods="POST PATCH""#;
+
+    #[test]
+    fn imports_the_resources_of_the_links() {
+        let result = link_format_to_amos(DOCUMENT).unwrap();
+        let amos = result.amos;
+
+        let names: Vec<&str> = amos
+            .operations
+            .iter()
+            .map(|o| o.info.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "get_sensors_temp",
+                "get_actuators_id_led",
+                "put_actuators_id_led",
+                "post_actuators_id_led",
+                "get_config_interval",
+                "put_config_interval",
+                "post_firmware"
+            ]
+        );
+
+        let put_led = &amos.operations[2];
+        assert_eq!(
+            put_led.meta_data,
+            Some(OperationMetaData::HTTP {
+                url: "/actuators/{id}/led".to_string(),
+                method: HTTPMethod::PUT
+            })
+        );
+        assert_eq!(put_led.parameters[0].name, "id");
+        assert_eq!(put_led.parameters[1].name, "on");
+        assert_eq!(put_led.parameters[1].schema, Schema::Bool);
+
+        // Without an example the body is a single value
+        let put_interval = &amos.operations[5];
+        assert_eq!(put_interval.parameters[0].name, "value");
+        assert_eq!(
+            put_interval.parameters[0].meta_data,
+            Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::Body
+            })
+        );
+
+        assert_eq!(
+            result.warnings[0].message,
+            "Imported 7 operations from 5 links"
+        );
+        assert_eq!(
+            result.warnings[1].message,
+            "Skipped PATCH of /firmware: the method is not supported"
+        );
+    }
+
+    #[test]
+    fn a_document_without_links_is_not_valid() {
+        assert!(link_format_to_amos("").is_err());
+        assert!(link_format_to_amos("</sensors/temp;rt=temperature").is_err());
+    }
+}
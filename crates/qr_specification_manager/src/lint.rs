@@ -0,0 +1,246 @@
+use qr_explore::amos;
+use qr_http_resource::http::{HTTPMethod, HTTPStatus};
+use qr_open_api::open_api::{self, ParameterIn, ParseResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum LintKind {
+    MissingOperationId,
+    UndeclaredClientError,
+    UnusedParameter,
+    UnreachableDefinition,
+}
+
+impl LintKind {
+    pub const ALL: [LintKind; 4] = [
+        LintKind::MissingOperationId,
+        LintKind::UndeclaredClientError,
+        LintKind::UnusedParameter,
+        LintKind::UnreachableDefinition,
+    ];
+
+    pub fn presentation(&self) -> &'static str {
+        match self {
+            LintKind::MissingOperationId => "Missing operationId",
+            LintKind::UndeclaredClientError => "Undeclared 4xx response",
+            LintKind::UnusedParameter => "Unused parameter",
+            LintKind::UnreachableDefinition => "Unreachable definition",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct LintFinding {
+    pub kind: LintKind,
+    /// The operation of the finding, none for a definition
+    pub operation: Option<String>,
+    pub message: String,
+}
+
+/// The quality of a specification, what makes it harder to explore
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn count(&self, kind: LintKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    fn push(&mut self, kind: LintKind, operation: Option<&str>, message: String) {
+        self.findings.push(LintFinding {
+            kind,
+            operation: operation.map(str::to_string),
+            message,
+        });
+    }
+}
+
+/// Lint the parsed specification and its AMOS, the operations of the parse
+///  result and the definitions of the AMOS
+pub fn lint(parse_result: &ParseResult, amos: &amos::AMOS) -> LintReport {
+    let mut report = LintReport::default();
+
+    for op in &parse_result.operations {
+        lint_operation(&mut report, op);
+    }
+
+    let reachable = amos.reachable_definitions();
+    for definition in &amos.definitions {
+        if !reachable.contains(&definition.name) {
+            report.push(
+                LintKind::UnreachableDefinition,
+                None,
+                format!(
+                    "The definition {} is not taken or responded by any operation",
+                    definition.name
+                ),
+            );
+        }
+    }
+
+    report
+}
+
+fn lint_operation(report: &mut LintReport, op: &open_api::Operation) {
+    // The parser names an operation without an id by its path and method
+    if op
+        .id
+        .strip_prefix(&op.url)
+        .is_some_and(|method| method.starts_with('-'))
+    {
+        report.push(
+            LintKind::MissingOperationId,
+            Some(&op.id),
+            format!("The operation has no operationId, it is named {}", op.id),
+        );
+    }
+
+    let client_error = op.responses.iter().any(|r| {
+        r.status == HTTPStatus::Default
+            || r.status
+                .code()
+                .is_some_and(|code| (400..=499).contains(&code))
+    });
+    if !client_error {
+        report.push(
+            LintKind::UndeclaredClientError,
+            Some(&op.id),
+            "No 4xx or default response is declared, an invalid request has no expected status"
+                .to_string(),
+        );
+    }
+
+    let without_body = matches!(op.method, HTTPMethod::GET | HTTPMethod::DELETE);
+    for param in &op.parameters {
+        let unused = match param.target {
+            ParameterIn::Path => !op.url.contains(&format!("{{{}}}", param.name)),
+            ParameterIn::Body | ParameterIn::FormData => without_body,
+            _ => false,
+        };
+        if unused {
+            report.push(
+                LintKind::UnusedParameter,
+                Some(&op.id),
+                format!(
+                    "The parameter {} is not sent, it is not in the {}",
+                    param.name,
+                    match param.target {
+                        ParameterIn::Path => "path",
+                        _ => "request, a request of the method has no body",
+                    }
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lints_the_operations_and_definitions() {
+        let oas_json = serde_json::json!({
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "type": "string" },
+                            { "name": "filter", "in": "body", "required": false, "type": "string" }
+                        ],
+                        "responses": {
+                            "200": { "description": "OK", "schema": { "$ref": "#/definitions/Pet" } },
+                            "404": { "description": "Not Found" }
+                        }
+                    },
+                    "delete": {
+                        "responses": { "204": { "description": "Deleted" } }
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "owners": { "type": "array", "items": { "$ref": "#/definitions/Owner" } }
+                    }
+                },
+                "Owner": { "type": "object", "properties": { "name": { "type": "string" } } },
+                "Legacy": { "type": "object", "properties": { "name": { "type": "string" } } }
+            }
+        });
+        let parse_result = open_api::parse_json_object(oas_json.as_object().unwrap()).unwrap();
+        let amos =
+            amos::open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+
+        let report = lint(&parse_result, &amos);
+
+        let findings: Vec<(LintKind, Option<&str>)> = report
+            .findings
+            .iter()
+            .map(|f| (f.kind, f.operation.as_deref()))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (LintKind::MissingOperationId, Some("/pets/{petId}-delete")),
+                (
+                    LintKind::UndeclaredClientError,
+                    Some("/pets/{petId}-delete")
+                ),
+                (LintKind::UnusedParameter, Some("getPet")),
+                (LintKind::UnusedParameter, Some("getPet")),
+                (LintKind::UnreachableDefinition, None),
+            ]
+        );
+        // The definitions referenced through a reachable one are reachable
+        assert!(report.findings[4].message.contains("Legacy"));
+        assert_eq!(report.count(LintKind::UnusedParameter), 2);
+    }
+
+    #[test]
+    fn any_declared_4xx_is_a_client_error() {
+        let oas_json = serde_json::json!({
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "operationId": "addPet",
+                        "responses": {
+                            "201": { "description": "Created" },
+                            "409": { "description": "Conflict" }
+                        }
+                    },
+                    "put": {
+                        "operationId": "updatePet",
+                        "responses": {
+                            "200": { "description": "OK" },
+                            "422": { "description": "Unprocessable" }
+                        }
+                    }
+                }
+            }
+        });
+        let parse_result = open_api::parse_json_object(oas_json.as_object().unwrap()).unwrap();
+        let amos =
+            amos::open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions).amos;
+
+        let report = lint(&parse_result, &amos);
+
+        assert_eq!(report.count(LintKind::UndeclaredClientError), 0);
+        assert!(parse_result.warnings.is_empty());
+    }
+}
@@ -15,8 +15,9 @@ pub fn load_open_api_file_path(file_path: &str) -> Result<(ParseResult, amos::Tr
         Err(e) => return Err(Error::OpenAPIParseFailed(e)),
     };
 
-    let translation_result =
+    let mut translation_result =
         amos::open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions);
+    translation_result.amos.base_path = parse_result.base_path.clone();
 
     Ok((parse_result, translation_result))
 }
@@ -33,8 +34,33 @@ pub fn fetch_open_api_from_url(
         Err(e) => return Err(Error::OpenAPIParseFailed(e)),
     };
 
-    let translation_result =
+    let mut translation_result =
         amos::open_api_v2_to_amos(&parse_result.operations, &parse_result.definitions);
+    translation_result.amos.base_path = parse_result.base_path.clone();
 
     Ok((parse_result, translation_result))
 }
+
+/// Load the OpenAPI files of several services into one AMOS, each service is
+///  named by its file name without the extension
+pub fn load_open_api_file_paths(file_paths: &[String]) -> Result<amos::TranslationResult> {
+    let mut services = vec![];
+
+    for file_path in file_paths {
+        let (parse_result, mut translation_result) = load_open_api_file_path(file_path)?;
+        translation_result.warnings.extend(
+            parse_result
+                .warnings
+                .iter()
+                .map(|w| amos::TranslationMessage::new(w.message.clone())),
+        );
+        let service = std::path::Path::new(file_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| file_path.clone());
+
+        services.push((service, translation_result));
+    }
+
+    Ok(amos::AMOS::merge(services))
+}
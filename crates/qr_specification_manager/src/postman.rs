@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+
+use qr_explore::amos::{self, TranslationMessage};
+use qr_http_resource::http::HTTPParameterTarget;
+use reqwest::Url;
+use serde_json::Value;
+use tracing::info;
+
+use crate::capture::http_method;
+use crate::error::{Error, Result};
+use crate::inference::OperationTemplate;
+
+pub fn load_postman_file_path(file_path: &str) -> Result<amos::TranslationResult> {
+    info!(file_path, "Import Postman collection");
+
+    let contents = std::fs::read_to_string(file_path)
+        .map_err(|e| Error::PostmanImportFailed(format!("Failed to read {}: {}", file_path, e)))?;
+    let collection: Value = serde_json::from_str(&contents)
+        .map_err(|e| Error::PostmanImportFailed(format!("Invalid JSON: {}", e)))?;
+
+    postman_to_amos(&collection)
+}
+
+/// An AMOS of the requests of a Postman collection, its folders included.
+///  Requests to the same method and path share one operation, the values
+///  of the requests are the examples of its parameters.
+pub fn postman_to_amos(collection: &Value) -> Result<amos::TranslationResult> {
+    let Some(items) = collection.get("item").and_then(Value::as_array) else {
+        return Err(Error::PostmanImportFailed(
+            "Not a Postman collection, there are no items".to_string(),
+        ));
+    };
+
+    let variables = variables(collection.get("variable"));
+    let mut templates: Vec<OperationTemplate> = vec![];
+    let mut warnings = vec![];
+    let mut requests = 0;
+
+    let mut pending: Vec<&Value> = items.iter().rev().collect();
+    while let Some(item) = pending.pop() {
+        // A folder holds items of its own
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            pending.extend(children.iter().rev());
+            continue;
+        }
+
+        let name = item.get("name").and_then(Value::as_str).unwrap_or("-");
+        let Some(request) = item.get("request") else {
+            continue;
+        };
+        match request_template(request, &variables) {
+            Ok(template) => {
+                requests += 1;
+                match templates.iter_mut().find(|t| t.same_operation(&template)) {
+                    Some(existing) => existing.merge(template),
+                    None => templates.push(template),
+                }
+            }
+            Err(reason) => {
+                warnings.push(TranslationMessage::new(format!(
+                    "Skipped the request {}: {}",
+                    name, reason
+                )));
+            }
+        }
+    }
+
+    let mut amos = amos::AMOS::new();
+    amos.name = collection
+        .pointer("/info/name")
+        .and_then(Value::as_str)
+        .unwrap_or("Postman collection")
+        .to_string();
+
+    let mut names = HashSet::new();
+    for template in templates {
+        let operation = template.into_operation(&mut names);
+        amos.push_operation(operation);
+    }
+
+    warnings.insert(
+        0,
+        TranslationMessage::new(format!(
+            "Imported {} operations from {} requests",
+            amos.operations.len(),
+            requests
+        )),
+    );
+
+    Ok(amos::TranslationResult {
+        amos,
+        warnings,
+        errors: vec![],
+    })
+}
+
+/// The values of the variables, by their key
+fn variables(variables: Option<&Value>) -> HashMap<String, String> {
+    variables
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|variable| {
+            let key = variable.get("key")?.as_str()?;
+            let value = match variable.get("value")? {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+/// The text with the variables of the collection, as in `{{token}}`, replaced
+///  by their values. Unknown variables are kept.
+fn resolve(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut resolved = text.to_string();
+    for (key, value) in variables {
+        resolved = resolved.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    resolved
+}
+
+/// The name of a variable path segment, as in `:id` or `{{id}}`
+fn variable_name(segment: &str) -> Option<&str> {
+    segment
+        .strip_prefix(':')
+        .or_else(|| {
+            segment
+                .strip_prefix("{{")
+                .and_then(|s| s.strip_suffix("}}"))
+        })
+        .filter(|name| !name.is_empty())
+}
+
+/// The path of a raw URL, without its query, host and any variable of the
+///  base URL, e.g. `/persons` of `{{baseUrl}}/persons?page=1`
+fn raw_path(raw: &str) -> &str {
+    let url = raw.split_once('?').map_or(raw, |(url, _)| url);
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    if url.starts_with('/') {
+        return url;
+    }
+    url.find('/').map_or("", |idx| &url[idx..])
+}
+
+fn request_template(
+    request: &Value,
+    variables: &HashMap<String, String>,
+) -> core::result::Result<OperationTemplate, String> {
+    // A request can be given as only its URL
+    let (method, url) = match request {
+        Value::String(_) => ("GET", request),
+        _ => (
+            request
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or("GET"),
+            request.get("url").unwrap_or(&Value::Null),
+        ),
+    };
+    let method = http_method(method).ok_or(format!("the method {} is not supported", method))?;
+
+    let raw = match url {
+        Value::String(raw) => raw.as_str(),
+        _ => url.get("raw").and_then(Value::as_str).unwrap_or(""),
+    };
+    let segments: Vec<String> = match url.get("path").and_then(Value::as_array) {
+        Some(path) => path
+            .iter()
+            .filter_map(|segment| match segment {
+                Value::String(segment) => Some(segment.clone()),
+                _ => segment.get("value")?.as_str().map(str::to_string),
+            })
+            .collect(),
+        None => raw_path(raw)
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+
+    // The path variables are templated, their values are the examples
+    let mut path_values = variables.clone();
+    path_values.extend(self::variables(url.get("variable")));
+    let mut path = String::new();
+    let mut examples = vec![];
+    for segment in &segments {
+        match variable_name(segment) {
+            Some(name) => {
+                path.push_str(&format!("/{{{}}}", name));
+                if let Some(value) = path_values.get(name) {
+                    examples.push((name, value.clone()));
+                }
+            }
+            None => path.push_str(&format!("/{}", resolve(segment, variables))),
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+
+    let mut query = Url::parse("http://localhost/").expect("Valid base URL");
+    match url.get("query").and_then(Value::as_array) {
+        Some(pairs) => {
+            for pair in pairs {
+                let disabled = pair.get("disabled").and_then(Value::as_bool) == Some(true);
+                let Some(key) = pair
+                    .get("key")
+                    .and_then(Value::as_str)
+                    .filter(|_| !disabled)
+                else {
+                    continue;
+                };
+                let value = pair.get("value").and_then(Value::as_str).unwrap_or("");
+                query
+                    .query_pairs_mut()
+                    .append_pair(key, &resolve(value, variables));
+            }
+        }
+        None => {
+            if let Some((_, raw_query)) = raw.split_once('?') {
+                query.set_query(Some(&resolve(raw_query, variables)));
+            }
+        }
+    }
+    if let Some(query) = query.query().filter(|query| !query.is_empty()) {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    let body = request.get("body");
+    let mode = body.and_then(|b| b.get("mode")).and_then(Value::as_str);
+    let raw_body = match (mode, body.and_then(|b| b.get("raw"))) {
+        (Some("raw"), Some(Value::String(raw))) => Some(resolve(raw, variables)),
+        _ => None,
+    };
+
+    let mut template = OperationTemplate::infer(method, &path, raw_body.as_deref());
+    for (name, value) in examples {
+        template.push_parameter(name, HTTPParameterTarget::Path, Some(value));
+    }
+
+    if let Some(Value::Array(fields)) = mode.and_then(|mode| body?.get(mode)) {
+        for field in fields {
+            let disabled = field.get("disabled").and_then(Value::as_bool) == Some(true);
+            let is_file = field.get("type").and_then(Value::as_str) == Some("file");
+            let Some(key) = field.get("key").and_then(Value::as_str) else {
+                continue;
+            };
+            if disabled || is_file {
+                continue;
+            }
+
+            let value = field
+                .get("value")
+                .and_then(Value::as_str)
+                .map(|value| resolve(value, variables));
+            template.push_parameter(key, HTTPParameterTarget::FormData, value);
+        }
+    }
+
+    Ok(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qr_explore::amos::{OperationMetaData, ParameterMetaData, Schema};
+    use qr_http_resource::http::HTTPMethod;
+
+    fn collection() -> Value {
+        serde_json::json!({
+            "info": {
+                "name": "Persons",
+                "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+            },
+            "variable": [
+                { "key": "baseUrl", "value": "http://localhost:8080" },
+                { "key": "page", "value": "2" }
+            ],
+            "item": [
+                {
+                    "name": "Persons",
+                    "item": [
+                        {
+                            "name": "Get person",
+                            "request": {
+                                "method": "GET",
+                                "url": {
+                                    "raw": "{{baseUrl}}/persons/:id?verbose=true",
+                                    "host": ["{{baseUrl}}"],
+                                    "path": ["persons", ":id"],
+                                    "query": [
+                                        { "key": "verbose", "value": "true" },
+                                        { "key": "debug", "value": "1", "disabled": true }
+                                    ],
+                                    "variable": [{ "key": "id", "value": "7" }]
+                                }
+                            }
+                        },
+                        {
+                            "name": "Create person",
+                            "request": {
+                                "method": "POST",
+                                "url": "{{baseUrl}}/persons",
+                                "body": {
+                                    "mode": "raw",
+                                    "raw": "{\"name\": \"Alice\", \"age\": 36}"
+                                }
+                            }
+                        }
+                    ]
+                },
+                {
+                    "name": "List persons",
+                    "request": "{{baseUrl}}/persons?page={{page}}"
+                },
+                {
+                    "name": "Login",
+                    "request": {
+                        "method": "POST",
+                        "url": "{{baseUrl}}/login",
+                        "body": {
+                            "mode": "urlencoded",
+                            "urlencoded": [{ "key": "user", "value": "ada" }]
+                        }
+                    }
+                },
+                {
+                    "name": "Patch person",
+                    "request": { "method": "PATCH", "url": "{{baseUrl}}/persons/:id" }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn imports_the_requests_of_the_collection() {
+        let result = postman_to_amos(&collection()).unwrap();
+        let amos = result.amos;
+
+        assert_eq!(amos.name, "Persons");
+        let names: Vec<&str> = amos
+            .operations
+            .iter()
+            .map(|o| o.info.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "get_persons_id",
+                "post_persons",
+                "get_persons",
+                "post_login"
+            ]
+        );
+
+        let get_person = &amos.operations[0];
+        assert_eq!(
+            get_person.meta_data,
+            Some(OperationMetaData::HTTP {
+                url: "/persons/{id}".to_string(),
+                method: HTTPMethod::GET
+            })
+        );
+        assert_eq!(get_person.parameters.len(), 2);
+        assert_eq!(get_person.parameters[0].name, "id");
+        assert_eq!(get_person.parameters[0].examples, vec!["7"]);
+        assert_eq!(get_person.parameters[1].name, "verbose");
+
+        let post_person = &amos.operations[1];
+        assert_eq!(post_person.parameters[0].name, "age");
+        assert_eq!(post_person.parameters[0].schema, Schema::Int);
+
+        // The variables of the collection are resolved
+        let get_persons = &amos.operations[2];
+        assert_eq!(get_persons.parameters[0].name, "page");
+        assert_eq!(get_persons.parameters[0].examples, vec!["2"]);
+
+        let login = &amos.operations[3];
+        assert_eq!(login.parameters[0].name, "user");
+        assert_eq!(
+            login.parameters[0].meta_data,
+            Some(ParameterMetaData::HTTP {
+                target: HTTPParameterTarget::FormData
+            })
+        );
+
+        assert_eq!(
+            result.warnings[0].message,
+            "Imported 4 operations from 4 requests"
+        );
+        assert_eq!(
+            result.warnings[1].message,
+            "Skipped the request Patch person: the method PATCH is not supported"
+        );
+    }
+
+    #[test]
+    fn a_document_without_items_is_not_a_collection() {
+        assert!(postman_to_amos(&serde_json::json!({ "swagger": "2.0" })).is_err());
+    }
+}
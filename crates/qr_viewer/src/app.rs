@@ -1,21 +1,52 @@
-use crate::app_state::AppState;
+use crate::app_state::{AppState, WindowLayout};
 use crate::central_navigation;
 use crate::command_sender::command_channel;
 use crate::command_sender::{CommandReceiver, CommandSender, UICommand};
 use crate::fuzzing::PropertySettings;
+use crate::history::{Snapshot, UndoHistory};
 use crate::main_navigation;
+use crate::recovery::{self, Recovery};
 use crate::sequencer;
+use crate::ui::command_palette::CommandPalette;
+use crate::ui::console::Console;
+use crate::ui::filter_bar::FilterBar;
+use crate::ui::generation_playground::GenerationPlayground;
+use crate::ui::latency_chart::{self, LatencyChart};
+use crate::ui::payload_inspector::{self, PayloadInspector};
+use crate::ui::request_preview::RequestPreviewWindow;
+use crate::ui::run_queue::RunQueue;
+use crate::ui::timeline::{self, Timeline};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 use qr_explore::amos::TranslationResult;
 use qr_explore::amos::AMOS;
+use qr_explore::amos_relations::{self, DependencyGraph};
 use qr_explore::behaviours::Behaviour;
-use qr_explore::exploration_settings::StateMutationSettings;
+use qr_explore::config::Config;
+use qr_explore::exploration_settings::{
+    BehaviourSettings, CannedResponse, CredentialSet, DeprecatedOperations, ParameterCombinations,
+    PayloadLimits, PayloadStress, RepeatedSequence, RunTagSettings, ShrinkSettings, ShrinkStrategy,
+    StateMutationSettings, StatusClass, StringGeneration, SutSettings,
+};
 use qr_explore::explore;
-use qr_explore::{amos, sequence};
+use qr_explore::invocation_log::{
+    size_histogram, FilteredInvocations, InvocationFilter, InvocationLog, StatusFilter,
+};
+use qr_explore::manifest::RunManifest;
+use qr_explore::observer::ExplorationObserver;
+use qr_explore::progress::TestProgress;
+use qr_explore::role_matrix::{self, RoleMatrix};
+use qr_explore::state_observer::{ObservationMode, StateObservation, StateObserver};
+use qr_explore::sut_manager::SutManager;
+use qr_explore::{amos, idempotency, notification, sequence};
 use qr_http_resource::http;
 use qr_open_api::open_api::ParseResult;
+use qr_report::comparison::{self, Comparison};
+use qr_report::export;
+use qr_report::report::{self, Report};
 use qr_specification_manager as spec;
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
 use std::thread;
 use tracing::{debug, error, info};
 
@@ -32,9 +63,20 @@ enum ExplorationState {
     Running,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum SutAction {
+    Start,
+    Stop,
+    Reset,
+}
+
+/// Invocations kept in memory before the older ones are spilled to disk
+const INVOCATIONS_IN_MEMORY: usize = 10_000;
+
 pub struct InvocationSpan {
     pub start: usize,
     pub end: usize,
+    pub enter: std::time::Instant,
     pub duration: std::time::Duration,
 }
 
@@ -43,58 +85,633 @@ pub enum DetailsNavigation {
     Logs,
     Details,
     Problems,
+    Lint,
+}
+
+enum TimeLineKind {
+    Start,
+    Progress,
+    End,
 }
 
 struct TimeLineEvent {
+    kind: TimeLineKind,
     instant: std::time::Instant,
     message: String,
 }
 
+const EXAMPLE_KINDS: [&str; 22] = [
+    "Response check",
+    "Response time",
+    "Response payload",
+    "Injection",
+    "Stale cache",
+    "Conditional request",
+    "Idempotent retry",
+    "Auth bypass",
+    "Role matrix",
+    "Latency degradation",
+    "Security findings",
+    "Model divergence",
+    "Not linearizable",
+    "Target divergence",
+    "Contract violation",
+    "Chaos inconsistency",
+    "State mutation",
+    "State identity",
+    "Response equality",
+    "Response inequality",
+    "No example",
+    "Failed",
+];
+
+const LOG_LEVELS: [&str; 3] = ["Info", "Warning", "Error"];
+
+/// The label and the kind of an example in the examples list, the operation
+///  by its alias
+fn example_presentation(
+    example: &explore::ExplorationResult,
+    amos: &AMOS,
+) -> (String, &'static str) {
+    match example {
+        explore::ExplorationResult::ResponseCheck { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Response check")
+        }
+        explore::ExplorationResult::ResponseTime { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Response time")
+        }
+        explore::ExplorationResult::PayloadCheck { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Response payload")
+        }
+        explore::ExplorationResult::Injection { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Injection")
+        }
+        explore::ExplorationResult::StaleCache { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Stale cache")
+        }
+        explore::ExplorationResult::ConditionalRequest { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Conditional request",
+        ),
+        explore::ExplorationResult::IdempotentRetry { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Idempotent retry")
+        }
+        explore::ExplorationResult::AuthBypass { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Auth bypass")
+        }
+        explore::ExplorationResult::RoleMatrix { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Role matrix")
+        }
+        explore::ExplorationResult::LatencyDegradation { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Latency degradation",
+        ),
+        explore::ExplorationResult::SecurityAudit { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Security findings",
+        ),
+        explore::ExplorationResult::ModelDivergence { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Model divergence")
+        }
+        explore::ExplorationResult::NonLinearizable { operation, .. } => {
+            (amos.display_name(operation).to_string(), "Not linearizable")
+        }
+        explore::ExplorationResult::TargetDivergence { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Target divergence",
+        ),
+        explore::ExplorationResult::ContractViolation { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Contract violation",
+        ),
+        explore::ExplorationResult::ChaosInconsistency {
+            query_operation, ..
+        } => (
+            amos.display_name(query_operation).to_string(),
+            "Chaos inconsistency",
+        ),
+        explore::ExplorationResult::NoExampleFound { operation } => (
+            format!("{} - No example found", amos.display_name(operation)),
+            "No example",
+        ),
+        explore::ExplorationResult::Failed { operation, .. } => (
+            format!("{} - Failed", amos.display_name(operation)),
+            "Failed",
+        ),
+        explore::ExplorationResult::StateMutation {
+            query_operation, ..
+        } => (
+            amos.display_name(query_operation).to_string(),
+            "State mutation",
+        ),
+        explore::ExplorationResult::StateIdentity {
+            query_operation, ..
+        } => (
+            amos.display_name(query_operation).to_string(),
+            "State identity",
+        ),
+        explore::ExplorationResult::ResponseEquality { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Response equality",
+        ),
+        explore::ExplorationResult::ResponseInEquality { operation, .. } => (
+            amos.display_name(operation).to_string(),
+            "Response inequality",
+        ),
+    }
+}
+
+/// Edit a credential header, unchecked to send none
+fn auth_ui(ui: &mut egui::Ui, auth: &mut Option<http::HTTPAuth>) {
+    ui.horizontal(|ui| {
+        let mut enabled = auth.is_some();
+        if ui.checkbox(&mut enabled, "").changed() {
+            *auth = enabled.then(|| http::HTTPAuth::bearer(""));
+        }
+        if let Some(auth) = auth {
+            ui.add(egui::TextEdit::singleline(&mut auth.header).desired_width(120.0));
+            ui.add(egui::TextEdit::singleline(&mut auth.value).password(true));
+        }
+    });
+}
+
+/// Edit the constant values by their names, e.g. the pinned parameters
+fn pinned_ui(ui: &mut egui::Ui, values: &mut BTreeMap<String, String>, hint: &str) {
+    let mut entries: Vec<(String, String)> =
+        values.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let mut changed = false;
+    let mut removed = None;
+
+    ui.vertical(|ui| {
+        for (idx, (name, value)) in entries.iter_mut().enumerate() {
+            ui.push_id(idx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("🗑").clicked() {
+                        removed = Some(idx);
+                    }
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(name)
+                                .hint_text(hint)
+                                .desired_width(120.0),
+                        )
+                        .changed();
+                    changed |= ui.text_edit_singleline(value).changed();
+                });
+            });
+        }
+        // Only one entry without a name at a time
+        if ui.button("➕").clicked() && !values.contains_key("") {
+            entries.push((String::new(), String::new()));
+            changed = true;
+        }
+    });
+
+    if let Some(idx) = removed {
+        entries.remove(idx);
+        changed = true;
+    }
+    if changed {
+        *values = entries.into_iter().collect();
+    }
+}
+
+/// Edit the order and the budget of shrinking, an unchecked budget is unbounded
+fn shrinking_ui(ui: &mut egui::Ui, shrinking: &mut ShrinkSettings) {
+    ui.horizontal(|ui| {
+        ui.selectable_value(
+            &mut shrinking.strategy,
+            ShrinkStrategy::LengthFirst,
+            "Length first",
+        );
+        ui.selectable_value(
+            &mut shrinking.strategy,
+            ShrinkStrategy::ValueFirst,
+            "Value first",
+        );
+    });
+    ui.horizontal(|ui| {
+        let mut bounded = shrinking.max_iterations.is_some();
+        if ui.checkbox(&mut bounded, "Max iterations").changed() {
+            shrinking.max_iterations = bounded.then_some(1000);
+        }
+        if let Some(max) = &mut shrinking.max_iterations {
+            ui.add(egui::DragValue::new(max).clamp_range(1..=100_000));
+        }
+    });
+    ui.horizontal(|ui| {
+        let mut bounded = shrinking.max_time_secs.is_some();
+        if ui.checkbox(&mut bounded, "Max time (s)").changed() {
+            shrinking.max_time_secs = bounded.then_some(60);
+        }
+        if let Some(max) = &mut shrinking.max_time_secs {
+            ui.add(egui::DragValue::new(max).clamp_range(1..=3600));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Repeated sequences")
+            .on_hover_text("A stateful SUT may not respond the same to a sequence twice");
+        let is_reset = matches!(shrinking.repeated, RepeatedSequence::ResetFirst { .. });
+        ui.selectable_value(
+            &mut shrinking.repeated,
+            RepeatedSequence::Reuse,
+            "Reuse check",
+        );
+        ui.selectable_value(
+            &mut shrinking.repeated,
+            RepeatedSequence::Reinvoke,
+            "Invoke again",
+        );
+        if ui.selectable_label(is_reset, "Reset first").clicked() && !is_reset {
+            shrinking.repeated = RepeatedSequence::ResetFirst {
+                command: String::new(),
+            };
+        }
+        if let RepeatedSequence::ResetFirst { command } = &mut shrinking.repeated {
+            ui.add(egui::TextEdit::singleline(command).hint_text("Reset command"));
+        }
+    });
+}
+
+/// Edit the settings only the behaviour has
+fn behaviour_settings_ui(ui: &mut egui::Ui, settings: &mut BehaviourSettings) {
+    match settings {
+        BehaviourSettings::None => (),
+        BehaviourSettings::ResponseEquality { ignore_fields } => {
+            ignore_fields_ui(ui, ignore_fields)
+        }
+        BehaviourSettings::ResponseCheck { failing_statuses } => {
+            ui.horizontal(|ui| {
+                ui.label("Fail on");
+                for class in StatusClass::ALL {
+                    let mut fails = failing_statuses.contains(&class);
+                    if ui.checkbox(&mut fails, class.to_string()).changed() {
+                        if fails {
+                            failing_statuses.push(class);
+                        } else {
+                            failing_statuses.retain(|failing| *failing != class);
+                        }
+                    }
+                }
+            });
+        }
+        BehaviourSettings::StateMutation {
+            observe_all_queries,
+        } => {
+            ui.checkbox(observe_all_queries, "Observe all query ops together")
+                .on_hover_text(
+                    "All query operations observe the state before and after the same mutations",
+                );
+        }
+        BehaviourSettings::StateIdentity {
+            observation_delay_ms,
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("Observation delay (ms)")
+                    .on_hover_text("Wait before the state is observed again, for a SUT that is eventually consistent");
+                ui.add(egui::DragValue::new(observation_delay_ms).clamp_range(0..=60_000));
+            });
+        }
+        BehaviourSettings::Differential {
+            reference_url,
+            ignore_fields,
+        } => {
+            ui.horizontal(|ui| {
+                ui.label("Reference").on_hover_text(
+                    "The target the responses are compared with, e.g. the version being replaced",
+                );
+                ui.add(
+                    egui::TextEdit::singleline(reference_url).hint_text("http://localhost:8081"),
+                );
+            });
+            ignore_fields_ui(ui, ignore_fields);
+        }
+        BehaviourSettings::Contract {
+            contract_file,
+            capture,
+        } => {
+            ui.horizontal(|ui| {
+                if ui.button("📂").clicked() {
+                    // A captured contract is written to a new file
+                    let dialog = rfd::FileDialog::new().add_filter("Contract", &["json"]);
+                    let file = if *capture {
+                        dialog.set_file_name("contract.json").save_file()
+                    } else {
+                        dialog.pick_file()
+                    };
+                    if let Some(file) = file {
+                        *contract_file = file.display().to_string();
+                    }
+                }
+                ui.add(egui::TextEdit::singleline(contract_file).hint_text("Contract file"));
+            });
+            ui.checkbox(capture, "Capture the contract").on_hover_text(
+                "Save the statuses and response shapes of a version known to be good, instead of checking them",
+            );
+        }
+        BehaviourSettings::Chaos { faults } => {
+            ui.horizontal(|ui| {
+                ui.label("Delay")
+                    .on_hover_text("The chance a request is delayed");
+                ui.add(egui::Slider::new(&mut faults.delay_probability, 0.0..=1.0));
+                ui.label("up to (ms)");
+                ui.add(egui::DragValue::new(&mut faults.max_delay_ms).clamp_range(0..=60_000));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Drop").on_hover_text(
+                    "The chance the rest of the requests of a sequence are dropped, the query operation is still invoked",
+                );
+                ui.add(egui::Slider::new(&mut faults.drop_probability, 0.0..=1.0));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Repeat")
+                    .on_hover_text("The chance a request is sent twice");
+                ui.add(egui::Slider::new(&mut faults.repeat_probability, 0.0..=1.0));
+            });
+        }
+        BehaviourSettings::Boundary { limits } => {
+            ui.horizontal(|ui| {
+                ui.label("Longest string");
+                ui.add(egui::DragValue::new(&mut limits.max_string_length).speed(16));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Most combinations")
+                    .on_hover_text("The combinations of an operation beyond these are left out");
+                ui.add(egui::DragValue::new(&mut limits.max_combinations).clamp_range(1..=100_000));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Combinations");
+                ui.selectable_value(&mut limits.combinations, ParameterCombinations::All, "All");
+                ui.selectable_value(
+                    &mut limits.combinations,
+                    ParameterCombinations::Pairwise,
+                    "Pairwise",
+                )
+                .on_hover_text("Every pair of values of two parameters, far fewer combinations");
+            });
+        }
+        BehaviourSettings::ConditionalRequest { accept_conflict } => {
+            ui.checkbox(accept_conflict, "Accept 409 Conflict")
+                .on_hover_text("A 409 rejects the update with a stale ETag, as the 412 does");
+        }
+        BehaviourSettings::IdempotentRetry { header } => {
+            ui.horizontal(|ui| {
+                ui.label("Idempotency header")
+                    .on_hover_text("The retry is sent with the key of the request it retries");
+                ui.text_edit_singleline(header);
+            });
+        }
+    }
+}
+
+fn ignore_fields_ui(ui: &mut egui::Ui, ignore_fields: &mut Vec<String>) {
+    ui.horizontal(|ui| {
+        ui.label("Ignored fields")
+            .on_hover_text("The responses are compared without the fields, e.g. timestamps");
+        if ui.button("➕").clicked() {
+            ignore_fields.push(String::new());
+        }
+    });
+    let mut removed = None;
+    for (idx, field) in ignore_fields.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(field).hint_text("Field name"));
+            if ui.button("➖").clicked() {
+                removed = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = removed {
+        ignore_fields.remove(idx);
+    }
+}
+
+/// A payload size in the largest unit it has at least one of
+fn format_size(size: usize) -> String {
+    match size {
+        s if s >= 1024 * 1024 => format!("{:.1} MiB", s as f64 / (1024.0 * 1024.0)),
+        s if s >= 1024 => format!("{:.1} KiB", s as f64 / 1024.0),
+        s => format!("{} B", s),
+    }
+}
+
+/// Bars of the number of invocations in each payload size bin
+fn size_histogram_ui(bins: &[(usize, usize)], ui: &mut egui::Ui) {
+    let Some(max) = bins.iter().map(|(_, count)| *count).max() else {
+        ui.label("No payload sizes");
+        return;
+    };
+
+    let height = 60.0;
+    ui.horizontal(|ui| {
+        for (bound, count) in bins {
+            ui.vertical(|ui| {
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(48.0, height), egui::Sense::hover());
+                let bar_height = height * *count as f32 / max as f32;
+                let bar = egui::Rect::from_min_max(
+                    egui::pos2(rect.left(), rect.bottom() - bar_height),
+                    rect.right_bottom(),
+                );
+                ui.painter()
+                    .rect_filled(bar, 2.0, ui.visuals().selection.bg_fill);
+                response.on_hover_text(format!(
+                    "{} invocations up to {}",
+                    count,
+                    format_size(*bound)
+                ));
+                ui.small(format_size(*bound));
+            });
+        }
+    });
+}
+
+fn manifest_ui(ui: &mut egui::Ui, manifest: &RunManifest) {
+    egui::Grid::new("run_manifest_grid")
+        .num_columns(2)
+        .spacing([40.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Target");
+            ui.label(&manifest.target);
+            ui.end_row();
+
+            ui.label("Behaviour");
+            ui.label(manifest.behaviour.presentation());
+            ui.end_row();
+
+            ui.label("Seed");
+            ui.label(manifest.seed.to_string());
+            ui.end_row();
+
+            ui.label("AMOS hash");
+            ui.monospace(&manifest.amos_hash);
+            ui.end_row();
+
+            ui.label("Tool version");
+            ui.label(&manifest.tool_version);
+            ui.end_row();
+
+            for (name, tag) in [
+                ("SUT version", &manifest.tags.sut_version),
+                ("Git commit", &manifest.tags.git_commit),
+                ("Environment", &manifest.tags.environment),
+            ] {
+                if let Some(tag) = tag {
+                    ui.label(name);
+                    ui.monospace(tag);
+                    ui.end_row();
+                }
+            }
+
+            // Seconds since the UNIX epoch
+            ui.label("Started");
+            ui.label(manifest.started.to_string());
+            ui.end_row();
+
+            ui.label("Duration");
+            match manifest.finished {
+                Some(finished) => {
+                    ui.label(format!("{} s", finished.saturating_sub(manifest.started)))
+                }
+                None => ui.label("Running"),
+            };
+            ui.end_row();
+        });
+
+    ui.collapsing("Settings", |ui| {
+        let settings = serde_json::to_string_pretty(&manifest.settings).unwrap_or_default();
+        ui.monospace(settings);
+    });
+}
+
+fn log_level_label(level: &explore::LogLevel) -> &'static str {
+    match level {
+        explore::LogLevel::Info => "Info",
+        explore::LogLevel::Warning => "Warning",
+        explore::LogLevel::Error => "Error",
+    }
+}
+
 pub struct App {
     // Serialized state of the app
     pub app_state: AppState,
 
     // File parsing
-    pub picked_path: Option<String>,
+    /// Several picked files are the specifications of services merged into
+    ///  one AMOS
+    pub picked_paths: Vec<String>,
     pub parse_state: ParseState,
     pub parse_result: Option<ParseResult>,
+    /// The lint of the parsed specification, of a single file
+    pub lint_report: Option<spec::lint::LintReport>,
 
     // Exploration
     exploration_state: ExplorationState,
+    // Raised to stop the current exploration, replaced for each new one
+    exploration_stop: explore::StopSignal,
+    exploration_pause: explore::PauseSignal,
 
     // AMOS
     pub translation_result: Option<TranslationResult>,
     pub amos: amos::AMOS,
     pub selected_amos_operation: usize,
     pub selected_amos_definition: usize,
+    // The operations checked in the Operations panel, added to behaviours
+    //  and fuzzers at once
+    pub checked_operations: std::collections::BTreeSet<usize>,
+
+    // Operation dependencies, derived from the current AMOS
+    pub dependency_graph: DependencyGraph,
+    pub selected_dependency_operation: Option<usize>,
+    pub relation_hint_input: String,
+    pub relation_hint_error: Option<String>,
 
     exploration_results: Vec<explore::ExplorationResult>,
+    // The manifest of the latest run, or of the opened report
+    run_manifest: Option<RunManifest>,
+    // Why the latest run was aborted, if it was
+    run_aborted: Option<String>,
+    // The tests completed of the running exploration
+    test_progress: Option<TestProgress>,
     // the stream of exploration events (TODO: Create an event type)
     exploration_log_rx: std::sync::mpsc::Receiver<explore::Event>,
     // TODO: add this to the exploration context creation
     exploration_log_tx: std::sync::mpsc::Sender<explore::Event>,
-    pub invocation_results: Vec<amos::InvokeResult>,
-    invocation_durations: Vec<std::time::Duration>,
+    pub invocations: InvocationLog,
+    invocation_filter: InvocationFilter,
+    filtered_invocations: FilteredInvocations,
+    show_size_histogram: bool,
+    show_latency_chart: bool,
+    latency_chart: LatencyChart,
     pub invocation_spans: Vec<InvocationSpan>,
     current_invocation_span_start: usize,
+    current_invocation_span_enter: Option<std::time::Instant>,
     exploration_log: Vec<explore::LogMessage>,
 
     // Generation
-    pub generated_values: Vec<String>,
-    pub generate_string_expression: String,
+    pub generation_playground: GenerationPlayground,
 
     // Timeline
     time_line_events: Vec<TimeLineEvent>,
+    timeline: Timeline,
+    progress_search: String,
 
     pub selected_span: usize,
     pub selected_result: usize,
 
     pub selected_details_navigation: DetailsNavigation,
     pub selected_details_sequence_operation: usize,
+    /// Set when the selected invocation is navigated to from another panel
+    pub scroll_to_selected_invocation: bool,
+    pub payload_inspector: PayloadInspector,
+    request_preview: RequestPreviewWindow,
 
     // Exploration navigation
     selected_exploration_result: usize,
 
+    // Filters of the list panels
+    pub example_filter: FilterBar,
+    pub log_filter: FilterBar,
+    pub operation_filter: FilterBar,
+
+    // Settings dialog, the appearance is (re-)applied at the start of the next frame
+    pub show_settings: bool,
+    pub apply_appearance: bool,
+    // The window layout of the last session is restored in the first frame
+    restore_window: bool,
+
+    // Comparison of the reports of two runs, each a report file or directory
+    pub show_comparison: bool,
+    pub show_console: bool,
+    pub console: Console,
+    pub comparison_baseline: Option<String>,
+    pub comparison_current: Option<String>,
+    pub comparison: Option<Result<Comparison, String>>,
+
+    pub command_palette: CommandPalette,
+
+    // Behaviours queued to run after each other, or several at once
+    pub show_run_queue: bool,
+    pub run_queue: RunQueue,
+
+    // An ongoing discovery of the target API
+    discovery_rx: Option<std::sync::mpsc::Receiver<spec::Result<TranslationResult>>>,
+
+    // The SUT started from the toolbar, and its ongoing start, stop or reset
+    sut_manager: std::sync::Arc<std::sync::Mutex<SutManager>>,
+    sut_rx: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+
+    // Undo of the edits of the app state and the AMOS
+    history: UndoHistory,
+    // If the pointer is held or a widget has focus, the edit is not yet done
+    editing: bool,
+
+    // Autosave of the session, and the recovery offered when the last session
+    //  did not shut down cleanly
+    last_autosave: std::time::Instant,
+    pending_recovery: Option<Recovery>,
+
     // Commands that will be run at the end of the frame
     pub command_sender: CommandSender,
     command_receiver: CommandReceiver,
@@ -146,40 +763,129 @@ impl App {
 
         let (command_sender, command_receiver) = command_channel();
 
-        Self {
-            picked_path: None,
+        let pending_recovery = recovery::start_session();
+
+        let dependency_graph = amos_relations::operation_dependency_graph(&amos.operations);
+
+        // Restore the working context of the last session, the selections are
+        //  kept within what was loaded
+        let session = &app_state.session;
+        let exploration_results = match &session.last_results_path {
+            Some(path) => report::read_results(path).unwrap_or_else(|e| {
+                error!("Could not load the results of the last run {}: {}", path, e);
+                vec![]
+            }),
+            None => vec![],
+        };
+        let last_index = |len: usize| len.saturating_sub(1);
+        let selected_amos_operation = session
+            .selected_amos_operation
+            .min(last_index(amos.operations.len()));
+        let selected_amos_definition = session
+            .selected_amos_definition
+            .min(last_index(amos.definitions.len()));
+        let selected_dependency_operation = session
+            .selected_dependency_operation
+            .filter(|idx| *idx < amos.operations.len());
+        let selected_exploration_result = session
+            .selected_exploration_result
+            .min(last_index(exploration_results.len()));
+        let show_settings = session.show_settings;
+        let show_comparison = session.show_comparison;
+        let show_size_histogram = session.show_size_histogram;
+        let show_latency_chart = session.show_latency_chart;
+
+        let mut app = Self {
+            picked_paths: vec![],
             parse_state: ParseState::Waiting,
             parse_result: None,
+            lint_report: None,
             amos,
             translation_result: None,
-            selected_amos_operation: 0,
-            selected_amos_definition: 0,
+            selected_amos_operation,
+            checked_operations: Default::default(),
+            selected_amos_definition,
+            dependency_graph,
+            selected_dependency_operation,
+            relation_hint_input: String::new(),
+            relation_hint_error: None,
             exploration_state: ExplorationState::Idle,
-            exploration_results: vec![],
+            exploration_stop: explore::StopSignal::default(),
+            exploration_pause: explore::PauseSignal::default(),
+            exploration_results,
+            run_manifest: None,
+            run_aborted: None,
+            test_progress: None,
             exploration_log_rx,
             exploration_log_tx,
             exploration_log: vec![],
-            invocation_results: vec![],
-            invocation_durations: vec![],
+            invocations: InvocationLog::new(
+                std::path::Path::new("./data/invocations.log"),
+                INVOCATIONS_IN_MEMORY,
+            ),
+            invocation_filter: InvocationFilter::default(),
+            filtered_invocations: FilteredInvocations::new(),
+            show_size_histogram,
+            show_latency_chart,
+            latency_chart: LatencyChart::new(),
             invocation_spans: vec![],
             current_invocation_span_start: 0,
+            current_invocation_span_enter: None,
             selected_result: 0,
             selected_span: 0,
             app_state,
 
             time_line_events: vec![],
+            timeline: Timeline::new(),
+            progress_search: String::new(),
 
             selected_details_navigation: DetailsNavigation::Details,
             selected_details_sequence_operation: 0,
-
-            selected_exploration_result: 0,
-
-            generated_values: vec![],
-            generate_string_expression: "[a-z]*".to_string(),
+            scroll_to_selected_invocation: false,
+            payload_inspector: PayloadInspector::new(),
+            request_preview: RequestPreviewWindow::new(),
+
+            selected_exploration_result,
+
+            example_filter: FilterBar::new(),
+            log_filter: FilterBar::new(),
+            operation_filter: FilterBar::new(),
+
+            command_palette: CommandPalette::new(),
+            show_run_queue: false,
+            run_queue: RunQueue::new(),
+            discovery_rx: None,
+            sut_manager: std::sync::Arc::new(std::sync::Mutex::new(SutManager::new(
+                SutSettings::new(),
+            ))),
+            sut_rx: None,
+            show_settings,
+            // Apply the persisted appearance and window layout on startup
+            apply_appearance: true,
+            restore_window: true,
+            show_comparison,
+            show_console: false,
+            console: Console::new(),
+            comparison_baseline: None,
+            comparison_current: None,
+            comparison: None,
+
+            generation_playground: GenerationPlayground::new(),
+
+            history: UndoHistory::new(),
+
+            last_autosave: std::time::Instant::now(),
+            pending_recovery,
+
+            editing: false,
 
             command_sender,
             command_receiver,
-        }
+        };
+        // The state to undo the first edit to
+        app.record_history();
+
+        app
     }
 
     fn create_target_from_settings(&self) -> explore::Target {
@@ -188,29 +894,43 @@ impl App {
                 base_url: self.app_state.target.base_url.clone(),
                 port: self.app_state.target.port.parse().unwrap(),
                 protocol: http::Protocol::HTTP,
+                base_path: self.app_state.target.base_path.clone(),
+                paths: self.app_state.target.paths.clone(),
+                pinning: self.app_state.target.pinning.clone(),
+                providers: self.app_state.target.providers.clone(),
             },
         }
+        .resolve_base_path(&self.amos)
     }
 
     fn process_exploration_events(&mut self, _ctx: &egui::Context) {
         while let Ok(event) = self.exploration_log_rx.try_recv() {
+            if let Some(message) = notification::notification_message(&event) {
+                notification::notify(&self.app_state.notifications, message);
+            }
+
             //info!("{}", format!("Received event {:?}", event));
             match event {
                 explore::Event::CurrentQueryOperation { .. } => {}
-                explore::Event::InvocationSpanEnter { .. } => {}
+                explore::Event::InvocationSpanEnter { enter } => {
+                    self.current_invocation_span_enter = Some(enter);
+                }
                 explore::Event::InvocationSpanExit { duration } => {
                     let start: usize = self.current_invocation_span_start;
 
                     // Check that this span actually contains invocations
                     // TODO: Display operations that where disscarded as we might want
                     //  to improve on the generation of those
-                    if start != self.invocation_results.len() {
-                        let end = start + (self.invocation_results.len() - 1 - start);
-                        self.current_invocation_span_start = self.invocation_results.len();
+                    if start != self.invocations.len() {
+                        let end = start + (self.invocations.len() - 1 - start);
+                        self.current_invocation_span_start = self.invocations.len();
                         debug!(start, end, "Span");
                         self.invocation_spans.push(InvocationSpan {
                             start,
                             end,
+                            enter: self
+                                .current_invocation_span_enter
+                                .unwrap_or_else(|| std::time::Instant::now() - duration),
                             duration,
                         })
                     }
@@ -218,31 +938,47 @@ impl App {
                 explore::Event::Invocation {
                     result: r,
                     sut_invocation_duration: d,
-                } => {
-                    self.invocation_durations.push(d);
-                    self.invocation_results.push(r)
-                }
+                } => self.invocations.push(r, d),
                 explore::Event::Log { message } => self.exploration_log.push(message),
 
                 explore::Event::Control { event } => {
                     match event {
                         explore::ControlEvent::Finished => {
-                            self.exploration_state = ExplorationState::Idle
+                            self.exploration_state = ExplorationState::Idle;
+                            self.test_progress = None;
+                            if let Some(manifest) = &mut self.run_manifest {
+                                manifest.finish();
+                            }
                         }
                         // We do not want to react to started, since the UI already
                         //  know that we started (button clicked) and can react to that
                         explore::ControlEvent::Started => (),
+                        explore::ControlEvent::Aborted { reason } => {
+                            self.run_aborted = Some(reason)
+                        }
                     }
                 }
 
                 explore::Event::Result { result } => {
                     self.exploration_results.push(result);
                 }
+                explore::Event::TestCompleted { completed, total } => {
+                    if let Some(progress) = &mut self.test_progress {
+                        progress.record(completed, total, std::time::Instant::now());
+                    }
+                }
+
+                explore::Event::Manifest { manifest } => {
+                    self.run_manifest = Some(*manifest);
+                    self.run_aborted = None;
+                    self.test_progress = Some(TestProgress::new(std::time::Instant::now()));
+                }
 
                 // Time line
                 explore::Event::TimeLineStart { enter, message } => {
                     debug!("{:?}:{:?}", enter, message);
                     self.time_line_events.push(TimeLineEvent {
+                        kind: TimeLineKind::Start,
                         instant: enter,
                         message,
                     });
@@ -250,6 +986,7 @@ impl App {
                 explore::Event::TimeLineProgress { time, message } => {
                     debug!("{:?}:{:?}", time, message);
                     self.time_line_events.push(TimeLineEvent {
+                        kind: TimeLineKind::Progress,
                         instant: time,
                         message,
                     });
@@ -257,6 +994,7 @@ impl App {
                 explore::Event::TimeLineEnd { time, message } => {
                     debug!("{:?}:{:?}", time, message);
                     self.time_line_events.push(TimeLineEvent {
+                        kind: TimeLineKind::End,
                         instant: time,
                         message,
                     });
@@ -297,6 +1035,308 @@ impl App {
                 ui.label("Port");
                 ui.text_edit_singleline(&mut self.app_state.target.port);
                 ui.end_row();
+
+                ui.label("Base path");
+                let target = &mut self.app_state.target;
+                let mut path = target.base_path.clone().unwrap_or_default();
+                let hint = self.amos.base_path.as_deref().unwrap_or("/");
+                if ui
+                    .add(egui::TextEdit::singleline(&mut path).hint_text(hint))
+                    .on_hover_text(
+                        "The operations are relative to it, the base path of the specification unless set",
+                    )
+                    .changed()
+                {
+                    target.base_path = (!path.is_empty()).then_some(path);
+                }
+                ui.end_row();
+
+                ui.label("Paths");
+                let paths = &mut self.app_state.target.paths;
+                ui.horizontal(|ui| {
+                    for (value, label) in [
+                        (http::TrailingSlash::Keep, "Keep trailing slash"),
+                        (http::TrailingSlash::Add, "Add"),
+                        (http::TrailingSlash::Remove, "Remove"),
+                    ] {
+                        ui.selectable_value(&mut paths.trailing_slash, value, label);
+                    }
+                    ui.checkbox(&mut paths.collapse_slashes, "Collapse '//'");
+                    ui.checkbox(&mut paths.lowercase, "Lowercase")
+                        .on_hover_text("The paths of the specification, the values keep their case");
+                });
+                ui.end_row();
+
+                let pinning = &mut self.app_state.target.pinning;
+                ui.label("Pinned parameters")
+                    .on_hover_text("Sent with the value instead of a generated one");
+                pinned_ui(ui, &mut pinning.parameters, "tenantId");
+                ui.end_row();
+
+                ui.label("Static headers").on_hover_text("Sent with every request");
+                pinned_ui(ui, &mut pinning.headers, "X-Feature");
+                ui.end_row();
+
+                let providers = &mut self.app_state.target.providers;
+                ui.label("Provided values").on_hover_text(
+                    "Computed when a request is sent: now(), uuid(), counter() or hmac(<secret>, body)",
+                );
+                pinned_ui(ui, &mut providers.parameters, "timestamp");
+                ui.end_row();
+
+                ui.label("Provided headers");
+                pinned_ui(ui, &mut providers.headers, "X-Signature");
+                ui.end_row();
+
+                let health = &mut self.app_state.exploration_settings.health;
+                ui.label("Health path");
+                let mut path = health.path.clone().unwrap_or_default();
+                if ui
+                    .add(egui::TextEdit::singleline(&mut path).hint_text("/health"))
+                    .on_hover_text(
+                        "Probed during the explorations, they pause while it is unhealthy",
+                    )
+                    .changed()
+                {
+                    health.path = (!path.is_empty()).then_some(path);
+                }
+                ui.end_row();
+
+                // The build of the SUT the runs are tagged with
+                let tags = &mut self.app_state.exploration_settings.tags;
+                for (name, tag, hint) in [
+                    ("Version path", &mut tags.version_path, "/version"),
+                    ("Git commit", &mut tags.git_commit, ""),
+                    ("Environment", &mut tags.environment, "staging"),
+                ] {
+                    ui.label(name);
+                    let mut value = tag.clone().unwrap_or_default();
+                    if ui
+                        .add(egui::TextEdit::singleline(&mut value).hint_text(hint))
+                        .on_hover_text("Recorded in the manifests of the runs")
+                        .changed()
+                    {
+                        *tag = (!value.is_empty()).then_some(value);
+                    }
+                    ui.end_row();
+                }
+
+                let observation = &mut self.app_state.exploration_settings.state_observation;
+
+                ui.label("State observer");
+                let mut command = match observation {
+                    Some(StateObservation {
+                        observer: StateObserver::Command { command },
+                        ..
+                    }) => command.clone(),
+                    None => String::new(),
+                };
+                let command_edit = ui.add(
+                    egui::TextEdit::singleline(&mut command)
+                        .hint_text("Command printing the state as JSON"),
+                );
+                if command_edit.changed() {
+                    let mode = observation
+                        .as_ref()
+                        .map_or(ObservationMode::InsteadOfQuery, |o| o.mode);
+                    *observation = (!command.is_empty()).then_some(StateObservation {
+                        observer: StateObserver::Command { command },
+                        mode,
+                    });
+                }
+                ui.end_row();
+
+                if let Some(observation) = observation {
+                    ui.label("Observation");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut observation.mode,
+                            ObservationMode::InsteadOfQuery,
+                            "Instead of query",
+                        );
+                        ui.selectable_value(
+                            &mut observation.mode,
+                            ObservationMode::AlongsideQuery,
+                            "Alongside query",
+                        );
+                    });
+                    ui.end_row();
+                }
+
+                let sla = &mut self.app_state.exploration_settings.response_time;
+                ui.label("Max response time (ms)");
+                ui.add(egui::DragValue::new(&mut sla.global_ms).speed(10))
+                    .on_hover_text(
+                        "Checked by the response time property, unless set for the operation",
+                    );
+                ui.end_row();
+
+                let payload = &mut self.app_state.exploration_settings.payload;
+                ui.label("Max payload size (bytes)");
+                ui.add(egui::DragValue::new(&mut payload.max_size).speed(1024));
+                ui.end_row();
+
+                ui.label("Compress payloads from (bytes)");
+                ui.horizontal(|ui| {
+                    let mut compress = payload.compress_from.is_some();
+                    if ui.checkbox(&mut compress, "").changed() {
+                        payload.compress_from =
+                            compress.then_some(PayloadLimits::DEFAULT_COMPRESS_FROM);
+                    }
+                    if let Some(from) = &mut payload.compress_from {
+                        ui.add(egui::DragValue::new(from).speed(64));
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Stress with large payloads").on_hover_text(
+                    "Very large strings and arrays in the bodies, a 413 is accepted but a 500 or a timeout fails",
+                );
+                ui.horizontal(|ui| {
+                    let mut stress = payload.stress.is_some();
+                    if ui.checkbox(&mut stress, "").changed() {
+                        payload.stress = stress.then(PayloadStress::new);
+                    }
+                    if let Some(stress) = &mut payload.stress {
+                        ui.label("Strings");
+                        ui.add(egui::DragValue::new(&mut stress.max_string_length).speed(1024));
+                        ui.label("Arrays");
+                        ui.add(egui::DragValue::new(&mut stress.max_array_length).speed(16));
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Shrinking")
+                    .on_hover_text("Out of budget, the simplest failing sequence so far is kept");
+                ui.vertical(|ui| {
+                    shrinking_ui(
+                        ui,
+                        &mut self.app_state.exploration_settings.state_mutation.shrinking,
+                    )
+                });
+                ui.end_row();
+
+                let correlation = &mut self.app_state.exploration_settings.correlation;
+                ui.label("Correlation header")
+                    .on_hover_text("Sends the id of each sequence, e.g. 'X-Request-Id'");
+                ui.horizontal(|ui| {
+                    let mut enabled = correlation.header.is_some();
+                    if ui.checkbox(&mut enabled, "").changed() {
+                        correlation.header = enabled.then(|| "X-Request-Id".to_string());
+                    }
+                    if let Some(header) = &mut correlation.header {
+                        ui.text_edit_singleline(header);
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Idempotency header")
+                    .on_hover_text("Sends a key of each POST, e.g. 'Idempotency-Key'");
+                ui.horizontal(|ui| {
+                    let mut enabled = correlation.idempotency_header.is_some();
+                    if ui.checkbox(&mut enabled, "").changed() {
+                        correlation.idempotency_header =
+                            enabled.then(|| idempotency::DEFAULT_HEADER.to_string());
+                    }
+                    if let Some(header) = &mut correlation.idempotency_header {
+                        ui.text_edit_singleline(header);
+                    }
+                });
+                ui.end_row();
+
+                let mock = &mut self.app_state.exploration_settings.mock;
+                ui.label("Dry run CRUD")
+                    .on_hover_text("Dry runs emulate collections, created by a POST to them");
+                ui.checkbox(&mut mock.crud, "");
+                ui.end_row();
+
+                ui.label("Dry run responses")
+                    .on_hover_text("The response of every dry run invocation of the operation");
+                ui.vertical(|ui| {
+                    let mut removed = None;
+                    for (idx, (operation, canned)) in mock.responses.iter_mut().enumerate() {
+                        ui.push_id(idx, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui.button("🗑").clicked() {
+                                    removed = Some(operation.clone());
+                                }
+                                ui.label(operation);
+                                ui.add(
+                                    egui::DragValue::new(&mut canned.status).clamp_range(100..=599),
+                                );
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut canned.body)
+                                        .hint_text("Body")
+                                        .desired_width(160.0),
+                                );
+                            });
+                        });
+                    }
+                    if let Some(operation) = removed {
+                        mock.responses.remove(&operation);
+                    }
+
+                    let unmocked: Vec<&str> = self
+                        .amos
+                        .operations
+                        .iter()
+                        .map(|op| op.info.name.as_str())
+                        .filter(|name| !mock.responses.contains_key(*name))
+                        .collect();
+                    ui.menu_button("➕", |ui| {
+                        for name in unmocked {
+                            if ui.button(name).clicked() {
+                                mock.responses.insert(
+                                    name.to_string(),
+                                    CannedResponse {
+                                        status: 500,
+                                        body: String::new(),
+                                        headers: Default::default(),
+                                    },
+                                );
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+                ui.end_row();
+
+                let auth = &mut self.app_state.exploration_settings.auth;
+                ui.label("Credentials")
+                    .on_hover_text("Sent with every request, not saved");
+                auth_ui(ui, &mut auth.credentials);
+                ui.end_row();
+
+                ui.label("Downgraded credentials")
+                    .on_hover_text("Replayed by the auth bypass property");
+                auth_ui(ui, &mut auth.downgraded);
+                ui.end_row();
+
+                ui.label("Roles")
+                    .on_hover_text("Invoked as each by the role matrix property");
+                ui.vertical(|ui| {
+                    let mut removed = None;
+                    for (idx, role) in auth.roles.iter_mut().enumerate() {
+                        ui.push_id(idx, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui.button("🗑").clicked() {
+                                    removed = Some(idx);
+                                }
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut role.name).desired_width(80.0),
+                                );
+                                auth_ui(ui, &mut role.auth);
+                            });
+                        });
+                    }
+                    if let Some(idx) = removed {
+                        auth.roles.remove(idx);
+                    }
+                    if ui.button("➕").clicked() {
+                        auth.roles.push(CredentialSet::new("anonymous", None));
+                    }
+                });
+                ui.end_row();
             });
     }
 
@@ -307,15 +1347,86 @@ impl App {
             .striped(true)
             .show(ui, |ui| {
                 ui.label("Number of invocations");
-                ui.label(self.invocation_results.len().to_string());
+                ui.label(self.invocations.len().to_string());
                 ui.end_row();
 
                 ui.label("Number of sequences");
                 ui.label(self.invocation_spans.len().to_string());
                 ui.end_row();
+
+                if let Some(reason) = &self.run_aborted {
+                    ui.label("Aborted");
+                    ui.colored_label(egui::Color32::RED, reason);
+                    ui.end_row();
+                }
             });
 
         ui.collapsing("Coverage", |ui| ui.label("Foo"));
+
+        if let Some(manifest) = &self.run_manifest {
+            ui.collapsing("Run manifest", |ui| manifest_ui(ui, manifest));
+        }
+    }
+
+    /// The first and last response of the operation in the sequence with the
+    ///  correlation id, if the invocations are still in the log
+    fn compared_responses(
+        &self,
+        correlation_id: &str,
+        operation: &str,
+    ) -> Option<(String, String)> {
+        let invocations: Vec<usize> = self
+            .invocations
+            .summaries()
+            .iter()
+            .enumerate()
+            .filter(|(_, summary)| {
+                summary.correlation_id.as_deref() == Some(correlation_id)
+                    && summary.operation == operation
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        match invocations.as_slice() {
+            [first, .., last] => Some((
+                self.invocations.get(*first)?.result,
+                self.invocations.get(*last)?.result,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Select the invocation, and the sequence it was made in
+    pub fn select_invocation(&mut self, idx: usize) {
+        self.selected_result = idx;
+
+        let span = self
+            .invocation_spans
+            .iter()
+            .position(|span| (span.start..=span.end).contains(&idx));
+        if let Some(span_idx) = span {
+            self.selected_span = span_idx;
+            self.selected_details_sequence_operation = self
+                .invocations
+                .summaries()
+                .get(idx)
+                .and_then(|summary| summary.position)
+                .map_or(idx - self.invocation_spans[span_idx].start, |position| {
+                    position.index
+                });
+        }
+    }
+
+    /// Show the invocation in the panel, from the panel of another view
+    pub fn navigate_to_invocation(
+        &mut self,
+        idx: usize,
+        navigation: central_navigation::Navigations,
+    ) {
+        self.select_invocation(idx);
+        self.scroll_to_selected_invocation =
+            navigation == central_navigation::Navigations::Invocations;
+        self.app_state.central_navigation.selected = navigation;
     }
 
     fn exploration_sequences_ui(&mut self, ui: &mut egui::Ui) {
@@ -329,16 +1440,22 @@ impl App {
                     .show(ui, |ui| {
                         for idx in 0..self.invocation_spans.len() {
                             let current_span = &self.invocation_spans[idx];
-                            let span_results =
-                                &self.invocation_results[current_span.start..current_span.end + 1];
+                            let span_results = &self.invocations.summaries()
+                                [current_span.start..current_span.end + 1];
                             let span_text = span_results
                                 .iter()
-                                .map(|result| "[".to_string() + &result.operation.name + "]")
+                                .map(|summary| "[".to_string() + &summary.operation + "]")
                                 .collect::<Vec<String>>()
                                 .join(" -> ");
 
+                            // The number of the sequence in the run, as in the correlation ids
+                            let test_case = span_results
+                                .first()
+                                .and_then(|summary| summary.position)
+                                .map_or(idx as u64 + 1, |position| position.test_case);
+
                             ui.push_id(idx, |ui| {
-                                ui.label((idx + 1).to_string());
+                                ui.label(test_case.to_string());
                                 ui.label(format!("{} ms", current_span.duration.as_millis()));
                                 ui.selectable_value(&mut self.selected_span, idx, span_text)
                             });
@@ -350,36 +1467,112 @@ impl App {
 
     fn exploration_invocations_ui(&mut self, ui: &mut egui::Ui) {
         egui::TopBottomPanel::top("Bar").show(ui.ctx(), |ui| {
-            if ui.button("🗑").clicked() {
-                self.invocation_results.clear();
-                self.invocation_spans.clear();
-                self.current_invocation_span_start = 0;
-                self.selected_span = 0;
-                self.selected_result = 0;
-            };
-        });
+            ui.horizontal(|ui| {
+                if ui.button("🗑").clicked() {
+                    self.invocations.clear();
+                    self.filtered_invocations = FilteredInvocations::new();
+                    self.invocation_spans.clear();
+                    self.current_invocation_span_start = 0;
+                    self.selected_span = 0;
+                    self.selected_result = 0;
+                };
 
-        egui::CentralPanel::default().show(ui.ctx(), |ui| {
-            let table = TableBuilder::new(ui)
-                .striped(true)
-                .resizable(true)
-                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                .column(Column::auto())
-                .column(Column::auto())
+                ui.separator();
+                ui.label("🔍");
+                ui.text_edit_singleline(&mut self.invocation_filter.text);
+
+                ui.separator();
+                for status in StatusFilter::ALL {
+                    let text = status.to_string();
+                    ui.selectable_value(&mut self.invocation_filter.status, status, text);
+                }
+
+                ui.separator();
+                ui.toggle_value(&mut self.show_size_histogram, "📊")
+                    .on_hover_text("Payload sizes");
+                ui.toggle_value(&mut self.show_latency_chart, "📈")
+                    .on_hover_text("Latencies over the run");
+
+                ui.separator();
+                if ui
+                    .button("💾 CSV")
+                    .on_hover_text("Export invocations, statistics and results")
+                    .clicked()
+                {
+                    if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                        let dir_path = dir.display().to_string();
+                        let message = match export::write_csv_export(
+                            &dir_path,
+                            self.invocations.summaries(),
+                            &self.exploration_results,
+                        ) {
+                            Ok(_) => explore::LogMessage {
+                                level: explore::LogLevel::Info,
+                                message: format!("Exported CSV to {}", dir_path),
+                            },
+                            Err(e) => explore::LogMessage {
+                                level: explore::LogLevel::Error,
+                                message: format!("Failed to export CSV: {}", e),
+                            },
+                        };
+                        self.exploration_log.push(message);
+                    }
+                }
+            });
+
+            if self.show_size_histogram {
+                size_histogram_ui(&size_histogram(self.invocations.summaries()), ui);
+            }
+            if self.show_latency_chart {
+                let (operations, points) = self.latency_points();
+                self.latency_chart.show(ui, &operations, &points);
+            }
+        });
+
+        egui::CentralPanel::default().show(ui.ctx(), |ui| {
+            let mut table = TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::auto())
+                .column(Column::auto())
+                .column(Column::auto())
+                .column(Column::auto())
                 .column(Column::auto())
                 .column(Column::auto())
                 .column(Column::remainder().clip(true));
 
+            if std::mem::take(&mut self.scroll_to_selected_invocation) {
+                let row = self
+                    .filtered_invocations
+                    .update(&self.invocation_filter, self.invocations.summaries())
+                    .iter()
+                    .position(|idx| *idx == self.selected_result);
+                if let Some(row) = row {
+                    table = table.scroll_to_row(row, Some(egui::Align::Center));
+                }
+            }
+
+            let mut clicked = None;
             table
                 .header(20.0, |mut header| {
                     header.col(|ui| {
                         ui.strong("Row");
                     });
 
+                    header.col(|ui| {
+                        ui.strong("Sequence")
+                            .on_hover_text("The number of the sequence, and the position in it");
+                    });
+
                     header.col(|ui| {
                         ui.strong("Duration");
                     });
 
+                    header.col(|ui| {
+                        ui.strong("Size");
+                    });
+
                     header.col(|ui| {
                         ui.strong("Operation");
                     });
@@ -392,56 +1585,89 @@ impl App {
                         ui.strong("URL");
                     });
                 })
-                .body(|mut body| {
-                    for idx in 0..self.invocation_results.len() {
-                        body.row(18.0, |mut row| {
+                .body(|body| {
+                    // Only the visible rows are laid out, the summaries are enough
+                    //  to list the invocations without loading the spilled results
+                    let summaries = self.invocations.summaries();
+                    let indices = self
+                        .filtered_invocations
+                        .update(&self.invocation_filter, summaries);
+                    body.rows(18.0, indices.len(), |row_idx, mut row| {
+                        let idx = indices[row_idx];
+                        let summary = &summaries[idx];
+
+                        row.col(|ui| {
+                            ui.label((idx + 1).to_string());
+                        });
+
+                        row.col(|ui| {
+                            match summary.position {
+                                Some(position) => ui.label(format!(
+                                    "{}.{}",
+                                    position.test_case,
+                                    position.index + 1
+                                )),
+                                None => ui.label("-"),
+                            };
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{} ms", summary.duration.as_millis()));
+                        });
+
+                        row.col(|ui| {
+                            match (summary.size, &summary.content_encoding) {
+                                (Some(size), Some(encoding)) => {
+                                    ui.label(format!("{} ({})", format_size(size), encoding))
+                                }
+                                (Some(size), None) => ui.label(format_size(size)),
+                                (None, _) => ui.label("-"),
+                            };
+                        });
+
+                        row.col(|ui| {
+                            if ui
+                                .selectable_value(
+                                    &mut self.selected_result,
+                                    idx,
+                                    self.amos.display_name(&summary.operation).to_string(),
+                                )
+                                .clicked()
+                            {
+                                clicked = Some(idx);
+                            };
+                        });
+
+                        if let (Some(status), Some(url)) = (&summary.status, &summary.url) {
                             row.col(|ui| {
-                                ui.label((idx + 1).to_string());
+                                ui.label(status.to_string());
                             });
 
                             row.col(|ui| {
-                                ui.label(format!(
-                                    "{} ms",
-                                    self.invocation_durations[idx].as_millis()
-                                ));
+                                ui.label(url);
                             });
-
+                        } else if let Some(error) = summary.transport_error {
+                            // No response, not a status of the SUT
                             row.col(|ui| {
-                                if ui
-                                    .selectable_value(
-                                        &mut self.selected_result,
-                                        idx,
-                                        self.invocation_results[idx].operation.name.clone(),
-                                    )
-                                    .clicked()
-                                {
-                                    // TODO: set the selected span
-                                    //  However, we need data in the result to
-                                    //  identify the span. This might fit in the
-                                    //  exploration result.
-                                };
+                                ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", error));
                             });
 
-                            if let Some(meta) = &self.invocation_results[idx].meta_data {
-                                match meta {
-                                    amos::ResultMetaData::HTTP { url, status } => {
-                                        row.col(|ui| {
-                                            ui.label(status.to_string());
-                                        });
-
-                                        row.col(|ui| {
-                                            ui.label(url);
-                                        });
-                                    }
-                                }
-                            }
-                        })
-                    }
+                            row.col(|ui| {
+                                ui.label("-");
+                            });
+                        }
+                    })
                 });
+
+            if let Some(idx) = clicked {
+                self.select_invocation(idx);
+            }
         });
     }
 
     fn exploration_examples_ui(&mut self, ui: &mut egui::Ui) {
+        let mut navigate_to = None;
+        let mut open_in_sequencer = false;
         egui::SidePanel::right("details_example")
             .resizable(true)
             .default_width(400.0)
@@ -455,9 +1681,102 @@ impl App {
                     if !self.exploration_results.is_empty() {
                         let selected = &self.exploration_results[self.selected_exploration_result];
 
+                        if let Some(truncation) = selected.shrink_truncation() {
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!(
+                                    "Shrinking stopped after {} iterations at {}, the sequence may not be minimal",
+                                    truncation.iterations, truncation.limit
+                                ),
+                            );
+                        }
+
+                        if selected.sequence().is_some()
+                            && ui
+                                .button("Open in sequencer")
+                                .on_hover_text("Vary the sequence, its values as the templates")
+                                .clicked()
+                        {
+                            open_in_sequencer = true;
+                        }
+
+                        if let Some(id) = selected.correlation_id() {
+                            ui.label(format!("Correlation id: {}", id));
+
+                            // The invocations of the example, if still in the log
+                            let invocation =
+                                self.invocations.summaries().iter().position(|summary| {
+                                    summary.correlation_id.as_deref() == Some(id)
+                                });
+                            if let Some(idx) = invocation {
+                                ui.horizontal(|ui| {
+                                    if ui.button("⬇ Sequence").clicked() {
+                                        navigate_to =
+                                            Some((idx, central_navigation::Navigations::Sequences));
+                                    }
+                                    if ui.button("Invocations").clicked() {
+                                        navigate_to = Some((
+                                            idx,
+                                            central_navigation::Navigations::Invocations,
+                                        ));
+                                    }
+                                });
+                            }
+
+                            // The responses the property compared
+                            let compared = match selected {
+                                explore::ExplorationResult::StateMutation {
+                                    query_operation,
+                                    ..
+                                }
+                                | explore::ExplorationResult::StateIdentity {
+                                    query_operation,
+                                    ..
+                                } => Some((query_operation, ("Before", "After"))),
+                                explore::ExplorationResult::ResponseEquality {
+                                    operation, ..
+                                }
+                                | explore::ExplorationResult::ResponseInEquality {
+                                    operation,
+                                    ..
+                                } => Some((operation, ("First response", "Second response"))),
+                                _ => None,
+                            };
+                            if let Some((operation, labels)) = compared {
+                                if let Some((first, last)) = self.compared_responses(id, operation)
+                                {
+                                    ui.collapsing("Responses", |ui| {
+                                        payload_inspector::payload_diff(
+                                            ui,
+                                            (labels.0, &first),
+                                            (labels.1, &last),
+                                        );
+                                    });
+                                }
+                            }
+                        }
+
                         match selected {
                             explore::ExplorationResult::ResponseCheck {
                                 minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::ResponseTime {
+                                minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::PayloadCheck {
+                                minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::Injection {
+                                minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::StaleCache {
+                                minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::ConditionalRequest {
+                                minimal_sequence, ..
+                            }
+                            | explore::ExplorationResult::IdempotentRetry {
+                                minimal_sequence, ..
                             } => {
                                 if let Some(seq) = minimal_sequence {
                                     for op in seq {
@@ -471,9 +1790,228 @@ impl App {
                             explore::ExplorationResult::NoExampleFound { .. } => {
                                 ui.label("No example found");
                             }
+                            explore::ExplorationResult::Failed { reason, .. } => {
+                                ui.colored_label(ui.visuals().error_fg_color, reason);
+                            }
+                            explore::ExplorationResult::LatencyDegradation {
+                                sequence,
+                                trend,
+                                ..
+                            } => {
+                                ui.label("Mean response time (ms) and error rate over the run");
+                                for (latency, error_rate) in
+                                    trend.latency_ms.iter().zip(&trend.error_rate)
+                                {
+                                    ui.label(format!(
+                                        "{:.1} ms - {:.0}% errors",
+                                        latency,
+                                        error_rate * 100.0
+                                    ));
+                                }
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("Repeated sequence", |ui| {
+                                        for op in seq {
+                                            ui.label(op.name.clone());
+                                        }
+                                    });
+                                }
+                            }
+                            explore::ExplorationResult::RoleMatrix { .. } => {
+                                // Every row is shown, to compare the operations
+                                let matrix = RoleMatrix::from_results(&self.exploration_results);
+                                egui::Grid::new("role_matrix_grid")
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label("Operation");
+                                        for role in &matrix.roles {
+                                            ui.strong(role);
+                                        }
+                                        ui.end_row();
+
+                                        for (operation, outcomes) in &matrix.rows {
+                                            if role_matrix::roles_differ(outcomes) {
+                                                ui.strong(operation)
+                                                    .on_hover_text("The roles differ");
+                                            } else {
+                                                ui.label(operation);
+                                            }
+                                            for outcome in outcomes {
+                                                ui.label(outcome.to_string());
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            }
+                            explore::ExplorationResult::AuthBypass { role, sequence, .. } => {
+                                ui.label(format!("Succeeded with {}", role));
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("Replayed sequence", |ui| {
+                                        for op in seq {
+                                            ui.label(format!("{:?}", op.name));
+                                            for p in &op.parameters {
+                                                ui.label(format!("{} - {:?}", p.name, p.value));
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                            explore::ExplorationResult::ChaosInconsistency {
+                                inconsistency,
+                                faults,
+                                sequence,
+                                ..
+                            } => {
+                                ui.label(inconsistency.to_string());
+                                ui.collapsing("Faults", |ui| {
+                                    for fault in faults {
+                                        ui.label(fault.to_string());
+                                    }
+                                });
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("Sequence", |ui| {
+                                        for op in seq {
+                                            ui.label(format!("{:?}", op.name));
+                                            for p in &op.parameters {
+                                                ui.label(format!("{} - {:?}", p.name, p.value));
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                            explore::ExplorationResult::ContractViolation {
+                                violations,
+                                sequence,
+                                ..
+                            } => {
+                                for violation in violations {
+                                    ui.label(violation.to_string());
+                                }
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("Sequence", |ui| {
+                                        for op in seq {
+                                            ui.label(format!("{:?}", op.name));
+                                            for p in &op.parameters {
+                                                ui.label(format!("{} - {:?}", p.name, p.value));
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                            explore::ExplorationResult::TargetDivergence {
+                                difference,
+                                sequence,
+                                ..
+                            } => {
+                                ui.label(difference.to_string());
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("Sequence", |ui| {
+                                        for op in seq {
+                                            ui.label(format!("{:?}", op.name));
+                                            for p in &op.parameters {
+                                                ui.label(format!("{} - {:?}", p.name, p.value));
+                                            }
+                                        }
+                                    });
+                                }
+                            }
+                            explore::ExplorationResult::ModelDivergence {
+                                divergence,
+                                minimal_sequence,
+                                ..
+                            } => {
+                                if let Some(divergence) = divergence {
+                                    ui.label(divergence.to_string());
+                                    ui.label(format!("Rule: {}", divergence.rule));
+                                }
+
+                                if let Some(seq) = minimal_sequence {
+                                    for op in seq {
+                                        ui.label(format!("{:?}", op.name));
+                                        for p in &op.parameters {
+                                            ui.label(format!("{} - {:?}", p.name, p.value));
+                                        }
+                                    }
+                                }
+                            }
+                            explore::ExplorationResult::NonLinearizable {
+                                windows,
+                                processes,
+                                ..
+                            } => {
+                                for window in windows {
+                                    ui.label(format!(
+                                        "{:.1} - {:.1} ms",
+                                        window.start_ms, window.end_ms
+                                    ));
+                                    egui::Grid::new(("window", window.start_ms.to_bits()))
+                                        .striped(true)
+                                        .show(ui, |ui| {
+                                            // The operations the window starts
+                                            //  from are shown dimmed before it
+                                            let earlier =
+                                                window.earlier.iter().map(|op| (op, true));
+                                            let in_window =
+                                                window.operations.iter().map(|op| (op, false));
+                                            for (op, is_earlier) in earlier.chain(in_window) {
+                                                let label = |ui: &mut egui::Ui, text: String| {
+                                                    if is_earlier {
+                                                        ui.weak(text);
+                                                    } else {
+                                                        ui.label(text);
+                                                    }
+                                                };
+                                                label(ui, format!("p{}", op.process));
+                                                label(ui, op.operation.clone());
+                                                label(
+                                                    ui,
+                                                    format!(
+                                                        "{:.1} - {:.1} ms",
+                                                        op.call_ms, op.return_ms
+                                                    ),
+                                                );
+                                                label(ui, op.observed.clone());
+                                                ui.end_row();
+                                            }
+                                        });
+                                }
+
+                                ui.collapsing("Processes", |ui| {
+                                    for (process, seq) in processes.iter().enumerate() {
+                                        let names: Vec<&str> =
+                                            seq.iter().map(|op| op.name.as_str()).collect();
+                                        ui.label(format!("p{}: {}", process, names.join(" -> ")));
+                                    }
+                                });
+                            }
+                            explore::ExplorationResult::SecurityAudit {
+                                findings,
+                                sequence,
+                                ..
+                            } => {
+                                for finding in findings {
+                                    ui.label(finding.to_string());
+                                }
+
+                                if let Some(seq) = sequence {
+                                    ui.collapsing("First sequence with a finding", |ui| {
+                                        for op in seq {
+                                            ui.label(format!("{:?}", op.name));
+                                            for p in &op.parameters {
+                                                ui.label(format!("{} - {:?}", p.name, p.value));
+                                            }
+                                        }
+                                    });
+                                }
+                            }
                             explore::ExplorationResult::StateMutation {
                                 query_operation,
                                 minimal_sequence,
+                                ..
                             } => {
                                 ui.label("Query operation:");
                                 ui.label(query_operation.clone());
@@ -502,6 +2040,7 @@ impl App {
                             explore::ExplorationResult::StateIdentity {
                                 query_operation,
                                 minimal_sequence,
+                                ..
                             } => {
                                 ui.label("Query operation:");
                                 ui.label(query_operation.clone());
@@ -588,61 +2127,33 @@ impl App {
                 });
             });
 
+        if let Some((idx, navigation)) = navigate_to {
+            self.navigate_to_invocation(idx, navigation);
+        }
+        if open_in_sequencer {
+            self.open_example_in_sequencer();
+        }
+
         if !self.exploration_results.is_empty() {
             // TODO: Does this make sense? The exploration result can contain
             //  any results so they should be in thier own buckets or?
             ui.collapsing("Examples", |ui| {
+                self.example_filter.show(ui, &EXAMPLE_KINDS, true);
+                // The examples with an invocation of the selected status
+                let with_status = self
+                    .example_filter
+                    .with_status(self.invocations.summaries(), |summary| {
+                        summary.correlation_id.as_deref()
+                    });
+
                 for (idx, example) in self.exploration_results.iter().enumerate() {
-                    match example {
-                        explore::ExplorationResult::ResponseCheck { operation, .. } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                operation.clone().to_string(),
-                            );
-                        }
-                        explore::ExplorationResult::NoExampleFound { operation } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                format!("{} - No example found", operation.clone()),
-                            );
-                        }
-                        explore::ExplorationResult::StateMutation {
-                            query_operation, ..
-                        } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                query_operation.clone(),
-                            );
-                        }
-                        explore::ExplorationResult::StateIdentity {
-                            query_operation, ..
-                        } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                query_operation.clone(),
-                            );
-                        }
-                        explore::ExplorationResult::ResponseEquality { operation, .. } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                operation.clone(),
-                            );
-                        }
-                        explore::ExplorationResult::ResponseInEquality { operation, .. } => {
-                            ui.selectable_value(
-                                &mut self.selected_exploration_result,
-                                idx,
-                                operation.clone(),
-                            );
-                        }
+                    let (label, kind) = example_presentation(example, &self.amos);
+                    let has_status = with_status.as_ref().is_none_or(|ids| {
+                        example.correlation_id().is_some_and(|id| ids.contains(id))
+                    });
+                    if has_status && self.example_filter.matches(&label, kind) {
+                        ui.selectable_value(&mut self.selected_exploration_result, idx, label);
                     }
-                    // ui.label(format!("{:#?}", example));
-                    // ui.separator();
                 }
             });
         } else {
@@ -651,73 +2162,183 @@ impl App {
     }
 
     fn exploration_progress_ui(&mut self, ui: &mut egui::Ui) {
+        egui::TopBottomPanel::top("progress_search").show(ui.ctx(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("🔍");
+                ui.text_edit_singleline(&mut self.progress_search);
+            });
+        });
+
         egui::CentralPanel::default().show(ui.ctx(), |ui| {
-            let table = TableBuilder::new(ui)
-                .striped(true)
-                .resizable(true)
-                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                .column(Column::auto())
-                .column(Column::auto())
-                .column(Column::auto())
-                .column(Column::remainder().clip(true));
-            table
-                .header(20.0, |mut header| {
-                    header.col(|ui| {
-                        ui.strong("Row");
-                    });
+            let (bars, markers) = self.timeline_items();
+            if bars.is_empty() && markers.is_empty() {
+                ui.label("No exploration to show");
+                return;
+            }
 
-                    header.col(|ui| {
-                        ui.strong("Rel.");
-                    });
+            self.timeline.show(
+                ui,
+                &["Phases", "Sequences", "Invocations"],
+                &bars,
+                &markers,
+                &self.progress_search,
+            );
+        });
+    }
 
-                    header.col(|ui| {
-                        ui.strong("Abs.");
-                    });
+    /// The phases, sequences and invocations of the run, as bars of the
+    ///  timeline. The invocations of a sequence are laid out one after the
+    ///  other from its start.
+    fn timeline_items(&self) -> (Vec<timeline::Bar>, Vec<timeline::Marker>) {
+        let Some(origin) = self.run_origin() else {
+            return (vec![], vec![]);
+        };
+        let seconds =
+            |instant: std::time::Instant| instant.saturating_duration_since(origin).as_secs_f64();
+        let now = seconds(std::time::Instant::now());
+
+        let mut bars = vec![];
+        let mut markers = vec![];
+
+        let mut open_phases = vec![];
+        for event in &self.time_line_events {
+            match event.kind {
+                TimeLineKind::Start => open_phases.push(event),
+                TimeLineKind::Progress => markers.push(timeline::Marker {
+                    lane: 0,
+                    time: seconds(event.instant),
+                    label: event.message.clone(),
+                }),
+                TimeLineKind::End => {
+                    if let Some(start) = open_phases.pop() {
+                        bars.push(timeline::Bar {
+                            lane: 0,
+                            start: seconds(start.instant),
+                            end: seconds(event.instant),
+                            label: start.message.clone(),
+                            color: egui::Color32::from_rgb(90, 120, 180),
+                        });
+                    }
+                }
+            }
+        }
+        // A phase still running lasts until now
+        for start in open_phases {
+            bars.push(timeline::Bar {
+                lane: 0,
+                start: seconds(start.instant),
+                end: now,
+                label: start.message.clone(),
+                color: egui::Color32::from_rgb(90, 120, 180),
+            });
+        }
 
-                    header.col(|ui| {
-                        ui.strong("Event");
-                    });
-                })
-                .body(|mut body| {
-                    for idx in 0..self.time_line_events.len() {
-                        let current_event = &self.time_line_events[idx];
-
-                        let relative_duration = if idx > 0 {
-                            current_event
-                                .instant
-                                .duration_since(self.time_line_events[idx - 1].instant)
-                        } else {
-                            current_event.instant.duration_since(current_event.instant)
-                        };
+        let summaries = self.invocations.summaries();
+        for (idx, span) in self.invocation_spans.iter().enumerate() {
+            let start = seconds(span.enter);
+            let test_case = summaries
+                .get(span.start)
+                .and_then(|summary| summary.position)
+                .map_or(idx as u64 + 1, |position| position.test_case);
+            bars.push(timeline::Bar {
+                lane: 1,
+                start,
+                end: start + span.duration.as_secs_f64(),
+                label: format!("Sequence {}", test_case),
+                color: egui::Color32::from_rgb(120, 120, 120),
+            });
+        }
+        for (idx, invocation_start) in self.invocation_starts(origin) {
+            let summary = &summaries[idx];
+            let end = invocation_start + summary.duration.as_secs_f64();
+            let color = if summary.transport_error.is_some() {
+                egui::Color32::YELLOW
+            } else if summary.success {
+                egui::Color32::from_rgb(80, 160, 80)
+            } else {
+                egui::Color32::from_rgb(200, 70, 70)
+            };
+            bars.push(timeline::Bar {
+                lane: 2,
+                start: invocation_start,
+                end,
+                label: self.amos.display_name(&summary.operation).to_string(),
+                color,
+            });
+        }
 
-                        let duration = current_event
-                            .instant
-                            .duration_since(self.time_line_events[0].instant);
+        (bars, markers)
+    }
 
-                        body.row(18.0, |mut row| {
-                            row.col(|ui| {
-                                ui.label((idx + 1).to_string());
-                            });
+    /// The operations invoked, sorted, and the latency of each invocation
+    ///  against the operation's index
+    fn latency_points(&self) -> (Vec<String>, Vec<latency_chart::Point>) {
+        let summaries = self.invocations.summaries();
+        let operations: Vec<String> = summaries
+            .iter()
+            .map(|summary| summary.operation.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let Some(origin) = self.run_origin() else {
+            return (operations, vec![]);
+        };
 
-                            row.col(|ui| {
-                                ui.label(format!("{} ms", relative_duration.as_millis(),));
-                            });
+        let points = self
+            .invocation_starts(origin)
+            .into_iter()
+            .map(|(idx, time)| {
+                let summary = &summaries[idx];
+                let class = if StatusFilter::TransportError.matches(summary) {
+                    latency_chart::StatusClass::TransportError
+                } else if StatusFilter::ServerError.matches(summary) {
+                    latency_chart::StatusClass::ServerError
+                } else if StatusFilter::Successful.matches(summary)
+                    || StatusFilter::Redirection.matches(summary)
+                {
+                    latency_chart::StatusClass::Successful
+                } else {
+                    latency_chart::StatusClass::ClientError
+                };
+                latency_chart::Point {
+                    series: operations
+                        .binary_search(&summary.operation)
+                        .unwrap_or_default(),
+                    time,
+                    latency_ms: summary.duration.as_secs_f64() * 1000.0,
+                    class,
+                }
+            })
+            .collect();
 
-                            row.col(|ui| {
-                                ui.label(format!("{} s", duration.as_secs(),));
-                            });
+        (operations, points)
+    }
 
-                            row.col(|ui| {
-                                ui.selectable_value(
-                                    &mut self.selected_result,
-                                    idx,
-                                    current_event.message.clone(),
-                                );
-                            });
-                        })
-                    }
-                })
-        });
+    /// The first instant recorded of the run
+    fn run_origin(&self) -> Option<std::time::Instant> {
+        self.time_line_events
+            .first()
+            .map(|event| event.instant)
+            .into_iter()
+            .chain(self.invocation_spans.first().map(|span| span.enter))
+            .min()
+    }
+
+    /// The index of each invocation with its start, in seconds since the
+    ///  origin. Within a sequence the invocations are taken to follow each
+    ///  other from the start of the sequence.
+    fn invocation_starts(&self, origin: std::time::Instant) -> Vec<(usize, f64)> {
+        let summaries = self.invocations.summaries();
+        let mut starts = vec![];
+        for span in &self.invocation_spans {
+            let mut start = span.enter.saturating_duration_since(origin).as_secs_f64();
+            let in_span = summaries.get(span.start..=span.end).unwrap_or_default();
+            for (offset, summary) in in_span.iter().enumerate() {
+                starts.push((span.start + offset, start));
+                start += summary.duration.as_secs_f64();
+            }
+        }
+        starts
     }
 
     fn exploration_sequencer_ui(&mut self, ui: &mut egui::Ui) {
@@ -748,20 +2369,8 @@ impl App {
                                 parameters,
                             } => {
                                 let current = &self.amos.operations[*amos_op_id];
-
-                                // The params of the op should use the schemas defined
-                                //  in the sequence UI
-                                let mut new_op = current.clone();
-                                let mut templated_params = vec![];
-                                for (idx, new_param) in new_op.parameters.iter_mut().enumerate() {
-                                    new_param.schema = amos::Schema::StringRegex {
-                                        regex: parameters[idx].template.clone(),
-                                    };
-                                    templated_params.push(new_param.clone());
-                                }
-
-                                new_op.parameters = templated_params;
-                                ops_to_invoke.push(new_op);
+                                ops_to_invoke
+                                    .push(sequencer::templated_operation(current, parameters));
                             }
                             sequencer::SequenceItem::Behaviour { .. } => (),
                             sequencer::SequenceItem::Fuzzer { .. } => (),
@@ -774,6 +2383,7 @@ impl App {
                 self.exploration_state = ExplorationState::Running;
 
                 let target = self.create_target_from_settings();
+                let auth = self.app_state.exploration_settings.auth.clone();
                 let is_dry_run =
                     if let Some(selected) = self.app_state.sequencer.selected_sequence() {
                         selected.is_dry_run
@@ -789,17 +2399,13 @@ impl App {
 
                 thread::spawn(move || {
                     let context = &explore::ExplorationContext {
-                        http_client: reqwest::blocking::Client::new(),
                         http_send_fn,
-                        target,
-                        query_operation: None,
-                        tx: Some(channel),
-                        number_of_tests: 1,
-                        min_length: 1,
-                        max_length: 1,
+                        observer: Some(Box::new(channel)),
+                        auth,
+                        ..explore::ExplorationContext::new(target)
                     };
 
-                    sequence::sequence_invoke(context, ops, explore::invoke, ops_to_invoke);
+                    sequence::sequence_invoke(context, ops, &explore::invoke, ops_to_invoke);
                 });
                 self.exploration_log.push(explore::LogMessage {
                     level: explore::LogLevel::Info,
@@ -841,18 +2447,7 @@ impl App {
                     }
 
                     main_navigation::MainNavigation::Operations => {
-                        let current_idx = self.selected_amos_operation;
-                        let current_op = &self.amos.operations[current_idx];
-
-                        let new_item =
-                            sequencer::SequenceItem::new(current_idx, &current_op.parameters);
-
-                        self.app_state.sequencer.push_item_to_selected(new_item);
-
-                        self.exploration_log.push(explore::LogMessage {
-                            level: explore::LogLevel::Info,
-                            message: format!("Added {} to sequence", current_op.info.name.clone()),
-                        });
+                        self.add_operation_to_sequence();
                     }
                     main_navigation::MainNavigation::Exploration => {
                         let behaviour = self.app_state.behaviour.selected.clone();
@@ -862,14 +2457,49 @@ impl App {
                             message: format!("Added {} to sequence", behaviour.presentation()),
                         });
 
+                        // The state is observed by the query operations
+                        //  suggested for the operations that can change it
+                        let query_operation_ids = match behaviour {
+                            Behaviour::StateMutation
+                            | Behaviour::StateIdentity
+                            | Behaviour::Linearizability
+                            | Behaviour::Chaos
+                            | Behaviour::StaleCache => self
+                                .amos
+                                .suggested_query_operations(&self.amos.mutating_operations()),
+                            // The updates are replayed, of the resources they read
+                            Behaviour::ConditionalRequest => self.amos.resource_updates(),
+                            // The POSTs are retried
+                            Behaviour::IdempotentRetry => self.amos.creations(),
+                            _ => vec![],
+                        };
+
                         let parameters = StateMutationSettings {
                             number_of_tests: 100,
                             min_length: 1,
                             max_length: 2,
-                            query_operation_ids: vec![],
+                            query_operation_ids,
                             selected_query_operation: None,
+                            coverage_guided: false,
+                            follow_links: false,
+                            harvest_responses: false,
+                            learn_precedence: false,
+                            mutate_sequences: false,
+                            honor_cache_headers: false,
+                            corpus_dir: None,
+                            script_file: None,
+                            deprecated: DeprecatedOperations::Include,
+                            strings: StringGeneration::Ascii,
+                            seed: None,
+                            shrinking: self
+                                .app_state
+                                .exploration_settings
+                                .state_mutation
+                                .shrinking
+                                .clone(),
                         };
                         let new_item = sequencer::SequenceItem::Behaviour {
+                            settings: BehaviourSettings::new(&behaviour),
                             behaviour,
                             parameters,
                         };
@@ -903,6 +2533,7 @@ impl App {
             if let Some(selected_idx) = self.app_state.sequencer.selected_sequence_id {
                 for idx in 0..self.app_state.sequencer.sequences[selected_idx].items.len() {
                     ui.vertical(|ui| {
+                        let mut preview = None;
                         ui.horizontal_top(|ui| {
                             let target = self.create_target_from_settings();
                             let selected = &mut self.app_state.sequencer.sequences[selected_idx];
@@ -915,33 +2546,29 @@ impl App {
                                     ui.selectable_value(
                                         &mut selected.selected,
                                         idx,
-                                        current.info.name.clone(),
+                                        current.info.display_name(),
                                     );
 
+                                    if ui.button("🔍").on_hover_text("Preview request").clicked()
+                                    {
+                                        preview = Some(sequencer::templated_operation(
+                                            current, parameters,
+                                        ));
+                                    }
+
                                     if ui.button("▶").clicked() {
                                         let channel = self.exploration_log_tx.clone();
                                         let ops = self.amos.operations.clone();
 
-                                        // The params of the op should use the schemas defined
-                                        //  in the sequence UI
-                                        let mut new_op = current.clone();
-                                        let mut templated_params = vec![];
-                                        for (idx, new_param) in
-                                            new_op.parameters.iter_mut().enumerate()
-                                        {
-                                            new_param.schema = amos::Schema::StringRegex {
-                                                regex: parameters[idx].template.clone(),
-                                            };
-                                            templated_params.push(new_param.clone());
-                                        }
-
-                                        new_op.parameters = templated_params;
+                                        let new_op =
+                                            sequencer::templated_operation(current, parameters);
 
                                         // Set exploration state to let the UI react accordingly
                                         // TODO: Make a more representable state
                                         self.exploration_state = ExplorationState::Running;
 
                                         let target = self.create_target_from_settings();
+                                        let auth = self.app_state.exploration_settings.auth.clone();
                                         let is_dry_run = if let Some(selected) =
                                             self.app_state.sequencer.selected_sequence()
                                         {
@@ -959,20 +2586,16 @@ impl App {
 
                                         thread::spawn(move || {
                                             let context = &explore::ExplorationContext {
-                                                http_client: reqwest::blocking::Client::new(),
                                                 http_send_fn,
-                                                target,
-                                                query_operation: None,
-                                                tx: Some(channel),
-                                                number_of_tests: 1,
-                                                min_length: 1,
-                                                max_length: 1,
-                                            };
+                                                observer: Some(Box::new(channel)),
+                                                auth,
+                                                ..explore::ExplorationContext::new(target)
+                                                                                        };
 
                                             sequence::sequence_invoke(
                                                 context,
                                                 ops,
-                                                invoke,
+                                                &invoke,
                                                 vec![new_op],
                                             );
                                         });
@@ -991,51 +2614,65 @@ impl App {
                                     if ui.button("▶").clicked() {
                                         // Set exploration state to let the UI react accordingly
                                         self.exploration_state = ExplorationState::Running;
+                                        self.exploration_stop = explore::StopSignal::default();
+                                        self.exploration_pause = explore::PauseSignal::default();
 
                                         qr_explore::spawn_exploration(
                                             &target,
                                             selected.is_dry_run,
                                             &self.amos,
-                                            self.exploration_log_tx.clone(),
-                                            self.amos.operations.clone(),
-                                            &Behaviour::Property,
-                                            // TODO: there is probably a difference between general parameters and behaviour specific ones
-                                            &StateMutationSettings {
-                                                number_of_tests: 100,
-                                                min_length: 1,
-                                                max_length: 1,
-                                                query_operation_ids: settings.operations.clone(),
-                                                selected_query_operation: None,
-                                            }, //&self.invocation_results,
+                                            Box::new(self.exploration_log_tx.clone()),
+                                            &property.behaviour(),
+                                            &sequencer::fuzzer_settings(
+                                                &self.app_state.exploration_settings,
+                                                property,
+                                                settings,
+                                            ),
+                                            explore::ExplorationControl::new().with_signals(
+                                                self.exploration_stop.clone(),
+                                                self.exploration_pause.clone(),
+                                            ),
                                         );
-                                    }
+                                                                            }
                                 }
                                 sequencer::SequenceItem::Behaviour {
                                     behaviour,
                                     parameters,
+                                    settings,
                                 } => {
                                     ui.label(behaviour.presentation());
 
                                     if ui.button("▶").clicked() {
                                         // Set exploration state to let the UI react accordingly
                                         self.exploration_state = ExplorationState::Running;
+                                        self.exploration_stop = explore::StopSignal::default();
+                                        self.exploration_pause = explore::PauseSignal::default();
 
                                         qr_explore::spawn_exploration(
                                             &target,
                                             selected.is_dry_run,
                                             &self.amos,
-                                            self.exploration_log_tx.clone(),
-                                            self.amos.operations.clone(),
+                                            Box::new(self.exploration_log_tx.clone()),
                                             behaviour,
-                                            // TODO: there is probably a difference between general parameters and behaviour specific ones
-                                            parameters,
-                                            //&self.invocation_results,
+                                            &sequencer::behaviour_settings(
+                                                &self.app_state.exploration_settings,
+                                                parameters,
+                                                settings,
+                                            ),
+                                            explore::ExplorationControl::new().with_signals(
+                                                self.exploration_stop.clone(),
+                                                self.exploration_pause.clone(),
+                                            ),
                                         );
                                     };
                                 }
                             }
                         });
 
+                        if let Some(operation) = preview {
+                            self.preview_requests(vec![operation]);
+                        }
+
                         ui.label("Parameters");
 
                         let selected =
@@ -1055,16 +2692,25 @@ impl App {
                                 ui.horizontal_top(|ui| {
                                     ui.label("Selected Operations");
 
-                                    if ui.add_enabled(true, egui::Button::new("➕")).clicked() {
-                                        let selected_id = self.selected_amos_operation;
-
-                                        settings.operations.push(selected_id);
+                                    if ui
+                                        .add_enabled(true, egui::Button::new("➕"))
+                                        .on_hover_text(
+                                            "Add the checked operations, or else the selected one",
+                                        )
+                                        .clicked()
+                                    {
+                                        let ids = operations_to_add(
+                                            &self.checked_operations,
+                                            self.selected_amos_operation,
+                                        );
+                                        let added =
+                                            sequencer::add_operation_ids(&mut settings.operations, &ids);
 
                                         self.exploration_log.push(explore::LogMessage {
                                             level: explore::LogLevel::Info,
                                             message: format!(
-                                                "Add operation to property: {}",
-                                                selected_id,
+                                                "Added {} operations to the property",
+                                                added,
                                             ),
                                         });
                                     }
@@ -1074,6 +2720,8 @@ impl App {
                                     }
                                 });
 
+                                ui.checkbox(&mut settings.follow_links, "Follow response links");
+
                                 for op in &settings.operations {
                                     let _name = self.amos.operations[*op].info.name.clone();
                                     // ui.collapsing(name, |ui| {
@@ -1092,7 +2740,10 @@ impl App {
                             sequencer::SequenceItem::Behaviour {
                                 behaviour: _,
                                 parameters,
+                                settings,
                             } => {
+                                behaviour_settings_ui(ui, settings);
+
                                 ui.add(
                                     egui::Slider::new(&mut parameters.number_of_tests, 1..=1000)
                                         .text("Number of tests/property"),
@@ -1108,18 +2759,123 @@ impl App {
                                         .text("Largest number of operations"),
                                 );
 
+                                ui.checkbox(&mut parameters.coverage_guided, "Coverage guided");
+                                ui.checkbox(
+                                    &mut parameters.harvest_responses,
+                                    "Sample values seen in responses",
+                                );
+                                ui.checkbox(
+                                    &mut parameters.learn_precedence,
+                                    "Learn the order of operations",
+                                );
+                                ui.checkbox(
+                                    &mut parameters.mutate_sequences,
+                                    "Mutate earlier sequences",
+                                )
+                                .on_hover_text(
+                                    "Swap, duplicate and drop operations of the sequences generated before, or reuse their values",
+                                );
+                                ui.checkbox(
+                                    &mut parameters.honor_cache_headers,
+                                    "Honor cache headers",
+                                )
+                                .on_hover_text(
+                                    "Cache the responses within each sequence, and revalidate them with their ETag",
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.label("Corpus");
+                                    ui.label(
+                                        parameters.corpus_dir.as_deref().unwrap_or("-"),
+                                    )
+                                    .on_hover_text(
+                                        "The sequences with new statuses are saved to it, and replayed first in later explorations",
+                                    );
+                                    if ui.button("📁").clicked() {
+                                        if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                            parameters.corpus_dir =
+                                                Some(dir.display().to_string());
+                                        }
+                                    }
+                                    if parameters.corpus_dir.is_some() && ui.button("🗑").clicked()
+                                    {
+                                        parameters.corpus_dir = None;
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Script");
+                                    ui.label(
+                                        parameters.script_file.as_deref().unwrap_or("-"),
+                                    )
+                                    .on_hover_text(
+                                        "The values of the invoked sequences are recorded to it, to replay them as they were sent",
+                                    );
+                                    if ui.button("📁").clicked() {
+                                        if let Some(file) = rfd::FileDialog::new()
+                                            .add_filter("Script", &["jsonl"])
+                                            .save_file()
+                                        {
+                                            parameters.script_file =
+                                                Some(file.display().to_string());
+                                        }
+                                    }
+                                    if parameters.script_file.is_some() && ui.button("🗑").clicked()
+                                    {
+                                        parameters.script_file = None;
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Deprecated")
+                                        .on_hover_text("The operations the specification marks as deprecated");
+                                    for (value, label) in [
+                                        (DeprecatedOperations::Include, "Include"),
+                                        (DeprecatedOperations::Exclude, "Exclude"),
+                                        (DeprecatedOperations::Only, "Only"),
+                                    ] {
+                                        ui.selectable_value(&mut parameters.deprecated, value, label);
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Strings");
+                                    ui.selectable_value(
+                                        &mut parameters.strings,
+                                        StringGeneration::Ascii,
+                                        "ASCII",
+                                    );
+                                    ui.selectable_value(
+                                        &mut parameters.strings,
+                                        StringGeneration::Unicode,
+                                        "Unicode",
+                                    )
+                                    .on_hover_text(
+                                        "Emoji, CJK, right-to-left and combining characters and null bytes, for the strings without a generator",
+                                    );
+                                });
+                                ui.label("Shrinking");
+                                shrinking_ui(ui, &mut parameters.shrinking);
+
                                 ui.horizontal_top(|ui| {
                                     ui.label("Select Query ops");
 
-                                    if ui.add_enabled(true, egui::Button::new("➕")).clicked() {
-                                        let selected_id = self.selected_amos_operation;
-
-                                        parameters.query_operation_ids.push(selected_id);
+                                    if ui
+                                        .add_enabled(true, egui::Button::new("➕"))
+                                        .on_hover_text(
+                                            "Add the checked operations, or else the selected one",
+                                        )
+                                        .clicked()
+                                    {
+                                        let ids = operations_to_add(
+                                            &self.checked_operations,
+                                            self.selected_amos_operation,
+                                        );
+                                        let added = sequencer::add_operation_ids(
+                                            &mut parameters.query_operation_ids,
+                                            &ids,
+                                        );
                                         self.exploration_log.push(explore::LogMessage {
                                             level: explore::LogLevel::Info,
                                             message: format!(
-                                                "Add operation to behaviour {}",
-                                                selected_id,
+                                                "Added {} query operations to the behaviour",
+                                                added,
                                             ),
                                         });
                                     }
@@ -1127,6 +2883,34 @@ impl App {
                                     if ui.button("➖").clicked() {
                                         parameters.remove_selected_query_operation();
                                     }
+
+                                    if ui
+                                        .button("Auto-select")
+                                        .on_hover_text(
+                                            "The GET operations responding with what the selected \
+                                             operation, or else any mutating operation, changes",
+                                        )
+                                        .clicked()
+                                    {
+                                        let selected = self.selected_amos_operation;
+                                        let mutating = self.amos.mutating_operations();
+                                        let mutating = if mutating.contains(&selected) {
+                                            vec![selected]
+                                        } else {
+                                            mutating
+                                        };
+
+                                        parameters.query_operation_ids =
+                                            self.amos.suggested_query_operations(&mutating);
+                                        parameters.selected_query_operation = None;
+                                        self.exploration_log.push(explore::LogMessage {
+                                            level: explore::LogLevel::Info,
+                                            message: format!(
+                                                "Selected {} query operations",
+                                                parameters.query_operation_ids.len(),
+                                            ),
+                                        });
+                                    }
                                 });
 
                                 for (id, query_op) in
@@ -1175,10 +2959,15 @@ impl App {
             central_navigation::Navigations::Sequencer => {
                 self.exploration_sequencer_ui(ui);
             }
+            central_navigation::Navigations::Dependencies => {
+                crate::ui::dependency_graph(self, ui);
+            }
         };
     }
 
-    pub fn log_table(&self, ui: &mut egui::Ui) {
+    pub fn log_table(&mut self, ui: &mut egui::Ui) {
+        self.log_filter.show(ui, &LOG_LEVELS, false);
+
         let messages = &self.exploration_log;
 
         let table = TableBuilder::new(ui)
@@ -1206,6 +2995,11 @@ impl App {
             })
             .body(|mut body| {
                 for (log_index, message) in messages.iter().enumerate() {
+                    let level = log_level_label(&message.level);
+                    if !self.log_filter.matches(&message.message, level) {
+                        continue;
+                    }
+
                     body.row(18.0, |mut row| {
                         row.col(|ui| {
                             ui.label((log_index + 1).to_string());
@@ -1213,13 +3007,7 @@ impl App {
 
                         // Level
                         row.col(|ui| {
-                            let label = match message.level {
-                                explore::LogLevel::Info => "Info".to_owned(),
-                                explore::LogLevel::Warning => "Warning".to_owned(),
-                                explore::LogLevel::Error => "Error".to_owned(),
-                            };
-
-                            ui.label(label);
+                            ui.label(level);
                         });
 
                         // Message
@@ -1231,21 +3019,867 @@ impl App {
             })
     }
 
-    fn save(&self) {
+    /// Make the translated AMOS the working AMOS
+    fn use_translation_result(&mut self, translation_result: TranslationResult) {
+        let path = self.app_state.current_amos_path.clone().unwrap();
+        let amos_path = std::path::Path::new(&path);
+
+        translation_result.amos.save(amos_path);
+
+        // TODO: Do we want a clone and preserve the 'original' in the result or not?
+        self.amos = translation_result.amos.clone();
+        self.dependency_graph = amos_relations::operation_dependency_graph(&self.amos.operations);
+        self.selected_dependency_operation = None;
+        self.checked_operations.clear();
+        self.translation_result = Some(translation_result);
+        // Currently, we only support one working AMOS, so clear the workspace
+        // This is not ideal, allow easy change between different AMOSes
+        // The appearance and the recent files are not part of the workspace,
+        //  keep them
+        let appearance = self.app_state.appearance;
+        let recent_files = std::mem::take(&mut self.app_state.recent_files);
+        self.app_state = AppState::new();
+        self.app_state.appearance = appearance;
+        self.app_state.recent_files = recent_files;
+    }
+
+    /// Parse the specifications in the next frame, several files are merged
+    ///  into one AMOS
+    pub fn open_specifications(&mut self, paths: Vec<String>) {
+        if paths.is_empty() {
+            return;
+        }
+        self.picked_paths = paths;
+        self.parse_state = ParseState::Parse;
+    }
+
+    /// Open the specifications dropped on the window, other files are
+    ///  ignored
+    fn open_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        if dropped.is_empty() {
+            return;
+        }
+
+        let (paths, ignored): (Vec<String>, Vec<String>) = dropped
+            .iter()
+            .filter_map(|file| file.path.as_ref())
+            .map(|path| path.display().to_string())
+            .partition(|path| is_specification_file(path));
+        for path in ignored {
+            self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Warning,
+                message: format!("{} is not an OpenAPI specification", path),
+            });
+        }
+        self.open_specifications(paths);
+    }
+
+    /// Discover the API of the target in the background, for targets without
+    ///  a specification
+    pub fn start_discovery(&mut self) {
+        let target = &self.app_state.target;
+        let url = format!("{}{}:{}", target.protocol, target.base_url, target.port);
+
+        let base = match reqwest::Url::parse(&url) {
+            Ok(base) => base,
+            Err(e) => {
+                self.exploration_log.push(explore::LogMessage {
+                    level: explore::LogLevel::Error,
+                    message: format!("Invalid target URL {}: {}", url, e),
+                });
+                return;
+            }
+        };
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: format!("Discover the API at {}", base),
+        });
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.discovery_rx = Some(rx);
+
+        thread::spawn(move || {
+            let result = spec::discovery::discover_from_url(
+                &base,
+                &spec::discovery::DiscoveryOptions::new(),
+            );
+            tx.send(result).ok();
+        });
+    }
+
+    /// Apply a config file shared with the CLI. The filters select the query
+    ///  operations of the current AMOS, the specification files are opened
+    /// Use the AMOS of the requests of a Postman collection
+    pub fn import_postman(&mut self, path: &str) {
+        self.use_import(path, spec::postman::load_postman_file_path(path));
+    }
+
+    /// Use the AMOS of the CoRE resources of a CoAP server
+    pub fn import_link_format(&mut self, path: &str) {
+        self.use_import(path, spec::link_format::load_link_format_file_path(path));
+    }
+
+    fn use_import(&mut self, path: &str, result: spec::Result<TranslationResult>) {
+        match result {
+            Ok(translation_result) => {
+                for warning in &translation_result.warnings {
+                    self.exploration_log.push(explore::LogMessage {
+                        level: explore::LogLevel::Info,
+                        message: warning.message.clone(),
+                    });
+                }
+                self.use_translation_result(translation_result);
+                self.parse_result = None;
+                self.lint_report = None;
+            }
+            Err(e) => self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Error,
+                message: format!("{}: {}", path, e),
+            }),
+        }
+    }
+
+    pub fn export_open_api(&mut self, path: &str) {
+        let document = qr_explore::amos::to_open_api(&self.amos);
+        let contents = serde_json::to_string_pretty(&document).expect("Valid OpenAPI JSON");
+        let message = match std::fs::write(path, contents) {
+            Ok(()) => explore::LogMessage {
+                level: explore::LogLevel::Info,
+                message: format!("Exported the AMOS to {}", path),
+            },
+            Err(e) => explore::LogMessage {
+                level: explore::LogLevel::Error,
+                message: format!("{}: {}", path, e),
+            },
+        };
+        self.exploration_log.push(message);
+    }
+
+    pub fn load_config(&mut self, path: &str) {
+        let config = match Config::read(std::path::Path::new(path)) {
+            Ok(config) => config,
+            Err(e) => {
+                self.exploration_log.push(explore::LogMessage {
+                    level: explore::LogLevel::Error,
+                    message: format!("{}: {}", path, e),
+                });
+                return;
+            }
+        };
+
+        // Validated when the config was read
+        if let Ok(Some((protocol, host, port))) = config.target() {
+            self.app_state.target.protocol = protocol;
+            self.app_state.target.base_url = host;
+            self.app_state.target.port = port.to_string();
+        }
+        if config.target.base_path.is_some() {
+            self.app_state.target.base_path = config.target.base_path.clone();
+        }
+        self.app_state.target.paths = config.target.paths.clone();
+        self.app_state.target.pinning = config.target.pinning.clone();
+        self.app_state.target.providers = config.target.providers.clone();
+        if let Ok(auth) = config.auth() {
+            self.app_state.exploration_settings.auth = auth;
+        }
+        if let Some(behaviour) = config.behaviours().ok().and_then(|b| b.into_iter().next()) {
+            self.app_state.behaviour.selected = behaviour;
+        }
+
+        let generation = &config.generation;
+        let settings = &mut self.app_state.exploration_settings;
+        let state_mutation = &mut settings.state_mutation;
+        state_mutation.number_of_tests = generation.tests.unwrap_or(state_mutation.number_of_tests);
+        state_mutation.min_length = generation.min_length.unwrap_or(state_mutation.min_length);
+        state_mutation.max_length = generation.max_length.unwrap_or(state_mutation.max_length);
+        state_mutation.coverage_guided = generation
+            .coverage_guided
+            .unwrap_or(state_mutation.coverage_guided);
+        state_mutation.follow_links = generation
+            .follow_links
+            .unwrap_or(state_mutation.follow_links);
+        state_mutation.harvest_responses = generation
+            .harvest_responses
+            .unwrap_or(state_mutation.harvest_responses);
+        state_mutation.learn_precedence = generation
+            .learn_precedence
+            .unwrap_or(state_mutation.learn_precedence);
+        state_mutation.mutate_sequences = generation
+            .mutate_sequences
+            .unwrap_or(state_mutation.mutate_sequences);
+        state_mutation.honor_cache_headers = generation
+            .honor_cache_headers
+            .unwrap_or(state_mutation.honor_cache_headers);
+        state_mutation.corpus_dir = generation
+            .corpus_dir
+            .clone()
+            .or(state_mutation.corpus_dir.take());
+        state_mutation.script_file = generation
+            .script_file
+            .clone()
+            .or(state_mutation.script_file.take());
+        state_mutation.deprecated = generation.deprecated.unwrap_or(state_mutation.deprecated);
+        state_mutation.strings = generation.strings.unwrap_or(state_mutation.strings);
+        state_mutation.seed = generation.seed.or(state_mutation.seed);
+        let shrinking = &mut state_mutation.shrinking;
+        shrinking.max_iterations = generation
+            .max_shrink_iterations
+            .or(shrinking.max_iterations);
+        shrinking.max_time_secs = generation.max_shrink_time_secs.or(shrinking.max_time_secs);
+        shrinking.strategy = generation.shrink_strategy.unwrap_or(shrinking.strategy);
+        if let Some(repeated) = &generation.repeated_sequences {
+            shrinking.repeated = repeated.clone();
+        }
+        if !generation.weights.is_empty() {
+            settings.operation_weights = generation.weights.clone();
+        }
+
+        let filters = &config.filters;
+        if !filters.include.is_empty() || !filters.exclude.is_empty() {
+            state_mutation.query_operation_ids = (0..self.amos.operations.len())
+                .filter(|idx| filters.matches(&self.amos.operations[*idx].info.name))
+                .collect();
+            state_mutation.selected_query_operation = None;
+        }
+
+        if config.target.health.is_enabled() {
+            settings.health = config.target.health.clone();
+        }
+        if config.sut.is_managed() {
+            settings.sut = config.sut.clone();
+        }
+        if config.tags != RunTagSettings::new() {
+            settings.tags = config.tags.clone();
+        }
+
+        self.open_specifications(config.specification.files.clone());
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: format!("Loaded config {}", path),
+        });
+    }
+
+    /// Open a report written by the CLI, to show the manifest of its run
+    pub fn open_report(&mut self, path: &str) {
+        match report::read_results_for_test(path) {
+            Ok(Report {
+                manifest: Some(manifest),
+                ..
+            }) => {
+                self.run_manifest = Some(manifest);
+                self.exploration_log.push(explore::LogMessage {
+                    level: explore::LogLevel::Info,
+                    message: format!("Opened report {}", path),
+                });
+            }
+            Ok(_) => self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Warning,
+                message: format!("The report {} has no run manifest", path),
+            }),
+            Err(e) => self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Error,
+                message: format!("Failed to open report {}: {}", path, e),
+            }),
+        }
+    }
+
+    pub fn compare_runs(&mut self) {
+        let (Some(baseline), Some(current)) = (&self.comparison_baseline, &self.comparison_current)
+        else {
+            return;
+        };
+
+        let comparison = comparison::read_reports(baseline).and_then(|baseline| {
+            comparison::read_reports(current).map(|current| Comparison::new(&baseline, &current))
+        });
+        self.comparison = Some(comparison.map_err(|e| e.to_string()));
+    }
+
+    pub fn is_sut_busy(&self) -> bool {
+        self.sut_rx.is_some()
+    }
+
+    /// Start, stop or reset the SUT in the background. A SUT that is not
+    ///  running is started with the current settings.
+    pub fn manage_sut(&mut self, action: SutAction) {
+        let target = self.create_target_from_settings();
+        let settings = self.app_state.exploration_settings.sut.clone();
+        let manager = self.sut_manager.clone();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.sut_rx = Some(rx);
+
+        thread::spawn(move || {
+            let mut manager = manager.lock().unwrap();
+            if !manager.is_running() {
+                *manager = SutManager::new(settings);
+            }
+            let result = match action {
+                SutAction::Start => manager.start(&target).map(|_| "Started the SUT"),
+                SutAction::Stop => manager.stop().map(|_| "Stopped the SUT"),
+                SutAction::Reset => manager.reset(&target).map(|_| "Reset the SUT"),
+            };
+            tx.send(result.map(str::to_string)).ok();
+        });
+    }
+
+    fn process_sut(&mut self) {
+        let Some(rx) = &self.sut_rx else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Err("Managing the SUT stopped".to_string())
+            }
+        };
+        self.sut_rx = None;
+
+        self.exploration_log.push(match result {
+            Ok(message) => explore::LogMessage {
+                level: explore::LogLevel::Info,
+                message,
+            },
+            Err(message) => explore::LogMessage {
+                level: explore::LogLevel::Error,
+                message,
+            },
+        });
+    }
+
+    pub fn is_discovering(&self) -> bool {
+        self.discovery_rx.is_some()
+    }
+
+    fn process_discovery(&mut self) {
+        let Some(rx) = &self.discovery_rx else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => Err(spec::Error::DiscoveryFailed(
+                "Discovery stopped".to_string(),
+            )),
+        };
+        self.discovery_rx = None;
+
+        match result {
+            Ok(translation_result) => {
+                for warning in &translation_result.warnings {
+                    self.exploration_log.push(explore::LogMessage {
+                        level: explore::LogLevel::Info,
+                        message: warning.message.clone(),
+                    });
+                }
+
+                // The discovered AMOS is of the current target, keep it
+                let target = std::mem::take(&mut self.app_state.target);
+                self.use_translation_result(translation_result);
+                self.app_state.target = target;
+            }
+            Err(e) => self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Error,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    fn save(&mut self) {
         info!("Save file");
 
+        self.save_session();
+
         // TODO: make this more production ready
         let project_path = std::path::Path::new("./data/project.json");
         self.app_state.save(project_path);
     }
 
-    fn run_ui_command(&self, command: UICommand) {
+    /// Keep the panels, selections and results of the session in the app state
+    fn save_session(&mut self) {
+        let session = &mut self.app_state.session;
+        session.show_settings = self.show_settings;
+        session.show_comparison = self.show_comparison;
+        session.show_size_histogram = self.show_size_histogram;
+        session.show_latency_chart = self.show_latency_chart;
+        session.selected_amos_operation = self.selected_amos_operation;
+        session.selected_amos_definition = self.selected_amos_definition;
+        session.selected_dependency_operation = self.selected_dependency_operation;
+        session.selected_exploration_result = self.selected_exploration_result;
+
+        if self.exploration_results.is_empty() {
+            session.last_results_path = None;
+            return;
+        }
+
+        let results_path = "./data/last_results.json";
+        let written = serde_json::to_string_pretty(&self.exploration_results)
+            .map_err(|e| e.to_string())
+            .and_then(|payload| std::fs::write(results_path, payload).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => session.last_results_path = Some(results_path.to_string()),
+            Err(e) => error!("Failed to save the results of the last run: {}", e),
+        }
+    }
+
+    /// Record the state as a change that can be undone, if it changed
+    fn record_history(&mut self) {
+        let snapshot = Snapshot::of(&self.app_state, &self.amos);
+        self.history.record(snapshot, self.app_state.history.depth);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    fn undo(&mut self) {
+        // An edit not yet recorded, like a text still being typed, is undone first
+        self.record_history();
+        if let Some(edit) = self.history.undo().cloned() {
+            self.edit_state(|app_state, amos| edit.undo_on(app_state, amos));
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(edit) = self.history.redo().cloned() {
+            self.edit_state(|app_state, amos| edit.redo_on(app_state, amos));
+        }
+    }
+
+    fn edit_state(&mut self, edit: impl FnOnce(&mut AppState, &mut AMOS)) {
+        let amos = self.amos.clone();
+        edit(&mut self.app_state, &mut self.amos);
+        self.apply_appearance = true;
+
+        if self.amos != amos {
+            self.dependency_graph =
+                amos_relations::operation_dependency_graph(&self.amos.operations);
+            self.selected_amos_operation = self
+                .selected_amos_operation
+                .min(self.amos.operations.len().saturating_sub(1));
+            self.selected_dependency_operation = None;
+            self.save_amos();
+        }
+    }
+
+    fn autosave(&mut self) {
+        if self.last_autosave.elapsed() < recovery::AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.last_autosave = std::time::Instant::now();
+
+        if let Err(e) = recovery::autosave(&self.app_state, &self.amos, &self.exploration_results) {
+            error!("Failed to autosave the session: {}", e);
+        }
+    }
+
+    /// Offer to restore the session that did not shut down cleanly
+    fn recovery_ui(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_recovery else {
+            return;
+        };
+
+        let mut restore = None;
+        egui::Window::new("Restore session")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("QuickREST did not shut down cleanly.");
+                ui.label(format!(
+                    "Restore the autosaved project {} and its {} results?",
+                    pending.amos.name,
+                    pending.results.len()
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button("Restore").clicked() {
+                        restore = Some(true);
+                    }
+                    if ui.button("Discard").clicked() {
+                        restore = Some(false);
+                    }
+                });
+            });
+
+        match restore {
+            Some(true) => {
+                if let Some(pending) = self.pending_recovery.take() {
+                    self.restore_recovery(pending);
+                }
+            }
+            Some(false) => self.pending_recovery = None,
+            None => (),
+        }
+    }
+
+    fn restore_recovery(&mut self, recovery: Recovery) {
+        self.app_state = recovery.app_state;
+        self.amos = recovery.amos;
+        self.exploration_results = recovery.results;
+        self.dependency_graph = amos_relations::operation_dependency_graph(&self.amos.operations);
+        self.selected_amos_operation = 0;
+        self.selected_amos_definition = 0;
+        self.selected_dependency_operation = None;
+        self.selected_exploration_result = 0;
+        self.apply_appearance = true;
+        self.save_amos();
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: "Restored the autosaved session".to_string(),
+        });
+    }
+
+    /// Save the working AMOS, after it is changed in the UI
+    pub fn save_amos(&self) {
+        if let Some(path) = &self.app_state.current_amos_path {
+            self.amos.save(std::path::Path::new(path));
+        }
+    }
+
+    /// Show the requests the operations would be sent as, nothing is sent
+    pub fn preview_requests(&mut self, operations: Vec<amos::Operation>) {
+        let context = explore::ExplorationContext {
+            http_send_fn: explore::invoke_dry,
+            auth: self.app_state.exploration_settings.auth.clone(),
+            ..explore::ExplorationContext::new(self.create_target_from_settings())
+        };
+
+        let previews = sequence::sequence_preview(&context, &self.amos.operations, operations);
+        self.request_preview.open(previews);
+    }
+
+    /// Invoke the operation once, its results are sent when it is done
+    pub fn invoke_manual(
+        &mut self,
+        operation: qr_explore::amos_generation::GeneratedOperation,
+    ) -> std::sync::mpsc::Receiver<Option<Vec<amos::InvokeResult>>> {
+        let (results_tx, results_rx) = std::sync::mpsc::channel();
+        let channel = self.exploration_log_tx.clone();
+        let ops = self.amos.operations.clone();
+        let target = self.create_target_from_settings();
+        let auth = self.app_state.exploration_settings.auth.clone();
+
+        thread::spawn(move || {
+            let context = &explore::ExplorationContext {
+                observer: Some(Box::new(channel)),
+                auth,
+                ..explore::ExplorationContext::new(target)
+            };
+
+            let results = explore::invoke(context, &ops, &[operation]);
+            // The console may have been closed
+            let _ = results_tx.send(results);
+        });
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: "Invoke from the console".to_string(),
+        });
+        results_rx
+    }
+
+    /// Add the checked operations to the selected behaviour or fuzzer of the
+    ///  selected sequence
+    pub fn add_checked_operations_to_item(&mut self) {
+        let ids: Vec<usize> = self.checked_operations.iter().copied().collect();
+        let message = match self.app_state.sequencer.selected_item_as_mut() {
+            Some(item @ sequencer::SequenceItem::Behaviour { .. })
+            | Some(item @ sequencer::SequenceItem::Fuzzer { .. }) => explore::LogMessage {
+                level: explore::LogLevel::Info,
+                message: format!("Added {} operations to the item", item.add_operations(&ids)),
+            },
+            _ => explore::LogMessage {
+                level: explore::LogLevel::Warning,
+                message: "Select a behaviour or a fuzzer of a sequence to add the operations to"
+                    .to_string(),
+            },
+        };
+        self.exploration_log.push(message);
+    }
+
+    /// Open the sequence of the selected example as a new sequence of the
+    ///  sequencer
+    fn open_example_in_sequencer(&mut self) {
+        let Some(example) = self
+            .exploration_results
+            .get(self.selected_exploration_result)
+        else {
+            return;
+        };
+        let Some(sequence) = example.sequence() else {
+            return;
+        };
+
+        let (name, _) = example_presentation(example, &self.amos);
+        let sequence = sequencer::Sequence::from_example(&name, sequence, &self.amos);
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: format!(
+                "Opened {} operations of {} in the sequencer",
+                sequence.items.len(),
+                name
+            ),
+        });
+        self.app_state.sequencer.open(sequence);
+        self.app_state.central_navigation.selected = central_navigation::Navigations::Sequencer;
+    }
+
+    fn add_operation_to_sequence(&mut self) {
+        let current_idx = self.selected_amos_operation;
+        let Some(current_op) = self.amos.operations.get(current_idx) else {
+            return;
+        };
+
+        let new_item = sequencer::SequenceItem::new(current_idx, &current_op.parameters);
+
+        self.app_state.sequencer.push_item_to_selected(new_item);
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: format!("Added {} to sequence", current_op.info.name.clone()),
+        });
+    }
+
+    /// Run the selected item of the selected sequence, if it is a behaviour
+    ///  or a fuzzer
+    /// Spawn the exploration of a behaviour or fuzzer item, returns its name
+    ///  and thread, or `None` if the item is an operation
+    fn spawn_item(
+        &self,
+        item: &sequencer::SequenceItem,
+        is_dry_run: bool,
+        observer: Box<dyn ExplorationObserver>,
+        stop: explore::StopSignal,
+        pause: explore::PauseSignal,
+    ) -> Option<(String, thread::JoinHandle<()>)> {
+        let target = self.create_target_from_settings();
+
+        match item {
+            sequencer::SequenceItem::Behaviour {
+                behaviour,
+                parameters,
+                settings,
+            } => {
+                let handle = qr_explore::spawn_exploration(
+                    &target,
+                    is_dry_run,
+                    &self.amos,
+                    observer,
+                    behaviour,
+                    &sequencer::behaviour_settings(
+                        &self.app_state.exploration_settings,
+                        parameters,
+                        settings,
+                    ),
+                    explore::ExplorationControl::new().with_signals(stop, pause),
+                );
+                Some((behaviour.presentation(), handle))
+            }
+            sequencer::SequenceItem::Fuzzer { property, settings } => {
+                let handle = qr_explore::spawn_exploration(
+                    &target,
+                    is_dry_run,
+                    &self.amos,
+                    observer,
+                    &property.behaviour(),
+                    &sequencer::fuzzer_settings(
+                        &self.app_state.exploration_settings,
+                        property,
+                        settings,
+                    ),
+                    explore::ExplorationControl::new().with_signals(stop, pause),
+                );
+                Some((property.to_string(), handle))
+            }
+            sequencer::SequenceItem::Operation { .. } => None,
+        }
+    }
+
+    fn run_selected_behaviour(&mut self) {
+        if let ExplorationState::Running = self.exploration_state {
+            self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Warning,
+                message: "An exploration is already running".to_string(),
+            });
+            return;
+        }
+
+        let stop = explore::StopSignal::default();
+        let pause = explore::PauseSignal::default();
+
+        let Some(selected) = self.app_state.sequencer.selected_sequence() else {
+            return;
+        };
+
+        let started = selected.items.get(selected.selected).and_then(|item| {
+            self.spawn_item(
+                item,
+                selected.is_dry_run,
+                Box::new(self.exploration_log_tx.clone()),
+                stop.clone(),
+                pause.clone(),
+            )
+        });
+
+        match started {
+            Some((name, _)) => {
+                // Set exploration state to let the UI react accordingly
+                self.exploration_state = ExplorationState::Running;
+                self.exploration_stop = stop;
+                self.exploration_pause = pause;
+                self.exploration_log.push(explore::LogMessage {
+                    level: explore::LogLevel::Info,
+                    message: format!("Run {}", name),
+                });
+            }
+            None => self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Warning,
+                message: "The selected sequence item is not a behaviour".to_string(),
+            }),
+        }
+    }
+
+    /// Queue the selected behaviour or fuzzer, as it is set up now
+    pub fn queue_selected_item(&mut self) {
+        let Some(selected) = self.app_state.sequencer.selected_sequence() else {
+            return;
+        };
+
+        let name = match selected.items.get(selected.selected) {
+            Some(sequencer::SequenceItem::Behaviour { behaviour, .. }) => behaviour.presentation(),
+            Some(sequencer::SequenceItem::Fuzzer { property, .. }) => property.to_string(),
+            _ => {
+                self.exploration_log.push(explore::LogMessage {
+                    level: explore::LogLevel::Warning,
+                    message: "The selected sequence item is not a behaviour".to_string(),
+                });
+                return;
+            }
+        };
+        let item = selected.items[selected.selected].clone();
+        let is_dry_run = selected.is_dry_run;
+
+        self.exploration_log.push(explore::LogMessage {
+            level: explore::LogLevel::Info,
+            message: format!("Queued {}", name),
+        });
+        self.run_queue.push(name, item, is_dry_run);
+    }
+
+    fn process_run_queue(&mut self) {
+        let mut queue = std::mem::take(&mut self.run_queue);
+
+        self.exploration_log.extend(queue.poll());
+        queue.start_pending(|entry, tx, stop| {
+            self.spawn_item(
+                &entry.item,
+                entry.is_dry_run,
+                Box::new(tx),
+                stop,
+                explore::PauseSignal::default(),
+            )
+            .map(|(_, handle)| handle)
+        });
+
+        self.run_queue = queue;
+    }
+
+    /// Show the results of the queued run in the Examples panel
+    pub fn show_queued_results(&mut self, idx: usize) {
+        let Some(entry) = self.run_queue.entries.get(idx) else {
+            return;
+        };
+
+        self.exploration_results = entry.results.clone();
+        self.selected_exploration_result = 0;
+        self.app_state.central_navigation.selected = central_navigation::Navigations::Examples;
+    }
+
+    /// The progress of the tests of the running exploration
+    pub fn test_progress(&self) -> Option<&TestProgress> {
+        match self.exploration_state {
+            ExplorationState::Running => self.test_progress.as_ref(),
+            ExplorationState::Idle => None,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.exploration_pause.load(Ordering::Relaxed)
+    }
+
+    /// Hold the running exploration between its test cases, or let it
+    ///  continue
+    pub fn toggle_pause(&mut self) {
+        if let ExplorationState::Running = self.exploration_state {
+            let paused = !self.is_paused();
+            self.exploration_pause.store(paused, Ordering::Relaxed);
+            self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Info,
+                message: if paused {
+                    "Pause requested".to_string()
+                } else {
+                    "Resume requested".to_string()
+                },
+            });
+        }
+    }
+
+    fn stop_exploration(&mut self) {
+        if let ExplorationState::Running = self.exploration_state {
+            self.exploration_stop.store(true, Ordering::Relaxed);
+            self.exploration_log.push(explore::LogMessage {
+                level: explore::LogLevel::Info,
+                message: "Stop requested".to_string(),
+            });
+        }
+    }
+
+    fn run_ui_command(&mut self, command: UICommand) {
         info!("Run ui command");
 
         match command {
             UICommand::Save => {
                 self.save();
             }
+            UICommand::Undo => self.undo(),
+            UICommand::Redo => self.redo(),
+            UICommand::RunSelectedBehaviour => self.run_selected_behaviour(),
+            UICommand::QueueSelectedBehaviour => self.queue_selected_item(),
+            UICommand::Stop => self.stop_exploration(),
+            UICommand::TogglePause => self.toggle_pause(),
+            UICommand::AddOperationToSequence => {
+                self.add_operation_to_sequence();
+                self.record_history();
+            }
+            UICommand::RecordEdit => self.record_history(),
+            UICommand::SwitchPanel(navigation) => {
+                self.app_state.central_navigation.selected = navigation;
+            }
+            UICommand::ToggleCommandPalette => self.command_palette.toggle(),
+        }
+    }
+
+    /// Send the commands of the shortcuts pressed this frame
+    fn handle_shortcuts(&self, ctx: &egui::Context) {
+        for command in UICommand::all() {
+            let Some(shortcut) = command.shortcut() else {
+                continue;
+            };
+
+            if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                self.command_sender.send_ui(command);
+            }
         }
     }
 
@@ -1257,14 +3891,36 @@ impl App {
 
     /// The top-level ui
     fn ui(&mut self, egui_ctx: &egui::Context) {
+        self.handle_shortcuts(egui_ctx);
+
+        self.command_palette.show(egui_ctx, &self.command_sender);
+
         crate::ui::top_panel(egui_ctx, self);
 
+        crate::ui::drop_overlay(egui_ctx);
+
         crate::ui::bottom_panel(egui_ctx, self);
 
         crate::ui::navigation_panels(egui_ctx, self);
     }
 }
 
+/// The checked operations, or else the selected operation
+fn operations_to_add(checked: &std::collections::BTreeSet<usize>, selected: usize) -> Vec<usize> {
+    if checked.is_empty() {
+        vec![selected]
+    } else {
+        checked.iter().copied().collect()
+    }
+}
+
+/// The specifications are read as JSON
+fn is_specification_file(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
 impl Default for App {
     fn default() -> Self {
         Self::new()
@@ -1272,84 +3928,89 @@ impl Default for App {
 }
 
 impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if self.apply_appearance {
+            self.app_state
+                .appearance
+                .apply(ctx, frame.info().native_pixels_per_point);
+            self.apply_appearance = false;
+        }
+
+        if self.restore_window {
+            if let Some(window) = self.app_state.session.window {
+                window.restore(frame);
+            }
+            self.restore_window = false;
+        }
+        self.app_state.session.window = Some(WindowLayout::of(&frame.info().window_info));
+
+        self.open_dropped_files(ctx);
+        self.process_discovery();
+        self.process_sut();
+        self.process_run_queue();
+        if self.is_discovering() || self.is_sut_busy() || self.run_queue.is_busy() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+
         self.ui(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // TODO: Move this somewhere..
             // Show file info
-            if let Some(picked_path) = &self.picked_path {
+            if let [picked_path] = self.picked_paths.as_slice() {
                 ui.horizontal(|ui| {
                     ui.label("Picked file:");
                     ui.monospace(picked_path);
                 });
 
                 if let ParseState::Parse = self.parse_state {
-                    let Ok((parse_result, translation_result)) =
-                        spec::manager::load_open_api_file_path(picked_path)
-                    else {
-                        // Decide how to signal errors to the user
-                        todo!()
-                    };
-                    let path = self.app_state.current_amos_path.clone().unwrap();
-                    let amos_path = std::path::Path::new(&path);
-
-                    translation_result.amos.save(amos_path);
+                    match spec::manager::load_open_api_file_path(picked_path) {
+                        Ok((parse_result, translation_result)) => {
+                            self.use_translation_result(translation_result);
+                            self.lint_report = Some(spec::lint::lint(&parse_result, &self.amos));
+                            self.parse_result = Some(parse_result);
+                            self.app_state.recent_files.add(&self.picked_paths);
+                        }
+                        Err(e) => self.exploration_log.push(explore::LogMessage {
+                            level: explore::LogLevel::Error,
+                            message: format!("Failed to open {}: {}", picked_path, e),
+                        }),
+                    }
+                    self.parse_state = ParseState::Done;
+                }
+            } else if !self.picked_paths.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Merged files:");
+                    for picked_path in &self.picked_paths {
+                        ui.monospace(picked_path);
+                    }
+                });
 
-                    // TODO: Do we want a clone and preserve the 'original' in the result or not?
-                    self.amos = translation_result.amos.clone();
-                    self.translation_result = Some(translation_result);
-                    self.parse_result = Some(parse_result);
+                if let ParseState::Parse = self.parse_state {
+                    match spec::manager::load_open_api_file_paths(&self.picked_paths) {
+                        Ok(translation_result) => {
+                            self.use_translation_result(translation_result);
+                            // The parse results are per file, the merge is in the
+                            //  translation result
+                            self.parse_result = None;
+                            self.lint_report = None;
+                            self.app_state.recent_files.add(&self.picked_paths);
+                        }
+                        Err(e) => error!("Failed to merge the specifications: {}", e),
+                    }
                     self.parse_state = ParseState::Done;
-                    // Currently, we only support one working AMOS, so clear the workspace
-                    // This is not ideal, allow easy change between different AMOSes
-                    self.app_state = AppState::new();
                 }
             }
 
             egui::TopBottomPanel::top("exploration_view_top_panel").show(ctx, |ui| {
                 ui.horizontal_wrapped(|ui| {
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Summary,
-                        "📊 Summary",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::APIs,
-                        "🕸 APIs",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Examples,
-                        "🗊 Examples",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Sequences,
-                        "⬇ Sequences",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Invocations,
-                        "📝 Invocations",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Progress,
-                        "🕟 Progress",
-                    );
-
-                    ui.selectable_value(
-                        &mut self.app_state.central_navigation.selected,
-                        central_navigation::Navigations::Sequencer,
-                        "↻ Sequencer",
-                    );
+                    for navigation in central_navigation::Navigations::ALL {
+                        ui.selectable_value(
+                            &mut self.app_state.central_navigation.selected,
+                            navigation,
+                            navigation.title(),
+                        );
+                    }
                 });
             });
 
@@ -1363,7 +4024,28 @@ impl eframe::App for App {
                 ctx.request_repaint();
             };
 
+            // Record once an edit is done, not for each step of a drag or a
+            //  typed character
+            let editing =
+                ctx.input(|i| i.pointer.any_down()) || ctx.memory(|m| m.focus().is_some());
+            if self.editing && !editing {
+                self.command_sender.send_ui(UICommand::RecordEdit);
+            }
+            self.editing = editing;
+
             self.run_pending_ui_commands();
         });
+
+        self.request_preview.show(ctx);
+        self.recovery_ui(ctx);
+        // Keep the recovery of the last session until it is restored or discarded
+        if self.pending_recovery.is_none() {
+            self.autosave();
+        }
+    }
+
+    fn on_close_event(&mut self) -> bool {
+        recovery::end_session();
+        true
     }
 }
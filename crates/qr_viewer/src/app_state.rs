@@ -1,11 +1,14 @@
+use eframe::egui;
 use serde;
 
 use qr_explore::behaviours::BehaviourControl;
 use qr_explore::exploration_settings::ExplorationSettings;
+use qr_explore::notification::NotificationSettings;
 use qr_http_resource::http;
 
 use crate::central_navigation::CentralNavigation;
 use crate::fuzzing;
+use crate::history::HistorySettings;
 use crate::main_navigation::MainNavigation;
 use crate::sequencer;
 
@@ -30,6 +33,26 @@ pub struct AppState {
 
     // Exploration Target
     pub target: TargetSettings,
+
+    // Theme and scale of the UI
+    #[serde(default)]
+    pub appearance: AppearanceSettings,
+
+    // Notifications of finished runs and found counterexamples
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+
+    // The working context of the last session, restored on startup
+    #[serde(default)]
+    pub session: SessionState,
+
+    // Undo of the edits of the state
+    #[serde(default)]
+    pub history: HistorySettings,
+
+    // The specifications opened lately, offered in the toolbar
+    #[serde(default)]
+    pub recent_files: RecentFiles,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -38,6 +61,18 @@ pub struct TargetSettings {
     pub base_url: String,
     pub protocol: http::Protocol,
     pub port: String,
+    /// The base path of the specification is used unless set
+    #[serde(default)]
+    pub base_path: Option<String>,
+    #[serde(default)]
+    pub paths: http::PathNormalization,
+    /// The pinned parameters and the static headers, of all operations and of
+    ///  each
+    #[serde(default)]
+    pub pinning: http::RequestPinning,
+    /// The parameters and headers computed when the requests are sent
+    #[serde(default)]
+    pub providers: http::ValueProviders,
 }
 
 impl TargetSettings {
@@ -46,6 +81,10 @@ impl TargetSettings {
             base_url: "".to_string(),
             protocol: http::Protocol::HTTP,
             port: 8080.to_string(),
+            base_path: None,
+            paths: http::PathNormalization::new(),
+            pinning: http::RequestPinning::new(),
+            providers: http::ValueProviders::new(),
         }
     }
 }
@@ -56,6 +95,119 @@ impl Default for TargetSettings {
     }
 }
 
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct AppearanceSettings {
+    pub theme: Theme,
+    // Scale of the UI, relative to the native scale of the display
+    pub ui_scale: f32,
+}
+
+impl AppearanceSettings {
+    pub const MIN_UI_SCALE: f32 = 0.5;
+    pub const MAX_UI_SCALE: f32 = 3.0;
+
+    pub fn new() -> Self {
+        AppearanceSettings {
+            theme: Theme::Dark,
+            ui_scale: 1.0,
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context, native_pixels_per_point: Option<f32>) {
+        let visuals = match self.theme {
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+        };
+        ctx.set_visuals(visuals);
+
+        let ui_scale = self.ui_scale.clamp(Self::MIN_UI_SCALE, Self::MAX_UI_SCALE);
+        ctx.set_pixels_per_point(native_pixels_per_point.unwrap_or(1.0) * ui_scale);
+    }
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Position and size of the window, in points
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct WindowLayout {
+    pub position: Option<[f32; 2]>,
+    pub size: [f32; 2],
+    pub maximized: bool,
+}
+
+impl WindowLayout {
+    pub fn of(window_info: &eframe::WindowInfo) -> Self {
+        WindowLayout {
+            position: window_info.position.map(|pos| [pos.x, pos.y]),
+            size: [window_info.size.x, window_info.size.y],
+            maximized: window_info.maximized,
+        }
+    }
+
+    pub fn restore(&self, frame: &mut eframe::Frame) {
+        if let Some([x, y]) = self.position {
+            frame.set_window_pos(egui::pos2(x, y));
+        }
+        let [width, height] = self.size;
+        frame.set_window_size(egui::vec2(width, height));
+        frame.set_maximized(self.maximized);
+    }
+}
+
+/// The specifications opened lately, the latest first. The files that were
+///  opened together to be merged are one entry.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, PartialEq, Debug)]
+pub struct RecentFiles {
+    pub entries: Vec<Vec<String>>,
+}
+
+impl RecentFiles {
+    pub const MAX_ENTRIES: usize = 10;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put the files first, the oldest entry is forgotten when there are too
+    ///  many
+    pub fn add(&mut self, paths: &[String]) {
+        if paths.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != paths);
+        self.entries.insert(0, paths.to_vec());
+        self.entries.truncate(RecentFiles::MAX_ENTRIES);
+    }
+}
+
+/// The panels, selections and window of a session, and where the results of
+///  its last run were saved
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+pub struct SessionState {
+    pub show_settings: bool,
+    pub show_comparison: bool,
+    pub show_size_histogram: bool,
+    pub show_latency_chart: bool,
+
+    pub selected_amos_operation: usize,
+    pub selected_amos_definition: usize,
+    pub selected_dependency_operation: Option<usize>,
+    pub selected_exploration_result: usize,
+
+    pub window: Option<WindowLayout>,
+    pub last_results_path: Option<String>,
+}
+
 impl AppState {
     pub fn new() -> Self {
         AppState {
@@ -67,6 +219,11 @@ impl AppState {
             behaviour: BehaviourControl::new(),
             fuzzing: fuzzing::Fuzzing::new(),
             target: TargetSettings::new(),
+            appearance: AppearanceSettings::new(),
+            notifications: NotificationSettings::new(),
+            session: SessionState::default(),
+            history: HistorySettings::new(),
+            recent_files: RecentFiles::new(),
         }
     }
 
@@ -1,4 +1,4 @@
-#[derive(PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(PartialEq, Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
 pub enum Navigations {
     Summary,
     Examples,
@@ -7,6 +7,34 @@ pub enum Navigations {
     Progress,
     Sequencer,
     APIs,
+    Dependencies,
+}
+
+impl Navigations {
+    /// All navigations, in the order of the tabs
+    pub const ALL: [Navigations; 8] = [
+        Navigations::Summary,
+        Navigations::APIs,
+        Navigations::Examples,
+        Navigations::Sequences,
+        Navigations::Invocations,
+        Navigations::Progress,
+        Navigations::Sequencer,
+        Navigations::Dependencies,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Navigations::Summary => "📊 Summary",
+            Navigations::APIs => "🕸 APIs",
+            Navigations::Examples => "🗊 Examples",
+            Navigations::Sequences => "⬇ Sequences",
+            Navigations::Invocations => "📝 Invocations",
+            Navigations::Progress => "🕟 Progress",
+            Navigations::Sequencer => "↻ Sequencer",
+            Navigations::Dependencies => "🔗 Dependencies",
+        }
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -1,5 +1,91 @@
+use eframe::egui::{Key, KeyboardShortcut, Modifiers};
+
+use crate::central_navigation::Navigations;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum UICommand {
     Save,
+    Undo,
+    Redo,
+    RunSelectedBehaviour,
+    QueueSelectedBehaviour,
+    Stop,
+    TogglePause,
+    AddOperationToSequence,
+    /// An edit of the user is done, and recorded to be undone. It is not
+    ///  listed in the command palette.
+    RecordEdit,
+    SwitchPanel(Navigations),
+    ToggleCommandPalette,
+}
+
+impl UICommand {
+    /// All commands, in the order they are listed in the command palette
+    pub fn all() -> Vec<UICommand> {
+        let mut commands = vec![
+            UICommand::Save,
+            UICommand::Undo,
+            UICommand::Redo,
+            UICommand::RunSelectedBehaviour,
+            UICommand::QueueSelectedBehaviour,
+            UICommand::Stop,
+            UICommand::TogglePause,
+            UICommand::AddOperationToSequence,
+        ];
+        commands.extend(Navigations::ALL.map(UICommand::SwitchPanel));
+        commands.push(UICommand::ToggleCommandPalette);
+
+        commands
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            UICommand::Save => "Save project".to_string(),
+            UICommand::Undo => "Undo".to_string(),
+            UICommand::Redo => "Redo".to_string(),
+            UICommand::RunSelectedBehaviour => "Run selected behaviour".to_string(),
+            UICommand::QueueSelectedBehaviour => "Queue selected behaviour".to_string(),
+            UICommand::Stop => "Stop exploration".to_string(),
+            UICommand::TogglePause => "Pause or resume exploration".to_string(),
+            UICommand::AddOperationToSequence => "Add selected operation to sequence".to_string(),
+            UICommand::RecordEdit => "Record edit".to_string(),
+            UICommand::SwitchPanel(navigation) => format!("Switch panel: {}", navigation.title()),
+            UICommand::ToggleCommandPalette => "Command palette".to_string(),
+        }
+    }
+
+    pub fn shortcut(&self) -> Option<KeyboardShortcut> {
+        let shortcut = match self {
+            UICommand::Save => KeyboardShortcut::new(Modifiers::COMMAND, Key::S),
+            UICommand::Undo => KeyboardShortcut::new(Modifiers::COMMAND, Key::Z),
+            UICommand::Redo => KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z),
+            UICommand::RunSelectedBehaviour => KeyboardShortcut::new(Modifiers::NONE, Key::F5),
+            UICommand::QueueSelectedBehaviour => KeyboardShortcut::new(Modifiers::COMMAND, Key::F5),
+            UICommand::Stop => KeyboardShortcut::new(Modifiers::SHIFT, Key::F5),
+            UICommand::TogglePause => KeyboardShortcut::new(Modifiers::NONE, Key::F6),
+            UICommand::AddOperationToSequence => {
+                KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::A)
+            }
+            UICommand::RecordEdit => return None,
+            UICommand::SwitchPanel(navigation) => {
+                const KEYS: [Key; 8] = [
+                    Key::Num1,
+                    Key::Num2,
+                    Key::Num3,
+                    Key::Num4,
+                    Key::Num5,
+                    Key::Num6,
+                    Key::Num7,
+                    Key::Num8,
+                ];
+                let idx = Navigations::ALL.iter().position(|n| n == navigation)?;
+                KeyboardShortcut::new(Modifiers::COMMAND, *KEYS.get(idx)?)
+            }
+            UICommand::ToggleCommandPalette => KeyboardShortcut::new(Modifiers::COMMAND, Key::P),
+        };
+
+        Some(shortcut)
+    }
 }
 
 pub struct CommandSender {
@@ -1,12 +1,40 @@
+use qr_explore::behaviours::Behaviour;
+
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
 pub enum Property {
     ResponseCheck,
+    ResponseTime,
+    Payload,
+    Injection,
+    AuthBypass,
+    RoleMatrix,
+    SecurityAudit,
+}
+
+impl Property {
+    pub fn behaviour(&self) -> Behaviour {
+        match self {
+            Self::ResponseCheck => Behaviour::Property,
+            Self::ResponseTime => Behaviour::ResponseTime,
+            Self::Payload => Behaviour::Payload,
+            Self::Injection => Behaviour::Injection,
+            Self::AuthBypass => Behaviour::AuthBypass,
+            Self::RoleMatrix => Behaviour::RoleMatrix,
+            Self::SecurityAudit => Behaviour::SecurityAudit,
+        }
+    }
 }
 
 impl std::fmt::Display for Property {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::ResponseCheck => write!(f, "Response Check"),
+            Self::ResponseTime => write!(f, "Response Time"),
+            Self::Payload => write!(f, "Response Payload"),
+            Self::Injection => write!(f, "Injection"),
+            Self::AuthBypass => write!(f, "Auth Bypass"),
+            Self::RoleMatrix => write!(f, "Role Matrix"),
+            Self::SecurityAudit => write!(f, "Security Audit"),
         }
     }
 }
@@ -34,6 +62,8 @@ impl Default for Fuzzing {
 pub struct PropertySettings {
     pub operations: Vec<usize>,
     pub selected_operation: Option<usize>,
+    #[serde(default)]
+    pub follow_links: bool,
 }
 
 impl PropertySettings {
@@ -41,6 +71,7 @@ impl PropertySettings {
         PropertySettings {
             operations: vec![],
             selected_operation: None,
+            follow_links: false,
         }
     }
 }
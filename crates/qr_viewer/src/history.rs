@@ -0,0 +1,336 @@
+use std::collections::VecDeque;
+
+use qr_explore::amos::AMOS;
+use serde_json::{json, Value};
+
+use crate::app_state::AppState;
+
+/// Parts of the app state that are not undone, the navigation and the session
+///  change as the user looks around rather than edits
+const NOT_UNDOABLE: [&str; 5] = [
+    "current_amos_path",
+    "selected_navigation",
+    "central_navigation",
+    "session",
+    "history",
+];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct HistorySettings {
+    // The number of changes that can be undone
+    pub depth: usize,
+}
+
+impl HistorySettings {
+    pub const MIN_DEPTH: usize = 1;
+    pub const MAX_DEPTH: usize = 1000;
+
+    pub fn new() -> Self {
+        HistorySettings { depth: 100 }
+    }
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The undoable state at one point in time, the app state and the AMOS as JSON
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot(Value);
+
+impl Snapshot {
+    pub fn of(app_state: &AppState, amos: &AMOS) -> Self {
+        let mut app_state = serde_json::to_value(app_state).unwrap_or_default();
+        if let Some(fields) = app_state.as_object_mut() {
+            for field in NOT_UNDOABLE {
+                fields.remove(field);
+            }
+        }
+
+        Snapshot(json!({
+            "app_state": app_state,
+            "amos": serde_json::to_value(amos).unwrap_or_default(),
+        }))
+    }
+
+    /// Restore the snapshot, keeping the parts of the app state that are not
+    ///  undoable
+    pub fn restore(&self, app_state: &mut AppState, amos: &mut AMOS) {
+        let Ok(mut current) = serde_json::to_value(&*app_state) else {
+            return;
+        };
+        if let (Some(fields), Some(snapshot)) =
+            (current.as_object_mut(), self.0["app_state"].as_object())
+        {
+            fields.extend(snapshot.clone());
+        }
+
+        match serde_json::from_value(current) {
+            Ok(restored) => *app_state = restored,
+            Err(e) => tracing::error!("Failed to restore the app state: {}", e),
+        }
+        match serde_json::from_value(self.0["amos"].clone()) {
+            Ok(restored) => *amos = restored,
+            Err(e) => tracing::error!("Failed to restore the AMOS: {}", e),
+        }
+    }
+}
+
+/// A value of the state replaced by another, at its JSON pointer
+#[derive(Clone, PartialEq, Debug)]
+struct Change {
+    pointer: String,
+    before: Value,
+    after: Value,
+}
+
+/// An edit of the state as a command, done again on a redo and reverted on an
+///  undo. It only holds the values it changed.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Edit {
+    changes: Vec<Change>,
+}
+
+impl Edit {
+    /// The edit from one state to the other, empty if they are the same
+    pub fn between(from: &Snapshot, to: &Snapshot) -> Self {
+        let mut changes = vec![];
+        diff(String::new(), &from.0, &to.0, &mut changes);
+        Edit { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn redo_on(&self, app_state: &mut AppState, amos: &mut AMOS) {
+        let mut state = Snapshot::of(app_state, amos);
+        self.apply(&mut state);
+        state.restore(app_state, amos);
+    }
+
+    pub fn undo_on(&self, app_state: &mut AppState, amos: &mut AMOS) {
+        let mut state = Snapshot::of(app_state, amos);
+        self.revert(&mut state);
+        state.restore(app_state, amos);
+    }
+
+    fn apply(&self, state: &mut Snapshot) {
+        for change in &self.changes {
+            if let Some(value) = state.0.pointer_mut(&change.pointer) {
+                *value = change.after.clone();
+            }
+        }
+    }
+
+    fn revert(&self, state: &mut Snapshot) {
+        for change in self.changes.iter().rev() {
+            if let Some(value) = state.0.pointer_mut(&change.pointer) {
+                *value = change.before.clone();
+            }
+        }
+    }
+}
+
+/// The changes from one value to the other, as deep as they have the same
+///  shape. An object with other keys or an array of another length is
+///  replaced as a whole.
+fn diff(pointer: String, before: &Value, after: &Value, changes: &mut Vec<Change>) {
+    match (before, after) {
+        _ if before == after => (),
+        (Value::Object(before), Value::Object(after)) if before.keys().eq(after.keys()) => {
+            for (key, value) in before {
+                // Escaped as a JSON pointer, the keys of a map may be paths
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                diff(format!("{pointer}/{escaped}"), value, &after[key], changes);
+            }
+        }
+        (Value::Array(before), Value::Array(after)) if before.len() == after.len() => {
+            for (index, (before, after)) in before.iter().zip(after).enumerate() {
+                diff(format!("{pointer}/{index}"), before, after, changes);
+            }
+        }
+        _ => changes.push(Change {
+            pointer,
+            before: before.clone(),
+            after: after.clone(),
+        }),
+    }
+}
+
+/// The edits of the state as commands. The edit from the recorded state to a
+///  new one is pushed, undoing it steps back to the recorded state.
+pub struct UndoHistory {
+    recorded: Option<Snapshot>,
+    undo: VecDeque<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        UndoHistory {
+            recorded: None,
+            undo: VecDeque::new(),
+            redo: vec![],
+        }
+    }
+
+    /// Record the edit to the snapshot if it changed, the oldest edits past
+    ///  the depth are forgotten. A new edit can no longer be redone.
+    pub fn record(&mut self, snapshot: Snapshot, depth: usize) {
+        if let Some(recorded) = &self.recorded {
+            let edit = Edit::between(recorded, &snapshot);
+            if !edit.is_empty() {
+                self.undo.push_back(edit);
+                while self.undo.len() > depth {
+                    self.undo.pop_front();
+                }
+                self.redo.clear();
+            }
+        }
+        self.recorded = Some(snapshot);
+    }
+
+    /// The edit to undo, the recorded state is the one before it
+    pub fn undo(&mut self) -> Option<&Edit> {
+        let edit = self.undo.pop_back()?;
+        if let Some(recorded) = &mut self.recorded {
+            edit.revert(recorded);
+        }
+        self.redo.push(edit);
+        self.redo.last()
+    }
+
+    /// The edit to redo, the recorded state is the one after it
+    pub fn redo(&mut self) -> Option<&Edit> {
+        let edit = self.redo.pop()?;
+        if let Some(recorded) = &mut self.recorded {
+            edit.apply(recorded);
+        }
+        self.undo.push_back(edit);
+        self.undo.back()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history as sut;
+    use serde_json::json;
+
+    fn snapshot(name: &str, timeout: u64) -> sut::Snapshot {
+        sut::Snapshot(json!({
+            "app_state": {
+                "name": name,
+                "target": { "timeout": timeout, "headers": { "/pets": "X-Pets" } },
+            },
+            "amos": { "operations": [] },
+        }))
+    }
+
+    #[test]
+    fn undo_reverts_the_last_edit() {
+        let mut history = sut::UndoHistory::new();
+        history.record(snapshot("a", 1), 10);
+        history.record(snapshot("b", 1), 10);
+
+        let mut state = snapshot("b", 1);
+        history.undo().unwrap().revert(&mut state);
+
+        assert_eq!(state, snapshot("a", 1));
+        assert_eq!(history.recorded, Some(snapshot("a", 1)));
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn redo_applies_the_undone_edit_again() {
+        let mut history = sut::UndoHistory::new();
+        history.record(snapshot("a", 1), 10);
+        history.record(snapshot("a", 2), 10);
+        history.record(snapshot("b", 2), 10);
+        history.undo();
+        history.undo();
+
+        let mut state = snapshot("a", 1);
+        history.redo().unwrap().apply(&mut state);
+
+        assert_eq!(state, snapshot("a", 2));
+        assert_eq!(history.recorded, Some(snapshot("a", 2)));
+        assert!(history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn a_new_edit_truncates_the_redo() {
+        let mut history = sut::UndoHistory::new();
+        history.record(snapshot("a", 1), 10);
+        history.record(snapshot("b", 1), 10);
+        history.record(snapshot("c", 1), 10);
+        history.undo();
+        history.undo();
+        history.record(snapshot("a", 5), 10);
+
+        assert!(!history.can_redo());
+        assert!(history.redo().is_none());
+
+        let mut state = snapshot("a", 5);
+        history.undo().unwrap().revert(&mut state);
+        assert_eq!(state, snapshot("a", 1));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn an_edit_only_holds_the_changed_values() {
+        let edit = sut::Edit::between(&snapshot("a", 1), &snapshot("a", 2));
+
+        assert_eq!(
+            edit.changes,
+            vec![sut::Change {
+                pointer: "/app_state/target/timeout".to_string(),
+                before: json!(1),
+                after: json!(2),
+            }]
+        );
+        assert!(sut::Edit::between(&snapshot("a", 1), &snapshot("a", 1)).is_empty());
+    }
+
+    #[test]
+    fn the_keys_of_an_edit_are_escaped() {
+        let mut edited = snapshot("a", 1);
+        edited.0["app_state"]["target"]["headers"]["/pets"] = json!("X-Cats");
+        let edit = sut::Edit::between(&snapshot("a", 1), &edited);
+
+        let mut state = snapshot("a", 1);
+        edit.apply(&mut state);
+
+        assert_eq!(state, edited);
+    }
+
+    #[test]
+    fn the_edits_past_the_depth_are_forgotten() {
+        let mut history = sut::UndoHistory::new();
+        history.record(snapshot("a", 1), 1);
+        history.record(snapshot("b", 1), 1);
+        history.record(snapshot("c", 1), 1);
+
+        assert!(history.undo().is_some());
+        assert!(history.undo().is_none());
+        assert_eq!(history.recorded, Some(snapshot("b", 1)));
+    }
+}
@@ -4,6 +4,8 @@ pub mod central_navigation;
 pub mod command_sender;
 pub mod context;
 pub mod fuzzing;
+pub mod history;
 pub mod main_navigation;
+pub mod recovery;
 pub mod sequencer;
 pub mod ui;
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use qr_explore::amos::AMOS;
+use qr_explore::explore::ExplorationResult;
+
+use crate::app_state::AppState;
+
+pub const RECOVERY_PATH: &str = "./data/recovery.json";
+
+/// Exists while the viewer runs, left behind if it did not shut down cleanly
+pub const SESSION_LOCK_PATH: &str = "./data/session.lock";
+
+pub const AUTOSAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// The project and the results of a session, as last autosaved
+#[derive(serde::Deserialize)]
+pub struct Recovery {
+    pub app_state: AppState,
+    pub amos: AMOS,
+    pub results: Vec<ExplorationResult>,
+}
+
+/// Start a session. The recovery of the last session is returned if it did
+///  not shut down cleanly.
+pub fn start_session() -> Option<Recovery> {
+    let unclean = Path::new(SESSION_LOCK_PATH).exists();
+    if let Err(e) = std::fs::write(SESSION_LOCK_PATH, std::process::id().to_string()) {
+        tracing::error!("Failed to write the session lock: {}", e);
+    }
+
+    if !unclean {
+        return None;
+    }
+
+    let recovery = std::fs::read(RECOVERY_PATH)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_slice(&content).map_err(|e| e.to_string()));
+    match recovery {
+        Ok(recovery) => Some(recovery),
+        Err(e) => {
+            tracing::info!(
+                "The last session did not shut down cleanly, no recovery: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// End the session cleanly, its recovery is no longer needed
+pub fn end_session() {
+    for path in [RECOVERY_PATH, SESSION_LOCK_PATH] {
+        if let Err(e) = std::fs::remove_file(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::error!("Failed to remove {}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Write the recovery of the session. It is written next to the recovery
+///  file and then moved, so a crash while writing keeps the last one.
+pub fn autosave(
+    app_state: &AppState,
+    amos: &AMOS,
+    results: &[ExplorationResult],
+) -> Result<(), String> {
+    let recovery = serde_json::json!({
+        "app_state": app_state,
+        "amos": amos,
+        "results": results,
+    });
+    let payload = serde_json::to_vec(&recovery).map_err(|e| e.to_string())?;
+
+    let partial_path = format!("{}.partial", RECOVERY_PATH);
+    std::fs::write(&partial_path, payload).map_err(|e| e.to_string())?;
+    std::fs::rename(&partial_path, RECOVERY_PATH).map_err(|e| e.to_string())
+}
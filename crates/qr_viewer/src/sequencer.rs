@@ -1,9 +1,15 @@
 use qr_explore::amos;
-use qr_explore::{behaviours::Behaviour, exploration_settings::StateMutationSettings};
+use qr_explore::amos_generation::{GeneratedOperation, ParameterValue, RegexPattern};
+use qr_explore::behaviours::Behaviour;
+use qr_explore::exploration_settings::{
+    BehaviourSettings, DeprecatedOperations, ExplorationSettings, StateMutationSettings,
+    StringGeneration,
+};
+use qr_explore::manual;
 
 use crate::fuzzing::{Property, PropertySettings};
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SequenceParameter {
     //amos_param_id: usize,
     // TODO: fix for other schema types
@@ -11,16 +17,18 @@ pub struct SequenceParameter {
     pub name: String,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum SequenceItem {
     Operation {
         amos_op_id: usize,
         parameters: Vec<SequenceParameter>,
     },
-    // TODO: there will prob. be different params etc for diff. behaviours
     Behaviour {
         behaviour: Behaviour,
         parameters: StateMutationSettings,
+        /// The settings only the behaviour has
+        #[serde(default)]
+        settings: BehaviourSettings,
     },
     Fuzzer {
         property: Property,
@@ -43,6 +51,135 @@ impl SequenceItem {
             parameters,
         }
     }
+
+    /// The operation with the values of the generated one as its templates,
+    ///  a parameter without a value keeps the default template
+    pub fn from_generated(
+        id: usize,
+        params: &[amos::Parameter],
+        generated: &GeneratedOperation,
+    ) -> Self {
+        let parameters = params
+            .iter()
+            .map(|param| {
+                let value = generated
+                    .parameters
+                    .iter()
+                    .find(|gen_param| gen_param.name == param.name)
+                    .map(|gen_param| &gen_param.value)
+                    .filter(|value| !matches!(value, ParameterValue::Empty));
+                SequenceParameter {
+                    template: value.map_or("[a-z]*".to_string(), |value| {
+                        literal(&manual::value_text(value))
+                    }),
+                    name: param.name.clone(),
+                }
+            })
+            .collect();
+
+        SequenceItem::Operation {
+            amos_op_id: id,
+            parameters,
+        }
+    }
+
+    /// Add the operations to the query operations of a behaviour, or to the
+    ///  operations of a fuzzer. Returns the number of operations that were
+    ///  not already added.
+    pub fn add_operations(&mut self, ids: &[usize]) -> usize {
+        let operations = match self {
+            SequenceItem::Operation { .. } => return 0,
+            SequenceItem::Behaviour { parameters, .. } => &mut parameters.query_operation_ids,
+            SequenceItem::Fuzzer { settings, .. } => &mut settings.operations,
+        };
+
+        add_operation_ids(operations, ids)
+    }
+}
+
+/// Add the ids not already among the operations, returns how many were added
+pub fn add_operation_ids(operations: &mut Vec<usize>, ids: &[usize]) -> usize {
+    let before = operations.len();
+    for id in ids {
+        if !operations.contains(id) {
+            operations.push(*id);
+        }
+    }
+    operations.len() - before
+}
+
+/// The template matching only the value. Only the metacharacters of the
+///  regex syntax of the generators are escaped.
+fn literal(value: &str) -> String {
+    let mut template = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            template.push('\\');
+        }
+        template.push(c);
+    }
+    template
+}
+
+/// The operation with the schemas of its parameters defined in the sequence UI
+pub fn templated_operation(
+    operation: &amos::Operation,
+    parameters: &[SequenceParameter],
+) -> amos::Operation {
+    let mut templated = operation.clone();
+    for (param, sequence_param) in templated.parameters.iter_mut().zip(parameters) {
+        param.schema = amos::Schema::StringRegex {
+            regex: RegexPattern::new(sequence_param.template.clone()),
+        };
+    }
+    templated
+}
+
+/// The settings a behaviour of a sequence is explored with, its own in place
+///  of the ones of the exploration
+pub fn behaviour_settings(
+    exploration: &ExplorationSettings,
+    parameters: &StateMutationSettings,
+    settings: &BehaviourSettings,
+) -> ExplorationSettings {
+    ExplorationSettings {
+        state_mutation: parameters.clone(),
+        behaviour: settings.clone(),
+        ..exploration.clone()
+    }
+}
+
+/// The settings a fuzzer of a sequence is explored with, one operation in
+///  each of its tests
+pub fn fuzzer_settings(
+    exploration: &ExplorationSettings,
+    property: &Property,
+    settings: &PropertySettings,
+) -> ExplorationSettings {
+    let parameters = StateMutationSettings {
+        number_of_tests: 100,
+        min_length: 1,
+        max_length: 1,
+        query_operation_ids: settings.operations.clone(),
+        selected_query_operation: None,
+        coverage_guided: false,
+        follow_links: settings.follow_links,
+        harvest_responses: false,
+        learn_precedence: false,
+        mutate_sequences: false,
+        honor_cache_headers: false,
+        corpus_dir: None,
+        script_file: None,
+        deprecated: DeprecatedOperations::Include,
+        strings: StringGeneration::Ascii,
+        seed: None,
+        shrinking: exploration.state_mutation.shrinking.clone(),
+    };
+    behaviour_settings(
+        exploration,
+        &parameters,
+        &BehaviourSettings::new(&property.behaviour()),
+    )
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -63,6 +200,31 @@ impl Sequence {
         }
     }
 
+    /// The sequence of an example, to vary it by hand. The operations not in
+    ///  the AMOS are left out.
+    pub fn from_example(name: &str, sequence: &[GeneratedOperation], amos: &amos::AMOS) -> Self {
+        let items = sequence
+            .iter()
+            .filter_map(|generated| {
+                let id = amos
+                    .operations
+                    .iter()
+                    .position(|op| op.info.name == generated.name)?;
+                Some(SequenceItem::from_generated(
+                    id,
+                    &amos.operations[id].parameters,
+                    generated,
+                ))
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            items,
+            ..Self::new()
+        }
+    }
+
     pub fn remove_selected(&mut self) {
         if self.selected < self.items.len() {
             self.items.remove(self.selected);
@@ -100,6 +262,12 @@ impl Sequencer {
         self.sequences.push(Sequence::new())
     }
 
+    /// Add the sequence and select it
+    pub fn open(&mut self, sequence: Sequence) {
+        self.sequences.push(sequence);
+        self.selected_sequence_id = Some(self.sequences.len() - 1);
+    }
+
     pub fn selected_sequence(&self) -> Option<&Sequence> {
         if let Some(id) = self.selected_sequence_id {
             Some(&self.sequences[id])
@@ -116,6 +284,13 @@ impl Sequencer {
         }
     }
 
+    /// The selected item of the selected sequence
+    pub fn selected_item_as_mut(&mut self) -> Option<&mut SequenceItem> {
+        let sequence = self.selected_sequence_as_mut()?;
+        let selected = sequence.selected;
+        sequence.items.get_mut(selected)
+    }
+
     pub fn push_item_to_selected(&mut self, item: SequenceItem) {
         if let Some(id) = self.selected_sequence_id {
             self.sequences[id].items.push(item);
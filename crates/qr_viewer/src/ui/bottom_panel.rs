@@ -1,10 +1,13 @@
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 use qr_explore::amos;
+use qr_specification_manager::lint::LintKind;
+use tracing::error;
 
 use crate::{
     app::{App, DetailsNavigation},
     central_navigation,
+    command_sender::UICommand,
 };
 
 pub fn bottom_panel(ctx: &egui::Context, app: &mut App) {
@@ -52,6 +55,21 @@ pub fn bottom_panel(ctx: &egui::Context, app: &mut App) {
 
                     ui.separator();
 
+                    let lint_label = match app.lint_report.as_ref() {
+                        Some(report) if !report.is_clean() => {
+                            format!("Lint ({})", report.findings.len())
+                        }
+                        _ => "Lint".to_string(),
+                    };
+                    ui.selectable_value(
+                        &mut app.selected_details_navigation,
+                        DetailsNavigation::Lint,
+                        lint_label,
+                    )
+                    .on_hover_text("The quality of the specification, before exploring it");
+
+                    ui.separator();
+
                     ui.selectable_value(
                         &mut app.selected_details_navigation,
                         DetailsNavigation::Details,
@@ -59,6 +77,35 @@ pub fn bottom_panel(ctx: &egui::Context, app: &mut App) {
                     );
 
                     ui.separator();
+
+                    if let Some(progress) = app.test_progress() {
+                        let remaining = match progress.remaining() {
+                            Some(remaining) => format!(", ~{} s left", remaining.as_secs()),
+                            None => String::new(),
+                        };
+                        ui.add(
+                            egui::ProgressBar::new(progress.fraction())
+                                .desired_width(240.0)
+                                .text(format!(
+                                    "Test {}/{}{}",
+                                    progress.completed, progress.total, remaining
+                                )),
+                        )
+                        .on_hover_text("The tests of the run");
+
+                        let label = if app.is_paused() {
+                            "▶ Resume"
+                        } else {
+                            "⏸ Pause"
+                        };
+                        if ui
+                            .button(label)
+                            .on_hover_text("Free up the SUT between test cases, and continue later")
+                            .clicked()
+                        {
+                            app.command_sender.send_ui(UICommand::TogglePause);
+                        }
+                    }
                 });
             });
 
@@ -71,6 +118,9 @@ pub fn bottom_panel(ctx: &egui::Context, app: &mut App) {
                     DetailsNavigation::Problems => {
                         problems(app, ui);
                     }
+                    DetailsNavigation::Lint => {
+                        lint(app, ui);
+                    }
                     DetailsNavigation::Details => {
                         context_details(app, ui);
                     }
@@ -221,6 +271,81 @@ pub fn problems(app: &mut App, ui: &mut egui::Ui) {
     }
 }
 
+pub fn lint(app: &mut App, ui: &mut egui::Ui) {
+    let Some(report) = app.lint_report.as_ref() else {
+        ui.label("Open a specification to lint it");
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        for kind in LintKind::ALL {
+            ui.label(format!("{}: {}", kind.presentation(), report.count(kind)));
+            ui.separator();
+        }
+        if ui
+            .button("💾 Export")
+            .on_hover_text("Save the lint report as JSON")
+            .clicked()
+        {
+            if let Some(file) = rfd::FileDialog::new()
+                .add_filter("Lint report", &["json"])
+                .set_file_name("lint.json")
+                .save_file()
+            {
+                if let Err(e) = report.save(&file) {
+                    error!("Failed to save the lint report: {}", e);
+                }
+            }
+        }
+    });
+
+    let table = TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::auto())
+        .column(Column::remainder());
+
+    table
+        .header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong("#");
+            });
+
+            header.col(|ui| {
+                ui.strong("Kind");
+            });
+
+            header.col(|ui| {
+                ui.strong("Operation");
+            });
+
+            header.col(|ui| {
+                ui.strong("Message");
+            });
+        })
+        .body(|mut body| {
+            for (idx, finding) in report.findings.iter().enumerate() {
+                body.row(18.0, |mut row| {
+                    row.col(|ui| {
+                        ui.label((idx + 1).to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(finding.kind.presentation());
+                    });
+                    row.col(|ui| {
+                        ui.label(finding.operation.as_deref().unwrap_or("-"));
+                    });
+                    row.col(|ui| {
+                        ui.label(&finding.message);
+                    });
+                });
+            }
+        });
+}
+
 pub fn context_details(app: &mut App, ui: &mut egui::Ui) {
     match app.app_state.central_navigation.selected {
         central_navigation::Navigations::Invocations => {
@@ -234,27 +359,39 @@ pub fn context_details(app: &mut App, ui: &mut egui::Ui) {
         central_navigation::Navigations::Progress => (),
         central_navigation::Navigations::Sequencer => context_details_sequencer(app, ui),
         central_navigation::Navigations::APIs => (),
+        central_navigation::Navigations::Dependencies => (),
     }
 }
 
-fn context_details_invocations(app: &App, ui: &mut egui::Ui) {
-    if !app.invocation_results.is_empty() {
-        let selected = &app.invocation_results[app.selected_result];
+fn context_details_invocations(app: &mut App, ui: &mut egui::Ui) {
+    if let Some(selected) = app.invocations.get(app.selected_result) {
+        ui.horizontal(|ui| {
+            ui.label("Operation:");
+            ui.label(app.amos.display_name(&selected.operation.name));
+
+            if ui
+                .button("⬇ Sequence")
+                .on_hover_text("Show the sequence of the invocation")
+                .clicked()
+            {
+                app.navigate_to_invocation(
+                    app.selected_result,
+                    central_navigation::Navigations::Sequences,
+                );
+            }
+        });
 
-        ui.label("Operation:");
-        ui.label(
-            app.invocation_results[app.selected_result]
-                .operation
-                .name
-                .clone(),
-        );
+        if let Some(error) = selected.transport_error {
+            ui.label(format!("No response: {}", error));
+        }
+
+        if let Some(id) = &selected.correlation_id {
+            ui.label(format!("Correlation id: {}", id));
+        }
 
-        // Result - prob some expantion here
         ui.collapsing("Result", |ui| {
-            ui.monospace(selected.result.clone());
+            app.payload_inspector.show(ui, &selected.result);
         });
-
-        ui.monospace(selected.result.clone());
     };
 }
 
@@ -270,8 +407,8 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                     if !app.invocation_spans.is_empty() {
                         let selected_span = &app.invocation_spans[app.selected_span];
 
-                        let span_results =
-                            &app.invocation_results[selected_span.start..selected_span.end + 1];
+                        let span_results = &app.invocations.summaries()
+                            [selected_span.start..selected_span.end + 1];
 
                         let table = TableBuilder::new(ui)
                             .striped(true)
@@ -302,7 +439,7 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                                                 .selectable_value(
                                                     &mut app.selected_details_sequence_operation,
                                                     idx,
-                                                    op.operation.name.clone(),
+                                                    app.amos.display_name(&op.operation),
                                                 )
                                                 .clicked()
                                             {
@@ -326,22 +463,30 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                 .default_width(450.0)
                 .width_range(450.0..=600.0)
                 .show_inside(ui, |ui| {
-                    if !app.invocation_spans.is_empty() {
-                        let selected_span = &app.invocation_spans[app.selected_span];
-                        let selected_span_invocation = &app.invocation_results
-                            [selected_span.start + app.selected_details_sequence_operation];
+                    let selected_span_invocation = app
+                        .invocation_spans
+                        .get(app.selected_span)
+                        .and_then(|span| {
+                            app.invocations
+                                .get(span.start + app.selected_details_sequence_operation)
+                        });
+
+                    if let Some(selected_span_invocation) = selected_span_invocation {
                         egui::Grid::new("operation_details_grid")
                             .num_columns(2)
                             .spacing([4.0, 4.0])
                             .striped(true)
                             .show(ui, |ui| {
                                 ui.strong("Name");
-                                ui.label(selected_span_invocation.operation.name.clone());
+                                ui.label(
+                                    app.amos
+                                        .display_name(&selected_span_invocation.operation.name),
+                                );
                                 ui.end_row();
 
                                 if let Some(meta) = &selected_span_invocation.meta_data {
                                     match meta {
-                                        amos::ResultMetaData::HTTP { url, status } => {
+                                        amos::ResultMetaData::HTTP { url, status, .. } => {
                                             ui.strong("URL");
                                             ui.label(url);
                                             ui.end_row();
@@ -352,7 +497,37 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                                         }
                                     }
                                 }
+
+                                if let Some(error) = selected_span_invocation.transport_error {
+                                    ui.strong("Transport error");
+                                    ui.label(error.to_string());
+                                    ui.end_row();
+                                }
+
+                                if let Some(id) = &selected_span_invocation.correlation_id {
+                                    ui.strong("Correlation id");
+                                    ui.label(id);
+                                    ui.end_row();
+                                }
                             });
+
+                        ui.collapsing("Response", |ui| {
+                            app.payload_inspector
+                                .show(ui, &selected_span_invocation.result);
+                        });
+
+                        if ui
+                            .button("Invocations")
+                            .on_hover_text("Show the invocation in the list of invocations")
+                            .clicked()
+                        {
+                            let idx = app.invocation_spans[app.selected_span].start
+                                + app.selected_details_sequence_operation;
+                            app.navigate_to_invocation(
+                                idx,
+                                central_navigation::Navigations::Invocations,
+                            );
+                        }
                     }
                 });
 
@@ -360,10 +535,15 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                 .default_width(450.0)
                 .width_range(450.0..=600.0)
                 .show_inside(ui, |ui| {
-                    if !app.invocation_spans.is_empty() {
-                        let selected_span = &app.invocation_spans[app.selected_span];
-                        let selected_span_invocation = &app.invocation_results
-                            [selected_span.start + app.selected_details_sequence_operation];
+                    let selected_span_invocation = app
+                        .invocation_spans
+                        .get(app.selected_span)
+                        .and_then(|span| {
+                            app.invocations
+                                .get(span.start + app.selected_details_sequence_operation)
+                        });
+
+                    if let Some(selected_span_invocation) = selected_span_invocation {
                         let table = TableBuilder::new(ui)
                             .striped(true)
                             .resizable(true)
@@ -398,8 +578,9 @@ fn context_details_sequences(app: &mut App, ui: &mut egui::Ui) {
                                             ui.label(format!("{:?}", param.value));
                                         });
 
+                                        // The reference and why it was chosen
                                         row.col(|ui| {
-                                            ui.label(format!("{:?}", param.ref_path));
+                                            ui.label(param.ref_path.as_deref().unwrap_or("-"));
                                         });
                                     });
                                 }
@@ -0,0 +1,124 @@
+use eframe::egui;
+
+use crate::command_sender::{CommandSender, UICommand};
+
+/// The command palette lists the commands matching the query, the selected one
+///  is run on enter and the others by click.
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, command_sender: &CommandSender) {
+        if !self.open {
+            return;
+        }
+
+        let commands = self.matching_commands();
+        self.selected = self.selected.min(commands.len().saturating_sub(1));
+
+        let (close, up, down, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if down && self.selected + 1 < commands.len() {
+            self.selected += 1;
+        }
+
+        let mut picked = if enter {
+            commands.get(self.selected).copied()
+        } else {
+            None
+        };
+
+        egui::Window::new("Command palette")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .fixed_size([400.0, 0.0])
+            .show(ctx, |ui| {
+                let query = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("Type a command")
+                        .desired_width(f32::INFINITY),
+                );
+                query.request_focus();
+                if query.changed() {
+                    self.selected = 0;
+                }
+
+                ui.separator();
+
+                if commands.is_empty() {
+                    ui.label("No matching command");
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for (idx, command) in commands.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let row = ui.selectable_label(idx == self.selected, command.text());
+                                if idx == self.selected && (up || down) {
+                                    row.scroll_to_me(None);
+                                }
+                                if row.clicked() {
+                                    picked = Some(*command);
+                                }
+
+                                if let Some(shortcut) = command.shortcut() {
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| ui.weak(ctx.format_shortcut(&shortcut)),
+                                    );
+                                }
+                            });
+                        }
+                    });
+            });
+
+        if let Some(command) = picked {
+            self.open = false;
+            // The palette is closed above, toggling it again would re-open it
+            if command != UICommand::ToggleCommandPalette {
+                command_sender.send_ui(command);
+            }
+        } else if close {
+            self.open = false;
+        }
+    }
+
+    /// The commands containing every word of the query, ignoring case
+    fn matching_commands(&self) -> Vec<UICommand> {
+        let query = self.query.to_lowercase();
+
+        UICommand::all()
+            .into_iter()
+            .filter(|command| {
+                let text = command.text().to_lowercase();
+                query.split_whitespace().all(|word| text.contains(word))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use eframe::egui;
+use qr_explore::amos::{InvokeResult, ResultMetaData};
+use qr_explore::{explore, manual};
+
+use crate::app::App;
+use crate::ui::payload_inspector::PayloadInspector;
+
+/// Invokes one operation with values filled in by hand, drawn once from the
+///  generators of its parameters
+#[derive(Default)]
+pub struct Console {
+    operation: Option<usize>,
+    values: Vec<String>,
+    seed: u64,
+    error: Option<String>,
+    // The results of the invocation, until it is done
+    pending: Option<Receiver<Option<Vec<InvokeResult>>>>,
+    response: Option<InvokeResult>,
+    inspector: PayloadInspector,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn draw(&mut self, app: &App) {
+        let Some(operation) = self.operation.and_then(|idx| app.amos.operations.get(idx)) else {
+            return;
+        };
+
+        // With the generators the parameters have in an exploration
+        let mut ops = vec![operation.clone()];
+        app.app_state
+            .exploration_settings
+            .generators
+            .apply(&mut ops);
+        self.values = manual::draw_values(&ops[0], self.seed);
+    }
+
+    fn poll(&mut self) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+
+        match pending.try_recv() {
+            Ok(Some(mut results)) => {
+                self.response = results.pop();
+                self.pending = None;
+            }
+            Ok(None) => {
+                self.error =
+                    Some("The parameters could not be translated to a request".to_string());
+                self.pending = None;
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.error = Some("The invocation did not complete".to_string());
+                self.pending = None;
+            }
+            Err(TryRecvError::Empty) => (),
+        }
+    }
+}
+
+pub fn console_window(ctx: &egui::Context, app: &mut App) {
+    let mut open = app.show_console;
+    let mut console = std::mem::take(&mut app.console);
+    console.poll();
+
+    egui::Window::new("Console")
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let selected_name = console
+                .operation
+                .and_then(|idx| app.amos.operations.get(idx))
+                .map_or("-".to_string(), |op| op.info.display_name().to_string());
+            let previous = console.operation;
+            egui::ComboBox::from_label("Operation")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (idx, op) in app.amos.operations.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut console.operation,
+                            Some(idx),
+                            op.info.display_name(),
+                        );
+                    }
+                });
+            if console.operation != previous {
+                console.error = None;
+                console.response = None;
+                console.draw(app);
+            }
+
+            let Some(operation) = console
+                .operation
+                .and_then(|idx| app.amos.operations.get(idx))
+                .cloned()
+            else {
+                ui.label("Select an operation to invoke");
+                return;
+            };
+
+            egui::Grid::new("console_parameters")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (param, value) in operation.parameters.iter().zip(&mut console.values) {
+                        let schema = if param.required {
+                            format!("{}, required", param.schema)
+                        } else {
+                            param.schema.to_string()
+                        };
+                        ui.label(&param.name).on_hover_text(schema);
+                        ui.text_edit_singleline(value);
+                        ui.end_row();
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                if ui.button("🎲").on_hover_text("Draw other values").clicked() {
+                    console.seed = explore::random_seed();
+                    console.draw(app);
+                }
+
+                let running = console.pending.is_some();
+                if ui
+                    .add_enabled(!running, egui::Button::new("▶ Invoke"))
+                    .clicked()
+                {
+                    console.error = None;
+                    console.response = None;
+                    match manual::manual_operation(&operation, &console.values) {
+                        Ok(generated) => console.pending = Some(app.invoke_manual(generated)),
+                        Err(e) => console.error = Some(e),
+                    }
+                }
+                if running {
+                    ui.spinner();
+                    ctx.request_repaint();
+                }
+            });
+
+            if let Some(error) = &console.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            if let Some(response) = &console.response {
+                ui.separator();
+                response_ui(ui, response, &mut console.inspector);
+            }
+        });
+
+    app.console = console;
+    app.show_console = open;
+}
+
+fn response_ui(ui: &mut egui::Ui, response: &InvokeResult, inspector: &mut PayloadInspector) {
+    if let Some(error) = &response.transport_error {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!("No response: {}", error),
+        );
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if let Some(ResultMetaData::HTTP { url, status, .. }) = &response.meta_data {
+            let color = if response.success {
+                egui::Color32::GREEN
+            } else {
+                ui.visuals().error_fg_color
+            };
+            ui.colored_label(color, status.to_string());
+            ui.monospace(url);
+        }
+        if let Some(duration) = response.duration {
+            ui.label(format!("{} ms", duration.as_millis()));
+        }
+    });
+
+    if let Some(ResultMetaData::HTTP { headers, .. }) = &response.meta_data {
+        ui.collapsing("Headers", |ui| {
+            for (name, value) in headers {
+                ui.monospace(format!("{}: {}", name, value));
+            }
+        });
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| inspector.show(ui, &response.result));
+}
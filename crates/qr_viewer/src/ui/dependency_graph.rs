@@ -0,0 +1,216 @@
+use eframe::egui;
+use petgraph::visit::EdgeRef;
+use qr_explore::amos_relations::{Relation, RelationHint};
+
+use crate::app::App;
+
+const NODE_RADIUS: f32 = 6.0;
+
+pub fn dependency_graph(app: &mut App, ui: &mut egui::Ui) {
+    if app.amos.operations.is_empty() {
+        ui.label("No operations to show");
+        return;
+    }
+
+    egui::SidePanel::right("dependency_details")
+        .resizable(true)
+        .default_width(300.0)
+        .width_range(80.0..=500.0)
+        .show_inside(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    relation_hints(app, ui);
+                    ui.separator();
+                    dependency_details(app, ui)
+                });
+        });
+
+    egui::CentralPanel::default().show_inside(ui, |ui| graph_view(app, ui));
+}
+
+/// Place the operations evenly on a circle that fits the given rect
+fn node_positions(rect: egui::Rect, count: usize) -> Vec<egui::Pos2> {
+    let center = rect.center();
+    // leave room for the node labels
+    let radius = (rect.width().min(rect.height()) / 2.0 - 40.0).max(NODE_RADIUS);
+
+    (0..count)
+        .map(|idx| {
+            let angle = std::f32::consts::TAU * idx as f32 / count as f32;
+            center + radius * egui::vec2(angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+fn graph_view(app: &mut App, ui: &mut egui::Ui) {
+    let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::click());
+
+    let positions = node_positions(response.rect, app.amos.operations.len());
+    let graph = &app.dependency_graph;
+    let visuals = ui.visuals();
+
+    let node_at = |pos: egui::Pos2| {
+        positions
+            .iter()
+            .position(|p| p.distance(pos) <= NODE_RADIUS * 2.0)
+    };
+
+    let hovered = response.hover_pos().and_then(node_at);
+
+    // Edges first, to have the nodes drawn on top
+    for edge in graph.edge_references() {
+        let source = graph[edge.source()];
+        let target = graph[edge.target()];
+
+        let highlighted = app.selected_dependency_operation == Some(source)
+            || app.selected_dependency_operation == Some(target);
+
+        let stroke = if highlighted {
+            egui::Stroke::new(2.0, egui::Color32::from_rgb(23, 147, 209))
+        } else {
+            egui::Stroke::new(1.0, visuals.weak_text_color())
+        };
+
+        let from = positions[source];
+        let to = positions[target];
+        let direction = (to - from).normalized();
+
+        painter.arrow(
+            from + direction * NODE_RADIUS,
+            (to - from) - direction * NODE_RADIUS * 2.0,
+            stroke,
+        );
+    }
+
+    for (idx, pos) in positions.iter().enumerate() {
+        let fill = if app.selected_dependency_operation == Some(idx) {
+            egui::Color32::from_rgb(23, 147, 209)
+        } else {
+            visuals.widgets.inactive.bg_fill
+        };
+
+        painter.circle(*pos, NODE_RADIUS, fill, visuals.widgets.inactive.fg_stroke);
+
+        // Only label the nodes of interest to keep large APIs readable
+        let labeled = app.amos.operations.len() <= 20
+            || hovered == Some(idx)
+            || app.selected_dependency_operation == Some(idx);
+
+        if labeled {
+            painter.text(
+                *pos + egui::vec2(0.0, NODE_RADIUS * 2.0),
+                egui::Align2::CENTER_TOP,
+                app.amos.operations[idx].info.display_name(),
+                egui::FontId::proportional(12.0),
+                visuals.text_color(),
+            );
+        }
+    }
+
+    if response.clicked() {
+        app.selected_dependency_operation = response.interact_pointer_pos().and_then(node_at);
+    }
+}
+
+/// The relations declared by the user, used by the generation before the
+///  relations inferred from the names
+fn relation_hints(app: &mut App, ui: &mut egui::Ui) {
+    ui.collapsing("Relation hints", |ui| {
+        let mut changed = false;
+
+        let mut removed = None;
+        for (idx, hint) in app.amos.relation_hints.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                    removed = Some(idx);
+                }
+                ui.monospace(hint.to_string());
+            });
+        }
+        if let Some(idx) = removed {
+            app.amos.relation_hints.remove(idx);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut app.relation_hint_input)
+                    .hint_text("operation:parameter <- operation:$.field"),
+            );
+            if ui.button("Add").clicked() {
+                match app.relation_hint_input.parse::<RelationHint>() {
+                    Ok(hint) => {
+                        app.amos.relation_hints.push(hint);
+                        app.relation_hint_input.clear();
+                        app.relation_hint_error = None;
+                        changed = true;
+                    }
+                    Err(e) => app.relation_hint_error = Some(e),
+                }
+            }
+        });
+
+        if let Some(error) = &app.relation_hint_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        if changed {
+            app.save_amos();
+        }
+    });
+}
+
+fn dependency_details(app: &App, ui: &mut egui::Ui) {
+    let Some(selected) = app.selected_dependency_operation else {
+        ui.label("Select an operation in the graph");
+        return;
+    };
+
+    let graph = &app.dependency_graph;
+    let operations = &app.amos.operations;
+
+    ui.strong(operations[selected].info.display_name());
+
+    ui.collapsing("Supplied by", |ui| {
+        for edge in graph.edge_references() {
+            if graph[edge.target()] == selected {
+                ui.label(format!(
+                    "{} <- {}",
+                    edge.weight().parameter,
+                    relation_presentation(&edge.weight().relation)
+                ));
+            }
+        }
+    });
+
+    ui.collapsing("Supplies", |ui| {
+        for edge in graph.edge_references() {
+            if graph[edge.source()] == selected {
+                ui.label(format!(
+                    "{}/{} <- {}",
+                    operations[graph[edge.target()]].info.display_name(),
+                    edge.weight().parameter,
+                    relation_presentation(&edge.weight().relation)
+                ));
+            }
+        }
+    });
+}
+
+fn relation_presentation(relation: &Relation) -> String {
+    match relation {
+        Relation::Parameter(info) => format!(
+            "{} parameter {} ({})",
+            info.operation,
+            info.name,
+            info.explanation()
+        ),
+        Relation::Response(info) => format!(
+            "{} response {} ({})",
+            info.operation,
+            info.name,
+            info.explanation()
+        ),
+    }
+}
@@ -0,0 +1,28 @@
+use eframe::egui;
+
+/// Darken the window while files are dragged over it, the dropped
+///  specifications are opened by the app
+pub fn drop_overlay(ctx: &egui::Context) {
+    let hovered = ctx.input(|i| i.raw.hovered_files.len());
+    if hovered == 0 {
+        return;
+    }
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("file_drop_overlay"),
+    ));
+    let screen = ctx.screen_rect();
+    painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(192));
+    painter.text(
+        screen.center(),
+        egui::Align2::CENTER_CENTER,
+        if hovered == 1 {
+            "Drop to open the specification".to_string()
+        } else {
+            format!("Drop to merge the {} specifications", hovered)
+        },
+        egui::TextStyle::Heading.resolve(&ctx.style()),
+        egui::Color32::WHITE,
+    );
+}
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use eframe::egui;
+use qr_explore::invocation_log::{InvocationSummary, StatusFilter};
+
+/// The filter bar of the list panels. The text matches as a substring, or as a
+///  regex when enabled, and the kinds given by the panel narrow the list to one
+///  kind of item, e.g. a log level. The status narrows it to the items with an
+///  invocation of the status, for the panels with invocations.
+#[derive(Default)]
+pub struct FilterBar {
+    pub text: String,
+    pub use_regex: bool,
+    pub kind: Option<&'static str>,
+    pub status: StatusFilter,
+    regex: Option<regex::Regex>,
+    compiled_text: String,
+}
+
+impl FilterBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, kinds: &[&'static str], with_status: bool) {
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.text).desired_width(150.0));
+            ui.checkbox(&mut self.use_regex, "Regex");
+
+            if !kinds.is_empty() {
+                ui.separator();
+                ui.selectable_value(&mut self.kind, None, "All");
+                for kind in kinds {
+                    ui.selectable_value(&mut self.kind, Some(*kind), *kind);
+                }
+            }
+        });
+
+        if with_status {
+            ui.horizontal(|ui| {
+                ui.label("Status");
+                for status in StatusFilter::ALL {
+                    let text = status.to_string();
+                    ui.selectable_value(&mut self.status, status, text);
+                }
+            });
+        }
+
+        if self.compiled_text != self.text {
+            self.compiled_text = self.text.clone();
+            self.regex = regex::RegexBuilder::new(&self.text)
+                .case_insensitive(true)
+                .build()
+                .ok();
+        }
+
+        if self.use_regex && !self.text.is_empty() && self.regex.is_none() {
+            ui.colored_label(ui.visuals().error_fg_color, "Invalid regex");
+        }
+    }
+
+    /// The keys of the invocations of the selected status, e.g. their
+    ///  operations, to match the items by. None if any status is selected.
+    pub fn with_status(
+        &self,
+        summaries: &[InvocationSummary],
+        key: impl Fn(&InvocationSummary) -> Option<&str>,
+    ) -> Option<HashSet<String>> {
+        if self.status == StatusFilter::All {
+            return None;
+        }
+
+        Some(
+            summaries
+                .iter()
+                .filter(|summary| self.status.matches(summary))
+                .filter_map(|summary| key(summary).map(str::to_string))
+                .collect(),
+        )
+    }
+
+    pub fn matches(&self, text: &str, kind: &str) -> bool {
+        if self.kind.is_some_and(|selected| selected != kind) {
+            return false;
+        }
+
+        if self.text.is_empty() {
+            return true;
+        }
+
+        if self.use_regex {
+            // Nothing matches an invalid regex, until it is fixed
+            self.regex
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(text))
+        } else {
+            text.to_lowercase().contains(&self.text.to_lowercase())
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use eframe::egui;
+use qr_explore::amos::{self, GeneratorSpec, Operation, Parameter, Schema};
+use qr_explore::amos_generation::{self as agen, ParameterValue, RegexPattern, ValuePreview};
+use qr_explore::exploration_settings::{GeneratorOverrides, PayloadStress};
+use qr_explore::explore;
+
+/// The schemas values can be generated for without an AMOS
+const SCHEMAS: [Schema; 12] = [
+    Schema::String,
+    Schema::StringNonEmpty,
+    Schema::StringDateTime,
+    Schema::Int,
+    Schema::Int8,
+    Schema::Int32,
+    Schema::Double,
+    Schema::Float,
+    Schema::Bool,
+    Schema::IPV4,
+    Schema::ArrayOfString,
+    Schema::File,
+];
+
+#[derive(PartialEq)]
+enum Source {
+    Schema(usize),
+    Regex,
+    /// One of the named generators
+    Generator(String),
+    /// The index of the operation and of its parameter in the AMOS
+    Parameter(usize, usize),
+}
+
+/// Previews the values generated for a parameter of the AMOS, a schema or a
+///  named generator, and the values they shrink to. Examples are generated
+///  alongside the schema, as the examples of a parameter are in an
+///  exploration. The generator of a parameter is assigned here.
+pub struct GenerationPlayground {
+    source: Source,
+    regex: String,
+    examples: String,
+    count: usize,
+    seed: u64,
+    previews: Vec<ValuePreview>,
+    error: Option<String>,
+    /// The name of the generator to add
+    new_generator: String,
+}
+
+impl Default for GenerationPlayground {
+    fn default() -> Self {
+        Self {
+            source: Source::Regex,
+            regex: "[a-z]*".to_string(),
+            examples: String::new(),
+            count: 10,
+            seed: 0,
+            previews: vec![],
+            error: None,
+            new_generator: String::new(),
+        }
+    }
+}
+
+impl GenerationPlayground {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        operations: &[Operation],
+        overrides: &mut GeneratorOverrides,
+    ) {
+        egui::CollapsingHeader::new("Generators").show(ui, |ui| {
+            generators_ui(ui, overrides, &mut self.new_generator);
+        });
+
+        egui::ComboBox::from_label("Generate")
+            .selected_text(self.source_name(operations))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.source, Source::Regex, "Regex");
+                for (idx, schema) in SCHEMAS.iter().enumerate() {
+                    ui.selectable_value(&mut self.source, Source::Schema(idx), schema.to_string());
+                }
+                if !overrides.generators.is_empty() {
+                    ui.separator();
+                    ui.weak("Generators");
+                }
+                for name in overrides.generators.keys() {
+                    ui.selectable_value(
+                        &mut self.source,
+                        Source::Generator(name.clone()),
+                        format!("⚙ {}", name),
+                    );
+                }
+                for (op_idx, op) in operations.iter().enumerate() {
+                    if op.parameters.is_empty() {
+                        continue;
+                    }
+                    ui.separator();
+                    ui.weak(&op.info.name);
+                    for (param_idx, param) in op.parameters.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut self.source,
+                            Source::Parameter(op_idx, param_idx),
+                            format!("{}: {}", param.name, param.schema),
+                        );
+                    }
+                }
+            });
+
+        egui::Grid::new("generation_playground_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                if self.source == Source::Regex {
+                    ui.label("Regex");
+                    ui.text_edit_singleline(&mut self.regex);
+                    ui.end_row();
+                }
+                if matches!(self.source, Source::Schema(_) | Source::Regex) {
+                    ui.label("Examples").on_hover_text("Comma separated values");
+                    ui.text_edit_singleline(&mut self.examples);
+                    ui.end_row();
+                }
+                if let Source::Parameter(op_idx, param_idx) = self.source {
+                    if let Some((op, param)) = operations
+                        .get(op_idx)
+                        .and_then(|op| Some((op, op.parameters.get(param_idx)?)))
+                    {
+                        ui.label("Generator")
+                            .on_hover_text("Generates the values in place of the schema");
+                        generator_assignment_ui(ui, overrides, &op.info.name, &param.name);
+                        ui.end_row();
+                    }
+                }
+
+                ui.label("Values");
+                ui.add(egui::DragValue::new(&mut self.count).clamp_range(1..=100));
+                ui.end_row();
+
+                ui.label("Seed");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.seed));
+                    if ui.button("🎲").on_hover_text("Random seed").clicked() {
+                        self.seed = explore::random_seed();
+                    }
+                });
+                ui.end_row();
+            });
+
+        if ui.button("Preview").clicked() {
+            self.preview(operations, overrides);
+        }
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (idx, preview) in self.previews.iter().enumerate() {
+                let id = ui.make_persistent_id(("generation_preview", idx));
+                egui::collapsing_header::CollapsingState::load_with_default_open(
+                    ui.ctx(),
+                    id,
+                    false,
+                )
+                .show_header(ui, |ui| {
+                    ui.monospace(value_text(&preview.value));
+                })
+                .body(|ui| {
+                    if preview.shrunk.is_empty() {
+                        ui.weak("Does not shrink");
+                    }
+                    for value in &preview.shrunk {
+                        ui.monospace(format!("↘ {}", value_text(value)));
+                    }
+                });
+            }
+        });
+    }
+
+    fn source_name(&self, operations: &[Operation]) -> String {
+        match &self.source {
+            Source::Schema(idx) => SCHEMAS[*idx].to_string(),
+            Source::Regex => "Regex".to_string(),
+            Source::Generator(name) => format!("⚙ {}", name),
+            Source::Parameter(op_idx, param_idx) => operations
+                .get(*op_idx)
+                .and_then(|op| Some((op, op.parameters.get(*param_idx)?)))
+                .map_or("-".to_string(), |(op, param)| {
+                    format!("{} {}", op.info.name, param.name)
+                }),
+        }
+    }
+
+    fn preview(&mut self, operations: &[Operation], overrides: &GeneratorOverrides) {
+        self.error = None;
+        self.previews = vec![];
+
+        let param = match &self.source {
+            Source::Parameter(op_idx, param_idx) => {
+                let Some(op) = operations.get(*op_idx) else {
+                    self.error = Some("The parameter is not in the AMOS".to_string());
+                    return;
+                };
+                // With the generator the parameter has in an exploration
+                let mut ops = vec![op.clone()];
+                overrides.apply(&mut ops);
+                match ops[0].parameters.get(*param_idx) {
+                    Some(param) => param.clone(),
+                    None => {
+                        self.error = Some("The parameter is not in the AMOS".to_string());
+                        return;
+                    }
+                }
+            }
+            Source::Generator(name) => match overrides.resolve(name) {
+                Some(generator) => Parameter {
+                    generator: Some(generator),
+                    ..self.parameter(Schema::String)
+                },
+                None => {
+                    self.error = Some(format!("The generator '{}' has no values", name));
+                    return;
+                }
+            },
+            Source::Schema(idx) => self.parameter(SCHEMAS[*idx].clone()),
+            Source::Regex => {
+                // An invalid regex would silently generate plain strings
+                let regex = RegexPattern::new(self.regex.clone());
+                if let Err(e) = regex.compile() {
+                    self.error = Some(format!("Invalid regex: {}", e));
+                    return;
+                }
+                self.parameter(Schema::StringRegex { regex })
+            }
+        };
+
+        self.previews = agen::preview_parameter_values(&param, self.count, self.seed);
+    }
+
+    fn parameter(&self, schema: Schema) -> Parameter {
+        Parameter {
+            name: "value".to_string(),
+            schema,
+            required: true,
+            ownership: amos::ParameterOwnership::Unknown,
+            meta_data: None,
+            examples: self
+                .examples
+                .split(',')
+                .map(str::trim)
+                .filter(|example| !example.is_empty())
+                .map(str::to_string)
+                .collect(),
+            relation_hints: vec![],
+            generator: None,
+        }
+    }
+}
+
+fn value_text(value: &ParameterValue) -> String {
+    let text = match value {
+        ParameterValue::StringValue { value, .. } => format!("{:?}", value),
+        ParameterValue::IntValue { value, .. } => value.to_string(),
+        ParameterValue::BoolValue { value, .. } => value.to_string(),
+        ParameterValue::DoubleValue { value, .. } => value.to_string(),
+        ParameterValue::IPV4Value {
+            value: (a, b, c, d),
+            ..
+        } => format!("{a}.{b}.{c}.{d}"),
+        ParameterValue::ArrayOfString { value, .. } => format!("{:?}", value),
+        ParameterValue::File { value, .. } => format!("file {}", value),
+        ParameterValue::Reference { idx, .. } => format!("reference {:?}", idx),
+        ParameterValue::Empty => "-".to_string(),
+    };
+
+    // Active values are replaced by a related value, when a sequence has one
+    if value.active() {
+        format!("{} (may reference)", text)
+    } else {
+        text
+    }
+}
+
+/// The named generator of the parameter, or its schema
+fn generator_assignment_ui(
+    ui: &mut egui::Ui,
+    overrides: &mut GeneratorOverrides,
+    operation: &str,
+    parameter: &str,
+) {
+    let current = overrides
+        .generator_name(operation, parameter)
+        .map(str::to_string);
+    let mut selected = current.clone();
+
+    egui::ComboBox::from_id_source(("generator_assignment", operation, parameter))
+        .selected_text(selected.as_deref().unwrap_or("Schema"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut selected, None, "Schema");
+            for name in overrides.generators.keys() {
+                ui.selectable_value(&mut selected, Some(name.clone()), name);
+            }
+        });
+
+    if selected != current {
+        overrides.set(operation, parameter, selected.as_deref());
+    }
+}
+
+/// The named generators, edited in place
+fn generators_ui(ui: &mut egui::Ui, overrides: &mut GeneratorOverrides, new_name: &mut String) {
+    let mut removed = None;
+    for (name, spec) in overrides.generators.iter_mut() {
+        ui.push_id(name, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("🗑").clicked() {
+                    removed = Some(name.clone());
+                }
+                ui.strong(name);
+
+                egui::ComboBox::from_id_source("kind")
+                    .selected_text(kind_name(spec))
+                    .show_ui(ui, |ui| {
+                        for kind in [
+                            GeneratorSpec::Regex {
+                                regex: RegexPattern::new("[a-z]+"),
+                            },
+                            GeneratorSpec::Enum { values: vec![] },
+                            GeneratorSpec::Range { min: 0, max: 100 },
+                            GeneratorSpec::Dictionary {
+                                path: String::new(),
+                            },
+                            GeneratorSpec::Unicode,
+                            GeneratorSpec::Large {
+                                max_length: PayloadStress::DEFAULT_MAX_STRING_LENGTH,
+                            },
+                        ] {
+                            let is_kind =
+                                std::mem::discriminant(spec) == std::mem::discriminant(&kind);
+                            if ui.selectable_label(is_kind, kind_name(&kind)).clicked() && !is_kind
+                            {
+                                *spec = kind;
+                            }
+                        }
+                    });
+            });
+
+            match spec {
+                GeneratorSpec::Regex { regex } => {
+                    let mut text = regex.to_string();
+                    if ui.text_edit_singleline(&mut text).changed() {
+                        *regex = RegexPattern::new(text);
+                    }
+                }
+                GeneratorSpec::Enum { values } => {
+                    // Kept as typed, the values are trimmed when generated
+                    let mut text = values.join(",");
+                    if ui
+                        .text_edit_singleline(&mut text)
+                        .on_hover_text("Comma separated values")
+                        .changed()
+                    {
+                        *values = text.split(',').map(str::to_string).collect();
+                    }
+                }
+                GeneratorSpec::Range { min, max } => {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(min));
+                        ui.label("to");
+                        ui.add(egui::DragValue::new(max));
+                    });
+                }
+                GeneratorSpec::Dictionary { path } => {
+                    ui.horizontal(|ui| {
+                        if ui.button("📂").clicked() {
+                            if let Some(file) = rfd::FileDialog::new().pick_file() {
+                                *path = file.display().to_string();
+                            }
+                        }
+                        ui.text_edit_singleline(path)
+                            .on_hover_text("A file with one value per line");
+                    });
+                }
+                GeneratorSpec::Unicode => {
+                    ui.weak("Emoji, CJK, right-to-left and combining characters and null bytes");
+                }
+                GeneratorSpec::Large { max_length } => {
+                    ui.horizontal(|ui| {
+                        ui.label("Max length");
+                        ui.add(egui::DragValue::new(max_length).speed(1024));
+                    });
+                }
+            }
+        });
+    }
+    if let Some(name) = removed {
+        overrides.remove(&name);
+    }
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(new_name).hint_text("Name"));
+        let name = new_name.trim().to_string();
+        if ui
+            .add_enabled(
+                !name.is_empty() && !overrides.generators.contains_key(&name),
+                egui::Button::new("Add generator"),
+            )
+            .clicked()
+        {
+            overrides.generators.insert(
+                name,
+                GeneratorSpec::Regex {
+                    regex: RegexPattern::new("[a-z]+"),
+                },
+            );
+            new_name.clear();
+        }
+    });
+}
+
+fn kind_name(spec: &GeneratorSpec) -> &'static str {
+    match spec {
+        GeneratorSpec::Regex { .. } => "Regex",
+        GeneratorSpec::Enum { .. } => "Enum",
+        GeneratorSpec::Range { .. } => "Range",
+        GeneratorSpec::Dictionary { .. } => "Dictionary",
+        GeneratorSpec::Unicode => "Unicode",
+        GeneratorSpec::Large { .. } => "Large",
+    }
+}
@@ -0,0 +1,169 @@
+use eframe::egui;
+use egui_plot::{Legend, Line, MarkerShape, Plot, PlotPoints, Points};
+
+const HEIGHT: f32 = 200.0;
+/// The invocations of an operation the trend line is the median of
+const TREND_WINDOW: usize = 15;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum StatusClass {
+    Successful,
+    ClientError,
+    ServerError,
+    TransportError,
+}
+
+impl StatusClass {
+    const ALL: [StatusClass; 4] = [
+        StatusClass::Successful,
+        StatusClass::ClientError,
+        StatusClass::ServerError,
+        StatusClass::TransportError,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            StatusClass::Successful => "2xx",
+            StatusClass::ClientError => "4xx",
+            StatusClass::ServerError => "5xx",
+            StatusClass::TransportError => "Transport",
+        }
+    }
+
+    fn shape(&self) -> MarkerShape {
+        match self {
+            StatusClass::Successful | StatusClass::ClientError => MarkerShape::Circle,
+            StatusClass::ServerError => MarkerShape::Square,
+            StatusClass::TransportError => MarkerShape::Cross,
+        }
+    }
+}
+
+/// The latency of an invocation of the operation at `series`, started at
+///  `time` seconds since the start of the run
+pub struct Point {
+    pub series: usize,
+    pub time: f64,
+    pub latency_ms: f64,
+    pub class: StatusClass,
+}
+
+/// A scatter chart of latencies over the run. Each operation has a color and
+///  a trend line, each status class a marker shape. Operations are hidden by
+///  clicking them in the legend.
+#[derive(Default)]
+pub struct LatencyChart;
+
+impl LatencyChart {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, series: &[String], points: &[Point]) {
+        if points.is_empty() {
+            ui.label("No invocation latencies");
+            return;
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for class in StatusClass::ALL {
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                marker(
+                    ui.painter(),
+                    rect.center(),
+                    class,
+                    ui.visuals().text_color(),
+                );
+                ui.label(class.label());
+            }
+        });
+
+        Plot::new("latency_chart")
+            .height(HEIGHT)
+            .legend(Legend::default())
+            .include_x(0.0)
+            .include_y(0.0)
+            .x_axis_label("Seconds")
+            .y_axis_label("ms")
+            .label_formatter(|name, point| {
+                if name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n{:.1} ms\nat {:.3} s", name, point.y, point.x)
+                }
+            })
+            .show(ui, |plot_ui| {
+                for (idx, name) in series.iter().enumerate() {
+                    let of_series: Vec<&Point> =
+                        points.iter().filter(|point| point.series == idx).collect();
+                    // The items of an operation share its name, and its entry
+                    //  in the legend
+                    for class in StatusClass::ALL {
+                        let of_class: PlotPoints = of_series
+                            .iter()
+                            .filter(|point| point.class == class)
+                            .map(|point| [point.time, point.latency_ms])
+                            .collect();
+                        plot_ui.points(
+                            Points::new(of_class)
+                                .shape(class.shape())
+                                .filled(class != StatusClass::ClientError)
+                                .radius(3.0)
+                                .color(series_color(idx))
+                                .name(name),
+                        );
+                    }
+
+                    let line: PlotPoints = trend(&of_series)
+                        .into_iter()
+                        .map(|(time, latency)| [time, latency])
+                        .collect();
+                    plot_ui.line(
+                        Line::new(line)
+                            .width(1.5)
+                            .color(series_color(idx).gamma_multiply(0.6))
+                            .name(name),
+                    );
+                }
+            });
+    }
+}
+
+/// The running median of the latencies, over the last `TREND_WINDOW` points
+fn trend(points: &[&Point]) -> Vec<(f64, f64)> {
+    (0..points.len())
+        .map(|idx| {
+            let mut window: Vec<f64> = points[idx.saturating_sub(TREND_WINDOW - 1)..=idx]
+                .iter()
+                .map(|p| p.latency_ms)
+                .collect();
+            window.sort_by(f64::total_cmp);
+            (points[idx].time, window[window.len() / 2])
+        })
+        .collect()
+}
+
+fn series_color(idx: usize) -> egui::Color32 {
+    // Golden ratio steps spread the hues of any number of operations
+    let hue = (idx as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.65, 0.9, 1.0).into()
+}
+
+fn marker(painter: &egui::Painter, at: egui::Pos2, class: StatusClass, color: egui::Color32) {
+    let r = 3.0;
+    match class {
+        StatusClass::Successful => painter.circle_filled(at, r, color),
+        StatusClass::ClientError => painter.circle_stroke(at, r, egui::Stroke::new(1.5, color)),
+        StatusClass::ServerError => painter.rect_filled(
+            egui::Rect::from_center_size(at, egui::vec2(2.0 * r, 2.0 * r)),
+            0.0,
+            color,
+        ),
+        StatusClass::TransportError => {
+            let stroke = egui::Stroke::new(1.5, color);
+            painter.line_segment([at - egui::vec2(r, r), at + egui::vec2(r, r)], stroke);
+            painter.line_segment([at + egui::vec2(-r, r), at + egui::vec2(r, -r)], stroke);
+        }
+    }
+}
@@ -1,8 +1,20 @@
 pub mod bottom_panel;
+pub mod command_palette;
+pub mod console;
+pub mod dependency_graph;
+pub mod file_drop;
+pub mod filter_bar;
+pub mod generation_playground;
+pub mod latency_chart;
 pub mod navigation_panels;
+pub mod payload_inspector;
+pub mod request_preview;
+pub mod run_queue;
+pub mod timeline;
 pub mod top_panel;
 
 pub(crate) use {
-    self::bottom_panel::bottom_panel, self::navigation_panels::navigation_panels,
+    self::bottom_panel::bottom_panel, self::dependency_graph::dependency_graph,
+    self::file_drop::drop_overlay, self::navigation_panels::navigation_panels,
     self::top_panel::top_panel,
 };
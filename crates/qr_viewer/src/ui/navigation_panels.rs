@@ -1,13 +1,21 @@
 use eframe::egui;
 
 use qr_explore::amos;
-use qr_explore::amos_generation as agen;
 use qr_explore::behaviours;
-use qr_explore::sequence;
+use qr_explore::model;
+use qr_http_resource::http;
 
 use crate::app::App;
 use crate::fuzzing;
 use crate::main_navigation;
+use crate::ui::filter_bar::FilterBar;
+
+fn http_method_label(operation: &amos::Operation) -> &'static str {
+    match &operation.meta_data {
+        Some(amos::OperationMetaData::HTTP { method, .. }) => method.as_str(),
+        None => "Unknown",
+    }
+}
 
 pub fn navigation_panels(ctx: &egui::Context, app: &mut App) {
     egui::SidePanel::left("nav_panel")
@@ -111,23 +119,72 @@ fn nav_details_panel(app: &mut App, ui: &mut egui::Ui) {
         main_navigation::MainNavigation::Generation => {
             ui.label("Generation");
 
-            ui.text_edit_singleline(&mut app.generate_string_expression);
+            app.generation_playground.show(
+                ui,
+                &app.amos.operations,
+                &mut app.app_state.exploration_settings.generators,
+            );
+        }
+        main_navigation::MainNavigation::Operations => {
+            let methods = http::HTTPMethod::SUPPORTED.map(|method| method.as_str());
+            app.operation_filter.show(ui, &methods, true);
+            // The operations invoked with the selected status in the run
+            let with_status = app
+                .operation_filter
+                .with_status(app.invocations.summaries(), |summary| {
+                    Some(&summary.operation)
+                });
+            let matches = |filter: &FilterBar, op: &amos::Operation| {
+                filter.matches(op.info.display_name(), http_method_label(op))
+                    && with_status
+                        .as_ref()
+                        .is_none_or(|invoked| invoked.contains(&op.info.name))
+            };
 
-            if ui.button("Banana Cakes").clicked() {
-                let value = sequence::banana_cakes_generation(&app.generate_string_expression);
-                if let agen::ParameterValue::StringValue { value, .. } = value {
-                    app.generated_values.push(value);
+            ui.horizontal(|ui| {
+                if ui
+                    .button("☑ All matching")
+                    .on_hover_text("Check the operations matching the filter")
+                    .clicked()
+                {
+                    let matching: Vec<usize> = (0..app.amos.operations.len())
+                        .filter(|idx| matches(&app.operation_filter, &app.amos.operations[*idx]))
+                        .collect();
+                    app.checked_operations.extend(matching);
                 }
-            }
+                if ui
+                    .add_enabled(
+                        !app.checked_operations.is_empty(),
+                        egui::Button::new("☐ None"),
+                    )
+                    .clicked()
+                {
+                    app.checked_operations.clear();
+                }
+                if ui
+                    .add_enabled(
+                        !app.checked_operations.is_empty(),
+                        egui::Button::new(format!("➕ Add {}", app.checked_operations.len())),
+                    )
+                    .on_hover_text(
+                        "Add the checked operations to the selected behaviour or fuzzer of the sequence",
+                    )
+                    .clicked()
+                {
+                    app.add_checked_operations_to_item();
+                }
+            });
 
-            for v in &app.generated_values {
-                ui.label(v.clone());
-            }
-        }
-        main_navigation::MainNavigation::Operations => {
+            let mut preview = None;
+            let mut renamed = None;
+            let mut owned = None;
             egui::collapsing_header::CollapsingHeader::new(app.amos.name.clone()).show(ui, |ui| {
                 for idx in 0..app.amos.operations.len() {
                     let current_op = &app.amos.operations[idx];
+                    if !matches(&app.operation_filter, current_op) {
+                        continue;
+                    }
+
                     let id = ui.make_persistent_id(current_op.info.name.clone());
                     egui::collapsing_header::CollapsingState::load_with_default_open(
                         ui.ctx(),
@@ -135,20 +192,97 @@ fn nav_details_panel(app: &mut App, ui: &mut egui::Ui) {
                         false,
                     )
                     .show_header(ui, |ui| {
+                        let mut checked = app.checked_operations.contains(&idx);
+                        if ui.checkbox(&mut checked, "").changed() {
+                            if checked {
+                                app.checked_operations.insert(idx);
+                            } else {
+                                app.checked_operations.remove(&idx);
+                            }
+                        }
                         ui.selectable_value(
                             &mut app.selected_amos_operation,
                             idx,
-                            current_op.info.name.clone(),
+                            current_op.info.display_name(),
                         );
+                        if current_op.info.deprecated {
+                            ui.label(egui::RichText::new("deprecated").weak().strikethrough())
+                                .on_hover_text("Marked as deprecated in the specification");
+                        }
+
+                        // Generation weight of the operation
+                        let weights = &mut app.app_state.exploration_settings.operation_weights;
+                        let mut weight = weights.weight(&current_op.info.name);
+                        if ui
+                            .add(egui::DragValue::new(&mut weight).clamp_range(0..=100))
+                            .on_hover_text("Generation weight")
+                            .changed()
+                        {
+                            weights.set(&current_op.info.name, weight);
+                        }
                     })
                     .body(|ui| {
+                        // The alias is shown instead of the name, the name stays the key
+                        let mut alias = current_op.info.alias.clone().unwrap_or_default();
+                        ui.horizontal(|ui| {
+                            ui.label("Alias");
+                            if ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut alias)
+                                        .hint_text(&current_op.info.name),
+                                )
+                                .changed()
+                            {
+                                renamed = Some((current_op.info.name.clone(), alias));
+                            }
+                        });
+
+                        // Max response time of the operation, the global one unless set
+                        let sla = &mut app.app_state.exploration_settings.response_time;
+                        let mut ms = sla
+                            .operations_ms
+                            .get(&current_op.info.name)
+                            .copied()
+                            .unwrap_or(sla.global_ms);
+                        ui.horizontal(|ui| {
+                            ui.label("Max response time (ms)");
+                            if ui.add(egui::DragValue::new(&mut ms).speed(10)).changed() {
+                                if ms == sla.global_ms {
+                                    sla.operations_ms.remove(&current_op.info.name);
+                                } else {
+                                    sla.operations_ms.insert(current_op.info.name.clone(), ms);
+                                }
+                            }
+                        });
+
+                        let injection = &mut app.app_state.exploration_settings.injection;
+                        let mut inject = injection.is_enabled(&current_op.info.name);
+                        if ui
+                            .checkbox(&mut inject, "Inject attack payloads")
+                            .on_hover_text("Explored by the injection property")
+                            .changed()
+                        {
+                            injection.set(&current_op.info.name, inject);
+                        }
+
                         if !&current_op.parameters.is_empty() {
                             ui.collapsing("Parameters", |ui| {
                                 for param in &current_op.parameters {
-                                    ui.label(format!(
-                                        "{} - {}/{:?}",
-                                        param.name, param.schema, param.ownership
-                                    ));
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!("{} - {}", param.name, param.schema));
+                                        let current = app
+                                            .amos
+                                            .ownership_override(&current_op.info.name, &param.name)
+                                            .cloned();
+                                        if let Some(ownership) = ownership_combo(ui, param, current)
+                                        {
+                                            owned = Some((
+                                                current_op.info.name.clone(),
+                                                param.name.clone(),
+                                                ownership,
+                                            ));
+                                        }
+                                    });
                                 }
                             });
                         };
@@ -160,9 +294,24 @@ fn nav_details_panel(app: &mut App, ui: &mut egui::Ui) {
                                 }
                             });
                         };
+
+                        if ui.button("Preview request").clicked() {
+                            preview = Some(current_op.clone());
+                        }
                     });
                 }
             });
+
+            if let Some((name, alias)) = renamed {
+                app.amos.set_alias(&name, &alias);
+            }
+            if let Some((operation, parameter, ownership)) = owned {
+                app.amos
+                    .set_ownership_override(&operation, &parameter, ownership);
+            }
+            if let Some(operation) = preview {
+                app.preview_requests(vec![operation]);
+            }
         }
         main_navigation::MainNavigation::Definitions => definition_tree(app, ui),
     };
@@ -234,6 +383,25 @@ fn nav_details_exploration_ui(app: &mut App, ui: &mut egui::Ui) {
                 behaviours::Behaviour::ResponseInequality,
                 behaviours::Behaviour::ResponseInequality.presentation(),
             );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Differential,
+                behaviours::Behaviour::Differential.presentation(),
+            );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Contract,
+                behaviours::Behaviour::Contract.presentation(),
+            );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Boundary,
+                behaviours::Behaviour::Boundary.presentation(),
+            )
+            .on_hover_text("A deterministic pass with the boundary values, before the fuzzing");
         });
         ui.collapsing("State-based", |ui| {
             ui.selectable_value(
@@ -247,6 +415,63 @@ fn nav_details_exploration_ui(app: &mut App, ui: &mut egui::Ui) {
                 behaviours::Behaviour::StateIdentity,
                 behaviours::Behaviour::StateIdentity.presentation(),
             );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Linearizability,
+                behaviours::Behaviour::Linearizability.presentation(),
+            );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Chaos,
+                behaviours::Behaviour::Chaos.presentation(),
+            );
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::StaleCache,
+                behaviours::Behaviour::StaleCache.presentation(),
+            )
+            .on_hover_text("GET operations serving the state cached before a mutation");
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::ConditionalRequest,
+                behaviours::Behaviour::ConditionalRequest.presentation(),
+            )
+            .on_hover_text("PUT operations overwriting the state with a stale ETag");
+
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::IdempotentRetry,
+                behaviours::Behaviour::IdempotentRetry.presentation(),
+            )
+            .on_hover_text("POST operations creating a duplicate when retried with the same key");
+        });
+        ui.collapsing("Performance-based", |ui| {
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Soak,
+                behaviours::Behaviour::Soak.presentation(),
+            );
+        });
+        ui.collapsing("Model-based", |ui| {
+            ui.selectable_value(
+                &mut app.app_state.behaviour.selected,
+                behaviours::Behaviour::Model,
+                behaviours::Behaviour::Model.presentation(),
+            );
+
+            let source = &mut app.app_state.exploration_settings.model.source;
+            ui.add(
+                egui::TextEdit::multiline(source)
+                    .code_editor()
+                    .hint_text("state persons\non get_persons: status 200, length == persons"),
+            );
+            if let Err(e) = model::Model::parse(source) {
+                ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+            }
         });
     });
 }
@@ -259,6 +484,36 @@ fn nav_details_fuzzing_ui(app: &mut App, ui: &mut egui::Ui) {
                 fuzzing::Property::ResponseCheck,
                 fuzzing::Property::ResponseCheck.to_string(),
             );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::ResponseTime,
+                fuzzing::Property::ResponseTime.to_string(),
+            );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::Payload,
+                fuzzing::Property::Payload.to_string(),
+            );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::Injection,
+                fuzzing::Property::Injection.to_string(),
+            );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::AuthBypass,
+                fuzzing::Property::AuthBypass.to_string(),
+            );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::RoleMatrix,
+                fuzzing::Property::RoleMatrix.to_string(),
+            );
+            ui.selectable_value(
+                &mut app.app_state.fuzzing.selected,
+                fuzzing::Property::SecurityAudit,
+                fuzzing::Property::SecurityAudit.to_string(),
+            );
         });
     });
 }
@@ -284,3 +539,35 @@ fn nav_details_sequences_ui(app: &mut App, ui: &mut egui::Ui) {
         );
     }
 }
+
+/// The ownership of the parameter, the inferred one unless overridden.
+///  Returns the new override when it is changed, `None` for the inferred.
+fn ownership_combo(
+    ui: &mut egui::Ui,
+    param: &amos::Parameter,
+    current: Option<amos::ParameterOwnership>,
+) -> Option<Option<amos::ParameterOwnership>> {
+    let inferred = format!("Inferred ({})", param.ownership);
+    let mut selected = current.clone();
+
+    egui::ComboBox::from_id_source(ui.next_auto_id())
+        .selected_text(match &selected {
+            Some(ownership) => ownership.to_string(),
+            None => inferred.clone(),
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut selected, None, inferred);
+            for ownership in [
+                amos::ParameterOwnership::Owned,
+                amos::ParameterOwnership::Dependency,
+                amos::ParameterOwnership::Unknown,
+            ] {
+                let label = ownership.to_string();
+                ui.selectable_value(&mut selected, Some(ownership), label);
+            }
+        })
+        .response
+        .on_hover_text("How likely the parameter is to reference a value of an earlier response");
+
+    (selected != current).then_some(selected)
+}
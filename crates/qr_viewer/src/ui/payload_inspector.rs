@@ -0,0 +1,171 @@
+use eframe::egui;
+use qr_explore::json_diff;
+use serde_json::Value;
+
+/// Shows a response payload as a tree of its JSON, or as text when it is not
+///  JSON. The search opens the nodes down to the matching keys and values.
+#[derive(Default)]
+pub struct PayloadInspector {
+    pub search: String,
+    // The payload is only parsed again when another one is shown
+    parsed: Option<(String, Option<Value>)>,
+}
+
+impl PayloadInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, payload: &str) {
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.search).desired_width(150.0));
+            if ui.button("📋 Copy").clicked() {
+                ui.output_mut(|o| o.copied_text = payload.to_string());
+            }
+        });
+
+        if self.parsed.as_ref().map(|(raw, _)| raw.as_str()) != Some(payload) {
+            self.parsed = Some((payload.to_string(), serde_json::from_str(payload).ok()));
+        }
+
+        let search = self.search.to_lowercase();
+        match self.parsed.as_ref().and_then(|(_, value)| value.as_ref()) {
+            Some(value) => value_ui(ui, "$", "", value, &search),
+            None => {
+                ui.monospace(payload);
+            }
+        }
+    }
+}
+
+/// The path of the value, as a JSON pointer, identifies its node
+fn value_ui(ui: &mut egui::Ui, key: &str, path: &str, value: &Value, search: &str) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (idx.to_string(), v))
+            .collect(),
+        _ => {
+            ui.horizontal(|ui| {
+                ui.label(highlighted(ui, &format!("{}:", key), search));
+                ui.label(highlighted(ui, &value.to_string(), search).monospace());
+            });
+            return;
+        }
+    };
+
+    let summary = match value {
+        Value::Object(_) => format!("{} {{{}}}", key, children.len()),
+        _ => format!("{} [{}]", key, children.len()),
+    };
+    let open = (!search.is_empty()).then(|| has_match(value, search));
+
+    egui::CollapsingHeader::new(highlighted(ui, &summary, search))
+        .id_source(path)
+        .default_open(path.is_empty())
+        .open(open)
+        .show(ui, |ui| {
+            for (child_key, child) in children {
+                value_ui(
+                    ui,
+                    &child_key,
+                    &format!("{path}/{child_key}"),
+                    child,
+                    search,
+                );
+            }
+        });
+}
+
+/// If any key or value below the value matches the search
+fn has_match(value: &Value, search: &str) -> bool {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .any(|(key, v)| key.to_lowercase().contains(search) || has_match(v, search)),
+        Value::Array(items) => items.iter().any(|v| has_match(v, search)),
+        _ => value.to_string().to_lowercase().contains(search),
+    }
+}
+
+fn highlighted(ui: &egui::Ui, text: &str, search: &str) -> egui::RichText {
+    let text = egui::RichText::new(text);
+    if !search.is_empty() && text.text().to_lowercase().contains(search) {
+        text.background_color(ui.visuals().selection.bg_fill)
+    } else {
+        text
+    }
+}
+
+/// Two payloads side by side, with the values that differ between them
+pub fn payload_diff(ui: &mut egui::Ui, left: (&str, &str), right: (&str, &str)) {
+    let (left_label, left) = left;
+    let (right_label, right) = right;
+
+    let parsed = (
+        serde_json::from_str::<Value>(left),
+        serde_json::from_str::<Value>(right),
+    );
+    let (left_text, right_text) = match &parsed {
+        (Ok(left_value), Ok(right_value)) => {
+            let differences = json_diff::diff(left_value, right_value);
+            differences_ui(ui, (left_label, right_label), &differences);
+
+            (pretty(left_value), pretty(right_value))
+        }
+        // Compared as text, if either is not JSON
+        _ => {
+            if left == right {
+                ui.label("The payloads are equal");
+            } else {
+                ui.colored_label(ui.visuals().warn_fg_color, "The payloads differ");
+            }
+
+            (left.to_string(), right.to_string())
+        }
+    };
+
+    ui.columns(2, |columns| {
+        columns[0].strong(left_label);
+        columns[0].monospace(left_text);
+        columns[1].strong(right_label);
+        columns[1].monospace(right_text);
+    });
+}
+
+fn differences_ui(ui: &mut egui::Ui, labels: (&str, &str), differences: &[json_diff::Difference]) {
+    if differences.is_empty() {
+        ui.label("The payloads are equal");
+        return;
+    }
+
+    let missing = |value: &Option<Value>| {
+        value
+            .as_ref()
+            .map_or("-".to_string(), |value| value.to_string())
+    };
+
+    egui::Grid::new("payload_differences")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Path");
+            ui.strong(labels.0);
+            ui.strong(labels.1);
+            ui.end_row();
+
+            for difference in differences {
+                ui.monospace(&difference.path);
+                ui.colored_label(ui.visuals().error_fg_color, missing(&difference.left));
+                ui.colored_label(egui::Color32::GREEN, missing(&difference.right));
+                ui.end_row();
+            }
+        });
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
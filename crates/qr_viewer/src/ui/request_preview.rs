@@ -0,0 +1,87 @@
+use eframe::egui;
+use qr_http_resource::reqwest_http::RequestPreview;
+
+/// Shows the requests operations would be sent as, by the operation
+#[derive(Default)]
+pub struct RequestPreviewWindow {
+    previews: Option<Vec<(String, Result<RequestPreview, String>)>>,
+}
+
+impl RequestPreviewWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, previews: Vec<(String, Result<RequestPreview, String>)>) {
+        self.previews = Some(previews);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let Some(previews) = &self.previews else {
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new("Request preview")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (idx, (operation, preview)) in previews.iter().enumerate() {
+                        ui.push_id(idx, |ui| {
+                            ui.strong(operation);
+                            match preview {
+                                Ok(preview) => preview_ui(ui, preview),
+                                Err(e) => {
+                                    ui.colored_label(ui.visuals().error_fg_color, e);
+                                }
+                            }
+                        });
+                        ui.separator();
+                    }
+                });
+            });
+
+        if !open {
+            self.previews = None;
+        }
+    }
+}
+
+fn preview_ui(ui: &mut egui::Ui, preview: &RequestPreview) {
+    ui.horizontal(|ui| {
+        ui.monospace(format!("{} {}", preview.method, preview.url));
+        if ui.button("📋 Copy").clicked() {
+            ui.output_mut(|o| o.copied_text = as_text(preview));
+        }
+    });
+
+    egui::Grid::new("request_preview_headers")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            for (name, value) in &preview.headers {
+                ui.monospace(name);
+                ui.monospace(value);
+                ui.end_row();
+            }
+        });
+
+    if let Some(body) = &preview.body {
+        ui.label("Body");
+        ui.monospace(body);
+    }
+}
+
+/// The request as the text of an HTTP message
+fn as_text(preview: &RequestPreview) -> String {
+    let mut text = format!("{} {}\n", preview.method, preview.url);
+    for (name, value) in &preview.headers {
+        text.push_str(&format!("{}: {}\n", name, value));
+    }
+    if let Some(body) = &preview.body {
+        text.push('\n');
+        text.push_str(body);
+    }
+    text
+}
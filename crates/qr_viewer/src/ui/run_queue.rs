@@ -0,0 +1,287 @@
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::JoinHandle;
+
+use eframe::egui;
+use qr_explore::explore;
+
+use crate::app::App;
+use crate::sequencer::SequenceItem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryState {
+    Pending,
+    Running,
+    Finished,
+    /// Cancelled before it finished
+    Cancelled,
+}
+
+/// A behaviour or fuzzer with the settings it had when it was queued, later
+///  edits of the sequencer do not change it
+pub struct QueueEntry {
+    pub name: String,
+    pub item: SequenceItem,
+    pub is_dry_run: bool,
+    pub state: EntryState,
+    pub results: Vec<explore::ExplorationResult>,
+    pub aborted: Option<String>,
+    run: Option<QueuedRun>,
+}
+
+/// The exploration of a running entry, with a stream of events of its own
+struct QueuedRun {
+    events: Receiver<explore::Event>,
+    stop: explore::StopSignal,
+    handle: JoinHandle<()>,
+}
+
+/// Runs the queued entries in order, at most `concurrency` of them at once
+pub struct RunQueue {
+    pub entries: Vec<QueueEntry>,
+    pub concurrency: usize,
+}
+
+impl RunQueue {
+    pub fn new() -> Self {
+        Self {
+            entries: vec![],
+            concurrency: 1,
+        }
+    }
+
+    pub fn push(&mut self, name: String, item: SequenceItem, is_dry_run: bool) {
+        self.entries.push(QueueEntry {
+            name,
+            item,
+            is_dry_run,
+            state: EntryState::Pending,
+            results: vec![],
+            aborted: None,
+            run: None,
+        });
+    }
+
+    pub fn running(&self) -> usize {
+        self.count(EntryState::Running)
+    }
+
+    pub fn count(&self, state: EntryState) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state == state)
+            .count()
+    }
+
+    /// If any entry is pending or running
+    pub fn is_busy(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| matches!(entry.state, EntryState::Pending | EntryState::Running))
+    }
+
+    /// Start the pending entries, in order, while fewer than `concurrency`
+    ///  run. The spawn gets the sender and the stop signal of the entry, an
+    ///  entry it does not spawn is cancelled.
+    pub fn start_pending(
+        &mut self,
+        mut spawn: impl FnMut(
+            &QueueEntry,
+            Sender<explore::Event>,
+            explore::StopSignal,
+        ) -> Option<JoinHandle<()>>,
+    ) {
+        let mut running = self.running();
+        for entry in &mut self.entries {
+            if running >= self.concurrency.max(1) {
+                break;
+            }
+            if entry.state != EntryState::Pending {
+                continue;
+            }
+
+            let (tx, events) = std::sync::mpsc::channel();
+            let stop = explore::StopSignal::default();
+            match spawn(entry, tx, stop.clone()) {
+                Some(handle) => {
+                    entry.run = Some(QueuedRun {
+                        events,
+                        stop,
+                        handle,
+                    });
+                    entry.state = EntryState::Running;
+                    running += 1;
+                }
+                None => entry.state = EntryState::Cancelled,
+            }
+        }
+    }
+
+    /// Collect the events of the running entries, returns their log messages
+    ///  prefixed with the name of the entry
+    pub fn poll(&mut self) -> Vec<explore::LogMessage> {
+        let mut messages = vec![];
+
+        for entry in &mut self.entries {
+            let Some(run) = &entry.run else {
+                continue;
+            };
+            // Checked before the events are drained, every event of a
+            //  finished run is in the channel
+            let finished = run.handle.is_finished();
+
+            while let Ok(event) = run.events.try_recv() {
+                match event {
+                    explore::Event::Result { result } => entry.results.push(result),
+                    explore::Event::Control {
+                        event: explore::ControlEvent::Aborted { reason },
+                    } => entry.aborted = Some(reason),
+                    explore::Event::Log { message } => messages.push(explore::LogMessage {
+                        level: message.level,
+                        message: format!("[{}] {}", entry.name, message.message),
+                    }),
+                    _ => (),
+                }
+            }
+
+            if finished {
+                entry.state = if run.stop.load(Ordering::Relaxed) {
+                    EntryState::Cancelled
+                } else {
+                    EntryState::Finished
+                };
+                entry.run = None;
+            }
+        }
+
+        messages
+    }
+
+    /// A pending entry is not started, a running entry is stopped
+    pub fn cancel(&mut self, idx: usize) {
+        let Some(entry) = self.entries.get_mut(idx) else {
+            return;
+        };
+
+        match &entry.run {
+            Some(run) => run.stop.store(true, Ordering::Relaxed),
+            None if entry.state == EntryState::Pending => entry.state = EntryState::Cancelled,
+            None => (),
+        }
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.entries
+            .retain(|entry| matches!(entry.state, EntryState::Pending | EntryState::Running));
+    }
+}
+
+impl Default for RunQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn state_label(ui: &mut egui::Ui, entry: &QueueEntry) {
+    match entry.state {
+        EntryState::Pending => ui.label("Pending"),
+        EntryState::Running => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Running")
+            })
+            .inner
+        }
+        EntryState::Finished if entry.aborted.is_some() => {
+            ui.colored_label(ui.visuals().error_fg_color, "Aborted")
+        }
+        EntryState::Finished => ui.colored_label(egui::Color32::GREEN, "Finished"),
+        EntryState::Cancelled => ui.weak("Cancelled"),
+    };
+}
+
+pub fn run_queue_window(ctx: &egui::Context, app: &mut App) {
+    let mut open = app.show_run_queue;
+
+    egui::Window::new("Run queue")
+        .open(&mut open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("➕ Queue selected")
+                    .on_hover_text("Queue the selected behaviour with its current settings")
+                    .clicked()
+                {
+                    app.queue_selected_item();
+                }
+                ui.label("Concurrency");
+                ui.add(egui::DragValue::new(&mut app.run_queue.concurrency).clamp_range(1..=8))
+                    .on_hover_text("How many queued runs execute at once, 1 runs them in order");
+                if ui.button("Clear finished").clicked() {
+                    app.run_queue.clear_finished();
+                }
+            });
+
+            let queue = &app.run_queue;
+            ui.label(format!(
+                "{} pending, {} running, {} finished",
+                queue.count(EntryState::Pending),
+                queue.count(EntryState::Running),
+                queue.count(EntryState::Finished)
+            ));
+            ui.separator();
+
+            let mut cancel = None;
+            let mut show = None;
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("run_queue_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (idx, entry) in app.run_queue.entries.iter().enumerate() {
+                            ui.label(if entry.is_dry_run {
+                                format!("{} (dry run)", entry.name)
+                            } else {
+                                entry.name.clone()
+                            });
+                            state_label(ui, entry);
+
+                            match &entry.aborted {
+                                Some(reason) => {
+                                    ui.colored_label(ui.visuals().error_fg_color, reason)
+                                }
+                                None => ui.label(format!("{} results", entry.results.len())),
+                            };
+
+                            ui.horizontal(|ui| {
+                                if matches!(entry.state, EntryState::Pending | EntryState::Running)
+                                    && ui.button("✖ Cancel").clicked()
+                                {
+                                    cancel = Some(idx);
+                                }
+                                if entry.state != EntryState::Pending
+                                    && !entry.results.is_empty()
+                                    && ui
+                                        .button("👁 Show")
+                                        .on_hover_text("Show the results in the Examples panel")
+                                        .clicked()
+                                {
+                                    show = Some(idx);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            if let Some(idx) = cancel {
+                app.run_queue.cancel(idx);
+            }
+            if let Some(idx) = show {
+                app.show_queued_results(idx);
+            }
+        });
+
+    app.show_run_queue = open;
+}
@@ -0,0 +1,135 @@
+use eframe::egui;
+use egui_plot::{Bar as PlotBar, BarChart, GridMark, Line, Plot};
+
+const LANE_HEIGHT: f32 = 24.0;
+/// The digits of the lane names shown along the y axis
+const LABEL_WIDTH_DIGITS: usize = 12;
+/// The height of a bar in its lane, of a lane height of one
+const BAR_WIDTH: f64 = 0.7;
+
+/// A span of time in a lane, in seconds since the start of the run
+pub struct Bar {
+    pub lane: usize,
+    pub start: f64,
+    pub end: f64,
+    pub label: String,
+    pub color: egui::Color32,
+}
+
+/// A point in time in a lane, e.g. a progress message
+pub struct Marker {
+    pub lane: usize,
+    pub time: f64,
+    pub label: String,
+}
+
+/// A Gantt-style chart of the bars in their lanes. Scrolling zooms around the
+///  pointer and dragging pans, until reset to show the whole run.
+#[derive(Default)]
+pub struct Timeline;
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        lanes: &[&str],
+        bars: &[Bar],
+        markers: &[Marker],
+        search: &str,
+    ) {
+        let run_end = bars
+            .iter()
+            .map(|bar| bar.end)
+            .chain(markers.iter().map(|marker| marker.time))
+            .fold(0.0, f64::max)
+            .max(0.001);
+
+        let mut reset = false;
+        ui.horizontal(|ui| {
+            reset = ui.button("Reset zoom").clicked();
+            ui.label(format!("Total: {:.1} s", run_end));
+        });
+
+        // The first lane at the top, the y axis of a plot grows upwards
+        let lane_y = |lane: usize| lanes.len().saturating_sub(lane + 1) as f64;
+        let names: Vec<String> = lanes.iter().map(|name| name.to_string()).collect();
+        let mut plot = Plot::new("timeline")
+            .height(LANE_HEIGHT * (lanes.len() as f32 + 1.0))
+            .y_axis_width(LABEL_WIDTH_DIGITS)
+            .allow_zoom([true, false])
+            .allow_drag([true, false])
+            .allow_scroll(false)
+            .show_grid([true, false])
+            .include_x(0.0)
+            .include_x(run_end)
+            .include_y(-0.5)
+            .include_y(lanes.len() as f64 - 0.5)
+            .x_axis_label("Seconds")
+            .y_grid_spacer(|input| {
+                let (min, max) = input.bounds;
+                (min.ceil() as i64..=max.floor() as i64)
+                    .map(|lane| GridMark {
+                        value: lane as f64,
+                        step_size: 1.0,
+                    })
+                    .collect()
+            })
+            .y_axis_formatter(move |y, _, _| {
+                let lane = (names.len() as f64 - 1.0 - y).round();
+                match names.get(lane as usize) {
+                    Some(name) if y.fract() == 0.0 && lane >= 0.0 => name.clone(),
+                    _ => String::new(),
+                }
+            })
+            .label_formatter(|name, point| {
+                if name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n{:.3} s", name, point.x)
+                }
+            });
+        if reset {
+            plot = plot.reset();
+        }
+
+        let search = search.to_lowercase();
+        let highlight = ui.visuals().selection.stroke;
+        plot.show(ui, |plot_ui| {
+            let bars = bars
+                .iter()
+                .map(|bar| {
+                    let matches = !search.is_empty() && bar.label.to_lowercase().contains(&search);
+                    let plotted = PlotBar::new(lane_y(bar.lane), bar.end - bar.start)
+                        .base_offset(bar.start)
+                        .width(BAR_WIDTH)
+                        .name(&bar.label)
+                        .fill(bar.color);
+                    if matches {
+                        plotted.stroke(highlight)
+                    } else {
+                        plotted.stroke(egui::Stroke::NONE)
+                    }
+                })
+                .collect();
+            plot_ui.bar_chart(BarChart::new(bars).horizontal().element_formatter(Box::new(
+                |bar, _| format!("{}\n{:.1} ms", bar.name, bar.value * 1000.0),
+            )));
+
+            for marker in markers {
+                let y = lane_y(marker.lane);
+                plot_ui.line(
+                    Line::new(vec![
+                        [marker.time, y - BAR_WIDTH / 2.0],
+                        [marker.time, y + BAR_WIDTH / 2.0],
+                    ])
+                    .color(plot_ui.ctx().style().visuals.text_color())
+                    .name(&marker.label),
+                );
+            }
+        });
+    }
+}
@@ -1,8 +1,14 @@
 use eframe::egui;
 
 use crate::app::App;
-use crate::app::ParseState;
+use crate::app::SutAction;
+use crate::app_state::{AppearanceSettings, Theme};
 use crate::command_sender::UICommand;
+use crate::history::HistorySettings;
+use crate::ui::console::console_window;
+use crate::ui::run_queue::run_queue_window;
+use qr_explore::exploration_settings::{SutLauncher, SutSettings};
+use qr_report::comparison::Finding;
 
 pub fn top_panel(ctx: &egui::Context, app: &mut App) {
     egui::TopBottomPanel::top("top_panel_main_menu")
@@ -13,13 +19,465 @@ pub fn top_panel(ctx: &egui::Context, app: &mut App) {
                 if ui.button("Save...").clicked() {
                     app.command_sender.send_ui(UICommand::Save);
                 }
-                // Open OAS file
-                if ui.button("Open file...").clicked() {
+                if ui
+                    .add_enabled(app.can_undo(), egui::Button::new("⮪"))
+                    .on_hover_text("Undo")
+                    .clicked()
+                {
+                    app.command_sender.send_ui(UICommand::Undo);
+                }
+                if ui
+                    .add_enabled(app.can_redo(), egui::Button::new("⮫"))
+                    .on_hover_text("Redo")
+                    .clicked()
+                {
+                    app.command_sender.send_ui(UICommand::Redo);
+                }
+                // Open OAS files, several files are merged into one AMOS
+                if ui
+                    .button("Open files...")
+                    .on_hover_text("Pick several specifications to merge the services")
+                    .clicked()
+                {
+                    if let Some(paths) = rfd::FileDialog::new()
+                        .add_filter("OpenAPI", &["json"])
+                        .pick_files()
+                    {
+                        app.open_specifications(
+                            paths.iter().map(|p| p.display().to_string()).collect(),
+                        );
+                    }
+                }
+                recent_files_menu(ui, app);
+                if ui
+                    .button("Import Postman...")
+                    .on_hover_text("Build the AMOS of the requests of a Postman collection")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Postman collection", &["json"])
+                        .pick_file()
+                    {
+                        app.import_postman(&path.display().to_string());
+                    }
+                }
+                if ui
+                    .button("Import CoRE resources...")
+                    .on_hover_text(
+                        "Build the AMOS of the resources of a CoAP server, as of '/.well-known/core'",
+                    )
+                    .clicked()
+                {
                     if let Some(path) = rfd::FileDialog::new().pick_file() {
-                        app.picked_path = Some(path.display().to_string());
-                        app.parse_state = ParseState::Parse;
+                        app.import_link_format(&path.display().to_string());
+                    }
+                }
+                if ui
+                    .button("Export OpenAPI...")
+                    .on_hover_text(
+                        "Save the AMOS, as edited and learned, as a Swagger 2.0 document",
+                    )
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("OpenAPI", &["json"])
+                        .set_file_name("openapi.json")
+                        .save_file()
+                    {
+                        app.export_open_api(&path.display().to_string());
+                    }
+                }
+                if ui
+                    .button("Load config...")
+                    .on_hover_text("Apply a quickrest.toml shared with the CLI")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Config", &["toml"])
+                        .pick_file()
+                    {
+                        app.load_config(&path.display().to_string());
                     }
                 }
+                if ui
+                    .button("Open report...")
+                    .on_hover_text("Show the run manifest of a report written by the CLI")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Report", &["json"])
+                        .pick_file()
+                    {
+                        app.open_report(&path.display().to_string());
+                    }
+                }
+                if ui
+                    .add_enabled(!app.is_discovering(), egui::Button::new("🔎 Discover API"))
+                    .on_hover_text("Build the AMOS by crawling the target, without a specification")
+                    .clicked()
+                {
+                    app.start_discovery();
+                }
+                if app.is_discovering() {
+                    ui.spinner();
+                }
+                let can_manage =
+                    app.app_state.exploration_settings.sut.is_managed() && !app.is_sut_busy();
+                for (action, label, hover) in [
+                    (
+                        SutAction::Start,
+                        "▶ Start SUT",
+                        "Start the SUT and wait until it is healthy",
+                    ),
+                    (SutAction::Stop, "⏹ Stop SUT", "Tear the SUT down"),
+                    (
+                        SutAction::Reset,
+                        "⟲ Reset SUT",
+                        "Restart the SUT in its initial state",
+                    ),
+                ] {
+                    if ui
+                        .add_enabled(can_manage, egui::Button::new(label))
+                        .on_hover_text(hover)
+                        .clicked()
+                    {
+                        app.manage_sut(action);
+                    }
+                }
+                if app.is_sut_busy() {
+                    ui.spinner();
+                }
+                if ui
+                    .button("⚖ Compare runs")
+                    .on_hover_text("Diff the findings of two runs")
+                    .clicked()
+                {
+                    app.show_comparison = !app.show_comparison;
+                }
+                if ui
+                    .button("⌨ Console")
+                    .on_hover_text("Invoke one operation with values filled in by hand")
+                    .clicked()
+                {
+                    app.show_console = !app.show_console;
+                }
+                if ui
+                    .button("☰ Queue")
+                    .on_hover_text("Run several behaviours, in order or at once")
+                    .clicked()
+                {
+                    app.show_run_queue = !app.show_run_queue;
+                }
+                if ui.button("⚙ Settings").clicked() {
+                    app.show_settings = !app.show_settings;
+                }
             });
         });
+
+    settings_window(ctx, app);
+    comparison_window(ctx, app);
+    console_window(ctx, app);
+    run_queue_window(ctx, app);
+}
+
+fn recent_files_menu(ui: &mut egui::Ui, app: &mut App) {
+    let recent = &app.app_state.recent_files;
+    ui.add_enabled_ui(!recent.entries.is_empty(), |ui| {
+        ui.menu_button("Recent", |ui| {
+            let mut opened = None;
+            for entry in &app.app_state.recent_files.entries {
+                let names: Vec<&str> = entry
+                    .iter()
+                    .map(|path| {
+                        std::path::Path::new(path)
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .unwrap_or(path)
+                    })
+                    .collect();
+                if ui
+                    .button(names.join(" + "))
+                    .on_hover_text(entry.join("\n"))
+                    .clicked()
+                {
+                    opened = Some(entry.clone());
+                }
+            }
+            ui.separator();
+            if ui.button("Clear").clicked() {
+                app.app_state.recent_files.entries.clear();
+                ui.close_menu();
+            }
+
+            if let Some(paths) = opened {
+                app.open_specifications(paths);
+                ui.close_menu();
+            }
+        });
+    })
+    .response
+    .on_disabled_hover_text("No specification was opened yet");
+}
+
+fn report_path_ui(ui: &mut egui::Ui, label: &str, path: &mut Option<String>) {
+    ui.label(label);
+    ui.horizontal(|ui| {
+        if ui
+            .button("📁")
+            .on_hover_text("Directory of the reports of the run")
+            .clicked()
+        {
+            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                *path = Some(dir.display().to_string());
+            }
+        }
+        ui.label(path.as_deref().unwrap_or("-"));
+    });
+    ui.end_row();
+}
+
+fn findings_ui(ui: &mut egui::Ui, title: &str, findings: &[Finding], color: egui::Color32) {
+    egui::CollapsingHeader::new(format!("{} ({})", title, findings.len()))
+        .default_open(!findings.is_empty())
+        .show(ui, |ui| {
+            for finding in findings {
+                ui.colored_label(color, finding.to_string());
+            }
+        });
+}
+
+fn comparison_window(ctx: &egui::Context, app: &mut App) {
+    let mut open = app.show_comparison;
+
+    egui::Window::new("Compare runs")
+        .open(&mut open)
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("comparison_grid")
+                .num_columns(2)
+                .spacing([20.0, 4.0])
+                .show(ui, |ui| {
+                    report_path_ui(ui, "Baseline", &mut app.comparison_baseline);
+                    report_path_ui(ui, "Current", &mut app.comparison_current);
+                });
+
+            let can_compare = app.comparison_baseline.is_some() && app.comparison_current.is_some();
+            if ui
+                .add_enabled(can_compare, egui::Button::new("Compare"))
+                .clicked()
+            {
+                app.compare_runs();
+            }
+
+            ui.separator();
+            match &app.comparison {
+                Some(Ok(comparison)) => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        findings_ui(ui, "New", &comparison.new, egui::Color32::RED);
+                        findings_ui(ui, "Fixed", &comparison.fixed, egui::Color32::GREEN);
+                        findings_ui(
+                            ui,
+                            "Persisting",
+                            &comparison.persisting,
+                            egui::Color32::YELLOW,
+                        );
+                    });
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(egui::Color32::RED, format!("Failed to read reports: {}", e));
+                }
+                None => {
+                    ui.label("Pick the report directories of two runs");
+                }
+            }
+        });
+
+    app.show_comparison = open;
+}
+
+fn settings_window(ctx: &egui::Context, app: &mut App) {
+    let mut open = app.show_settings;
+
+    egui::Window::new("Settings")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let appearance = &mut app.app_state.appearance;
+            let theme = appearance.theme;
+            let mut apply_scale = false;
+
+            egui::Grid::new("settings_grid")
+                .num_columns(2)
+                .spacing([20.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Theme");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut appearance.theme, Theme::Dark, "🌙 Dark");
+                        ui.selectable_value(&mut appearance.theme, Theme::Light, "☀ Light");
+                    });
+                    ui.end_row();
+
+                    ui.label("UI scale");
+                    let scale = ui.add(
+                        egui::Slider::new(
+                            &mut appearance.ui_scale,
+                            AppearanceSettings::MIN_UI_SCALE..=AppearanceSettings::MAX_UI_SCALE,
+                        )
+                        .step_by(0.05)
+                        .fixed_decimals(2),
+                    );
+                    // Rescaling while dragging would move the slider under the pointer
+                    apply_scale = scale.drag_released() || (scale.changed() && !scale.dragged());
+                    ui.end_row();
+                });
+
+            if ui.button("Reset").clicked() {
+                *appearance = AppearanceSettings::new();
+                apply_scale = true;
+            }
+
+            if apply_scale || appearance.theme != theme {
+                app.apply_appearance = true;
+            }
+
+            ui.separator();
+            ui.label("Notify when a run finishes or finds a counterexample");
+
+            let notifications = &mut app.app_state.notifications;
+            ui.checkbox(&mut notifications.desktop, "Desktop notification");
+
+            ui.horizontal(|ui| {
+                ui.label("Webhook URL");
+                let mut url = notifications.webhook_url.clone().unwrap_or_default();
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut url)
+                            .hint_text("https://hooks.slack.com/..."),
+                    )
+                    .changed()
+                {
+                    notifications.webhook_url = (!url.is_empty()).then_some(url);
+                }
+            });
+
+            ui.separator();
+            sut_settings_ui(ui, &mut app.app_state.exploration_settings.sut);
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Undo history depth");
+                ui.add(
+                    egui::DragValue::new(&mut app.app_state.history.depth)
+                        .clamp_range(HistorySettings::MIN_DEPTH..=HistorySettings::MAX_DEPTH),
+                );
+            });
+        });
+
+    app.show_settings = open;
+}
+
+/// How the SUT is started from the toolbar and before the explorations
+fn sut_settings_ui(ui: &mut egui::Ui, sut: &mut SutSettings) {
+    ui.label("System under test");
+
+    ui.horizontal(|ui| {
+        let kind = match sut.launcher {
+            SutLauncher::None => "Not managed",
+            SutLauncher::Command { .. } => "Command",
+            SutLauncher::Compose { .. } => "Docker compose",
+        };
+        egui::ComboBox::from_id_source("sut_launcher")
+            .selected_text(kind)
+            .show_ui(ui, |ui| {
+                if ui
+                    .selectable_label(matches!(sut.launcher, SutLauncher::None), "Not managed")
+                    .clicked()
+                {
+                    sut.launcher = SutLauncher::None;
+                }
+                if ui
+                    .selectable_label(
+                        matches!(sut.launcher, SutLauncher::Command { .. }),
+                        "Command",
+                    )
+                    .clicked()
+                    && !matches!(sut.launcher, SutLauncher::Command { .. })
+                {
+                    sut.launcher = SutLauncher::Command {
+                        start: String::new(),
+                        stop: None,
+                    };
+                }
+                if ui
+                    .selectable_label(
+                        matches!(sut.launcher, SutLauncher::Compose { .. }),
+                        "Docker compose",
+                    )
+                    .clicked()
+                    && !matches!(sut.launcher, SutLauncher::Compose { .. })
+                {
+                    sut.launcher = SutLauncher::Compose {
+                        file: "docker-compose.yml".to_string(),
+                    };
+                }
+            });
+    });
+
+    if !sut.is_managed() {
+        return;
+    }
+
+    egui::Grid::new("sut_settings_grid")
+        .num_columns(2)
+        .spacing([20.0, 4.0])
+        .show(ui, |ui| {
+            match &mut sut.launcher {
+                SutLauncher::None => (),
+                SutLauncher::Command { start, stop } => {
+                    ui.label("Start command");
+                    ui.text_edit_singleline(start);
+                    ui.end_row();
+
+                    ui.label("Stop command");
+                    let mut command = stop.clone().unwrap_or_default();
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut command)
+                                .hint_text("Killed if not given"),
+                        )
+                        .changed()
+                    {
+                        *stop = (!command.is_empty()).then_some(command);
+                    }
+                    ui.end_row();
+                }
+                SutLauncher::Compose { file } => {
+                    ui.label("Compose file");
+                    ui.text_edit_singleline(file);
+                    ui.end_row();
+                }
+            }
+
+            ui.label("Health path");
+            let mut path = sut.health_path.clone().unwrap_or_default();
+            if ui
+                .add(egui::TextEdit::singleline(&mut path).hint_text("/health"))
+                .on_hover_text("Probed until it responds, the target is probed if not given")
+                .changed()
+            {
+                sut.health_path = (!path.is_empty()).then_some(path);
+            }
+            ui.end_row();
+
+            ui.label("Startup timeout (s)");
+            ui.add(egui::DragValue::new(&mut sut.startup_timeout_secs).clamp_range(1..=600));
+            ui.end_row();
+        });
+
+    ui.checkbox(
+        &mut sut.reset_between_behaviours,
+        "Reset between behaviours",
+    );
 }
@@ -0,0 +1,793 @@
+//! Embed QuickREST in a Rust program. The [`Explorer`] loads the
+//!  specification, explores the target with the behaviours and collects the
+//!  events of the runs, without the channels and contexts of the exploration.
+//!
+//! ```no_run
+//! use quickrest::{Behaviour, Explorer};
+//!
+//! let outcome = Explorer::new()
+//!     .with_spec("openapi.json")
+//!     .with_target("http://localhost:8080")
+//!     .with_behaviour(Behaviour::Property)
+//!     .with_behaviour(Behaviour::StateMutation)
+//!     .run()
+//!     .expect("Exploration failed");
+//!
+//! for finding in outcome.findings() {
+//!     println!("{}", finding);
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+
+use qr_explore::amos::{OperationMetaData, AMOS};
+use qr_explore::coap::CoapConfiguration;
+use qr_explore::config;
+use qr_explore::exploration_settings::{
+    BehaviourSettings, CorrelationSettings, HealthSettings, InjectionSettings, PayloadLimits,
+    ResponseTimeSla, StateMutationSettings,
+};
+use qr_explore::explore::{self, ControlEvent, Event};
+use qr_explore::observer;
+use qr_explore::queue::{self, QueueConfiguration};
+use qr_http_resource::http::{self, HTTPMethod};
+use qr_objective_manager::manager as obj_mgr;
+use qr_report::{comparison, report};
+use qr_specification_manager::manager as spec_mgr;
+
+pub use qr_explore::amos::{GeneratorSpec, InvokeResult, Operation};
+pub use qr_explore::amos_generation::GeneratedOperation;
+pub use qr_explore::behaviours::Behaviour;
+pub use qr_explore::exploration_settings::{
+    AuthSettings, CannedResponse, GeneratorOverrides, MockSettings, ModelSettings,
+    OperationWeights, RunTagSettings,
+};
+pub use qr_explore::explore::{ExplorationContext, ExplorationResult};
+pub use qr_explore::manifest::{RunManifest, RunTags};
+pub use qr_explore::model::Model;
+pub use qr_explore::observer::ExplorationObserver;
+pub use qr_explore::queue::{Broker, Message, Reply};
+pub use qr_explore::script::ReplayScript;
+pub use qr_http_resource::http::{
+    OperationPinning, OperationProviders, PathNormalization, RequestPinning, TrailingSlash,
+    ValueProviders,
+};
+pub use qr_report::comparison::Finding;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSpecification,
+    MissingTarget,
+    InvalidTarget(String),
+    InvalidSpecificationUrl(String),
+    SpecificationFailure(qr_specification_manager::error::Error),
+    ExplorationPanicked,
+}
+
+impl From<qr_specification_manager::error::Error> for Error {
+    fn from(val: qr_specification_manager::error::Error) -> Self {
+        Self::SpecificationFailure(val)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSpecification => write!(f, "No specification to explore"),
+            Error::MissingTarget => write!(f, "No target to explore"),
+            Error::InvalidTarget(msg) => write!(f, "Invalid target: {}", msg),
+            Error::InvalidSpecificationUrl(msg) => {
+                write!(f, "Invalid specification URL: {}", msg)
+            }
+            Error::SpecificationFailure(e) => write!(f, "Could not load specification: {}", e),
+            Error::ExplorationPanicked => write!(f, "Exploration thread panicked"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+enum Specification {
+    File(String),
+    Url(String),
+    Amos(AMOS),
+}
+
+/// Builds and runs an exploration. Without a behaviour the target is fuzzed.
+pub struct Explorer {
+    specification: Option<Specification>,
+    target: Option<String>,
+    base_path: Option<String>,
+    paths: http::PathNormalization,
+    pinning: http::RequestPinning,
+    providers: http::ValueProviders,
+    behaviours: Vec<Behaviour>,
+    settings: StateMutationSettings,
+    behaviour_settings: Vec<BehaviourSettings>,
+    operation_weights: OperationWeights,
+    auth: AuthSettings,
+    correlation: CorrelationSettings,
+    health: HealthSettings,
+    tags: RunTagSettings,
+    generators: GeneratorOverrides,
+    model: ModelSettings,
+    mock: MockSettings,
+    is_dry_run: bool,
+    observer: Option<Box<dyn ExplorationObserver>>,
+    invoker: Option<explore::SharedInvoker>,
+    broker: Option<std::sync::Arc<dyn Broker>>,
+}
+
+impl Explorer {
+    pub fn new() -> Self {
+        Self {
+            specification: None,
+            target: None,
+            base_path: None,
+            paths: http::PathNormalization::new(),
+            pinning: http::RequestPinning::new(),
+            providers: http::ValueProviders::new(),
+            behaviours: vec![],
+            settings: StateMutationSettings::new(),
+            behaviour_settings: vec![],
+            operation_weights: OperationWeights::new(),
+            auth: AuthSettings::new(),
+            correlation: CorrelationSettings::new(),
+            health: HealthSettings::new(),
+            tags: RunTagSettings::new(),
+            generators: GeneratorOverrides::new(),
+            model: ModelSettings::new(),
+            mock: MockSettings::new(),
+            is_dry_run: false,
+            observer: None,
+            invoker: None,
+            broker: None,
+        }
+    }
+
+    /// The path of an OpenAPI-specification file
+    pub fn with_spec(mut self, path: &str) -> Self {
+        self.specification = Some(Specification::File(path.to_string()));
+        self
+    }
+
+    /// The URL an OpenAPI-specification is fetched from
+    pub fn with_spec_url(mut self, url: &str) -> Self {
+        self.specification = Some(Specification::Url(url.to_string()));
+        self
+    }
+
+    /// An already translated specification
+    pub fn with_amos(mut self, amos: AMOS) -> Self {
+        self.specification = Some(Specification::Amos(amos));
+        self
+    }
+
+    /// E.g. 'http://localhost:8080', or 'coap://localhost:5683' for a CoAP
+    ///  server
+    pub fn with_target(mut self, url: &str) -> Self {
+        self.target = Some(url.to_string());
+        self
+    }
+
+    /// The path the operations are relative to on the target, e.g. '/api/v2',
+    ///  instead of the base path of the specification
+    pub fn with_base_path(mut self, path: &str) -> Self {
+        self.base_path = Some(path.to_string());
+        self
+    }
+
+    /// Normalize the paths of the requests, e.g. without trailing slashes
+    pub fn with_path_normalization(mut self, paths: PathNormalization) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Send the parameter with a constant value instead of a generated one,
+    ///  e.g. `tenantId` with "42"
+    pub fn with_pinned_parameter(mut self, name: &str, value: &str) -> Self {
+        self.pinning
+            .parameters
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Send the header with every request
+    pub fn with_static_header(mut self, name: &str, value: &str) -> Self {
+        self.pinning
+            .headers
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Compute the parameter when a request is sent, by the expression of its
+    ///  provider, e.g. `now()`, `uuid()`, `counter()` or `hmac(<secret>, body)`
+    pub fn with_provided_parameter(mut self, name: &str, expression: &str) -> Self {
+        self.providers
+            .parameters
+            .insert(name.to_string(), expression.to_string());
+        self
+    }
+
+    /// Compute the header when a request is sent, e.g. a signature of its
+    ///  body as `hmac(<secret>, body)`
+    pub fn with_provided_header(mut self, name: &str, expression: &str) -> Self {
+        self.providers
+            .headers
+            .insert(name.to_string(), expression.to_string());
+        self
+    }
+
+    /// The pinned parameters and the static headers, of all operations and
+    ///  of each
+    pub fn with_request_pinning(mut self, pinning: RequestPinning) -> Self {
+        self.pinning = pinning;
+        self
+    }
+
+    /// Add a behaviour, the behaviours are explored in the order added
+    pub fn with_behaviour(mut self, behaviour: Behaviour) -> Self {
+        if !self.behaviours.contains(&behaviour) {
+            self.behaviours.push(behaviour);
+        }
+        self
+    }
+
+    /// The settings of the behaviour they are of, e.g. the fields the
+    ///  response equality ignores
+    pub fn with_behaviour_settings(mut self, settings: BehaviourSettings) -> Self {
+        self.behaviour_settings.push(settings);
+        self
+    }
+
+    pub fn with_tests(mut self, number_of_tests: u16) -> Self {
+        self.settings.number_of_tests = number_of_tests;
+        self
+    }
+
+    pub fn with_sequence_length(mut self, min_length: u8, max_length: u8) -> Self {
+        self.settings.min_length = min_length;
+        self.settings.max_length = max_length;
+        self
+    }
+
+    /// Generate the same sequences as an earlier run with the seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Record the values of the invoked sequences to the file, to replay
+    ///  them as they were sent with `qr_explore::explore::replay`
+    pub fn with_script(mut self, path: &str) -> Self {
+        self.settings.script_file = Some(path.to_string());
+        self
+    }
+
+    /// Honor the cache headers of the responses, e.g. revalidate with the
+    ///  ETag, as a client caching the responses would
+    pub fn with_cache(mut self) -> Self {
+        self.settings.honor_cache_headers = true;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: OperationWeights) -> Self {
+        self.operation_weights = weights;
+        self
+    }
+
+    pub fn with_auth(mut self, auth: AuthSettings) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Send the correlation id of each sequence in the header, e.g. 'X-Request-Id'
+    pub fn with_correlation_header(mut self, header: &str) -> Self {
+        self.correlation.header = Some(header.to_string());
+        self
+    }
+
+    /// Send a key of each POST in the header, e.g. 'Idempotency-Key', the
+    ///  idempotent retry behaviour sends its keys in it
+    pub fn with_idempotency_header(mut self, header: &str) -> Self {
+        self.correlation.idempotency_header = Some(header.to_string());
+        self
+    }
+
+    /// Probe the health endpoint of the target, e.g. '/health', pausing the
+    ///  invocations while it is unhealthy
+    pub fn with_health_path(mut self, path: &str) -> Self {
+        self.health.path = Some(path.to_string());
+        self
+    }
+
+    /// Tag the manifests of the runs with the build of the SUT
+    pub fn with_tags(mut self, tags: RunTagSettings) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Generate the values of the assigned parameters with the named generators
+    pub fn with_generators(mut self, generators: GeneratorOverrides) -> Self {
+        self.generators = generators;
+        self
+    }
+
+    /// The model the model behaviour checks the responses against, see
+    ///  `Model`
+    pub fn with_model(mut self, source: &str) -> Self {
+        self.model.source = source.to_string();
+        self
+    }
+
+    /// Do not send any requests, every operation gets a fake response
+    pub fn dry_run(mut self) -> Self {
+        self.is_dry_run = true;
+        self
+    }
+
+    /// The responses of a dry run, instead of the same fake response for all
+    pub fn with_mock(mut self, mock: MockSettings) -> Self {
+        self.mock = mock;
+        self
+    }
+
+    /// Also give the events of the runs to the observer, as they happen
+    pub fn with_observer(mut self, observer: Box<dyn ExplorationObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Invoke the sequences with the function instead of over HTTP, e.g. to
+    ///  explore a backend of another protocol or to instrument the requests.
+    ///  The target is not probed before the runs.
+    pub fn with_invoker<F>(mut self, invoker: F) -> Self
+    where
+        F: Fn(
+                &ExplorationContext,
+                &[Operation],
+                &[GeneratedOperation],
+            ) -> Option<Vec<InvokeResult>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.invoker = Some(std::sync::Arc::new(invoker));
+        self
+    }
+
+    /// Publish the operations as messages with the broker, the target is then
+    ///  the URL of a queue such as 'amqp://localhost:5672/orders' or
+    ///  'kafka://localhost:9092/orders'
+    pub fn with_broker(mut self, broker: impl Broker + 'static) -> Self {
+        self.broker = Some(std::sync::Arc::new(broker));
+        self
+    }
+
+    pub fn run(self) -> Result<Outcome> {
+        let amos = match self.specification {
+            Some(Specification::File(path)) => spec_mgr::load_open_api_file_path(&path)?.1.amos,
+            Some(Specification::Url(url)) => {
+                let url = reqwest::Url::parse(&url)
+                    .map_err(|e| Error::InvalidSpecificationUrl(e.to_string()))?;
+                spec_mgr::fetch_open_api_from_url(&url)?.1.amos
+            }
+            Some(Specification::Amos(amos)) => amos,
+            None => return Err(Error::MissingSpecification),
+        };
+
+        let url = self.target.ok_or(Error::MissingTarget)?;
+        let (target, invoker) = match self.broker {
+            Some(broker) => {
+                let target = explore::Target::Queue {
+                    config: QueueConfiguration::parse(&url).map_err(Error::InvalidTarget)?,
+                };
+                (target, Some(queue::invoker(broker)))
+            }
+            // Without a broker, an AMQP queue is invoked by the client of the
+            //  'amqp' feature
+            None if url.starts_with("amqp://") || url.starts_with("kafka://") => {
+                let target = explore::Target::Queue {
+                    config: QueueConfiguration::parse(&url).map_err(Error::InvalidTarget)?,
+                };
+                (target, self.invoker)
+            }
+            None if url.starts_with("coap://") => {
+                let target = explore::Target::CoAP {
+                    config: CoapConfiguration::parse(&url)
+                        .map_err(Error::InvalidTarget)?
+                        .with_base_path(self.base_path),
+                };
+                (target, self.invoker)
+            }
+            None => {
+                let (protocol, host, port) =
+                    config::parse_target_url(&url).map_err(Error::InvalidTarget)?;
+                let target = explore::Target::HTTP {
+                    config: http::HTTPConfiguration::new(host, port, protocol)
+                        .with_base_path(self.base_path)
+                        .with_paths(self.paths)
+                        .with_pinning(self.pinning)
+                        .with_providers(self.providers),
+                };
+                (target, self.invoker)
+            }
+        };
+
+        let options = obj_mgr::Options {
+            is_dry_run: self.is_dry_run,
+            operation_weights: self.operation_weights,
+            state_observation: None,
+            response_time: ResponseTimeSla::new(),
+            payload: PayloadLimits::new(),
+            injection: InjectionSettings::new(),
+            auth: self.auth,
+            correlation: self.correlation,
+            generators: self.generators,
+            model: self.model,
+            mock: self.mock,
+            health: self.health,
+            tags: self.tags,
+            invoker,
+        };
+
+        let behaviours = if self.behaviours.is_empty() {
+            vec![Behaviour::Property]
+        } else {
+            self.behaviours
+        };
+
+        let mut runs = vec![];
+        for behaviour in behaviours {
+            let settings = StateMutationSettings {
+                query_operation_ids: query_operations(&amos, &behaviour),
+                ..self.settings.clone()
+            };
+
+            let behaviour_settings = self
+                .behaviour_settings
+                .iter()
+                .rev()
+                .find(|settings| settings.is_of(&behaviour))
+                .cloned()
+                .unwrap_or_else(|| match behaviour {
+                    Behaviour::IdempotentRetry => BehaviourSettings::IdempotentRetry {
+                        header: options.correlation.idempotency_key_header().to_string(),
+                    },
+                    _ => BehaviourSettings::new(&behaviour),
+                });
+
+            let (handle, rx) = obj_mgr::explore(
+                &target,
+                &options,
+                &amos,
+                &behaviour,
+                &settings,
+                &behaviour_settings,
+            );
+
+            let mut run = Run {
+                behaviour,
+                manifest: None,
+                results: vec![],
+                invocations: vec![],
+                aborted: None,
+            };
+            // The channel closes when the exploration is done
+            for event in rx {
+                match &event {
+                    Event::Invocation { result, .. } => run.invocations.push(result.clone()),
+                    Event::Result { result } => run.results.push(result.clone()),
+                    Event::Manifest { manifest } => run.manifest = Some(*manifest.clone()),
+                    Event::Control {
+                        event: ControlEvent::Aborted { reason },
+                    } => run.aborted = Some(reason.clone()),
+                    _ => (),
+                }
+                if let Some(observer) = &self.observer {
+                    observer::notify(observer.as_ref(), event);
+                }
+            }
+            handle.join().map_err(|_| Error::ExplorationPanicked)?;
+
+            if let Some(manifest) = &mut run.manifest {
+                manifest.finish();
+            }
+            runs.push(run);
+        }
+
+        Ok(Outcome { amos, runs })
+    }
+}
+
+impl Default for Explorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state behaviours query with the GETs, the conditional requests update
+///  with the PUTs of the read resources, the others explore every operation
+fn query_operations(amos: &AMOS, behaviour: &Behaviour) -> Vec<usize> {
+    match behaviour {
+        Behaviour::StateMutation
+        | Behaviour::StateIdentity
+        | Behaviour::Linearizability
+        | Behaviour::Chaos
+        | Behaviour::StaleCache => amos
+            .operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| {
+                matches!(
+                    &op.meta_data,
+                    Some(OperationMetaData::HTTP { method, .. }) if *method == HTTPMethod::GET
+                )
+            })
+            .map(|(idx, _)| idx)
+            .collect(),
+        Behaviour::ConditionalRequest => amos.resource_updates(),
+        Behaviour::IdempotentRetry => amos.creations(),
+        _ => (0..amos.operations.len()).collect(),
+    }
+}
+
+/// The exploration of one behaviour
+#[derive(Debug)]
+pub struct Run {
+    pub behaviour: Behaviour,
+    pub manifest: Option<RunManifest>,
+    pub results: Vec<ExplorationResult>,
+    pub invocations: Vec<InvokeResult>,
+    /// Why the run stopped before it was done, e.g. an unreachable target
+    pub aborted: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    /// The specification the runs explored
+    pub amos: AMOS,
+    pub runs: Vec<Run>,
+}
+
+impl Outcome {
+    /// The findings of every run, as compared between runs and in baselines
+    pub fn findings(&self) -> BTreeSet<Finding> {
+        comparison::findings(&self.reports())
+    }
+
+    /// The reports the CLI writes for each run
+    pub fn reports(&self) -> Vec<report::Report> {
+        self.runs
+            .iter()
+            .map(|run| {
+                report::report_for_test(
+                    &run.behaviour,
+                    &self.amos,
+                    &run.results,
+                    run.manifest.as_ref(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const SPEC: &str = "../qr_explore/test/resources/feature-service.json";
+
+    #[test]
+    fn explore_dry_run() {
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target("http://localhost:8080")
+            .with_behaviour(Behaviour::Property)
+            .with_tests(5)
+            .with_seed(42)
+            .dry_run()
+            .run()
+            .unwrap();
+
+        assert_eq!(outcome.runs.len(), 1);
+        let run = &outcome.runs[0];
+        assert_eq!(run.behaviour, Behaviour::Property);
+        assert_eq!(run.manifest.as_ref().unwrap().seed, 42);
+        assert!(!run.invocations.is_empty());
+        // Every fake response is a success
+        assert!(outcome.findings().is_empty());
+    }
+
+    struct InvocationCounter(Arc<AtomicUsize>);
+
+    impl ExplorationObserver for InvocationCounter {
+        fn on_invocation(&self, _result: &InvokeResult) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn observe_dry_run() {
+        let invocations = Arc::new(AtomicUsize::new(0));
+
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target("http://localhost:8080")
+            .with_tests(5)
+            .with_observer(Box::new(InvocationCounter(invocations.clone())))
+            .dry_run()
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            invocations.load(Ordering::SeqCst),
+            outcome.runs[0].invocations.len()
+        );
+    }
+
+    /// Replies to the queries with the number of commands published so far
+    #[derive(Default)]
+    struct CountingBroker {
+        published: std::sync::Mutex<Vec<Message>>,
+    }
+
+    impl Broker for CountingBroker {
+        fn publish(&self, message: &Message) -> core::result::Result<(), String> {
+            self.published.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+
+        fn reply(
+            &self,
+            correlation_id: &str,
+            _timeout: std::time::Duration,
+        ) -> core::result::Result<Option<Reply>, String> {
+            let published = self.published.lock().unwrap();
+            let message = published
+                .iter()
+                .find(|message| message.correlation_id == correlation_id)
+                .unwrap();
+            Ok(message
+                .key
+                .starts_with("get")
+                .then(|| Reply::new(200, published.len().to_string())))
+        }
+    }
+
+    #[test]
+    fn explore_a_queue_with_a_broker() {
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target("amqp://localhost/features")
+            .with_tests(5)
+            .with_broker(CountingBroker::default())
+            .run()
+            .unwrap();
+
+        let run = &outcome.runs[0];
+        assert!(run.aborted.is_none());
+        assert!(!run.invocations.is_empty());
+        assert!(run
+            .invocations
+            .iter()
+            .all(|result| result.meta_data.as_ref().is_some_and(
+                |meta| matches!(meta, qr_explore::amos::ResultMetaData::HTTP { url, .. }
+                if url.starts_with("amqp://localhost:5672/features."))
+            )));
+        assert!(outcome.findings().is_empty());
+    }
+
+    #[test]
+    fn explore_with_an_invoker() {
+        let invoked = Arc::new(AtomicUsize::new(0));
+        let counted = invoked.clone();
+
+        // Nothing listens on the target, the invoker answers instead
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target("http://127.0.0.1:1")
+            .with_tests(5)
+            .with_invoker(move |_ctx, _ops, gen_ops| {
+                counted.fetch_add(1, Ordering::SeqCst);
+                Some(
+                    gen_ops
+                        .iter()
+                        .map(|gen_op| {
+                            InvokeResult::new(gen_op.clone(), "{}".to_string(), true, None)
+                        })
+                        .collect(),
+                )
+            })
+            .run()
+            .unwrap();
+
+        let run = &outcome.runs[0];
+        assert!(run.aborted.is_none());
+        assert!(invoked.load(Ordering::SeqCst) > 0);
+        assert!(outcome.findings().is_empty());
+    }
+
+    #[test]
+    fn dry_run_with_mock() {
+        let mock = MockSettings {
+            responses: [(
+                "deleteProductByName".to_string(),
+                CannedResponse {
+                    status: 500,
+                    body: String::new(),
+                    headers: Default::default(),
+                },
+            )]
+            .into(),
+            crud: true,
+        };
+
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target("http://localhost:8080")
+            .with_behaviour(Behaviour::Property)
+            .with_tests(20)
+            .with_seed(42)
+            .with_mock(mock)
+            .dry_run()
+            .run()
+            .unwrap();
+
+        // Only the crashing operation is found, shrunk to itself
+        let findings: Vec<Finding> = outcome.findings().into_iter().collect();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].operation, "deleteProductByName");
+        assert_eq!(findings[0].sequence, vec!["deleteProductByName"]);
+    }
+
+    #[test]
+    fn abort_unreachable_target() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let outcome = Explorer::new()
+            .with_spec(SPEC)
+            .with_target(&format!("http://127.0.0.1:{}", port))
+            .run()
+            .unwrap();
+
+        let run = &outcome.runs[0];
+        assert!(run.aborted.is_some());
+        assert!(run.invocations.is_empty());
+    }
+
+    #[test]
+    fn explore_requires_spec_and_target() {
+        assert!(matches!(
+            Explorer::new().with_target("http://localhost").run(),
+            Err(Error::MissingSpecification)
+        ));
+        assert!(matches!(
+            Explorer::new().with_spec(SPEC).run(),
+            Err(Error::MissingTarget)
+        ));
+        assert!(matches!(
+            Explorer::new()
+                .with_spec(SPEC)
+                .with_target("ftp://localhost")
+                .run(),
+            Err(Error::InvalidTarget(_))
+        ));
+    }
+
+    #[test]
+    fn explore_requires_a_valid_spec_url() {
+        let result = Explorer::new()
+            .with_spec_url("not a url")
+            .with_target("http://localhost")
+            .run();
+
+        assert!(matches!(result, Err(Error::InvalidSpecificationUrl(_))));
+    }
+}